@@ -0,0 +1,95 @@
+use clap::ArgMatches;
+use solana_clap_utils::{
+    input_parsers::pubkey_of_signer,
+    keypair::{signer_from_path_with_config, SignerFromPathConfig},
+};
+use solana_cli_output::OutputFormat;
+use solana_client::{blockhash_query::BlockhashQuery, rpc_client::RpcClient};
+use solana_remote_wallet::remote_wallet::RemoteWalletManager;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use std::{process::exit, sync::Arc};
+
+pub(crate) struct Config {
+    pub(crate) rpc_client: Arc<RpcClient>,
+    pub(crate) output_format: OutputFormat,
+    pub(crate) fee_payer: Pubkey,
+    pub(crate) default_keypair_path: String,
+    pub(crate) nonce_account: Option<Pubkey>,
+    pub(crate) nonce_authority: Option<Pubkey>,
+    pub(crate) blockhash_query: BlockhashQuery,
+    pub(crate) sign_only: bool,
+    pub(crate) dump_transaction_message: bool,
+    pub(crate) program_id: Pubkey,
+}
+
+impl Config {
+    // Checks if an explicit address was provided, otherwise return the default address.
+    pub(crate) fn pubkey_or_default(
+        &self,
+        arg_matches: &ArgMatches,
+        address_name: &str,
+        wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    ) -> Pubkey {
+        if let Some(address) = pubkey_of_signer(arg_matches, address_name, wallet_manager).unwrap()
+        {
+            return address;
+        }
+
+        self.default_signer(arg_matches, wallet_manager)
+            .unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                exit(1);
+            })
+            .pubkey()
+    }
+
+    // Checks if an explicit signer was provided, otherwise return the default signer.
+    pub(crate) fn signer_or_default(
+        &self,
+        arg_matches: &ArgMatches,
+        authority_name: &str,
+        wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    ) -> (Box<dyn Signer>, Pubkey) {
+        let config = SignerFromPathConfig {
+            allow_null_signer: false,
+        };
+        let mut load_authority = move || {
+            if let Some(authority_path) = arg_matches.value_of(authority_name) {
+                signer_from_path_with_config(
+                    arg_matches,
+                    authority_path,
+                    authority_name,
+                    wallet_manager,
+                    &config,
+                )
+            } else {
+                self.default_signer(arg_matches, wallet_manager)
+            }
+        };
+
+        let authority = load_authority().unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(1);
+        });
+
+        let authority_address = authority.pubkey();
+        (authority, authority_address)
+    }
+
+    fn default_signer(
+        &self,
+        arg_matches: &ArgMatches,
+        wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    ) -> Result<Box<dyn Signer>, Box<dyn std::error::Error>> {
+        let path = &self.default_keypair_path;
+        signer_from_path_with_config(
+            arg_matches,
+            path,
+            "default",
+            wallet_manager,
+            &SignerFromPathConfig {
+                allow_null_signer: false,
+            },
+        )
+    }
+}
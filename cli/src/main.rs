@@ -0,0 +1,2570 @@
+use borsh::BorshDeserialize;
+use clap::{
+    crate_description, crate_name, crate_version, value_t_or_exit, App, AppSettings, Arg,
+    ArgMatches, SubCommand,
+};
+use shihon::{
+    decode::{decode_account, UiShihonAccount},
+    instruction::{
+        accept_bc_token_authority, approve_kicker_coin, cancel_tanistry, candidate, claim_refund,
+        create_bc_token, create_rate_option, create_ring, create_triple_multisig, crowning,
+        deny_kicker_coin, draft_blank_check, drop_pinhole, kicking_to_coordinator, mix_content,
+        promote_mixed_content, rate_content, remove_rate_option, set_bc_token_authority,
+        set_bc_token_delegate, set_candidate_delegate, settle_tanistry, sign_off_rate_option,
+        vote_for_cc,
+    },
+    pda::{
+        get_bc_token_address, get_candidate_limit_record_address, get_cc_vote_record_address,
+        get_kicker_coin_owner_record_address, get_mix_content_record_address, get_pinhole_address,
+        get_rate_option_address, get_rate_other_record_address, get_ring_address,
+        get_tanistry_address, get_triple_multisig_address,
+    },
+    state::{
+        bc_token_metadata::{BcTokenMetadata, ContentRange, ContentType},
+        candidate_limit_record::CandidateLimitRecord,
+        enums::{PinholeSide, ShihonAccountType, VoteSource},
+        mix_content_record::MixContentRecord,
+        tanistry::Tanistry,
+    },
+};
+use solana_clap_utils::{
+    fee_payer::fee_payer_arg,
+    input_parsers::{pubkey_of, pubkey_of_signer, pubkeys_of},
+    input_validators::{
+        is_amount, is_parsable, is_url_or_moniker, is_valid_pubkey, is_valid_signer,
+        normalize_to_url_if_moniker,
+    },
+    keypair::{signer_from_path, CliSignerInfo},
+    nonce::*,
+    offline::*,
+    ArgConstant, DisplayError,
+};
+use solana_cli_output::{
+    return_signers_data, CliSignOnlyData, CliSignature, OutputFormat, ReturnSignersConfig,
+};
+use solana_client::{blockhash_query::BlockhashQuery, rpc_client::RpcClient};
+use solana_sdk::{
+    borsh::try_from_slice_unchecked, commitment_config::CommitmentConfig, instruction::Instruction,
+    message::Message, native_token::*, pubkey::Pubkey, signature::Signer, transaction::Transaction,
+};
+use std::{process::exit, sync::Arc};
+
+mod config;
+use config::Config;
+
+mod output;
+use output::*;
+
+pub const OWNER_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "owner",
+    long: "owner",
+    help: "Keypair of the token owner. Defaults to the client keypair.",
+};
+
+pub const AUTHORITY_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "authority",
+    long: "authority",
+    help: "Keypair of the bcToken authority. Defaults to the client keypair.",
+};
+
+pub const KICKER_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "kicker",
+    long: "kicker",
+    help: "Keypair of the KickerCoin holder. Defaults to the client keypair.",
+};
+
+pub const COORDINATOR_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "coordinator",
+    long: "coordinator",
+    help: "Keypair of the Tanistry coordinator. Defaults to the client keypair.",
+};
+
+pub const VOTER_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "voter",
+    long: "voter",
+    help: "Keypair of the CC voter. Defaults to the client keypair.",
+};
+
+pub const CLAIMANT_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "claimant",
+    long: "claimant",
+    help: "Keypair of the refund claimant. Defaults to the client keypair.",
+};
+
+pub const PARTY_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "party",
+    long: "party",
+    help: "Keypair of the kicker or the coordinator. Defaults to the client keypair.",
+};
+
+pub const PINHOLE_AUTHORITY_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "pinhole_authority",
+    long: "pinhole-authority",
+    help: "Fresh one-time keypair signing on behalf of the party through the pinhole.",
+};
+
+pub const COSIGNER_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "cosigner",
+    long: "cosigner",
+    help: "Keypair of the buddy or the first kicker approving through the TripleMultisig.",
+};
+
+pub const PENDING_AUTHORITY_KEYPAIR_ARG: ArgConstant<'static> = ArgConstant {
+    name: "pending_authority",
+    long: "pending-authority",
+    help: "Keypair of the proposed bcToken authority. Defaults to the client keypair.",
+};
+
+fn keypair_arg<'a, 'b>(arg: &ArgConstant<'static>, value_name: &'static str) -> Arg<'a, 'b> {
+    Arg::with_name(arg.name)
+        .long(arg.long)
+        .takes_value(true)
+        .value_name(value_name)
+        .validator(is_valid_signer)
+        .help(arg.help)
+}
+
+fn address_arg<'a, 'b>(name: &'static str, value_name: &'static str, index: u64) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .validator(is_valid_pubkey)
+        .value_name(value_name)
+        .takes_value(true)
+        .index(index)
+        .required(true)
+}
+
+fn amount_arg<'a, 'b>(name: &'static str, value_name: &'static str, index: u64) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .validator(is_amount)
+        .value_name(value_name)
+        .takes_value(true)
+        .index(index)
+        .required(true)
+}
+
+pub(crate) type Error = Box<dyn std::error::Error>;
+
+type BulkSigners = Vec<Box<dyn Signer>>;
+pub(crate) type CommandResult = Result<String, Error>;
+
+pub(crate) fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(), Error> {
+    let balance = config.rpc_client.get_balance(&config.fee_payer)?;
+    if balance < required_balance {
+        Err(format!(
+            "Fee payer, {}, has insufficient balance: {} required, {} available",
+            config.fee_payer,
+            lamports_to_sol(required_balance),
+            lamports_to_sol(balance)
+        )
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Parses the mix range given as START..END in bytes
+fn parse_content_range(value: &str) -> Result<ContentRange, String> {
+    let (start, end) = value
+        .split_once("..")
+        .ok_or_else(|| format!("Invalid range {}, expected START..END", value))?;
+
+    Ok(ContentRange {
+        start: start
+            .parse()
+            .map_err(|e| format!("Invalid range start: {}", e))?,
+        end: end
+            .parse()
+            .map_err(|e| format!("Invalid range end: {}", e))?,
+    })
+}
+
+fn is_content_range(value: String) -> Result<(), String> {
+    parse_content_range(&value).map(|_| ())
+}
+
+fn content_range_of(matches: &ArgMatches<'_>, name: &str) -> Option<ContentRange> {
+    matches
+        .value_of(name)
+        .map(|value| parse_content_range(value).unwrap())
+}
+
+fn get_shihon_account<T: BorshDeserialize>(config: &Config, address: &Pubkey) -> Result<T, Error> {
+    let account = config
+        .rpc_client
+        .get_account(address)
+        .map_err(|_| format!("Could not find Shihon account {}", address))?;
+    if account.owner != config.program_id {
+        return Err(format!(
+            "Account {} is not owned by the Shihon program {}",
+            address, config.program_id
+        )
+        .into());
+    }
+    try_from_slice_unchecked::<T>(&account.data).map_err(|e| e.into())
+}
+
+fn format_tx_return(config: &Config, tx_return: TransactionReturnData) -> String {
+    match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&signature)
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    }
+}
+
+fn format_address_tx_return(
+    config: &Config,
+    address_name: &str,
+    address: Pubkey,
+    tx_return: TransactionReturnData,
+) -> String {
+    match tx_return {
+        TransactionReturnData::CliSignature(signature) => {
+            config.output_format.formatted_string(&CliShihonAddress {
+                address_name: address_name.to_string(),
+                address: address.to_string(),
+                transaction_data: signature,
+            })
+        }
+        TransactionReturnData::CliSignOnlyData(sign_only_data) => {
+            config.output_format.formatted_string(&sign_only_data)
+        }
+    }
+}
+
+fn command_draft_blank_check(
+    config: &Config,
+    name: String,
+    bc_token_mint: Pubkey,
+    bc_authority: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let bc_address = get_bc_token_address(&config.program_id, &name);
+    println_display(
+        config,
+        format!("Drafting blank check {} ({})", name, bc_address),
+    );
+
+    let instructions = vec![draft_blank_check(
+        &config.program_id,
+        &bc_authority,
+        &bc_token_mint,
+        &config.fee_payer,
+        name,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "Blank check",
+        bc_address,
+        tx_return,
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_create_bc_token(
+    config: &Config,
+    name: String,
+    bc_token_mint: Pubkey,
+    amount: u64,
+    content_type: ContentType,
+    version: String,
+    content_length: u64,
+    content_duration: u64,
+    bc_token_authority: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let bc_token_address = get_bc_token_address(&config.program_id, &name);
+    println_display(
+        config,
+        format!("Publishing bcToken {} ({})", name, bc_token_address),
+    );
+
+    let bc_token_metadata = BcTokenMetadata {
+        account_type: ShihonAccountType::BcTokenMetadataV3,
+        bump: 0,
+        content_type,
+        updated_at: 0,
+        version,
+        reserved: [0; 64],
+        is_writable: false,
+        is_signer: false,
+        content_length,
+        content_duration,
+        lineage: None,
+    };
+
+    let instructions = vec![create_bc_token(
+        &config.program_id,
+        &bc_token_authority,
+        &bc_token_mint,
+        &config.fee_payer,
+        name,
+        amount,
+        bc_token_metadata,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "bcToken",
+        bc_token_address,
+        tx_return,
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_kick(
+    config: &Config,
+    kicker_bc_token: Pubkey,
+    kicker_token_mint: Pubkey,
+    kicker_token_source: Pubkey,
+    coordinator: Pubkey,
+    amount: u64,
+    kicker: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let kicker_coin_owner_record =
+        get_kicker_coin_owner_record_address(&config.program_id, &kicker, &coordinator);
+    println_display(
+        config,
+        format!(
+            "Kicking {} KickerCoin to coordinator {}",
+            amount, coordinator
+        ),
+    );
+
+    let instructions = vec![kicking_to_coordinator(
+        &config.program_id,
+        &kicker_bc_token,
+        &kicker,
+        &kicker_token_source,
+        &kicker_token_mint,
+        &config.fee_payer,
+        &coordinator,
+        amount,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "KickerCoin owner record",
+        kicker_coin_owner_record,
+        tx_return,
+    ))
+}
+
+fn command_approve_kicker_coin(
+    config: &Config,
+    kicker_coin_owner_record: Pubkey,
+    tanistry_token_mint: Pubkey,
+    coordinator_input: String,
+    coordinator: Pubkey,
+    previous_tanistry: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let tanistry = get_tanistry_address(&config.program_id, &kicker_coin_owner_record);
+    println_display(
+        config,
+        format!("Approving KickerCoin {}", kicker_coin_owner_record),
+    );
+
+    // The Crown of the previous Tanistry kicked the KickerCoin on and the new Tanistry joins its Ring
+    let previous_tanistry_crown = match previous_tanistry {
+        Some(previous_tanistry) => {
+            let previous_tanistry_data = get_shihon_account::<Tanistry>(config, &previous_tanistry)?;
+            let crown = previous_tanistry_data
+                .crown
+                .ok_or_else(|| format!("Tanistry {} isn't crowned", previous_tanistry))?;
+            Some((previous_tanistry, crown))
+        }
+        None => None,
+    };
+
+    let instructions = vec![approve_kicker_coin(
+        &config.program_id,
+        &kicker_coin_owner_record,
+        &coordinator,
+        &tanistry_token_mint,
+        &config.fee_payer,
+        previous_tanistry_crown
+            .as_ref()
+            .map(|(previous_tanistry, crown)| (previous_tanistry, crown)),
+        coordinator_input,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config, "Tanistry", tanistry, tx_return,
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_deny_kicker_coin(
+    config: &Config,
+    kicker_bc_token: Pubkey,
+    kicker_coin_owner_record: Pubkey,
+    kicker_token_account: Pubkey,
+    kicker_token_mint: Pubkey,
+    coordinator: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    println_display(
+        config,
+        format!("Denying KickerCoin {}", kicker_coin_owner_record),
+    );
+
+    let instructions = vec![deny_kicker_coin(
+        &config.program_id,
+        &kicker_coin_owner_record,
+        &coordinator,
+        &kicker_bc_token,
+        &kicker_token_account,
+        &kicker_token_mint,
+        &config.fee_payer,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_candidate(
+    config: &Config,
+    tanistry: Pubkey,
+    candidate_token_source: Pubkey,
+    bc_token: Pubkey,
+    amount: u64,
+    owner: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let tanistry_data = get_shihon_account::<Tanistry>(config, &tanistry)?;
+    let candidate_token_mint = tanistry_data.tanistry_token_mint;
+    let candidate_limit_record = get_candidate_limit_record_address(
+        &config.program_id,
+        &tanistry,
+        &candidate_token_mint,
+        &owner,
+    );
+    println_display(
+        config,
+        format!("Joining Tanistry {} with {} tokens", tanistry, amount),
+    );
+
+    let instructions = vec![candidate(
+        &config.program_id,
+        &tanistry,
+        &owner,
+        &candidate_token_source,
+        &bc_token,
+        &candidate_token_mint,
+        &config.fee_payer,
+        amount,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "Candidate limit record",
+        candidate_limit_record,
+        tx_return,
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_mix(
+    config: &Config,
+    rater_candidate: Pubkey,
+    buddy_content_holder: Pubkey,
+    kicker_coin_owner_record: Pubkey,
+    coordinator: Pubkey,
+    init_bc_token: Pubkey,
+    buddy_bc_token: Pubkey,
+    time_shift_a: u64,
+    time_shift_b: u64,
+    range_a: Option<ContentRange>,
+    range_b: Option<ContentRange>,
+    owner: Pubkey,
+    cosigner: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let mix_content_record =
+        get_mix_content_record_address(&config.program_id, &rater_candidate, &buddy_content_holder);
+    println_display(
+        config,
+        format!(
+            "Mixing content of {} with {}",
+            init_bc_token, buddy_bc_token
+        ),
+    );
+
+    let instructions = vec![mix_content(
+        &config.program_id,
+        &rater_candidate,
+        &owner,
+        &buddy_content_holder,
+        &kicker_coin_owner_record,
+        &coordinator,
+        &init_bc_token,
+        &buddy_bc_token,
+        &config.fee_payer,
+        &[owner, cosigner],
+        time_shift_a,
+        time_shift_b,
+        range_a,
+        range_b,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "Mix content record",
+        mix_content_record,
+        tx_return,
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn command_rate(
+    config: &Config,
+    rater_candidate: Pubkey,
+    buddy_candidate: Pubkey,
+    mix_content_record: Pubkey,
+    rating: u64,
+    owner: Pubkey,
+    cosigner: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    println_display(
+        config,
+        format!("Rating {} with {}", mix_content_record, rating),
+    );
+
+    let instructions = vec![rate_content(
+        &config.program_id,
+        &rater_candidate,
+        &owner,
+        &buddy_candidate,
+        &mix_content_record,
+        &config.fee_payer,
+        &[owner, cosigner],
+        rating,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_create_rate_option(
+    config: &Config,
+    issuer_roydamna: Pubkey,
+    mix_content_record: Pubkey,
+    owner: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let rate_option =
+        get_rate_option_address(&config.program_id, &mix_content_record, &issuer_roydamna);
+    println_display(config, format!("Creating RateOption {}", rate_option));
+
+    let instructions = vec![create_rate_option(
+        &config.program_id,
+        &issuer_roydamna,
+        &owner,
+        &mix_content_record,
+        &config.fee_payer,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "RateOption",
+        rate_option,
+        tx_return,
+    ))
+}
+
+fn command_sign_off_rate_option(
+    config: &Config,
+    issuer_roydamna: Pubkey,
+    mix_content_record: Pubkey,
+    owner: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let mix_content_record_data =
+        get_shihon_account::<MixContentRecord>(config, &mix_content_record)?;
+    let rate_other_record = get_rate_other_record_address(
+        &config.program_id,
+        &mix_content_record_data.rater_candidate,
+        &mix_content_record_data.buddy_candidate,
+    );
+    println_display(
+        config,
+        format!(
+            "Signing off RateOption {} with {}",
+            get_rate_option_address(&config.program_id, &mix_content_record, &issuer_roydamna),
+            rate_other_record
+        ),
+    );
+
+    let instructions = vec![sign_off_rate_option(
+        &config.program_id,
+        &issuer_roydamna,
+        &owner,
+        &mix_content_record,
+        &rate_other_record,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_remove_rate_option(
+    config: &Config,
+    issuer_roydamna: Pubkey,
+    mix_content_record: Pubkey,
+    owner: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    println_display(
+        config,
+        format!(
+            "Removing RateOption {}",
+            get_rate_option_address(&config.program_id, &mix_content_record, &issuer_roydamna)
+        ),
+    );
+
+    let instructions = vec![remove_rate_option(
+        &config.program_id,
+        &issuer_roydamna,
+        &owner,
+        &mix_content_record,
+        &config.fee_payer,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_set_candidate_delegate(
+    config: &Config,
+    candidate_limit_record: Pubkey,
+    new_candidate_delegate: Option<Pubkey>,
+    owner: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    match new_candidate_delegate {
+        Some(delegate) => println_display(
+            config,
+            format!(
+                "Setting delegate {} of candidate {}",
+                delegate, candidate_limit_record
+            ),
+        ),
+        None => println_display(
+            config,
+            format!("Removing delegate of candidate {}", candidate_limit_record),
+        ),
+    }
+
+    let instructions = vec![set_candidate_delegate(
+        &config.program_id,
+        &candidate_limit_record,
+        &owner,
+        new_candidate_delegate,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_set_bc_token_delegate(
+    config: &Config,
+    bc_token: Pubkey,
+    new_delegate: Option<Pubkey>,
+    authority: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    match new_delegate {
+        Some(delegate) => println_display(
+            config,
+            format!("Setting delegate {} of bcToken {}", delegate, bc_token),
+        ),
+        None => println_display(config, format!("Removing delegate of bcToken {}", bc_token)),
+    }
+
+    let instructions = vec![set_bc_token_delegate(
+        &config.program_id,
+        &bc_token,
+        &authority,
+        new_delegate,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_set_bc_token_authority(
+    config: &Config,
+    bc_token: Pubkey,
+    new_authority: Option<Pubkey>,
+    require_acceptance: bool,
+    renounce_authority: bool,
+    authority: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    if new_authority.is_none() && !require_acceptance && !renounce_authority {
+        return Err("Either --new-authority or --renounce must be given".into());
+    }
+
+    match (new_authority, require_acceptance) {
+        (Some(new_authority), true) => println_display(
+            config,
+            format!(
+                "Proposing authority {} of bcToken {}",
+                new_authority, bc_token
+            ),
+        ),
+        (None, true) => println_display(
+            config,
+            format!("Withdrawing proposed authority of bcToken {}", bc_token),
+        ),
+        (Some(new_authority), false) => println_display(
+            config,
+            format!(
+                "Setting authority {} of bcToken {}",
+                new_authority, bc_token
+            ),
+        ),
+        (None, false) => println_display(config, format!("Freezing bcToken {}", bc_token)),
+    }
+
+    let instructions = vec![set_bc_token_authority(
+        &config.program_id,
+        &bc_token,
+        &authority,
+        new_authority,
+        require_acceptance,
+        renounce_authority,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_accept_bc_token_authority(
+    config: &Config,
+    bc_token: Pubkey,
+    pending_authority: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    println_display(
+        config,
+        format!(
+            "Accepting authority {} of bcToken {}",
+            pending_authority, bc_token
+        ),
+    );
+
+    let instructions = vec![accept_bc_token_authority(
+        &config.program_id,
+        &bc_token,
+        &pending_authority,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_promote_mixed_content(
+    config: &Config,
+    mix_content_record: Pubkey,
+    bc_token_mint: Pubkey,
+    name: String,
+    version: String,
+    owner: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let mix_content_record_data =
+        get_shihon_account::<MixContentRecord>(config, &mix_content_record)?;
+    let bc_token_address = get_bc_token_address(&config.program_id, &name);
+    println_display(
+        config,
+        format!(
+            "Promoting mixed content {} into bcToken {} ({})",
+            mix_content_record, name, bc_token_address
+        ),
+    );
+
+    let instructions = vec![promote_mixed_content(
+        &config.program_id,
+        &mix_content_record,
+        &mix_content_record_data.rater_candidate,
+        &owner,
+        &mix_content_record_data.init_bc_token,
+        &mix_content_record_data.buddy_bc_token,
+        &bc_token_mint,
+        &config.fee_payer,
+        name,
+        version,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "bcToken",
+        bc_token_address,
+        tx_return,
+    ))
+}
+
+fn command_crown(
+    config: &Config,
+    kicker_coin_owner_record: Pubkey,
+    crown: Pubkey,
+    ring: Pubkey,
+    coordinator: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let tanistry = get_tanistry_address(&config.program_id, &kicker_coin_owner_record);
+    let tanistry_data = get_shihon_account::<Tanistry>(config, &tanistry)?;
+    println_display(config, format!("Crowning {}", crown));
+
+    let instructions = vec![crowning(
+        &config.program_id,
+        &coordinator,
+        &kicker_coin_owner_record,
+        &ring,
+        &tanistry_data.candidate_limit_record_list,
+        &crown,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_vote_for_cc(
+    config: &Config,
+    candidate_limit_record: Pubkey,
+    target_ring: Pubkey,
+    vote: VoteSource,
+    voter: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let cc_vote_record = get_cc_vote_record_address(&config.program_id, &candidate_limit_record);
+    println_display(config, format!("Voting for CC on ring {}", target_ring));
+
+    // The target ring is pulled or pushed relative to the Ring of the voter's Tanistry
+    let tanistry = get_shihon_account::<CandidateLimitRecord>(config, &candidate_limit_record)?
+        .belonging_tanistry;
+    let ring = get_shihon_account::<Tanistry>(config, &tanistry)?.ring;
+
+    let instructions = vec![vote_for_cc(
+        &config.program_id,
+        &candidate_limit_record,
+        &tanistry,
+        &ring,
+        &voter,
+        &config.fee_payer,
+        &target_ring,
+        vote,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "CC vote record",
+        cc_vote_record,
+        tx_return,
+    ))
+}
+
+fn command_create_ring(
+    config: &Config,
+    first_tanistry: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let ring = get_ring_address(&config.program_id, &first_tanistry);
+    println_display(config, format!("Creating Ring {}", ring));
+
+    let instructions = vec![create_ring(
+        &config.program_id,
+        &first_tanistry,
+        &config.fee_payer,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(config, "Ring", ring, tx_return))
+}
+
+fn command_create_triple_multisig(
+    config: &Config,
+    rater_candidate: Pubkey,
+    buddy_candidate: Pubkey,
+    kicker_coin_owner_record: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let triple_multisig =
+        get_triple_multisig_address(&config.program_id, &rater_candidate, &buddy_candidate);
+    println_display(
+        config,
+        format!("Creating TripleMultisig {}", triple_multisig),
+    );
+
+    let instructions = vec![create_triple_multisig(
+        &config.program_id,
+        &rater_candidate,
+        &buddy_candidate,
+        &get_tanistry_address(&config.program_id, &kicker_coin_owner_record),
+        &kicker_coin_owner_record,
+        &config.fee_payer,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config,
+        "TripleMultisig",
+        triple_multisig,
+        tx_return,
+    ))
+}
+
+fn command_drop_pinhole(
+    config: &Config,
+    kicker_coin_owner_record: Pubkey,
+    party: Pubkey,
+    pinhole_authority: Pubkey,
+    side: PinholeSide,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let pinhole = get_pinhole_address(
+        &config.program_id,
+        &kicker_coin_owner_record,
+        &pinhole_authority,
+    );
+    println_display(config, format!("Dropping Pinhole {}", pinhole));
+
+    let instructions = vec![drop_pinhole(
+        &config.program_id,
+        &kicker_coin_owner_record,
+        &party,
+        &pinhole_authority,
+        &config.fee_payer,
+        side,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_address_tx_return(
+        config, "Pinhole", pinhole, tx_return,
+    ))
+}
+
+fn command_settle_tanistry(
+    config: &Config,
+    tanistry: Pubkey,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let tanistry_data = get_shihon_account::<Tanistry>(config, &tanistry)?;
+    println_display(config, format!("Settling Tanistry {}", tanistry));
+
+    let instructions = vec![settle_tanistry(
+        &config.program_id,
+        &tanistry,
+        &tanistry_data.kicker_coin_owner_record,
+        &tanistry_data.tanistry_token_mint,
+        &tanistry_data.candidate_limit_record_list,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_cancel_tanistry(
+    config: &Config,
+    tanistry: Pubkey,
+    bc_tokens: Vec<Pubkey>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let tanistry_data = get_shihon_account::<Tanistry>(config, &tanistry)?;
+    println_display(config, format!("Cancelling Tanistry {}", tanistry));
+
+    let instructions = vec![cancel_tanistry(
+        &config.program_id,
+        &tanistry,
+        &tanistry_data.kicker_coin_owner_record,
+        &tanistry_data.tanistry_token_mint,
+        &tanistry_data.candidate_limit_record_list,
+        &bc_tokens,
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_claim_refund(
+    config: &Config,
+    tanistry: Pubkey,
+    refund_record: Pubkey,
+    claimant_token_account: Pubkey,
+    claimant: Pubkey,
+    pinhole: Option<Pubkey>,
+    bulk_signers: BulkSigners,
+) -> CommandResult {
+    let tanistry_data = get_shihon_account::<Tanistry>(config, &tanistry)?;
+    // The Tanistry token can be either SPL Token or Token-2022 mint
+    let tanistry_token_program_id = config
+        .rpc_client
+        .get_account(&tanistry_data.tanistry_token_mint)
+        .map_err(|_| {
+            format!(
+                "Could not find Tanistry token mint {}",
+                tanistry_data.tanistry_token_mint
+            )
+        })?
+        .owner;
+    println_display(
+        config,
+        format!("Claiming refund from Tanistry {}", tanistry),
+    );
+
+    let instructions = vec![claim_refund(
+        &config.program_id,
+        &tanistry,
+        &tanistry_data.tanistry_token_mint,
+        &refund_record,
+        &claimant,
+        &claimant_token_account,
+        &tanistry_token_program_id,
+        pinhole.as_ref(),
+    )];
+    let tx_return = handle_tx(
+        &CliSignerInfo {
+            signers: bulk_signers,
+        },
+        config,
+        false,
+        0,
+        instructions,
+    )?;
+    Ok(format_tx_return(config, tx_return))
+}
+
+fn command_show(config: &Config, address: Pubkey) -> CommandResult {
+    let account = config
+        .rpc_client
+        .get_account(&address)
+        .map_err(|_| format!("Could not find Shihon account {}", address))?;
+    if account.owner != config.program_id {
+        return Err(format!(
+            "Account {} is not owned by the Shihon program {}",
+            address, config.program_id
+        )
+        .into());
+    }
+
+    let shihon_account = decode_account(&account.data)?;
+
+    let cli_shihon_account = CliShihonAccount {
+        address: address.to_string(),
+        lamports: account.lamports,
+        data_len: account.data.len(),
+        account: UiShihonAccount::from(&shihon_account),
+    };
+    Ok(config.output_format.formatted_string(&cli_shihon_account))
+}
+
+fn main() -> Result<(), Error> {
+    let app_matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("verbose")
+                .short("v")
+                .long("verbose")
+                .takes_value(false)
+                .global(true)
+                .help("Show additional information"),
+        )
+        .arg(
+            Arg::with_name("output_format")
+                .long("output")
+                .value_name("FORMAT")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["json", "json-compact"])
+                .help("Return information in specified output format"),
+        )
+        .arg(
+            Arg::with_name("program_id")
+                .short("p")
+                .long("program-id")
+                .value_name("ADDRESS")
+                .takes_value(true)
+                .global(true)
+                .required(true)
+                .validator(is_valid_pubkey)
+                .help("Shihon program id"),
+        )
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .short("u")
+                .long("url")
+                .value_name("URL_OR_MONIKER")
+                .takes_value(true)
+                .global(true)
+                .validator(is_url_or_moniker)
+                .help(
+                    "URL for Solana's JSON RPC or moniker (or their first letter): \
+                       [mainnet-beta, testnet, devnet, localhost] \
+                    Default from the configuration file.",
+                ),
+        )
+        .arg(fee_payer_arg().global(true))
+        .subcommand(
+            SubCommand::with_name("draft-blank-check")
+                .about("Draft a new blank check")
+                .arg(
+                    Arg::with_name("name")
+                        .value_name("NAME")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Name of the blank check"),
+                )
+                .arg(
+                    address_arg("bc_token_mint", "MINT_ADDRESS", 2)
+                        .help("The mint of the bcToken deposit"),
+                )
+                .arg(keypair_arg(&AUTHORITY_KEYPAIR_ARG, "AUTHORITY_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("create-bc-token")
+                .about("Publish a drafted blank check as bcToken")
+                .arg(
+                    Arg::with_name("name")
+                        .value_name("NAME")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Name of the drafted blank check"),
+                )
+                .arg(
+                    address_arg("bc_token_mint", "MINT_ADDRESS", 2)
+                        .help("The mint of the bcToken deposit"),
+                )
+                .arg(
+                    amount_arg("amount", "AMOUNT", 3)
+                        .help("Amount of coin deposited with the content"),
+                )
+                .arg(
+                    Arg::with_name("content_type")
+                        .long("content-type")
+                        .value_name("CONTENT_TYPE")
+                        .takes_value(true)
+                        .possible_values(&["ordinary", "partially", "other"])
+                        .default_value("ordinary")
+                        .help("How the content can be mixed"),
+                )
+                .arg(
+                    Arg::with_name("content_version")
+                        .long("content-version")
+                        .value_name("VERSION")
+                        .takes_value(true)
+                        .default_value("1")
+                        .help("Version of the content"),
+                )
+                .arg(
+                    Arg::with_name("content_length")
+                        .long("content-length")
+                        .value_name("BYTES")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .default_value("0")
+                        .help("Recorded length of the content in bytes"),
+                )
+                .arg(
+                    Arg::with_name("content_duration")
+                        .long("content-duration")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .default_value("0")
+                        .help("Recorded duration of the content in seconds"),
+                )
+                .arg(keypair_arg(&AUTHORITY_KEYPAIR_ARG, "AUTHORITY_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("kick")
+                .about("Kick KickerCoin to a coordinator")
+                .arg(
+                    address_arg("kicker_bc_token", "BC_TOKEN_ADDRESS", 1)
+                        .help("The kicker's bcToken the Tanistry starts from"),
+                )
+                .arg(
+                    address_arg("kicker_token_mint", "MINT_ADDRESS", 2)
+                        .help("The mint of the KickerCoin"),
+                )
+                .arg(
+                    address_arg("kicker_token_source", "TOKEN_ACCOUNT_ADDRESS", 3)
+                        .help("The token account to kick the KickerCoin from"),
+                )
+                .arg(
+                    address_arg("coordinator", "COORDINATOR_ADDRESS", 4)
+                        .help("The coordinator receiving the KickerCoin"),
+                )
+                .arg(amount_arg("amount", "AMOUNT", 5).help("Amount of KickerCoin to kick"))
+                .arg(keypair_arg(&KICKER_KEYPAIR_ARG, "KICKER_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("approve-kicker-coin")
+                .about("Approve the kicked KickerCoin and form the Tanistry")
+                .arg(
+                    address_arg("kicker_coin_owner_record", "KICKER_COIN_OWNER_RECORD", 1)
+                        .help("The KickerCoin owner record to approve"),
+                )
+                .arg(
+                    address_arg("tanistry_token_mint", "MINT_ADDRESS", 2)
+                        .help("The mint of the Tanistry deposits"),
+                )
+                .arg(
+                    Arg::with_name("coordinator_input")
+                        .value_name("INPUT")
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Coordinator's input for making new RFT"),
+                )
+                .arg(
+                    Arg::with_name("previous_tanistry")
+                        .long("previous-tanistry")
+                        .value_name("TANISTRY_ADDRESS")
+                        .takes_value(true)
+                        .validator(is_valid_pubkey)
+                        .help(
+                            "The crowned Tanistry whose Crown kicked the KickerCoin on, \
+                            the new Tanistry joins its Ring",
+                        ),
+                )
+                .arg(keypair_arg(&COORDINATOR_KEYPAIR_ARG, "COORDINATOR_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("deny-kicker-coin")
+                .about("Deny the kicked KickerCoin")
+                .arg(
+                    address_arg("kicker_bc_token", "BC_TOKEN_ADDRESS", 1)
+                        .help("The first kicker's bcToken"),
+                )
+                .arg(
+                    address_arg("kicker_coin_owner_record", "KICKER_COIN_OWNER_RECORD", 2)
+                        .help("The KickerCoin owner record to deny"),
+                )
+                .arg(
+                    address_arg("kicker_token_account", "TOKEN_ACCOUNT_ADDRESS", 3)
+                        .help("The kicker's token account the KickerCoin is returned to"),
+                )
+                .arg(
+                    address_arg("kicker_token_mint", "MINT_ADDRESS", 4)
+                        .help("The mint of the KickerCoin"),
+                )
+                .arg(keypair_arg(&COORDINATOR_KEYPAIR_ARG, "COORDINATOR_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("candidate")
+                .about("Join a Tanistry as candidate")
+                .arg(address_arg("tanistry", "TANISTRY_ADDRESS", 1).help("The Tanistry to join"))
+                .arg(
+                    address_arg("candidate_token_source", "TOKEN_ACCOUNT_ADDRESS", 2)
+                        .help("The token account to deposit the self-rate from"),
+                )
+                .arg(
+                    address_arg("bc_token", "BC_TOKEN_ADDRESS", 3)
+                        .help("The bcToken to join the Tanistry with"),
+                )
+                .arg(amount_arg("amount", "AMOUNT", 4).help("Amount of self-rate deposit"))
+                .arg(keypair_arg(&OWNER_KEYPAIR_ARG, "OWNER_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("mix")
+                .about("Mix the initial content with a buddy candidate's content")
+                .arg(
+                    address_arg("rater_candidate", "CANDIDATE_LIMIT_RECORD", 1)
+                        .help("The rater's candidate limit record"),
+                )
+                .arg(
+                    address_arg("buddy_content_holder", "BUDDY_ADDRESS", 2)
+                        .help("The buddy candidate's content holder"),
+                )
+                .arg(
+                    address_arg("kicker_coin_owner_record", "KICKER_COIN_OWNER_RECORD", 3)
+                        .help("The KickerCoinOwnerRecord the rater's Tanistry was formed from"),
+                )
+                .arg(
+                    address_arg("coordinator", "COORDINATOR_ADDRESS", 4)
+                        .help("The Tanistry coordinator"),
+                )
+                .arg(
+                    address_arg("init_bc_token", "INIT_BC_TOKEN_ADDRESS", 5)
+                        .help("The init content's bcToken"),
+                )
+                .arg(
+                    address_arg("buddy_bc_token", "BUDDY_BC_TOKEN_ADDRESS", 6)
+                        .help("The buddy content's bcToken"),
+                )
+                .arg(
+                    Arg::with_name("time_shift_a")
+                        .long("time-shift-a")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .default_value("0")
+                        .help("Time shift applied to the init content"),
+                )
+                .arg(
+                    Arg::with_name("time_shift_b")
+                        .long("time-shift-b")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .default_value("0")
+                        .help("Time shift applied to the buddy content"),
+                )
+                .arg(
+                    Arg::with_name("range_a")
+                        .long("range-a")
+                        .value_name("START..END")
+                        .takes_value(true)
+                        .validator(is_content_range)
+                        .help("Byte range of the init content, required for Partially content"),
+                )
+                .arg(
+                    Arg::with_name("range_b")
+                        .long("range-b")
+                        .value_name("START..END")
+                        .takes_value(true)
+                        .validator(is_content_range)
+                        .help("Byte range of the buddy content, required for Partially content"),
+                )
+                .arg(keypair_arg(&OWNER_KEYPAIR_ARG, "OWNER_KEYPAIR"))
+                .arg(keypair_arg(&COSIGNER_KEYPAIR_ARG, "COSIGNER_KEYPAIR").required(true))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("rate")
+                .about("Rate a buddy candidate's mixed content")
+                .arg(
+                    address_arg("rater_candidate", "CANDIDATE_LIMIT_RECORD", 1)
+                        .help("The rater's candidate limit record"),
+                )
+                .arg(
+                    address_arg("buddy_candidate", "BUDDY_CANDIDATE_LIMIT_RECORD", 2)
+                        .help("The buddy's candidate limit record"),
+                )
+                .arg(
+                    address_arg("mix_content_record", "MIX_CONTENT_RECORD", 3)
+                        .help("The mix content record to rate"),
+                )
+                .arg(amount_arg("rating", "RATING", 4).help("Rating amount"))
+                .arg(keypair_arg(&OWNER_KEYPAIR_ARG, "OWNER_KEYPAIR"))
+                .arg(keypair_arg(&COSIGNER_KEYPAIR_ARG, "COSIGNER_KEYPAIR").required(true))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("create-rate-option")
+                .about("Issue the Roydamna's RateOption on a mix content record")
+                .arg(
+                    address_arg("issuer_roydamna", "CANDIDATE_LIMIT_RECORD", 1)
+                        .help("The issuer Roydamna's candidate limit record"),
+                )
+                .arg(
+                    address_arg("mix_content_record", "MIX_CONTENT_RECORD", 2)
+                        .help("The mix content record governed by the RateOption"),
+                )
+                .arg(keypair_arg(&OWNER_KEYPAIR_ARG, "OWNER_KEYPAIR"))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("sign-off-rate-option")
+                .about("Sign off the Roydamna's RateOption once the rating has been cast")
+                .arg(
+                    address_arg("issuer_roydamna", "CANDIDATE_LIMIT_RECORD", 1)
+                        .help("The issuer Roydamna's candidate limit record"),
+                )
+                .arg(
+                    address_arg("mix_content_record", "MIX_CONTENT_RECORD", 2)
+                        .help("The mix content record governed by the RateOption"),
+                )
+                .arg(keypair_arg(&OWNER_KEYPAIR_ARG, "OWNER_KEYPAIR"))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-rate-option")
+                .about("Remove the Roydamna's RateOption which hasn't been signed off")
+                .arg(
+                    address_arg("issuer_roydamna", "CANDIDATE_LIMIT_RECORD", 1)
+                        .help("The issuer Roydamna's candidate limit record"),
+                )
+                .arg(
+                    address_arg("mix_content_record", "MIX_CONTENT_RECORD", 2)
+                        .help("The mix content record governed by the RateOption"),
+                )
+                .arg(keypair_arg(&OWNER_KEYPAIR_ARG, "OWNER_KEYPAIR"))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("set-candidate-delegate")
+                .about("Set or remove the delegate allowed to mix, rate and bump self rate")
+                .arg(
+                    address_arg("candidate_limit_record", "CANDIDATE_LIMIT_RECORD", 1)
+                        .help("The candidate limit record"),
+                )
+                .arg(
+                    Arg::with_name("delegate")
+                        .long("delegate")
+                        .value_name("DELEGATE_ADDRESS")
+                        .takes_value(true)
+                        .validator(is_valid_pubkey)
+                        .help("The new delegate, the current delegate is removed when omitted"),
+                )
+                .arg(keypair_arg(&OWNER_KEYPAIR_ARG, "OWNER_KEYPAIR"))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("set-bc-token-delegate")
+                .about("Set or remove the delegate allowed to sign for the bcToken")
+                .arg(address_arg("bc_token", "BC_TOKEN_ADDRESS", 1).help("The bcToken"))
+                .arg(
+                    Arg::with_name("delegate")
+                        .long("delegate")
+                        .value_name("DELEGATE_ADDRESS")
+                        .takes_value(true)
+                        .validator(is_valid_pubkey)
+                        .help("The new delegate, the current delegate is removed when omitted"),
+                )
+                .arg(keypair_arg(&AUTHORITY_KEYPAIR_ARG, "AUTHORITY_KEYPAIR"))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("set-bc-token-authority")
+                .about("Hand the bcToken over to the new authority or freeze it")
+                .arg(address_arg("bc_token", "BC_TOKEN_ADDRESS", 1).help("The bcToken"))
+                .arg(
+                    Arg::with_name("new_authority")
+                        .long("new-authority")
+                        .value_name("NEW_AUTHORITY_ADDRESS")
+                        .takes_value(true)
+                        .validator(is_valid_pubkey)
+                        .help(
+                            "The new authority, the proposal is withdrawn when omitted \
+                            with --require-acceptance",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("require_acceptance")
+                        .long("require-acceptance")
+                        .takes_value(false)
+                        .help("Only propose the new authority which has to accept the bcToken"),
+                )
+                .arg(
+                    Arg::with_name("renounce")
+                        .long("renounce")
+                        .takes_value(false)
+                        .conflicts_with_all(&["new_authority", "require_acceptance"])
+                        .help("Renounce the authority and freeze the bcToken for good"),
+                )
+                .arg(keypair_arg(&AUTHORITY_KEYPAIR_ARG, "AUTHORITY_KEYPAIR"))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("accept-bc-token-authority")
+                .about("Accept the authority of the bcToken proposed to you")
+                .arg(address_arg("bc_token", "BC_TOKEN_ADDRESS", 1).help("The bcToken"))
+                .arg(keypair_arg(
+                    &PENDING_AUTHORITY_KEYPAIR_ARG,
+                    "PENDING_AUTHORITY_KEYPAIR",
+                ))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("promote-mixed-content")
+                .about("Promote the Succeeded mixed content into a new bcToken")
+                .arg(
+                    address_arg("mix_content_record", "MIX_CONTENT_RECORD", 1)
+                        .help("The mix content record of the Succeeded mix"),
+                )
+                .arg(
+                    Arg::with_name("name")
+                        .value_name("NAME")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Name of the new bcToken"),
+                )
+                .arg(
+                    address_arg("bc_token_mint", "MINT_ADDRESS", 3)
+                        .help("The mint of the new bcToken"),
+                )
+                .arg(
+                    Arg::with_name("content_version")
+                        .long("content-version")
+                        .value_name("VERSION")
+                        .takes_value(true)
+                        .default_value("1")
+                        .help("Version of the mixed content"),
+                )
+                .arg(keypair_arg(&OWNER_KEYPAIR_ARG, "OWNER_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("crown")
+                .about("Crown a candidate of the Tanistry")
+                .arg(
+                    address_arg("kicker_coin_owner_record", "KICKER_COIN_OWNER_RECORD", 1)
+                        .help("The KickerCoin owner record the Tanistry was formed from"),
+                )
+                .arg(address_arg("crown", "CROWN_ADDRESS", 2).help("The candidate to crown"))
+                .arg(address_arg("ring", "RING_ADDRESS", 3).help("The Ring of the Tanistry chain"))
+                .arg(keypair_arg(&COORDINATOR_KEYPAIR_ARG, "COORDINATOR_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("create-ring")
+                .about("Create the Ring of the Tanistry chain")
+                .arg(
+                    address_arg("first_tanistry", "TANISTRY_ADDRESS", 1)
+                        .help("The first Tanistry of the chain"),
+                )
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("create-triple-multisig")
+                .about("Create the TripleMultisig of the rater, the buddy and the first kicker")
+                .arg(
+                    address_arg("rater_candidate", "CANDIDATE_LIMIT_RECORD", 1)
+                        .help("The rater's candidate limit record"),
+                )
+                .arg(
+                    address_arg("buddy_candidate", "BUDDY_CANDIDATE_LIMIT_RECORD", 2)
+                        .help("The buddy's candidate limit record"),
+                )
+                .arg(
+                    address_arg("kicker_coin_owner_record", "KICKER_COIN_OWNER_RECORD", 3)
+                        .help("The KickerCoin owner record the Tanistry was formed from"),
+                )
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("drop-pinhole")
+                .about("Drop the one-time pinhole of the kicker or the coordinator for the round")
+                .arg(
+                    address_arg("kicker_coin_owner_record", "KICKER_COIN_OWNER_RECORD", 1)
+                        .help("The KickerCoin owner record"),
+                )
+                .arg(
+                    Arg::with_name("side")
+                        .long("side")
+                        .value_name("SIDE")
+                        .takes_value(true)
+                        .possible_values(&["kicker", "coordinator"])
+                        .required(true)
+                        .help("The party the pinhole is dropped for"),
+                )
+                .arg(keypair_arg(&PARTY_KEYPAIR_ARG, "PARTY_KEYPAIR"))
+                .arg(
+                    keypair_arg(&PINHOLE_AUTHORITY_KEYPAIR_ARG, "PINHOLE_AUTHORITY_KEYPAIR")
+                        .required(true),
+                )
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("vote-for-cc")
+                .about("Cast CC vote for a ring")
+                .arg(
+                    address_arg("candidate_limit_record", "CANDIDATE_LIMIT_RECORD", 1)
+                        .help("The voter's candidate limit record"),
+                )
+                .arg(
+                    address_arg("target_ring", "TARGET_RING_ADDRESS", 2)
+                        .help("The ring to vote for"),
+                )
+                .arg(
+                    Arg::with_name("vote")
+                        .long("vote")
+                        .value_name("VOTE")
+                        .takes_value(true)
+                        .possible_values(&["pull", "push"])
+                        .required(true)
+                        .help(
+                            "Pull a ring smaller than the voter's ring \
+                            or push a ring bigger than the voter's ring",
+                        ),
+                )
+                .arg(keypair_arg(&VOTER_KEYPAIR_ARG, "VOTER_KEYPAIR"))
+                .nonce_args(true)
+                .offline_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("settle-tanistry")
+                .about("Settle the crowned Tanistry")
+                .arg(address_arg("tanistry", "TANISTRY_ADDRESS", 1).help("The Tanistry to settle"))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("cancel-tanistry")
+                .about("Cancel the Tanistry whose enrollment ended without enough candidates")
+                .arg(address_arg("tanistry", "TANISTRY_ADDRESS", 1).help("The Tanistry to cancel"))
+                .arg(
+                    Arg::with_name("bc_tokens")
+                        .validator(is_valid_pubkey)
+                        .value_name("BC_TOKEN_ADDRESS")
+                        .takes_value(true)
+                        .index(2)
+                        .multiple(true)
+                        .help("The kicker's bcToken followed by the candidates' bcTokens in the candidate order"),
+                )
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("claim-refund")
+                .about("Claim own refund from the settled Tanistry")
+                .arg(address_arg("tanistry", "TANISTRY_ADDRESS", 1).help("The settled Tanistry"))
+                .arg(
+                    address_arg("refund_record", "REFUND_RECORD_ADDRESS", 2)
+                        .help("The claimant's candidate limit record or KickerCoin owner record"),
+                )
+                .arg(
+                    address_arg("claimant_token_account", "TOKEN_ACCOUNT_ADDRESS", 3)
+                        .help("The token account receiving the refund"),
+                )
+                .arg(
+                    Arg::with_name("pinhole")
+                        .long("pinhole")
+                        .value_name("PINHOLE_ADDRESS")
+                        .takes_value(true)
+                        .validator(is_valid_pubkey)
+                        .help(
+                            "Claim through the pinhole, the claimant is the pinhole authority \
+                            and the refund goes to the main wallet behind the pinhole",
+                        ),
+                )
+                .arg(keypair_arg(&CLAIMANT_KEYPAIR_ARG, "CLAIMANT_KEYPAIR"))
+                .nonce_args(true),
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Decode and show any Shihon account")
+                .arg(
+                    address_arg("address", "ADDRESS", 1)
+                        .help("The address of the Shihon account to show"),
+                ),
+        )
+        .get_matches();
+
+    let mut wallet_manager = None;
+    let mut bulk_signers: Vec<Box<dyn Signer>> = Vec::new();
+
+    let (sub_command, sub_matches) = app_matches.subcommand();
+    let matches = sub_matches.unwrap();
+
+    let config = {
+        let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+            solana_cli_config::Config::load(config_file).unwrap_or_default()
+        } else {
+            solana_cli_config::Config::default()
+        };
+        let json_rpc_url = normalize_to_url_if_moniker(
+            matches
+                .value_of("json_rpc_url")
+                .unwrap_or(&cli_config.json_rpc_url),
+        );
+
+        let (signer, fee_payer) = signer_from_path(
+            matches,
+            matches
+                .value_of("fee_payer")
+                .unwrap_or(&cli_config.keypair_path),
+            "fee_payer",
+            &mut wallet_manager,
+        )
+        .map(|s| {
+            let p = s.pubkey();
+            (s, p)
+        })
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(1);
+        });
+        bulk_signers.push(signer);
+
+        let verbose = matches.is_present("verbose");
+        let output_format = matches
+            .value_of("output_format")
+            .map(|value| match value {
+                "json" => OutputFormat::Json,
+                "json-compact" => OutputFormat::JsonCompact,
+                _ => unreachable!(),
+            })
+            .unwrap_or(if verbose {
+                OutputFormat::DisplayVerbose
+            } else {
+                OutputFormat::Display
+            });
+
+        let nonce_account = pubkey_of_signer(matches, NONCE_ARG.name, &mut wallet_manager)
+            .unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                exit(1);
+            });
+        let nonce_authority = if nonce_account.is_some() {
+            let (signer, nonce_authority) = signer_from_path(
+                matches,
+                matches
+                    .value_of(NONCE_AUTHORITY_ARG.name)
+                    .unwrap_or(&cli_config.keypair_path),
+                NONCE_AUTHORITY_ARG.name,
+                &mut wallet_manager,
+            )
+            .map(|s| {
+                let p = s.pubkey();
+                (s, p)
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                exit(1);
+            });
+            bulk_signers.push(signer);
+
+            Some(nonce_authority)
+        } else {
+            None
+        };
+
+        let blockhash_query = BlockhashQuery::new_from_matches(matches);
+        let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
+        let dump_transaction_message = matches.is_present(DUMP_TRANSACTION_MESSAGE.name);
+        let program_id = pubkey_of(matches, "program_id").unwrap();
+
+        Config {
+            rpc_client: Arc::new(RpcClient::new_with_commitment(
+                json_rpc_url,
+                CommitmentConfig::confirmed(),
+            )),
+            output_format,
+            fee_payer,
+            default_keypair_path: cli_config.keypair_path,
+            nonce_account,
+            nonce_authority,
+            blockhash_query,
+            sign_only,
+            dump_transaction_message,
+            program_id,
+        }
+    };
+
+    solana_logger::setup_with_default("solana=info");
+
+    let result = match (sub_command, sub_matches) {
+        ("draft-blank-check", Some(arg_matches)) => {
+            let name = value_t_or_exit!(arg_matches, "name", String);
+            let bc_token_mint = pubkey_of_signer(arg_matches, "bc_token_mint", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (authority_signer, authority) =
+                config.signer_or_default(arg_matches, "authority", &mut wallet_manager);
+            bulk_signers.push(authority_signer);
+
+            command_draft_blank_check(&config, name, bc_token_mint, authority, bulk_signers)
+        }
+        ("create-bc-token", Some(arg_matches)) => {
+            let name = value_t_or_exit!(arg_matches, "name", String);
+            let bc_token_mint = pubkey_of_signer(arg_matches, "bc_token_mint", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let amount = value_t_or_exit!(arg_matches, "amount", u64);
+            let content_type = match arg_matches.value_of("content_type").unwrap() {
+                "ordinary" => ContentType::Ordinary,
+                "partially" => ContentType::Partially,
+                "other" => ContentType::Other,
+                _ => unreachable!(),
+            };
+            let version = value_t_or_exit!(arg_matches, "content_version", String);
+            let content_length = value_t_or_exit!(arg_matches, "content_length", u64);
+            let content_duration = value_t_or_exit!(arg_matches, "content_duration", u64);
+            let (authority_signer, authority) =
+                config.signer_or_default(arg_matches, "authority", &mut wallet_manager);
+            bulk_signers.push(authority_signer);
+
+            command_create_bc_token(
+                &config,
+                name,
+                bc_token_mint,
+                amount,
+                content_type,
+                version,
+                content_length,
+                content_duration,
+                authority,
+                bulk_signers,
+            )
+        }
+        ("kick", Some(arg_matches)) => {
+            let kicker_bc_token =
+                pubkey_of_signer(arg_matches, "kicker_bc_token", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let kicker_token_mint =
+                pubkey_of_signer(arg_matches, "kicker_token_mint", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let kicker_token_source =
+                pubkey_of_signer(arg_matches, "kicker_token_source", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let coordinator = pubkey_of_signer(arg_matches, "coordinator", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let amount = value_t_or_exit!(arg_matches, "amount", u64);
+            let (kicker_signer, kicker) =
+                config.signer_or_default(arg_matches, "kicker", &mut wallet_manager);
+            bulk_signers.push(kicker_signer);
+
+            command_kick(
+                &config,
+                kicker_bc_token,
+                kicker_token_mint,
+                kicker_token_source,
+                coordinator,
+                amount,
+                kicker,
+                bulk_signers,
+            )
+        }
+        ("approve-kicker-coin", Some(arg_matches)) => {
+            let kicker_coin_owner_record =
+                pubkey_of_signer(arg_matches, "kicker_coin_owner_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let tanistry_token_mint =
+                pubkey_of_signer(arg_matches, "tanistry_token_mint", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let coordinator_input = value_t_or_exit!(arg_matches, "coordinator_input", String);
+            let previous_tanistry =
+                pubkey_of_signer(arg_matches, "previous_tanistry", &mut wallet_manager).unwrap();
+            let (coordinator_signer, coordinator) =
+                config.signer_or_default(arg_matches, "coordinator", &mut wallet_manager);
+            bulk_signers.push(coordinator_signer);
+
+            command_approve_kicker_coin(
+                &config,
+                kicker_coin_owner_record,
+                tanistry_token_mint,
+                coordinator_input,
+                coordinator,
+                previous_tanistry,
+                bulk_signers,
+            )
+        }
+        ("deny-kicker-coin", Some(arg_matches)) => {
+            let kicker_bc_token =
+                pubkey_of_signer(arg_matches, "kicker_bc_token", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let kicker_coin_owner_record =
+                pubkey_of_signer(arg_matches, "kicker_coin_owner_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let kicker_token_account =
+                pubkey_of_signer(arg_matches, "kicker_token_account", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let kicker_token_mint =
+                pubkey_of_signer(arg_matches, "kicker_token_mint", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let (coordinator_signer, coordinator) =
+                config.signer_or_default(arg_matches, "coordinator", &mut wallet_manager);
+            bulk_signers.push(coordinator_signer);
+
+            command_deny_kicker_coin(
+                &config,
+                kicker_bc_token,
+                kicker_coin_owner_record,
+                kicker_token_account,
+                kicker_token_mint,
+                coordinator,
+                bulk_signers,
+            )
+        }
+        ("candidate", Some(arg_matches)) => {
+            let tanistry = pubkey_of_signer(arg_matches, "tanistry", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let candidate_token_source =
+                pubkey_of_signer(arg_matches, "candidate_token_source", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let bc_token = pubkey_of_signer(arg_matches, "bc_token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let amount = value_t_or_exit!(arg_matches, "amount", u64);
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            bulk_signers.push(owner_signer);
+
+            command_candidate(
+                &config,
+                tanistry,
+                candidate_token_source,
+                bc_token,
+                amount,
+                owner,
+                bulk_signers,
+            )
+        }
+        ("mix", Some(arg_matches)) => {
+            let rater_candidate =
+                pubkey_of_signer(arg_matches, "rater_candidate", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let buddy_content_holder =
+                pubkey_of_signer(arg_matches, "buddy_content_holder", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let kicker_coin_owner_record =
+                pubkey_of_signer(arg_matches, "kicker_coin_owner_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let coordinator = pubkey_of_signer(arg_matches, "coordinator", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let init_bc_token = pubkey_of_signer(arg_matches, "init_bc_token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let buddy_bc_token =
+                pubkey_of_signer(arg_matches, "buddy_bc_token", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let time_shift_a = value_t_or_exit!(arg_matches, "time_shift_a", u64);
+            let time_shift_b = value_t_or_exit!(arg_matches, "time_shift_b", u64);
+            let range_a = content_range_of(arg_matches, "range_a");
+            let range_b = content_range_of(arg_matches, "range_b");
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            bulk_signers.push(owner_signer);
+            let (cosigner_signer, cosigner) =
+                config.signer_or_default(arg_matches, "cosigner", &mut wallet_manager);
+            bulk_signers.push(cosigner_signer);
+
+            command_mix(
+                &config,
+                rater_candidate,
+                buddy_content_holder,
+                kicker_coin_owner_record,
+                coordinator,
+                init_bc_token,
+                buddy_bc_token,
+                time_shift_a,
+                time_shift_b,
+                range_a,
+                range_b,
+                owner,
+                cosigner,
+                bulk_signers,
+            )
+        }
+        ("rate", Some(arg_matches)) => {
+            let rater_candidate =
+                pubkey_of_signer(arg_matches, "rater_candidate", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let buddy_candidate =
+                pubkey_of_signer(arg_matches, "buddy_candidate", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let mix_content_record =
+                pubkey_of_signer(arg_matches, "mix_content_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let rating = value_t_or_exit!(arg_matches, "rating", u64);
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            bulk_signers.push(owner_signer);
+            let (cosigner_signer, cosigner) =
+                config.signer_or_default(arg_matches, "cosigner", &mut wallet_manager);
+            bulk_signers.push(cosigner_signer);
+
+            command_rate(
+                &config,
+                rater_candidate,
+                buddy_candidate,
+                mix_content_record,
+                rating,
+                owner,
+                cosigner,
+                bulk_signers,
+            )
+        }
+        ("create-rate-option", Some(arg_matches)) => {
+            let issuer_roydamna =
+                pubkey_of_signer(arg_matches, "issuer_roydamna", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let mix_content_record =
+                pubkey_of_signer(arg_matches, "mix_content_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            bulk_signers.push(owner_signer);
+
+            command_create_rate_option(
+                &config,
+                issuer_roydamna,
+                mix_content_record,
+                owner,
+                bulk_signers,
+            )
+        }
+        ("sign-off-rate-option", Some(arg_matches)) => {
+            let issuer_roydamna =
+                pubkey_of_signer(arg_matches, "issuer_roydamna", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let mix_content_record =
+                pubkey_of_signer(arg_matches, "mix_content_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            bulk_signers.push(owner_signer);
+
+            command_sign_off_rate_option(
+                &config,
+                issuer_roydamna,
+                mix_content_record,
+                owner,
+                bulk_signers,
+            )
+        }
+        ("remove-rate-option", Some(arg_matches)) => {
+            let issuer_roydamna =
+                pubkey_of_signer(arg_matches, "issuer_roydamna", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let mix_content_record =
+                pubkey_of_signer(arg_matches, "mix_content_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            bulk_signers.push(owner_signer);
+
+            command_remove_rate_option(
+                &config,
+                issuer_roydamna,
+                mix_content_record,
+                owner,
+                bulk_signers,
+            )
+        }
+        ("set-candidate-delegate", Some(arg_matches)) => {
+            let candidate_limit_record =
+                pubkey_of_signer(arg_matches, "candidate_limit_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let delegate = pubkey_of_signer(arg_matches, "delegate", &mut wallet_manager).unwrap();
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            bulk_signers.push(owner_signer);
+
+            command_set_candidate_delegate(
+                &config,
+                candidate_limit_record,
+                delegate,
+                owner,
+                bulk_signers,
+            )
+        }
+        ("set-bc-token-delegate", Some(arg_matches)) => {
+            let bc_token = pubkey_of_signer(arg_matches, "bc_token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let delegate = pubkey_of_signer(arg_matches, "delegate", &mut wallet_manager).unwrap();
+            let (authority_signer, authority) =
+                config.signer_or_default(arg_matches, "authority", &mut wallet_manager);
+            bulk_signers.push(authority_signer);
+
+            command_set_bc_token_delegate(&config, bc_token, delegate, authority, bulk_signers)
+        }
+        ("set-bc-token-authority", Some(arg_matches)) => {
+            let bc_token = pubkey_of_signer(arg_matches, "bc_token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let new_authority =
+                pubkey_of_signer(arg_matches, "new_authority", &mut wallet_manager).unwrap();
+            let require_acceptance = arg_matches.is_present("require_acceptance");
+            let renounce_authority = arg_matches.is_present("renounce");
+            let (authority_signer, authority) =
+                config.signer_or_default(arg_matches, "authority", &mut wallet_manager);
+            bulk_signers.push(authority_signer);
+
+            command_set_bc_token_authority(
+                &config,
+                bc_token,
+                new_authority,
+                require_acceptance,
+                renounce_authority,
+                authority,
+                bulk_signers,
+            )
+        }
+        ("accept-bc-token-authority", Some(arg_matches)) => {
+            let bc_token = pubkey_of_signer(arg_matches, "bc_token", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (pending_authority_signer, pending_authority) =
+                config.signer_or_default(arg_matches, "pending_authority", &mut wallet_manager);
+            bulk_signers.push(pending_authority_signer);
+
+            command_accept_bc_token_authority(&config, bc_token, pending_authority, bulk_signers)
+        }
+        ("promote-mixed-content", Some(arg_matches)) => {
+            let mix_content_record =
+                pubkey_of_signer(arg_matches, "mix_content_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let name = value_t_or_exit!(arg_matches, "name", String);
+            let bc_token_mint = pubkey_of_signer(arg_matches, "bc_token_mint", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let version = value_t_or_exit!(arg_matches, "content_version", String);
+            let (owner_signer, owner) =
+                config.signer_or_default(arg_matches, "owner", &mut wallet_manager);
+            bulk_signers.push(owner_signer);
+
+            command_promote_mixed_content(
+                &config,
+                mix_content_record,
+                bc_token_mint,
+                name,
+                version,
+                owner,
+                bulk_signers,
+            )
+        }
+        ("crown", Some(arg_matches)) => {
+            let kicker_coin_owner_record =
+                pubkey_of_signer(arg_matches, "kicker_coin_owner_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let crown = pubkey_of_signer(arg_matches, "crown", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let ring = pubkey_of_signer(arg_matches, "ring", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let (coordinator_signer, coordinator) =
+                config.signer_or_default(arg_matches, "coordinator", &mut wallet_manager);
+            bulk_signers.push(coordinator_signer);
+
+            command_crown(
+                &config,
+                kicker_coin_owner_record,
+                crown,
+                ring,
+                coordinator,
+                bulk_signers,
+            )
+        }
+        ("create-ring", Some(arg_matches)) => {
+            let first_tanistry =
+                pubkey_of_signer(arg_matches, "first_tanistry", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+
+            command_create_ring(&config, first_tanistry, bulk_signers)
+        }
+        ("create-triple-multisig", Some(arg_matches)) => {
+            let rater_candidate =
+                pubkey_of_signer(arg_matches, "rater_candidate", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let buddy_candidate =
+                pubkey_of_signer(arg_matches, "buddy_candidate", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let kicker_coin_owner_record =
+                pubkey_of_signer(arg_matches, "kicker_coin_owner_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+
+            command_create_triple_multisig(
+                &config,
+                rater_candidate,
+                buddy_candidate,
+                kicker_coin_owner_record,
+                bulk_signers,
+            )
+        }
+        ("drop-pinhole", Some(arg_matches)) => {
+            let kicker_coin_owner_record =
+                pubkey_of_signer(arg_matches, "kicker_coin_owner_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let side = match arg_matches.value_of("side").unwrap() {
+                "kicker" => PinholeSide::Kicker,
+                "coordinator" => PinholeSide::Coordinator,
+                _ => unreachable!(),
+            };
+            let (party_signer, party) =
+                config.signer_or_default(arg_matches, "party", &mut wallet_manager);
+            bulk_signers.push(party_signer);
+            let (pinhole_authority_signer, pinhole_authority) =
+                config.signer_or_default(arg_matches, "pinhole_authority", &mut wallet_manager);
+            bulk_signers.push(pinhole_authority_signer);
+
+            command_drop_pinhole(
+                &config,
+                kicker_coin_owner_record,
+                party,
+                pinhole_authority,
+                side,
+                bulk_signers,
+            )
+        }
+        ("vote-for-cc", Some(arg_matches)) => {
+            let candidate_limit_record =
+                pubkey_of_signer(arg_matches, "candidate_limit_record", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let target_ring = pubkey_of_signer(arg_matches, "target_ring", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let vote = match arg_matches.value_of("vote").unwrap() {
+                "pull" => VoteSource::Pull,
+                "push" => VoteSource::Push,
+                _ => unreachable!(),
+            };
+            let (voter_signer, voter) =
+                config.signer_or_default(arg_matches, "voter", &mut wallet_manager);
+            bulk_signers.push(voter_signer);
+
+            command_vote_for_cc(
+                &config,
+                candidate_limit_record,
+                target_ring,
+                vote,
+                voter,
+                bulk_signers,
+            )
+        }
+        ("settle-tanistry", Some(arg_matches)) => {
+            let tanistry = pubkey_of_signer(arg_matches, "tanistry", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+
+            command_settle_tanistry(&config, tanistry, bulk_signers)
+        }
+        ("cancel-tanistry", Some(arg_matches)) => {
+            let tanistry = pubkey_of_signer(arg_matches, "tanistry", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let bc_tokens = pubkeys_of(arg_matches, "bc_tokens").unwrap_or_default();
+
+            command_cancel_tanistry(&config, tanistry, bc_tokens, bulk_signers)
+        }
+        ("claim-refund", Some(arg_matches)) => {
+            let tanistry = pubkey_of_signer(arg_matches, "tanistry", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let refund_record = pubkey_of_signer(arg_matches, "refund_record", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            let claimant_token_account =
+                pubkey_of_signer(arg_matches, "claimant_token_account", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let pinhole = pubkey_of_signer(arg_matches, "pinhole", &mut wallet_manager).unwrap();
+            let (claimant_signer, claimant) =
+                config.signer_or_default(arg_matches, "claimant", &mut wallet_manager);
+            bulk_signers.push(claimant_signer);
+
+            command_claim_refund(
+                &config,
+                tanistry,
+                refund_record,
+                claimant_token_account,
+                claimant,
+                pinhole,
+                bulk_signers,
+            )
+        }
+        ("show", Some(arg_matches)) => {
+            let address = pubkey_of_signer(arg_matches, "address", &mut wallet_manager)
+                .unwrap()
+                .unwrap();
+            command_show(&config, address)
+        }
+        _ => unreachable!(),
+    }
+    .map_err::<Error, _>(|err| DisplayError::new_as_boxed(err).into())?;
+    println!("{}", result);
+    Ok(())
+}
+
+enum TransactionReturnData {
+    CliSignature(CliSignature),
+    CliSignOnlyData(CliSignOnlyData),
+}
+fn handle_tx(
+    signer_info: &CliSignerInfo,
+    config: &Config,
+    no_wait: bool,
+    minimum_balance_for_rent_exemption: u64,
+    instructions: Vec<Instruction>,
+) -> Result<TransactionReturnData, Box<dyn std::error::Error>> {
+    let fee_payer = Some(&config.fee_payer);
+
+    let message = if let Some(nonce_account) = config.nonce_account.as_ref() {
+        Message::new_with_nonce(
+            instructions,
+            fee_payer,
+            nonce_account,
+            config.nonce_authority.as_ref().unwrap(),
+        )
+    } else {
+        Message::new(&instructions, fee_payer)
+    };
+    let (recent_blockhash, fee_calculator) = config
+        .blockhash_query
+        .get_blockhash_and_fee_calculator(&config.rpc_client, config.rpc_client.commitment())
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(1);
+        });
+
+    if !config.sign_only {
+        check_fee_payer_balance(
+            config,
+            minimum_balance_for_rent_exemption + fee_calculator.calculate_fee(&message),
+        )?;
+    }
+
+    let signers = signer_info.signers_for_message(&message);
+    let mut transaction = Transaction::new_unsigned(message);
+
+    if config.sign_only {
+        transaction.try_partial_sign(&signers, recent_blockhash)?;
+        Ok(TransactionReturnData::CliSignOnlyData(return_signers_data(
+            &transaction,
+            &ReturnSignersConfig {
+                dump_transaction_message: config.dump_transaction_message,
+            },
+        )))
+    } else {
+        transaction.try_sign(&signers, recent_blockhash)?;
+        let signature = if no_wait {
+            config.rpc_client.send_transaction(&transaction)?
+        } else {
+            config
+                .rpc_client
+                .send_and_confirm_transaction_with_spinner(&transaction)?
+        };
+        Ok(TransactionReturnData::CliSignature(CliSignature {
+            signature: signature.to_string(),
+        }))
+    }
+}
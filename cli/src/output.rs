@@ -0,0 +1,140 @@
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+use shihon::decode::UiShihonAccount;
+use solana_cli_output::{display::writeln_name_value, OutputFormat, QuietDisplay, VerboseDisplay};
+use std::fmt::{self, Display};
+
+pub(crate) fn println_display(config: &Config, message: String) {
+    match config.output_format {
+        OutputFormat::Display | OutputFormat::DisplayVerbose => {
+            println!("{}", message);
+        }
+        _ => {}
+    }
+}
+
+/// The address of the Shihon account created or used by the command along with the transaction
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliShihonAddress<T>
+where
+    T: Serialize + Display + QuietDisplay + VerboseDisplay,
+{
+    pub(crate) address_name: String,
+    pub(crate) address: String,
+    pub(crate) transaction_data: T,
+}
+
+impl<T> Display for CliShihonAddress<T>
+where
+    T: Serialize + Display + QuietDisplay + VerboseDisplay,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(f, &format!("{}:", self.address_name), &self.address)?;
+        Display::fmt(&self.transaction_data, f)
+    }
+}
+
+impl<T> QuietDisplay for CliShihonAddress<T>
+where
+    T: Serialize + Display + QuietDisplay + VerboseDisplay,
+{
+    fn write_str(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(w)?;
+        writeln_name_value(w, &format!("{}:", self.address_name), &self.address)?;
+        QuietDisplay::write_str(&self.transaction_data, w)
+    }
+}
+
+impl<T> VerboseDisplay for CliShihonAddress<T>
+where
+    T: Serialize + Display + QuietDisplay + VerboseDisplay,
+{
+    fn write_str(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(w)?;
+        writeln_name_value(w, &format!("{}:", self.address_name), &self.address)?;
+        VerboseDisplay::write_str(&self.transaction_data, w)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CliShihonAccount {
+    pub(crate) address: String,
+    pub(crate) lamports: u64,
+    pub(crate) data_len: usize,
+    pub(crate) account: UiShihonAccount,
+}
+
+impl QuietDisplay for CliShihonAccount {}
+impl VerboseDisplay for CliShihonAccount {
+    fn write_str(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(w)?;
+        writeln_name_value(w, "Address:", &self.address)?;
+        writeln_name_value(w, "Lamports:", &self.lamports.to_string())?;
+        writeln_name_value(w, "Data length:", &self.data_len.to_string())?;
+        write!(w, "{}", self.account)
+    }
+}
+
+impl fmt::Display for CliShihonAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(f, "Address:", &self.address)?;
+        Display::fmt(&self.account, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use shihon::{
+        decode::ShihonAccount,
+        state::{candidate_limit_record::CandidateLimitRecord, enums::ShihonAccountType},
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_shihon_account_json_output() {
+        // Arrange
+        let candidate_token_owner = Pubkey::new_unique();
+        let candidate_limit_record = CandidateLimitRecord {
+            account_type: ShihonAccountType::CandidateLimitRecordV2,
+            bump: 255,
+            belonging_tanistry: Pubkey::new_unique(),
+            number_of_candidate_count: 1,
+            candidate_token_mint: Pubkey::new_unique(),
+            candidate_token_owner,
+            candidate_delegate: None,
+            bc_token: Pubkey::new_unique(),
+            candidate_token_deposit_amount: 100,
+            received_rating: 0,
+            refund_amount: 0,
+            is_refunded: false,
+            candidate_fee_amount: 0,
+            rate_options_count: 0,
+        };
+
+        let cli_shihon_account = CliShihonAccount {
+            address: Pubkey::new_unique().to_string(),
+            lamports: 1,
+            data_len: 0,
+            account: UiShihonAccount::from(&ShihonAccount::CandidateLimitRecord(
+                candidate_limit_record,
+            )),
+        };
+
+        // Act
+        let json = serde_json::to_value(&cli_shihon_account).unwrap();
+
+        // Assert
+        assert_eq!(json["address"], cli_shihon_account.address);
+        assert_eq!(json["dataLen"], 0);
+        assert_eq!(json["account"]["type"], "candidateLimitRecord");
+        assert_eq!(
+            json["account"]["info"]["candidateTokenOwner"],
+            candidate_token_owner.to_string()
+        );
+    }
+}
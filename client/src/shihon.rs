@@ -0,0 +1,970 @@
+use spl_token_client::client::{ProgramClient, ProgramClientError, SendTransaction};
+use borsh::BorshDeserialize;
+use shihon::{
+    decode::{decode_account, ShihonAccount},
+    error::ShihonError,
+    instruction,
+    pda::{
+        get_bc_token_address, get_candidate_limit_record_address, get_cc_vote_record_address,
+        get_kicker_coin_owner_record_address, get_mix_content_record_address, get_pinhole_address,
+        get_rate_option_address, get_rate_other_record_address, get_ring_address,
+        get_shihon_config_address, get_tanistry_address, get_triple_multisig_address,
+    },
+    state::{
+        bc_token::BcToken,
+        bc_token_metadata::{BcTokenMetadata, ContentRange},
+        candidate_limit_record::CandidateLimitRecord,
+        cc_vote_record::CCVoteRecord,
+        enums::{PinholeSide, VoteSource},
+        kicker_coin_owner_record::KickerCoinOwnerRecord,
+        mix_content_record::MixContentRecord,
+        pinhole::Pinhole,
+        rate_option::RateOption,
+        ring::Ring,
+        shihon_config::ShihonConfig,
+        tanistry::Tanistry,
+        triple_multisig::TripleMultisig,
+    },
+};
+use solana_sdk::{
+    account::Account as BaseAccount,
+    borsh::try_from_slice_unchecked,
+    instruction::Instruction,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    signer::{signers::Signers, Signer},
+    transaction::Transaction,
+};
+use std::{collections::HashSet, fmt, sync::Arc};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ShihonClientError {
+    #[error("client error: {0}")]
+    Client(ProgramClientError),
+    #[error("program error: {0}")]
+    Program(#[from] ProgramError),
+    #[error("account not found")]
+    AccountNotFound,
+    #[error("invalid account owner")]
+    AccountInvalidOwner,
+    #[error("invalid account data")]
+    AccountInvalidData,
+}
+impl PartialEq for ShihonClientError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            // TODO not great, but workable for tests
+            (Self::Client(ref a), Self::Client(ref b)) => a.to_string() == b.to_string(),
+            (Self::Program(ref a), Self::Program(ref b)) => a == b,
+            (Self::AccountNotFound, Self::AccountNotFound) => true,
+            (Self::AccountInvalidOwner, Self::AccountInvalidOwner) => true,
+            (Self::AccountInvalidData, Self::AccountInvalidData) => true,
+            _ => false,
+        }
+    }
+}
+
+pub type ShihonClientResult<T> = Result<T, ShihonClientError>;
+
+pub struct Shihon<T, S> {
+    client: Arc<dyn ProgramClient<T>>,
+    payer: S,
+    program_id: Pubkey,
+}
+
+impl<T, S> fmt::Debug for Shihon<T, S>
+where
+    S: Signer,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shihon")
+            .field("program_id", &self.program_id)
+            .field("payer", &self.payer.pubkey())
+            .finish()
+    }
+}
+
+impl<T, S> Shihon<T, S>
+where
+    T: SendTransaction,
+    S: Signer,
+{
+    pub fn new(client: Arc<dyn ProgramClient<T>>, program_id: &Pubkey, payer: S) -> Self {
+        Shihon {
+            client,
+            payer,
+            program_id: *program_id,
+        }
+    }
+
+    /// Get Shihon program id.
+    pub fn get_program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    pub fn with_payer<S2: Signer>(&self, payer: S2) -> Shihon<T, S2> {
+        Shihon {
+            client: Arc::clone(&self.client),
+            payer,
+            program_id: self.program_id,
+        }
+    }
+
+    pub async fn process_ixs<S2: Signers>(
+        &self,
+        instructions: &[Instruction],
+        signing_keypairs: &S2,
+    ) -> ShihonClientResult<T::Output> {
+        let recent_blockhash = self
+            .client
+            .get_latest_blockhash()
+            .await
+            .map_err(ShihonClientError::Client)?;
+
+        let mut tx = Transaction::new_with_payer(instructions, Some(&self.payer.pubkey()));
+        tx.try_partial_sign(&[&self.payer], recent_blockhash)
+            .map_err(|error| ShihonClientError::Client(error.into()))?;
+        tx.try_sign(signing_keypairs, recent_blockhash)
+            .map_err(|error| ShihonClientError::Client(error.into()))?;
+
+        self.client
+            .send_transaction(&tx)
+            .await
+            .map_err(ShihonClientError::Client)
+    }
+
+    /// Create bcToken.
+    pub async fn create_bc_token<S2: Signer>(
+        &self,
+        bc_token_authority: &S2,
+        bc_token_mint: &Pubkey,
+        name: String,
+        amount: u64,
+        config: BcTokenMetadata,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::create_bc_token(
+                &self.program_id,
+                &bc_token_authority.pubkey(),
+                bc_token_mint,
+                &self.payer.pubkey(),
+                name,
+                amount,
+                config,
+            )],
+            &[bc_token_authority],
+        )
+        .await
+    }
+
+    /// Kick KickerCoin to the coordinator.
+    /// Returns the address of the issued KickerCoinOwnerRecord.
+    pub async fn kick<S2: Signer>(
+        &self,
+        kicker: &S2,
+        kicker_bc_token: &Pubkey,
+        kicker_token_mint: &Pubkey,
+        kicker_token_source: &Pubkey,
+        coordinator: &Pubkey,
+        amount: u64,
+    ) -> ShihonClientResult<Pubkey> {
+        let kicker_coin_owner_record =
+            get_kicker_coin_owner_record_address(&self.program_id, &kicker.pubkey(), coordinator);
+
+        self.process_ixs(
+            &[instruction::kicking_to_coordinator(
+                &self.program_id,
+                kicker_bc_token,
+                &kicker.pubkey(),
+                kicker_token_source,
+                kicker_token_mint,
+                &self.payer.pubkey(),
+                coordinator,
+                amount,
+            )],
+            &[kicker],
+        )
+        .await
+        .map(|_| kicker_coin_owner_record)
+    }
+
+    /// Drop the one-time pinhole of the kicker or the coordinator for the round of the KickerCoin.
+    /// The pinhole authority should be a fresh keypair used for nothing else.
+    /// Returns the address of the Pinhole.
+    pub async fn drop_pinhole<S2: Signer, S3: Signer>(
+        &self,
+        kicker_coin_owner_record: &Pubkey,
+        party: &S2,
+        pinhole_authority: &S3,
+        side: PinholeSide,
+    ) -> ShihonClientResult<Pubkey> {
+        self.process_ixs(
+            &[instruction::drop_pinhole(
+                &self.program_id,
+                kicker_coin_owner_record,
+                &party.pubkey(),
+                &pinhole_authority.pubkey(),
+                &self.payer.pubkey(),
+                side,
+            )],
+            &vec![party as &dyn Signer, pinhole_authority],
+        )
+        .await
+        .map(|_| {
+            get_pinhole_address(
+                &self.program_id,
+                kicker_coin_owner_record,
+                &pinhole_authority.pubkey(),
+            )
+        })
+    }
+
+    /// Approve the kicked KickerCoin and form the Tanistry.
+    /// The Tanistry joins the Ring of the previous Tanistry if its Crown kicked the KickerCoin on.
+    /// Returns the address of the Tanistry.
+    pub async fn approve<S2: Signer>(
+        &self,
+        coordinator: &S2,
+        kicker_coin_owner_record: &Pubkey,
+        tanistry_token_mint: &Pubkey,
+        previous_tanistry: Option<&Pubkey>,
+        coordinator_input: String,
+    ) -> ShihonClientResult<Pubkey> {
+        let previous_tanistry_crown = match previous_tanistry {
+            Some(previous_tanistry) => {
+                let crown = self
+                    .get_tanistry(previous_tanistry)
+                    .await?
+                    .crown
+                    .ok_or_else(|| {
+                        ShihonClientError::Program(
+                            ShihonError::InvalidPreviousTanistryCannotLink.into(),
+                        )
+                    })?;
+                Some((*previous_tanistry, crown))
+            }
+            None => None,
+        };
+
+        self.process_ixs(
+            &[instruction::approve_kicker_coin(
+                &self.program_id,
+                kicker_coin_owner_record,
+                &coordinator.pubkey(),
+                tanistry_token_mint,
+                &self.payer.pubkey(),
+                previous_tanistry_crown
+                    .as_ref()
+                    .map(|(previous_tanistry, crown)| (previous_tanistry, crown)),
+                coordinator_input,
+            )],
+            &[coordinator],
+        )
+        .await
+        .map(|_| get_tanistry_address(&self.program_id, kicker_coin_owner_record))
+    }
+
+    /// Enroll a candidate into the Tanistry.
+    /// Returns the address of the candidate's CandidateLimitRecord.
+    pub async fn candidate<S2: Signer>(
+        &self,
+        tanistry: &Pubkey,
+        candidate_token_source: &Pubkey,
+        candidate_token_owner: &S2,
+        bc_token: &Pubkey,
+        candidate_token_mint: &Pubkey,
+        amount: u64,
+    ) -> ShihonClientResult<Pubkey> {
+        self.process_ixs(
+            &[instruction::candidate(
+                &self.program_id,
+                tanistry,
+                &candidate_token_owner.pubkey(),
+                candidate_token_source,
+                bc_token,
+                candidate_token_mint,
+                &self.payer.pubkey(),
+                amount,
+            )],
+            &[candidate_token_owner],
+        )
+        .await
+        .map(|_| {
+            get_candidate_limit_record_address(
+                &self.program_id,
+                tanistry,
+                candidate_token_mint,
+                &candidate_token_owner.pubkey(),
+            )
+        })
+    }
+
+    /// Create the TripleMultisig of the rater, the buddy and the first kicker of the Tanistry.
+    /// Returns the address of the TripleMultisig.
+    pub async fn create_triple_multisig(
+        &self,
+        rater_candidate: &Pubkey,
+        buddy_candidate: &Pubkey,
+        tanistry: &Pubkey,
+        kicker_coin_owner_record: &Pubkey,
+    ) -> ShihonClientResult<Pubkey> {
+        self.process_ixs(
+            &[instruction::create_triple_multisig(
+                &self.program_id,
+                rater_candidate,
+                buddy_candidate,
+                tanistry,
+                kicker_coin_owner_record,
+                &self.payer.pubkey(),
+            )],
+            &Vec::<&dyn Signer>::new(),
+        )
+        .await
+        .map(|_| get_triple_multisig_address(&self.program_id, rater_candidate, buddy_candidate))
+    }
+
+    /// Mix the init content with the buddy candidate's content.
+    /// Partially contents require the range taken into the mix.
+    /// The cosigner is the buddy or the first kicker completing the TripleMultisig approval.
+    /// Returns the address of the MixContentRecord.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn mix<S2: Signer, S3: Signer>(
+        &self,
+        rater_candidate: &Pubkey,
+        rater_token_owner: &S2,
+        cosigner: &S3,
+        buddy_content_holder: &Pubkey,
+        kicker_coin_owner_record: &Pubkey,
+        coordinator: &Pubkey,
+        init_bc_token: &Pubkey,
+        buddy_bc_token: &Pubkey,
+        time_shift_a: u64,
+        time_shift_b: u64,
+        range_a: Option<ContentRange>,
+        range_b: Option<ContentRange>,
+    ) -> ShihonClientResult<Pubkey> {
+        self.process_ixs(
+            &[instruction::mix_content(
+                &self.program_id,
+                rater_candidate,
+                &rater_token_owner.pubkey(),
+                buddy_content_holder,
+                kicker_coin_owner_record,
+                coordinator,
+                init_bc_token,
+                buddy_bc_token,
+                &self.payer.pubkey(),
+                &[rater_token_owner.pubkey(), cosigner.pubkey()],
+                time_shift_a,
+                time_shift_b,
+                range_a,
+                range_b,
+            )],
+            &vec![rater_token_owner as &dyn Signer, cosigner],
+        )
+        .await
+        .map(|_| {
+            get_mix_content_record_address(&self.program_id, rater_candidate, buddy_content_holder)
+        })
+    }
+
+    /// Rate the buddy candidate's mixed content.
+    /// The cosigner is the buddy or the first kicker completing the TripleMultisig approval.
+    pub async fn rate<S2: Signer, S3: Signer>(
+        &self,
+        rater_candidate: &Pubkey,
+        rater_token_owner: &S2,
+        cosigner: &S3,
+        buddy_candidate: &Pubkey,
+        mix_content_record: &Pubkey,
+        rating: u64,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::rate_content(
+                &self.program_id,
+                rater_candidate,
+                &rater_token_owner.pubkey(),
+                buddy_candidate,
+                mix_content_record,
+                &self.payer.pubkey(),
+                &[rater_token_owner.pubkey(), cosigner.pubkey()],
+                rating,
+            )],
+            &vec![rater_token_owner as &dyn Signer, cosigner],
+        )
+        .await
+    }
+
+    /// Issue the RateOption of the Roydamna on the MixContentRecord it takes part in.
+    /// Returns the address of the RateOption.
+    pub async fn create_rate_option<S2: Signer>(
+        &self,
+        issuer_roydamna: &Pubkey,
+        issuer_token_owner: &S2,
+        mix_content_record: &Pubkey,
+    ) -> ShihonClientResult<Pubkey> {
+        self.process_ixs(
+            &[instruction::create_rate_option(
+                &self.program_id,
+                issuer_roydamna,
+                &issuer_token_owner.pubkey(),
+                mix_content_record,
+                &self.payer.pubkey(),
+            )],
+            &[issuer_token_owner],
+        )
+        .await
+        .map(|_| get_rate_option_address(&self.program_id, mix_content_record, issuer_roydamna))
+    }
+
+    /// Sign off the RateOption once the rating of the MixContentRecord has been cast.
+    pub async fn sign_off_rate_option<S2: Signer>(
+        &self,
+        issuer_roydamna: &Pubkey,
+        issuer_token_owner: &S2,
+        mix_content_record: &Pubkey,
+    ) -> ShihonClientResult<T::Output> {
+        let mix_content_record_data = self.get_mix_content_record(mix_content_record).await?;
+
+        self.process_ixs(
+            &[instruction::sign_off_rate_option(
+                &self.program_id,
+                issuer_roydamna,
+                &issuer_token_owner.pubkey(),
+                mix_content_record,
+                &get_rate_other_record_address(
+                    &self.program_id,
+                    &mix_content_record_data.rater_candidate,
+                    &mix_content_record_data.buddy_candidate,
+                ),
+            )],
+            &[issuer_token_owner],
+        )
+        .await
+    }
+
+    /// Remove the RateOption which hasn't been signed off, the payer receives the lamports.
+    pub async fn remove_rate_option<S2: Signer>(
+        &self,
+        issuer_roydamna: &Pubkey,
+        issuer_token_owner: &S2,
+        mix_content_record: &Pubkey,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::remove_rate_option(
+                &self.program_id,
+                issuer_roydamna,
+                &issuer_token_owner.pubkey(),
+                mix_content_record,
+                &self.payer.pubkey(),
+            )],
+            &[issuer_token_owner],
+        )
+        .await
+    }
+
+    /// Bump the candidate's self rate by depositing more Tanistry tokens.
+    /// Both the candidate and the bcToken may be signed by either their owner or their delegate.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn bump_self_rate<S2: Signer, S3: Signer>(
+        &self,
+        tanistry: &Pubkey,
+        candidate_limit_record: &Pubkey,
+        candidate_token_source: &Pubkey,
+        candidate_token_authority: &S2,
+        bc_token: &Pubkey,
+        bc_token_authority: &S3,
+        tanistry_token_mint: &Pubkey,
+        amount: u64,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::bump_self_rate(
+                &self.program_id,
+                tanistry,
+                candidate_limit_record,
+                candidate_token_source,
+                &candidate_token_authority.pubkey(),
+                bc_token,
+                &bc_token_authority.pubkey(),
+                tanistry_token_mint,
+                amount,
+            )],
+            &vec![candidate_token_authority as &dyn Signer, bc_token_authority],
+        )
+        .await
+    }
+
+    /// Set or remove the delegate of the CandidateLimitRecord, signed by the candidate token owner.
+    pub async fn set_candidate_delegate<S2: Signer>(
+        &self,
+        candidate_limit_record: &Pubkey,
+        candidate_token_owner: &S2,
+        new_candidate_delegate: Option<Pubkey>,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::set_candidate_delegate(
+                &self.program_id,
+                candidate_limit_record,
+                &candidate_token_owner.pubkey(),
+                new_candidate_delegate,
+            )],
+            &[candidate_token_owner],
+        )
+        .await
+    }
+
+    /// Set or remove the delegate of the bcToken, signed by the bcToken authority.
+    pub async fn set_bc_token_delegate<S2: Signer>(
+        &self,
+        bc_token: &Pubkey,
+        bc_token_authority: &S2,
+        new_delegate: Option<Pubkey>,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::set_bc_token_delegate(
+                &self.program_id,
+                bc_token,
+                &bc_token_authority.pubkey(),
+                new_delegate,
+            )],
+            &[bc_token_authority],
+        )
+        .await
+    }
+
+    /// Set the new authority of the bcToken, or only propose it when the acceptance is required.
+    /// None withdraws the pending proposal when the acceptance is required.
+    pub async fn set_bc_token_authority<S2: Signer>(
+        &self,
+        bc_token: &Pubkey,
+        bc_token_authority: &S2,
+        new_authority: Option<Pubkey>,
+        require_acceptance: bool,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::set_bc_token_authority(
+                &self.program_id,
+                bc_token,
+                &bc_token_authority.pubkey(),
+                new_authority,
+                require_acceptance,
+                false,
+            )],
+            &[bc_token_authority],
+        )
+        .await
+    }
+
+    /// Renounce the authority of the bcToken which freezes the bcToken for good.
+    pub async fn renounce_bc_token_authority<S2: Signer>(
+        &self,
+        bc_token: &Pubkey,
+        bc_token_authority: &S2,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::set_bc_token_authority(
+                &self.program_id,
+                bc_token,
+                &bc_token_authority.pubkey(),
+                None,
+                false,
+                true,
+            )],
+            &[bc_token_authority],
+        )
+        .await
+    }
+
+    /// Accept the authority of the bcToken proposed to the pending authority.
+    pub async fn accept_bc_token_authority<S2: Signer>(
+        &self,
+        bc_token: &Pubkey,
+        pending_authority: &S2,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::accept_bc_token_authority(
+                &self.program_id,
+                bc_token,
+                &pending_authority.pubkey(),
+            )],
+            &[pending_authority],
+        )
+        .await
+    }
+
+    /// Promote the Succeeded mixed content into a new bcToken owned by the rater.
+    /// Returns the address of the new bcToken.
+    pub async fn promote_mixed_content<S2: Signer>(
+        &self,
+        mix_content_record: &Pubkey,
+        rater_token_owner: &S2,
+        bc_token_mint: &Pubkey,
+        name: String,
+        version: String,
+    ) -> ShihonClientResult<Pubkey> {
+        let mix_content_record_data = self.get_mix_content_record(mix_content_record).await?;
+        let bc_token = get_bc_token_address(&self.program_id, &name);
+
+        self.process_ixs(
+            &[instruction::promote_mixed_content(
+                &self.program_id,
+                mix_content_record,
+                &mix_content_record_data.rater_candidate,
+                &rater_token_owner.pubkey(),
+                &mix_content_record_data.init_bc_token,
+                &mix_content_record_data.buddy_bc_token,
+                bc_token_mint,
+                &self.payer.pubkey(),
+                name,
+                version,
+            )],
+            &[rater_token_owner],
+        )
+        .await
+        .map(|_| bc_token)
+    }
+
+    /// Elect the Crown from the Tanistry candidates.
+    /// The deposits and the RFT volume of the Tanistry are added to the Ring of the Tanistry chain.
+    pub async fn crown<S2: Signer>(
+        &self,
+        coordinator: &S2,
+        kicker_coin_owner_record: &Pubkey,
+        ring: &Pubkey,
+        crown: &Pubkey,
+    ) -> ShihonClientResult<T::Output> {
+        let tanistry = get_tanistry_address(&self.program_id, kicker_coin_owner_record);
+        let tanistry_data = self.get_tanistry(&tanistry).await?;
+
+        self.process_ixs(
+            &[instruction::crowning(
+                &self.program_id,
+                &coordinator.pubkey(),
+                kicker_coin_owner_record,
+                ring,
+                &tanistry_data.candidate_limit_record_list,
+                crown,
+            )],
+            &[coordinator],
+        )
+        .await
+    }
+
+    /// Create the Ring of the Tanistry chain started from the first Tanistry.
+    /// Returns the address of the Ring.
+    pub async fn create_ring(&self, first_tanistry: &Pubkey) -> ShihonClientResult<Pubkey> {
+        self.process_ixs(
+            &[instruction::create_ring(
+                &self.program_id,
+                first_tanistry,
+                &self.payer.pubkey(),
+            )],
+            &[&self.payer],
+        )
+        .await
+        .map(|_| get_ring_address(&self.program_id, first_tanistry))
+    }
+
+    /// Returns the Tanistry the candidate belongs to and the Ring of its chain.
+    async fn get_voter_tanistry_and_ring(
+        &self,
+        candidate_limit_record: &Pubkey,
+    ) -> ShihonClientResult<(Pubkey, Pubkey)> {
+        let tanistry = self
+            .get_candidate_limit_record(candidate_limit_record)
+            .await?
+            .belonging_tanistry;
+        let ring = self.get_tanistry(&tanistry).await?.ring;
+
+        Ok((tanistry, ring))
+    }
+
+    /// Cast CC vote pulling or pushing the target ring.
+    /// Returns the address of the CCVoteRecord.
+    pub async fn vote<S2: Signer>(
+        &self,
+        candidate_limit_record: &Pubkey,
+        voter: &S2,
+        target_ring: &Pubkey,
+        vote: VoteSource,
+    ) -> ShihonClientResult<Pubkey> {
+        let (tanistry, ring) = self
+            .get_voter_tanistry_and_ring(candidate_limit_record)
+            .await?;
+
+        self.process_ixs(
+            &[instruction::vote_for_cc(
+                &self.program_id,
+                candidate_limit_record,
+                &tanistry,
+                &ring,
+                &voter.pubkey(),
+                &self.payer.pubkey(),
+                target_ring,
+                vote,
+            )],
+            &[voter],
+        )
+        .await
+        .map(|_| get_cc_vote_record_address(&self.program_id, candidate_limit_record))
+    }
+
+    /// Relinquish CC vote from the target ring.
+    pub async fn relinquish_vote<S2: Signer>(
+        &self,
+        target_ring: &Pubkey,
+        candidate_limit_record: &Pubkey,
+        voter: &S2,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::relinquish_cc_vote(
+                &self.program_id,
+                target_ring,
+                candidate_limit_record,
+                &voter.pubkey(),
+            )],
+            &[voter],
+        )
+        .await
+    }
+
+    /// Redirect CC vote to the new target ring.
+    /// Returns the address of the CCVoteRecord, which is kept for the new ring.
+    pub async fn change_vote<S2: Signer>(
+        &self,
+        target_ring: &Pubkey,
+        candidate_limit_record: &Pubkey,
+        voter: &S2,
+        new_target_ring: &Pubkey,
+        vote: VoteSource,
+    ) -> ShihonClientResult<Pubkey> {
+        let (tanistry, ring) = self
+            .get_voter_tanistry_and_ring(candidate_limit_record)
+            .await?;
+
+        self.process_ixs(
+            &[instruction::change_cc_vote(
+                &self.program_id,
+                target_ring,
+                candidate_limit_record,
+                &tanistry,
+                &ring,
+                &voter.pubkey(),
+                new_target_ring,
+                vote,
+            )],
+            &[voter],
+        )
+        .await
+        .map(|_| get_cc_vote_record_address(&self.program_id, candidate_limit_record))
+    }
+
+    /// Settle the crowned Tanistry.
+    pub async fn settle(
+        &self,
+        tanistry: &Pubkey,
+        tanistry_token_mint: &Pubkey,
+    ) -> ShihonClientResult<T::Output> {
+        let tanistry_data = self.get_tanistry(tanistry).await?;
+
+        self.process_ixs(
+            &[instruction::settle_tanistry(
+                &self.program_id,
+                tanistry,
+                &tanistry_data.kicker_coin_owner_record,
+                tanistry_token_mint,
+                &tanistry_data.candidate_limit_record_list,
+            )],
+            &[&self.payer],
+        )
+        .await
+    }
+
+    /// Cancel the Tanistry whose enrollment ended without enough candidates.
+    /// The bcTokens are the kicker's and the candidates' bcTokens to return to PublicOnTheGround.
+    pub async fn cancel(
+        &self,
+        tanistry: &Pubkey,
+        tanistry_token_mint: &Pubkey,
+        bc_tokens: &[Pubkey],
+    ) -> ShihonClientResult<T::Output> {
+        let tanistry_data = self.get_tanistry(tanistry).await?;
+
+        self.process_ixs(
+            &[instruction::cancel_tanistry(
+                &self.program_id,
+                tanistry,
+                &tanistry_data.kicker_coin_owner_record,
+                tanistry_token_mint,
+                &tanistry_data.candidate_limit_record_list,
+                bc_tokens,
+            )],
+            &[&self.payer],
+        )
+        .await
+    }
+
+    /// Claim own refund from the settled Tanistry.
+    /// The refund record is the claimant's CandidateLimitRecord or the Tanistry's KickerCoinOwnerRecord.
+    /// The Tanistry token program is either SPL Token or Token-2022 program owning the Tanistry token mint.
+    /// When the pinhole is given the claimant is its authority and the refund goes to the main wallet behind it.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn refund<S2: Signer>(
+        &self,
+        tanistry: &Pubkey,
+        tanistry_token_mint: &Pubkey,
+        tanistry_token_program_id: &Pubkey,
+        refund_record: &Pubkey,
+        claimant: &S2,
+        claimant_token_account: &Pubkey,
+        pinhole: Option<&Pubkey>,
+    ) -> ShihonClientResult<T::Output> {
+        self.process_ixs(
+            &[instruction::claim_refund(
+                &self.program_id,
+                tanistry,
+                tanistry_token_mint,
+                refund_record,
+                &claimant.pubkey(),
+                claimant_token_account,
+                tanistry_token_program_id,
+                pinhole,
+            )],
+            &[claimant],
+        )
+        .await
+    }
+
+    /// Retrieve a raw account
+    pub async fn get_account(&self, account: &Pubkey) -> ShihonClientResult<BaseAccount> {
+        self.client
+            .get_account(*account)
+            .await
+            .map_err(ShihonClientError::Client)?
+            .ok_or(ShihonClientError::AccountNotFound)
+    }
+
+    /// Retrieve Shihon program account information.
+    pub async fn get_program_account<A: BorshDeserialize>(
+        &self,
+        account: &Pubkey,
+    ) -> ShihonClientResult<A> {
+        let account = self.get_account(account).await?;
+        if account.owner != self.program_id {
+            return Err(ShihonClientError::AccountInvalidOwner);
+        }
+
+        try_from_slice_unchecked::<A>(&account.data)
+            .map_err(|_| ShihonClientError::AccountInvalidData)
+    }
+
+    /// Retrieve any Shihon program account decoded by its account type.
+    pub async fn get_decoded_account(&self, account: &Pubkey) -> ShihonClientResult<ShihonAccount> {
+        let account = self.get_account(account).await?;
+        if account.owner != self.program_id {
+            return Err(ShihonClientError::AccountInvalidOwner);
+        }
+
+        decode_account(&account.data).map_err(|_| ShihonClientError::AccountInvalidData)
+    }
+
+    /// Retrieve bcToken information.
+    pub async fn get_bc_token(&self, bc_token: &Pubkey) -> ShihonClientResult<BcToken> {
+        self.get_program_account(bc_token).await
+    }
+
+    /// Walk the content lineage of the bcToken back to the original contents.
+    /// Returns the original bcTokens without lineage, the bcToken itself if it's an original.
+    pub async fn get_content_origins(&self, bc_token: &Pubkey) -> ShihonClientResult<Vec<Pubkey>> {
+        let mut origins = vec![];
+        let mut visited = HashSet::new();
+        let mut pending = vec![*bc_token];
+
+        while let Some(bc_token) = pending.pop() {
+            if !visited.insert(bc_token) {
+                continue;
+            }
+
+            let parent_bc_tokens = self
+                .get_bc_token(&bc_token)
+                .await?
+                .config
+                .get_parent_bc_tokens();
+
+            if parent_bc_tokens.is_empty() {
+                origins.push(bc_token);
+            } else {
+                pending.extend(parent_bc_tokens);
+            }
+        }
+
+        Ok(origins)
+    }
+
+    /// Retrieve KickerCoinOwnerRecord information.
+    pub async fn get_kicker_coin_owner_record(
+        &self,
+        kicker_coin_owner_record: &Pubkey,
+    ) -> ShihonClientResult<KickerCoinOwnerRecord> {
+        self.get_program_account(kicker_coin_owner_record).await
+    }
+
+    /// Retrieve Tanistry information.
+    pub async fn get_tanistry(&self, tanistry: &Pubkey) -> ShihonClientResult<Tanistry> {
+        self.get_program_account(tanistry).await
+    }
+
+    /// Retrieve Ring information.
+    pub async fn get_ring(&self, ring: &Pubkey) -> ShihonClientResult<Ring> {
+        self.get_program_account(ring).await
+    }
+
+    /// Retrieve Pinhole information.
+    pub async fn get_pinhole(&self, pinhole: &Pubkey) -> ShihonClientResult<Pinhole> {
+        self.get_program_account(pinhole).await
+    }
+
+    /// Retrieve TripleMultisig information.
+    pub async fn get_triple_multisig(
+        &self,
+        triple_multisig: &Pubkey,
+    ) -> ShihonClientResult<TripleMultisig> {
+        self.get_program_account(triple_multisig).await
+    }
+
+    /// Retrieve CandidateLimitRecord information.
+    pub async fn get_candidate_limit_record(
+        &self,
+        candidate_limit_record: &Pubkey,
+    ) -> ShihonClientResult<CandidateLimitRecord> {
+        self.get_program_account(candidate_limit_record).await
+    }
+
+    /// Retrieve MixContentRecord information.
+    pub async fn get_mix_content_record(
+        &self,
+        mix_content_record: &Pubkey,
+    ) -> ShihonClientResult<MixContentRecord> {
+        self.get_program_account(mix_content_record).await
+    }
+
+    /// Retrieve RateOption information.
+    pub async fn get_rate_option(&self, rate_option: &Pubkey) -> ShihonClientResult<RateOption> {
+        self.get_program_account(rate_option).await
+    }
+
+    /// Retrieve CCVoteRecord information.
+    pub async fn get_cc_vote_record(
+        &self,
+        cc_vote_record: &Pubkey,
+    ) -> ShihonClientResult<CCVoteRecord> {
+        self.get_program_account(cc_vote_record).await
+    }
+
+    /// Retrieve the ShihonConfig of the deployment.
+    pub async fn get_shihon_config(&self) -> ShihonClientResult<ShihonConfig> {
+        self.get_program_account(&get_shihon_config_address(&self.program_id))
+            .await
+    }
+}
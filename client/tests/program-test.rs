@@ -0,0 +1,827 @@
+use borsh::BorshSerialize;
+use shihon::{
+    error::ShihonError,
+    pda::{
+        find_bump_seed, get_cc_vote_record_address, get_cc_vote_record_address_seeds,
+        get_tanistry_address,
+    },
+    processor::process_instruction,
+    state::{
+        bc_token::BcToken,
+        bc_token_metadata::{BcTokenMetadata, ContentType},
+        candidate_limit_record::CandidateLimitRecord,
+        cc_vote_record::CCVoteRecord,
+        enums::{BcTokenState, ShihonAccountType, TanistryState, VoteSource},
+        kicker_coin_owner_record::KickerCoinOwnerRecord,
+        ring::Ring,
+        tanistry::Tanistry,
+    },
+};
+use shihon_client::shihon::{Shihon, ShihonClientError};
+use solana_program_test::{
+    processor,
+    tokio::{self, sync::Mutex},
+    ProgramTest, ProgramTestContext,
+};
+use solana_sdk::{
+    account::Account,
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    rent::Rent,
+    signer::{keypair::Keypair, Signer},
+    system_program, sysvar,
+    transaction::TransactionError,
+    transport::TransportError,
+};
+use spl_token_client::client::{
+    ProgramBanksClient, ProgramBanksClientProcessTransaction, ProgramClient,
+};
+use std::sync::Arc;
+
+/// Extra space given to the preloaded accounts for the Options set by the instructions
+const ACCOUNT_PADDING: usize = 128;
+
+const DEPOSIT_AMOUNT: u64 = 50;
+const RECEIVED_RATING: u64 = 20;
+const VOTER_WEIGHT: u64 = 50;
+
+struct TestContext {
+    pub context: Arc<Mutex<ProgramTestContext>>,
+    pub shihon: Shihon<ProgramBanksClientProcessTransaction, Keypair>,
+}
+
+impl TestContext {
+    async fn new() -> Self {
+        Self::new_with_accounts(&Pubkey::new_unique(), vec![]).await
+    }
+
+    /// Starts the program with the given accounts already in place
+    /// The accounts are preloaded because the program can't create accounts through CPIs when it runs as native code
+    /// The native processor doesn't keep the data the program writes either so the tests checking written state need test-bpf
+    async fn new_with_accounts(program_id: &Pubkey, accounts: Vec<(Pubkey, Account)>) -> Self {
+        let mut program_test =
+            ProgramTest::new("shihon", *program_id, processor!(process_instruction));
+
+        for (address, account) in accounts {
+            program_test.add_account(address, account);
+        }
+
+        let ctx = program_test.start_with_context().await;
+        let ctx = Arc::new(Mutex::new(ctx));
+
+        let payer = keypair_clone(&ctx.lock().await.payer);
+
+        let client: Arc<dyn ProgramClient<ProgramBanksClientProcessTransaction>> =
+            Arc::new(ProgramBanksClient::new_from_context(
+                Arc::clone(&ctx),
+                ProgramBanksClientProcessTransaction,
+            ));
+
+        Self {
+            context: ctx,
+            shihon: Shihon::new(client, program_id, payer),
+        }
+    }
+}
+
+fn keypair_clone(kp: &Keypair) -> Keypair {
+    Keypair::from_bytes(&kp.to_bytes()).expect("failed to copy keypair")
+}
+
+/// Rent exempt account owned by the program holding the given state
+fn program_account<T: BorshSerialize>(program_id: &Pubkey, data: &T) -> Account {
+    let mut data = data.try_to_vec().unwrap();
+    data.resize(data.len() + ACCOUNT_PADDING, 0);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: *program_id,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// The error returned by the client when the first instruction of the transaction fails with the given error
+fn shihon_client_error(error: ShihonError) -> ShihonClientError {
+    ShihonClientError::Client(Box::new(TransportError::TransactionError(
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32)),
+    )))
+}
+
+fn create_test_bc_token(authority: &Pubkey) -> BcToken {
+    BcToken {
+        account_type: ShihonAccountType::BcTokenV3,
+        bump: 255,
+        bc_token_owner_pubkey: *authority,
+        amount_of_coin: 10,
+        issue_at: 0,
+        reserved: [0; 8],
+        authority: Some(*authority),
+        delegate: None,
+        pending_authority: None,
+        name: "Coke and Mentos".to_string(),
+        bc_token_mint: Pubkey::new_unique(),
+        bc_token_state: BcTokenState::HoldingOnPrivate,
+        config: BcTokenMetadata {
+            account_type: ShihonAccountType::BcTokenMetadataV3,
+            bump: 255,
+            content_type: ContentType::Ordinary,
+            updated_at: 0,
+            version: "1".to_string(),
+            reserved: [0; 64],
+            is_writable: false,
+            is_signer: false,
+            content_length: 1_000,
+            content_duration: 60,
+            lineage: None,
+        },
+    }
+}
+
+fn create_test_candidate_limit_record(
+    tanistry: &Pubkey,
+    candidate_token_owner: &Pubkey,
+) -> CandidateLimitRecord {
+    CandidateLimitRecord {
+        account_type: ShihonAccountType::CandidateLimitRecordV2,
+        bump: 255,
+        belonging_tanistry: *tanistry,
+        number_of_candidate_count: 1,
+        candidate_token_mint: Pubkey::new_unique(),
+        candidate_token_owner: *candidate_token_owner,
+        candidate_delegate: None,
+        candidate_token_deposit_amount: DEPOSIT_AMOUNT,
+        received_rating: RECEIVED_RATING,
+        refund_amount: 0,
+        is_refunded: false,
+        candidate_fee_amount: 0,
+        rate_options_count: 0,
+        bc_token: Pubkey::new_unique(),
+    }
+}
+
+fn create_test_ring(first_tanistry: &Pubkey, cc_vote_weight: u64) -> Ring {
+    Ring {
+        account_type: ShihonAccountType::Ring,
+        bump: 255,
+        first_tanistry: *first_tanistry,
+        last_crowned_tanistry: None,
+        crowned_tanistry_count: 0,
+        cumulative_deposit_amount: 0,
+        cumulative_rft_volume: 0,
+        cc_vote_weight,
+        cc_vote_counting_time: None,
+        reserved: [0; 64],
+    }
+}
+
+fn create_test_tanistry(
+    kicker_coin_owner_record: &Pubkey,
+    ring: &Pubkey,
+    candidate_limit_records: Vec<Pubkey>,
+) -> Tanistry {
+    Tanistry {
+        account_type: ShihonAccountType::TanistryV2,
+        bump: 255,
+        next_tanistry_id: None,
+        previous_tanistry_id: None,
+        ring: *ring,
+        reserved: [0; 8],
+        kicker_coin_owner_record: *kicker_coin_owner_record,
+        state: TanistryState::Active,
+        tanistry_token_mint: Pubkey::new_unique(),
+        crown: None,
+        outside_buyer_deposit_amount: 0,
+        settled_amount: 0,
+        refunded_amount: 0,
+        kicker_refund_amount: 0,
+        coordinator_refund_amount: 0,
+        is_kicker_refunded: false,
+        is_coordinator_refunded: false,
+        coordinator_refund_receipt_mint: None,
+        enrolling_at: 0,
+        candidate_limit_record_list: candidate_limit_records,
+    }
+}
+
+/// The accounts of an Active Tanistry with two candidates which is the first link of its Ring
+struct TanistryFixture {
+    coordinator: Keypair,
+    kicker_coin_owner_record: Pubkey,
+    tanistry: Pubkey,
+    ring: Pubkey,
+    candidate_limit_records: Vec<Pubkey>,
+}
+
+impl TanistryFixture {
+    fn new(program_id: &Pubkey) -> Self {
+        let kicker_coin_owner_record = Pubkey::new_unique();
+
+        Self {
+            coordinator: Keypair::new(),
+            kicker_coin_owner_record,
+            tanistry: get_tanistry_address(program_id, &kicker_coin_owner_record),
+            ring: Pubkey::new_unique(),
+            candidate_limit_records: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        }
+    }
+
+    fn accounts(&self, program_id: &Pubkey) -> Vec<(Pubkey, Account)> {
+        let kicker_coin_owner_record_data = KickerCoinOwnerRecord {
+            account_type: ShihonAccountType::KickerCoinOwnerRecordV2,
+            bump: 255,
+            kicker_coin_holder: Pubkey::new_unique(),
+            latest_coordinator: self.coordinator.pubkey(),
+            amount_of_kicker_coin: 100,
+            round: 1,
+            building_hash_key: vec![],
+            is_kick_off: true,
+            kicker_coin_fee_amount: 0,
+            kicker_pinhole: None,
+            coordinator_pinhole: None,
+            kicker_bc_token: Pubkey::new_unique(),
+        };
+
+        let tanistry_data = create_test_tanistry(
+            &self.kicker_coin_owner_record,
+            &self.ring,
+            self.candidate_limit_records.clone(),
+        );
+
+        let mut accounts = vec![
+            (
+                self.kicker_coin_owner_record,
+                program_account(program_id, &kicker_coin_owner_record_data),
+            ),
+            (self.tanistry, program_account(program_id, &tanistry_data)),
+            (
+                self.ring,
+                program_account(program_id, &create_test_ring(&self.tanistry, 0)),
+            ),
+        ];
+
+        accounts.extend(
+            self.candidate_limit_records
+                .iter()
+                .map(|candidate_limit_record| {
+                    (
+                        *candidate_limit_record,
+                        program_account(
+                            program_id,
+                            &create_test_candidate_limit_record(
+                                &self.tanistry,
+                                &Pubkey::new_unique(),
+                            ),
+                        ),
+                    )
+                }),
+        );
+
+        accounts
+    }
+}
+
+/// The accounts of a CC vote cast for the first of two Rings
+/// The voter's Ring is empty so both target Rings are pushed
+struct CCVoteFixture {
+    voter: Keypair,
+    candidate_limit_record: Pubkey,
+    cc_vote_record: Pubkey,
+    voter_tanistry: Pubkey,
+    voter_ring: Pubkey,
+    target_ring: Pubkey,
+    new_target_ring: Pubkey,
+}
+
+impl CCVoteFixture {
+    fn new(program_id: &Pubkey) -> Self {
+        let candidate_limit_record = Pubkey::new_unique();
+
+        Self {
+            voter: Keypair::new(),
+            candidate_limit_record,
+            cc_vote_record: get_cc_vote_record_address(program_id, &candidate_limit_record),
+            voter_tanistry: Pubkey::new_unique(),
+            voter_ring: Pubkey::new_unique(),
+            target_ring: Pubkey::new_unique(),
+            new_target_ring: Pubkey::new_unique(),
+        }
+    }
+
+    fn accounts(&self, program_id: &Pubkey) -> Vec<(Pubkey, Account)> {
+        let cc_vote_record_data = CCVoteRecord {
+            account_type: ShihonAccountType::CCVoteRecordV2,
+            bump: find_bump_seed(
+                program_id,
+                &get_cc_vote_record_address_seeds(&self.candidate_limit_record),
+            ),
+            target_ring: self.target_ring,
+            voter: self.voter.pubkey(),
+            is_relinquished: false,
+            voter_weight: VOTER_WEIGHT,
+            vote: VoteSource::Push,
+        };
+
+        vec![
+            (
+                self.candidate_limit_record,
+                program_account(
+                    program_id,
+                    &create_test_candidate_limit_record(&self.voter_tanistry, &self.voter.pubkey()),
+                ),
+            ),
+            (
+                self.voter_tanistry,
+                program_account(
+                    program_id,
+                    &create_test_tanistry(&Pubkey::new_unique(), &self.voter_ring, vec![]),
+                ),
+            ),
+            (
+                self.voter_ring,
+                program_account(program_id, &create_test_ring(&self.voter_tanistry, 0)),
+            ),
+            (
+                self.cc_vote_record,
+                program_account(program_id, &cc_vote_record_data),
+            ),
+            (
+                self.target_ring,
+                program_account(
+                    program_id,
+                    &create_test_ring(&Pubkey::new_unique(), VOTER_WEIGHT),
+                ),
+            ),
+            (
+                self.new_target_ring,
+                program_account(
+                    program_id,
+                    &Ring {
+                        cumulative_deposit_amount: DEPOSIT_AMOUNT,
+                        ..create_test_ring(&Pubkey::new_unique(), 0)
+                    },
+                ),
+            ),
+        ]
+    }
+}
+
+#[tokio::test]
+async fn get_tanistry_not_found() {
+    let TestContext { shihon, .. } = TestContext::new().await;
+
+    let error = shihon
+        .get_tanistry(&Pubkey::new_unique())
+        .await
+        .unwrap_err();
+
+    assert_eq!(error, ShihonClientError::AccountNotFound);
+}
+
+#[tokio::test]
+async fn get_tanistry_invalid_owner() {
+    let TestContext { shihon, .. } = TestContext::new().await;
+
+    // The Rent sysvar isn't owned by the Shihon program
+    let error = shihon.get_tanistry(&sysvar::rent::id()).await.unwrap_err();
+
+    assert_eq!(error, ShihonClientError::AccountInvalidOwner);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn set_bc_token_delegate() {
+    let program_id = Pubkey::new_unique();
+    let bc_token_authority = Keypair::new();
+    let bc_token = Pubkey::new_unique();
+
+    let TestContext { shihon, .. } = TestContext::new_with_accounts(
+        &program_id,
+        vec![(
+            bc_token,
+            program_account(
+                &program_id,
+                &create_test_bc_token(&bc_token_authority.pubkey()),
+            ),
+        )],
+    )
+    .await;
+
+    let delegate = Pubkey::new_unique();
+
+    shihon
+        .set_bc_token_delegate(&bc_token, &bc_token_authority, Some(delegate))
+        .await
+        .unwrap();
+
+    let bc_token_data = shihon.get_bc_token(&bc_token).await.unwrap();
+    assert_eq!(bc_token_data.delegate, Some(delegate));
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn set_bc_token_authority_with_acceptance() {
+    let program_id = Pubkey::new_unique();
+    let bc_token_authority = Keypair::new();
+    let bc_token = Pubkey::new_unique();
+
+    let TestContext { shihon, .. } = TestContext::new_with_accounts(
+        &program_id,
+        vec![(
+            bc_token,
+            program_account(
+                &program_id,
+                &create_test_bc_token(&bc_token_authority.pubkey()),
+            ),
+        )],
+    )
+    .await;
+
+    let new_authority = Keypair::new();
+
+    shihon
+        .set_bc_token_authority(
+            &bc_token,
+            &bc_token_authority,
+            Some(new_authority.pubkey()),
+            true,
+        )
+        .await
+        .unwrap();
+
+    // The authority doesn't change until the pending authority accepts it
+    let bc_token_data = shihon.get_bc_token(&bc_token).await.unwrap();
+    assert_eq!(bc_token_data.authority, Some(bc_token_authority.pubkey()));
+    assert_eq!(
+        bc_token_data.pending_authority,
+        Some(new_authority.pubkey())
+    );
+
+    shihon
+        .accept_bc_token_authority(&bc_token, &new_authority)
+        .await
+        .unwrap();
+
+    let bc_token_data = shihon.get_bc_token(&bc_token).await.unwrap();
+    assert_eq!(bc_token_data.authority, Some(new_authority.pubkey()));
+    assert_eq!(bc_token_data.pending_authority, None);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn renounce_bc_token_authority() {
+    let program_id = Pubkey::new_unique();
+    let bc_token_authority = Keypair::new();
+    let bc_token = Pubkey::new_unique();
+
+    let TestContext { shihon, .. } = TestContext::new_with_accounts(
+        &program_id,
+        vec![(
+            bc_token,
+            program_account(
+                &program_id,
+                &create_test_bc_token(&bc_token_authority.pubkey()),
+            ),
+        )],
+    )
+    .await;
+
+    shihon
+        .renounce_bc_token_authority(&bc_token, &bc_token_authority)
+        .await
+        .unwrap();
+
+    let bc_token_data = shihon.get_bc_token(&bc_token).await.unwrap();
+    assert_eq!(bc_token_data.authority, None);
+}
+
+#[tokio::test]
+async fn set_bc_token_authority_with_other_authority_error() {
+    let program_id = Pubkey::new_unique();
+    let bc_token = Pubkey::new_unique();
+
+    let TestContext { shihon, .. } = TestContext::new_with_accounts(
+        &program_id,
+        vec![(
+            bc_token,
+            program_account(&program_id, &create_test_bc_token(&Pubkey::new_unique())),
+        )],
+    )
+    .await;
+
+    let error = shihon
+        .set_bc_token_authority(&bc_token, &Keypair::new(), None, false)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        shihon_client_error(ShihonError::InvalidAuthorityForBcToken)
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn set_candidate_delegate() {
+    let program_id = Pubkey::new_unique();
+    let candidate_token_owner = Keypair::new();
+    let candidate_limit_record = Pubkey::new_unique();
+
+    let TestContext { shihon, .. } = TestContext::new_with_accounts(
+        &program_id,
+        vec![(
+            candidate_limit_record,
+            program_account(
+                &program_id,
+                &create_test_candidate_limit_record(
+                    &Pubkey::new_unique(),
+                    &candidate_token_owner.pubkey(),
+                ),
+            ),
+        )],
+    )
+    .await;
+
+    let candidate_delegate = Pubkey::new_unique();
+
+    shihon
+        .set_candidate_delegate(
+            &candidate_limit_record,
+            &candidate_token_owner,
+            Some(candidate_delegate),
+        )
+        .await
+        .unwrap();
+
+    let candidate_limit_record_data = shihon
+        .get_candidate_limit_record(&candidate_limit_record)
+        .await
+        .unwrap();
+    assert_eq!(
+        candidate_limit_record_data.candidate_delegate,
+        Some(candidate_delegate)
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn crown() {
+    let program_id = Pubkey::new_unique();
+    let fixture = TanistryFixture::new(&program_id);
+
+    let TestContext { shihon, .. } =
+        TestContext::new_with_accounts(&program_id, fixture.accounts(&program_id)).await;
+
+    let crown = fixture.candidate_limit_records[0];
+
+    shihon
+        .crown(
+            &fixture.coordinator,
+            &fixture.kicker_coin_owner_record,
+            &fixture.ring,
+            &crown,
+        )
+        .await
+        .unwrap();
+
+    let tanistry_data = shihon.get_tanistry(&fixture.tanistry).await.unwrap();
+    assert_eq!(tanistry_data.state, TanistryState::Crowned);
+    assert_eq!(tanistry_data.crown, Some(crown));
+
+    let ring_data = shihon.get_ring(&fixture.ring).await.unwrap();
+    assert_eq!(ring_data.last_crowned_tanistry, Some(fixture.tanistry));
+    assert_eq!(ring_data.cumulative_deposit_amount, 2 * DEPOSIT_AMOUNT);
+    assert_eq!(ring_data.cumulative_rft_volume, 2 * RECEIVED_RATING);
+}
+
+#[tokio::test]
+async fn crown_with_other_coordinator_error() {
+    let program_id = Pubkey::new_unique();
+    let fixture = TanistryFixture::new(&program_id);
+
+    let TestContext { shihon, .. } =
+        TestContext::new_with_accounts(&program_id, fixture.accounts(&program_id)).await;
+
+    let error = shihon
+        .crown(
+            &Keypair::new(),
+            &fixture.kicker_coin_owner_record,
+            &fixture.ring,
+            &fixture.candidate_limit_records[0],
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(error, shihon_client_error(ShihonError::CoordinatorMustSign));
+
+    let tanistry_data = shihon.get_tanistry(&fixture.tanistry).await.unwrap();
+    assert_eq!(tanistry_data.state, TanistryState::Active);
+}
+
+#[tokio::test]
+async fn crown_with_missing_tanistry_error() {
+    let TestContext { shihon, .. } = TestContext::new().await;
+
+    let error = shihon
+        .crown(
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(error, ShihonClientError::AccountNotFound);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn change_vote() {
+    let program_id = Pubkey::new_unique();
+    let fixture = CCVoteFixture::new(&program_id);
+
+    let TestContext { shihon, .. } =
+        TestContext::new_with_accounts(&program_id, fixture.accounts(&program_id)).await;
+
+    let cc_vote_record = shihon
+        .change_vote(
+            &fixture.target_ring,
+            &fixture.candidate_limit_record,
+            &fixture.voter,
+            &fixture.new_target_ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(cc_vote_record, fixture.cc_vote_record);
+
+    let cc_vote_record_data = shihon.get_cc_vote_record(&cc_vote_record).await.unwrap();
+    assert_eq!(cc_vote_record_data.target_ring, fixture.new_target_ring);
+
+    let target_ring_data = shihon.get_ring(&fixture.target_ring).await.unwrap();
+    assert_eq!(target_ring_data.cc_vote_weight, 0);
+
+    let new_target_ring_data = shihon.get_ring(&fixture.new_target_ring).await.unwrap();
+    assert_eq!(new_target_ring_data.cc_vote_weight, VOTER_WEIGHT);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn relinquish_vote() {
+    let program_id = Pubkey::new_unique();
+    let fixture = CCVoteFixture::new(&program_id);
+
+    let TestContext { shihon, .. } =
+        TestContext::new_with_accounts(&program_id, fixture.accounts(&program_id)).await;
+
+    shihon
+        .relinquish_vote(
+            &fixture.target_ring,
+            &fixture.candidate_limit_record,
+            &fixture.voter,
+        )
+        .await
+        .unwrap();
+
+    let cc_vote_record_data = shihon
+        .get_cc_vote_record(&fixture.cc_vote_record)
+        .await
+        .unwrap();
+    assert!(cc_vote_record_data.is_relinquished);
+
+    let target_ring_data = shihon.get_ring(&fixture.target_ring).await.unwrap();
+    assert_eq!(target_ring_data.cc_vote_weight, 0);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn relinquish_vote_twice_error() {
+    let program_id = Pubkey::new_unique();
+    let fixture = CCVoteFixture::new(&program_id);
+
+    let TestContext { context, shihon } =
+        TestContext::new_with_accounts(&program_id, fixture.accounts(&program_id)).await;
+
+    shihon
+        .relinquish_vote(
+            &fixture.target_ring,
+            &fixture.candidate_limit_record,
+            &fixture.voter,
+        )
+        .await
+        .unwrap();
+
+    // Move to the next slot so the same transaction isn't deduplicated
+    context.lock().await.warp_to_slot(10).unwrap();
+
+    let error = shihon
+        .relinquish_vote(
+            &fixture.target_ring,
+            &fixture.candidate_limit_record,
+            &fixture.voter,
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        shihon_client_error(ShihonError::CCVoteAlreadyRelinquished)
+    );
+}
+
+#[tokio::test]
+async fn change_vote_to_same_ring_error() {
+    let program_id = Pubkey::new_unique();
+    let fixture = CCVoteFixture::new(&program_id);
+
+    let TestContext { shihon, .. } =
+        TestContext::new_with_accounts(&program_id, fixture.accounts(&program_id)).await;
+
+    let error = shihon
+        .change_vote(
+            &fixture.target_ring,
+            &fixture.candidate_limit_record,
+            &fixture.voter,
+            &fixture.target_ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        shihon_client_error(ShihonError::CCVoteTargetRingNotChanged)
+    );
+}
+
+#[tokio::test]
+async fn relinquish_vote_with_other_voter_error() {
+    let program_id = Pubkey::new_unique();
+    let fixture = CCVoteFixture::new(&program_id);
+
+    let TestContext { shihon, .. } =
+        TestContext::new_with_accounts(&program_id, fixture.accounts(&program_id)).await;
+
+    let error = shihon
+        .relinquish_vote(
+            &fixture.target_ring,
+            &fixture.candidate_limit_record,
+            &Keypair::new(),
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        shihon_client_error(ShihonError::CandidateTokenOwnerOrDelegateMustSign)
+    );
+}
+
+#[tokio::test]
+async fn with_payer() {
+    let program_id = Pubkey::new_unique();
+    let bc_token_authority = Keypair::new();
+    let bc_token = Pubkey::new_unique();
+    let payer = Keypair::new();
+    let payer_lamports = 1_000_000_000;
+
+    let TestContext { context, shihon } = TestContext::new_with_accounts(
+        &program_id,
+        vec![
+            (
+                bc_token,
+                program_account(
+                    &program_id,
+                    &create_test_bc_token(&bc_token_authority.pubkey()),
+                ),
+            ),
+            (
+                payer.pubkey(),
+                Account::new(payer_lamports, 0, &system_program::id()),
+            ),
+        ],
+    )
+    .await;
+
+    let shihon = shihon.with_payer(keypair_clone(&payer));
+    assert_eq!(shihon.get_program_id(), &program_id);
+
+    shihon
+        .set_bc_token_delegate(&bc_token, &bc_token_authority, Some(Pubkey::new_unique()))
+        .await
+        .unwrap();
+
+    // The transaction fee is paid by the new payer
+    let payer_account = context
+        .lock()
+        .await
+        .banks_client
+        .get_account(payer.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(payer_account.lamports < payer_lamports);
+}
@@ -0,0 +1,1193 @@
+//! Decoders for Shihon accounts
+//! Raw account data is dispatched on the leading ShihonAccountType tag into a typed ShihonAccount
+//! The Ui* representations carry serde serialization and human-readable Display for clients and explorers
+
+use std::fmt::{self, Display};
+
+use serde_derive::{Deserialize, Serialize};
+use solana_program::{
+    borsh::try_from_slice_unchecked, program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::state::{
+    bc_token::BcToken,
+    bc_token_metadata::{BcTokenMetadata, ContentLineage, ContentRange},
+    candidate_limit_record::CandidateLimitRecord,
+    cc_vote_record::CCVoteRecord,
+    enums::ShihonAccountType,
+    kicker_coin_owner_record::KickerCoinOwnerRecord,
+    legacy::{
+        BcTokenMetadataV1, BcTokenMetadataV2, BcTokenV1, BcTokenV2, CCVoteRecordV1,
+        CandidateLimitRecordV1, KickerCoinOwnerRecordV1, MixContentRecordV1, OutsideBuyerRecordV1,
+        OutsideBuyerRecordV2, RateOptionV1, RateOtherRecordV1, TanistryV1,
+    },
+    mix_content_record::MixContentRecord,
+    outside_buyer_record::OutsideBuyerRecord,
+    pinhole::Pinhole,
+    rate_option::RateOption,
+    rate_other_record::RateOtherRecord,
+    ring::Ring,
+    shihon_config::ShihonConfig,
+    tanistry::Tanistry,
+    triple_multisig::TripleMultisig,
+};
+
+/// Decoded Shihon account
+/// V1 and V2 accounts are translated into the current layout and keep their account_type
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum ShihonAccount {
+    /// bcToken account (V1, V2 or V3)
+    BcToken(BcToken),
+
+    /// bcToken Metadata account (V1, V2 or V3)
+    BcTokenMetadata(BcTokenMetadata),
+
+    /// KickerCoin Owner Record account
+    KickerCoinOwnerRecord(KickerCoinOwnerRecord),
+
+    /// Tanistry account
+    Tanistry(Tanistry),
+
+    /// Candidate Limit Record account
+    CandidateLimitRecord(CandidateLimitRecord),
+
+    /// Mix Content Record account
+    MixContentRecord(MixContentRecord),
+
+    /// Rate Option account
+    RateOption(RateOption),
+
+    /// Rate Other Record account
+    RateOtherRecord(RateOtherRecord),
+
+    /// Outside Buyer Record account (V1, V2 or V3)
+    OutsideBuyerRecord(OutsideBuyerRecord),
+
+    /// CC Vote Record account
+    CCVoteRecord(CCVoteRecord),
+
+    /// Shihon Config account
+    ShihonConfig(ShihonConfig),
+
+    /// Ring account
+    Ring(Ring),
+
+    /// Pinhole account
+    Pinhole(Pinhole),
+
+    /// Triple Multisig account
+    TripleMultisig(TripleMultisig),
+}
+
+/// Decodes raw Shihon account data by its leading ShihonAccountType tag
+pub fn decode_account(data: &[u8]) -> Result<ShihonAccount, ProgramError> {
+    let account_type: ShihonAccountType = try_from_slice_unchecked(data)?;
+
+    let account = match account_type {
+        ShihonAccountType::Uninitialized => return Err(ProgramError::UninitializedAccount),
+        ShihonAccountType::BcTokenV1 => {
+            ShihonAccount::BcToken(try_from_slice_unchecked::<BcTokenV1>(data)?.into())
+        }
+        ShihonAccountType::BcTokenV2 => {
+            ShihonAccount::BcToken(try_from_slice_unchecked::<BcTokenV2>(data)?.into())
+        }
+        ShihonAccountType::BcTokenV3 => {
+            ShihonAccount::BcToken(try_from_slice_unchecked::<BcToken>(data)?)
+        }
+        ShihonAccountType::BcTokenMetadataV1 => ShihonAccount::BcTokenMetadata(
+            try_from_slice_unchecked::<BcTokenMetadataV1>(data)?.into(),
+        ),
+        ShihonAccountType::BcTokenMetadataV2 => ShihonAccount::BcTokenMetadata(
+            try_from_slice_unchecked::<BcTokenMetadataV2>(data)?.into(),
+        ),
+        ShihonAccountType::BcTokenMetadataV3 => {
+            ShihonAccount::BcTokenMetadata(try_from_slice_unchecked::<BcTokenMetadata>(data)?)
+        }
+        ShihonAccountType::KickerCoinOwnerRecordV1 => ShihonAccount::KickerCoinOwnerRecord(
+            try_from_slice_unchecked::<KickerCoinOwnerRecordV1>(data)?.into(),
+        ),
+        ShihonAccountType::KickerCoinOwnerRecordV2 => ShihonAccount::KickerCoinOwnerRecord(
+            try_from_slice_unchecked::<KickerCoinOwnerRecord>(data)?,
+        ),
+        ShihonAccountType::TanistryV1 => {
+            ShihonAccount::Tanistry(try_from_slice_unchecked::<TanistryV1>(data)?.into())
+        }
+        ShihonAccountType::TanistryV2 => {
+            ShihonAccount::Tanistry(try_from_slice_unchecked::<Tanistry>(data)?)
+        }
+        ShihonAccountType::CandidateLimitRecordV1 => ShihonAccount::CandidateLimitRecord(
+            try_from_slice_unchecked::<CandidateLimitRecordV1>(data)?.into(),
+        ),
+        ShihonAccountType::CandidateLimitRecordV2 => ShihonAccount::CandidateLimitRecord(
+            try_from_slice_unchecked::<CandidateLimitRecord>(data)?,
+        ),
+        ShihonAccountType::MixContentRecordV1 => ShihonAccount::MixContentRecord(
+            try_from_slice_unchecked::<MixContentRecordV1>(data)?.into(),
+        ),
+        ShihonAccountType::MixContentRecordV2 => {
+            ShihonAccount::MixContentRecord(try_from_slice_unchecked::<MixContentRecord>(data)?)
+        }
+        ShihonAccountType::RateOptionV1 => {
+            ShihonAccount::RateOption(try_from_slice_unchecked::<RateOptionV1>(data)?.into())
+        }
+        ShihonAccountType::RateOptionV2 => {
+            ShihonAccount::RateOption(try_from_slice_unchecked::<RateOption>(data)?)
+        }
+        ShihonAccountType::RateOtherRecordV1 => ShihonAccount::RateOtherRecord(
+            try_from_slice_unchecked::<RateOtherRecordV1>(data)?.into(),
+        ),
+        ShihonAccountType::RateOtherRecordV2 => {
+            ShihonAccount::RateOtherRecord(try_from_slice_unchecked::<RateOtherRecord>(data)?)
+        }
+        ShihonAccountType::OutsideBuyerRecordV1 => ShihonAccount::OutsideBuyerRecord(
+            try_from_slice_unchecked::<OutsideBuyerRecordV1>(data)?.into(),
+        ),
+        ShihonAccountType::OutsideBuyerRecordV2 => ShihonAccount::OutsideBuyerRecord(
+            try_from_slice_unchecked::<OutsideBuyerRecordV2>(data)?.into(),
+        ),
+        ShihonAccountType::OutsideBuyerRecordV3 => {
+            ShihonAccount::OutsideBuyerRecord(try_from_slice_unchecked::<OutsideBuyerRecord>(data)?)
+        }
+        ShihonAccountType::CCVoteRecordV1 => {
+            ShihonAccount::CCVoteRecord(try_from_slice_unchecked::<CCVoteRecordV1>(data)?.into())
+        }
+        ShihonAccountType::CCVoteRecordV2 => {
+            ShihonAccount::CCVoteRecord(try_from_slice_unchecked::<CCVoteRecord>(data)?)
+        }
+        ShihonAccountType::ShihonConfig => {
+            ShihonAccount::ShihonConfig(try_from_slice_unchecked::<ShihonConfig>(data)?)
+        }
+        ShihonAccountType::Ring => ShihonAccount::Ring(try_from_slice_unchecked::<Ring>(data)?),
+        ShihonAccountType::Pinhole => {
+            ShihonAccount::Pinhole(try_from_slice_unchecked::<Pinhole>(data)?)
+        }
+        ShihonAccountType::TripleMultisig => {
+            ShihonAccount::TripleMultisig(try_from_slice_unchecked::<TripleMultisig>(data)?)
+        }
+    };
+
+    Ok(account)
+}
+
+impl ShihonAccount {
+    /// Returns the account type stored in the decoded account
+    pub fn account_type(&self) -> ShihonAccountType {
+        match self {
+            ShihonAccount::BcToken(account) => account.account_type.clone(),
+            ShihonAccount::BcTokenMetadata(account) => account.account_type.clone(),
+            ShihonAccount::KickerCoinOwnerRecord(account) => account.account_type.clone(),
+            ShihonAccount::Tanistry(account) => account.account_type.clone(),
+            ShihonAccount::CandidateLimitRecord(account) => account.account_type.clone(),
+            ShihonAccount::MixContentRecord(account) => account.account_type.clone(),
+            ShihonAccount::RateOption(account) => account.account_type.clone(),
+            ShihonAccount::RateOtherRecord(account) => account.account_type.clone(),
+            ShihonAccount::OutsideBuyerRecord(account) => account.account_type.clone(),
+            ShihonAccount::CCVoteRecord(account) => account.account_type.clone(),
+            ShihonAccount::ShihonConfig(account) => account.account_type.clone(),
+            ShihonAccount::Ring(account) => account.account_type.clone(),
+            ShihonAccount::Pinhole(account) => account.account_type.clone(),
+            ShihonAccount::TripleMultisig(account) => account.account_type.clone(),
+        }
+    }
+}
+
+impl serde::Serialize for ShihonAccount {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&UiShihonAccount::from(self), serializer)
+    }
+}
+
+impl Display for ShihonAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&UiShihonAccount::from(self), f)
+    }
+}
+
+/// Human-readable Shihon account
+/// Serialized as {"type": ..., "info": ...} like the parsed accounts of the RPC
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type", content = "info")]
+pub enum UiShihonAccount {
+    /// bcToken account
+    BcToken(UiBcToken),
+
+    /// bcToken Metadata account
+    BcTokenMetadata(UiBcTokenMetadata),
+
+    /// KickerCoin Owner Record account
+    KickerCoinOwnerRecord(UiKickerCoinOwnerRecord),
+
+    /// Tanistry account
+    Tanistry(UiTanistry),
+
+    /// Candidate Limit Record account
+    CandidateLimitRecord(UiCandidateLimitRecord),
+
+    /// Mix Content Record account
+    MixContentRecord(UiMixContentRecord),
+
+    /// Rate Option account
+    RateOption(UiRateOption),
+
+    /// Rate Other Record account
+    RateOtherRecord(UiRateOtherRecord),
+
+    /// Outside Buyer Record account
+    OutsideBuyerRecord(UiOutsideBuyerRecord),
+
+    /// CC Vote Record account
+    #[serde(rename = "ccVoteRecord")]
+    CCVoteRecord(UiCCVoteRecord),
+
+    /// Shihon Config account
+    ShihonConfig(UiShihonConfig),
+
+    /// Ring account
+    Ring(UiRing),
+
+    /// Pinhole account
+    Pinhole(UiPinhole),
+
+    /// Triple Multisig account
+    TripleMultisig(UiTripleMultisig),
+}
+
+impl From<&ShihonAccount> for UiShihonAccount {
+    fn from(account: &ShihonAccount) -> Self {
+        match account {
+            ShihonAccount::BcToken(account) => UiShihonAccount::BcToken(account.into()),
+            ShihonAccount::BcTokenMetadata(account) => {
+                UiShihonAccount::BcTokenMetadata(account.into())
+            }
+            ShihonAccount::KickerCoinOwnerRecord(account) => {
+                UiShihonAccount::KickerCoinOwnerRecord(account.into())
+            }
+            ShihonAccount::Tanistry(account) => UiShihonAccount::Tanistry(account.into()),
+            ShihonAccount::CandidateLimitRecord(account) => {
+                UiShihonAccount::CandidateLimitRecord(account.into())
+            }
+            ShihonAccount::MixContentRecord(account) => {
+                UiShihonAccount::MixContentRecord(account.into())
+            }
+            ShihonAccount::RateOption(account) => UiShihonAccount::RateOption(account.into()),
+            ShihonAccount::RateOtherRecord(account) => {
+                UiShihonAccount::RateOtherRecord(account.into())
+            }
+            ShihonAccount::OutsideBuyerRecord(account) => {
+                UiShihonAccount::OutsideBuyerRecord(account.into())
+            }
+            ShihonAccount::CCVoteRecord(account) => UiShihonAccount::CCVoteRecord(account.into()),
+            ShihonAccount::ShihonConfig(account) => UiShihonAccount::ShihonConfig(account.into()),
+            ShihonAccount::Ring(account) => UiShihonAccount::Ring(account.into()),
+            ShihonAccount::Pinhole(account) => UiShihonAccount::Pinhole(account.into()),
+            ShihonAccount::TripleMultisig(account) => {
+                UiShihonAccount::TripleMultisig(account.into())
+            }
+        }
+    }
+}
+
+impl Display for UiShihonAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UiShihonAccount::BcToken(account) => Display::fmt(account, f),
+            UiShihonAccount::BcTokenMetadata(account) => Display::fmt(account, f),
+            UiShihonAccount::KickerCoinOwnerRecord(account) => Display::fmt(account, f),
+            UiShihonAccount::Tanistry(account) => Display::fmt(account, f),
+            UiShihonAccount::CandidateLimitRecord(account) => Display::fmt(account, f),
+            UiShihonAccount::MixContentRecord(account) => Display::fmt(account, f),
+            UiShihonAccount::RateOption(account) => Display::fmt(account, f),
+            UiShihonAccount::RateOtherRecord(account) => Display::fmt(account, f),
+            UiShihonAccount::OutsideBuyerRecord(account) => Display::fmt(account, f),
+            UiShihonAccount::CCVoteRecord(account) => Display::fmt(account, f),
+            UiShihonAccount::ShihonConfig(account) => Display::fmt(account, f),
+            UiShihonAccount::Ring(account) => Display::fmt(account, f),
+            UiShihonAccount::Pinhole(account) => Display::fmt(account, f),
+            UiShihonAccount::TripleMultisig(account) => Display::fmt(account, f),
+        }
+    }
+}
+
+fn option_pubkey_to_string(pubkey: &Option<Pubkey>) -> Option<String> {
+    pubkey.as_ref().map(|pubkey| pubkey.to_string())
+}
+
+fn option_content_range_to_string(range: &Option<ContentRange>) -> Option<String> {
+    range
+        .as_ref()
+        .map(|range| format!("{}..{}", range.start, range.end))
+}
+
+fn writeln_option(f: &mut fmt::Formatter, name: &str, value: &Option<String>) -> fmt::Result {
+    writeln!(f, "{} {}", name, value.as_deref().unwrap_or("(not set)"))
+}
+
+/// Human-readable bcToken Metadata
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiBcTokenMetadata {
+    pub account_type: String,
+    pub content_type: String,
+    pub updated_at: u64,
+    pub version: String,
+    pub is_writable: bool,
+    pub is_signer: bool,
+    pub content_length: u64,
+    pub content_duration: u64,
+    pub lineage: Option<UiContentLineage>,
+}
+
+/// Human-readable provenance of a mixed content
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiContentLineage {
+    pub mix_content_record: String,
+    pub parent_bc_token_a: String,
+    pub parent_bc_token_b: String,
+    pub time_shift_a: u64,
+    pub time_shift_b: u64,
+}
+
+impl From<&ContentLineage> for UiContentLineage {
+    fn from(content_lineage: &ContentLineage) -> Self {
+        Self {
+            mix_content_record: content_lineage.mix_content_record.to_string(),
+            parent_bc_token_a: content_lineage.parent_bc_token_a.to_string(),
+            parent_bc_token_b: content_lineage.parent_bc_token_b.to_string(),
+            time_shift_a: content_lineage.time_shift_a,
+            time_shift_b: content_lineage.time_shift_b,
+        }
+    }
+}
+
+impl From<&BcTokenMetadata> for UiBcTokenMetadata {
+    fn from(bc_token_metadata: &BcTokenMetadata) -> Self {
+        Self {
+            account_type: format!("{:?}", bc_token_metadata.account_type),
+            content_type: format!("{:?}", bc_token_metadata.content_type),
+            updated_at: bc_token_metadata.updated_at,
+            version: bc_token_metadata.version.clone(),
+            is_writable: bc_token_metadata.is_writable,
+            is_signer: bc_token_metadata.is_signer,
+            content_length: bc_token_metadata.content_length,
+            content_duration: bc_token_metadata.content_duration,
+            lineage: bc_token_metadata
+                .lineage
+                .as_ref()
+                .map(UiContentLineage::from),
+        }
+    }
+}
+
+impl fmt::Display for UiBcTokenMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Content type: {}", self.content_type)?;
+        writeln!(f, "Updated at slot: {}", self.updated_at)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Writable: {}", self.is_writable)?;
+        writeln!(f, "Signer: {}", self.is_signer)?;
+        writeln!(f, "Content length: {} bytes", self.content_length)?;
+        writeln!(f, "Content duration: {} seconds", self.content_duration)?;
+
+        match &self.lineage {
+            Some(lineage) => {
+                writeln!(f, "Mixed from: {}", lineage.mix_content_record)?;
+                writeln!(
+                    f,
+                    "Parent bcToken A: {} (time shift {})",
+                    lineage.parent_bc_token_a, lineage.time_shift_a
+                )?;
+                writeln!(
+                    f,
+                    "Parent bcToken B: {} (time shift {})",
+                    lineage.parent_bc_token_b, lineage.time_shift_b
+                )
+            }
+            None => writeln!(f, "Mixed from: (original content)"),
+        }
+    }
+}
+
+/// Human-readable bcToken
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiBcToken {
+    pub account_type: String,
+    pub bc_token_owner: String,
+    pub amount_of_coin: u64,
+    pub issue_at: i64,
+    pub authority: Option<String>,
+    pub delegate: Option<String>,
+    pub pending_authority: Option<String>,
+    pub name: String,
+    pub bc_token_mint: String,
+    pub bc_token_state: String,
+    pub config: UiBcTokenMetadata,
+}
+
+impl From<&BcToken> for UiBcToken {
+    fn from(bc_token: &BcToken) -> Self {
+        Self {
+            account_type: format!("{:?}", bc_token.account_type),
+            bc_token_owner: bc_token.bc_token_owner_pubkey.to_string(),
+            amount_of_coin: bc_token.amount_of_coin,
+            issue_at: bc_token.issue_at,
+            authority: option_pubkey_to_string(&bc_token.authority),
+            delegate: option_pubkey_to_string(&bc_token.delegate),
+            pending_authority: option_pubkey_to_string(&bc_token.pending_authority),
+            name: bc_token.name.clone(),
+            bc_token_mint: bc_token.bc_token_mint.to_string(),
+            bc_token_state: format!("{:?}", bc_token.bc_token_state),
+            config: UiBcTokenMetadata::from(&bc_token.config),
+        }
+    }
+}
+
+impl fmt::Display for UiBcToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Name: {}", self.name)?;
+        writeln!(f, "Owner: {}", self.bc_token_owner)?;
+        writeln_option(f, "Authority:", &self.authority)?;
+        writeln_option(f, "Delegate:", &self.delegate)?;
+        writeln_option(f, "Pending authority:", &self.pending_authority)?;
+        writeln!(f, "Mint: {}", self.bc_token_mint)?;
+        writeln!(f, "Amount of coin: {}", self.amount_of_coin)?;
+        writeln!(f, "Issued at: {}", self.issue_at)?;
+        writeln!(f, "State: {}", self.bc_token_state)?;
+        writeln!(f, "Metadata:")?;
+        Display::fmt(&self.config, f)
+    }
+}
+
+/// Human-readable KickerCoin Owner Record
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiKickerCoinOwnerRecord {
+    pub account_type: String,
+    pub kicker_coin_holder: String,
+    pub latest_coordinator: String,
+    pub amount_of_kicker_coin: u64,
+    pub round: u8,
+    pub is_kick_off: bool,
+    pub kicker_coin_fee_amount: u64,
+    pub kicker_pinhole: Option<String>,
+    pub coordinator_pinhole: Option<String>,
+}
+
+impl From<&KickerCoinOwnerRecord> for UiKickerCoinOwnerRecord {
+    fn from(kicker_coin_owner_record: &KickerCoinOwnerRecord) -> Self {
+        Self {
+            account_type: format!("{:?}", kicker_coin_owner_record.account_type),
+            kicker_coin_holder: kicker_coin_owner_record.kicker_coin_holder.to_string(),
+            latest_coordinator: kicker_coin_owner_record.latest_coordinator.to_string(),
+            amount_of_kicker_coin: kicker_coin_owner_record.amount_of_kicker_coin,
+            round: kicker_coin_owner_record.round,
+            is_kick_off: kicker_coin_owner_record.is_kick_off,
+            kicker_coin_fee_amount: kicker_coin_owner_record.kicker_coin_fee_amount,
+            kicker_pinhole: option_pubkey_to_string(&kicker_coin_owner_record.kicker_pinhole),
+            coordinator_pinhole: option_pubkey_to_string(
+                &kicker_coin_owner_record.coordinator_pinhole,
+            ),
+        }
+    }
+}
+
+impl fmt::Display for UiKickerCoinOwnerRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "KickerCoin holder: {}", self.kicker_coin_holder)?;
+        writeln!(f, "Latest coordinator: {}", self.latest_coordinator)?;
+        writeln!(f, "Amount of KickerCoin: {}", self.amount_of_kicker_coin)?;
+        writeln!(f, "Round: {}", self.round)?;
+        writeln!(f, "Kicked off: {}", self.is_kick_off)?;
+        writeln!(f, "KickerCoin fee: {}", self.kicker_coin_fee_amount)?;
+        writeln_option(f, "Kicker pinhole:", &self.kicker_pinhole)?;
+        writeln_option(f, "Coordinator pinhole:", &self.coordinator_pinhole)
+    }
+}
+
+/// Human-readable Tanistry
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiTanistry {
+    pub account_type: String,
+    pub state: String,
+    pub kicker_coin_owner_record: String,
+    pub tanistry_token_mint: String,
+    pub crown: Option<String>,
+    pub next_tanistry: Option<String>,
+    pub previous_tanistry: Option<String>,
+    pub ring: String,
+    pub outside_buyer_deposit_amount: u64,
+    pub settled_amount: u64,
+    pub refunded_amount: u64,
+    pub kicker_refund_amount: u64,
+    pub coordinator_refund_amount: u64,
+    pub is_kicker_refunded: bool,
+    pub is_coordinator_refunded: bool,
+    pub coordinator_refund_receipt_mint: Option<String>,
+    pub enrolling_at: i64,
+    pub candidate_limit_records: Vec<String>,
+}
+
+impl From<&Tanistry> for UiTanistry {
+    fn from(tanistry: &Tanistry) -> Self {
+        Self {
+            account_type: format!("{:?}", tanistry.account_type),
+            state: format!("{:?}", tanistry.state),
+            kicker_coin_owner_record: tanistry.kicker_coin_owner_record.to_string(),
+            tanistry_token_mint: tanistry.tanistry_token_mint.to_string(),
+            crown: option_pubkey_to_string(&tanistry.crown),
+            next_tanistry: option_pubkey_to_string(&tanistry.next_tanistry_id),
+            previous_tanistry: option_pubkey_to_string(&tanistry.previous_tanistry_id),
+            ring: tanistry.ring.to_string(),
+            outside_buyer_deposit_amount: tanistry.outside_buyer_deposit_amount,
+            settled_amount: tanistry.settled_amount,
+            refunded_amount: tanistry.refunded_amount,
+            kicker_refund_amount: tanistry.kicker_refund_amount,
+            coordinator_refund_amount: tanistry.coordinator_refund_amount,
+            is_kicker_refunded: tanistry.is_kicker_refunded,
+            is_coordinator_refunded: tanistry.is_coordinator_refunded,
+            coordinator_refund_receipt_mint: option_pubkey_to_string(
+                &tanistry.coordinator_refund_receipt_mint,
+            ),
+            enrolling_at: tanistry.enrolling_at,
+            candidate_limit_records: tanistry
+                .candidate_limit_record_list
+                .iter()
+                .map(|candidate_limit_record| candidate_limit_record.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for UiTanistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "State: {}", self.state)?;
+        writeln!(
+            f,
+            "KickerCoin owner record: {}",
+            self.kicker_coin_owner_record
+        )?;
+        writeln!(f, "Tanistry token mint: {}", self.tanistry_token_mint)?;
+        writeln_option(f, "Crown:", &self.crown)?;
+        writeln_option(f, "Next Tanistry:", &self.next_tanistry)?;
+        writeln_option(f, "Previous Tanistry:", &self.previous_tanistry)?;
+        writeln!(
+            f,
+            "Outside buyer deposit: {}",
+            self.outside_buyer_deposit_amount
+        )?;
+        writeln!(f, "Settled amount: {}", self.settled_amount)?;
+        writeln!(f, "Refunded amount: {}", self.refunded_amount)?;
+        writeln!(
+            f,
+            "Kicker refund: {} (refunded: {})",
+            self.kicker_refund_amount, self.is_kicker_refunded
+        )?;
+        writeln!(
+            f,
+            "Coordinator refund: {} (refunded: {})",
+            self.coordinator_refund_amount, self.is_coordinator_refunded
+        )?;
+        writeln_option(
+            f,
+            "Coordinator refund receipt mint:",
+            &self.coordinator_refund_receipt_mint,
+        )?;
+        writeln!(f, "Enrolling at: {}", self.enrolling_at)?;
+        writeln!(f, "Candidates:")?;
+        for (i, candidate_limit_record) in self.candidate_limit_records.iter().enumerate() {
+            writeln!(f, "  {}: {}", i, candidate_limit_record)?;
+        }
+        Ok(())
+    }
+}
+
+/// Human-readable Candidate Limit Record
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiCandidateLimitRecord {
+    pub account_type: String,
+    pub belonging_tanistry: String,
+    pub number_of_candidate_count: u32,
+    pub candidate_token_mint: String,
+    pub candidate_token_owner: String,
+    pub candidate_delegate: Option<String>,
+    pub candidate_token_deposit_amount: u64,
+    pub received_rating: u64,
+    pub refund_amount: u64,
+    pub is_refunded: bool,
+    pub candidate_fee_amount: u64,
+    pub rate_options_count: u8,
+}
+
+impl From<&CandidateLimitRecord> for UiCandidateLimitRecord {
+    fn from(candidate_limit_record: &CandidateLimitRecord) -> Self {
+        Self {
+            account_type: format!("{:?}", candidate_limit_record.account_type),
+            belonging_tanistry: candidate_limit_record.belonging_tanistry.to_string(),
+            number_of_candidate_count: candidate_limit_record.number_of_candidate_count,
+            candidate_token_mint: candidate_limit_record.candidate_token_mint.to_string(),
+            candidate_token_owner: candidate_limit_record.candidate_token_owner.to_string(),
+            candidate_delegate: option_pubkey_to_string(&candidate_limit_record.candidate_delegate),
+            candidate_token_deposit_amount: candidate_limit_record.candidate_token_deposit_amount,
+            received_rating: candidate_limit_record.received_rating,
+            refund_amount: candidate_limit_record.refund_amount,
+            is_refunded: candidate_limit_record.is_refunded,
+            candidate_fee_amount: candidate_limit_record.candidate_fee_amount,
+            rate_options_count: candidate_limit_record.rate_options_count,
+        }
+    }
+}
+
+impl fmt::Display for UiCandidateLimitRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Tanistry: {}", self.belonging_tanistry)?;
+        writeln!(f, "Candidate count: {}", self.number_of_candidate_count)?;
+        writeln!(f, "Candidate token mint: {}", self.candidate_token_mint)?;
+        writeln!(f, "Candidate token owner: {}", self.candidate_token_owner)?;
+        writeln_option(f, "Candidate delegate:", &self.candidate_delegate)?;
+        writeln!(f, "Deposit amount: {}", self.candidate_token_deposit_amount)?;
+        writeln!(f, "Fee amount: {}", self.candidate_fee_amount)?;
+        writeln!(f, "Received rating: {}", self.received_rating)?;
+        writeln!(f, "Rate options: {}", self.rate_options_count)?;
+        writeln!(
+            f,
+            "Refund: {} (refunded: {})",
+            self.refund_amount, self.is_refunded
+        )
+    }
+}
+
+/// Human-readable Mix Content Record
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiMixContentRecord {
+    pub account_type: String,
+    pub encrypted_link: String,
+    pub rater_candidate: String,
+    pub buddy_candidate: String,
+    pub option_mix_result: String,
+    pub finger_print_of_mixed_content: String,
+    pub init_bc_token: String,
+    pub buddy_bc_token: String,
+    pub time_shift_a: u64,
+    pub time_shift_b: u64,
+    pub range_a: Option<String>,
+    pub range_b: Option<String>,
+    pub promoted_bc_token: Option<String>,
+}
+
+impl From<&MixContentRecord> for UiMixContentRecord {
+    fn from(mix_content_record: &MixContentRecord) -> Self {
+        Self {
+            account_type: format!("{:?}", mix_content_record.account_type),
+            encrypted_link: mix_content_record.encrypted_link.clone(),
+            rater_candidate: mix_content_record.rater_candidate.to_string(),
+            buddy_candidate: mix_content_record.buddy_candidate.to_string(),
+            option_mix_result: format!("{:?}", mix_content_record.option_mix_result),
+            finger_print_of_mixed_content: mix_content_record.finger_print_of_mixed_content.clone(),
+            init_bc_token: mix_content_record.init_bc_token.to_string(),
+            buddy_bc_token: mix_content_record.buddy_bc_token.to_string(),
+            time_shift_a: mix_content_record.time_shift_a,
+            time_shift_b: mix_content_record.time_shift_b,
+            range_a: option_content_range_to_string(&mix_content_record.range_a),
+            range_b: option_content_range_to_string(&mix_content_record.range_b),
+            promoted_bc_token: option_pubkey_to_string(&mix_content_record.promoted_bc_token),
+        }
+    }
+}
+
+impl fmt::Display for UiMixContentRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Rater candidate: {}", self.rater_candidate)?;
+        writeln!(f, "Buddy candidate: {}", self.buddy_candidate)?;
+        writeln!(f, "Mix result: {}", self.option_mix_result)?;
+        writeln!(f, "Encrypted link: {}", self.encrypted_link)?;
+        writeln!(f, "Fingerprint: {}", self.finger_print_of_mixed_content)?;
+        writeln!(f, "Init bcToken: {}", self.init_bc_token)?;
+        writeln!(f, "Buddy bcToken: {}", self.buddy_bc_token)?;
+        writeln!(f, "Time shift A: {}", self.time_shift_a)?;
+        writeln!(f, "Time shift B: {}", self.time_shift_b)?;
+        writeln_option(f, "Range A:", &self.range_a)?;
+        writeln_option(f, "Range B:", &self.range_b)?;
+        writeln_option(f, "Promoted bcToken:", &self.promoted_bc_token)
+    }
+}
+
+/// Human-readable Rate Option
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiRateOption {
+    pub account_type: String,
+    pub issuer_roydamna: String,
+    pub mix_content_record: String,
+    pub rate_other_record: String,
+    pub number_of_issue: u8,
+    pub buddy_candidate: String,
+    pub init_content: String,
+    pub signed_off: bool,
+}
+
+impl From<&RateOption> for UiRateOption {
+    fn from(rate_option: &RateOption) -> Self {
+        Self {
+            account_type: format!("{:?}", rate_option.account_type),
+            issuer_roydamna: rate_option.issuer_roydamna.to_string(),
+            mix_content_record: rate_option.mix_content_record.to_string(),
+            rate_other_record: rate_option.rate_other_record.to_string(),
+            number_of_issue: rate_option.number_of_issue,
+            buddy_candidate: rate_option.buddy_candidate.to_string(),
+            init_content: rate_option.init_content.to_string(),
+            signed_off: rate_option.signed_off,
+        }
+    }
+}
+
+impl fmt::Display for UiRateOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Issuer Roydamna: {}", self.issuer_roydamna)?;
+        writeln!(f, "Mix content record: {}", self.mix_content_record)?;
+        writeln!(f, "Rate other record: {}", self.rate_other_record)?;
+        writeln!(f, "Number of issue: {}", self.number_of_issue)?;
+        writeln!(f, "Buddy candidate: {}", self.buddy_candidate)?;
+        writeln!(f, "Init content: {}", self.init_content)?;
+        writeln!(f, "Signed off: {}", self.signed_off)
+    }
+}
+
+/// Human-readable Rate Other Record
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiRateOtherRecord {
+    pub account_type: String,
+    pub mix_content_record: String,
+    pub cc_vote_record: String,
+    pub outside_buyer_record: String,
+    pub rate_amount: u64,
+    pub rating_time: Option<i64>,
+}
+
+impl From<&RateOtherRecord> for UiRateOtherRecord {
+    fn from(rate_other_record: &RateOtherRecord) -> Self {
+        Self {
+            account_type: format!("{:?}", rate_other_record.account_type),
+            mix_content_record: rate_other_record.mix_content_record.to_string(),
+            cc_vote_record: rate_other_record.cc_vote_record.to_string(),
+            outside_buyer_record: rate_other_record.outside_buyer_record.to_string(),
+            rate_amount: rate_other_record.rate_amount,
+            rating_time: rate_other_record.rating_time,
+        }
+    }
+}
+
+impl fmt::Display for UiRateOtherRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Mix content record: {}", self.mix_content_record)?;
+        writeln!(f, "CC vote record: {}", self.cc_vote_record)?;
+        writeln!(f, "Outside buyer record: {}", self.outside_buyer_record)?;
+        writeln!(f, "Rate amount: {}", self.rate_amount)?;
+        writeln_option(
+            f,
+            "Rating time:",
+            &self.rating_time.map(|rating_time| rating_time.to_string()),
+        )
+    }
+}
+
+/// Human-readable Outside Buyer Record
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiOutsideBuyerRecord {
+    pub account_type: String,
+    pub tanistry: String,
+    pub outside_buyer_token_mint: String,
+    pub outside_buyer_token_owner: String,
+    pub outside_buyer_token_deposit_amount: u64,
+    pub outside_buyer_fee_amount: u64,
+    pub is_refunded: bool,
+}
+
+impl From<&OutsideBuyerRecord> for UiOutsideBuyerRecord {
+    fn from(outside_buyer_record: &OutsideBuyerRecord) -> Self {
+        Self {
+            account_type: format!("{:?}", outside_buyer_record.account_type),
+            tanistry: outside_buyer_record.tanistry.to_string(),
+            outside_buyer_token_mint: outside_buyer_record.outside_buyer_token_mint.to_string(),
+            outside_buyer_token_owner: outside_buyer_record.outside_buyer_token_owner.to_string(),
+            outside_buyer_token_deposit_amount: outside_buyer_record
+                .outside_buyer_token_deposit_amount,
+            outside_buyer_fee_amount: outside_buyer_record.outside_buyer_fee_amount,
+            is_refunded: outside_buyer_record.is_refunded,
+        }
+    }
+}
+
+impl fmt::Display for UiOutsideBuyerRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Tanistry: {}", self.tanistry)?;
+        writeln!(f, "Buyer token mint: {}", self.outside_buyer_token_mint)?;
+        writeln!(f, "Buyer token owner: {}", self.outside_buyer_token_owner)?;
+        writeln!(
+            f,
+            "Deposit amount: {}",
+            self.outside_buyer_token_deposit_amount
+        )?;
+        writeln!(f, "Fee amount: {}", self.outside_buyer_fee_amount)?;
+        writeln!(f, "Refunded: {}", self.is_refunded)
+    }
+}
+
+/// Human-readable CC Vote Record
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiCCVoteRecord {
+    pub account_type: String,
+    pub target_ring: String,
+    pub voter: String,
+    pub is_relinquished: bool,
+    pub voter_weight: u64,
+    pub vote: String,
+}
+
+impl From<&CCVoteRecord> for UiCCVoteRecord {
+    fn from(cc_vote_record: &CCVoteRecord) -> Self {
+        Self {
+            account_type: format!("{:?}", cc_vote_record.account_type),
+            target_ring: cc_vote_record.target_ring.to_string(),
+            voter: cc_vote_record.voter.to_string(),
+            is_relinquished: cc_vote_record.is_relinquished,
+            voter_weight: cc_vote_record.voter_weight,
+            vote: format!("{:?}", cc_vote_record.vote),
+        }
+    }
+}
+
+impl fmt::Display for UiCCVoteRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Target ring: {}", self.target_ring)?;
+        writeln!(f, "Voter: {}", self.voter)?;
+        writeln!(f, "Voter weight: {}", self.voter_weight)?;
+        writeln!(f, "Vote: {}", self.vote)?;
+        writeln!(f, "Relinquished: {}", self.is_relinquished)
+    }
+}
+
+/// Human-readable Shihon Config
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiShihonConfig {
+    pub account_type: String,
+    pub admin_authority: String,
+    pub kicker_coin_mint: String,
+    pub rft_mint: String,
+    pub oracle_authority: String,
+    pub max_candidates: u32,
+    pub min_candidates: u32,
+    pub enrollment_time: u32,
+    pub kicker_coin_fee_bps: u16,
+    pub candidate_fee_bps: u16,
+    pub outside_buyer_fee_bps: u16,
+    pub guardian: Option<String>,
+    pub pause_flags: u32,
+}
+
+impl From<&ShihonConfig> for UiShihonConfig {
+    fn from(shihon_config: &ShihonConfig) -> Self {
+        Self {
+            account_type: format!("{:?}", shihon_config.account_type),
+            admin_authority: shihon_config.admin_authority.to_string(),
+            kicker_coin_mint: shihon_config.config.kicker_coin_mint.to_string(),
+            rft_mint: shihon_config.config.rft_mint.to_string(),
+            oracle_authority: shihon_config.config.oracle_authority.to_string(),
+            max_candidates: shihon_config.config.max_candidates,
+            min_candidates: shihon_config.config.min_candidates,
+            enrollment_time: shihon_config.config.enrollment_time,
+            kicker_coin_fee_bps: shihon_config.config.kicker_coin_fee_bps,
+            candidate_fee_bps: shihon_config.config.candidate_fee_bps,
+            outside_buyer_fee_bps: shihon_config.config.outside_buyer_fee_bps,
+            guardian: shihon_config
+                .config
+                .guardian
+                .map(|guardian| guardian.to_string()),
+            pause_flags: shihon_config.pause_flags,
+        }
+    }
+}
+
+impl fmt::Display for UiShihonConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Admin authority: {}", self.admin_authority)?;
+        writeln!(f, "KickerCoin mint: {}", self.kicker_coin_mint)?;
+        writeln!(f, "RFT mint: {}", self.rft_mint)?;
+        writeln!(f, "Oracle authority: {}", self.oracle_authority)?;
+        writeln!(f, "Max candidates: {}", self.max_candidates)?;
+        writeln!(f, "Min candidates: {}", self.min_candidates)?;
+        writeln!(f, "Enrollment time: {}s", self.enrollment_time)?;
+        writeln!(f, "KickerCoin fee: {}bps", self.kicker_coin_fee_bps)?;
+        writeln!(f, "Candidate fee: {}bps", self.candidate_fee_bps)?;
+        writeln!(f, "Outside buyer fee: {}bps", self.outside_buyer_fee_bps)?;
+        writeln!(
+            f,
+            "Guardian: {}",
+            self.guardian.as_deref().unwrap_or("None")
+        )?;
+        writeln!(f, "Pause flags: {:#b}", self.pause_flags)
+    }
+}
+
+/// Human-readable Ring
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiRing {
+    pub account_type: String,
+    pub first_tanistry: String,
+    pub last_crowned_tanistry: Option<String>,
+    pub crowned_tanistry_count: u32,
+    pub cumulative_deposit_amount: u64,
+    pub cumulative_rft_volume: u64,
+    pub cc_vote_weight: u64,
+    pub cc_vote_counting_time: Option<u64>,
+}
+
+impl From<&Ring> for UiRing {
+    fn from(ring: &Ring) -> Self {
+        Self {
+            account_type: format!("{:?}", ring.account_type),
+            first_tanistry: ring.first_tanistry.to_string(),
+            last_crowned_tanistry: option_pubkey_to_string(&ring.last_crowned_tanistry),
+            crowned_tanistry_count: ring.crowned_tanistry_count,
+            cumulative_deposit_amount: ring.cumulative_deposit_amount,
+            cumulative_rft_volume: ring.cumulative_rft_volume,
+            cc_vote_weight: ring.cc_vote_weight,
+            cc_vote_counting_time: ring.cc_vote_counting_time,
+        }
+    }
+}
+
+impl fmt::Display for UiRing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "First Tanistry: {}", self.first_tanistry)?;
+        writeln_option(f, "Last crowned Tanistry:", &self.last_crowned_tanistry)?;
+        writeln!(f, "Crowned Tanistries: {}", self.crowned_tanistry_count)?;
+        writeln!(f, "Cumulative deposit: {}", self.cumulative_deposit_amount)?;
+        writeln!(f, "Cumulative RFT volume: {}", self.cumulative_rft_volume)?;
+        writeln!(f, "CC vote weight: {}", self.cc_vote_weight)?;
+        writeln_option(
+            f,
+            "CC vote counting time:",
+            &self
+                .cc_vote_counting_time
+                .map(|cc_vote_counting_time| cc_vote_counting_time.to_string()),
+        )
+    }
+}
+
+/// Human-readable Pinhole
+/// Note: The main wallet behind the pinhole isn't stored so it can't be displayed
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiPinhole {
+    pub account_type: String,
+    pub kicker_coin_owner_record: String,
+    pub round: u8,
+    pub side: String,
+    pub pinhole_authority: String,
+}
+
+impl From<&Pinhole> for UiPinhole {
+    fn from(pinhole: &Pinhole) -> Self {
+        Self {
+            account_type: format!("{:?}", pinhole.account_type),
+            kicker_coin_owner_record: pinhole.kicker_coin_owner_record.to_string(),
+            round: pinhole.round,
+            side: format!("{:?}", pinhole.side),
+            pinhole_authority: pinhole.pinhole_authority.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for UiPinhole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(
+            f,
+            "KickerCoinOwnerRecord: {}",
+            self.kicker_coin_owner_record
+        )?;
+        writeln!(f, "Round: {}", self.round)?;
+        writeln!(f, "Side: {}", self.side)?;
+        writeln!(f, "Pinhole authority: {}", self.pinhole_authority)
+    }
+}
+
+/// Human-readable Triple Multisig
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiTripleMultisig {
+    pub account_type: String,
+    pub rater_candidate: String,
+    pub buddy_candidate: String,
+    pub m: u8,
+    pub n: u8,
+    pub signers: Vec<String>,
+}
+
+impl From<&TripleMultisig> for UiTripleMultisig {
+    fn from(triple_multisig: &TripleMultisig) -> Self {
+        Self {
+            account_type: format!("{:?}", triple_multisig.account_type),
+            rater_candidate: triple_multisig.rater_candidate.to_string(),
+            buddy_candidate: triple_multisig.buddy_candidate.to_string(),
+            m: triple_multisig.m,
+            n: triple_multisig.n,
+            signers: triple_multisig.signers[0..triple_multisig.n as usize]
+                .iter()
+                .map(|signer| signer.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for UiTripleMultisig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Account type: {}", self.account_type)?;
+        writeln!(f, "Rater candidate: {}", self.rater_candidate)?;
+        writeln!(f, "Buddy candidate: {}", self.buddy_candidate)?;
+        writeln!(f, "Required signers: {} of {}", self.m, self.n)?;
+        for signer in self.signers.iter() {
+            writeln!(f, "  Signer: {}", signer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use borsh::BorshSerialize;
+
+    use super::*;
+
+    fn create_test_candidate_limit_record() -> CandidateLimitRecord {
+        CandidateLimitRecord {
+            account_type: ShihonAccountType::CandidateLimitRecordV2,
+            bump: 254,
+            belonging_tanistry: Pubkey::new_unique(),
+            number_of_candidate_count: 2,
+            candidate_token_mint: Pubkey::new_unique(),
+            candidate_token_owner: Pubkey::new_unique(),
+            candidate_delegate: None,
+            bc_token: Pubkey::new_unique(),
+            candidate_token_deposit_amount: 100,
+            received_rating: 30,
+            refund_amount: 0,
+            is_refunded: false,
+            candidate_fee_amount: 0,
+            rate_options_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_decode_candidate_limit_record() {
+        // Arrange
+        let candidate_limit_record = create_test_candidate_limit_record();
+        let data = candidate_limit_record.try_to_vec().unwrap();
+
+        // Act
+        let account = decode_account(&data).unwrap();
+
+        // Assert
+        assert_eq!(
+            account,
+            ShihonAccount::CandidateLimitRecord(candidate_limit_record)
+        );
+        assert_eq!(
+            account.account_type(),
+            ShihonAccountType::CandidateLimitRecordV2
+        );
+    }
+
+    #[test]
+    fn test_decode_outside_buyer_record_v1() {
+        // Arrange
+        let outside_buyer_record_v1 = OutsideBuyerRecordV1 {
+            account_type: ShihonAccountType::OutsideBuyerRecordV1,
+            tanistry: Pubkey::new_unique(),
+            outside_buyer_token_mint: Pubkey::new_unique(),
+            outside_buyer_token_owner: Pubkey::new_unique(),
+            outside_buyer_token_deposit_amount: 10,
+            reserved: [0; 7],
+        };
+        let data = outside_buyer_record_v1.try_to_vec().unwrap();
+
+        // Act
+        let account = decode_account(&data).unwrap();
+
+        // Assert
+        assert_eq!(
+            account,
+            ShihonAccount::OutsideBuyerRecord(outside_buyer_record_v1.into())
+        );
+        assert_eq!(
+            account.account_type(),
+            ShihonAccountType::OutsideBuyerRecordV1
+        );
+    }
+
+    #[test]
+    fn test_decode_uninitialized_account_error() {
+        // Arrange
+        let data = [0u8; 32];
+
+        // Act
+        let err = decode_account(&data).err().unwrap();
+
+        // Assert
+        assert_eq!(err, ProgramError::UninitializedAccount);
+    }
+
+    #[test]
+    fn test_decode_truncated_account_error() {
+        // Arrange
+        let data = create_test_candidate_limit_record().try_to_vec().unwrap();
+
+        // Act
+        let err = decode_account(&data[..16]).err().unwrap();
+
+        // Assert
+        assert!(matches!(err, ProgramError::BorshIoError(_)));
+    }
+
+    #[test]
+    fn test_shihon_account_serde_and_display() {
+        // Arrange
+        let candidate_limit_record = create_test_candidate_limit_record();
+        let candidate_token_owner = candidate_limit_record.candidate_token_owner.to_string();
+        let account = ShihonAccount::CandidateLimitRecord(candidate_limit_record);
+
+        // Act
+        let json = serde_json::to_value(&account).unwrap();
+        let ui_account: UiShihonAccount = serde_json::from_value(json.clone()).unwrap();
+        let display = account.to_string();
+
+        // Assert
+        assert_eq!(json["type"], "candidateLimitRecord");
+        assert_eq!(json["info"]["candidateTokenOwner"], candidate_token_owner);
+        assert_eq!(json["info"]["candidateTokenDepositAmount"], 100);
+        assert_eq!(ui_account, UiShihonAccount::from(&account));
+        assert!(display.contains(&format!("Candidate token owner: {}", candidate_token_owner)));
+    }
+}
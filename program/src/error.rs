@@ -8,167 +8,292 @@ use solana_program::{
 };
 use thiserror::Error;
 
-/// Errors that may be returned by the Governance program
+/// Errors that may be returned by the Shihon program
 #[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
 pub enum ShihonError {
     /// Invalid instruction passed to program
     #[error("Invalid instruction passed to program")]
-    InvalidInstruction = 500, // Start Governance custom errors from 500 to avoid conflicts with programs invoked via CPI
+    InvalidInstruction = 500, // Start Shihon custom errors from 500 to avoid conflicts with programs invoked via CPI
 
-    /// Realm with the given name and governing mints already exists
-    #[error("Realm with the given name and governing mints already exists")]
-    RealmAlreadyExists,
-
-    /// Invalid Realm
-    #[error("Invalid realm")]
-    InvalidRealm,
+    /// ---- bcToken Errors ----
 
     /// Invalid bcToken Mint
     #[error("Invalid bcToken Mint")]
     InvalidBcTokenMint,
 
-    /// Candidate Token Owner must sign transaction
-    #[error("Candidate Token Owner must sign transaction")]
-    CandidateTokenOwnerMustSign,
-
-    /// Governing Token Owner or Delegate  must sign transaction
-    #[error("Governing Token Owner or Delegate  must sign transaction")]
-    GoverningTokenOwnerOrDelegateMustSign,
+    /// Invalid bcToken holding account
+    #[error("Invalid bcToken holding account")]
+    InvalidBcTokenHoldingAccount,
 
-    /// All votes must be relinquished to withdraw governing tokens
-    #[error("All votes must be relinquished to withdraw governing tokens")]
-    AllVotesMustBeRelinquishedToWithdrawGoverningTokens,
+    /// Invalid Authority for bcToken
+    #[error("Invalid Authority for bcToken")]
+    InvalidAuthorityForBcToken,
 
-    /// Invalid Token Owner Record account address
-    #[error("Invalid Token Owner Record account address")]
-    InvalidTokenOwnerRecordAccountAddress,
+    /// bcToken has no authority
+    #[error("bcToken has no authority")]
+    BcTokenHasNoAuthority,
 
-    /// Invalid GoverningMint for TokenOwnerRecord
-    #[error("Invalid GoverningMint for TokenOwnerRecord")]
-    InvalidGoverningMintForTokenOwnerRecord,
+    /// bcToken authority must sign transaction
+    #[error("bcToken authority must sign transaction")]
+    BcTokenAuthorityMustSign,
 
-    /// Invalid Realm for TokenOwnerRecord
-    #[error("Invalid Realm for TokenOwnerRecord")]
-    InvalidRealmForTokenOwnerRecord,
+    /// bcToken deposits not allowed
+    #[error("bcToken deposits not allowed")]
+    BcTokenDepositsNotAllowed,
 
-    /// Invalid Proposal for ProposalInstruction
-    #[error("Invalid Proposal for ProposalInstruction")]
-    InvalidProposalForProposalInstruction,
+    /// Invalid bcToken state
+    #[error("Invalid bcToken state")]
+    InvalidBcTokenState,
 
-    /// Invalid Signatory account address
-    #[error("Invalid Signatory account address")]
-    InvalidSignatoryAddress,
+    /// ---- KickerCoin Errors ----
 
     /// KickerCoin already kicked off
     #[error("KickerCoin already kicked off")]
     KickerCoinAlreadyKickedOff,
 
-    /// KickerCoin holder must sign
-    #[error("KickerCoin holder must sign")]
+    /// KickerCoin holder must sign transaction
+    #[error("KickerCoin holder must sign transaction")]
     KickerCoinHolderMustSign,
 
-    /// Invalid Proposal Owner
-    #[error("Invalid Proposal Owner")]
-    InvalidProposalOwnerAccount,
+    /// Invalid KickerCoinOwnerRecord account address
+    #[error("Invalid KickerCoinOwnerRecord account address")]
+    InvalidKickerCoinOwnerRecordAddress,
+
+    /// Invalid KickerCoinOwnerRecord for Tanistry
+    #[error("Invalid KickerCoinOwnerRecord for Tanistry")]
+    InvalidKickerCoinOwnerRecordForTanistry,
+
+    /// Invalid coordinator for Tanistry
+    #[error("Invalid coordinator for Tanistry")]
+    InvalidCoordinatorForTanistry,
+
+    /// Coordinator must sign transaction
+    #[error("Coordinator must sign transaction")]
+    CoordinatorMustSign,
+
+    /// Coordinator already withdrawn from the Tanistry
+    #[error("Coordinator already withdrawn from the Tanistry")]
+    CoordinatorAlreadyWithdrawn,
+
+    /// ---- Tanistry Errors ----
+
+    /// Invalid bcToken for Tanistry
+    #[error("Invalid bcToken for Tanistry")]
+    InvalidBcTokenForTanistry,
+
+    /// Invalid Tanistry token holding account
+    #[error("Invalid Tanistry token holding account")]
+    InvalidTanistryTokenHoldingAccount,
+
+    /// Invalid Crown for Tanistry
+    #[error("Invalid Crown for Tanistry")]
+    InvalidCrownForTanistry,
+
+    /// Invalid Tanistry state: Can't settle
+    #[error("Invalid Tanistry state: Can't settle")]
+    InvalidTanistryStateCannotSettle,
+
+    /// Invalid Tanistry state: Can't refund
+    #[error("Invalid Tanistry state: Can't refund")]
+    InvalidTanistryStateCannotRefund,
+
+    /// Invalid Tanistry state: Coordinator can't withdraw
+    #[error("Invalid Tanistry state: Coordinator can't withdraw")]
+    InvalidTanistryStateCannotWithdrawCoordinator,
+
+    /// ---- Candidate Errors ----
+
+    /// Candidate token owner must sign transaction
+    #[error("Candidate token owner must sign transaction")]
+    CandidateTokenOwnerMustSign,
+
+    /// Invalid CandidateLimitRecord account address
+    #[error("Invalid CandidateLimitRecord account address")]
+    InvalidCandidateLimitRecordAddress,
+
+    /// Invalid Tanistry for CandidateLimitRecord
+    #[error("Invalid Tanistry for CandidateLimitRecord")]
+    InvalidTanistryForCandidateLimitRecord,
+
+    /// Invalid candidate token mint for CandidateLimitRecord
+    #[error("Invalid candidate token mint for CandidateLimitRecord")]
+    InvalidCandidateMintForCandidateLimitRecord,
+
+    /// Invalid rater for CandidateLimitRecord
+    #[error("Invalid rater for CandidateLimitRecord")]
+    InvalidRaterForCandidateLimitRecord,
+
+    /// Invalid candidate token mint
+    #[error("Invalid candidate token mint")]
+    InvalidCandidateTokenMint,
+
+    /// Candidate doesn't have enough tokens to join the Tanistry
+    #[error("Candidate doesn't have enough tokens to join the Tanistry")]
+    NotEnoughTokensToCandidate,
+
+    /// Too many candidates in the Tanistry
+    #[error("Too many candidates in the Tanistry")]
+    TooManyCandidates,
+
+    /// ---- Mixing Errors ----
+
+    /// Invalid content for mixing
+    #[error("Invalid content for mixing")]
+    InvalidContentForMixing,
+
+    /// Invalid content mint for mixing
+    #[error("Invalid content mint for mixing")]
+    InvalidContentMintForMixing,
+
+    /// ---- Rating Errors ----
+
+    /// Invalid RateOption account address
+    #[error("Invalid RateOption account address")]
+    InvalidRateOptionAddress,
+
+    /// Invalid rate options
+    #[error("Invalid rate options")]
+    InvalidRateOptions,
+
+    /// RateOption already signed off
+    #[error("RateOption already signed off")]
+    RateOptionAlreadySignedOff,
+
+    /// RateOption issuer must sign transaction
+    #[error("RateOption issuer must sign transaction")]
+    RateOptionIssuerMustSign,
+
+    /// Invalid RateOtherRecord account address
+    #[error("Invalid RateOtherRecord account address")]
+    InvalidRateOtherRecordAddress,
+
+    /// Rating already casted
+    #[error("Rating already casted")]
+    RatingAlreadyCasted,
 
-    /// Invalid Proposal for VoterRecord
-    #[error("Invalid Proposal for VoterRecord")]
-    InvalidProposalForVoterRecord,
+    /// Rater must sign transaction
+    #[error("Rater must sign transaction")]
+    RaterMustSign,
 
-    /// Invalid GoverningTokenOwner  for VoteRecord
-    #[error("Invalid GoverningTokenOwner for VoteRecord")]
-    InvalidGoverningTokenOwnerForVoteRecord,
+    /// ---- Outside Buying Errors ----
 
-    /// Invalid Governance config: Vote threshold percentage out of range"
-    #[error("Invalid Governance config: Vote threshold percentage out of range")]
-    InvalidVoteThresholdPercentage,
+    /// Invalid OutsideBuyerRecord account address
+    #[error("Invalid OutsideBuyerRecord account address")]
+    InvalidOutsideBuyerRecordAddress,
 
-    /// Proposal for the given Governance, Governing Token Mint and index already exists
-    #[error("Proposal for the given Governance, Governing Token Mint and index already exists")]
-    ProposalAlreadyExists,
+    /// Invalid rater for OutsideBuyerRecord
+    #[error("Invalid rater for OutsideBuyerRecord")]
+    InvalidRaterForOutsideBuyerRecord,
 
-    /// Token Owner already voted on the Proposal
-    #[error("Token Owner already voted on the Proposal")]
-    VoteAlreadyExists,
+    /// Invalid outside buyer token mint for OutsideBuyerRecord
+    #[error("Invalid outside buyer token mint for OutsideBuyerRecord")]
+    InvalidMintForOutsideBuyerRecord,
 
-    /// Owner doesn't have enough governing tokens to create Proposal
-    #[error("Owner doesn't have enough governing tokens to create Proposal")]
-    NotEnoughTokensToCreateProposal,
+    /// Invalid content owner for OutsideBuyerRecord
+    #[error("Invalid content owner for OutsideBuyerRecord")]
+    InvalidContentOwnerForOutsideBuyerRecord,
 
-    /// Invalid State: Can't edit Signatories
-    #[error("Invalid State: Can't edit Signatories")]
-    InvalidStateCannotEditSignatories,
+    /// Invalid outside buyer token mint
+    #[error("Invalid outside buyer token mint")]
+    InvalidOutsideBuyerTokenMint,
 
-    /// Invalid Proposal state
-    #[error("Invalid Proposal state")]
-    InvalidProposalState,
-    /// Invalid State: Can't edit instructions
-    #[error("Invalid State: Can't edit instructions")]
-    InvalidStateCannotEditInstructions,
+    /// Outside buyer token owner must sign transaction
+    #[error("Outside buyer token owner must sign transaction")]
+    OutsideBuyerTokenOwnerMustSign,
 
-    /// Invalid State: Can't execute instruction
-    #[error("Invalid State: Can't execute instruction")]
-    InvalidStateCannotExecuteInstruction,
+    /// Outside buyer doesn't have enough tokens to buy exceeded rate token
+    #[error("Outside buyer doesn't have enough tokens to buy exceeded rate token")]
+    NotEnoughTokensToBuyExceededRateToken,
 
-    /// Can't execute instruction within its hold up time
-    #[error("Can't execute instruction within its hold up time")]
-    CannotExecuteInstructionWithinHoldUpTime,
+    /// Not enough exceeded rate tokens to sell
+    #[error("Not enough exceeded rate tokens to sell")]
+    NotEnoughExceededRateTokensToSell,
 
-    /// Instruction already executed
-    #[error("Instruction already executed")]
-    InstructionAlreadyExecuted,
+    /// Too many outstanding exceeded rate token purchases
+    #[error("Too many outstanding exceeded rate token purchases")]
+    TooManyOutstandingPurchases,
 
-    /// Invalid Instruction index
-    #[error("Invalid Instruction index")]
-    InvalidInstructionIndex,
+    /// All CC votes must be relinquished to refund exceeded rate tokens
+    #[error("All CC votes must be relinquished to refund exceeded rate tokens")]
+    AllCCVotesMustBeRelinquishedToRefund,
 
-    /// Instruction hold up time is below the min specified by Governance
-    #[error("Instruction hold up time is below the min specified by Governance")]
-    InstructionHoldUpTimeBelowRequiredMin,
+    /// All ratings must be finalized to refund exceeded rate tokens
+    #[error("All ratings must be finalized to refund exceeded rate tokens")]
+    AllRatingsMustBeFinalisedToRefund,
 
-    /// Instruction at the given index for the Proposal already exists
-    #[error("Instruction at the given index for the Proposal already exists")]
-    InstructionAlreadyExists,
+    /// ---- CC Voting Errors ----
 
-    /// Invalid State: Can't sign off
-    #[error("Invalid State: Can't sign off")]
-    InvalidStateCannotSignOff,
+    /// CC vote already exists
+    #[error("CC vote already exists")]
+    CCVoteAlreadyExists,
 
-    /// Invalid State: Can't vote
-    #[error("Invalid State: Can't vote")]
-    InvalidStateCannotVote,
+    /// CC vote already relinquished
+    #[error("CC vote already relinquished")]
+    CCVoteAlreadyRelinquished,
 
-    /// Invalid State: Can't finalize vote
-    #[error("Invalid State: Can't finalize vote")]
-    InvalidStateCannotFinalize,
+    /// CC vote counting time has been reached
+    #[error("CC vote counting time has been reached")]
+    CCVoteCountingTimeReached,
 
-    /// Invalid State: Can't cancel Proposal
-    #[error("Invalid State: Can't cancel Proposal")]
-    InvalidStateCannotCancelProposal,
+    /// Invalid target ring for CCVoteRecord
+    #[error("Invalid target ring for CCVoteRecord")]
+    InvalidTargetRingForCCVoteRecord,
 
-    /// Vote already relinquished
-    #[error("Vote already relinquished")]
-    VoteAlreadyRelinquished,
+    /// Invalid voter for CCVoteRecord
+    #[error("Invalid voter for CCVoteRecord")]
+    InvalidVoterForCCVoteRecord,
 
-    /// Can't finalize vote. Voting still in progress
-    #[error("Can't finalize vote. Voting still in progress")]
-    CannotFinalizeVotingInProgress,
+    /// CC voter must sign transaction
+    #[error("CC voter must sign transaction")]
+    CCVoterMustSign,
 
-    /// Proposal voting time expired
-    #[error("Proposal voting time expired")]
-    ProposalVotingTimeExpired,
+    /// CC vote weight overflow
+    #[error("CC vote weight overflow")]
+    CCVoteWeightOverflow,
 
-    /// Invalid Signatory Mint
-    #[error("Invalid Signatory Mint")]
-    InvalidSignatoryMint,
+    /// CC vote can't be changed to the same target ring
+    #[error("CC vote can't be changed to the same target ring")]
+    CCVoteTargetRingNotChanged,
 
-    /// Proposal does not belong to the given Governance
-    #[error("Proposal does not belong to the given Governance")]
-    InvalidGovernanceForProposal,
+    /// ---- Refund Errors ----
 
-    /// Proposal does not belong to given Governing Mint"
-    #[error("Proposal does not belong to given Governing Mint")]
-    InvalidGoverningMintForProposal,
+    /// CandidateLimitRecords don't match the Tanistry's candidate list
+    #[error("CandidateLimitRecords don't match the Tanistry's candidate list")]
+    InvalidCandidateLimitRecordsForSettlement,
+
+    /// Tanistry vault balance doesn't cover the deposits
+    #[error("Tanistry vault balance doesn't cover the deposits")]
+    TanistryVaultBalanceMismatch,
+
+    /// Refund amount overflow
+    #[error("Refund amount overflow")]
+    RefundAmountOverflow,
+
+    /// Refund exceeds the settled amount
+    #[error("Refund exceeds the settled amount")]
+    RefundExceedsSettledAmount,
+
+    /// Refund already claimed
+    #[error("Refund already claimed")]
+    RefundAlreadyClaimed,
+
+    /// Invalid refund claimant
+    #[error("Invalid refund claimant")]
+    InvalidRefundClaimant,
+
+    /// Refund claimant must sign transaction
+    #[error("Refund claimant must sign transaction")]
+    RefundClaimantMustSign,
+
+    /// Invalid coordinator refund receipt mint
+    #[error("Invalid coordinator refund receipt mint")]
+    InvalidCoordinatorRefundReceiptMint,
+
+    /// Coordinator refund must be redeemed with the refund receipt
+    #[error("Coordinator refund must be redeemed with the refund receipt")]
+    CoordinatorRefundReceiptMustBeRedeemed,
+
+    /// ---- SPL Token Tools Errors ----
 
     /// Current mint authority must sign transaction
     #[error("Current mint authority must sign transaction")]
@@ -182,8 +307,6 @@ pub enum ShihonError {
     #[error("Mint has no authority")]
     MintHasNoAuthority,
 
-    /// ---- SPL Token Tools Errors ----
-
     /// Invalid Token account owner
     #[error("Invalid Token account owner")]
     SplTokenAccountWithInvalidOwner,
@@ -216,6 +339,14 @@ pub enum ShihonError {
     #[error("Token Mint account doesn't exist")]
     SplTokenMintDoesNotExist,
 
+    /// Invalid token owner
+    #[error("Invalid token owner")]
+    InvalidTokenOwner,
+
+    /// Current token owner must sign transaction
+    #[error("Current token owner must sign transaction")]
+    TokenOwnerMustSign,
+
     /// ---- Bpf Upgradable Loader Tools Errors ----
 
     /// Invalid ProgramData account Address
@@ -238,137 +369,429 @@ pub enum ShihonError {
     #[error("Given program is not upgradable")]
     ProgramNotUpgradable,
 
-    /// Invalid token owner
-    #[error("Invalid token owner")]
-    InvalidTokenOwner,
+    /// ---- PDA Address Errors ----
 
-    /// Current token owner must sign transaction
-    #[error("Current token owner must sign transaction")]
-    TokenOwnerMustSign,
+    /// Invalid bcToken account address
+    #[error("Invalid bcToken account address")]
+    InvalidBcTokenAddress,
 
-    /// Given VoteThresholdPercentageType is not supported
-    #[error("Given VoteThresholdPercentageType is not supported")]
-    VoteThresholdPercentageTypeNotSupported,
+    /// Invalid bcToken Metadata account address
+    #[error("Invalid bcToken Metadata account address")]
+    InvalidBcTokenMetadataAddress,
 
-    /// Given VoteWeightSource is not supported
-    #[error("Given VoteWeightSource is not supported")]
-    VoteWeightSourceNotSupported,
+    /// Invalid Tanistry account address
+    #[error("Invalid Tanistry account address")]
+    InvalidTanistryAddress,
 
-    /// Proposal cool off time is not supported
-    #[error("Proposal cool off time is not supported")]
-    ProposalCoolOffTimeNotSupported,
+    /// Invalid MixContentRecord account address
+    #[error("Invalid MixContentRecord account address")]
+    InvalidMixContentRecordAddress,
 
-    /// Governance PDA must sign
-    #[error("Governance PDA must sign")]
-    GovernancePdaMustSign,
+    /// Invalid CCVoteRecord account address
+    #[error("Invalid CCVoteRecord account address")]
+    InvalidCCVoteRecordAddress,
 
-    /// Instruction already flagged with error
-    #[error("Instruction already flagged with error")]
-    InstructionAlreadyFlaggedWithError,
+    /// ---- Account Migration Errors ----
 
-    /// Invalid Realm for Governance
-    #[error("Invalid Realm for Governance")]
-    InvalidRealmForGovernance,
+    /// Account is already migrated to the latest version
+    #[error("Account is already migrated to the latest version")]
+    AccountAlreadyMigrated,
 
-    /// Invalid Authority for bcToken
-    #[error("Invalid Authority for bcToken")]
-    InvalidAuthorityForBcToken,
+    /// Account type can't be migrated
+    #[error("Account type can't be migrated")]
+    InvalidAccountTypeForMigration,
 
-    /// Realm has no authority
-    #[error("Realm has no authority")]
-    RealmHasNoAuthority,
+    /// ---- Tanistry Lifecycle Errors ----
 
-    /// Realm authority must sign
-    #[error("Realm authority must sign")]
-    RealmAuthorityMustSign,
+    /// Invalid Tanistry state: Can't candidate
+    #[error("Invalid Tanistry state: Can't candidate")]
+    InvalidTanistryStateCannotCandidate,
 
-    /// Invalid  bcToken holding account
-    #[error("Invalid  bc_token holding account")]
-    InvalidBcTokenHoldingAccount,
+    /// Tanistry enrollment has ended
+    #[error("Tanistry enrollment has ended")]
+    TanistryEnrollmentEnded,
 
-    /// Realm council mint change is not supported
-    #[error("Realm council mint change is not supported")]
-    RealmCouncilMintChangeIsNotSupported,
+    /// Invalid Tanistry state: Can't crown
+    #[error("Invalid Tanistry state: Can't crown")]
+    InvalidTanistryStateCannotCrown,
 
-    /// Not supported mint max vote weight source
-    #[error("Not supported mint max vote weight source")]
-    MintMaxVoteWeightSourceNotSupported,
+    /// Invalid KickerCoin mint
+    #[error("Invalid KickerCoin mint")]
+    InvalidKickerCoinMint,
 
-    /// Invalid max vote weight supply fraction
-    #[error("Invalid max vote weight supply fraction")]
-    InvalidMaxVoteWeightSupplyFraction,
+    /// bcToken can't be discarded while the bcToken is in an active Tanistry
+    #[error("bcToken can't be discarded while the bcToken is in an active Tanistry")]
+    BcTokenCannotBeDiscarded,
 
-    /// Owner doesn't have enough governing tokens to create Governance
-    #[error("Owner doesn't have enough governing tokens to create Governance")]
-    NotEnoughTokensToCreateGovernance,
+    /// Coordinator input can't be empty
+    #[error("Coordinator input can't be empty")]
+    InvalidCoordinatorInput,
 
-    /// Too many outstanding proposals
-    #[error("Too many outstanding proposals")]
-    TooManyOutstandingProposals,
+    /// Invalid Tanistry state: Can't buy exceeded rate token
+    #[error("Invalid Tanistry state: Can't buy exceeded rate token")]
+    InvalidTanistryStateCannotBuy,
 
-    /// All proposals must be finalized to withdraw governing tokens
-    #[error("All proposals must be finalized to withdraw governing tokens")]
-    AllProposalsMustBeFinalisedToWithdrawGoverningTokens,
+    /// Outside buyer deposit amount overflow
+    #[error("Outside buyer deposit amount overflow")]
+    OutsideBuyerDepositOverflow,
 
-    /// Invalid VoterWeightRecord for Realm
-    #[error("Invalid VoterWeightRecord for Realm")]
-    InvalidVoterWeightRecordForRealm,
+    /// ---- Token-2022 Errors ----
 
-    /// Invalid VoterWeightRecord for GoverningTokenMint
-    #[error("Invalid VoterWeightRecord for GoverningTokenMint")]
-    InvalidVoterWeightRecordForGoverningTokenMint,
+    /// Invalid Tanistry token mint
+    #[error("Invalid Tanistry token mint")]
+    InvalidTanistryTokenMint,
 
-    /// Invalid VoterWeightRecord for TokenOwner
-    #[error("Invalid VoterWeightRecord for TokenOwner")]
-    InvalidVoterWeightRecordForTokenOwner,
+    /// Invalid SPL Token program
+    #[error("Invalid SPL Token program")]
+    InvalidSplTokenProgram,
 
-    /// VoterWeightRecord expired
-    #[error("VoterWeightRecord expired")]
-    VoterWeightRecordExpired,
+    /// Token account is frozen
+    #[error("Token account is frozen")]
+    SplTokenAccountFrozen,
 
-    /// Invalid RealmConfig for Realm
-    #[error("Invalid RealmConfig for Realm")]
-    InvalidRealmConfigForRealm,
+    /// Token transfer fee overflow
+    #[error("Token transfer fee overflow")]
+    SplTokenTransferFeeOverflow,
 
-    /// TokenOwnerRecord already exists
-    #[error("TokenOwnerRecord already exists")]
-    TokenOwnerRecordAlreadyExists,
+    /// ---- Shihon Config Errors ----
 
-    /// bcToken deposits not allowed
-    #[error("bcToken deposits not allowed")]
-    BcTokenDepositsNotAllowed,
+    /// Invalid ShihonConfig account address
+    #[error("Invalid ShihonConfig account address")]
+    InvalidShihonConfigAddress,
+
+    /// Invalid admin authority
+    #[error("Invalid admin authority")]
+    InvalidAdminAuthority,
+
+    /// Admin authority must sign transaction
+    #[error("Admin authority must sign transaction")]
+    AdminAuthorityMustSign,
+
+    /// Invalid max candidates
+    #[error("Invalid max candidates")]
+    InvalidMaxCandidates,
+
+    /// Fee exceeds max basis points
+    #[error("Fee exceeds max basis points")]
+    InvalidFeeBps,
+
+    /// ---- Pause Errors ----
+
+    /// Instruction is paused
+    #[error("Instruction is paused")]
+    ShihonPaused,
+
+    /// Invalid pause authority
+    #[error("Invalid pause authority")]
+    InvalidPauseAuthority,
+
+    /// Pause authority must sign
+    #[error("Pause authority must sign")]
+    PauseAuthorityMustSign,
 
-    /// Invalid vote choice weight percentage
-    #[error("Invalid vote choice weight percentage")]
-    InvalidVoteChoiceWeightPercentage,
+    /// Guardian can only pause instructions
+    #[error("Guardian can only pause instructions")]
+    GuardianCannotUnpause,
 
-    /// Vote type not supported
-    #[error("Vote type not supported")]
-    VoteTypeNotSupported,
+    /// ---- Treasury Errors ----
 
-    /// InvalidProposalOptions
-    #[error("Invalid proposal options")]
-    InvalidProposalOptions,
+    /// Invalid Treasury account address
+    #[error("Invalid Treasury account address")]
+    InvalidTreasuryAddress,
 
-    /// Proposal is not not executable
-    #[error("Proposal is not not executable")]
-    ProposalIsNotExecutable,
+    /// Fee amount overflow
+    #[error("Fee amount overflow")]
+    FeeAmountOverflow,
 
-    /// Invalid vote
-    #[error("Invalid vote")]
-    InvalidVote,
+    /// ---- Tanistry Cancellation Errors ----
 
-    /// Cannot execute defeated option
-    #[error("Cannot execute defeated option")]
-    CannotExecuteDefeatedOption,
+    /// Invalid Tanistry state: Can't cancel
+    #[error("Invalid Tanistry state: Can't cancel")]
+    InvalidTanistryStateCannotCancel,
 
-    /// VoterWeightRecord invalid action
-    #[error("VoterWeightRecord invalid action")]
-    VoterWeightRecordInvalidAction,
+    /// Tanistry enrollment hasn't ended yet
+    #[error("Tanistry enrollment hasn't ended yet")]
+    TanistryEnrollmentNotEnded,
 
-    /// VoterWeightRecord invalid action target
-    #[error("VoterWeightRecord invalid action target")]
-    VoterWeightRecordInvalidActionTarget,
+    /// Tanistry has enough candidates and can't be cancelled
+    #[error("Tanistry has enough candidates and can't be cancelled")]
+    TanistryHasEnoughCandidates,
+
+    /// Invalid min candidates
+    #[error("Invalid min candidates")]
+    InvalidMinCandidates,
+
+    /// ---- Ring Errors ----
+
+    /// Invalid Ring account address
+    #[error("Invalid Ring account address")]
+    InvalidRingAddress,
+
+    /// Ring must start from the first Tanistry of a chain
+    #[error("Ring must start from the first Tanistry of a chain")]
+    RingMustStartFromFirstTanistry,
+
+    /// Tanistry isn't the next Tanistry of the Ring
+    #[error("Tanistry isn't the next Tanistry of the Ring")]
+    InvalidTanistryForRing,
+
+    /// Ring volume overflow
+    #[error("Ring volume overflow")]
+    RingVolumeOverflow,
+
+    /// Only a ring smaller than your ring can be pulled
+    #[error("Only a ring smaller than your ring can be pulled")]
+    CannotPullBiggerRing,
+
+    /// Only a ring bigger than your ring can be pushed
+    #[error("Only a ring bigger than your ring can be pushed")]
+    CannotPushSmallerRing,
+
+    /// ---- Mixed Content Promotion Errors ----
+
+    /// Mixed content can only be promoted when the mix Succeeded
+    #[error("Mixed content can only be promoted when the mix Succeeded")]
+    MixContentNotSucceeded,
+
+    /// Mixed content has already been promoted into a bcToken
+    #[error("Mixed content has already been promoted into a bcToken")]
+    MixContentAlreadyPromoted,
+
+    /// Invalid parent bcToken for MixContentRecord
+    #[error("Invalid parent bcToken for MixContentRecord")]
+    InvalidParentBcTokenForMixContent,
+
+    /// Invalid rater CandidateLimitRecord for MixContentRecord
+    #[error("Invalid rater CandidateLimitRecord for MixContentRecord")]
+    InvalidRaterForMixContent,
+
+    /// ---- Mixing Rules Errors ----
+
+    /// Content types can't be mixed with each other
+    #[error("Content types can't be mixed with each other")]
+    IncompatibleContentTypesForMixing,
+
+    /// Partially content requires a mix range
+    #[error("Partially content requires a mix range")]
+    MixRangeRequiredForPartialContent,
+
+    /// Mix range can only be given for Partially content
+    #[error("Mix range can only be given for Partially content")]
+    MixRangeOnlyForPartialContent,
+
+    /// Mix range must be non-empty and within the content length
+    #[error("Mix range must be non-empty and within the content length")]
+    InvalidMixRange,
+
+    /// Time shift exceeds the content duration
+    #[error("Time shift exceeds the content duration")]
+    TimeShiftExceedsContentDuration,
+
+    /// ---- Pinhole Errors ----
+
+    /// Invalid Pinhole account address
+    #[error("Invalid Pinhole account address")]
+    InvalidPinholeAddress,
+
+    /// Pinhole already dropped for the party in the round
+    #[error("Pinhole already dropped for the party in the round")]
+    PinholeAlreadyDropped,
+
+    /// Pinhole hasn't been dropped for the party yet
+    #[error("Pinhole hasn't been dropped for the party yet")]
+    PinholeNotDropped,
+
+    /// Pinhole doesn't belong to the KickerCoinOwnerRecord
+    #[error("Pinhole doesn't belong to the KickerCoinOwnerRecord")]
+    InvalidPinholeForKickerCoinOwnerRecord,
+
+    /// Pinhole authority must sign transaction
+    #[error("Pinhole authority must sign transaction")]
+    PinholeAuthorityMustSign,
+
+    /// Pinhole party must sign transaction
+    #[error("Pinhole party must sign transaction")]
+    PinholePartyMustSign,
+
+    /// ---- Triple Multisig Errors ----
+
+    /// Invalid TripleMultisig account address
+    #[error("Invalid TripleMultisig account address")]
+    InvalidTripleMultisigAddress,
+
+    /// Rater and buddy of the TripleMultisig must be different candidates
+    #[error("Rater and buddy of the TripleMultisig must be different candidates")]
+    InvalidTripleMultisigCandidates,
+
+    /// TripleMultisig signer must sign transaction
+    #[error("TripleMultisig signer must sign transaction")]
+    TripleMultisigSignerMustSign,
+
+    /// Not enough TripleMultisig signers
+    #[error("Not enough TripleMultisig signers")]
+    NotEnoughTripleMultisigSigners,
+
+    /// Rating must be greater than zero
+    #[error("Rating must be greater than zero")]
+    InvalidRatingAmount,
+
+    /// Rating overflow
+    #[error("Rating overflow")]
+    RatingOverflow,
+
+    /// ---- Rate Option Errors ----
+
+    /// RateOption issuer must be the rater or the buddy of the MixContentRecord
+    #[error("RateOption issuer must be the rater or the buddy of the MixContentRecord")]
+    InvalidRateOptionIssuer,
+
+    /// Invalid RateOtherRecord for RateOption
+    #[error("Invalid RateOtherRecord for RateOption")]
+    InvalidRateOtherRecordForRateOption,
+
+    /// Too many RateOptions issued
+    #[error("Too many RateOptions issued")]
+    TooManyRateOptionsIssued,
+
+    /// ---- Delegate Errors ----
+
+    /// bcToken authority or its delegate must sign transaction
+    #[error("bcToken authority or its delegate must sign transaction")]
+    BcTokenAuthorityOrDelegateMustSign,
+
+    /// bcToken must be migrated to the current version
+    #[error("bcToken must be migrated to the current version")]
+    BcTokenMustBeMigrated,
+
+    /// bcToken isn't owned by the candidate token owner
+    #[error("bcToken isn't owned by the candidate token owner")]
+    InvalidBcTokenForCandidate,
+
+    /// Invalid Tanistry state: Can't bump self-rate
+    #[error("Invalid Tanistry state: Can't bump self-rate")]
+    InvalidTanistryStateCannotBumpSelfRate,
+
+    /// Candidate deposit amount overflow
+    #[error("Candidate deposit amount overflow")]
+    CandidateDepositOverflow,
+
+    /// Candidate token owner or its delegate must sign transaction
+    #[error("Candidate token owner or its delegate must sign transaction")]
+    CandidateTokenOwnerOrDelegateMustSign,
+
+    /// ---- bcToken Authority Errors ----
+
+    /// bcToken authority can't be changed while the bcToken is in an active Tanistry
+    #[error("bcToken authority can't be changed while the bcToken is in an active Tanistry")]
+    BcTokenInActiveTanistry,
+
+    /// Invalid pending authority for bcToken
+    #[error("Invalid pending authority for bcToken")]
+    InvalidPendingAuthorityForBcToken,
+
+    /// bcToken pending authority must sign transaction
+    #[error("bcToken pending authority must sign transaction")]
+    BcTokenPendingAuthorityMustSign,
+
+    /// ---- Account Migration Errors ----
+
+    /// OutsideBuyerRecord must be migrated to the current version
+    #[error("OutsideBuyerRecord must be migrated to the current version")]
+    OutsideBuyerRecordMustBeMigrated,
+
+    /// ---- bcToken Authority Renounce Errors ----
+
+    /// bcToken authority can be set to None only by an explicit renounce
+    #[error("bcToken authority can be set to None only by an explicit renounce")]
+    InvalidBcTokenAuthorityRenounce,
+
+    /// ---- Outside Buyer Refund Errors ----
+
+    /// Outside buyers can't buy into a Tanistry without the minimum number of candidates
+    #[error("Outside buyers can't buy into a Tanistry without the minimum number of candidates")]
+    TanistryHasNotEnoughCandidates,
+
+    /// Outside buyers can only be refunded from a cancelled Tanistry
+    #[error("Outside buyers can only be refunded from a cancelled Tanistry")]
+    OutsideBuyerRefundOnlyFromCancelledTanistry,
+
+    /// ---- Mix Content bcToken Errors ----
+
+    /// Init bcToken isn't the kicker's bcToken of the Tanistry
+    #[error("Init bcToken isn't the kicker's bcToken of the Tanistry")]
+    InvalidInitBcTokenForMixContent,
+
+    /// Buddy bcToken isn't the bcToken of the buddy candidate
+    #[error("Buddy bcToken isn't the bcToken of the buddy candidate")]
+    InvalidBuddyBcTokenForMixContent,
+
+    /// ---- Ring Chain Errors ----
+
+    /// Only a crowned Tanistry without a next Tanistry can be linked
+    #[error("Only a crowned Tanistry without a next Tanistry can be linked")]
+    InvalidPreviousTanistryCannotLink,
+
+    /// Kicker isn't the Crown of the previous Tanistry
+    #[error("Kicker isn't the Crown of the previous Tanistry")]
+    KickerIsNotCrownOfPreviousTanistry,
+
+    /// Ring isn't the Ring of the Tanistry
+    #[error("Ring isn't the Ring of the Tanistry")]
+    InvalidRingForTanistry,
+
+    /// CC vote must either pull or push the target ring
+    #[error("CC vote must either pull or push the target ring")]
+    InvalidCCVoteSource,
+
+    /// ---- Triple Multisig Signer Errors ----
+
+    /// TripleMultisig signers must be different keys
+    #[error("TripleMultisig signers must be different keys")]
+    DuplicateTripleMultisigSigners,
+
+    /// ---- CC Vote Eligibility Errors ----
+
+    /// Refunded CandidateLimitRecord can't vote for CC
+    #[error("Refunded CandidateLimitRecord can't vote for CC")]
+    CCVoteFromRefundedCandidate,
+
+    /// Candidates of a cancelled Tanistry can't vote for CC
+    #[error("Candidates of a cancelled Tanistry can't vote for CC")]
+    CCVoteFromCancelledTanistry,
+
+    /// ---- Account Migration Errors ----
+
+    /// KickerCoinOwnerRecord must be migrated to the current version
+    #[error("KickerCoinOwnerRecord must be migrated to the current version")]
+    KickerCoinOwnerRecordMustBeMigrated,
+
+    /// Tanistry must be migrated to the current version
+    #[error("Tanistry must be migrated to the current version")]
+    TanistryMustBeMigrated,
+
+    /// CandidateLimitRecord must be migrated to the current version
+    #[error("CandidateLimitRecord must be migrated to the current version")]
+    CandidateLimitRecordMustBeMigrated,
+
+    /// MixContentRecord must be migrated to the current version
+    #[error("MixContentRecord must be migrated to the current version")]
+    MixContentRecordMustBeMigrated,
+
+    /// RateOption must be migrated to the current version
+    #[error("RateOption must be migrated to the current version")]
+    RateOptionMustBeMigrated,
+
+    /// RateOtherRecord must be migrated to the current version
+    #[error("RateOtherRecord must be migrated to the current version")]
+    RateOtherRecordMustBeMigrated,
+
+    /// CCVoteRecord must be migrated to the current version
+    #[error("CCVoteRecord must be migrated to the current version")]
+    CCVoteRecordMustBeMigrated,
+
+    /// Invalid linked account for the migrated account
+    #[error("Invalid linked account for the migrated account")]
+    InvalidLinkedAccountForMigration,
 }
 
 impl PrintProgramError for ShihonError {
@@ -388,3 +811,50 @@ impl From<ShihonError> for ProgramError {
         ProgramError::Custom(e as u32)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn test_shihon_error_round_trips_through_custom_program_error() {
+        let mut code = ShihonError::InvalidInstruction as u32;
+
+        while let Some(error) = ShihonError::from_u32(code) {
+            let program_error: ProgramError = error.clone().into();
+            assert_eq!(program_error, ProgramError::Custom(code));
+
+            let decoded_error = match program_error {
+                ProgramError::Custom(custom_code) => ShihonError::from_u32(custom_code),
+                _ => None,
+            };
+            assert_eq!(decoded_error, Some(error.clone()));
+
+            assert!(!error.to_string().is_empty());
+            assert_eq!(
+                <ShihonError as DecodeError<ShihonError>>::type_of(),
+                "Shihon Error"
+            );
+
+            code += 1;
+        }
+
+        // Codes are contiguous so the walk must have stopped after the last variant
+        assert_eq!(
+            code,
+            ShihonError::InvalidLinkedAccountForMigration as u32 + 1
+        );
+        assert!(ShihonError::from_u32(code + 1).is_none());
+    }
+
+    #[test]
+    fn test_shihon_error_codes_are_stable() {
+        // New variants are appended so the codes clients already decode never shift
+        assert_eq!(ShihonError::InvalidBcTokenMint as u32, 501);
+        assert_eq!(ShihonError::InvalidContentForMixing as u32, 529);
+        assert_eq!(ShihonError::RefundAmountOverflow as u32, 559);
+        assert_eq!(ShihonError::ProgramNotUpgradable as u32, 583);
+        assert_eq!(ShihonError::InvalidBcTokenAddress as u32, 584);
+    }
+}
@@ -0,0 +1,591 @@
+//! Program events
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{clock::Slot, log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Events emitted by the Shihon program through sol_log_data
+/// Each event is logged as a single Borsh serialized data entry ("Program data: <base64>")
+/// Off-chain indexers and notification services decode the entries with decode_event
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum ShihonEvent {
+    /// bcToken has been created as DraftBlankCheck
+    BcTokenCreated {
+        /// bcToken account
+        bc_token: Pubkey,
+        /// bcToken mint
+        bc_token_mint: Pubkey,
+        /// bcToken authority
+        authority: Pubkey,
+        /// bcToken name
+        name: String,
+    },
+
+    /// KickerCoin has been kicked to the coordinator
+    /// The coordinator subscribes to the event to get notified about the kick
+    KickerCoinKicked {
+        /// KickerCoinOwnerRecord issued for the kick
+        kicker_coin_owner_record: Pubkey,
+        /// First kicker or Crown kicking the KickerCoin
+        kicker: Pubkey,
+        /// Coordinator the KickerCoin was kicked to
+        coordinator: Pubkey,
+        /// Amount of KickerCoin
+        amount: u64,
+    },
+
+    /// The coordinator approved the KickerCoin and the Tanistry is formed
+    KickerCoinApproved {
+        /// KickerCoinOwnerRecord the Tanistry is formed from
+        kicker_coin_owner_record: Pubkey,
+        /// Coordinator approving the KickerCoin
+        coordinator: Pubkey,
+        /// Tanistry formed for the KickerCoin
+        tanistry: Pubkey,
+    },
+
+    /// Candidate joined the Tanistry
+    CandidateJoined {
+        /// Tanistry the candidate joined
+        tanistry: Pubkey,
+        /// CandidateLimitRecord issued for the candidate
+        candidate_limit_record: Pubkey,
+        /// Candidate token owner
+        candidate: Pubkey,
+        /// Amount of candidate tokens deposited into the Tanistry
+        deposit_amount: u64,
+    },
+
+    /// Init content and buddy candidate's content have been mixed
+    ContentMixed {
+        /// MixContentRecord issued for the mixing
+        mix_content_record: Pubkey,
+        /// Coordinator giving the input for the mixing
+        coordinator: Pubkey,
+        /// Candidate rating the mixed content
+        rater: Pubkey,
+        /// Buddy candidate whose content was mixed
+        buddy: Pubkey,
+        /// Time shift applied to the init content
+        time_shift_a: u64,
+        /// Time shift applied to the buddy content
+        time_shift_b: u64,
+    },
+
+    /// Candidate rated the buddy candidate
+    Rated {
+        /// Candidate casting the rating
+        rater: Pubkey,
+        /// Buddy candidate receiving the rating
+        buddy: Pubkey,
+        /// MixContentRecord the rating is based on
+        mix_content_record: Pubkey,
+        /// Rating point
+        rating: u64,
+    },
+
+    /// The Crown has been elected from the Tanistry candidates
+    Crowned {
+        /// Tanistry the Crown was elected in
+        tanistry: Pubkey,
+        /// The Crown
+        crown: Pubkey,
+    },
+
+    /// CC vote has been cast
+    CCVoted {
+        /// Ring the vote was cast for
+        target_ring: Pubkey,
+        /// CCVoteRecord of the vote
+        cc_vote_record: Pubkey,
+        /// Voter
+        voter: Pubkey,
+        /// Weight of the vote
+        voter_weight: u64,
+        /// Slot the vote counting happens
+        counting_time: Slot,
+    },
+
+    /// CC vote has been relinquished
+    CCVoteRelinquished {
+        /// Ring the vote was withdrawn from
+        target_ring: Pubkey,
+        /// CCVoteRecord of the vote
+        cc_vote_record: Pubkey,
+        /// Voter
+        voter: Pubkey,
+        /// Weight of the withdrawn vote
+        voter_weight: u64,
+    },
+
+    /// Tanistry vault has been split between the participants
+    TanistrySettled {
+        /// Settled Tanistry
+        tanistry: Pubkey,
+        /// Total amount held by the vault at the settlement
+        settled_amount: u64,
+    },
+
+    /// Coordinator dropped the game and received the refund receipt
+    CoordinatorWithdrawn {
+        /// Tanistry the coordinator withdrew from
+        tanistry: Pubkey,
+        /// Coordinator
+        coordinator: Pubkey,
+        /// Mint of the refund receipt
+        refund_receipt_mint: Pubkey,
+    },
+
+    /// Refund has been paid out of the Tanistry vault
+    Refunded {
+        /// Tanistry paying the refund
+        tanistry: Pubkey,
+        /// Owner of the token account receiving the refund
+        claimant: Pubkey,
+        /// Refunded amount
+        amount: u64,
+    },
+
+    /// ShihonConfig has been created or updated
+    ShihonConfigUpdated {
+        /// ShihonConfig account
+        shihon_config: Pubkey,
+        /// Admin authority of the config
+        admin_authority: Pubkey,
+    },
+
+    /// ShihonConfig pause flags have been changed
+    ShihonPauseFlagsChanged {
+        /// ShihonConfig account
+        shihon_config: Pubkey,
+        /// Admin authority or guardian which changed the flags
+        pause_authority: Pubkey,
+        /// Bitfield of the paused instructions
+        pause_flags: u32,
+    },
+
+    /// Protocol fees have been withdrawn from the treasury
+    TreasuryWithdrawn {
+        /// Treasury token account
+        treasury: Pubkey,
+        /// Token account receiving the fees
+        destination: Pubkey,
+        /// Withdrawn amount
+        amount: u64,
+    },
+
+    /// Tanistry was cancelled for lack of candidates and every deposit became refundable
+    TanistryCancelled {
+        /// Cancelled Tanistry
+        tanistry: Pubkey,
+        /// Number of candidates enrolled when the Tanistry was cancelled
+        candidates: u32,
+        /// Total amount held by the vault at the cancellation
+        settled_amount: u64,
+    },
+
+    /// Ring has been created for the Tanistry chain
+    RingCreated {
+        /// Ring account
+        ring: Pubkey,
+        /// The Tanistry the chain starts from
+        first_tanistry: Pubkey,
+    },
+
+    /// The crowned Tanistry has been added to the Ring
+    RingUpdated {
+        /// Ring account
+        ring: Pubkey,
+        /// Crowned Tanistry
+        tanistry: Pubkey,
+        /// Total deposit amount of the Ring after the update
+        cumulative_deposit_amount: u64,
+        /// Total RFT volume of the Ring after the update
+        cumulative_rft_volume: u64,
+    },
+
+    /// Succeeded mixed content has been promoted into a new bcToken
+    MixedContentPromoted {
+        /// bcToken created for the mixed content
+        bc_token: Pubkey,
+        /// MixContentRecord the content was promoted from
+        mix_content_record: Pubkey,
+        /// The init content's bcToken
+        parent_bc_token_a: Pubkey,
+        /// The buddy content's bcToken
+        parent_bc_token_b: Pubkey,
+    },
+
+    /// Pinhole has been dropped for the round of the KickerCoinOwnerRecord
+    /// Note: The main wallet of the party isn't included on purpose
+    PinholeDropped {
+        /// Pinhole account
+        pinhole: Pubkey,
+        /// KickerCoinOwnerRecord the pinhole was dropped for
+        kicker_coin_owner_record: Pubkey,
+        /// Round of the KickerCoinOwnerRecord
+        round: u8,
+    },
+
+    /// TripleMultisig has been created for the rater and buddy pair
+    TripleMultisigCreated {
+        /// TripleMultisig account
+        triple_multisig: Pubkey,
+        /// The rater's CandidateLimitRecord
+        rater: Pubkey,
+        /// The buddy's CandidateLimitRecord
+        buddy: Pubkey,
+    },
+
+    /// RateOption has been created by the Roydamna
+    RateOptionCreated {
+        /// RateOption account
+        rate_option: Pubkey,
+        /// The issuer Roydamna's CandidateLimitRecord
+        issuer_roydamna: Pubkey,
+        /// MixContentRecord governed by the RateOption
+        mix_content_record: Pubkey,
+        /// The issue number of the RateOption
+        number_of_issue: u8,
+    },
+
+    /// RateOption has been signed off by the Roydamna
+    RateOptionSignedOff {
+        /// RateOption account
+        rate_option: Pubkey,
+        /// The issuer Roydamna's CandidateLimitRecord
+        issuer_roydamna: Pubkey,
+        /// RateOtherRecord governed by the RateOption
+        rate_other_record: Pubkey,
+    },
+
+    /// RateOption has been removed by the Roydamna
+    RateOptionRemoved {
+        /// RateOption account
+        rate_option: Pubkey,
+        /// The issuer Roydamna's CandidateLimitRecord
+        issuer_roydamna: Pubkey,
+    },
+
+    /// Candidate bumped the self-rate depositing more into the Tanistry
+    SelfRateBumped {
+        /// Tanistry the deposit was added to
+        tanistry: Pubkey,
+        /// CandidateLimitRecord of the candidate
+        candidate_limit_record: Pubkey,
+        /// Amount of candidate tokens added to the deposit
+        amount: u64,
+        /// Total deposit amount of the candidate after the bump
+        deposit_amount: u64,
+    },
+
+    /// Candidate token owner has set the delegate of the CandidateLimitRecord
+    CandidateDelegateSet {
+        /// CandidateLimitRecord account
+        candidate_limit_record: Pubkey,
+        /// New delegate, None if the delegate was removed
+        candidate_delegate: Option<Pubkey>,
+    },
+
+    /// bcToken authority has set the delegate of the bcToken
+    BcTokenDelegateSet {
+        /// bcToken account
+        bc_token: Pubkey,
+        /// New delegate, None if the delegate was removed
+        delegate: Option<Pubkey>,
+    },
+
+    /// bcToken authority has proposed the new authority of the bcToken
+    BcTokenAuthorityProposed {
+        /// bcToken account
+        bc_token: Pubkey,
+        /// Proposed authority, None if the proposal was withdrawn
+        pending_authority: Option<Pubkey>,
+    },
+
+    /// The authority of the bcToken has changed
+    BcTokenAuthoritySet {
+        /// bcToken account
+        bc_token: Pubkey,
+        /// New authority, None if the bcToken was frozen
+        authority: Option<Pubkey>,
+    },
+
+    /// The coordinator denied the KickerCoin and the escrowed KickerCoin went back to the kicker
+    KickerCoinDenied {
+        /// KickerCoinOwnerRecord account disposed by the deny
+        kicker_coin_owner_record: Pubkey,
+        /// The kicker
+        kicker: Pubkey,
+        /// The coordinator who denied the KickerCoin
+        coordinator: Pubkey,
+        /// The refunded amount
+        amount: u64,
+    },
+
+    /// Outside buyer bought into the exceeded rate of the Tanistry
+    ExceededRateTokenBought {
+        /// Tanistry account
+        tanistry: Pubkey,
+        /// OutsideBuyerRecord account
+        outside_buyer_record: Pubkey,
+        /// The outside buyer
+        outside_buyer: Pubkey,
+        /// The amount deposited into the Tanistry vault
+        deposit_amount: u64,
+        /// The fee paid to the treasury
+        fee_amount: u64,
+    },
+
+    /// Protocol fee has been taken into the treasury
+    ProtocolFeeCollected {
+        /// Treasury token account
+        treasury: Pubkey,
+        /// KickerCoinOwnerRecord or CandidateLimitRecord recording the fee
+        record: Pubkey,
+        /// The fee paid to the treasury
+        fee_amount: u64,
+    },
+}
+
+impl ShihonEvent {
+    /// Logs the event as Borsh serialized program data
+    pub fn emit(&self) {
+        sol_log_data(&[&self.try_to_vec().unwrap()]);
+    }
+}
+
+/// Decodes the event from a program data entry logged by ShihonEvent::emit
+pub fn decode_event(data: &[u8]) -> Result<ShihonEvent, ProgramError> {
+    ShihonEvent::try_from_slice(data).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_events() -> Vec<ShihonEvent> {
+        vec![
+            ShihonEvent::BcTokenCreated {
+                bc_token: Pubkey::new_unique(),
+                bc_token_mint: Pubkey::new_unique(),
+                authority: Pubkey::new_unique(),
+                name: "bcToken".to_string(),
+            },
+            ShihonEvent::KickerCoinKicked {
+                kicker_coin_owner_record: Pubkey::new_unique(),
+                kicker: Pubkey::new_unique(),
+                coordinator: Pubkey::new_unique(),
+                amount: 10,
+            },
+            ShihonEvent::CCVoted {
+                target_ring: Pubkey::new_unique(),
+                cc_vote_record: Pubkey::new_unique(),
+                voter: Pubkey::new_unique(),
+                voter_weight: 100,
+                counting_time: 432_000,
+            },
+            ShihonEvent::Refunded {
+                tanistry: Pubkey::new_unique(),
+                claimant: Pubkey::new_unique(),
+                amount: 50,
+            },
+            ShihonEvent::ShihonConfigUpdated {
+                shihon_config: Pubkey::new_unique(),
+                admin_authority: Pubkey::new_unique(),
+            },
+            ShihonEvent::ShihonPauseFlagsChanged {
+                shihon_config: Pubkey::new_unique(),
+                pause_authority: Pubkey::new_unique(),
+                pause_flags: 0b101,
+            },
+            ShihonEvent::TreasuryWithdrawn {
+                treasury: Pubkey::new_unique(),
+                destination: Pubkey::new_unique(),
+                amount: 25,
+            },
+            ShihonEvent::TanistryCancelled {
+                tanistry: Pubkey::new_unique(),
+                candidates: 1,
+                settled_amount: 60,
+            },
+            ShihonEvent::RingCreated {
+                ring: Pubkey::new_unique(),
+                first_tanistry: Pubkey::new_unique(),
+            },
+            ShihonEvent::RingUpdated {
+                ring: Pubkey::new_unique(),
+                tanistry: Pubkey::new_unique(),
+                cumulative_deposit_amount: 150,
+                cumulative_rft_volume: 50,
+            },
+            ShihonEvent::PinholeDropped {
+                pinhole: Pubkey::new_unique(),
+                kicker_coin_owner_record: Pubkey::new_unique(),
+                round: 1,
+            },
+            ShihonEvent::TripleMultisigCreated {
+                triple_multisig: Pubkey::new_unique(),
+                rater: Pubkey::new_unique(),
+                buddy: Pubkey::new_unique(),
+            },
+            ShihonEvent::SelfRateBumped {
+                tanistry: Pubkey::new_unique(),
+                candidate_limit_record: Pubkey::new_unique(),
+                amount: 10,
+                deposit_amount: 60,
+            },
+            ShihonEvent::CandidateDelegateSet {
+                candidate_limit_record: Pubkey::new_unique(),
+                candidate_delegate: Some(Pubkey::new_unique()),
+            },
+            ShihonEvent::BcTokenDelegateSet {
+                bc_token: Pubkey::new_unique(),
+                delegate: None,
+            },
+            ShihonEvent::BcTokenAuthorityProposed {
+                bc_token: Pubkey::new_unique(),
+                pending_authority: Some(Pubkey::new_unique()),
+            },
+            ShihonEvent::BcTokenAuthoritySet {
+                bc_token: Pubkey::new_unique(),
+                authority: None,
+            },
+            ShihonEvent::RateOptionCreated {
+                rate_option: Pubkey::new_unique(),
+                issuer_roydamna: Pubkey::new_unique(),
+                mix_content_record: Pubkey::new_unique(),
+                number_of_issue: 1,
+            },
+            ShihonEvent::RateOptionSignedOff {
+                rate_option: Pubkey::new_unique(),
+                issuer_roydamna: Pubkey::new_unique(),
+                rate_other_record: Pubkey::new_unique(),
+            },
+            ShihonEvent::RateOptionRemoved {
+                rate_option: Pubkey::new_unique(),
+                issuer_roydamna: Pubkey::new_unique(),
+            },
+            ShihonEvent::MixedContentPromoted {
+                bc_token: Pubkey::new_unique(),
+                mix_content_record: Pubkey::new_unique(),
+                parent_bc_token_a: Pubkey::new_unique(),
+                parent_bc_token_b: Pubkey::new_unique(),
+            },
+            ShihonEvent::KickerCoinDenied {
+                kicker_coin_owner_record: Pubkey::new_unique(),
+                kicker: Pubkey::new_unique(),
+                coordinator: Pubkey::new_unique(),
+                amount: 90,
+            },
+            ShihonEvent::ExceededRateTokenBought {
+                tanistry: Pubkey::new_unique(),
+                outside_buyer_record: Pubkey::new_unique(),
+                outside_buyer: Pubkey::new_unique(),
+                deposit_amount: 36,
+                fee_amount: 4,
+            },
+            ShihonEvent::ProtocolFeeCollected {
+                treasury: Pubkey::new_unique(),
+                record: Pubkey::new_unique(),
+                fee_amount: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_decode_event() {
+        for event in create_test_events() {
+            // Arrange
+            let data = event.try_to_vec().unwrap();
+
+            // Act
+            let decoded_event = decode_event(&data).unwrap();
+
+            // Assert
+            assert_eq!(event, decoded_event);
+        }
+    }
+
+    #[test]
+    fn test_event_discriminants_are_stable() {
+        // Arrange
+        let events = vec![
+            (
+                ShihonEvent::BcTokenCreated {
+                    bc_token: Pubkey::new_unique(),
+                    bc_token_mint: Pubkey::new_unique(),
+                    authority: Pubkey::new_unique(),
+                    name: "bcToken".to_string(),
+                },
+                0,
+            ),
+            (
+                ShihonEvent::Refunded {
+                    tanistry: Pubkey::new_unique(),
+                    claimant: Pubkey::new_unique(),
+                    amount: 50,
+                },
+                11,
+            ),
+            (
+                ShihonEvent::MixedContentPromoted {
+                    bc_token: Pubkey::new_unique(),
+                    mix_content_record: Pubkey::new_unique(),
+                    parent_bc_token_a: Pubkey::new_unique(),
+                    parent_bc_token_b: Pubkey::new_unique(),
+                },
+                18,
+            ),
+            (
+                ShihonEvent::SelfRateBumped {
+                    tanistry: Pubkey::new_unique(),
+                    candidate_limit_record: Pubkey::new_unique(),
+                    amount: 10,
+                    deposit_amount: 60,
+                },
+                24,
+            ),
+        ];
+
+        for (event, discriminant) in events {
+            // Act
+            let data = event.try_to_vec().unwrap();
+
+            // Assert
+            assert_eq!(data[0], discriminant);
+        }
+    }
+
+    #[test]
+    fn test_decode_event_from_program_data_log() {
+        // Arrange
+        let event = ShihonEvent::Crowned {
+            tanistry: Pubkey::new_unique(),
+            crown: Pubkey::new_unique(),
+        };
+
+        let log = format!(
+            "Program data: {}",
+            base64::encode(event.try_to_vec().unwrap())
+        );
+
+        // Act
+        let data = base64::decode(log.strip_prefix("Program data: ").unwrap()).unwrap();
+        let decoded_event = decode_event(&data).unwrap();
+
+        // Assert
+        assert_eq!(event, decoded_event);
+    }
+
+    #[test]
+    fn test_decode_event_with_invalid_data_error() {
+        // Arrange
+        let data = vec![u8::MAX, 1, 2];
+
+        // Act
+        let err = decode_event(&data).err().unwrap();
+
+        // Assert
+        assert_eq!(err, ProgramError::InvalidAccountData);
+    }
+}
@@ -1,14 +1,22 @@
 use crate::{
+    pda::{
+        get_bc_token_address, get_candidate_limit_record_address,
+        get_cc_vote_record_address, get_coordinator_refund_receipt_mint_address,
+        get_coordinator_refund_receipt_token_address, get_kicker_coin_owner_record_address,
+        get_mix_content_record_address, get_outside_buyer_record_address, get_pinhole_address,
+        get_rate_option_address, get_rate_other_record_address,
+        get_ring_address, get_triple_multisig_address, get_shihon_config_address, get_tanistry_address,
+        get_tanistry_token_holding_address, get_treasury_address,
+    },
     state::{
-        bc_token::{get_bc_token_address, get_bc_token_holding_address, BcToken},
-        bc_token_metadata::BcTokenMetadata,
-        kicker_coin_owner_record::get_kicker_coin_owner_record_address,
+        bc_token_metadata::{BcTokenMetadata, ContentRange},
+        enums::{PinholeSide, VoteSource},
+        shihon_config::ShihonConfigArgs,
     },
     tools::bpf_loader_upgradeable::get_program_data_address,
 };
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    bpf_loader_upgradeable,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program, sysvar,
@@ -20,14 +28,18 @@ use solana_program::{
 pub enum ShihonInstruction {
     /// Draft Blank Check instruction
     /// Note: "bc" means "Blank Check"
+    /// The blank check holds only its authority, the content metadata is blank until it's created as bcToken
     ///
     /// Accounts expected:
     ///
-    /// 0. `[writable]` bc account. PDA seeds:['bc',name]
-    /// 1. `[]` bc authority
-    /// 2. `[]` System
-    /// 3. `[]` SPL Token
-    /// 4. `[]` Sysvar Rent
+    /// 0. `[writable]` bcToken account. PDA seeds:['bc-token', name]
+    /// 1. `[signer]` bcToken authority
+    /// 2. `[]` bcToken Mint
+    /// 3. `[writable, signer]` Payer
+    /// 4. `[]` System
+    /// 5. `[]` Sysvar Rent
+    /// 6. `[]` Sysvar Clock
+    /// 7. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
     ///
     DraftBlankCheck { name: String },
 
@@ -36,67 +48,75 @@ pub enum ShihonInstruction {
     ///
     /// Accounts expected:
     ///
-    /// 0. `[writable]` bcToken account. PDA seeds:['bcToken', config ]
+    /// 0. `[writable]` bcToken account. PDA seeds:['bc-token', name]
     /// 1. `[signer]` bcToken authority
     /// 2. `[]` bcToken Mint
-    /// 3. `[writable]` KickerCoin's Token Holding account. PDA seeds: ['bcToken',bcToken,kicker_coin_mint ]
-    ///     The account will be created with the bcToken PDA as its owner
-    /// 4. `[signer]` Payer
-    /// 5. `[writable]` Candidate's self-rating Coin Token Holding account PDA seeds: ['bcToken',bcToken,candidate_mint]
-    /// 6. `[writable]` BcTokenMetadata account. PDA seeds: ['bcToken-metadata', bcToken ]
-    /// 7. `[]` System
-    /// 8. `[]` SPL Token
-    /// 9. `[]` Sysvar Rent
+    /// 3. `[writable, signer]` Payer
+    /// 4. `[]` System
+    /// 5. `[]` Sysvar Rent
+    /// 6. `[]` Sysvar Clock
+    /// 7. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
     CreateBcToken {
+        name: String,
         amount: u64,
         config: BcTokenMetadata,
     },
 
     /// Discard bcToken instruction
     /// Note: If you delete bc, bcTokenMetadata in that bc would be deleted immediately
+    /// A bcToken taking part in a Tanistry can't be discarded
+    ///
     /// Accounts expected:
     ///
-    /// 0. `[writable]` bcToken account. PDA seeds:['bcToken', config]
+    /// 0. `[writable]` bcToken account. PDA seeds:['bc-token', name]
     /// 1. `[signer]` bcToken authority
-    /// 2. `[]` bcToken Mint
-    /// 3. `[signer]` Payer
+    /// 2. `[writable]` Beneficiary account which would receive lamports from the disposed bcToken account
     DiscardBcToken,
 
     /// Kicking to coordinator instruction to next coordinator
     ///  ~ Terminate 1
     /// The first kicker decides how much KickerCoin by himself, and then throws it with own BcToken at other's BcToken to initialize (e)RFT as the tanistry ring. Note that all BcTokens here must not have been initialized to (e)RFT at any time.
-    ///
+    /// The KickerCoin is escrowed in the Tanistry token holding account until the coordinator approves or denies it
     ///
     /// Accounts expected:
     ///
-    ///   0. `[signer]` kicker's account
-    ///   1. `[]` TokenOwnerRecord account of the owner
-    ///   2. `[signer]` The account of the person as first kicker initializing the two BcToken into Tanistry Ring
-    ///   3. `[writable]` KickerCoin Owner Record PDA seeds: ['KickerCoinOwnerRecord', coordinator ]
-    ///   4. `[writable]` Coordinator's bcToken account to flag as received KickerCoin from first kicker
-    ///   5. `[]` Clock sysvar
+    ///   0. `[writable]` KickerCoinOwnerRecord account. PDA seeds: ['kicker-coin-owner-record', kicker, coordinator]
+    ///   1. `[writable]` The kicker's bcToken account kicking the KickerCoin
+    ///   2. `[signer]` The kicker, the bcToken authority and the transfer authority of the KickerCoin token source
+    ///   3. `[writable]` The kicker's KickerCoin token source account
+    ///   4. `[writable]` Tanistry token holding account. PDA seeds: ['shihon', tanistry, kicker_coin_mint]
+    ///      The account is created with the Tanistry PDA as its owner
+    ///   5. `[]` Tanistry account. PDA seeds: ['tanistry', kicker_coin_owner_record]
+    ///      The Tanistry itself is created when the coordinator approves the KickerCoin
+    ///   6. `[]` KickerCoin Mint
+    ///   7. `[writable, signer]` Payer
+    ///   8. `[]` System program
+    ///   9. `[]` SPL Token program
+    ///   10. `[]` Sysvar Rent
+    ///   11. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    ///   12. `[writable]` Treasury token account of the KickerCoin mint. PDA seeds: ['shihon-treasury', kicker_coin_mint]
+    ///       The config's kicker_coin_fee_bps of the KickerCoin is transferred into the treasury
     KickingToCoordinator { coordinator: Pubkey, amount: u64 },
 
     /// Approve KickerCoin instruction
     /// Coordinator received KickerCoin can choose it approve or deny
-    /// If approve it, you need to use this instruction
+    /// If approve it, you need to use this instruction and the Tanistry is formed
     ///
     /// Accounts expected:
     ///
-    ///
-    /// 0. `[]` first kicker's bcToken account
-    /// 1. `[signer]` Coordinator's bcToken account
-    /// 2. `[Writable]` The Tanistry account holding the Tanistry info. PDA seeds: ['tanistry', bcToken, tanistry_account]
-    /// 3. `[writable]` The Coordinator's token account for send his input PDA seeds: ['account-tanistry', bcToken, tanistry_account]
-    /// 4. `[writable]` The Kicker's token Source account for the token he send his KickerCoin into Coordinator's wallet.
-    /// 5. `[signer]` Tanistry authority (Coordinator has this!)
-    /// 6. `[]` The Coordinator's token account fot receive the NFT as Refund when he drop the game
-    /// 7. `[writable]` KickerCoin Owner Record account PDA seeds: ['tanistry', bcToken, kicker_coin_owner_record]
-    /// 8. `[]` The kicker's token account that will receive tokens (for refund)
-    /// 9. `[writable]` Candidate's self-rating Coin Token Holding account PDA seeds: ['bcToken',bcToken,candidate_mint]
-    /// 10. `[]` System program
-    /// 11. `[]` Sysvar Rent
-    /// 12. `[]` Clock sysvar
+    /// 0. `[writable]` Tanistry account. PDA seeds: ['tanistry', kicker_coin_owner_record]
+    /// 1. `[writable]` KickerCoinOwnerRecord account
+    /// 2. `[signer]` Coordinator the KickerCoin was kicked to
+    /// 3. `[]` Tanistry token holding account. PDA seeds: ['shihon', tanistry, tanistry_token_mint]
+    /// 4. `[]` Tanistry token Mint (the KickerCoin Mint)
+    /// 5. `[writable, signer]` Payer
+    /// 6. `[]` System program
+    /// 7. `[]` Sysvar Rent
+    /// 8. `[]` Sysvar Clock
+    /// 9. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 10. `[writable]` Optional previous crowned Tanistry when the Crown kicked its KickerCoin on
+    ///     The new Tanistry is linked to it and joins its Ring, without it the Tanistry starts a new chain
+    /// 11. `[]` Optional Crown's CandidateLimitRecord of the previous Tanistry. Required with the previous Tanistry
     ApproveKickerCoin {
         /// for making new RFT
         coordinator_input: String,
@@ -104,135 +124,579 @@ pub enum ShihonInstruction {
 
     /// Deny KickerCoin instruction
     /// If coordinator cancels KickerCoin by first kicker for candidate before Tanistry
+    /// The escrowed KickerCoin is returned to the kicker and the KickerCoinOwnerRecord is disposed
     ///
     /// Accounts expected:
     ///
-    /// 0. `[]` first kicker's bcToken account
-    /// 1. `[signer]` Tanistry authority (Coordinator has this!)
-    /// 2. `[writable]` KickerCoin Owner Record account PDA seeds: ['tanistry', bcToken, kicker_coin_owner_record]
-    /// 3. `[]` The kicker's token account that will receive tokens (for refund)
-    /// 4. `[writable]` Candidate's self-rating Coin Token Holding account PDA seeds: ['bcToken',bcToken,candidate_mint]
+    /// 0. `[writable]` KickerCoinOwnerRecord account
+    /// 1. `[signer]` Coordinator the KickerCoin was kicked to
+    /// 2. `[writable]` The kicker's bcToken account
+    /// 3. `[writable]` Tanistry token holding account. PDA seeds: ['shihon', tanistry, kicker_coin_mint]
+    /// 4. `[]` Tanistry account. PDA seeds: ['tanistry', kicker_coin_owner_record]
+    /// 5. `[writable]` The kicker's token account that will receive the KickerCoin back
+    /// 6. `[]` KickerCoin Mint
+    /// 7. `[]` SPL Token program
+    /// 8. `[writable]` Beneficiary account which would receive lamports from the disposed KickerCoinOwnerRecord account
     DenyKickerCoin,
 
     /// Candidate instruction
     /// This is a parameter for the candidate. Here, BcToken can be submitted only for (e)RFT to perform the candidate.
+    /// The deposit without the fee is transferred into the Tanistry vault as the candidate's self-rate
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` Candidate's account for becoming Roydamna in the Tanistry.
-    /// 1. `[writable]` The Candidate's token Source account for the token he send as self-rate value. All tokens from the account will be transferred to the Holding account.
-    /// 2. `[writable]` Tanistry Token Holding account. PDA seeds: ['tanistry',bcToken, tanistry_token_mint]
-    /// 3. `[]` The Candidate's token account for the token they will receive cash as refund when game finished
-    /// 4. `[signer]` The kicker's token account that will receive tokens.
-    /// 5. `[writable]` CandidateLimitRecord account
-    /// 6. `[writable]` The Tanistry account holding the Tanistry info PDA seeds: ['tanistry', bcToken, tanistry_token_mint]
-    /// 7. `[]` The SPL Token program
-    /// 8. `[]` System program
+    /// 0. `[writable]` Tanistry account
+    /// 1. `[writable]` CandidateLimitRecord account. PDA seeds: ['candidate-limit-record', tanistry, tanistry_token_mint, candidate_token_owner]
+    /// 2. `[signer]` Candidate token owner, the transfer authority of the token source and the authority of the candidate's bcToken
+    /// 3. `[writable]` The Candidate's token source account for the token he send as self-rate value
+    /// 4. `[writable]` Tanistry token holding account. PDA seeds: ['shihon', tanistry, tanistry_token_mint]
+    /// 5. `[writable]` The Candidate's bcToken account standing in the Tanistry
+    /// 6. `[writable, signer]` Payer
+    /// 7. `[]` System program
+    /// 8. `[]` SPL Token program
     /// 9. `[]` Sysvar Rent
-    Candidate { coordinator: Pubkey, amount: u64 },
+    /// 10. `[]` Sysvar Clock
+    /// 11. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 12. `[writable]` Treasury token account of the Tanistry token mint. PDA seeds: ['shihon-treasury', tanistry_token_mint]
+    ///     The config's candidate_fee_bps of the deposit is transferred into the treasury
+    /// 13. `[]` Tanistry token Mint
+    Candidate { amount: u64 },
 
     /// Terminate 1 ~ Terminate 2 and Terminate 4 ~ Terminate 5
-    /// Accounts expected:
+    /// Mixes the init content with the buddy candidate's content for the rater
+    /// The content types must be compatible, Partially contents require a range within the content length
+    /// and the time shifts can't exceed the content durations
     ///
+    /// Accounts expected:
     ///
-    /// 0. `[]` Coordinator's account
-    /// 1. `[writable]` The Rater Candidate's token account for send his input the rating amount
-    /// 2. `[writable]` The Buddy Candidate's token account for mix the own content
-    /// 3. `[writable]` The first kicker's (Initial content) account for mix the content
-    /// 4. `[]` The coordinator's input token account for minting RFT for the rating
-    /// 5. `[Writable]` The Tanistry account holding the Tanistry info  PDA seeds: ['shihon', mix, index]
-    /// 6. `[signer]` Content Authority (Rater or Buddy)
-    /// 7. `[signer]` Buddy Candidate's account
-    /// 8. `[writable]` MixContentRecord account
-    /// 9. `[]` The rating program
-    /// 10. `[]` System program
-    /// 11. `[]` Sysvar Rent
+    /// 0. `[]` The coordinator of the KickerCoinOwnerRecord
+    /// 1. `[]` The Rater Candidate's CandidateLimitRecord account
+    /// 2. `[signer]` The Rater Candidate's token owner
+    /// 3. `[]` The Buddy Candidate's CandidateLimitRecord account
+    /// 4. `[]` KickerCoinOwnerRecord account the rater's Tanistry was formed from
+    /// 5. `[]` The init content's bcToken account, the kicker's bcToken of the KickerCoinOwnerRecord
+    /// 6. `[]` The buddy content's bcToken account, the bcToken of the Buddy Candidate's CandidateLimitRecord
+    /// 7. `[writable]` MixContentRecord account. PDA seeds: ['mix-content-record', rater_candidate, buddy_candidate]
+    /// 8. `[writable, signer]` Payer
+    /// 9. `[]` System program
+    /// 10. `[]` Sysvar Rent
+    /// 11. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 12. `[]` TripleMultisig account. PDA seeds: ['triple-multisig', rater_candidate, buddy_candidate]
+    /// 13. `[signer]` At least two of the TripleMultisig signers: the rater, the buddy and the first kicker
+    ///     The rater's and the buddy's positions can also be signed by their candidate delegates
     MixContent {
+        /// Time shift applied to the init content
         time_shift_a: u64,
+        /// Time shift applied to the buddy content
         time_shift_b: u64,
+        /// The part of the init content taken into the mix, only for Partially content
+        range_a: Option<ContentRange>,
+        /// The part of the buddy content taken into the mix, only for Partially content
+        range_b: Option<ContentRange>,
     },
 
     /// Rate other content instruction
+    /// Puts the rating on the buddy's content the rater mixed with
+    /// Needs two of the three approvals of the rater, the buddy and the first kicker
+    ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` Rater Candidate account
-    /// 1. `[writable]` The Rater Candidate account for send his input the rating amount
-    /// 2. `[writable]` The Buddy Candidate's token account for mix the content
-    /// 3. `[writable]` The first kicker's (Initial content) account for mix the content
-    /// 4. `[]` The coordinator's input token account for minting RFT for the rating
-    /// 5. `[Writable]` The Tanistry account holding the Tanistry info  PDA seeds: ['shihon', mix, index]
-    /// 6. `[writable]` MixContentRecord account
-    /// 7. `[]` The rating program
-    /// 8. `[]` System program
-    /// 9. `[]` Sysvar Rent
+    /// 0. `[]` The Rater Candidate's CandidateLimitRecord account
+    /// 1. `[signer]` The Rater Candidate's token owner
+    /// 2. `[writable]` The Buddy Candidate's CandidateLimitRecord account
+    /// 3. `[]` MixContentRecord account. PDA seeds: ['mix-content-record', rater_candidate, buddy_candidate]
+    /// 4. `[writable]` RateOtherRecord account. PDA seeds: ['rate-other-record', rater_candidate, buddy_candidate]
+    /// 5. `[]` TripleMultisig account. PDA seeds: ['triple-multisig', rater_candidate, buddy_candidate]
+    /// 6. `[writable, signer]` Payer
+    /// 7. `[]` System program
+    /// 8. `[]` Sysvar Rent
+    /// 9. `[]` Sysvar Clock
+    /// 10. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 11. `[signer]` At least two of the TripleMultisig signers: the rater, the buddy and the first kicker
+    ///     The rater's and the buddy's positions can also be signed by their candidate delegates
     RateOtherContent { rating: u64 },
 
     /// BumpSelfRate instruction
-    /// Terminate 3 ~ Terminate 4
-    /// Accounts expected:
+    /// Adds more coin to the candidate's self-rate with the same bcToken it stands with
+    /// The candidate token owner or its delegate can bump
     ///
+    /// Accounts expected:
     ///
-    /// 0. `[signer]` The Candidate authority of bcToken
-    /// 1. `[signer]` bcToken Authority
-    /// 2. `[writable]` The token source of same content holder as candidate
-    /// 3. `[writable]` Temporary token account for add self-rating on the same Candidate's content PDA seeds: []
-    /// 4. `[Writable]` The Tanistry account holding the Tanistry info  PDA seeds: []
-    /// 5. `[writable]` CandidateLimitRecord account
-    /// 6. `[]` The SPL Token program
-    /// 7. `[]` System program
-    /// 8. `[]` Sysvar Rent
+    /// 0. `[]` Tanistry account
+    /// 1. `[writable]` Tanistry token holding account. PDA seeds: ['shihon', tanistry, tanistry_token_mint]
+    /// 2. `[writable]` The candidate's token source
+    /// 3. `[signer]` The candidate token owner or its delegate, the transfer authority of the token source
+    /// 4. `[writable]` CandidateLimitRecord account
+    /// 5. `[]` The candidate's bcToken account
+    /// 6. `[signer]` The bcToken authority or its delegate
+    /// 7. `[]` Tanistry token mint
+    /// 8. `[]` SPL Token program
+    /// 9. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
     BumpSelfRate { amount: u64 },
 
     /// Buy exceeded rate token instruction
     /// Terminate 5 ~ Terminate 6
+    /// The deposit without the fee is transferred into the Tanistry vault and shared by the candidates once settled
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` Outside Buyer account
-    /// 1. `[writable]`  OutsideBuyerRecord account. PDA seeds: ['tanistry',bcToken, tanistry_token_mint, tanistry_token_owner]
-    /// 2. `[writable]` The Seller's token account for selling own token as NFT
-    /// 3. `[writable]` The Buyer's token account to buy NFT of Candidate (Source)
-    /// 4. `[]` The Buyer's token account for the token they will receive refund if won the game
-    /// 5. `[Writable]` The Outside buyer account holding the OutsideBuyer info  PDA seeds: []
-    /// 6. `[]` The seller token Mint
-    /// 7. `[]` System program
-    /// 8. `[]` Clock sysvar
-    /// 9. `[]` Sysvar Rent
-    BuyExceededRateToken { token: Pubkey, amount: u64 },
+    /// 0. `[writable]` Tanistry account
+    /// 1. `[writable]` OutsideBuyerRecord account. PDA seeds: ['outside-buyer-record', tanistry, tanistry_token_mint, outside_buyer]
+    /// 2. `[signer]` Outside buyer, the transfer authority of the token source
+    /// 3. `[writable]` The Buyer's token source account
+    /// 4. `[writable]` Tanistry token holding account. PDA seeds: ['shihon', tanistry, tanistry_token_mint]
+    /// 5. `[writable, signer]` Payer
+    /// 6. `[]` System program
+    /// 7. `[]` SPL Token program
+    /// 8. `[]` Sysvar Rent
+    /// 9. `[]` Sysvar Clock
+    /// 10. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 11. `[writable]` Treasury token account of the Tanistry token mint. PDA seeds: ['shihon-treasury', tanistry_token_mint]
+    ///     The config's outside_buyer_fee_bps of the deposit is transferred into the treasury
+    /// 12. `[]` Tanistry token Mint
+    BuyExceededRateToken { amount: u64 },
 
     /// Crowing instruction
     /// Terminate 6 ~
+    /// The coordinator elects the Crown from the Tanistry candidates
+    /// The coordinator can't crown after dropping the game with CoordinatorWithdraw
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` The account of the person as Coordinator taking the Tanistry.
-    /// 1. `[writable]` The Coordinator's token account for send his input
-    /// 2. `[writable]` The Coordinator's token account for receive the NFT as Refund when he drop the game
-    /// 3. `[writable]` KickerCoin Owner Record(from Coordinator to Candidate as next Crown)
-    /// 4. `[writable]` The new Crown's token account for choosing next coordinator
-    /// 5. `[Writable]` The Tanistry account holding the Tanistry info  PDA seeds: []
-    /// 6. `[]` The token program
-    /// 7. `[]` System program
-    /// 8. `[]` Sysvar Rent
+    /// 0. `[signer]` The coordinator of the KickerCoinOwnerRecord the Tanistry is formed from
+    /// 1. `[writable]` Tanistry account. PDA seeds: ['tanistry', kicker_coin_owner_record]
+    /// 2. `[]` KickerCoinOwnerRecord account
+    /// 3. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 4. `[writable]` Ring account of the Tanistry chain. PDA seeds: ['ring', first_tanistry]
+    ///    The deposits and the RFT volume of the Tanistry are added to the Ring
+    /// 5. `[]` CandidateLimitRecord accounts in the order of the Tanistry's candidate list
     Crowning { crown: Pubkey },
 
     /// CC Voting instruction
     ///
     /// Accounts expected:
     ///
-    /// `[writable]` CCVoteRecord account PDA seeds: []
-    /// `[signer]` voter
-    /// `[]`
-    /// `[]`
+    /// 0. `[writable]` The target Ring account holding the CC vote tally. PDA seeds: ['ring', first_tanistry]
+    /// 1. `[writable]` CCVoteRecord account PDA seeds: ['cc-vote-record', candidate_limit_record]
+    ///    The relinquished record of the CandidateLimitRecord is reused within the counting time of the target ring
+    /// 2. `[]` The voter's CandidateLimitRecord account
+    /// 3. `[signer]` voter (candidate token owner or its delegate), the vote is recorded for the owner
+    /// 4. `[signer]` Payer
+    /// 5. `[]` System program
+    /// 6. `[]` Sysvar Rent
+    /// 7. `[]` Clock sysvar
+    /// 8. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 9. `[]` The voter's Tanistry the CandidateLimitRecord belongs to
+    /// 10. `[]` The voter's Ring, the Ring of the voter's Tanistry
+    ///     A Pull vote needs a target ring smaller than the voter's ring and a Push vote a bigger one
+    ///
+    /// The first vote for the target ring opens the counting window shared by every vote for the ring
+    /// Refunded CandidateLimitRecords and the candidates of a cancelled Tanistry can't vote
     VoteForCC {
         target_ring: Pubkey,
+        vote: VoteSource,
         // config:
     },
+
+    /// Relinquish CC vote instruction
+    /// The voter can withdraw the vote until the counting time of the target ring
+    /// and the weight is removed from the target ring
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The target Ring account holding the CC vote tally. PDA seeds: ['ring', first_tanistry]
+    /// 1. `[writable]` CCVoteRecord account PDA seeds: ['cc-vote-record', candidate_limit_record]
+    /// 2. `[signer]` voter (candidate token owner or its delegate)
+    /// 3. `[]` Clock sysvar
+    /// 4. `[]` The voter's CandidateLimitRecord account
+    RelinquishCCVote,
+
+    /// Change CC vote instruction
+    /// The voter can redirect the vote to another ring until the counting time of the current target ring
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The current target Ring account. PDA seeds: ['ring', first_tanistry]
+    /// 1. `[writable]` CCVoteRecord account PDA seeds: ['cc-vote-record', candidate_limit_record]
+    /// 2. `[writable]` The new target Ring account. PDA seeds: ['ring', first_tanistry]
+    /// 3. `[signer]` voter (candidate token owner or its delegate)
+    /// 4. `[]` Clock sysvar
+    /// 5. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 6. `[]` The voter's CandidateLimitRecord account
+    /// 7. `[]` The voter's Tanistry the CandidateLimitRecord belongs to
+    /// 8. `[]` The voter's Ring, the new target ring must be pulled or pushed like in VoteForCC
+    ChangeCCVote {
+        new_target_ring: Pubkey,
+        vote: VoteSource,
+    },
+
+    /// Settle Tanistry instruction
+    /// Anybody can crank it once the Crown has been chosen
+    /// It splits the Tanistry vault between the kicker, the coordinator, the Crown and the candidates
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The Tanistry account PDA seeds: ['tanistry', kicker_coin_owner_record]
+    /// 1. `[]` KickerCoinOwnerRecord account the Tanistry was formed from
+    /// 2. `[]` Tanistry Token Holding account. PDA seeds: ['shihon', tanistry, tanistry_token_mint]
+    /// 3. `[writable]` CandidateLimitRecord accounts in the order of the Tanistry's candidate list
+    SettleTanistry,
+
+    /// Claim refund instruction
+    /// Every participant claims own share of the settled or cancelled Tanistry vault
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The Tanistry account PDA seeds: ['tanistry', kicker_coin_owner_record]
+    /// 1. `[writable]` Tanistry Token Holding account. PDA seeds: ['shihon', tanistry, tanistry_token_mint]
+    /// 2. `[writable]` The claimant's CandidateLimitRecord (candidate or Crown)
+    ///    or the Tanistry's KickerCoinOwnerRecord (kicker or coordinator)
+    ///    or the claimant's OutsideBuyerRecord (outside buyer of a cancelled Tanistry)
+    /// 3. `[signer]` Claimant, or the pinhole authority when the kicker or the coordinator claims through the pinhole
+    /// 4. `[writable]` The claimant's token account that will receive the refund
+    ///    When claimed through the pinhole it must be owned by the main wallet the pinhole resolves to
+    /// 5. `[]` SPL Token or Token-2022 program of the Tanistry token mint
+    /// 6. `[]` Tanistry token Mint
+    /// 7. `[]` Optional Pinhole account of the kicker or the coordinator. PDA seeds: ['pinhole', kicker_coin_owner_record, pinhole_authority]
+    ClaimRefund,
+
+    /// Coordinator withdraw instruction
+    /// The coordinator drops a live Tanistry and receives the NFT as Refund receipt
+    /// The receipt redeems the coordinator's share once the Tanistry is settled
+    /// The coordinator can't crown afterwards, so the Tanistry dropped before crowning can only be cancelled
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The Tanistry account PDA seeds: ['tanistry', kicker_coin_owner_record]
+    /// 1. `[]` KickerCoinOwnerRecord account the Tanistry was formed from
+    /// 2. `[signer]` Coordinator
+    /// 3. `[writable]` Refund receipt Mint. PDA seeds: ['refund-receipt', tanistry]
+    /// 4. `[writable]` The Coordinator's token account for receive the NFT as Refund. PDA seeds: ['refund-receipt', tanistry, coordinator]
+    /// 5. `[signer]` Payer
+    /// 6. `[]` System program
+    /// 7. `[]` SPL Token program
+    /// 8. `[]` Sysvar Rent
+    CoordinatorWithdraw,
+
+    /// Redeem coordinator refund receipt instruction
+    /// Burns the refund receipt NFT and pays the coordinator's share to the receipt holder
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The Tanistry account PDA seeds: ['tanistry', kicker_coin_owner_record]
+    /// 1. `[writable]` Tanistry Token Holding account. PDA seeds: ['shihon', tanistry, tanistry_token_mint]
+    /// 2. `[writable]` Refund receipt Mint. PDA seeds: ['refund-receipt', tanistry]
+    /// 3. `[writable]` The holder's token account holding the refund receipt
+    /// 4. `[signer]` Refund receipt holder
+    /// 5. `[writable]` The holder's token account that will receive the refund
+    /// 6. `[]` SPL Token program of the refund receipt
+    /// 7. `[]` Tanistry token Mint
+    /// 8. `[]` SPL Token or Token-2022 program of the Tanistry token mint
+    RedeemCoordinatorRefundReceipt,
+
+    /// Migrate account instruction
+    /// Reallocates a legacy account and rewrites it in the current layout
+    /// bcToken, bcToken Metadata and OutsideBuyerRecord are migrated from V1 or V2 to V3
+    /// KickerCoinOwnerRecord, Tanistry, CandidateLimitRecord, MixContentRecord, RateOption, RateOtherRecord
+    /// and CCVoteRecord are migrated from V1 to V2
+    /// Anybody can migrate the account and the payer covers the rent of the bigger layout
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The legacy account to migrate
+    /// 1. `[writable, signer]` Payer
+    /// 2. `[]` System program
+    /// 3. `[]` Sysvar Rent
+    /// 4. `[]` Optional linked account the PDA of the migrated account is resolved from. Required for bcToken Metadata (bcToken), RateOtherRecord (MixContentRecord), CCVoteRecord (voter's CandidateLimitRecord) and a Tanistry which isn't the first one of its Ring (previous Tanistry, migrated first)
+    MigrateAccount,
+
+    /// Create ShihonConfig instruction
+    /// Creates the singleton config of the deployment and appoints its admin authority
+    /// Only the program upgrade authority can create the config
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 1. `[]` Admin authority
+    /// 2. `[]` Shihon program ProgramData account
+    /// 3. `[signer]` Shihon program upgrade authority
+    /// 4. `[writable, signer]` Payer
+    /// 5. `[]` System program
+    /// 6. `[]` Sysvar Rent
+    CreateShihonConfig {
+        /// Config parameters
+        config_args: ShihonConfigArgs,
+    },
+
+    /// Update ShihonConfig instruction
+    /// Replaces the config parameters and optionally hands the config over to a new admin authority
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 1. `[signer]` Admin authority
+    /// 2. `[]` Optional new admin authority
+    UpdateShihonConfig {
+        /// Config parameters
+        config_args: ShihonConfigArgs,
+    },
+
+    /// Set pause flags instruction
+    /// Circuit breaker which pauses the instructions guarded by the given PAUSE_* flags
+    /// The guardian can only add flags while the admin authority can also clear them
+    /// SettleTanistry, CancelTanistry, ClaimRefund, CoordinatorWithdraw and RedeemCoordinatorRefundReceipt are never paused
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 1. `[signer]` Admin authority or guardian
+    SetPauseFlags {
+        /// Bitfield of the paused instructions
+        pause_flags: u32,
+    },
+
+    /// Create Treasury instruction
+    /// Creates the token account collecting the protocol fees of the given mint
+    /// Anybody can create the treasury and it's owned by the ShihonConfig PDA
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` Treasury token account. PDA seeds: ['shihon-treasury', treasury_mint]
+    /// 1. `[]` Treasury Mint
+    /// 2. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 3. `[writable, signer]` Payer
+    /// 4. `[]` System program
+    /// 5. `[]` SPL Token program
+    /// 6. `[]` Sysvar Rent
+    CreateTreasury,
+
+    /// Withdraw Treasury instruction
+    /// Transfers the collected protocol fees out of the treasury
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 1. `[signer]` Admin authority
+    /// 2. `[writable]` Treasury token account. PDA seeds: ['shihon-treasury', treasury_mint]
+    /// 3. `[]` Treasury Mint
+    /// 4. `[writable]` The token account that will receive the fees
+    /// 5. `[]` SPL Token program
+    WithdrawTreasury {
+        /// Amount to withdraw
+        amount: u64,
+    },
+
+    /// Cancel Tanistry instruction
+    /// Anybody can cancel the Tanistry once the enrollment has ended with fewer than min_candidates of the ShihonConfig
+    /// or once the coordinator has withdrawn before crowning
+    /// Every candidate's deposit and the KickerCoin become refundable with ClaimRefund
+    /// and the bcTokens enrolled into the Tanistry are returned to PublicOnTheGround
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The Tanistry account PDA seeds: ['tanistry', kicker_coin_owner_record]
+    /// 1. `[]` KickerCoinOwnerRecord account the Tanistry was formed from
+    /// 2. `[]` Tanistry Token Holding account. PDA seeds: ['shihon', tanistry, tanistry_token_mint]
+    /// 3. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    /// 4. `[]` Clock sysvar
+    /// 5. `[writable]` CandidateLimitRecord accounts in the order of the Tanistry's candidate list
+    /// 6. `[writable]` bcToken accounts, the kicker's one followed by the candidates' ones in the order of the candidate list
+    CancelTanistry,
+
+    /// Create Ring instruction
+    /// Creates the Ring of the Tanistry chain started from the given Tanistry
+    /// Anybody can create the Ring of a Tanistry without a previous Tanistry
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` Ring account. PDA seeds: ['ring', first_tanistry]
+    /// 1. `[]` The first Tanistry of the chain
+    /// 2. `[writable, signer]` Payer
+    /// 3. `[]` System program
+    /// 4. `[]` Sysvar Rent
+    CreateRing,
+
+    /// Promote Mixed Content instruction
+    /// Promotes the Succeeded mixed content into a new bcToken which can be kicked into future Tanistries
+    /// The metadata of the new bcToken records both parent bcTokens and the time shifts of the mix
+    /// The rater who mixed the content owns the new bcToken and every mix can be promoted only once
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` New bcToken account. PDA seeds: ['bc-token', name]
+    /// 1. `[writable]` MixContentRecord account. PDA seeds: ['mix-content-record', rater_candidate, buddy_candidate]
+    /// 2. `[]` The rater's CandidateLimitRecord account
+    /// 3. `[signer]` The rater's candidate token owner
+    /// 4. `[]` The init content's bcToken account
+    /// 5. `[]` The buddy content's bcToken account
+    /// 6. `[]` bcToken Mint of the new bcToken
+    /// 7. `[writable, signer]` Payer
+    /// 8. `[]` System program
+    /// 9. `[]` Sysvar Rent
+    /// 10. `[]` Sysvar Clock
+    /// 11. `[]` ShihonConfig account. PDA seeds: ['shihon-config']
+    PromoteMixedContent {
+        /// Name of the new bcToken
+        name: String,
+        /// Version of the mixed content
+        version: String,
+    },
+
+    /// Drop Pinhole instruction
+    /// Drops the one-time address of the kicker or the coordinator for the round of the KickerCoinOwnerRecord
+    /// The parties interact through each other's pinhole and the program resolves the main wallet only for payouts
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` Pinhole account. PDA seeds: ['pinhole', kicker_coin_owner_record, pinhole_authority]
+    /// 1. `[writable]` KickerCoinOwnerRecord account
+    /// 2. `[signer]` The main wallet of the party, the KickerCoin holder or the latest coordinator
+    /// 3. `[signer]` One-time pinhole authority
+    /// 4. `[writable, signer]` Payer
+    /// 5. `[]` System program
+    /// 6. `[]` Sysvar Rent
+    DropPinhole {
+        /// The party the pinhole is dropped for
+        side: PinholeSide,
+    },
+
+    /// Create Triple Multisig instruction
+    /// Creates the 2-of-3 approval of the rater, the buddy and the first kicker required by MixContent and RateOtherContent
+    /// The signers are taken from the Tanistry records so anybody can create it
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` TripleMultisig account. PDA seeds: ['triple-multisig', rater_candidate, buddy_candidate]
+    /// 1. `[]` The Rater Candidate's CandidateLimitRecord account
+    /// 2. `[]` The Buddy Candidate's CandidateLimitRecord account
+    /// 3. `[]` The Tanistry both candidates are enrolled into
+    /// 4. `[]` KickerCoinOwnerRecord account the Tanistry was formed from
+    /// 5. `[writable, signer]` Payer
+    /// 6. `[]` System program
+    /// 7. `[]` Sysvar Rent
+    CreateTripleMultisig,
+
+    /// Create Rate Option instruction
+    /// The Roydamna issues the RateOption on the MixContentRecord it takes part in as the rater or the buddy
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` RateOption account. PDA seeds: ['rate-option', mix_content_record, issuer_roydamna]
+    /// 1. `[writable]` The issuer Roydamna's CandidateLimitRecord account
+    /// 2. `[signer]` The issuer Roydamna's candidate token owner
+    /// 3. `[]` MixContentRecord account
+    /// 4. `[writable, signer]` Payer
+    /// 5. `[]` System program
+    /// 6. `[]` Sysvar Rent
+    CreateRateOption,
+
+    /// Sign Off Rate Option instruction
+    /// The Roydamna signs off the RateOption once the RateOtherRecord it governs has been cast
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` RateOption account
+    /// 1. `[]` The issuer Roydamna's CandidateLimitRecord account
+    /// 2. `[signer]` The issuer Roydamna's candidate token owner
+    /// 3. `[]` RateOtherRecord account governed by the RateOption
+    SignOffRateOption,
+
+    /// Remove Rate Option instruction
+    /// Removes the RateOption which hasn't been signed off yet
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` RateOption account
+    /// 1. `[]` The issuer Roydamna's CandidateLimitRecord account
+    /// 2. `[signer]` The issuer Roydamna's candidate token owner
+    /// 3. `[]` MixContentRecord account governed by the RateOption
+    /// 4. `[writable]` Beneficiary account which would receive lamports from the disposed RateOption account
+    RemoveRateOption,
+
+    /// Set Candidate Delegate instruction
+    /// The delegate can mix, rate and bump self-rate on behalf of the candidate token owner
+    /// but never withdraw the deposit or claim the refund
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` CandidateLimitRecord account
+    /// 1. `[signer]` The candidate token owner
+    SetCandidateDelegate {
+        /// New delegate, None removes the delegate
+        new_candidate_delegate: Option<Pubkey>,
+    },
+
+    /// Set bcToken Delegate instruction
+    /// The delegate can sign for the bcToken where the authority or its delegate is accepted
+    /// but never withdraw or discard the bcToken
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` bcToken account
+    /// 1. `[signer]` bcToken authority
+    SetBcTokenDelegate {
+        /// New delegate, None removes the delegate
+        new_delegate: Option<Pubkey>,
+    },
+
+    /// Set bcToken Authority instruction
+    /// Hands the bcToken over to the new authority, or only proposes it when the acceptance is required
+    /// Setting the authority to None freezes the bcToken for good and must be an explicit renounce
+    /// The authority can't be changed while the bcToken is in an active Tanistry
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` bcToken account
+    /// 1. `[signer]` bcToken authority
+    SetBcTokenAuthority {
+        /// New authority, None freezes the bcToken or withdraws the pending proposal
+        new_authority: Option<Pubkey>,
+        /// Whether the new authority has to accept the bcToken with AcceptBcTokenAuthority
+        require_acceptance: bool,
+        /// Whether the authority renounces the bcToken, required to set the authority to None
+        renounce_authority: bool,
+    },
+
+    /// Accept bcToken Authority instruction
+    /// The proposed authority takes the bcToken over
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` bcToken account
+    /// 1. `[signer]` Pending bcToken authority
+    AcceptBcTokenAuthority,
 }
 
-///TODO: we need create DraftBlankCheck instruction function here
-pub fn draft_blank_check() {
-    unimplemented!();
+/// Draft Blank Check instruction
+pub fn draft_blank_check(
+    program_id: &Pubkey,
+    // Accounts
+    bc_authority: &Pubkey,
+    bc_token_mint: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    name: String,
+) -> Instruction {
+    let bc_address = get_bc_token_address(program_id, &name);
+
+    let accounts = vec![
+        AccountMeta::new(bc_address, false),
+        AccountMeta::new_readonly(*bc_authority, true),
+        AccountMeta::new_readonly(*bc_token_mint, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+    ];
+
+    let instruction = ShihonInstruction::DraftBlankCheck { name };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
 }
 
 /// Create bcToken instruction
@@ -243,26 +707,51 @@ pub fn create_bc_token(
     bc_token_mint: &Pubkey,
     payer: &Pubkey,
     // Args
+    name: String,
     amount: u64,
     config: BcTokenMetadata,
 ) -> Instruction {
     let bc_token_address = get_bc_token_address(program_id, &name);
-    let bc_token_holding_address =
-        get_bc_token_holding_address(program_id, &bc_token_address, bc_token_mint);
 
-    let mut accounts = vec![
+    let accounts = vec![
         AccountMeta::new(bc_token_address, false),
-        AccountMeta::new_readonly(*bc_token_authority, false),
+        AccountMeta::new_readonly(*bc_token_authority, true),
         AccountMeta::new_readonly(*bc_token_mint, false),
-        AccountMeta::new(bc_token_holding_address, false),
         AccountMeta::new(*payer, true),
-        AccountMeta::new_readonly(sysvar::clock::id(), false),
         AccountMeta::new_readonly(system_program::id(), false),
-        AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+    ];
+
+    let instruction = ShihonInstruction::CreateBcToken {
+        name,
+        amount,
+        config,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Discard bcToken instruction
+pub fn discard_bc_token(
+    program_id: &Pubkey,
+    // Accounts
+    bc_token: &Pubkey,
+    bc_token_authority: &Pubkey,
+    beneficiary: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*bc_token, false),
+        AccountMeta::new_readonly(*bc_token_authority, true),
+        AccountMeta::new(*beneficiary, false),
     ];
 
-    let instruction = ShihonInstruction::CreateBcToken { amount, config };
+    let instruction = ShihonInstruction::DiscardBcToken;
 
     Instruction {
         program_id: *program_id,
@@ -272,28 +761,39 @@ pub fn create_bc_token(
 }
 
 /// kicking KickerCoin to another bcToken for making (e)RFT instruction
+#[allow(clippy::too_many_arguments)]
 pub fn kicking_to_coordinator(
     program_id: &Pubkey,
     // Accounts
-    kicker_token_mint: &Pubkey,
-    kicker_coin_owner_record: &Pubkey,
-    bc_token_authority: &Pubkey,
+    bc_token: &Pubkey,
+    kicker: &Pubkey,
+    kicker_token_source: &Pubkey,
+    kicker_coin_mint: &Pubkey,
     payer: &Pubkey,
     // Args
     coordinator: &Pubkey,
     amount: u64,
 ) -> Instruction {
     let kicker_coin_owner_record_address =
-        get_kicker_coin_owner_record_address(program_id, kicker_token_mint, coordinator);
+        get_kicker_coin_owner_record_address(program_id, kicker, coordinator);
+    let tanistry_address = get_tanistry_address(program_id, &kicker_coin_owner_record_address);
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, &tanistry_address, kicker_coin_mint);
 
     let accounts = vec![
-        AccountMeta::new(*kicker_token_mint, false),
-        AccountMeta::new_readonly(*kicker_coin_owner_record, false),
-        AccountMeta::new_readonly(*bc_token_authority, true),
         AccountMeta::new(kicker_coin_owner_record_address, false),
+        AccountMeta::new(*bc_token, false),
+        AccountMeta::new_readonly(*kicker, true),
+        AccountMeta::new(*kicker_token_source, false),
+        AccountMeta::new(tanistry_token_holding_address, false),
+        AccountMeta::new_readonly(tanistry_address, false),
+        AccountMeta::new_readonly(*kicker_coin_mint, false),
         AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new(get_treasury_address(program_id, kicker_coin_mint), false),
     ];
 
     let instruction = ShihonInstruction::KickingToCoordinator {
@@ -309,38 +809,39 @@ pub fn kicking_to_coordinator(
 }
 
 /// Instruction whether the coordinator approve the KickerCoin
-#[allow(clippy::too_many_arguments)]
 pub fn approve_kicker_coin(
     program_id: &Pubkey,
     // Accounts
-    tanistry_account: &Pubkey,
-    tanistry_holding: &Pubkey,
-    tanistry_authority: &Pubkey,
+    kicker_coin_owner_record: &Pubkey,
+    coordinator: &Pubkey,
     tanistry_token_mint: &Pubkey,
     payer: &Pubkey,
-    tanistry_index: &Pubkey,
+    previous_tanistry_and_crown: Option<(&Pubkey, &Pubkey)>,
     // Args
     coordinator_input: String,
 ) -> Instruction {
-    let tanistry_address = get_tanistry_address(
-        program_id,
-        tanistry_holding,
-        tanistry_token_mint,
-        &tanistry_index.to_le_bytes(),
-    );
+    let tanistry_address = get_tanistry_address(program_id, kicker_coin_owner_record);
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, &tanistry_address, tanistry_token_mint);
 
     let mut accounts = vec![
-        AccountMeta::new_readonly(*tanistry_account, false),
-        AccountMeta::new(tanistry_index, false),
-        AccountMeta::new(*tanistry_holding, false),
-        AccountMeta::new(*tanistry_authority, false),
+        AccountMeta::new(tanistry_address, false),
+        AccountMeta::new(*kicker_coin_owner_record, false),
+        AccountMeta::new_readonly(*coordinator, true),
+        AccountMeta::new_readonly(tanistry_token_holding_address, false),
         AccountMeta::new_readonly(*tanistry_token_mint, false),
         AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
     ];
 
+    if let Some((previous_tanistry, crown)) = previous_tanistry_and_crown {
+        accounts.push(AccountMeta::new(*previous_tanistry, false));
+        accounts.push(AccountMeta::new_readonly(*crown, false));
+    }
+
     let instruction = ShihonInstruction::ApproveKickerCoin { coordinator_input };
 
     Instruction {
@@ -350,25 +851,31 @@ pub fn approve_kicker_coin(
     }
 }
 
-/// Instruction whether the coordinator approve the KickerCoin
+/// Instruction whether the coordinator deny the KickerCoin
 pub fn deny_kicker_coin(
     program_id: &Pubkey,
     // Accounts
-    tanistry_account: &Pubkey,
-    tanistry_token_owner_record: &Pubkey,
-    bc_token_authority: &Pubkey,
-    payer: &Pubkey,
     kicker_coin_owner_record: &Pubkey,
+    coordinator: &Pubkey,
+    bc_token: &Pubkey,
+    kicker_token_account: &Pubkey,
+    kicker_coin_mint: &Pubkey,
+    beneficiary: &Pubkey,
 ) -> Instruction {
-    let kicker_coin_owner_record_address =
-        get_kicker_coin_owner_record_address(program_id, bc_token_authority, payer);
+    let tanistry_address = get_tanistry_address(program_id, kicker_coin_owner_record);
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, &tanistry_address, kicker_coin_mint);
 
     let accounts = vec![
-        AccountMeta::new_readonly(*tanistry_account, false),
-        AccountMeta::new_readonly(*tanistry_token_owner_record, false),
-        AccountMeta::new_readonly(*bc_token_authority, true),
-        AccountMeta::new(kicker_coin_owner_record_address, false),
-        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new(*kicker_coin_owner_record, false),
+        AccountMeta::new_readonly(*coordinator, true),
+        AccountMeta::new(*bc_token, false),
+        AccountMeta::new(tanistry_token_holding_address, false),
+        AccountMeta::new_readonly(tanistry_address, false),
+        AccountMeta::new(*kicker_token_account, false),
+        AccountMeta::new_readonly(*kicker_coin_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(*beneficiary, false),
     ];
 
     let instruction = ShihonInstruction::DenyKickerCoin;
@@ -385,42 +892,39 @@ pub fn deny_kicker_coin(
 pub fn candidate(
     program_id: &Pubkey,
     // Accounts
-    payer: &Pubkey,
-    tanistry_account: &Pubkey,
+    tanistry: &Pubkey,
+    candidate_token_owner: &Pubkey,
     candidate_token_source: &Pubkey,
-    candidate_token_holding: &Pubkey,
-    candidate_token_transfer_authority: &Pubkey,
-    candidate_token_owner_record: &Pubkey,
-    tanistry_token_holding: &Pubkey,
+    bc_token: &Pubkey,
+    tanistry_token_mint: &Pubkey,
+    payer: &Pubkey,
     // Args
     amount: u64,
-    governing_token_mint: &Pubkey,
 ) -> Instruction {
-    let candidate_token_owner_record_address = get_candidate_token_owner_record_address(
-        program_id,
-        tanistry_account,
-        candidate_token_source,
-        candidate_token_transfer_authority,
-    );
-
-    let candidate_token_holding_address = get_tanistry_token_holding_address(
+    let candidate_limit_record_address = get_candidate_limit_record_address(
         program_id,
-        tanistry_account,
-        candidate_token_owner_record,
+        tanistry,
+        tanistry_token_mint,
+        candidate_token_owner,
     );
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, tanistry, tanistry_token_mint);
 
     let accounts = vec![
-        AccountMeta::new_readonly(*tanistry_account, false),
-        AccountMeta::new(*payer, true),
+        AccountMeta::new(*tanistry, false),
+        AccountMeta::new(candidate_limit_record_address, false),
+        AccountMeta::new_readonly(*candidate_token_owner, true),
         AccountMeta::new(*candidate_token_source, false),
-        AccountMeta::new_readonly(*candidate_token_holding, true),
-        AccountMeta::new_readonly(*candidate_token_transfer_authority, true),
-        AccountMeta::new(kicker_coin_owner_record_address, false),
-        AccountMeta::new(tanistry_token_owner_record, true),
-        AccountMeta::new_readonly(*tanistry_token_holding, true),
-        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(tanistry_token_holding_address, false),
+        AccountMeta::new(*bc_token, false),
+        AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new(get_treasury_address(program_id, tanistry_token_mint), false),
+        AccountMeta::new_readonly(*tanistry_token_mint, false),
     ];
 
     let instruction = ShihonInstruction::Candidate { amount };
@@ -432,41 +936,59 @@ pub fn candidate(
     }
 }
 
-///TODO: need to fix
 /// Mix content instruction
+#[allow(clippy::too_many_arguments)]
 pub fn mix_content(
     program_id: &Pubkey,
     // Accounts
     rater_candidate: &Pubkey,
     rater_token_owner: &Pubkey,
     buddy_content_holder: &Pubkey,
-    init_content_holder: &Pubkey,
+    kicker_coin_owner_record: &Pubkey,
     coordinator: &Pubkey,
-    rater_token_mint_for_rating: &Pubkey,
-    mix_content_record: &Pubkey,
+    init_bc_token: &Pubkey,
+    buddy_bc_token: &Pubkey,
+    payer: &Pubkey,
+    triple_multisig_signers: &[Pubkey],
     // Args
-    amount: u64,
+    time_shift_a: u64,
+    time_shift_b: u64,
+    range_a: Option<ContentRange>,
+    range_b: Option<ContentRange>,
 ) -> Instruction {
-    let rate_other_record_address = get_rate_other_record_address(
-        program_id,
-        rater_candidate,
-        rater_token_mint_for_rating,
-        init_content_holder,
-    );
+    let mix_content_record_address =
+        get_mix_content_record_address(program_id, rater_candidate, buddy_content_holder);
+    let triple_multisig_address =
+        get_triple_multisig_address(program_id, rater_candidate, buddy_content_holder);
 
-    let accounts = vec![
-        AccountMeta::new(*rater_candidate, true),
-        AccountMeta::new_readonly(*rater_token_owner, false),
-        AccountMeta::new(*buddy_content_holder false),
-        AccountMeta::new(*init_content_holder false),
-        AccountMeta::new_readonly(coordinator, false),
-        AccountMeta::new(*mix_content_record, false),
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*coordinator, false),
+        AccountMeta::new_readonly(*rater_candidate, false),
+        AccountMeta::new_readonly(*rater_token_owner, true),
+        AccountMeta::new_readonly(*buddy_content_holder, false),
+        AccountMeta::new_readonly(*kicker_coin_owner_record, false),
+        AccountMeta::new_readonly(*init_bc_token, false),
+        AccountMeta::new_readonly(*buddy_bc_token, false),
+        AccountMeta::new(mix_content_record_address, false),
+        AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
-        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new_readonly(triple_multisig_address, false),
     ];
 
-    let instruction = ShihonInstruction::RateOtherContent { amount };
+    accounts.extend(
+        triple_multisig_signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+
+    let instruction = ShihonInstruction::MixContent {
+        time_shift_a,
+        time_shift_b,
+        range_a,
+        range_b,
+    };
 
     Instruction {
         program_id: *program_id,
@@ -476,39 +998,45 @@ pub fn mix_content(
 }
 
 /// Rating other mixed content instruction
+#[allow(clippy::too_many_arguments)]
 pub fn rate_content(
     program_id: &Pubkey,
     // Accounts
     rater_candidate: &Pubkey,
     rater_token_owner: &Pubkey,
-    buddy_content_holder: &Pubkey,
-    init_content_holder: &Pubkey,
-    coordinator: &Pubkey,
-    rater_token_mint_for_rating: &Pubkey,
+    buddy_candidate: &Pubkey,
     mix_content_record: &Pubkey,
+    payer: &Pubkey,
+    triple_multisig_signers: &[Pubkey],
     // Args
-    amount: u64,
+    rating: u64,
 ) -> Instruction {
-    let rate_other_record_address = get_rate_other_record_address(
-        program_id,
-        rater_candidate,
-        rater_token_mint_for_rating,
-        init_content_holder,
-    );
+    let rate_other_record_address =
+        get_rate_other_record_address(program_id, rater_candidate, buddy_candidate);
+    let triple_multisig_address =
+        get_triple_multisig_address(program_id, rater_candidate, buddy_candidate);
 
-    let accounts = vec![
-        AccountMeta::new(*rater_candidate, true),
-        AccountMeta::new_readonly(*rater_token_owner, false),
-        AccountMeta::new(*buddy_content_holder false),
-        AccountMeta::new(*init_content_holder false),
-        AccountMeta::new_readonly(coordinator, false),
-        AccountMeta::new(*mix_content_record, false),
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*rater_candidate, false),
+        AccountMeta::new_readonly(*rater_token_owner, true),
+        AccountMeta::new(*buddy_candidate, false),
+        AccountMeta::new_readonly(*mix_content_record, false),
+        AccountMeta::new(rate_other_record_address, false),
+        AccountMeta::new_readonly(triple_multisig_address, false),
+        AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
     ];
 
-    let instruction = ShihonInstruction::RateOtherContent { amount };
+    accounts.extend(
+        triple_multisig_signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+
+    let instruction = ShihonInstruction::RateOtherContent { rating };
 
     Instruction {
         program_id: *program_id,
@@ -517,39 +1045,35 @@ pub fn rate_content(
     }
 }
 
-/// Bump self rating instruction
+/// Creates BumpSelfRate instruction
 #[allow(clippy::too_many_arguments)]
 pub fn bump_self_rate(
     program_id: &Pubkey,
     // Accounts
-    candidate_account: &Pubkey,
+    tanistry: &Pubkey,
+    candidate_limit_record: &Pubkey,
     candidate_token_source: &Pubkey,
-    candidate_token_owner: &Pubkey,
-    candidate_token_transfer_authority: &Pubkey,
-    payer: &Pubkey,
-    candidate_token_owner_record: &Pubkey,
+    candidate_token_authority: &Pubkey,
+    bc_token: &Pubkey,
+    bc_token_authority: &Pubkey,
+    tanistry_token_mint: &Pubkey,
     // Args
     amount: u64,
 ) -> Instruction {
-    let candidate_token_owner_record_address = get_candidate_token_owner_record_address(
-        program_id,
-        candidate_account,
-        candidate_token_source,
-        candidate_token_owner,
-    );
-
-    let candidate_token_holding_address =
-        get_candidate_token_holding_address(program_id, candidate_account, candidate_token_owner);
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, tanistry, tanistry_token_mint);
 
     let accounts = vec![
-        AccountMeta::new_readonly(*candidate_account, false),
-        AccountMeta::new(candidate_token_holding_address, false),
+        AccountMeta::new_readonly(*tanistry, false),
+        AccountMeta::new(tanistry_token_holding_address, false),
         AccountMeta::new(*candidate_token_source, false),
-        AccountMeta::new_readonly(*candidate_token_owner, true),
-        AccountMeta::new(candidate_token_owner_record_address, false),
-        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*candidate_token_authority, true),
+        AccountMeta::new(*candidate_limit_record, false),
+        AccountMeta::new_readonly(*bc_token, false),
+        AccountMeta::new_readonly(*bc_token_authority, true),
+        AccountMeta::new_readonly(*tanistry_token_mint, false),
         AccountMeta::new_readonly(spl_token::id(), false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
     ];
 
     let instruction = ShihonInstruction::BumpSelfRate { amount };
@@ -562,35 +1086,39 @@ pub fn bump_self_rate(
 }
 
 /// buy exceeded rate token instruction
-#[allow(clippy::too_many_arguments)]
 pub fn buy_exceeded_rate_token(
     program_id: &Pubkey,
     // Accounts
-    buyer: &Pubkey,
-    outside_buyer_record: &Pubkey,
-    buyer_token: &Pubkey,
-    buyer_token_owner: &Pubkey,
+    tanistry: &Pubkey,
+    outside_buyer: &Pubkey,
+    outside_buyer_token_source: &Pubkey,
+    tanistry_token_mint: &Pubkey,
     payer: &Pubkey,
     // Args
-    token: &Pubkey,
     amount: u64,
 ) -> Instruction {
-    let token_governance_address = get_outside_buyer_token_address(program_id, buyer, buyer_token);
+    let outside_buyer_record_address =
+        get_outside_buyer_record_address(program_id, tanistry, tanistry_token_mint, outside_buyer);
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, tanistry, tanistry_token_mint);
 
-    let mut accounts = vec![
-        AccountMeta::new_readonly(*realm, false),
-        AccountMeta::new(token_governance_address, false),
-        AccountMeta::new(*governed_token, false),
-        AccountMeta::new_readonly(*governed_token_owner, true),
-        AccountMeta::new_readonly(*token_owner_record, false),
+    let accounts = vec![
+        AccountMeta::new(*tanistry, false),
+        AccountMeta::new(outside_buyer_record_address, false),
+        AccountMeta::new_readonly(*outside_buyer, true),
+        AccountMeta::new(*outside_buyer_token_source, false),
+        AccountMeta::new(tanistry_token_holding_address, false),
         AccountMeta::new(*payer, true),
-        AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
-        AccountMeta::new_readonly(*governance_authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new(get_treasury_address(program_id, tanistry_token_mint), false),
+        AccountMeta::new_readonly(*tanistry_token_mint, false),
     ];
 
-    let instruction = ShihonInstruction::BuyExceededRateToken { token, amount };
+    let instruction = ShihonInstruction::BuyExceededRateToken { amount };
 
     Instruction {
         program_id: *program_id,
@@ -604,26 +1132,686 @@ pub fn crowning(
     program_id: &Pubkey,
     // Accounts
     coordinator: &Pubkey,
-    kicker_coin_authority: &Pubkey,
     kicker_coin_owner_record: &Pubkey,
+    ring: &Pubkey,
+    candidate_limit_records: &[Pubkey],
     // Args
     crown: &Pubkey,
 ) -> Instruction {
-    let kicker_coin_owner_record_address =
-        get_kicker_coin_owner_record_address(program_id, crown, coordinator);
+    let tanistry_address = get_tanistry_address(program_id, kicker_coin_owner_record);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*coordinator, true),
+        AccountMeta::new(tanistry_address, false),
+        AccountMeta::new_readonly(*kicker_coin_owner_record, false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new(*ring, false),
+    ];
+
+    accounts.extend(
+        candidate_limit_records
+            .iter()
+            .map(|candidate_limit_record| AccountMeta::new_readonly(*candidate_limit_record, false)),
+    );
+
+    let instruction = ShihonInstruction::Crowning { crown: *crown };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Vote for CC instruction
+#[allow(clippy::too_many_arguments)]
+pub fn vote_for_cc(
+    program_id: &Pubkey,
+    // Accounts
+    candidate_limit_record: &Pubkey,
+    tanistry: &Pubkey,
+    ring: &Pubkey,
+    voter: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    target_ring: &Pubkey,
+    vote: VoteSource,
+) -> Instruction {
+    let cc_vote_record_address = get_cc_vote_record_address(program_id, candidate_limit_record);
+
+    let accounts = vec![
+        AccountMeta::new(*target_ring, false),
+        AccountMeta::new(cc_vote_record_address, false),
+        AccountMeta::new_readonly(*candidate_limit_record, false),
+        AccountMeta::new_readonly(*voter, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new_readonly(*tanistry, false),
+        AccountMeta::new_readonly(*ring, false),
+    ];
+
+    let instruction = ShihonInstruction::VoteForCC {
+        target_ring: *target_ring,
+        vote,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Relinquish CC vote instruction
+pub fn relinquish_cc_vote(
+    program_id: &Pubkey,
+    // Accounts
+    target_ring: &Pubkey,
+    candidate_limit_record: &Pubkey,
+    voter: &Pubkey,
+) -> Instruction {
+    let cc_vote_record_address = get_cc_vote_record_address(program_id, candidate_limit_record);
+
+    let accounts = vec![
+        AccountMeta::new(*target_ring, false),
+        AccountMeta::new(cc_vote_record_address, false),
+        AccountMeta::new_readonly(*voter, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(*candidate_limit_record, false),
+    ];
+
+    let instruction = ShihonInstruction::RelinquishCCVote;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Change CC vote instruction
+#[allow(clippy::too_many_arguments)]
+pub fn change_cc_vote(
+    program_id: &Pubkey,
+    // Accounts
+    target_ring: &Pubkey,
+    candidate_limit_record: &Pubkey,
+    tanistry: &Pubkey,
+    ring: &Pubkey,
+    voter: &Pubkey,
+    // Args
+    new_target_ring: &Pubkey,
+    vote: VoteSource,
+) -> Instruction {
+    let cc_vote_record_address = get_cc_vote_record_address(program_id, candidate_limit_record);
 
     let accounts = vec![
+        AccountMeta::new(*target_ring, false),
+        AccountMeta::new(cc_vote_record_address, false),
+        AccountMeta::new(*new_target_ring, false),
+        AccountMeta::new_readonly(*voter, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new_readonly(*candidate_limit_record, false),
+        AccountMeta::new_readonly(*tanistry, false),
+        AccountMeta::new_readonly(*ring, false),
+    ];
+
+    let instruction = ShihonInstruction::ChangeCCVote {
+        new_target_ring: *new_target_ring,
+        vote,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Settle Tanistry instruction
+pub fn settle_tanistry(
+    program_id: &Pubkey,
+    // Accounts
+    tanistry: &Pubkey,
+    kicker_coin_owner_record: &Pubkey,
+    tanistry_token_mint: &Pubkey,
+    candidate_limit_records: &[Pubkey],
+) -> Instruction {
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, tanistry, tanistry_token_mint);
+
+    let mut accounts = vec![
+        AccountMeta::new(*tanistry, false),
+        AccountMeta::new_readonly(*kicker_coin_owner_record, false),
+        AccountMeta::new_readonly(tanistry_token_holding_address, false),
+    ];
+
+    accounts.extend(
+        candidate_limit_records
+            .iter()
+            .map(|candidate_limit_record| AccountMeta::new(*candidate_limit_record, false)),
+    );
+
+    let instruction = ShihonInstruction::SettleTanistry;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Claim refund instruction
+#[allow(clippy::too_many_arguments)]
+pub fn claim_refund(
+    program_id: &Pubkey,
+    // Accounts
+    tanistry: &Pubkey,
+    tanistry_token_mint: &Pubkey,
+    refund_record: &Pubkey,
+    claimant: &Pubkey,
+    claimant_token_account: &Pubkey,
+    tanistry_token_program_id: &Pubkey,
+    pinhole: Option<&Pubkey>,
+) -> Instruction {
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, tanistry, tanistry_token_mint);
+
+    let mut accounts = vec![
+        AccountMeta::new(*tanistry, false),
+        AccountMeta::new(tanistry_token_holding_address, false),
+        AccountMeta::new(*refund_record, false),
+        AccountMeta::new_readonly(*claimant, true),
+        AccountMeta::new(*claimant_token_account, false),
+        AccountMeta::new_readonly(*tanistry_token_program_id, false),
+        AccountMeta::new_readonly(*tanistry_token_mint, false),
+    ];
+
+    if let Some(pinhole) = pinhole {
+        accounts.push(AccountMeta::new_readonly(*pinhole, false));
+    }
+
+    let instruction = ShihonInstruction::ClaimRefund;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Coordinator withdraw instruction
+pub fn coordinator_withdraw(
+    program_id: &Pubkey,
+    // Accounts
+    tanistry: &Pubkey,
+    kicker_coin_owner_record: &Pubkey,
+    coordinator: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let refund_receipt_mint_address =
+        get_coordinator_refund_receipt_mint_address(program_id, tanistry);
+    let refund_receipt_token_address =
+        get_coordinator_refund_receipt_token_address(program_id, tanistry, coordinator);
+
+    let accounts = vec![
+        AccountMeta::new(*tanistry, false),
+        AccountMeta::new_readonly(*kicker_coin_owner_record, false),
         AccountMeta::new_readonly(*coordinator, true),
-        AccountMeta::new(kicker_coin_onwer_record_address, false),
-        AccountMeta::new(*kicker_coin_owner_record, false),
-        AccountMeta::new_readonly(*governing_token_mint, false),
-        AccountMeta::new_readonly(*kicker_coin_authority, true),
+        AccountMeta::new(refund_receipt_mint_address, false),
+        AccountMeta::new(refund_receipt_token_address, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = ShihonInstruction::CoordinatorWithdraw;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Redeem coordinator refund receipt instruction
+pub fn redeem_coordinator_refund_receipt(
+    program_id: &Pubkey,
+    // Accounts
+    tanistry: &Pubkey,
+    tanistry_token_mint: &Pubkey,
+    refund_receipt_token_account: &Pubkey,
+    holder: &Pubkey,
+    holder_token_account: &Pubkey,
+    tanistry_token_program_id: &Pubkey,
+) -> Instruction {
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, tanistry, tanistry_token_mint);
+    let refund_receipt_mint_address =
+        get_coordinator_refund_receipt_mint_address(program_id, tanistry);
+
+    let accounts = vec![
+        AccountMeta::new(*tanistry, false),
+        AccountMeta::new(tanistry_token_holding_address, false),
+        AccountMeta::new(refund_receipt_mint_address, false),
+        AccountMeta::new(*refund_receipt_token_account, false),
+        AccountMeta::new_readonly(*holder, true),
+        AccountMeta::new(*holder_token_account, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*tanistry_token_mint, false),
+        AccountMeta::new_readonly(*tanistry_token_program_id, false),
+    ];
+
+    let instruction = ShihonInstruction::RedeemCoordinatorRefundReceipt;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Migrate account instruction
+pub fn migrate_account(
+    program_id: &Pubkey,
+    // Accounts
+    account: &Pubkey,
+    payer: &Pubkey,
+    linked_account: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*account, false),
+        AccountMeta::new(*payer, true),
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    if let Some(linked_account) = linked_account {
+        accounts.push(AccountMeta::new_readonly(*linked_account, false));
+    }
+
+    let instruction = ShihonInstruction::MigrateAccount;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates CreateShihonConfig instruction
+pub fn create_shihon_config(
+    program_id: &Pubkey,
+    // Accounts
+    admin_authority: &Pubkey,
+    program_upgrade_authority: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    config_args: ShihonConfigArgs,
+) -> Instruction {
+    let shihon_config_address = get_shihon_config_address(program_id);
+    let program_data_address = get_program_data_address(program_id);
+
+    let accounts = vec![
+        AccountMeta::new(shihon_config_address, false),
+        AccountMeta::new_readonly(*admin_authority, false),
+        AccountMeta::new_readonly(program_data_address, false),
+        AccountMeta::new_readonly(*program_upgrade_authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = ShihonInstruction::CreateShihonConfig { config_args };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates UpdateShihonConfig instruction
+pub fn update_shihon_config(
+    program_id: &Pubkey,
+    // Accounts
+    admin_authority: &Pubkey,
+    new_admin_authority: Option<&Pubkey>,
+    // Args
+    config_args: ShihonConfigArgs,
+) -> Instruction {
+    let shihon_config_address = get_shihon_config_address(program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(shihon_config_address, false),
+        AccountMeta::new_readonly(*admin_authority, true),
+    ];
+
+    if let Some(new_admin_authority) = new_admin_authority {
+        accounts.push(AccountMeta::new_readonly(*new_admin_authority, false));
+    }
+
+    let instruction = ShihonInstruction::UpdateShihonConfig { config_args };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates SetPauseFlags instruction
+pub fn set_pause_flags(
+    program_id: &Pubkey,
+    // Accounts
+    pause_authority: &Pubkey,
+    // Args
+    pause_flags: u32,
+) -> Instruction {
+    let shihon_config_address = get_shihon_config_address(program_id);
+
+    let accounts = vec![
+        AccountMeta::new(shihon_config_address, false),
+        AccountMeta::new_readonly(*pause_authority, true),
+    ];
+
+    let instruction = ShihonInstruction::SetPauseFlags { pause_flags };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates CreateTreasury instruction
+pub fn create_treasury(
+    program_id: &Pubkey,
+    // Accounts
+    treasury_mint: &Pubkey,
+    payer: &Pubkey,
+    treasury_token_program_id: &Pubkey,
+) -> Instruction {
+    let treasury_address = get_treasury_address(program_id, treasury_mint);
+
+    let accounts = vec![
+        AccountMeta::new(treasury_address, false),
+        AccountMeta::new_readonly(*treasury_mint, false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*treasury_token_program_id, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = ShihonInstruction::CreateTreasury;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates WithdrawTreasury instruction
+pub fn withdraw_treasury(
+    program_id: &Pubkey,
+    // Accounts
+    admin_authority: &Pubkey,
+    treasury_mint: &Pubkey,
+    destination: &Pubkey,
+    treasury_token_program_id: &Pubkey,
+    // Args
+    amount: u64,
+) -> Instruction {
+    let treasury_address = get_treasury_address(program_id, treasury_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+        AccountMeta::new_readonly(*admin_authority, true),
+        AccountMeta::new(treasury_address, false),
+        AccountMeta::new_readonly(*treasury_mint, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(*treasury_token_program_id, false),
+    ];
+
+    let instruction = ShihonInstruction::WithdrawTreasury { amount };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates CancelTanistry instruction
+pub fn cancel_tanistry(
+    program_id: &Pubkey,
+    // Accounts
+    tanistry: &Pubkey,
+    kicker_coin_owner_record: &Pubkey,
+    tanistry_token_mint: &Pubkey,
+    candidate_limit_records: &[Pubkey],
+    bc_tokens: &[Pubkey],
+) -> Instruction {
+    let tanistry_token_holding_address =
+        get_tanistry_token_holding_address(program_id, tanistry, tanistry_token_mint);
+
+    let mut accounts = vec![
+        AccountMeta::new(*tanistry, false),
+        AccountMeta::new_readonly(*kicker_coin_owner_record, false),
+        AccountMeta::new_readonly(tanistry_token_holding_address, false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
     ];
 
-    let instruction = ShihonInstruction::Crowning { crown };
+    accounts.extend(
+        candidate_limit_records
+            .iter()
+            .map(|candidate_limit_record| AccountMeta::new(*candidate_limit_record, false)),
+    );
+
+    accounts.extend(bc_tokens.iter().map(|bc_token| AccountMeta::new(*bc_token, false)));
+
+    let instruction = ShihonInstruction::CancelTanistry;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates CreateRing instruction
+pub fn create_ring(
+    program_id: &Pubkey,
+    // Accounts
+    first_tanistry: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let ring_address = get_ring_address(program_id, first_tanistry);
+
+    let accounts = vec![
+        AccountMeta::new(ring_address, false),
+        AccountMeta::new_readonly(*first_tanistry, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = ShihonInstruction::CreateRing;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates PromoteMixedContent instruction
+#[allow(clippy::too_many_arguments)]
+pub fn promote_mixed_content(
+    program_id: &Pubkey,
+    // Accounts
+    mix_content_record: &Pubkey,
+    rater_candidate: &Pubkey,
+    rater_token_owner: &Pubkey,
+    init_bc_token: &Pubkey,
+    buddy_bc_token: &Pubkey,
+    bc_token_mint: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    name: String,
+    version: String,
+) -> Instruction {
+    let bc_token_address = get_bc_token_address(program_id, &name);
+
+    let accounts = vec![
+        AccountMeta::new(bc_token_address, false),
+        AccountMeta::new(*mix_content_record, false),
+        AccountMeta::new_readonly(*rater_candidate, false),
+        AccountMeta::new_readonly(*rater_token_owner, true),
+        AccountMeta::new_readonly(*init_bc_token, false),
+        AccountMeta::new_readonly(*buddy_bc_token, false),
+        AccountMeta::new_readonly(*bc_token_mint, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(get_shihon_config_address(program_id), false),
+    ];
+
+    let instruction = ShihonInstruction::PromoteMixedContent { name, version };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates DropPinhole instruction
+pub fn drop_pinhole(
+    program_id: &Pubkey,
+    // Accounts
+    kicker_coin_owner_record: &Pubkey,
+    party: &Pubkey,
+    pinhole_authority: &Pubkey,
+    payer: &Pubkey,
+    // Args
+    side: PinholeSide,
+) -> Instruction {
+    let pinhole_address =
+        get_pinhole_address(program_id, kicker_coin_owner_record, pinhole_authority);
+
+    let accounts = vec![
+        AccountMeta::new(pinhole_address, false),
+        AccountMeta::new(*kicker_coin_owner_record, false),
+        AccountMeta::new_readonly(*party, true),
+        AccountMeta::new_readonly(*pinhole_authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = ShihonInstruction::DropPinhole { side };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates CreateTripleMultisig instruction
+pub fn create_triple_multisig(
+    program_id: &Pubkey,
+    // Accounts
+    rater_candidate: &Pubkey,
+    buddy_candidate: &Pubkey,
+    tanistry: &Pubkey,
+    kicker_coin_owner_record: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let triple_multisig_address =
+        get_triple_multisig_address(program_id, rater_candidate, buddy_candidate);
+
+    let accounts = vec![
+        AccountMeta::new(triple_multisig_address, false),
+        AccountMeta::new_readonly(*rater_candidate, false),
+        AccountMeta::new_readonly(*buddy_candidate, false),
+        AccountMeta::new_readonly(*tanistry, false),
+        AccountMeta::new_readonly(*kicker_coin_owner_record, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = ShihonInstruction::CreateTripleMultisig;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates CreateRateOption instruction
+pub fn create_rate_option(
+    program_id: &Pubkey,
+    // Accounts
+    issuer_roydamna: &Pubkey,
+    issuer_token_owner: &Pubkey,
+    mix_content_record: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let rate_option_address =
+        get_rate_option_address(program_id, mix_content_record, issuer_roydamna);
+
+    let accounts = vec![
+        AccountMeta::new(rate_option_address, false),
+        AccountMeta::new(*issuer_roydamna, false),
+        AccountMeta::new_readonly(*issuer_token_owner, true),
+        AccountMeta::new_readonly(*mix_content_record, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    let instruction = ShihonInstruction::CreateRateOption;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates SignOffRateOption instruction
+pub fn sign_off_rate_option(
+    program_id: &Pubkey,
+    // Accounts
+    issuer_roydamna: &Pubkey,
+    issuer_token_owner: &Pubkey,
+    mix_content_record: &Pubkey,
+    rate_other_record: &Pubkey,
+) -> Instruction {
+    let rate_option_address =
+        get_rate_option_address(program_id, mix_content_record, issuer_roydamna);
+
+    let accounts = vec![
+        AccountMeta::new(rate_option_address, false),
+        AccountMeta::new_readonly(*issuer_roydamna, false),
+        AccountMeta::new_readonly(*issuer_token_owner, true),
+        AccountMeta::new_readonly(*rate_other_record, false),
+    ];
+
+    let instruction = ShihonInstruction::SignOffRateOption;
 
     Instruction {
         program_id: *program_id,
@@ -632,8 +1820,129 @@ pub fn crowning(
     }
 }
 
-///TODO: we need to create VoteForCC instruction function here
+/// Creates RemoveRateOption instruction
+pub fn remove_rate_option(
+    program_id: &Pubkey,
+    // Accounts
+    issuer_roydamna: &Pubkey,
+    issuer_token_owner: &Pubkey,
+    mix_content_record: &Pubkey,
+    beneficiary: &Pubkey,
+) -> Instruction {
+    let rate_option_address =
+        get_rate_option_address(program_id, mix_content_record, issuer_roydamna);
+
+    let accounts = vec![
+        AccountMeta::new(rate_option_address, false),
+        AccountMeta::new_readonly(*issuer_roydamna, false),
+        AccountMeta::new_readonly(*issuer_token_owner, true),
+        AccountMeta::new_readonly(*mix_content_record, false),
+        AccountMeta::new(*beneficiary, false),
+    ];
+
+    let instruction = ShihonInstruction::RemoveRateOption;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
 
-pub fn vote_for_cc() {
-    unimplemented!();
+/// Creates SetCandidateDelegate instruction
+pub fn set_candidate_delegate(
+    program_id: &Pubkey,
+    // Accounts
+    candidate_limit_record: &Pubkey,
+    candidate_token_owner: &Pubkey,
+    // Args
+    new_candidate_delegate: Option<Pubkey>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*candidate_limit_record, false),
+        AccountMeta::new_readonly(*candidate_token_owner, true),
+    ];
+
+    let instruction = ShihonInstruction::SetCandidateDelegate {
+        new_candidate_delegate,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates SetBcTokenDelegate instruction
+pub fn set_bc_token_delegate(
+    program_id: &Pubkey,
+    // Accounts
+    bc_token: &Pubkey,
+    bc_token_authority: &Pubkey,
+    // Args
+    new_delegate: Option<Pubkey>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*bc_token, false),
+        AccountMeta::new_readonly(*bc_token_authority, true),
+    ];
+
+    let instruction = ShihonInstruction::SetBcTokenDelegate { new_delegate };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates SetBcTokenAuthority instruction
+pub fn set_bc_token_authority(
+    program_id: &Pubkey,
+    // Accounts
+    bc_token: &Pubkey,
+    bc_token_authority: &Pubkey,
+    // Args
+    new_authority: Option<Pubkey>,
+    require_acceptance: bool,
+    renounce_authority: bool,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*bc_token, false),
+        AccountMeta::new_readonly(*bc_token_authority, true),
+    ];
+
+    let instruction = ShihonInstruction::SetBcTokenAuthority {
+        new_authority,
+        require_acceptance,
+        renounce_authority,
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates AcceptBcTokenAuthority instruction
+pub fn accept_bc_token_authority(
+    program_id: &Pubkey,
+    // Accounts
+    bc_token: &Pubkey,
+    pending_authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*bc_token, false),
+        AccountMeta::new_readonly(*pending_authority, true),
+    ];
+
+    let instruction = ShihonInstruction::AcceptBcTokenAuthority;
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.try_to_vec().unwrap(),
+    }
 }
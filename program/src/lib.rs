@@ -1,6 +1,9 @@
+pub mod decode;
 pub mod entrypoint;
 pub mod error;
+pub mod events;
 pub mod instruction;
+pub mod pda;
 pub mod processor;
 pub mod state;
 pub mod tools;
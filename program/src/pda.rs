@@ -0,0 +1,1085 @@
+//! Program Derived Addresses
+//!
+//! Seeds for every account type owned by the program
+//! Each state account type has its own seed prefix so PDAs of different account types can never collide
+//! The bcToken and Tanistry token holding accounts share PROGRAM_AUTHORITY_SEED and are told apart by
+//! the bcToken or Tanistry PDA in their second seed
+//! The bump seed of the PDA is stored in the account and used to validate its address cheaply
+
+use solana_program::pubkey::Pubkey;
+
+use crate::PROGRAM_AUTHORITY_SEED;
+
+/// Seed prefix for bcToken PDAs
+pub const BC_TOKEN_SEED: &[u8] = b"bc-token";
+
+/// Seed prefix for bcToken Metadata PDAs
+pub const BC_TOKEN_METADATA_SEED: &[u8] = b"bc-token-metadata";
+
+/// Seed prefix for KickerCoinOwnerRecord PDAs
+pub const KICKER_COIN_OWNER_RECORD_SEED: &[u8] = b"kicker-coin-owner-record";
+
+/// Seed prefix for Tanistry PDAs
+pub const TANISTRY_SEED: &[u8] = b"tanistry";
+
+/// Seed prefix for CandidateLimitRecord PDAs
+pub const CANDIDATE_LIMIT_RECORD_SEED: &[u8] = b"candidate-limit-record";
+
+/// Seed prefix for MixContentRecord PDAs
+pub const MIX_CONTENT_RECORD_SEED: &[u8] = b"mix-content-record";
+
+/// Seed prefix for RateOption PDAs
+pub const RATE_OPTION_SEED: &[u8] = b"rate-option";
+
+/// Seed prefix for RateOtherRecord PDAs
+pub const RATE_OTHER_RECORD_SEED: &[u8] = b"rate-other-record";
+
+/// Seed prefix for OutsideBuyerRecord PDAs
+pub const OUTSIDE_BUYER_RECORD_SEED: &[u8] = b"outside-buyer-record";
+
+/// Seed prefix for CCVoteRecord PDAs
+pub const CC_VOTE_RECORD_SEED: &[u8] = b"cc-vote-record";
+
+/// Seed prefix for Coordinator Refund Receipt PDAs
+pub const REFUND_RECEIPT_SEED: &[u8] = b"refund-receipt";
+
+/// Seed for the singleton ShihonConfig PDA
+pub const SHIHON_CONFIG_SEED: &[u8] = b"shihon-config";
+
+/// Seed prefix for Treasury token account PDAs
+pub const TREASURY_SEED: &[u8] = b"shihon-treasury";
+
+/// Seed prefix for Ring PDAs
+pub const RING_SEED: &[u8] = b"ring";
+
+/// Seed prefix for Pinhole PDAs
+pub const PINHOLE_SEED: &[u8] = b"pinhole";
+
+/// Seed prefix for TripleMultisig PDAs
+pub const TRIPLE_MULTISIG_SEED: &[u8] = b"triple-multisig";
+
+/// Returns the bump seed of the PDA derived from the given seeds
+pub fn find_bump_seed(program_id: &Pubkey, seeds: &[&[u8]]) -> u8 {
+    Pubkey::find_program_address(seeds, program_id).1
+}
+
+/// Checks the given address is the PDA derived from the given seeds and the stored bump seed
+pub fn is_valid_pda(program_id: &Pubkey, address: &Pubkey, seeds: &[&[u8]], bump: u8) -> bool {
+    let bump = [bump];
+    let mut seeds_with_bump = seeds.to_vec();
+    seeds_with_bump.push(&bump);
+
+    match Pubkey::create_program_address(&seeds_with_bump, program_id) {
+        Ok(pda) => pda == *address,
+        Err(_) => false,
+    }
+}
+
+/// Returns bcToken PDA seeds
+pub fn get_bc_token_address_seeds(name: &str) -> [&[u8]; 2] {
+    [BC_TOKEN_SEED, name.as_bytes()]
+}
+
+/// Returns bcToken PDA address
+pub fn get_bc_token_address(program_id: &Pubkey, name: &str) -> Pubkey {
+    Pubkey::find_program_address(&get_bc_token_address_seeds(name), program_id).0
+}
+
+/// Returns bcToken Holding PDA seeds
+pub fn get_bc_token_holding_address_seeds<'a>(
+    bc_token: &'a Pubkey,
+    bc_token_mint: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        PROGRAM_AUTHORITY_SEED,
+        bc_token.as_ref(),
+        bc_token_mint.as_ref(),
+    ]
+}
+
+/// Returns bcToken Holding PDA address
+pub fn get_bc_token_holding_address(
+    program_id: &Pubkey,
+    bc_token: &Pubkey,
+    bc_token_mint: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_bc_token_holding_address_seeds(bc_token, bc_token_mint),
+        program_id,
+    )
+    .0
+}
+
+/// Returns bcToken Metadata PDA seeds
+/// Every bcToken has its own metadata account
+pub fn get_bc_token_metadata_address_seeds(bc_token: &Pubkey) -> [&[u8]; 2] {
+    [BC_TOKEN_METADATA_SEED, bc_token.as_ref()]
+}
+
+/// Returns bcToken Metadata PDA address
+pub fn get_bc_token_metadata_address(program_id: &Pubkey, bc_token: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_bc_token_metadata_address_seeds(bc_token), program_id).0
+}
+
+/// Returns KickerCoinOwnerRecord PDA seeds
+pub fn get_kicker_coin_owner_record_address_seeds<'a>(
+    kicker_coin_holder: &'a Pubkey,
+    coordinator: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        KICKER_COIN_OWNER_RECORD_SEED,
+        kicker_coin_holder.as_ref(),
+        coordinator.as_ref(),
+    ]
+}
+
+/// Returns KickerCoinOwnerRecord PDA address
+pub fn get_kicker_coin_owner_record_address(
+    program_id: &Pubkey,
+    kicker_coin_holder: &Pubkey,
+    coordinator: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_kicker_coin_owner_record_address_seeds(kicker_coin_holder, coordinator),
+        program_id,
+    )
+    .0
+}
+
+/// Returns Tanistry PDA seeds
+/// A Tanistry is formed from the approved KickerCoinOwnerRecord
+/// Note: The Tanistry PDA is also the authority of the Tanistry vault and the refund receipt mint
+pub fn get_tanistry_address_seeds(kicker_coin_owner_record: &Pubkey) -> [&[u8]; 2] {
+    [TANISTRY_SEED, kicker_coin_owner_record.as_ref()]
+}
+
+/// Returns Tanistry PDA address
+pub fn get_tanistry_address(program_id: &Pubkey, kicker_coin_owner_record: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_tanistry_address_seeds(kicker_coin_owner_record),
+        program_id,
+    )
+    .0
+}
+
+/// Returns Tanistry Token Holding PDA seeds
+/// The holding account is owned by the Tanistry PDA
+pub fn get_tanistry_token_holding_address_seeds<'a>(
+    tanistry: &'a Pubkey,
+    tanistry_token_mint: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        PROGRAM_AUTHORITY_SEED,
+        tanistry.as_ref(),
+        tanistry_token_mint.as_ref(),
+    ]
+}
+
+/// Returns Tanistry Token Holding PDA address
+pub fn get_tanistry_token_holding_address(
+    program_id: &Pubkey,
+    tanistry: &Pubkey,
+    tanistry_token_mint: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_tanistry_token_holding_address_seeds(tanistry, tanistry_token_mint),
+        program_id,
+    )
+    .0
+}
+
+/// Returns Coordinator Refund Receipt Mint PDA seeds
+/// The mint authority is the Tanistry PDA until the single receipt is minted
+pub fn get_coordinator_refund_receipt_mint_address_seeds(tanistry: &Pubkey) -> [&[u8]; 2] {
+    [REFUND_RECEIPT_SEED, tanistry.as_ref()]
+}
+
+/// Returns Coordinator Refund Receipt Mint PDA address
+pub fn get_coordinator_refund_receipt_mint_address(
+    program_id: &Pubkey,
+    tanistry: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_coordinator_refund_receipt_mint_address_seeds(tanistry),
+        program_id,
+    )
+    .0
+}
+
+/// Returns Coordinator Refund Receipt Token account PDA seeds
+pub fn get_coordinator_refund_receipt_token_address_seeds<'a>(
+    tanistry: &'a Pubkey,
+    coordinator: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [REFUND_RECEIPT_SEED, tanistry.as_ref(), coordinator.as_ref()]
+}
+
+/// Returns Coordinator Refund Receipt Token account PDA address
+pub fn get_coordinator_refund_receipt_token_address(
+    program_id: &Pubkey,
+    tanistry: &Pubkey,
+    coordinator: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_coordinator_refund_receipt_token_address_seeds(tanistry, coordinator),
+        program_id,
+    )
+    .0
+}
+
+/// Returns CandidateLimitRecord PDA seeds
+pub fn get_candidate_limit_record_address_seeds<'a>(
+    tanistry: &'a Pubkey,
+    candidate_token_mint: &'a Pubkey,
+    candidate_token_owner: &'a Pubkey,
+) -> [&'a [u8]; 4] {
+    [
+        CANDIDATE_LIMIT_RECORD_SEED,
+        tanistry.as_ref(),
+        candidate_token_mint.as_ref(),
+        candidate_token_owner.as_ref(),
+    ]
+}
+
+/// Returns CandidateLimitRecord PDA address
+pub fn get_candidate_limit_record_address(
+    program_id: &Pubkey,
+    tanistry: &Pubkey,
+    candidate_token_mint: &Pubkey,
+    candidate_token_owner: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_candidate_limit_record_address_seeds(
+            tanistry,
+            candidate_token_mint,
+            candidate_token_owner,
+        ),
+        program_id,
+    )
+    .0
+}
+
+/// Returns MixContentRecord PDA seeds
+pub fn get_mix_content_record_address_seeds<'a>(
+    rater_candidate: &'a Pubkey,
+    buddy_candidate: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        MIX_CONTENT_RECORD_SEED,
+        rater_candidate.as_ref(),
+        buddy_candidate.as_ref(),
+    ]
+}
+
+/// Returns MixContentRecord PDA address
+pub fn get_mix_content_record_address(
+    program_id: &Pubkey,
+    rater_candidate: &Pubkey,
+    buddy_candidate: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_mix_content_record_address_seeds(rater_candidate, buddy_candidate),
+        program_id,
+    )
+    .0
+}
+
+/// Returns RateOption PDA seeds
+pub fn get_rate_option_address_seeds<'a>(
+    mix_content_record: &'a Pubkey,
+    issuer_roydamna: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        RATE_OPTION_SEED,
+        mix_content_record.as_ref(),
+        issuer_roydamna.as_ref(),
+    ]
+}
+
+/// Returns RateOption PDA address
+pub fn get_rate_option_address(
+    program_id: &Pubkey,
+    mix_content_record: &Pubkey,
+    issuer_roydamna: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_rate_option_address_seeds(mix_content_record, issuer_roydamna),
+        program_id,
+    )
+    .0
+}
+
+/// Returns RateOtherRecord PDA seeds
+pub fn get_rate_other_record_address_seeds<'a>(
+    rater: &'a Pubkey,
+    rated_content: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        RATE_OTHER_RECORD_SEED,
+        rater.as_ref(),
+        rated_content.as_ref(),
+    ]
+}
+
+/// Returns RateOtherRecord PDA address
+pub fn get_rate_other_record_address(
+    program_id: &Pubkey,
+    rater: &Pubkey,
+    rated_content: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_rate_other_record_address_seeds(rater, rated_content),
+        program_id,
+    )
+    .0
+}
+
+/// Returns OutsideBuyerRecord PDA seeds
+pub fn get_outside_buyer_record_address_seeds<'a>(
+    tanistry: &'a Pubkey,
+    outside_buyer_token_mint: &'a Pubkey,
+    outside_buyer_token_owner: &'a Pubkey,
+) -> [&'a [u8]; 4] {
+    [
+        OUTSIDE_BUYER_RECORD_SEED,
+        tanistry.as_ref(),
+        outside_buyer_token_mint.as_ref(),
+        outside_buyer_token_owner.as_ref(),
+    ]
+}
+
+/// Returns OutsideBuyerRecord PDA address
+pub fn get_outside_buyer_record_address(
+    program_id: &Pubkey,
+    tanistry: &Pubkey,
+    outside_buyer_token_mint: &Pubkey,
+    outside_buyer_token_owner: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_outside_buyer_record_address_seeds(
+            tanistry,
+            outside_buyer_token_mint,
+            outside_buyer_token_owner,
+        ),
+        program_id,
+    )
+    .0
+}
+
+/// Returns CCVoteRecord PDA seeds
+/// The vote is casted with the CandidateLimitRecord so its deposit can back a single vote at a time
+pub fn get_cc_vote_record_address_seeds(candidate_limit_record: &Pubkey) -> [&[u8]; 2] {
+    [CC_VOTE_RECORD_SEED, candidate_limit_record.as_ref()]
+}
+
+/// Returns CCVoteRecord PDA address
+pub fn get_cc_vote_record_address(program_id: &Pubkey, candidate_limit_record: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_cc_vote_record_address_seeds(candidate_limit_record),
+        program_id,
+    )
+    .0
+}
+
+/// Returns ShihonConfig PDA seeds
+/// There is a single config per program deployment
+pub fn get_shihon_config_address_seeds() -> [&'static [u8]; 1] {
+    [SHIHON_CONFIG_SEED]
+}
+
+/// Returns ShihonConfig PDA address
+pub fn get_shihon_config_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_shihon_config_address_seeds(), program_id).0
+}
+
+/// Returns Treasury token account PDA seeds
+/// The treasury collects the protocol fees in the given mint and is owned by the ShihonConfig PDA
+pub fn get_treasury_address_seeds(treasury_mint: &Pubkey) -> [&[u8]; 2] {
+    [TREASURY_SEED, treasury_mint.as_ref()]
+}
+
+/// Returns Treasury token account PDA address
+pub fn get_treasury_address(program_id: &Pubkey, treasury_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_treasury_address_seeds(treasury_mint), program_id).0
+}
+
+/// Returns Ring PDA seeds
+/// A Ring is identified by the first Tanistry of its chain
+pub fn get_ring_address_seeds(first_tanistry: &Pubkey) -> [&[u8]; 2] {
+    [RING_SEED, first_tanistry.as_ref()]
+}
+
+/// Returns Ring PDA address
+pub fn get_ring_address(program_id: &Pubkey, first_tanistry: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&get_ring_address_seeds(first_tanistry), program_id).0
+}
+
+/// Returns Pinhole PDA seeds
+/// The pinhole authority is a one-time key so the address doesn't reveal the main wallet behind it
+pub fn get_pinhole_address_seeds<'a>(
+    kicker_coin_owner_record: &'a Pubkey,
+    pinhole_authority: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        PINHOLE_SEED,
+        kicker_coin_owner_record.as_ref(),
+        pinhole_authority.as_ref(),
+    ]
+}
+
+/// Returns Pinhole PDA address
+pub fn get_pinhole_address(
+    program_id: &Pubkey,
+    kicker_coin_owner_record: &Pubkey,
+    pinhole_authority: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_pinhole_address_seeds(kicker_coin_owner_record, pinhole_authority),
+        program_id,
+    )
+    .0
+}
+
+/// Returns TripleMultisig PDA seeds
+/// The multisig is scoped to the rater and buddy pair like the MixContentRecord it authorizes
+pub fn get_triple_multisig_address_seeds<'a>(
+    rater_candidate: &'a Pubkey,
+    buddy_candidate: &'a Pubkey,
+) -> [&'a [u8]; 3] {
+    [
+        TRIPLE_MULTISIG_SEED,
+        rater_candidate.as_ref(),
+        buddy_candidate.as_ref(),
+    ]
+}
+
+/// Returns TripleMultisig PDA address
+pub fn get_triple_multisig_address(
+    program_id: &Pubkey,
+    rater_candidate: &Pubkey,
+    buddy_candidate: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &get_triple_multisig_address_seeds(rater_candidate, buddy_candidate),
+        program_id,
+    )
+    .0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::BorshSerialize;
+    use solana_program::account_info::AccountInfo;
+
+    use crate::{
+        error::ShihonError,
+        instruction::{
+            candidate, change_cc_vote, claim_refund, coordinator_withdraw, create_rate_option,
+            create_ring, create_triple_multisig, drop_pinhole, vote_for_cc, withdraw_treasury,
+        },
+        state::{
+            candidate_limit_record::{
+                get_candidate_limit_record_data_for_seeds, CandidateLimitRecord,
+            },
+            cc_vote_record::{get_cc_vote_record_data_for_seeds, CCVoteRecord},
+            enums::{PinholeSide, ShihonAccountType, VoteSource},
+            pinhole::{get_pinhole_data_for_kicker_coin_owner_record, Pinhole},
+            rate_option::{get_rate_option_data_for_seeds, RateOption},
+            ring::{get_ring_data_for_seeds, Ring},
+            triple_multisig::{get_triple_multisig_data_for_candidates, TripleMultisig},
+        },
+    };
+
+    /// Returns the PDA and its bump for the seeds spelled out by the test
+    fn find_address(program_id: &Pubkey, seeds: &[&[u8]]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    fn create_test_cc_vote_record(bump: u8, target_ring: Pubkey) -> CCVoteRecord {
+        CCVoteRecord {
+            account_type: ShihonAccountType::CCVoteRecordV2,
+            bump,
+            target_ring,
+            voter: Pubkey::new_unique(),
+            is_relinquished: false,
+            voter_weight: 100,
+            vote: VoteSource::Pull,
+        }
+    }
+
+    #[test]
+    fn test_vote_for_cc_cc_vote_record_address_passes_processor_check() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let target_ring = Pubkey::new_unique();
+        let candidate_limit_record = Pubkey::new_unique();
+
+        let instruction = vote_for_cc(
+            &program_id,
+            &candidate_limit_record,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &target_ring,
+            VoteSource::Pull,
+        );
+
+        let (address, bump) = find_address(
+            &program_id,
+            &[b"cc-vote-record", candidate_limit_record.as_ref()],
+        );
+        let mut lamports = 0;
+        let mut data = create_test_cc_vote_record(bump, target_ring)
+            .try_to_vec()
+            .unwrap();
+        let account_info = AccountInfo::new(
+            &instruction.accounts[1].pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let result =
+            get_cc_vote_record_data_for_seeds(&program_id, &account_info, &candidate_limit_record);
+
+        // Assert
+        assert_eq!(instruction.accounts[1].pubkey, address);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_change_cc_vote_cc_vote_record_address_passes_processor_check() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let target_ring = Pubkey::new_unique();
+        let candidate_limit_record = Pubkey::new_unique();
+
+        let instruction = change_cc_vote(
+            &program_id,
+            &target_ring,
+            &candidate_limit_record,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            VoteSource::Push,
+        );
+
+        let (_, bump) = find_address(
+            &program_id,
+            &[b"cc-vote-record", candidate_limit_record.as_ref()],
+        );
+        let mut lamports = 0;
+        let mut data = create_test_cc_vote_record(bump, target_ring)
+            .try_to_vec()
+            .unwrap();
+        let account_info = AccountInfo::new(
+            &instruction.accounts[1].pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let result =
+            get_cc_vote_record_data_for_seeds(&program_id, &account_info, &candidate_limit_record);
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_cc_vote_record_of_other_candidate_limit_record_fails_processor_check() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let target_ring = Pubkey::new_unique();
+        let candidate_limit_record = Pubkey::new_unique();
+
+        let instruction = vote_for_cc(
+            &program_id,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &target_ring,
+            VoteSource::Pull,
+        );
+
+        let (_, bump) = find_address(
+            &program_id,
+            &[b"cc-vote-record", candidate_limit_record.as_ref()],
+        );
+        let mut lamports = 0;
+        let mut data = create_test_cc_vote_record(bump, target_ring)
+            .try_to_vec()
+            .unwrap();
+        let account_info = AccountInfo::new(
+            &instruction.accounts[1].pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let err =
+            get_cc_vote_record_data_for_seeds(&program_id, &account_info, &candidate_limit_record)
+                .err()
+                .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidCCVoteRecordAddress.into());
+    }
+
+    #[test]
+    fn test_create_ring_ring_address_passes_processor_check() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let first_tanistry = Pubkey::new_unique();
+
+        let instruction = create_ring(&program_id, &first_tanistry, &Pubkey::new_unique());
+
+        let (address, bump) = find_address(&program_id, &[b"ring", first_tanistry.as_ref()]);
+        let ring = Ring {
+            account_type: ShihonAccountType::Ring,
+            bump,
+            first_tanistry,
+            last_crowned_tanistry: None,
+            crowned_tanistry_count: 0,
+            cumulative_deposit_amount: 0,
+            cumulative_rft_volume: 0,
+            cc_vote_weight: 0,
+            cc_vote_counting_time: None,
+            reserved: [0; 64],
+        };
+        let mut lamports = 0;
+        let mut data = ring.try_to_vec().unwrap();
+        let account_info = AccountInfo::new(
+            &instruction.accounts[0].pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let result = get_ring_data_for_seeds(&program_id, &account_info);
+
+        // Assert
+        assert_eq!(instruction.accounts[0].pubkey, address);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_drop_pinhole_pinhole_address_passes_processor_check() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let kicker_coin_owner_record = Pubkey::new_unique();
+        let pinhole_authority = Pubkey::new_unique();
+
+        let instruction = drop_pinhole(
+            &program_id,
+            &kicker_coin_owner_record,
+            &Pubkey::new_unique(),
+            &pinhole_authority,
+            &Pubkey::new_unique(),
+            PinholeSide::Kicker,
+        );
+
+        let (address, bump) = find_address(
+            &program_id,
+            &[
+                b"pinhole",
+                kicker_coin_owner_record.as_ref(),
+                pinhole_authority.as_ref(),
+            ],
+        );
+        let pinhole = Pinhole {
+            account_type: ShihonAccountType::Pinhole,
+            bump,
+            kicker_coin_owner_record,
+            round: 0,
+            side: PinholeSide::Kicker,
+            pinhole_authority,
+        };
+        let mut lamports = 0;
+        let mut data = pinhole.try_to_vec().unwrap();
+        let account_info = AccountInfo::new(
+            &instruction.accounts[0].pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let result = get_pinhole_data_for_kicker_coin_owner_record(
+            &program_id,
+            &account_info,
+            &kicker_coin_owner_record,
+        );
+
+        // Assert
+        assert_eq!(instruction.accounts[0].pubkey, address);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_create_triple_multisig_triple_multisig_address_passes_processor_check() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let rater_candidate = Pubkey::new_unique();
+        let buddy_candidate = Pubkey::new_unique();
+
+        let instruction = create_triple_multisig(
+            &program_id,
+            &rater_candidate,
+            &buddy_candidate,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+        );
+
+        let (address, bump) = find_address(
+            &program_id,
+            &[
+                b"triple-multisig",
+                rater_candidate.as_ref(),
+                buddy_candidate.as_ref(),
+            ],
+        );
+        let triple_multisig = TripleMultisig {
+            account_type: ShihonAccountType::TripleMultisig,
+            bump,
+            rater_candidate,
+            buddy_candidate,
+            m: 2,
+            n: 3,
+            signers: [
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        };
+        let mut lamports = 0;
+        let mut data = triple_multisig.try_to_vec().unwrap();
+        let account_info = AccountInfo::new(
+            &instruction.accounts[0].pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let result = get_triple_multisig_data_for_candidates(
+            &program_id,
+            &account_info,
+            &rater_candidate,
+            &buddy_candidate,
+        );
+
+        // Assert
+        assert_eq!(instruction.accounts[0].pubkey, address);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_create_rate_option_rate_option_address_passes_processor_check() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let issuer_roydamna = Pubkey::new_unique();
+        let mix_content_record = Pubkey::new_unique();
+
+        let instruction = create_rate_option(
+            &program_id,
+            &issuer_roydamna,
+            &Pubkey::new_unique(),
+            &mix_content_record,
+            &Pubkey::new_unique(),
+        );
+
+        let (address, bump) = find_address(
+            &program_id,
+            &[
+                b"rate-option",
+                mix_content_record.as_ref(),
+                issuer_roydamna.as_ref(),
+            ],
+        );
+        let rate_option = RateOption {
+            account_type: ShihonAccountType::RateOptionV2,
+            bump,
+            issuer_roydamna,
+            mix_content_record,
+            rate_other_record: Pubkey::new_unique(),
+            number_of_issue: 1,
+            buddy_candidate: Pubkey::new_unique(),
+            init_content: Pubkey::new_unique(),
+            signed_off: false,
+        };
+        let mut lamports = 0;
+        let mut data = rate_option.try_to_vec().unwrap();
+        let account_info = AccountInfo::new(
+            &instruction.accounts[0].pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let result = get_rate_option_data_for_seeds(
+            &program_id,
+            &account_info,
+            &mix_content_record,
+            &issuer_roydamna,
+        );
+
+        // Assert
+        assert_eq!(instruction.accounts[0].pubkey, address);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_candidate_candidate_limit_record_address_passes_processor_check() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let tanistry = Pubkey::new_unique();
+        let tanistry_token_mint = Pubkey::new_unique();
+        let candidate_token_owner = Pubkey::new_unique();
+
+        let instruction = candidate(
+            &program_id,
+            &tanistry,
+            &candidate_token_owner,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &tanistry_token_mint,
+            &Pubkey::new_unique(),
+            10,
+        );
+
+        let (address, bump) = find_address(
+            &program_id,
+            &[
+                b"candidate-limit-record",
+                tanistry.as_ref(),
+                tanistry_token_mint.as_ref(),
+                candidate_token_owner.as_ref(),
+            ],
+        );
+        let candidate_limit_record = CandidateLimitRecord {
+            account_type: ShihonAccountType::CandidateLimitRecordV2,
+            bump,
+            belonging_tanistry: tanistry,
+            number_of_candidate_count: 1,
+            candidate_token_mint: tanistry_token_mint,
+            candidate_token_owner,
+            candidate_delegate: None,
+            candidate_token_deposit_amount: 10,
+            received_rating: 0,
+            refund_amount: 0,
+            is_refunded: false,
+            candidate_fee_amount: 0,
+            rate_options_count: 0,
+            bc_token: Pubkey::new_unique(),
+        };
+        let mut lamports = 0;
+        let mut data = candidate_limit_record.try_to_vec().unwrap();
+        let account_info = AccountInfo::new(
+            &instruction.accounts[1].pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let result = get_candidate_limit_record_data_for_seeds(
+            &program_id,
+            &account_info,
+            &tanistry,
+            &tanistry_token_mint,
+            &candidate_token_owner,
+        );
+
+        // Assert
+        assert_eq!(instruction.accounts[1].pubkey, address);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_claim_refund_derives_tanistry_token_holding_address() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let tanistry = Pubkey::new_unique();
+        let tanistry_token_mint = Pubkey::new_unique();
+
+        // Act
+        let instruction = claim_refund(
+            &program_id,
+            &tanistry,
+            &tanistry_token_mint,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &spl_token::id(),
+            None,
+        );
+
+        // Assert
+        assert_eq!(
+            instruction.accounts[1].pubkey,
+            find_address(
+                &program_id,
+                &[b"shihon", tanistry.as_ref(), tanistry_token_mint.as_ref()]
+            )
+            .0
+        );
+    }
+
+    #[test]
+    fn test_coordinator_withdraw_derives_refund_receipt_addresses() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let tanistry = Pubkey::new_unique();
+        let coordinator = Pubkey::new_unique();
+
+        // Act
+        let instruction = coordinator_withdraw(
+            &program_id,
+            &tanistry,
+            &Pubkey::new_unique(),
+            &coordinator,
+            &Pubkey::new_unique(),
+        );
+
+        // Assert
+        assert_eq!(
+            instruction.accounts[3].pubkey,
+            find_address(&program_id, &[b"refund-receipt", tanistry.as_ref()]).0
+        );
+        assert_eq!(
+            instruction.accounts[4].pubkey,
+            find_address(
+                &program_id,
+                &[b"refund-receipt", tanistry.as_ref(), coordinator.as_ref()]
+            )
+            .0
+        );
+    }
+
+    #[test]
+    fn test_withdraw_treasury_derives_treasury_address() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let treasury_mint = Pubkey::new_unique();
+
+        // Act
+        let instruction = withdraw_treasury(
+            &program_id,
+            &Pubkey::new_unique(),
+            &treasury_mint,
+            &Pubkey::new_unique(),
+            &spl_token::id(),
+            10,
+        );
+
+        // Assert
+        assert_eq!(
+            instruction.accounts[2].pubkey,
+            find_address(&program_id, &[b"shihon-treasury", treasury_mint.as_ref()]).0
+        );
+    }
+
+    #[test]
+    fn test_is_valid_pda_with_stored_bump() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let kicker_coin_owner_record = Pubkey::new_unique();
+        let seeds = get_tanistry_address_seeds(&kicker_coin_owner_record);
+        let (tanistry, bump) = Pubkey::find_program_address(&seeds, &program_id);
+
+        // Act
+        let is_valid = is_valid_pda(&program_id, &tanistry, &seeds, bump);
+
+        // Assert
+        assert!(is_valid);
+    }
+
+    #[test]
+    fn test_is_valid_pda_with_invalid_bump_error() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let kicker_coin_owner_record = Pubkey::new_unique();
+        let seeds = get_tanistry_address_seeds(&kicker_coin_owner_record);
+        let (tanistry, bump) = Pubkey::find_program_address(&seeds, &program_id);
+
+        // Act
+        let is_valid = is_valid_pda(&program_id, &tanistry, &seeds, bump.wrapping_sub(1));
+
+        // Assert
+        assert!(!is_valid);
+    }
+
+    #[test]
+    fn test_account_types_with_same_keys_derive_different_addresses() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        // Act
+        let addresses = vec![
+            get_kicker_coin_owner_record_address(&program_id, &first, &second),
+            get_mix_content_record_address(&program_id, &first, &second),
+            get_rate_option_address(&program_id, &first, &second),
+            get_rate_other_record_address(&program_id, &first, &second),
+            get_tanistry_token_holding_address(&program_id, &first, &second),
+            get_coordinator_refund_receipt_token_address(&program_id, &first, &second),
+            get_pinhole_address(&program_id, &first, &second),
+            get_triple_multisig_address(&program_id, &first, &second),
+            get_bc_token_metadata_address(&program_id, &first),
+            get_tanistry_address(&program_id, &first),
+            get_cc_vote_record_address(&program_id, &first),
+            get_treasury_address(&program_id, &first),
+            get_ring_address(&program_id, &first),
+        ];
+
+        // Assert
+        for (i, address) in addresses.iter().enumerate() {
+            assert!(!addresses[i + 1..].contains(address));
+        }
+    }
+}
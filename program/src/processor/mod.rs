@@ -1,34 +1,82 @@
 //! Program processor
 
+mod process_accept_bc_token_authority;
 mod process_approve_kicker_coin;
 mod process_bump_self_rate;
 mod process_buy_exceeded_rate_token;
+mod process_cancel_tanistry;
 mod process_candidate;
+mod process_change_cc_vote;
+mod process_claim_refund;
+mod process_coordinator_withdraw;
 mod process_create_bc_token;
+mod process_create_rate_option;
+mod process_create_ring;
+mod process_create_shihon_config;
+mod process_create_treasury;
+mod process_create_triple_multisig;
 mod process_crowning;
 mod process_deny_kicker_coin;
 mod process_discard_bc_token;
 mod process_draft_blank_check;
+mod process_drop_pinhole;
 mod process_kick_to_coordinator;
+mod process_migrate_account;
 mod process_mix_content;
+mod process_promote_mixed_content;
 mod process_rate_other;
+mod process_redeem_coordinator_refund_receipt;
+mod process_relinquish_cc_vote;
+mod process_remove_rate_option;
+mod process_set_bc_token_authority;
+mod process_set_bc_token_delegate;
+mod process_set_candidate_delegate;
+mod process_set_pause_flags;
+mod process_settle_tanistry;
+mod process_sign_off_rate_option;
+mod process_update_shihon_config;
 mod process_vote_for_cc;
+mod process_withdraw_treasury;
 
 use crate::instruction::ShihonInstruction;
 
+use process_accept_bc_token_authority::*;
 use process_approve_kicker_coin::*;
 use process_bump_self_rate::*;
 use process_buy_exceeded_rate_token::*;
+use process_cancel_tanistry::*;
 use process_candidate::*;
+use process_change_cc_vote::*;
+use process_claim_refund::*;
+use process_coordinator_withdraw::*;
 use process_create_bc_token::*;
+use process_create_rate_option::*;
+use process_create_ring::*;
+use process_create_shihon_config::*;
+use process_create_treasury::*;
+use process_create_triple_multisig::*;
 use process_crowning::*;
 use process_deny_kicker_coin::*;
 use process_discard_bc_token::*;
 use process_draft_blank_check::*;
+use process_drop_pinhole::*;
 use process_kick_to_coordinator::*;
+use process_migrate_account::*;
 use process_mix_content::*;
+use process_promote_mixed_content::*;
 use process_rate_other::*;
+use process_redeem_coordinator_refund_receipt::*;
+use process_relinquish_cc_vote::*;
+use process_remove_rate_option::*;
+use process_set_bc_token_authority::*;
+use process_set_bc_token_delegate::*;
+use process_set_candidate_delegate::*;
+use process_set_pause_flags::*;
+use process_settle_tanistry::*;
+use process_sign_off_rate_option::*;
+use process_update_shihon_config::*;
 use process_vote_for_cc::*;
+use process_withdraw_treasury::*;
 
 use solana_program::{
     account_info::AccountInfo, borsh::try_from_slice_unchecked, entrypoint::ProgramResult, msg,
@@ -46,49 +94,143 @@ pub fn process_instruction(
     let instruction: ShihonInstruction =
         try_from_slice_unchecked(input).map_err(|_| ProgramError::InvalidInstructionData)?;
 
-    ShihonInstruction::DraftBlankCheck { name } => process_draft_blank_check(program_id, accounts),
+    match instruction {
+        ShihonInstruction::DraftBlankCheck { name } => {
+            process_draft_blank_check(program_id, accounts, name)
+        }
 
-    ShihonInstruction::CreateBcToken {
-        amount,
-        config
-    } => process_create_bc_token(program_id, accounts),
+        ShihonInstruction::CreateBcToken {
+            name,
+            amount,
+            config,
+        } => process_create_bc_token(program_id, accounts, name, amount, config),
 
-    ShihonInstruction::DiscardBcToken {} => process_discard_bc_token(program_id, accounts),
+        ShihonInstruction::DiscardBcToken => process_discard_bc_token(program_id, accounts),
 
-    ShihonInstruction::KickingToCoordinator { coordinator, amount } => process_kick_to_coordinator(program_id, accounts),
+        ShihonInstruction::KickingToCoordinator {
+            coordinator,
+            amount,
+        } => process_kick_to_coordinator(program_id, accounts, coordinator, amount),
 
-    ShihonInstruction::ApproveKickerCoin {
-        coordinator_input
-    } => process_approve_kicker_coin(program_id, accounts),
+        ShihonInstruction::ApproveKickerCoin { coordinator_input } => {
+            process_approve_kicker_coin(program_id, accounts, coordinator_input)
+        }
 
-    ShihonInstruction::DenyKickerCoin {} => process_deny_kicker_coin(program_id, accounts),
+        ShihonInstruction::DenyKickerCoin => process_deny_kicker_coin(program_id, accounts),
 
-    ShihonInstruction::Candidate { coordinator, amount }, {} => process_candidate(program_id, accounts),
+        ShihonInstruction::Candidate { amount } => process_candidate(program_id, accounts, amount),
 
-    ShihonInstruction::MixContent {
-        time_shift_a,
-        time_shift_b,
-    } => process_mix_content(program_id, accounts),
+        ShihonInstruction::MixContent {
+            time_shift_a,
+            time_shift_b,
+            range_a,
+            range_b,
+        } => process_mix_content(
+            program_id,
+            accounts,
+            time_shift_a,
+            time_shift_b,
+            range_a,
+            range_b,
+        ),
 
-    ShihonInstruction::RateOtherContent { rating } => process_rate_other(program_id, accounts),
+        ShihonInstruction::RateOtherContent { rating } => {
+            process_rate_other(program_id, accounts, rating)
+        }
 
-    ShihonInstruction::BumpSelfRate { amount } => process_bump_self_rate(program_id, accounts),
+        ShihonInstruction::BumpSelfRate { amount } => {
+            process_bump_self_rate(program_id, accounts, amount)
+        }
 
-    ShihonInstruction::BuyExceededRateToken { token, amount } => process_buy_exceeded_rate_token(program_id, accounts),
+        ShihonInstruction::BuyExceededRateToken { amount } => {
+            process_buy_exceeded_rate_token(program_id, accounts, amount)
+        }
 
-    ShihonInstruction::Crowning { crown } => process_crowning(program_id, accounts),
+        ShihonInstruction::Crowning { crown } => process_crowning(program_id, accounts, crown),
 
-    ShihonInstruction::VoteForCC {
-        target_ring
-    } => process_vote_for_cc(program_id, accounts),
+        ShihonInstruction::VoteForCC { target_ring, vote } => {
+            process_vote_for_cc(program_id, accounts, target_ring, vote)
+        }
 
-}
+        ShihonInstruction::RelinquishCCVote => process_relinquish_cc_vote(program_id, accounts),
 
-/// Checks whether bcToken account can go forward to the next process
-fn assert_can_go_forward_to_the_process() {
-    unimplemented!();
-}
-/// Checks that how many percentage of process has done by all the member
-fn count_percentage_done_process_member() {
-    unimplemented!();
+        ShihonInstruction::ChangeCCVote {
+            new_target_ring,
+            vote,
+        } => process_change_cc_vote(program_id, accounts, new_target_ring, vote),
+
+        ShihonInstruction::SettleTanistry => process_settle_tanistry(program_id, accounts),
+
+        ShihonInstruction::ClaimRefund => process_claim_refund(program_id, accounts),
+
+        ShihonInstruction::CoordinatorWithdraw => {
+            process_coordinator_withdraw(program_id, accounts)
+        }
+
+        ShihonInstruction::RedeemCoordinatorRefundReceipt => {
+            process_redeem_coordinator_refund_receipt(program_id, accounts)
+        }
+
+        ShihonInstruction::MigrateAccount => process_migrate_account(program_id, accounts),
+
+        ShihonInstruction::CreateShihonConfig { config_args } => {
+            process_create_shihon_config(program_id, accounts, config_args)
+        }
+
+        ShihonInstruction::UpdateShihonConfig { config_args } => {
+            process_update_shihon_config(program_id, accounts, config_args)
+        }
+
+        ShihonInstruction::SetPauseFlags { pause_flags } => {
+            process_set_pause_flags(program_id, accounts, pause_flags)
+        }
+
+        ShihonInstruction::CreateTreasury => process_create_treasury(program_id, accounts),
+
+        ShihonInstruction::WithdrawTreasury { amount } => {
+            process_withdraw_treasury(program_id, accounts, amount)
+        }
+
+        ShihonInstruction::CancelTanistry => process_cancel_tanistry(program_id, accounts),
+
+        ShihonInstruction::CreateRing => process_create_ring(program_id, accounts),
+
+        ShihonInstruction::PromoteMixedContent { name, version } => {
+            process_promote_mixed_content(program_id, accounts, name, version)
+        }
+
+        ShihonInstruction::DropPinhole { side } => process_drop_pinhole(program_id, accounts, side),
+
+        ShihonInstruction::CreateTripleMultisig => {
+            process_create_triple_multisig(program_id, accounts)
+        }
+
+        ShihonInstruction::CreateRateOption => process_create_rate_option(program_id, accounts),
+
+        ShihonInstruction::SignOffRateOption => process_sign_off_rate_option(program_id, accounts),
+
+        ShihonInstruction::RemoveRateOption => process_remove_rate_option(program_id, accounts),
+
+        ShihonInstruction::SetCandidateDelegate {
+            new_candidate_delegate,
+        } => process_set_candidate_delegate(program_id, accounts, new_candidate_delegate),
+
+        ShihonInstruction::SetBcTokenDelegate { new_delegate } => {
+            process_set_bc_token_delegate(program_id, accounts, new_delegate)
+        }
+        ShihonInstruction::SetBcTokenAuthority {
+            new_authority,
+            require_acceptance,
+            renounce_authority,
+        } => process_set_bc_token_authority(
+            program_id,
+            accounts,
+            new_authority,
+            require_acceptance,
+            renounce_authority,
+        ),
+        ShihonInstruction::AcceptBcTokenAuthority => {
+            process_accept_bc_token_authority(program_id, accounts)
+        }
+    }
 }
@@ -0,0 +1,38 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{events::ShihonEvent, state::bc_token::get_bc_token_data};
+
+/// Processes AcceptBcTokenAuthority instruction
+pub fn process_accept_bc_token_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let bc_token_info = next_account_info(account_info_iter)?; // 0
+    let pending_authority_info = next_account_info(account_info_iter)?; // 1
+
+    let mut bc_token_data = get_bc_token_data(program_id, bc_token_info)?;
+
+    bc_token_data.assert_pending_authority_is_signer(pending_authority_info)?;
+
+    // The bcToken could have joined a Tanistry after the authority was proposed
+    bc_token_data.assert_can_set_authority()?;
+
+    bc_token_data.set_authority(Some(*pending_authority_info.key));
+    bc_token_data.serialize(&mut *bc_token_info.data.borrow_mut())?;
+
+    ShihonEvent::BcTokenAuthoritySet {
+        bc_token: *bc_token_info.key,
+        authority: Some(*pending_authority_info.key),
+    }
+    .emit();
+
+    Ok(())
+}
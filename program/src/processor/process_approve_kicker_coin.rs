@@ -2,43 +2,169 @@
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
+    hash::hash,
     pubkey::Pubkey,
     rent::Rent,
     sysvar::Sysvar,
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{
+        find_bump_seed, get_ring_address, get_tanistry_address_seeds,
+        get_tanistry_token_holding_address,
+    },
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        enums::{ShihonAccountType, TanistryState},
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_KICKER_COIN},
+        tanistry::{get_tanistry_data, Tanistry},
+    },
+    tools::account::realloc_and_serialize_account_data,
+};
+
 /// Processes Approve instruction
-pub fn process_approve_kicker_coin(program_id: &Pubkey, accounts: &[AccountInfo],coordinator_input: String,) -> ProgramResult {
-    unimplemented!();
+pub fn process_approve_kicker_coin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    coordinator_input: String,
+) -> ProgramResult {
     // this function make coordinator to input some text message to first kicker
     // that text make rater and init content holder to create RFT for rating other
     // this minting can only when first kicker and coordinator stay in same tanistry
-    // At this point, we need to create a zero version of CandidateLimitRecord to prepare for the candidate
-    // The program to create the mpc is in this record
+    // The hash of the input is kept in the KickerCoinOwnerRecord as the building hash key of the round
+    // The KickerCoin escrowed by the kick stays in the vault and the Tanistry opens the enrollment for the candidates
 
     let account_info_iter = &mut accounts.iter();
 
-    let kicker_info = next_account_info(account_info_iter)?; // 0
-    let coordinator_info = next_account_info(account_info_iter)?; // 1
-    let tanistry_info = next_account_info(account_info_iter)?; // 2
-    let coordinator_input_info = next_account_info(account_info_iter)?; // 3
-    let kicker_token_source_info = next_account_info(account_info_iter)?; // 4
-    let tanistry_authority_info = next_account_info(account_info_iter)?; // 5
-    let coordinator_receive_token_info = next_account_info(account_info_iter)?; // 6
-    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 7
-    let kicker_receive_token_info = next_account_info(account_info_iter)?; // 8
-    let candidate_token_holding_info = next_account_info(account_info_iter)?; // 8
-    let system_info = next_account_info(account_info_iter)?; // 10
-    let rent_sysvar_info = next_account_info(account_info_iter)?; // 11
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 1
+    let coordinator_info = next_account_info(account_info_iter)?; // 2
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 3
+    let tanistry_token_mint_info = next_account_info(account_info_iter)?; // 4
+    let payer_info = next_account_info(account_info_iter)?; // 5
+    let system_info = next_account_info(account_info_iter)?; // 6
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 7
     let rent = &Rent::from_account_info(rent_sysvar_info)?;
-    let clock_info = next_account_info(account_info_iter)?; // 12
+
+    let clock_info = next_account_info(account_info_iter)?; // 8
     let clock = Clock::from_account_info(clock_info)?;
 
-    assert_can_approve_kicker_coin();
+    let shihon_config_info = next_account_info(account_info_iter)?; // 9
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_KICKER_COIN)?;
+
+    let mut kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+    kicker_coin_owner_record_data.assert_can_kick_off(coordinator_info)?;
+
+    if coordinator_input.is_empty() {
+        return Err(ShihonError::InvalidCoordinatorInput.into());
+    }
+
+    if get_tanistry_token_holding_address(
+        program_id,
+        tanistry_info.key,
+        tanistry_token_mint_info.key,
+    ) != *tanistry_token_holding_info.key
+    {
+        return Err(ShihonError::InvalidTanistryTokenHoldingAccount.into());
+    }
+
+    // The Crown of a crowned Tanistry continues its chain by kicking the KickerCoin on
+    // The new Tanistry is linked to the previous one and joins its Ring, otherwise it starts a new chain
+    let (previous_tanistry_id, ring) =
+        if let Some(previous_tanistry_info) = account_info_iter.next() {
+            let crown_candidate_limit_record_info = next_account_info(account_info_iter)?; // 11
+
+            let mut previous_tanistry_data = get_tanistry_data(program_id, previous_tanistry_info)?;
+            previous_tanistry_data.assert_can_link_next_tanistry()?;
+
+            if previous_tanistry_data.crown != Some(*crown_candidate_limit_record_info.key) {
+                return Err(ShihonError::InvalidCrownForTanistry.into());
+            }
+
+            let crown_candidate_limit_record_data = get_candidate_limit_record_data_for_tanistry(
+                program_id,
+                crown_candidate_limit_record_info,
+                previous_tanistry_info.key,
+            )?;
+
+            if crown_candidate_limit_record_data.candidate_token_owner
+                != kicker_coin_owner_record_data.kicker_coin_holder
+            {
+                return Err(ShihonError::KickerIsNotCrownOfPreviousTanistry.into());
+            }
+
+            previous_tanistry_data.next_tanistry_id = Some(*tanistry_info.key);
+            previous_tanistry_data.serialize(&mut *previous_tanistry_info.data.borrow_mut())?;
+
+            (
+                Some(*previous_tanistry_info.key),
+                previous_tanistry_data.ring,
+            )
+        } else {
+            (None, get_ring_address(program_id, tanistry_info.key))
+        };
+
+    let tanistry_address_seeds = get_tanistry_address_seeds(kicker_coin_owner_record_info.key);
+
+    let tanistry_data = Tanistry {
+        account_type: ShihonAccountType::TanistryV2,
+        bump: find_bump_seed(program_id, &tanistry_address_seeds),
+        next_tanistry_id: None,
+        previous_tanistry_id,
+        ring,
+        reserved: [0; 8],
+        kicker_coin_owner_record: *kicker_coin_owner_record_info.key,
+        state: TanistryState::Active,
+        tanistry_token_mint: *tanistry_token_mint_info.key,
+        crown: None,
+        outside_buyer_deposit_amount: 0,
+        settled_amount: 0,
+        refunded_amount: 0,
+        kicker_refund_amount: 0,
+        coordinator_refund_amount: 0,
+        is_kicker_refunded: false,
+        is_coordinator_refunded: false,
+        coordinator_refund_receipt_mint: None,
+        enrolling_at: clock.unix_timestamp,
+        candidate_limit_record_list: vec![],
+    };
+
+    create_and_serialize_account_signed::<Tanistry>(
+        payer_info,
+        tanistry_info,
+        &tanistry_data,
+        &tanistry_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    kicker_coin_owner_record_data.is_kick_off = true;
+    kicker_coin_owner_record_data.building_hash_key =
+        hash(coordinator_input.as_bytes()).to_bytes().to_vec();
 
-    assert_valid_coordinator_input_on_kicker_coin();
+    realloc_and_serialize_account_data(
+        &kicker_coin_owner_record_data,
+        kicker_coin_owner_record_info,
+        payer_info,
+        system_info,
+        rent,
+    )?;
 
+    ShihonEvent::KickerCoinApproved {
+        kicker_coin_owner_record: *kicker_coin_owner_record_info.key,
+        coordinator: *coordinator_info.key,
+        tanistry: *tanistry_info.key,
+    }
+    .emit();
 
+    Ok(())
 }
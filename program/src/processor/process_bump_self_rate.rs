@@ -4,33 +4,89 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
-    rent::Rent,
-    sysvar::Sysvar,
 };
-use spl_governance_tools::account::create_and_serialize_account_signed;
 
-/// Processes Bump instruction
-pub fn process_bump_self_rate(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
-    // what this func needed is only some validators
-    // for update amount of self rating as coin on CandidateLimitRecord, the candidate need to issue same bcToken again without metadata. if want stay his rate same, bcToken with 0 deposit be permitted to issue. and we need to verify that it is the same bcToken by the same person.
-    // in this moment, this candidate can see his own RateOtherRecord for realizing self rating point
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    state::{
+        bc_token::get_bc_token_data,
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_CANDIDATE},
+        tanistry::get_tanistry_data,
+    },
+    tools::spl_token::transfer_spl_tokens,
+};
 
-    let account_info_iter = &mut accounts.iter();
+/// Processes BumpSelfRate instruction
+pub fn process_bump_self_rate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    // The candidate adds more coin to the self-rate with the same bcToken it stands with in the Tanistry
+    // The owner or the delegate can bump, the tokens only move into the vault so the delegate can't take anything out
 
-    let bc_token_info = next_account_info(account_info_iter)?; // 0
-    let bc_token_authority_info = next_account_info(account_info_iter)?; // 1
+    let account_info_iter = &mut accounts.iter();
 
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 1
     let candidate_token_source_info = next_account_info(account_info_iter)?; // 2
-    let candidate_token_info = next_account_info(account_info_iter)?; // 3
+    let candidate_token_authority_info = next_account_info(account_info_iter)?; // 3
+    let candidate_limit_record_info = next_account_info(account_info_iter)?; // 4
+    let bc_token_info = next_account_info(account_info_iter)?; // 5
+    let bc_token_authority_info = next_account_info(account_info_iter)?; // 6
+    let tanistry_token_mint_info = next_account_info(account_info_iter)?; // 7
+    let spl_token_info = next_account_info(account_info_iter)?; // 8
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 9
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_CANDIDATE)?;
+
+    let tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+    tanistry_data.assert_can_bump_self_rate()?;
+    tanistry_data.assert_is_valid_tanistry_token_holding(
+        program_id,
+        tanistry_info.key,
+        tanistry_token_holding_info.key,
+    )?;
+    tanistry_data.assert_is_valid_tanistry_token_mint(tanistry_token_mint_info.key)?;
+
+    let mut candidate_limit_record_data = get_candidate_limit_record_data_for_tanistry(
+        program_id,
+        candidate_limit_record_info,
+        tanistry_info.key,
+    )?;
+    candidate_limit_record_data
+        .assert_token_owner_or_delegate_is_signer(candidate_token_authority_info)?;
 
-    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 4
+    let bc_token_data = get_bc_token_data(program_id, bc_token_info)?;
+    if bc_token_data.bc_token_owner_pubkey != candidate_limit_record_data.candidate_token_owner {
+        return Err(ShihonError::InvalidBcTokenForCandidate.into());
+    }
+    bc_token_data.assert_authority_or_delegate_is_signer(bc_token_authority_info)?;
 
-    let candidate_limit_record_info = next_account_info(account_info_iter)?; // 5
-    let spl_token_info = next_account_info(account_info_iter)?; // 6
+    let received_amount = transfer_spl_tokens(
+        candidate_token_source_info,
+        tanistry_token_mint_info,
+        tanistry_token_holding_info,
+        candidate_token_authority_info,
+        amount,
+        spl_token_info,
+    )?;
 
-    let system_info = next_account_info(account_info_iter)?; // 7
+    candidate_limit_record_data.candidate_token_deposit_amount = candidate_limit_record_data
+        .candidate_token_deposit_amount
+        .checked_add(received_amount)
+        .ok_or(ShihonError::CandidateDepositOverflow)?;
+    candidate_limit_record_data.serialize(&mut *candidate_limit_record_info.data.borrow_mut())?;
 
-    let rent_sysvar_info = next_account_info(account_info_iter)?; // 8
-    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+    ShihonEvent::SelfRateBumped {
+        tanistry: *tanistry_info.key,
+        candidate_limit_record: *candidate_limit_record_info.key,
+        amount: received_amount,
+        deposit_amount: candidate_limit_record_data.candidate_token_deposit_amount,
+    }
+    .emit();
 
+    Ok(())
 }
@@ -2,6 +2,7 @@
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
@@ -9,35 +10,169 @@ use solana_program::{
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_outside_buyer_record_address_seeds},
+    state::{
+        enums::ShihonAccountType,
+        outside_buyer_record::{
+            get_outside_buyer_token_owner_record_data_for_seeds, OutsideBuyerRecord,
+        },
+        shihon_config::{
+            assert_shihon_is_not_paused, calculate_fee_amount, get_shihon_config_data_if_exists,
+            transfer_fee_to_treasury, PAUSE_OUTSIDE_BUYER,
+        },
+        tanistry::get_tanistry_data,
+    },
+    tools::spl_token::transfer_spl_tokens,
+};
+
 /// Processes Buy instruction
 pub fn process_buy_exceeded_rate_token(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    token: Pubkey,
     amount: u64,
 ) -> ProgramResult {
     // Almost the same as methods that CandidateLimitRecord has done with by using OutsideBuyerRecord
-    // we need to set time limits for this functions in some way.
+    // Outside buyers can only buy once the enrollment has ended with enough candidates and until the Crown is elected
+    // The deposit goes into the Tanistry vault and is shared by the candidates once the Tanistry is settled
 
     let account_info_iter = &mut accounts.iter();
 
-    let outside_buyer_info = next_account_info(account_info_iter)?; // 0
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
     let outside_buyer_record_info = next_account_info(account_info_iter)?; // 1
+    let outside_buyer_info = next_account_info(account_info_iter)?; // 2
+    let outside_buyer_token_source_info = next_account_info(account_info_iter)?; // 3
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 4
+    let payer_info = next_account_info(account_info_iter)?; // 5
+    let system_info = next_account_info(account_info_iter)?; // 6
+    let spl_token_info = next_account_info(account_info_iter)?; // 7
 
-    let seller_token_info = next_account_info(account_info_iter)?; // 2
-    let buyer_token_source_info = next_account_info(account_info_iter)?; // 3
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 8
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
 
-    let buyer_token_receive_info = next_account_info(account_info_iter)?; // 4
+    let clock_info = next_account_info(account_info_iter)?; // 9
+    let clock = Clock::from_account_info(clock_info)?;
 
-    let outside_buyer_holding_info = next_account_info(account_info_iter)?; // 5
-    let seller_token_mint_info = next_account_info(account_info_iter)?; // 6
+    let shihon_config_info = next_account_info(account_info_iter)?; // 10
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_OUTSIDE_BUYER)?;
 
-    let system_info = next_account_info(account_info_iter)?; // 7
+    let treasury_info = next_account_info(account_info_iter)?; // 11
+    let tanistry_token_mint_info = next_account_info(account_info_iter)?; // 12
 
-    let clock_info = next_account_info(account_info_iter)?; // 8
-    let clock = Clock::from_account_info(clock_info)?;
+    if !outside_buyer_info.is_signer {
+        return Err(ShihonError::OutsideBuyerTokenOwnerMustSign.into());
+    }
 
-    let rent_sysvar_info = next_account_info(account_info_iter)?; // 9
-    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+    let shihon_config_data = get_shihon_config_data_if_exists(program_id, shihon_config_info)?;
+
+    let mut tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+    tanistry_data.assert_can_buy(
+        shihon_config_data
+            .as_ref()
+            .map(|shihon_config_data| &shihon_config_data.config),
+        clock.unix_timestamp,
+    )?;
+    tanistry_data.assert_is_valid_tanistry_token_holding(
+        program_id,
+        tanistry_info.key,
+        tanistry_token_holding_info.key,
+    )?;
+    tanistry_data.assert_is_valid_tanistry_token_mint(tanistry_token_mint_info.key)?;
+
+    let outside_buyer_fee_bps = shihon_config_data
+        .map(|shihon_config_data| shihon_config_data.config.outside_buyer_fee_bps)
+        .unwrap_or(0);
+    let outside_buyer_fee_amount = calculate_fee_amount(amount, outside_buyer_fee_bps);
+
+    let collected_fee_amount = transfer_fee_to_treasury(
+        program_id,
+        outside_buyer_token_source_info,
+        tanistry_token_mint_info,
+        treasury_info,
+        outside_buyer_info,
+        outside_buyer_fee_amount,
+        spl_token_info,
+    )?;
+
+    let deposit_amount = transfer_spl_tokens(
+        outside_buyer_token_source_info,
+        tanistry_token_mint_info,
+        tanistry_token_holding_info,
+        outside_buyer_info,
+        amount - outside_buyer_fee_amount,
+        spl_token_info,
+    )?;
+
+    let outside_buyer_record_address_seeds = get_outside_buyer_record_address_seeds(
+        tanistry_info.key,
+        tanistry_token_mint_info.key,
+        outside_buyer_info.key,
+    );
+
+    // The buyer can buy several times into the same record so the deposits and the fees are accumulated
+    if outside_buyer_record_info.data_is_empty() {
+        let outside_buyer_record_data = OutsideBuyerRecord {
+            account_type: ShihonAccountType::OutsideBuyerRecordV3,
+            bump: find_bump_seed(program_id, &outside_buyer_record_address_seeds),
+            tanistry: *tanistry_info.key,
+            outside_buyer_token_mint: *tanistry_token_mint_info.key,
+            outside_buyer_token_owner: *outside_buyer_info.key,
+            outside_buyer_token_deposit_amount: deposit_amount,
+            outside_buyer_fee_amount: collected_fee_amount,
+            is_refunded: false,
+            reserved: [0; 6],
+        };
+
+        create_and_serialize_account_signed::<OutsideBuyerRecord>(
+            payer_info,
+            outside_buyer_record_info,
+            &outside_buyer_record_data,
+            &outside_buyer_record_address_seeds,
+            program_id,
+            system_info,
+            rent,
+        )?;
+    } else {
+        let mut outside_buyer_record_data = get_outside_buyer_token_owner_record_data_for_seeds(
+            program_id,
+            outside_buyer_record_info,
+            tanistry_info.key,
+            tanistry_token_mint_info.key,
+            outside_buyer_info.key,
+        )?;
+
+        // V1 and V2 records have no room for the fee and have to be migrated first
+        if outside_buyer_record_data.account_type != ShihonAccountType::OutsideBuyerRecordV3 {
+            return Err(ShihonError::OutsideBuyerRecordMustBeMigrated.into());
+        }
+
+        outside_buyer_record_data.outside_buyer_token_deposit_amount = outside_buyer_record_data
+            .outside_buyer_token_deposit_amount
+            .checked_add(deposit_amount)
+            .ok_or(ShihonError::OutsideBuyerDepositOverflow)?;
+        outside_buyer_record_data.outside_buyer_fee_amount = outside_buyer_record_data
+            .outside_buyer_fee_amount
+            .checked_add(collected_fee_amount)
+            .ok_or(ShihonError::FeeAmountOverflow)?;
+        outside_buyer_record_data.serialize(&mut *outside_buyer_record_info.data.borrow_mut())?;
+    }
+
+    tanistry_data.outside_buyer_deposit_amount = tanistry_data
+        .outside_buyer_deposit_amount
+        .checked_add(deposit_amount)
+        .ok_or(ShihonError::OutsideBuyerDepositOverflow)?;
+    tanistry_data.serialize(&mut *tanistry_info.data.borrow_mut())?;
+
+    ShihonEvent::ExceededRateTokenBought {
+        tanistry: *tanistry_info.key,
+        outside_buyer_record: *outside_buyer_record_info.key,
+        outside_buyer: *outside_buyer_info.key,
+        deposit_amount,
+        fee_amount: collected_fee_amount,
+    }
+    .emit();
 
+    Ok(())
 }
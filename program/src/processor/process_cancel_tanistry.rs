@@ -0,0 +1,143 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    state::{
+        bc_token::get_bc_token_data,
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        enums::{BcTokenState, TanistryState},
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+        shihon_config::get_shihon_config_data,
+        tanistry::get_tanistry_data,
+    },
+    tools::spl_token::get_spl_token_amount,
+};
+
+/// Processes CancelTanistry instruction
+pub fn process_cancel_tanistry(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Anybody can crank the cancellation once the enrollment has ended without enough candidates
+    // The vault isn't split, every candidate and outside buyer gets own deposit back and the kicker gets the KickerCoin back
+    // The refunds are claimed with ClaimRefund the same way as for a settled Tanistry
+
+    let account_info_iter = &mut accounts.iter();
+
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 1
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 2
+    let shihon_config_info = next_account_info(account_info_iter)?; // 3
+    let clock_info = next_account_info(account_info_iter)?; // 4
+    let clock = Clock::from_account_info(clock_info)?;
+
+    let shihon_config_data = get_shihon_config_data(program_id, shihon_config_info)?;
+
+    let mut tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+    tanistry_data.assert_can_cancel(&shihon_config_data.config, clock.unix_timestamp)?;
+    tanistry_data.assert_is_valid_tanistry_token_holding(
+        program_id,
+        tanistry_info.key,
+        tanistry_token_holding_info.key,
+    )?;
+
+    if tanistry_data.kicker_coin_owner_record != *kicker_coin_owner_record_info.key {
+        return Err(ShihonError::InvalidKickerCoinOwnerRecordForTanistry.into());
+    }
+
+    let kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+
+    // 5..n CandidateLimitRecord accounts in the order of candidate_limit_record_list followed by the bcToken accounts
+    // The bcTokens are the kicker's one followed by the candidates' ones in the same order
+    let remaining_infos = account_info_iter.as_slice();
+
+    if remaining_infos.len() < tanistry_data.candidate_limit_record_list.len() {
+        return Err(ShihonError::InvalidCandidateLimitRecordsForSettlement.into());
+    }
+
+    let (candidate_limit_record_infos, bc_token_infos) =
+        remaining_infos.split_at(tanistry_data.candidate_limit_record_list.len());
+
+    // Conservation of funds: the vault must hold the KickerCoin, the self-rating deposits and the outside buyers' funds
+    let mut expected_vault_amount = kicker_coin_owner_record_data
+        .amount_of_kicker_coin
+        .checked_add(tanistry_data.outside_buyer_deposit_amount)
+        .ok_or(ShihonError::RefundAmountOverflow)?;
+    let mut bc_tokens = vec![kicker_coin_owner_record_data.kicker_bc_token];
+
+    for (candidate_limit_record_info, candidate_limit_record) in candidate_limit_record_infos
+        .iter()
+        .zip(tanistry_data.candidate_limit_record_list.iter())
+    {
+        if candidate_limit_record_info.key != candidate_limit_record {
+            return Err(ShihonError::InvalidCandidateLimitRecordsForSettlement.into());
+        }
+
+        let mut candidate_limit_record_data = get_candidate_limit_record_data_for_tanistry(
+            program_id,
+            candidate_limit_record_info,
+            tanistry_info.key,
+        )?;
+
+        expected_vault_amount = expected_vault_amount
+            .checked_add(candidate_limit_record_data.candidate_token_deposit_amount)
+            .ok_or(ShihonError::RefundAmountOverflow)?;
+        bc_tokens.push(candidate_limit_record_data.bc_token);
+
+        candidate_limit_record_data.refund_amount =
+            candidate_limit_record_data.candidate_token_deposit_amount;
+        candidate_limit_record_data
+            .serialize(&mut *candidate_limit_record_info.data.borrow_mut())?;
+    }
+
+    // Anybody can send tokens to the vault, the surplus isn't allocated and stays in the vault
+    if get_spl_token_amount(tanistry_token_holding_info)? < expected_vault_amount {
+        return Err(ShihonError::TanistryVaultBalanceMismatch.into());
+    }
+
+    // Every participant's bcToken must be given exactly once so none of them stays locked in the Tanistry
+    if bc_token_infos.len() != bc_tokens.len() {
+        return Err(ShihonError::InvalidBcTokenForTanistry.into());
+    }
+
+    for (bc_token_info, bc_token) in bc_token_infos.iter().zip(bc_tokens.iter()) {
+        if bc_token_info.key != bc_token {
+            return Err(ShihonError::InvalidBcTokenForTanistry.into());
+        }
+
+        let mut bc_token_data = get_bc_token_data(program_id, bc_token_info)?;
+
+        if bc_token_data.bc_token_state != BcTokenState::EnableToCandidate
+            && bc_token_data.bc_token_state != BcTokenState::TanistryHasSetIn
+        {
+            return Err(ShihonError::InvalidBcTokenState.into());
+        }
+
+        bc_token_data.bc_token_state = BcTokenState::PublicOnTheGround;
+        bc_token_data.serialize(&mut *bc_token_info.data.borrow_mut())?;
+    }
+
+    tanistry_data.kicker_refund_amount = kicker_coin_owner_record_data.amount_of_kicker_coin;
+    tanistry_data.coordinator_refund_amount = 0;
+    tanistry_data.settled_amount = expected_vault_amount;
+    tanistry_data.refunded_amount = 0;
+    tanistry_data.state = TanistryState::Cancelled;
+
+    tanistry_data.serialize(&mut *tanistry_info.data.borrow_mut())?;
+
+    ShihonEvent::TanistryCancelled {
+        tanistry: *tanistry_info.key,
+        candidates: tanistry_data.candidate_limit_record_list.len() as u32,
+        settled_amount: tanistry_data.settled_amount,
+    }
+    .emit();
+
+    Ok(())
+}
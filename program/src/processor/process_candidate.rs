@@ -2,6 +2,7 @@
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
@@ -9,8 +10,29 @@ use solana_program::{
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_candidate_limit_record_address_seeds},
+    state::{
+        bc_token::get_bc_token_data_for_authority,
+        candidate_limit_record::CandidateLimitRecord,
+        enums::{BcTokenState, ShihonAccountType},
+        shihon_config::{
+            assert_shihon_is_not_paused, calculate_fee_amount, get_shihon_config_data_if_exists,
+            transfer_fee_to_treasury, PAUSE_CANDIDATE,
+        },
+        tanistry::get_tanistry_data,
+    },
+    tools::{account::realloc_and_serialize_account_data, spl_token::transfer_spl_tokens},
+};
+
 /// Processes Candidate instruction
-pub fn process_candidate(program_id: &Pubkey, accounts: &[AccountInfo],coordinator: Pubkey, amount: u64) -> ProgramResult {
+pub fn process_candidate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
     // The previously created CandidateLimitRecord has a mechanism to limit the number of coins and people called candidate limit bar
     // CandidateLimitRecord is issued for each new candidate, always referring to the previous record to calculate the number of people and the total coin limit
     // Note: The tanistry will not be formed until the last candidate arrives
@@ -18,15 +40,143 @@ pub fn process_candidate(program_id: &Pubkey, accounts: &[AccountInfo],coordinat
 
     let account_info_iter = &mut accounts.iter();
 
-    let candidate_info = next_account_info(account_info_iter)?; // 0
-    let candidate_source_token_info = next_account_info(account_info_iter)?; // 1
-    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 2
-    let candidate_receive_token_info = next_account_info(account_info_iter)?; // 3
-    let kicker_receive_token_info = next_account_info(account_info_iter)?; // 4
-    let candidate_limit_record_info = next_account_info(account_info_iter)?; // 5
-    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 6
-    let spl_token_info = next_account_info(account_info_iter)?; // 7
-    let system_info = next_account_info(account_info_iter)?; // 8
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
+    let candidate_limit_record_info = next_account_info(account_info_iter)?; // 1
+    let candidate_token_owner_info = next_account_info(account_info_iter)?; // 2
+    let candidate_token_source_info = next_account_info(account_info_iter)?; // 3
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 4
+    let bc_token_info = next_account_info(account_info_iter)?; // 5
+    let payer_info = next_account_info(account_info_iter)?; // 6
+    let system_info = next_account_info(account_info_iter)?; // 7
+    let spl_token_info = next_account_info(account_info_iter)?; // 8
+
     let rent_sysvar_info = next_account_info(account_info_iter)?; // 9
     let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let clock_info = next_account_info(account_info_iter)?; // 10
+    let clock = Clock::from_account_info(clock_info)?;
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 11
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_CANDIDATE)?;
+
+    let treasury_info = next_account_info(account_info_iter)?; // 12
+    let tanistry_token_mint_info = next_account_info(account_info_iter)?; // 13
+
+    if !candidate_token_owner_info.is_signer {
+        return Err(ShihonError::CandidateTokenOwnerMustSign.into());
+    }
+
+    let shihon_config_data = get_shihon_config_data_if_exists(program_id, shihon_config_info)?;
+
+    let mut tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+    tanistry_data.assert_can_candidate(
+        shihon_config_data
+            .as_ref()
+            .map(|shihon_config_data| &shihon_config_data.config),
+        clock.unix_timestamp,
+    )?;
+    tanistry_data.assert_is_valid_tanistry_token_holding(
+        program_id,
+        tanistry_info.key,
+        tanistry_token_holding_info.key,
+    )?;
+    tanistry_data.assert_is_valid_tanistry_token_mint(tanistry_token_mint_info.key)?;
+
+    // The candidate stands with own bcToken which can take part in one Tanistry at a time
+    let mut bc_token_data =
+        get_bc_token_data_for_authority(program_id, bc_token_info, candidate_token_owner_info.key)?;
+    bc_token_data.assert_can_candidate()?;
+
+    // The fee is taken from the deposit, the candidate's self-rate is the deposit without the fee
+    let candidate_fee_bps = shihon_config_data
+        .map(|shihon_config_data| shihon_config_data.config.candidate_fee_bps)
+        .unwrap_or(0);
+    let candidate_fee_amount = calculate_fee_amount(amount, candidate_fee_bps);
+
+    let collected_fee_amount = transfer_fee_to_treasury(
+        program_id,
+        candidate_token_source_info,
+        tanistry_token_mint_info,
+        treasury_info,
+        candidate_token_owner_info,
+        candidate_fee_amount,
+        spl_token_info,
+    )?;
+
+    let deposit_amount = transfer_spl_tokens(
+        candidate_token_source_info,
+        tanistry_token_mint_info,
+        tanistry_token_holding_info,
+        candidate_token_owner_info,
+        amount - candidate_fee_amount,
+        spl_token_info,
+    )?;
+
+    let candidate_limit_record_address_seeds = get_candidate_limit_record_address_seeds(
+        tanistry_info.key,
+        tanistry_token_mint_info.key,
+        candidate_token_owner_info.key,
+    );
+
+    let candidate_limit_record_data = CandidateLimitRecord {
+        account_type: ShihonAccountType::CandidateLimitRecordV2,
+        bump: find_bump_seed(program_id, &candidate_limit_record_address_seeds),
+        belonging_tanistry: *tanistry_info.key,
+        number_of_candidate_count: tanistry_data.candidate_limit_record_list.len() as u32 + 1,
+        candidate_token_mint: *tanistry_token_mint_info.key,
+        candidate_token_owner: *candidate_token_owner_info.key,
+        candidate_delegate: None,
+        candidate_token_deposit_amount: deposit_amount,
+        received_rating: 0,
+        refund_amount: 0,
+        is_refunded: false,
+        candidate_fee_amount: collected_fee_amount,
+        rate_options_count: 0,
+        bc_token: *bc_token_info.key,
+    };
+
+    create_and_serialize_account_signed::<CandidateLimitRecord>(
+        payer_info,
+        candidate_limit_record_info,
+        &candidate_limit_record_data,
+        &candidate_limit_record_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    // The Tanistry grows with every candidate so the account is reallocated to fit the list
+    tanistry_data
+        .candidate_limit_record_list
+        .push(*candidate_limit_record_info.key);
+
+    realloc_and_serialize_account_data(
+        &tanistry_data,
+        tanistry_info,
+        payer_info,
+        system_info,
+        rent,
+    )?;
+
+    bc_token_data.bc_token_state = BcTokenState::EnableToCandidate;
+    bc_token_data.serialize(&mut *bc_token_info.data.borrow_mut())?;
+
+    ShihonEvent::CandidateJoined {
+        tanistry: *tanistry_info.key,
+        candidate_limit_record: *candidate_limit_record_info.key,
+        candidate: *candidate_token_owner_info.key,
+        deposit_amount,
+    }
+    .emit();
+
+    if collected_fee_amount > 0 {
+        ShihonEvent::ProtocolFeeCollected {
+            treasury: *treasury_info.key,
+            record: *candidate_limit_record_info.key,
+            fee_amount: collected_fee_amount,
+        }
+        .emit();
+    }
+
+    Ok(())
 }
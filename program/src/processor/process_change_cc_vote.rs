@@ -0,0 +1,121 @@
+//! Program state processor
+
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        cc_vote_record::get_cc_vote_record_data_for_seeds,
+        enums::VoteSource,
+        ring::get_ring_data,
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_CC_VOTE},
+        tanistry::get_tanistry_data,
+    },
+};
+
+/// Processes ChangeCCVote instruction
+pub fn process_change_cc_vote(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_target_ring: Pubkey,
+    vote: VoteSource,
+) -> ProgramResult {
+    // Redirecting the vote moves its weight to the new ring and keeps the same CCVoteRecord
+    // The vote can be changed until the counting time of the current target ring
+    // and joins the counting window of the new target ring
+    // The new target ring must follow the same pull and push rule as VoteForCC
+
+    let account_info_iter = &mut accounts.iter();
+
+    let target_ring_info = next_account_info(account_info_iter)?; // 0
+    let cc_vote_record_info = next_account_info(account_info_iter)?; // 1
+    let new_target_ring_info = next_account_info(account_info_iter)?; // 2
+    let voter_info = next_account_info(account_info_iter)?; // 3
+
+    let clock_info = next_account_info(account_info_iter)?; // 4
+    let clock = Clock::from_account_info(clock_info)?;
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 5
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_CC_VOTE)?;
+
+    let candidate_limit_record_info = next_account_info(account_info_iter)?; // 6
+    let voter_tanistry_info = next_account_info(account_info_iter)?; // 7
+    let voter_ring_info = next_account_info(account_info_iter)?; // 8
+
+    if *new_target_ring_info.key != new_target_ring {
+        return Err(ShihonError::InvalidTargetRingForCCVoteRecord.into());
+    }
+
+    if new_target_ring == *target_ring_info.key {
+        return Err(ShihonError::CCVoteTargetRingNotChanged.into());
+    }
+
+    let mut cc_vote_record_data = get_cc_vote_record_data_for_seeds(
+        program_id,
+        cc_vote_record_info,
+        candidate_limit_record_info.key,
+    )?;
+
+    if cc_vote_record_data.target_ring != *target_ring_info.key {
+        return Err(ShihonError::InvalidTargetRingForCCVoteRecord.into());
+    }
+
+    let candidate_limit_record_data = get_candidate_limit_record_data_for_tanistry(
+        program_id,
+        candidate_limit_record_info,
+        voter_tanistry_info.key,
+    )?;
+    candidate_limit_record_data.assert_token_owner_or_delegate_is_signer(voter_info)?;
+    candidate_limit_record_data.assert_can_cc_vote()?;
+
+    let mut target_ring_data = get_ring_data(program_id, target_ring_info)?;
+    cc_vote_record_data.assert_can_vote(&target_ring_data, clock.slot)?;
+
+    let voter_tanistry_data = get_tanistry_data(program_id, voter_tanistry_info)?;
+    voter_tanistry_data.assert_can_cc_vote()?;
+    voter_tanistry_data.assert_is_valid_ring(voter_ring_info.key)?;
+
+    let mut new_target_ring_data = get_ring_data(program_id, new_target_ring_info)?;
+
+    let voter_ring_data = get_ring_data(program_id, voter_ring_info)?;
+    voter_ring_data.assert_can_cc_vote(&new_target_ring_data, &vote)?;
+
+    target_ring_data.decrease_cc_vote_weight(cc_vote_record_data.voter_weight)?;
+    target_ring_data.serialize(&mut *target_ring_info.data.borrow_mut())?;
+
+    let counting_time = new_target_ring_data.open_cc_vote_counting_window(clock.slot)?;
+    new_target_ring_data.increase_cc_vote_weight(cc_vote_record_data.voter_weight)?;
+    new_target_ring_data.serialize(&mut *new_target_ring_info.data.borrow_mut())?;
+
+    cc_vote_record_data.target_ring = new_target_ring;
+    cc_vote_record_data.vote = vote;
+    cc_vote_record_data.serialize(&mut *cc_vote_record_info.data.borrow_mut())?;
+
+    ShihonEvent::CCVoteRelinquished {
+        target_ring: *target_ring_info.key,
+        cc_vote_record: *cc_vote_record_info.key,
+        voter: cc_vote_record_data.voter,
+        voter_weight: cc_vote_record_data.voter_weight,
+    }
+    .emit();
+
+    ShihonEvent::CCVoted {
+        target_ring: new_target_ring,
+        cc_vote_record: *cc_vote_record_info.key,
+        voter: cc_vote_record_data.voter,
+        voter_weight: cc_vote_record_data.voter_weight,
+        counting_time,
+    }
+    .emit();
+
+    Ok(())
+}
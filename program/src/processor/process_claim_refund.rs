@@ -0,0 +1,184 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+use spl_governance_tools::error::GovernanceToolsError;
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::get_tanistry_address_seeds,
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        enums::ShihonAccountType, kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+        outside_buyer_record::get_outside_buyer_token_owner_record_data_for_seeds,
+        pinhole::get_pinhole_data_for_kicker_coin_owner_record, tanistry::get_tanistry_data,
+    },
+    tools::spl_token::{get_spl_token_amount, get_spl_token_owner, transfer_spl_tokens_signed},
+};
+
+/// Processes ClaimRefund instruction
+pub fn process_claim_refund(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Candidates (and the Crown) claim with their CandidateLimitRecord
+    // The kicker and the coordinator claim with the KickerCoinOwnerRecord the Tanistry was formed from
+    // Outside buyers claim their deposit back with their OutsideBuyerRecord once the Tanistry is cancelled
+    // They can also claim through their pinhole, then the refund goes to the main wallet the pinhole resolves to
+
+    let account_info_iter = &mut accounts.iter();
+
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 1
+    let refund_record_info = next_account_info(account_info_iter)?; // 2
+    let claimant_info = next_account_info(account_info_iter)?; // 3
+    let claimant_token_info = next_account_info(account_info_iter)?; // 4
+    let spl_token_info = next_account_info(account_info_iter)?; // 5
+    let tanistry_token_mint_info = next_account_info(account_info_iter)?; // 6
+    let pinhole_info = next_account_info(account_info_iter).ok(); // 7
+
+    let mut tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+    tanistry_data.assert_can_claim_refund()?;
+    tanistry_data.assert_is_valid_tanistry_token_holding(
+        program_id,
+        tanistry_info.key,
+        tanistry_token_holding_info.key,
+    )?;
+    tanistry_data.assert_is_valid_tanistry_token_mint(tanistry_token_mint_info.key)?;
+
+    if !claimant_info.is_signer {
+        return Err(ShihonError::RefundClaimantMustSign.into());
+    }
+
+    let account_type: ShihonAccountType =
+        try_from_slice_unchecked(&refund_record_info.data.borrow())?;
+
+    let refund_amount = match account_type {
+        ShihonAccountType::CandidateLimitRecordV1 | ShihonAccountType::CandidateLimitRecordV2 => {
+            let mut candidate_limit_record_data = get_candidate_limit_record_data_for_tanistry(
+                program_id,
+                refund_record_info,
+                tanistry_info.key,
+            )?;
+
+            if candidate_limit_record_data.candidate_token_owner != *claimant_info.key {
+                return Err(ShihonError::InvalidRefundClaimant.into());
+            }
+
+            candidate_limit_record_data.assert_can_claim_refund()?;
+
+            candidate_limit_record_data.is_refunded = true;
+            candidate_limit_record_data.serialize(&mut *refund_record_info.data.borrow_mut())?;
+
+            candidate_limit_record_data.refund_amount
+        }
+        ShihonAccountType::KickerCoinOwnerRecordV1 | ShihonAccountType::KickerCoinOwnerRecordV2 => {
+            if tanistry_data.kicker_coin_owner_record != *refund_record_info.key {
+                return Err(ShihonError::InvalidKickerCoinOwnerRecordForTanistry.into());
+            }
+
+            let kicker_coin_owner_record_data =
+                get_kicker_coin_owner_record_data(program_id, refund_record_info)?;
+
+            let claimant = match pinhole_info {
+                Some(pinhole_info) => {
+                    let pinhole_data = get_pinhole_data_for_kicker_coin_owner_record(
+                        program_id,
+                        pinhole_info,
+                        refund_record_info.key,
+                    )?;
+                    pinhole_data.assert_pinhole_authority_is_signer(claimant_info)?;
+
+                    let owner =
+                        kicker_coin_owner_record_data.resolve_pinhole_owner(pinhole_info.key)?;
+
+                    if get_spl_token_owner(claimant_token_info)? != owner {
+                        return Err(ShihonError::InvalidRefundClaimant.into());
+                    }
+
+                    owner
+                }
+                None => *claimant_info.key,
+            };
+
+            if kicker_coin_owner_record_data.kicker_coin_holder == claimant {
+                if tanistry_data.is_kicker_refunded {
+                    return Err(ShihonError::RefundAlreadyClaimed.into());
+                }
+                tanistry_data.is_kicker_refunded = true;
+                tanistry_data.kicker_refund_amount
+            } else if kicker_coin_owner_record_data.latest_coordinator == claimant {
+                if tanistry_data.coordinator_refund_receipt_mint.is_some() {
+                    return Err(ShihonError::CoordinatorRefundReceiptMustBeRedeemed.into());
+                }
+                if tanistry_data.is_coordinator_refunded {
+                    return Err(ShihonError::RefundAlreadyClaimed.into());
+                }
+                tanistry_data.is_coordinator_refunded = true;
+                tanistry_data.coordinator_refund_amount
+            } else {
+                return Err(ShihonError::InvalidRefundClaimant.into());
+            }
+        }
+        ShihonAccountType::OutsideBuyerRecordV1
+        | ShihonAccountType::OutsideBuyerRecordV2
+        | ShihonAccountType::OutsideBuyerRecordV3 => {
+            let mut outside_buyer_record_data =
+                get_outside_buyer_token_owner_record_data_for_seeds(
+                    program_id,
+                    refund_record_info,
+                    tanistry_info.key,
+                    tanistry_token_mint_info.key,
+                    claimant_info.key,
+                )?;
+
+            outside_buyer_record_data.assert_can_claim_refund(&tanistry_data.state)?;
+
+            outside_buyer_record_data.is_refunded = true;
+            let refund_amount = outside_buyer_record_data.outside_buyer_token_deposit_amount;
+            outside_buyer_record_data.serialize(&mut *refund_record_info.data.borrow_mut())?;
+
+            refund_amount
+        }
+        _ => return Err(GovernanceToolsError::InvalidAccountType.into()),
+    };
+
+    tanistry_data.add_refunded_amount(refund_amount)?;
+
+    if refund_amount > 0 {
+        transfer_spl_tokens_signed(
+            tanistry_token_holding_info,
+            tanistry_token_mint_info,
+            claimant_token_info,
+            tanistry_info,
+            &get_tanistry_address_seeds(&tanistry_data.kicker_coin_owner_record),
+            program_id,
+            refund_amount,
+            spl_token_info,
+        )?;
+    }
+
+    // Conservation of funds: whatever hasn't been refunded yet must still be in the vault
+    // The surplus somebody sent to the vault on top of the settled amount is ignored
+    let outstanding_amount = tanistry_data
+        .settled_amount
+        .checked_sub(tanistry_data.refunded_amount)
+        .unwrap();
+
+    if get_spl_token_amount(tanistry_token_holding_info)? < outstanding_amount {
+        return Err(ShihonError::TanistryVaultBalanceMismatch.into());
+    }
+
+    tanistry_data.serialize(&mut *tanistry_info.data.borrow_mut())?;
+
+    ShihonEvent::Refunded {
+        tanistry: *tanistry_info.key,
+        claimant: *claimant_info.key,
+        amount: refund_amount,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{
+        get_coordinator_refund_receipt_mint_address_seeds,
+        get_coordinator_refund_receipt_token_address_seeds, get_tanistry_address_seeds,
+    },
+    state::{
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data, tanistry::get_tanistry_data,
+    },
+    tools::spl_token::{
+        create_spl_token_account_signed, create_spl_token_mint_signed, mint_spl_tokens_to_signed,
+        remove_spl_token_mint_authority_signed,
+    },
+};
+
+/// Processes CoordinatorWithdraw instruction
+pub fn process_coordinator_withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    // The coordinator can drop the game while the Tanistry is still live
+    // Instead of the refund the coordinator receives the NFT (0 decimals, supply 1) as a receipt
+    // Whoever holds the receipt redeems the coordinator's share once the Tanistry is settled
+
+    let account_info_iter = &mut accounts.iter();
+
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 1
+    let coordinator_info = next_account_info(account_info_iter)?; // 2
+    let refund_receipt_mint_info = next_account_info(account_info_iter)?; // 3
+    let refund_receipt_token_info = next_account_info(account_info_iter)?; // 4
+    let payer_info = next_account_info(account_info_iter)?; // 5
+    let system_info = next_account_info(account_info_iter)?; // 6
+    let spl_token_info = next_account_info(account_info_iter)?; // 7
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 8
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let mut tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+    tanistry_data.assert_can_coordinator_withdraw()?;
+
+    if tanistry_data.kicker_coin_owner_record != *kicker_coin_owner_record_info.key {
+        return Err(ShihonError::InvalidKickerCoinOwnerRecordForTanistry.into());
+    }
+
+    let kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+
+    if kicker_coin_owner_record_data.latest_coordinator != *coordinator_info.key {
+        return Err(ShihonError::InvalidCoordinatorForTanistry.into());
+    }
+
+    if !coordinator_info.is_signer {
+        return Err(ShihonError::CoordinatorMustSign.into());
+    }
+
+    let tanistry_address_seeds =
+        get_tanistry_address_seeds(&tanistry_data.kicker_coin_owner_record);
+
+    create_spl_token_mint_signed(
+        payer_info,
+        refund_receipt_mint_info,
+        &get_coordinator_refund_receipt_mint_address_seeds(tanistry_info.key),
+        tanistry_info.key,
+        0,
+        program_id,
+        system_info,
+        spl_token_info,
+        rent_sysvar_info,
+        rent,
+    )?;
+
+    create_spl_token_account_signed(
+        payer_info,
+        refund_receipt_token_info,
+        &get_coordinator_refund_receipt_token_address_seeds(
+            tanistry_info.key,
+            coordinator_info.key,
+        ),
+        refund_receipt_mint_info,
+        coordinator_info,
+        program_id,
+        system_info,
+        spl_token_info,
+        rent_sysvar_info,
+        rent,
+    )?;
+
+    mint_spl_tokens_to_signed(
+        refund_receipt_mint_info,
+        refund_receipt_token_info,
+        tanistry_info,
+        &tanistry_address_seeds,
+        program_id,
+        1,
+        spl_token_info,
+    )?;
+
+    // Fix the supply at 1 so the receipt can't be duplicated
+    remove_spl_token_mint_authority_signed(
+        refund_receipt_mint_info,
+        tanistry_info,
+        &tanistry_address_seeds,
+        program_id,
+        spl_token_info,
+    )?;
+
+    tanistry_data.coordinator_refund_receipt_mint = Some(*refund_receipt_mint_info.key);
+    tanistry_data.serialize(&mut *tanistry_info.data.borrow_mut())?;
+
+    ShihonEvent::CoordinatorWithdrawn {
+        tanistry: *tanistry_info.key,
+        coordinator: *coordinator_info.key,
+        refund_receipt_mint: *refund_receipt_mint_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -2,6 +2,7 @@
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
@@ -9,6 +10,19 @@ use solana_program::{
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_bc_token_address_seeds, get_bc_token_metadata_address_seeds},
+    state::{
+        bc_token::{get_bc_token_data_for_seeds, BcToken},
+        bc_token_metadata::BcTokenMetadata,
+        enums::{BcTokenState, ShihonAccountType},
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_BC_TOKEN},
+    },
+    tools::account::realloc_and_serialize_account_data,
+};
+
 /// Processes CreateBcToken instruction
 pub fn process_create_bc_token(
     program_id: &Pubkey,
@@ -22,6 +36,7 @@ pub fn process_create_bc_token(
     // Step 2: post a bcToken as DraftBlankCheck into Oracle, and then receive some metadata from the Oracle
     // metadata has content's fingerprint certify that it has been stored securely and some other information about each content
     // After these two processes, bcToken is completed for using as first kicking
+    // A bcToken which wasn't drafted before is created in one go
     // Note: At this moment, we would not write code about generating a metadata
     // Note: any payment information exists in KickerCoinOwnerRecord or CandidateLimitRecord, not in bcToken itself
     let account_info_iter = &mut accounts.iter();
@@ -29,31 +44,96 @@ pub fn process_create_bc_token(
     let bc_token_info = next_account_info(account_info_iter)?; // 0
     let bc_token_authority_info = next_account_info(account_info_iter)?; // 1
     let bc_token_mint_info = next_account_info(account_info_iter)?; // 2
-    let kicker_coin_token_holding_info = next_account_info(account_info_iter)?; // 3
-    let payer_info = next_account_info(account_info_iter)?; // 4
-    let candidate_token_holding_info = next_account_info(account_info_iter)?; // 5
-    let governance_authority_info = next_account_info(account_info_iter)?; // 6
-    let system_info = next_account_info(account_info_iter)?; // 7
-    let spl_token_info = next_account_info(account_info_iter)?; // 8
-    let rent_sysvar_info = next_account_info(account_info_iter)?; // 9
+    let payer_info = next_account_info(account_info_iter)?; // 3
+    let system_info = next_account_info(account_info_iter)?; // 4
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 5
     let rent = &Rent::from_account_info(rent_sysvar_info)?;
 
+    let clock_info = next_account_info(account_info_iter)?; // 6
+    let clock = Clock::from_account_info(clock_info)?;
 
-    assert_create_bc_token(program_id, &config, bc_token_info);
+    let shihon_config_info = next_account_info(account_info_iter)?; // 7
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_BC_TOKEN)?;
 
+    if !bc_token_authority_info.is_signer {
+        return Err(ShihonError::BcTokenAuthorityMustSign.into());
+    }
 
-    create_and_serialize_account_signed::<BcTokenMetadata>(
-        payer_info,
-        bc_token_info,
-        &get_proposal_instruction_address_seeds(
-            proposal_info.key,
-            &option_index.to_le_bytes(),
-            &instruction_index.to_le_bytes(),
+    // The metadata is a fresh original content, the lineage is only set by PromoteMixedContent
+    let config = BcTokenMetadata {
+        account_type: ShihonAccountType::BcTokenMetadataV3,
+        bump: find_bump_seed(
+            program_id,
+            &get_bc_token_metadata_address_seeds(bc_token_info.key),
         ),
-        program_id,
-        system_info,
-        rent,
-    )?;
+        updated_at: clock.slot,
+        lineage: None,
+        ..config
+    };
+
+    if bc_token_info.data_is_empty() {
+        let bc_token_address_seeds = get_bc_token_address_seeds(&name);
+
+        let bc_token_data = BcToken {
+            account_type: ShihonAccountType::BcTokenV3,
+            bump: find_bump_seed(program_id, &bc_token_address_seeds),
+            bc_token_owner_pubkey: *bc_token_authority_info.key,
+            amount_of_coin: amount,
+            issue_at: clock.unix_timestamp,
+            reserved: [0; 8],
+            authority: Some(*bc_token_authority_info.key),
+            delegate: None,
+            pending_authority: None,
+            name: name.clone(),
+            bc_token_mint: *bc_token_mint_info.key,
+            bc_token_state: BcTokenState::HoldingOnPrivate,
+            config,
+        };
+
+        create_and_serialize_account_signed::<BcToken>(
+            payer_info,
+            bc_token_info,
+            &bc_token_data,
+            &bc_token_address_seeds,
+            program_id,
+            system_info,
+            rent,
+        )?;
+    } else {
+        let mut bc_token_data = get_bc_token_data_for_seeds(program_id, bc_token_info, &name)?;
+
+        if bc_token_data.authority != Some(*bc_token_authority_info.key) {
+            return Err(ShihonError::InvalidAuthorityForBcToken.into());
+        }
+
+        if bc_token_data.bc_token_state != BcTokenState::DraftBlankCheck {
+            return Err(ShihonError::InvalidBcTokenState.into());
+        }
+
+        bc_token_data.assert_is_valid_bc_token_mint(bc_token_mint_info.key)?;
+
+        bc_token_data.amount_of_coin = amount;
+        bc_token_data.bc_token_state = BcTokenState::HoldingOnPrivate;
+        bc_token_data.config = config;
+
+        // The blank metadata of the draft has no version so the account grows with the filled in metadata
+        realloc_and_serialize_account_data(
+            &bc_token_data,
+            bc_token_info,
+            payer_info,
+            system_info,
+            rent,
+        )?;
+    }
+
+    ShihonEvent::BcTokenCreated {
+        bc_token: *bc_token_info.key,
+        bc_token_mint: *bc_token_mint_info.key,
+        authority: *bc_token_authority_info.key,
+        name,
+    }
+    .emit();
 
     Ok(())
 }
@@ -0,0 +1,89 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use spl_governance_tools::account::create_and_serialize_account_signed;
+
+use crate::{
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_rate_option_address_seeds, get_rate_other_record_address},
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data,
+        enums::ShihonAccountType,
+        mix_content_record::get_mix_content_record_data,
+        rate_option::{assert_is_valid_issuer_roydamna, RateOption},
+    },
+};
+
+/// Processes CreateRateOption instruction
+pub fn process_create_rate_option(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // A Roydamna issues one RateOption per MixContentRecord it takes part in
+    // The RateOtherRecord is linked by its address because the rating may not be cast yet
+
+    let account_info_iter = &mut accounts.iter();
+
+    let rate_option_info = next_account_info(account_info_iter)?; // 0
+    let issuer_roydamna_info = next_account_info(account_info_iter)?; // 1
+    let issuer_token_owner_info = next_account_info(account_info_iter)?; // 2
+    let mix_content_record_info = next_account_info(account_info_iter)?; // 3
+    let payer_info = next_account_info(account_info_iter)?; // 4
+    let system_info = next_account_info(account_info_iter)?; // 5
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 6
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let mut issuer_roydamna_data =
+        get_candidate_limit_record_data(program_id, issuer_roydamna_info)?;
+    issuer_roydamna_data.assert_token_owner_or_delegate_is_signer(issuer_token_owner_info)?;
+
+    let mix_content_record_data = get_mix_content_record_data(program_id, mix_content_record_info)?;
+    assert_is_valid_issuer_roydamna(&mix_content_record_data, issuer_roydamna_info.key)?;
+
+    let number_of_issue = issuer_roydamna_data.issue_rate_option()?;
+
+    let rate_option_address_seeds =
+        get_rate_option_address_seeds(mix_content_record_info.key, issuer_roydamna_info.key);
+
+    let rate_option_data = RateOption {
+        account_type: ShihonAccountType::RateOptionV2,
+        bump: find_bump_seed(program_id, &rate_option_address_seeds),
+        issuer_roydamna: *issuer_roydamna_info.key,
+        mix_content_record: *mix_content_record_info.key,
+        rate_other_record: get_rate_other_record_address(
+            program_id,
+            &mix_content_record_data.rater_candidate,
+            &mix_content_record_data.buddy_candidate,
+        ),
+        number_of_issue,
+        buddy_candidate: mix_content_record_data.buddy_candidate,
+        init_content: mix_content_record_data.init_bc_token,
+        signed_off: false,
+    };
+
+    create_and_serialize_account_signed::<RateOption>(
+        payer_info,
+        rate_option_info,
+        &rate_option_data,
+        &rate_option_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    issuer_roydamna_data.serialize(&mut *issuer_roydamna_info.data.borrow_mut())?;
+
+    ShihonEvent::RateOptionCreated {
+        rate_option: *rate_option_info.key,
+        issuer_roydamna: *issuer_roydamna_info.key,
+        mix_content_record: *mix_content_record_info.key,
+        number_of_issue,
+    }
+    .emit();
+
+    Ok(())
+}
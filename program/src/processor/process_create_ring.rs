@@ -0,0 +1,72 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use spl_governance_tools::account::create_and_serialize_account_signed;
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_ring_address_seeds},
+    state::{enums::ShihonAccountType, ring::Ring, tanistry::get_tanistry_data},
+};
+
+/// Processes CreateRing instruction
+pub fn process_create_ring(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // A Ring starts from a Tanistry without a previous Tanistry and follows the chain by Crowning
+    // The Ring PDA is derived from the first Tanistry so every chain has exactly one Ring
+
+    let account_info_iter = &mut accounts.iter();
+
+    let ring_info = next_account_info(account_info_iter)?; // 0
+    let first_tanistry_info = next_account_info(account_info_iter)?; // 1
+    let payer_info = next_account_info(account_info_iter)?; // 2
+    let system_info = next_account_info(account_info_iter)?; // 3
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 4
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let first_tanistry_data = get_tanistry_data(program_id, first_tanistry_info)?;
+
+    if first_tanistry_data.previous_tanistry_id.is_some() {
+        return Err(ShihonError::RingMustStartFromFirstTanistry.into());
+    }
+
+    let ring_address_seeds = get_ring_address_seeds(first_tanistry_info.key);
+
+    let ring_data = Ring {
+        account_type: ShihonAccountType::Ring,
+        bump: find_bump_seed(program_id, &ring_address_seeds),
+        first_tanistry: *first_tanistry_info.key,
+        last_crowned_tanistry: None,
+        crowned_tanistry_count: 0,
+        cumulative_deposit_amount: 0,
+        cumulative_rft_volume: 0,
+        cc_vote_weight: 0,
+        cc_vote_counting_time: None,
+        reserved: [0; 64],
+    };
+
+    create_and_serialize_account_signed::<Ring>(
+        payer_info,
+        ring_info,
+        &ring_data,
+        &ring_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    ShihonEvent::RingCreated {
+        ring: *ring_info.key,
+        first_tanistry: *first_tanistry_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
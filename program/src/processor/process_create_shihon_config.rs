@@ -0,0 +1,79 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use spl_governance_tools::account::create_and_serialize_account_signed;
+
+use crate::{
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_shihon_config_address_seeds},
+    state::{
+        enums::ShihonAccountType,
+        shihon_config::{ShihonConfig, ShihonConfigArgs},
+    },
+    tools::bpf_loader_upgradeable::assert_program_upgrade_authority_is_signer,
+};
+
+/// Processes CreateShihonConfig instruction
+pub fn process_create_shihon_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    config_args: ShihonConfigArgs,
+) -> ProgramResult {
+    // The config is a singleton PDA so it can be created only once per deployment
+    // The upgrade authority appoints the admin who tunes the parameters from then on
+
+    let account_info_iter = &mut accounts.iter();
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 0
+    let admin_authority_info = next_account_info(account_info_iter)?; // 1
+    let program_data_info = next_account_info(account_info_iter)?; // 2
+    let program_upgrade_authority_info = next_account_info(account_info_iter)?; // 3
+    let payer_info = next_account_info(account_info_iter)?; // 4
+    let system_info = next_account_info(account_info_iter)?; // 5
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 6
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    assert_program_upgrade_authority_is_signer(
+        program_id,
+        program_data_info,
+        program_upgrade_authority_info,
+    )?;
+
+    config_args.assert_is_valid()?;
+
+    let shihon_config_address_seeds = get_shihon_config_address_seeds();
+
+    let shihon_config_data = ShihonConfig {
+        account_type: ShihonAccountType::ShihonConfig,
+        bump: find_bump_seed(program_id, &shihon_config_address_seeds),
+        admin_authority: *admin_authority_info.key,
+        config: config_args,
+        pause_flags: 0,
+        reserved: [0; 64],
+    };
+
+    create_and_serialize_account_signed::<ShihonConfig>(
+        payer_info,
+        shihon_config_info,
+        &shihon_config_data,
+        &shihon_config_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    ShihonEvent::ShihonConfigUpdated {
+        shihon_config: *shihon_config_info.key,
+        admin_authority: shihon_config_data.admin_authority,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    pda::get_treasury_address_seeds, state::shihon_config::get_shihon_config_data,
+    tools::spl_token::create_spl_token_account_signed,
+};
+
+/// Processes CreateTreasury instruction
+pub fn process_create_treasury(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Anybody can create the treasury of a mint because only the admin authority can withdraw from it
+
+    let account_info_iter = &mut accounts.iter();
+
+    let treasury_info = next_account_info(account_info_iter)?; // 0
+    let treasury_mint_info = next_account_info(account_info_iter)?; // 1
+    let shihon_config_info = next_account_info(account_info_iter)?; // 2
+    let payer_info = next_account_info(account_info_iter)?; // 3
+    let system_info = next_account_info(account_info_iter)?; // 4
+    let spl_token_info = next_account_info(account_info_iter)?; // 5
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 6
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    // Validates the ShihonConfig PDA which owns the treasury
+    get_shihon_config_data(program_id, shihon_config_info)?;
+
+    create_spl_token_account_signed(
+        payer_info,
+        treasury_info,
+        &get_treasury_address_seeds(treasury_mint_info.key),
+        treasury_mint_info,
+        shihon_config_info,
+        program_id,
+        system_info,
+        spl_token_info,
+        rent_sysvar_info,
+        rent,
+    )?;
+
+    Ok(())
+}
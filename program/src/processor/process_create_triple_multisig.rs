@@ -0,0 +1,108 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use spl_governance_tools::account::create_and_serialize_account_signed;
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_triple_multisig_address_seeds},
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        enums::ShihonAccountType,
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+        tanistry::get_tanistry_data,
+        triple_multisig::{TripleMultisig, TRIPLE_MULTISIG_SIGNERS, TRIPLE_MULTISIG_THRESHOLD},
+    },
+};
+
+/// Processes CreateTripleMultisig instruction
+pub fn process_create_triple_multisig(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    // The signers are taken from the Tanistry records so anybody can create the multisig
+    // Signers: the rater's candidate token owner, the buddy's candidate token owner and the first kicker
+
+    let account_info_iter = &mut accounts.iter();
+
+    let triple_multisig_info = next_account_info(account_info_iter)?; // 0
+    let rater_candidate_info = next_account_info(account_info_iter)?; // 1
+    let buddy_candidate_info = next_account_info(account_info_iter)?; // 2
+    let tanistry_info = next_account_info(account_info_iter)?; // 3
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 4
+    let payer_info = next_account_info(account_info_iter)?; // 5
+    let system_info = next_account_info(account_info_iter)?; // 6
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 7
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    if rater_candidate_info.key == buddy_candidate_info.key {
+        return Err(ShihonError::InvalidTripleMultisigCandidates.into());
+    }
+
+    let tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+
+    if tanistry_data.kicker_coin_owner_record != *kicker_coin_owner_record_info.key {
+        return Err(ShihonError::InvalidKickerCoinOwnerRecordForTanistry.into());
+    }
+
+    let kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+
+    let rater_candidate_data = get_candidate_limit_record_data_for_tanistry(
+        program_id,
+        rater_candidate_info,
+        tanistry_info.key,
+    )?;
+    let buddy_candidate_data = get_candidate_limit_record_data_for_tanistry(
+        program_id,
+        buddy_candidate_info,
+        tanistry_info.key,
+    )?;
+
+    let triple_multisig_address_seeds =
+        get_triple_multisig_address_seeds(rater_candidate_info.key, buddy_candidate_info.key);
+
+    let triple_multisig_data = TripleMultisig {
+        account_type: ShihonAccountType::TripleMultisig,
+        bump: find_bump_seed(program_id, &triple_multisig_address_seeds),
+        rater_candidate: *rater_candidate_info.key,
+        buddy_candidate: *buddy_candidate_info.key,
+        m: TRIPLE_MULTISIG_THRESHOLD,
+        n: TRIPLE_MULTISIG_SIGNERS as u8,
+        signers: [
+            rater_candidate_data.candidate_token_owner,
+            buddy_candidate_data.candidate_token_owner,
+            kicker_coin_owner_record_data.kicker_coin_holder,
+        ],
+    };
+
+    // The kicker enrolled as a candidate would otherwise hold two positions and approve alone
+    triple_multisig_data.assert_signers_are_distinct()?;
+
+    create_and_serialize_account_signed::<TripleMultisig>(
+        payer_info,
+        triple_multisig_info,
+        &triple_multisig_data,
+        &triple_multisig_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    ShihonEvent::TripleMultisigCreated {
+        triple_multisig: *triple_multisig_info.key,
+        rater: *rater_candidate_info.key,
+        buddy: *buddy_candidate_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -1,47 +1,116 @@
 //! Program state processor
 
+use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
-    rent::Rent,
-    sysvar::Sysvar,
 };
-use spl_governance_tools::account::create_and_serialize_account_signed;
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        enums::TanistryState,
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+        ring::get_ring_data,
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_CROWNING},
+        tanistry::get_tanistry_data,
+    },
+};
 
 /// Processes Crowning instruction
-pub fn process_crowning(program_id: &Pubkey, accounts: &[AccountInfo],crown: Pubkey) -> ProgramResult {
+pub fn process_crowning(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    crown: Pubkey,
+) -> ProgramResult {
     // Update the ownership of KickerCoin: from first kicker to crown
     // The crown is elected from candidates in the same tanistry
     // Note: Every time the ownership of KickerCoin leaves the tanistry, the building hash must be kept updated.
 
-    /// 0. `[signer]` The account of the person as Coordinator taking the Tanistry.
-    /// 1. `[writable]` The Coordinator's token account for send his input
-    /// 2. `[writable]` The Coordinator's token account for receive the NFT as Refund when he drop the game
-    /// 3. `[writable]` KickerCoin Owner Record(from Coordinator to Candidate as next Crown)
-    /// 4. `[writable]` The new Crown's token account for choosing next coordinator
-    /// 5. `[Writable]` The Tanistry account holding the Tanistry info  PDA seeds: []
-    /// 6. `[]` The token program
-    /// 7. `[]` System program
-    /// 8. `[]` Sysvar Rent
-
     let account_info_iter = &mut accounts.iter();
 
-    let realm_info = next_account_info(account_info_iter)?; // 0
-    let program_governance_info = next_account_info(account_info_iter)?; // 1
+    let coordinator_info = next_account_info(account_info_iter)?; // 0
+    let tanistry_info = next_account_info(account_info_iter)?; // 1
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 2
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 3
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_CROWNING)?;
+
+    let ring_info = next_account_info(account_info_iter)?; // 4
+
+    let mut tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+
+    if tanistry_data.kicker_coin_owner_record != *kicker_coin_owner_record_info.key {
+        return Err(ShihonError::InvalidKickerCoinOwnerRecordForTanistry.into());
+    }
+
+    // Only the coordinator the Tanistry was formed by can elect the Crown
+    let kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+    kicker_coin_owner_record_data.assert_coordinator_is_signer(coordinator_info)?;
+
+    tanistry_data.assert_can_crown(&crown)?;
+
+    // The Tanistry must be the next link of the chain so the same Tanistry is never counted twice
+    tanistry_data.assert_is_valid_ring(ring_info.key)?;
+    let mut ring_data = get_ring_data(program_id, ring_info)?;
+    ring_data.assert_is_next_tanistry(tanistry_info.key, tanistry_data.previous_tanistry_id)?;
+
+    // 5..n CandidateLimitRecord accounts in the order of candidate_limit_record_list
+    let candidate_limit_record_infos = account_info_iter.as_slice();
+
+    if candidate_limit_record_infos.len() != tanistry_data.candidate_limit_record_list.len() {
+        return Err(ShihonError::InvalidCandidateLimitRecordsForSettlement.into());
+    }
+
+    let mut deposit_amount = tanistry_data.outside_buyer_deposit_amount;
+    let mut rft_volume: u64 = 0;
+
+    for (candidate_limit_record_info, candidate_limit_record) in candidate_limit_record_infos
+        .iter()
+        .zip(tanistry_data.candidate_limit_record_list.iter())
+    {
+        if candidate_limit_record_info.key != candidate_limit_record {
+            return Err(ShihonError::InvalidCandidateLimitRecordsForSettlement.into());
+        }
+
+        let candidate_limit_record_data = get_candidate_limit_record_data_for_tanistry(
+            program_id,
+            candidate_limit_record_info,
+            tanistry_info.key,
+        )?;
 
-    let governed_program_info = next_account_info(account_info_iter)?; // 2
-    let governed_program_data_info = next_account_info(account_info_iter)?; // 3
-    let governed_program_upgrade_authority_info = next_account_info(account_info_iter)?; // 4
+        deposit_amount = deposit_amount
+            .checked_add(candidate_limit_record_data.candidate_token_deposit_amount)
+            .ok_or(ShihonError::RingVolumeOverflow)?;
+        rft_volume = rft_volume
+            .checked_add(candidate_limit_record_data.received_rating)
+            .ok_or(ShihonError::RingVolumeOverflow)?;
+    }
 
-    let token_owner_record_info = next_account_info(account_info_iter)?; // 5
+    ring_data.add_crowned_tanistry(tanistry_info.key, deposit_amount, rft_volume)?;
+    ring_data.serialize(&mut *ring_info.data.borrow_mut())?;
 
-    let payer_info = next_account_info(account_info_iter)?; // 6
-    let bpf_upgrade_loader_info = next_account_info(account_info_iter)?; // 7
+    tanistry_data.crown = Some(crown);
+    tanistry_data.state = TanistryState::Crowned;
+    tanistry_data.serialize(&mut *tanistry_info.data.borrow_mut())?;
 
-    let system_info = next_account_info(account_info_iter)?; // 8
+    ShihonEvent::RingUpdated {
+        ring: *ring_info.key,
+        tanistry: *tanistry_info.key,
+        cumulative_deposit_amount: ring_data.cumulative_deposit_amount,
+        cumulative_rft_volume: ring_data.cumulative_rft_volume,
+    }
+    .emit();
 
-    let rent_sysvar_info = next_account_info(account_info_iter)?; // 9
-    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+    ShihonEvent::Crowned {
+        tanistry: *tanistry_info.key,
+        crown,
+    }
+    .emit();
 
+    Ok(())
 }
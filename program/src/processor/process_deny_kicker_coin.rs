@@ -4,20 +4,83 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
-    rent::Rent,
-    sysvar::Sysvar,
 };
-use spl_governance_tools::account::create_and_serialize_account_signed;
+use spl_governance_tools::account::dispose_account;
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{get_tanistry_address, get_tanistry_address_seeds, get_tanistry_token_holding_address},
+    state::{
+        bc_token::get_bc_token_data, enums::BcTokenState,
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+    },
+    tools::spl_token::{get_spl_token_owner, transfer_spl_tokens_signed},
+};
 
 /// Processes Deny instruction
 pub fn process_deny_kicker_coin(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // The Tanistry is never formed, the escrowed KickerCoin goes back to the kicker without the fee
+    // The kicker's bcToken is released so it can be kicked to another coordinator
 
     let account_info_iter = &mut accounts.iter();
 
-    let kicker_info = next_account_info(account_info_iter)?; // 0
-    let tanistry_authority_info = next_account_info(account_info_iter)?; // 1
-    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 2
-    let kicker__receive_token_info = next_account_info(account_info_iter)?; // 3
-    let candidate_token_holding_info = next_account_info(account_info_iter)?; // 4
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 0
+    let coordinator_info = next_account_info(account_info_iter)?; // 1
+    let bc_token_info = next_account_info(account_info_iter)?; // 2
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 3
+    let tanistry_info = next_account_info(account_info_iter)?; // 4
+    let kicker_token_info = next_account_info(account_info_iter)?; // 5
+    let kicker_coin_mint_info = next_account_info(account_info_iter)?; // 6
+    let spl_token_info = next_account_info(account_info_iter)?; // 7
+    let beneficiary_info = next_account_info(account_info_iter)?; // 8
+
+    let kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+    kicker_coin_owner_record_data.assert_can_kick_off(coordinator_info)?;
+
+    if *tanistry_info.key != get_tanistry_address(program_id, kicker_coin_owner_record_info.key) {
+        return Err(ShihonError::InvalidTanistryAddress.into());
+    }
+
+    if get_tanistry_token_holding_address(program_id, tanistry_info.key, kicker_coin_mint_info.key)
+        != *tanistry_token_holding_info.key
+    {
+        return Err(ShihonError::InvalidTanistryTokenHoldingAccount.into());
+    }
+
+    if kicker_coin_owner_record_data.kicker_bc_token != *bc_token_info.key {
+        return Err(ShihonError::InvalidBcTokenForTanistry.into());
+    }
+
+    if get_spl_token_owner(kicker_token_info)? != kicker_coin_owner_record_data.kicker_coin_holder {
+        return Err(ShihonError::InvalidTokenOwner.into());
+    }
+
+    transfer_spl_tokens_signed(
+        tanistry_token_holding_info,
+        kicker_coin_mint_info,
+        kicker_token_info,
+        tanistry_info,
+        &get_tanistry_address_seeds(kicker_coin_owner_record_info.key),
+        program_id,
+        kicker_coin_owner_record_data.amount_of_kicker_coin,
+        spl_token_info,
+    )?;
+
+    let mut bc_token_data = get_bc_token_data(program_id, bc_token_info)?;
+    bc_token_data.bc_token_state = BcTokenState::PublicOnTheGround;
+    bc_token_data.serialize(&mut *bc_token_info.data.borrow_mut())?;
+
+    dispose_account(kicker_coin_owner_record_info, beneficiary_info);
+
+    ShihonEvent::KickerCoinDenied {
+        kicker_coin_owner_record: *kicker_coin_owner_record_info.key,
+        kicker: kicker_coin_owner_record_data.kicker_coin_holder,
+        coordinator: *coordinator_info.key,
+        amount: kicker_coin_owner_record_data.amount_of_kicker_coin,
+    }
+    .emit();
 
+    Ok(())
 }
@@ -4,26 +4,30 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     pubkey::Pubkey,
-    rent::Rent,
-    sysvar::Sysvar,
 };
-use spl_governance_tools::account::create_and_serialize_account_signed;
+use spl_governance_tools::account::dispose_account;
+
+use crate::{error::ShihonError, state::bc_token::get_bc_token_data_for_authority};
 
 /// Processes DiscardBcToken instruction
 pub fn process_discard_bc_token(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-   /// 0. `[writable]` bcToken account. PDA seeds:['bcToken', config]
-    /// 1. `[signer]` bcToken authority
-    /// 2. `[]` bcToken Mint
-    /// 3. `[signer]` Payer
+    // The bcToken and its metadata are disposed together, the rent goes to the beneficiary
     let account_info_iter = &mut accounts.iter();
 
     let bc_token_info = next_account_info(account_info_iter)?; // 0
     let bc_token_authority_info = next_account_info(account_info_iter)?; // 1
-    let bc_token_mint_info = next_account_info(account_info_iter)?; // 2
-    let payer_info = next_account_info(account_info_iter)?; // 3
+    let beneficiary_info = next_account_info(account_info_iter)?; // 2
+
+    let bc_token_data =
+        get_bc_token_data_for_authority(program_id, bc_token_info, bc_token_authority_info.key)?;
 
+    if !bc_token_authority_info.is_signer {
+        return Err(ShihonError::BcTokenAuthorityMustSign.into());
+    }
 
-    assert_can_discard_bc_token()?;
+    bc_token_data.assert_can_discard()?;
 
+    dispose_account(bc_token_info, beneficiary_info);
 
+    Ok(())
 }
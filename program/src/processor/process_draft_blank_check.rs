@@ -2,6 +2,7 @@
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
@@ -9,34 +10,98 @@ use solana_program::{
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_bc_token_address_seeds, get_bc_token_metadata_address_seeds},
+    state::{
+        bc_token::BcToken,
+        bc_token_metadata::{BcTokenMetadata, ContentType},
+        enums::{BcTokenState, ShihonAccountType},
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_BC_TOKEN},
+    },
+};
+
 /// Processes draft blank check instruction
 pub fn process_draft_blank_check(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     name: String,
 ) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
     // this processor can create blank check with no content information
-    // blank check holds only issuer pubkey and how much deposit for its content
+    // blank check holds only issuer pubkey, the content metadata is filled in by CreateBcToken
     // when bump own content self rate, you can input only this blank check
-    let bc_info = next_account_info(account_info_iter)?; // 0
-    let bc_authority_info = next_account_info(account_info_iter)?; // 1
-    let system_info = next_account_info(account_info_iter)?; // 2
-    let token_info = next_account_info(account_info_iter)?; // 3
-    let rent_sysvar_info = next_account_info(account_info_iter)?; // 4
+    let account_info_iter = &mut accounts.iter();
+
+    let bc_token_info = next_account_info(account_info_iter)?; // 0
+    let bc_token_authority_info = next_account_info(account_info_iter)?; // 1
+    let bc_token_mint_info = next_account_info(account_info_iter)?; // 2
+    let payer_info = next_account_info(account_info_iter)?; // 3
+    let system_info = next_account_info(account_info_iter)?; // 4
 
-    assert_valid_create_blank_check(program_id, &name, bc_info)?;
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 5
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
 
-    let bc_data_info = get_be_data(program_id, bc_info);
+    let clock_info = next_account_info(account_info_iter)?; // 6
+    let clock = Clock::from_account_info(clock_info)?;
 
-    create_and_serialize_account_signed::<Governance>(
+    let shihon_config_info = next_account_info(account_info_iter)?; // 7
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_BC_TOKEN)?;
+
+    if !bc_token_authority_info.is_signer {
+        return Err(ShihonError::BcTokenAuthorityMustSign.into());
+    }
+
+    let bc_token_address_seeds = get_bc_token_address_seeds(&name);
+
+    let bc_token_data = BcToken {
+        account_type: ShihonAccountType::BcTokenV3,
+        bump: find_bump_seed(program_id, &bc_token_address_seeds),
+        bc_token_owner_pubkey: *bc_token_authority_info.key,
+        amount_of_coin: 0,
+        issue_at: clock.unix_timestamp,
+        reserved: [0; 8],
+        authority: Some(*bc_token_authority_info.key),
+        delegate: None,
+        pending_authority: None,
+        name: name.clone(),
+        bc_token_mint: *bc_token_mint_info.key,
+        bc_token_state: BcTokenState::DraftBlankCheck,
+        config: BcTokenMetadata {
+            account_type: ShihonAccountType::BcTokenMetadataV3,
+            bump: find_bump_seed(
+                program_id,
+                &get_bc_token_metadata_address_seeds(bc_token_info.key),
+            ),
+            content_type: ContentType::Ordinary,
+            updated_at: clock.slot,
+            version: String::new(),
+            reserved: [0; 64],
+            is_writable: false,
+            is_signer: false,
+            content_length: 0,
+            content_duration: 0,
+            lineage: None,
+        },
+    };
+
+    create_and_serialize_account_signed::<BcToken>(
         payer_info,
-        bc_data_info,
-        &get_program_governance_address_seeds(bc_data_info.key),
+        bc_token_info,
+        &bc_token_data,
+        &bc_token_address_seeds,
         program_id,
         system_info,
         rent,
     )?;
 
+    ShihonEvent::BcTokenCreated {
+        bc_token: *bc_token_info.key,
+        bc_token_mint: *bc_token_mint_info.key,
+        authority: *bc_token_authority_info.key,
+        name,
+    }
+    .emit();
+
     Ok(())
 }
@@ -0,0 +1,92 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use spl_governance_tools::account::create_and_serialize_account_signed;
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_pinhole_address_seeds},
+    state::{
+        enums::{PinholeSide, ShihonAccountType},
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+        pinhole::Pinhole,
+    },
+};
+
+/// Processes DropPinhole instruction
+pub fn process_drop_pinhole(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    side: PinholeSide,
+) -> ProgramResult {
+    // The main wallet signs only once to bind the one-time pinhole authority to its side of the round
+    // From then on the opposite party only sees the pinhole and the event never names the main wallet
+
+    let account_info_iter = &mut accounts.iter();
+
+    let pinhole_info = next_account_info(account_info_iter)?; // 0
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 1
+    let party_info = next_account_info(account_info_iter)?; // 2
+    let pinhole_authority_info = next_account_info(account_info_iter)?; // 3
+    let payer_info = next_account_info(account_info_iter)?; // 4
+    let system_info = next_account_info(account_info_iter)?; // 5
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 6
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let mut kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+
+    if kicker_coin_owner_record_data.get_party(&side) != *party_info.key || !party_info.is_signer {
+        return Err(ShihonError::PinholePartyMustSign.into());
+    }
+
+    if !pinhole_authority_info.is_signer {
+        return Err(ShihonError::PinholeAuthorityMustSign.into());
+    }
+
+    kicker_coin_owner_record_data.drop_address(&side, pinhole_info.key)?;
+
+    let pinhole_address_seeds = get_pinhole_address_seeds(
+        kicker_coin_owner_record_info.key,
+        pinhole_authority_info.key,
+    );
+
+    let pinhole_data = Pinhole {
+        account_type: ShihonAccountType::Pinhole,
+        bump: find_bump_seed(program_id, &pinhole_address_seeds),
+        kicker_coin_owner_record: *kicker_coin_owner_record_info.key,
+        round: kicker_coin_owner_record_data.round,
+        side,
+        pinhole_authority: *pinhole_authority_info.key,
+    };
+
+    create_and_serialize_account_signed::<Pinhole>(
+        payer_info,
+        pinhole_info,
+        &pinhole_data,
+        &pinhole_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    kicker_coin_owner_record_data
+        .serialize(&mut *kicker_coin_owner_record_info.data.borrow_mut())?;
+
+    ShihonEvent::PinholeDropped {
+        pinhole: *pinhole_info.key,
+        kicker_coin_owner_record: *kicker_coin_owner_record_info.key,
+        round: pinhole_data.round,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -9,26 +9,167 @@ use solana_program::{
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{
+        find_bump_seed, get_kicker_coin_owner_record_address_seeds, get_tanistry_address,
+        get_tanistry_token_holding_address_seeds,
+    },
+    state::{
+        bc_token::get_bc_token_data_for_authority,
+        enums::{BcTokenState, ShihonAccountType},
+        kicker_coin_owner_record::KickerCoinOwnerRecord,
+        shihon_config::{
+            assert_shihon_is_not_paused, calculate_fee_amount, get_shihon_config_data_if_exists,
+            transfer_fee_to_treasury, PAUSE_KICKER_COIN,
+        },
+    },
+    tools::spl_token::{create_spl_token_account_signed, transfer_spl_tokens},
+};
+
 /// Processes Kicking instruction
-pub fn process_kick_to_coordinator(program_id: &Pubkey, accounts: &[AccountInfo],coordinator: Pubkey, amount: u64) -> ProgramResult {
+pub fn process_kick_to_coordinator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    coordinator: Pubkey,
+    amount: u64,
+) -> ProgramResult {
     // this function is for first kicker or crown
     // We need to make sure that targeted bcToken's state in PublicOtherGround or HoldingOnPrivate
     // Now, issue new KickerCoinOwnerRecord. This Record would have Building hash and some Variables for example, round: u8, amount_of_kicker_coin:u64
     // It will be issued the KickerCoinOwnerRecord even if the coordinator does not approve it because this record's role must map each crown and keep your KickerCoin safe.
-    // Note: The coordinator will be notified. I don't know how exactly to do that.
+    // Note: The coordinator is notified through the KickerCoinKicked event
     // Need to create validation that the content is compliant. I consider that after create bcToken metadata
-
-    ///   2. `[signer]` The account of the person as first kicker initializing the two BcToken into Tanistry Ring
-    ///   3. `[writable]` KickerCoin Owner Record PDA seeds: ['KickerCoinOwnerRecord', coordinator ]
-    ///   4. `[writable]` Coordinator's bcToken account to flag as received KickerCoin from first kicker
-    ///   5. `[]` Clock sysvar
     let account_info_iter = &mut accounts.iter();
 
-    let kicker_info = next_account_info(account_info_iter)?; // 0
-    let token_owner_record_info = next_account_info(account_info_iter)?; // 1
-    let kicker_coin_info = next_account_info(account_info_iter)?; // 2
-    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 3
-    let payer_info = next_account_info(account_info_iter)?; // 4
-    let clock_info = next_account_info(account_info_iter)?; //5
-    let clock = Clock::from_account_info(clock_info)?;
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 0
+    let bc_token_info = next_account_info(account_info_iter)?; // 1
+    let kicker_info = next_account_info(account_info_iter)?; // 2
+    let kicker_token_source_info = next_account_info(account_info_iter)?; // 3
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 4
+    let tanistry_info = next_account_info(account_info_iter)?; // 5
+    let kicker_coin_mint_info = next_account_info(account_info_iter)?; // 6
+    let payer_info = next_account_info(account_info_iter)?; // 7
+    let system_info = next_account_info(account_info_iter)?; // 8
+    let spl_token_info = next_account_info(account_info_iter)?; // 9
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 10
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 11
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_KICKER_COIN)?;
+
+    let treasury_info = next_account_info(account_info_iter)?; // 12
+
+    if !kicker_info.is_signer {
+        return Err(ShihonError::KickerCoinHolderMustSign.into());
+    }
+
+    let shihon_config_data = get_shihon_config_data_if_exists(program_id, shihon_config_info)?;
+
+    if let Some(shihon_config_data) = &shihon_config_data {
+        if shihon_config_data.config.kicker_coin_mint != *kicker_coin_mint_info.key {
+            return Err(ShihonError::InvalidKickerCoinMint.into());
+        }
+    }
+
+    // The vault is owned by the Tanistry PDA which is created once the coordinator approves the KickerCoin
+    if *tanistry_info.key != get_tanistry_address(program_id, kicker_coin_owner_record_info.key) {
+        return Err(ShihonError::InvalidTanistryAddress.into());
+    }
+
+    let mut bc_token_data =
+        get_bc_token_data_for_authority(program_id, bc_token_info, kicker_info.key)?;
+    bc_token_data.assert_can_be_kicker()?;
+
+    // The fee is taken when the KickerCoin is kicked so it's not refunded if the coordinator denies it
+    let kicker_coin_fee_bps = shihon_config_data
+        .map(|shihon_config_data| shihon_config_data.config.kicker_coin_fee_bps)
+        .unwrap_or(0);
+    let kicker_coin_fee_amount = calculate_fee_amount(amount, kicker_coin_fee_bps);
+
+    let collected_fee_amount = transfer_fee_to_treasury(
+        program_id,
+        kicker_token_source_info,
+        kicker_coin_mint_info,
+        treasury_info,
+        kicker_info,
+        kicker_coin_fee_amount,
+        spl_token_info,
+    )?;
+
+    create_spl_token_account_signed(
+        payer_info,
+        tanistry_token_holding_info,
+        &get_tanistry_token_holding_address_seeds(tanistry_info.key, kicker_coin_mint_info.key),
+        kicker_coin_mint_info,
+        tanistry_info,
+        program_id,
+        system_info,
+        spl_token_info,
+        rent_sysvar_info,
+        rent,
+    )?;
+
+    // The KickerCoin is escrowed in the vault until the coordinator approves or denies it
+    let escrowed_amount = transfer_spl_tokens(
+        kicker_token_source_info,
+        kicker_coin_mint_info,
+        tanistry_token_holding_info,
+        kicker_info,
+        amount - kicker_coin_fee_amount,
+        spl_token_info,
+    )?;
+
+    let kicker_coin_owner_record_address_seeds =
+        get_kicker_coin_owner_record_address_seeds(kicker_info.key, &coordinator);
+
+    let kicker_coin_owner_record_data = KickerCoinOwnerRecord {
+        account_type: ShihonAccountType::KickerCoinOwnerRecordV2,
+        bump: find_bump_seed(program_id, &kicker_coin_owner_record_address_seeds),
+        kicker_coin_holder: *kicker_info.key,
+        latest_coordinator: coordinator,
+        amount_of_kicker_coin: escrowed_amount,
+        round: 1,
+        building_hash_key: vec![],
+        is_kick_off: false,
+        kicker_coin_fee_amount: collected_fee_amount,
+        kicker_pinhole: None,
+        coordinator_pinhole: None,
+        kicker_bc_token: *bc_token_info.key,
+    };
+    kicker_coin_owner_record_data.assert_can_kick_to_suitable_coordinator()?;
+
+    create_and_serialize_account_signed::<KickerCoinOwnerRecord>(
+        payer_info,
+        kicker_coin_owner_record_info,
+        &kicker_coin_owner_record_data,
+        &kicker_coin_owner_record_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    bc_token_data.bc_token_state = BcTokenState::EnableToCandidate;
+    bc_token_data.serialize(&mut *bc_token_info.data.borrow_mut())?;
+
+    ShihonEvent::KickerCoinKicked {
+        kicker_coin_owner_record: *kicker_coin_owner_record_info.key,
+        kicker: *kicker_info.key,
+        coordinator,
+        amount,
+    }
+    .emit();
+
+    if collected_fee_amount > 0 {
+        ShihonEvent::ProtocolFeeCollected {
+            treasury: *treasury_info.key,
+            record: *kicker_coin_owner_record_info.key,
+            fee_amount: collected_fee_amount,
+        }
+        .emit();
+    }
+
+    Ok(())
 }
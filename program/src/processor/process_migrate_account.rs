@@ -0,0 +1,483 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::ShihonError,
+    pda::{
+        find_bump_seed, get_bc_token_address_seeds, get_bc_token_metadata_address_seeds,
+        get_candidate_limit_record_address_seeds, get_cc_vote_record_address_seeds,
+        get_kicker_coin_owner_record_address_seeds, get_mix_content_record_address_seeds,
+        get_outside_buyer_record_address_seeds, get_rate_option_address_seeds,
+        get_rate_other_record_address_seeds, get_ring_address, get_tanistry_address_seeds,
+        is_valid_pda,
+    },
+    state::{
+        bc_token::{get_bc_token_data, BcToken},
+        bc_token_metadata::{
+            get_bc_token_metadata_data, get_bc_token_metadata_data_for_seeds, BcTokenMetadata,
+        },
+        candidate_limit_record::{get_candidate_limit_record_data, CandidateLimitRecord},
+        cc_vote_record::{get_cc_vote_record_data, CCVoteRecord},
+        enums::ShihonAccountType,
+        kicker_coin_owner_record::{get_kicker_coin_owner_record_data, KickerCoinOwnerRecord},
+        mix_content_record::{get_mix_content_record_data, MixContentRecord},
+        outside_buyer_record::{get_outside_buyer_token_owner_record_data, OutsideBuyerRecord},
+        rate_option::{get_rate_option_data, RateOption},
+        rate_other_record::{get_rate_other_record_data, RateOtherRecord},
+        tanistry::{get_tanistry_data, Tanistry},
+    },
+    tools::account::realloc_and_serialize_account_data,
+};
+
+/// Processes MigrateAccount instruction
+pub fn process_migrate_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Anybody can migrate a legacy account because the migration doesn't change the account's content
+    // The legacy layout is translated into the current one with the PDA bump and the account is reallocated to its size
+
+    let account_info_iter = &mut accounts.iter();
+
+    let account_info = next_account_info(account_info_iter)?; // 0
+    let payer_info = next_account_info(account_info_iter)?; // 1
+    let system_info = next_account_info(account_info_iter)?; // 2
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 3
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let account_type: ShihonAccountType = try_from_slice_unchecked(&account_info.data.borrow())?;
+
+    match account_type {
+        ShihonAccountType::BcTokenV1 => {
+            let bc_token_data = get_bc_token_data(program_id, account_info)?;
+
+            let (bc_token_address, bump) = Pubkey::find_program_address(
+                &get_bc_token_address_seeds(&bc_token_data.name),
+                program_id,
+            );
+
+            if bc_token_address != *account_info.key {
+                return Err(ShihonError::InvalidBcTokenAddress.into());
+            }
+
+            let bc_token_data = BcToken {
+                account_type: ShihonAccountType::BcTokenV3,
+                bump,
+                config: BcTokenMetadata {
+                    account_type: ShihonAccountType::BcTokenMetadataV3,
+                    bump: find_bump_seed(
+                        program_id,
+                        &get_bc_token_metadata_address_seeds(account_info.key),
+                    ),
+                    ..bc_token_data.config
+                },
+                ..bc_token_data
+            };
+
+            realloc_and_serialize_account_data(
+                &bc_token_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::BcTokenMetadataV1 => {
+            // The metadata PDA is derived from the bcToken which the V1 layout doesn't store
+            let bc_token_info = next_account_info(account_info_iter)?; // 4
+
+            let bc_token_metadata_data = get_bc_token_metadata_data(program_id, account_info)?;
+
+            let (bc_token_metadata_address, bump) = Pubkey::find_program_address(
+                &get_bc_token_metadata_address_seeds(bc_token_info.key),
+                program_id,
+            );
+
+            if bc_token_metadata_address != *account_info.key {
+                return Err(ShihonError::InvalidBcTokenMetadataAddress.into());
+            }
+
+            let bc_token_metadata_data = BcTokenMetadata {
+                account_type: ShihonAccountType::BcTokenMetadataV3,
+                bump,
+                ..bc_token_metadata_data
+            };
+
+            realloc_and_serialize_account_data(
+                &bc_token_metadata_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::OutsideBuyerRecordV1 => {
+            let outside_buyer_record_data =
+                get_outside_buyer_token_owner_record_data(program_id, account_info)?;
+
+            let (outside_buyer_record_address, bump) = Pubkey::find_program_address(
+                &get_outside_buyer_record_address_seeds(
+                    &outside_buyer_record_data.tanistry,
+                    &outside_buyer_record_data.outside_buyer_token_mint,
+                    &outside_buyer_record_data.outside_buyer_token_owner,
+                ),
+                program_id,
+            );
+
+            if outside_buyer_record_address != *account_info.key {
+                return Err(ShihonError::InvalidOutsideBuyerRecordAddress.into());
+            }
+
+            let outside_buyer_record_data = OutsideBuyerRecord {
+                account_type: ShihonAccountType::OutsideBuyerRecordV3,
+                bump,
+                ..outside_buyer_record_data
+            };
+
+            realloc_and_serialize_account_data(
+                &outside_buyer_record_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::BcTokenV2 => {
+            // V2 accounts store their bumps already and only gain the V3 fields
+            let bc_token_data = get_bc_token_data(program_id, account_info)?;
+
+            if !is_valid_pda(
+                program_id,
+                account_info.key,
+                &get_bc_token_address_seeds(&bc_token_data.name),
+                bc_token_data.bump,
+            ) {
+                return Err(ShihonError::InvalidBcTokenAddress.into());
+            }
+
+            let bc_token_data = BcToken {
+                account_type: ShihonAccountType::BcTokenV3,
+                config: BcTokenMetadata {
+                    account_type: ShihonAccountType::BcTokenMetadataV3,
+                    ..bc_token_data.config
+                },
+                ..bc_token_data
+            };
+
+            realloc_and_serialize_account_data(
+                &bc_token_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::BcTokenMetadataV2 => {
+            let bc_token_info = next_account_info(account_info_iter)?; // 4
+
+            let bc_token_metadata_data =
+                get_bc_token_metadata_data_for_seeds(program_id, account_info, bc_token_info.key)?;
+
+            let bc_token_metadata_data = BcTokenMetadata {
+                account_type: ShihonAccountType::BcTokenMetadataV3,
+                ..bc_token_metadata_data
+            };
+
+            realloc_and_serialize_account_data(
+                &bc_token_metadata_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::OutsideBuyerRecordV2 => {
+            let outside_buyer_record_data =
+                get_outside_buyer_token_owner_record_data(program_id, account_info)?;
+
+            if !is_valid_pda(
+                program_id,
+                account_info.key,
+                &get_outside_buyer_record_address_seeds(
+                    &outside_buyer_record_data.tanistry,
+                    &outside_buyer_record_data.outside_buyer_token_mint,
+                    &outside_buyer_record_data.outside_buyer_token_owner,
+                ),
+                outside_buyer_record_data.bump,
+            ) {
+                return Err(ShihonError::InvalidOutsideBuyerRecordAddress.into());
+            }
+
+            let outside_buyer_record_data = OutsideBuyerRecord {
+                account_type: ShihonAccountType::OutsideBuyerRecordV3,
+                ..outside_buyer_record_data
+            };
+
+            realloc_and_serialize_account_data(
+                &outside_buyer_record_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::KickerCoinOwnerRecordV1 => {
+            let kicker_coin_owner_record_data =
+                get_kicker_coin_owner_record_data(program_id, account_info)?;
+
+            let (kicker_coin_owner_record_address, bump) = Pubkey::find_program_address(
+                &get_kicker_coin_owner_record_address_seeds(
+                    &kicker_coin_owner_record_data.kicker_coin_holder,
+                    &kicker_coin_owner_record_data.latest_coordinator,
+                ),
+                program_id,
+            );
+
+            if kicker_coin_owner_record_address != *account_info.key {
+                return Err(ShihonError::InvalidKickerCoinOwnerRecordAddress.into());
+            }
+
+            let kicker_coin_owner_record_data = KickerCoinOwnerRecord {
+                account_type: ShihonAccountType::KickerCoinOwnerRecordV2,
+                bump,
+                ..kicker_coin_owner_record_data
+            };
+
+            realloc_and_serialize_account_data(
+                &kicker_coin_owner_record_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::TanistryV1 => {
+            let tanistry_data = get_tanistry_data(program_id, account_info)?;
+
+            let (tanistry_address, bump) = Pubkey::find_program_address(
+                &get_tanistry_address_seeds(&tanistry_data.kicker_coin_owner_record),
+                program_id,
+            );
+
+            if tanistry_address != *account_info.key {
+                return Err(ShihonError::InvalidTanistryAddress.into());
+            }
+
+            // The first Tanistry seeds the Ring and the next ones inherit it from the previous Tanistry
+            // which has to be migrated first
+            let ring = if let Some(previous_tanistry_id) = tanistry_data.previous_tanistry_id {
+                let previous_tanistry_info = next_account_info(account_info_iter)?; // 4
+
+                if *previous_tanistry_info.key != previous_tanistry_id {
+                    return Err(ShihonError::InvalidLinkedAccountForMigration.into());
+                }
+
+                let previous_tanistry_data = get_tanistry_data(program_id, previous_tanistry_info)?;
+
+                if previous_tanistry_data.account_type != ShihonAccountType::TanistryV2 {
+                    return Err(ShihonError::TanistryMustBeMigrated.into());
+                }
+
+                previous_tanistry_data.ring
+            } else {
+                get_ring_address(program_id, account_info.key)
+            };
+
+            let tanistry_data = Tanistry {
+                account_type: ShihonAccountType::TanistryV2,
+                bump,
+                ring,
+                ..tanistry_data
+            };
+
+            realloc_and_serialize_account_data(
+                &tanistry_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::CandidateLimitRecordV1 => {
+            let candidate_limit_record_data =
+                get_candidate_limit_record_data(program_id, account_info)?;
+
+            let (candidate_limit_record_address, bump) = Pubkey::find_program_address(
+                &get_candidate_limit_record_address_seeds(
+                    &candidate_limit_record_data.belonging_tanistry,
+                    &candidate_limit_record_data.candidate_token_mint,
+                    &candidate_limit_record_data.candidate_token_owner,
+                ),
+                program_id,
+            );
+
+            if candidate_limit_record_address != *account_info.key {
+                return Err(ShihonError::InvalidCandidateLimitRecordAddress.into());
+            }
+
+            let candidate_limit_record_data = CandidateLimitRecord {
+                account_type: ShihonAccountType::CandidateLimitRecordV2,
+                bump,
+                ..candidate_limit_record_data
+            };
+
+            realloc_and_serialize_account_data(
+                &candidate_limit_record_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::MixContentRecordV1 => {
+            let mix_content_record_data = get_mix_content_record_data(program_id, account_info)?;
+
+            let (mix_content_record_address, bump) = Pubkey::find_program_address(
+                &get_mix_content_record_address_seeds(
+                    &mix_content_record_data.rater_candidate,
+                    &mix_content_record_data.buddy_candidate,
+                ),
+                program_id,
+            );
+
+            if mix_content_record_address != *account_info.key {
+                return Err(ShihonError::InvalidMixContentRecordAddress.into());
+            }
+
+            let mix_content_record_data = MixContentRecord {
+                account_type: ShihonAccountType::MixContentRecordV2,
+                bump,
+                ..mix_content_record_data
+            };
+
+            realloc_and_serialize_account_data(
+                &mix_content_record_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::RateOptionV1 => {
+            let rate_option_data = get_rate_option_data(program_id, account_info)?;
+
+            let (rate_option_address, bump) = Pubkey::find_program_address(
+                &get_rate_option_address_seeds(
+                    &rate_option_data.mix_content_record,
+                    &rate_option_data.issuer_roydamna,
+                ),
+                program_id,
+            );
+
+            if rate_option_address != *account_info.key {
+                return Err(ShihonError::InvalidRateOptionAddress.into());
+            }
+
+            let rate_option_data = RateOption {
+                account_type: ShihonAccountType::RateOptionV2,
+                bump,
+                ..rate_option_data
+            };
+
+            realloc_and_serialize_account_data(
+                &rate_option_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::RateOtherRecordV1 => {
+            // The RateOtherRecord PDA is derived from the candidates of its MixContentRecord
+            let mix_content_record_info = next_account_info(account_info_iter)?; // 4
+
+            let rate_other_record_data = get_rate_other_record_data(program_id, account_info)?;
+
+            if *mix_content_record_info.key != rate_other_record_data.mix_content_record {
+                return Err(ShihonError::InvalidLinkedAccountForMigration.into());
+            }
+
+            let mix_content_record_data =
+                get_mix_content_record_data(program_id, mix_content_record_info)?;
+
+            let (rate_other_record_address, bump) = Pubkey::find_program_address(
+                &get_rate_other_record_address_seeds(
+                    &mix_content_record_data.rater_candidate,
+                    &mix_content_record_data.buddy_candidate,
+                ),
+                program_id,
+            );
+
+            if rate_other_record_address != *account_info.key {
+                return Err(ShihonError::InvalidRateOtherRecordAddress.into());
+            }
+
+            let rate_other_record_data = RateOtherRecord {
+                account_type: ShihonAccountType::RateOtherRecordV2,
+                bump,
+                ..rate_other_record_data
+            };
+
+            realloc_and_serialize_account_data(
+                &rate_other_record_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::CCVoteRecordV1 => {
+            // The CCVoteRecord PDA is derived from the voter's CandidateLimitRecord which the V1 layout doesn't store
+            let candidate_limit_record_info = next_account_info(account_info_iter)?; // 4
+
+            let cc_vote_record_data = get_cc_vote_record_data(program_id, account_info)?;
+
+            let (cc_vote_record_address, bump) = Pubkey::find_program_address(
+                &get_cc_vote_record_address_seeds(candidate_limit_record_info.key),
+                program_id,
+            );
+
+            if cc_vote_record_address != *account_info.key {
+                return Err(ShihonError::InvalidCCVoteRecordAddress.into());
+            }
+
+            let candidate_limit_record_data =
+                get_candidate_limit_record_data(program_id, candidate_limit_record_info)?;
+
+            if candidate_limit_record_data.candidate_token_owner != cc_vote_record_data.voter {
+                return Err(ShihonError::InvalidVoterForCCVoteRecord.into());
+            }
+
+            let cc_vote_record_data = CCVoteRecord {
+                account_type: ShihonAccountType::CCVoteRecordV2,
+                bump,
+                ..cc_vote_record_data
+            };
+
+            realloc_and_serialize_account_data(
+                &cc_vote_record_data,
+                account_info,
+                payer_info,
+                system_info,
+                rent,
+            )
+        }
+        ShihonAccountType::BcTokenV3
+        | ShihonAccountType::BcTokenMetadataV3
+        | ShihonAccountType::OutsideBuyerRecordV3
+        | ShihonAccountType::KickerCoinOwnerRecordV2
+        | ShihonAccountType::TanistryV2
+        | ShihonAccountType::CandidateLimitRecordV2
+        | ShihonAccountType::MixContentRecordV2
+        | ShihonAccountType::RateOptionV2
+        | ShihonAccountType::RateOtherRecordV2
+        | ShihonAccountType::CCVoteRecordV2 => Err(ShihonError::AccountAlreadyMigrated.into()),
+        _ => Err(ShihonError::InvalidAccountTypeForMigration.into()),
+    }
+}
@@ -9,31 +9,162 @@ use solana_program::{
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
-/// Processes mix content instruction
-pub fn process_mix_content(program_id: &Pubkey, accounts: &[AccountInfo],time_shift_a: u64,
-        time_shift_b: u64) -> ProgramResult {
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_mix_content_record_address_seeds, get_tanistry_address},
+    state::{
+        bc_token::get_bc_token_data,
+        bc_token_metadata::{assert_can_mix_contents, ContentRange},
+        candidate_limit_record::{
+            get_candidate_limit_record_data, get_candidate_limit_record_data_for_tanistry,
+        },
+        enums::ShihonAccountType,
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+        mix_content_record::{MixContentRecord, OptionMixResult},
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_MIX_AND_RATE},
+        triple_multisig::get_triple_multisig_data_for_candidates,
+    },
+};
 
+/// Processes mix content instruction
+pub fn process_mix_content(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    time_shift_a: u64,
+    time_shift_b: u64,
+    range_a: Option<ContentRange>,
+    range_b: Option<ContentRange>,
+) -> ProgramResult {
     // If you do not agree to mix the content, you lose the right to rate on next process
     // The elements needed to mix are as follows.
-    // 1. input of coordinator
+    // 1. input of coordinator of the KickerCoinOwnerRecord the Tanistry was formed from
     // 2. Init content info on Metadata in First Kicker's bcToken
     // 3. Buddy content info on Metadata in buddy Candidate's bcToken
+    // 4. Two of the three approvals of the rater, the buddy and the first kicker
 
     let account_info_iter = &mut accounts.iter();
 
     let coordinator_info = next_account_info(account_info_iter)?; // 0
     let rater_candidate_info = next_account_info(account_info_iter)?; // 1
-    let buddy_candidate_info = next_account_info(account_info_iter)?; // 2
-    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 3
-    let coordinator_input_info = next_account_info(account_info_iter)?; // 4
-    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 5
-    let content_authority_info = next_account_info(account_info_iter)?; // 6
-    let buddy_candidate_info = next_account_info(account_info_iter)?; // 7
-    let mix_content_record_info = next_account_info(account_info_iter)?; // 8
-    let rating_info = next_account_info(account_info_iter)?; // 9
-    let system_info = next_account_info(account_info_iter)?; // 10
-    let rent = &Rent::from_account_info(rent_sysvar_info)?; // 11
+    let rater_token_owner_info = next_account_info(account_info_iter)?; // 2
+    let buddy_candidate_info = next_account_info(account_info_iter)?; // 3
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 4
+    let init_bc_token_info = next_account_info(account_info_iter)?; // 5
+    let buddy_bc_token_info = next_account_info(account_info_iter)?; // 6
+    let mix_content_record_info = next_account_info(account_info_iter)?; // 7
+    let payer_info = next_account_info(account_info_iter)?; // 8
+    let system_info = next_account_info(account_info_iter)?; // 9
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 10
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 11
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_MIX_AND_RATE)?;
+
+    let triple_multisig_info = next_account_info(account_info_iter)?; // 12
+    let triple_multisig_signer_infos = account_info_iter.as_slice(); // 13..
+
+    let rater_candidate_data = get_candidate_limit_record_data(program_id, rater_candidate_info)?;
+    rater_candidate_data.assert_token_owner_or_delegate_is_signer(rater_token_owner_info)?;
+
+    // The buddy must be enrolled into the same Tanistry as the rater
+    let buddy_candidate_data = get_candidate_limit_record_data_for_tanistry(
+        program_id,
+        buddy_candidate_info,
+        &rater_candidate_data.belonging_tanistry,
+    )?;
+
+    // The Tanistry PDA is derived from the KickerCoinOwnerRecord it was formed from
+    if get_tanistry_address(program_id, kicker_coin_owner_record_info.key)
+        != rater_candidate_data.belonging_tanistry
+    {
+        return Err(ShihonError::InvalidKickerCoinOwnerRecordForTanistry.into());
+    }
+
+    let kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+
+    if kicker_coin_owner_record_data.latest_coordinator != *coordinator_info.key {
+        return Err(ShihonError::InvalidCoordinatorForTanistry.into());
+    }
+
+    // The content rules are only checked against the contents standing in the Tanistry
+    if kicker_coin_owner_record_data.kicker_bc_token != *init_bc_token_info.key {
+        return Err(ShihonError::InvalidInitBcTokenForMixContent.into());
+    }
+
+    if buddy_candidate_data.bc_token != *buddy_bc_token_info.key {
+        return Err(ShihonError::InvalidBuddyBcTokenForMixContent.into());
+    }
+
+    get_triple_multisig_data_for_candidates(
+        program_id,
+        triple_multisig_info,
+        rater_candidate_info.key,
+        buddy_candidate_info.key,
+    )?
+    .assert_signed_by_threshold(
+        triple_multisig_signer_infos,
+        &[
+            rater_candidate_data.candidate_delegate,
+            buddy_candidate_data.candidate_delegate,
+            None,
+        ],
+    )?;
+
+    let init_bc_token_data = get_bc_token_data(program_id, init_bc_token_info)?;
+    let buddy_bc_token_data = get_bc_token_data(program_id, buddy_bc_token_info)?;
+
+    assert_can_mix_contents(
+        &init_bc_token_data.config,
+        &buddy_bc_token_data.config,
+        time_shift_a,
+        time_shift_b,
+        &range_a,
+        &range_b,
+    )?;
+
+    let mix_content_record_address_seeds =
+        get_mix_content_record_address_seeds(rater_candidate_info.key, buddy_candidate_info.key);
+
+    // The encrypted link and the fingerprint are delivered by the Oracle once the mixed content is stored
+    let mix_content_record_data = MixContentRecord {
+        account_type: ShihonAccountType::MixContentRecordV2,
+        bump: find_bump_seed(program_id, &mix_content_record_address_seeds),
+        encrypted_link: "".to_string(),
+        rater_candidate: *rater_candidate_info.key,
+        buddy_candidate: *buddy_candidate_info.key,
+        option_mix_result: OptionMixResult::None,
+        finger_print_of_mixed_content: "".to_string(),
+        init_bc_token: *init_bc_token_info.key,
+        buddy_bc_token: *buddy_bc_token_info.key,
+        time_shift_a,
+        time_shift_b,
+        range_a,
+        range_b,
+        promoted_bc_token: None,
+    };
 
+    create_and_serialize_account_signed::<MixContentRecord>(
+        payer_info,
+        mix_content_record_info,
+        &mix_content_record_data,
+        &mix_content_record_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
 
+    ShihonEvent::ContentMixed {
+        mix_content_record: *mix_content_record_info.key,
+        coordinator: *coordinator_info.key,
+        rater: *rater_candidate_info.key,
+        buddy: *buddy_candidate_info.key,
+        time_shift_a,
+        time_shift_b,
+    }
+    .emit();
 
+    Ok(())
 }
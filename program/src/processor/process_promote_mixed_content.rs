@@ -0,0 +1,148 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use spl_governance_tools::account::create_and_serialize_account_signed;
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_bc_token_address_seeds, get_bc_token_metadata_address_seeds},
+    state::{
+        bc_token::{get_bc_token_data, BcToken},
+        bc_token_metadata::{BcTokenMetadata, ContentLineage},
+        candidate_limit_record::get_candidate_limit_record_data,
+        enums::{BcTokenState, ShihonAccountType},
+        mix_content_record::get_mix_content_record_data,
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_BC_TOKEN},
+    },
+};
+
+/// Processes PromoteMixedContent instruction
+pub fn process_promote_mixed_content(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    version: String,
+) -> ProgramResult {
+    // The mixed content has already been fingerprinted by the mix so the new bcToken starts as HoldingOnPrivate
+    // The lineage links the new bcToken back to both parents which can be mixed contents themselves
+
+    let account_info_iter = &mut accounts.iter();
+
+    let bc_token_info = next_account_info(account_info_iter)?; // 0
+    let mix_content_record_info = next_account_info(account_info_iter)?; // 1
+    let rater_candidate_info = next_account_info(account_info_iter)?; // 2
+    let rater_token_owner_info = next_account_info(account_info_iter)?; // 3
+    let init_bc_token_info = next_account_info(account_info_iter)?; // 4
+    let buddy_bc_token_info = next_account_info(account_info_iter)?; // 5
+    let bc_token_mint_info = next_account_info(account_info_iter)?; // 6
+    let payer_info = next_account_info(account_info_iter)?; // 7
+    let system_info = next_account_info(account_info_iter)?; // 8
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 9
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let clock_info = next_account_info(account_info_iter)?; // 10
+    let clock = Clock::from_account_info(clock_info)?;
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 11
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_BC_TOKEN)?;
+
+    let mut mix_content_record_data =
+        get_mix_content_record_data(program_id, mix_content_record_info)?;
+    mix_content_record_data.assert_can_promote()?;
+
+    if mix_content_record_data.rater_candidate != *rater_candidate_info.key {
+        return Err(ShihonError::InvalidRaterForMixContent.into());
+    }
+
+    let rater_candidate_data = get_candidate_limit_record_data(program_id, rater_candidate_info)?;
+    rater_candidate_data.assert_token_owner_or_delegate_is_signer(rater_token_owner_info)?;
+
+    if mix_content_record_data.init_bc_token != *init_bc_token_info.key
+        || mix_content_record_data.buddy_bc_token != *buddy_bc_token_info.key
+    {
+        return Err(ShihonError::InvalidParentBcTokenForMixContent.into());
+    }
+
+    // The mixed content keeps the content type, the length and the duration of the init content it was mixed into
+    let init_bc_token_data = get_bc_token_data(program_id, init_bc_token_info)?;
+    get_bc_token_data(program_id, buddy_bc_token_info)?;
+
+    let bc_token_address_seeds = get_bc_token_address_seeds(&name);
+
+    let bc_token_data = BcToken {
+        account_type: ShihonAccountType::BcTokenV3,
+        bump: find_bump_seed(program_id, &bc_token_address_seeds),
+        bc_token_owner_pubkey: rater_candidate_data.candidate_token_owner,
+        amount_of_coin: 0,
+        issue_at: clock.unix_timestamp,
+        reserved: [0; 8],
+        authority: Some(rater_candidate_data.candidate_token_owner),
+        delegate: None,
+        pending_authority: None,
+        name: name.clone(),
+        bc_token_mint: *bc_token_mint_info.key,
+        bc_token_state: BcTokenState::HoldingOnPrivate,
+        config: BcTokenMetadata {
+            account_type: ShihonAccountType::BcTokenMetadataV3,
+            bump: find_bump_seed(
+                program_id,
+                &get_bc_token_metadata_address_seeds(bc_token_info.key),
+            ),
+            content_type: init_bc_token_data.config.content_type,
+            updated_at: clock.slot,
+            version,
+            reserved: [0; 64],
+            is_writable: false,
+            is_signer: false,
+            content_length: init_bc_token_data.config.content_length,
+            content_duration: init_bc_token_data.config.content_duration,
+            lineage: Some(ContentLineage {
+                mix_content_record: *mix_content_record_info.key,
+                parent_bc_token_a: mix_content_record_data.init_bc_token,
+                parent_bc_token_b: mix_content_record_data.buddy_bc_token,
+                time_shift_a: mix_content_record_data.time_shift_a,
+                time_shift_b: mix_content_record_data.time_shift_b,
+            }),
+        },
+    };
+
+    create_and_serialize_account_signed::<BcToken>(
+        payer_info,
+        bc_token_info,
+        &bc_token_data,
+        &bc_token_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    mix_content_record_data.promoted_bc_token = Some(*bc_token_info.key);
+    mix_content_record_data.serialize(&mut *mix_content_record_info.data.borrow_mut())?;
+
+    ShihonEvent::MixedContentPromoted {
+        bc_token: *bc_token_info.key,
+        mix_content_record: *mix_content_record_info.key,
+        parent_bc_token_a: mix_content_record_data.init_bc_token,
+        parent_bc_token_b: mix_content_record_data.buddy_bc_token,
+    }
+    .emit();
+
+    ShihonEvent::BcTokenCreated {
+        bc_token: *bc_token_info.key,
+        bc_token_mint: *bc_token_mint_info.key,
+        authority: rater_candidate_data.candidate_token_owner,
+        name,
+    }
+    .emit();
+
+    Ok(())
+}
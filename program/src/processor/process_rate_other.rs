@@ -2,6 +2,7 @@
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
@@ -9,11 +10,30 @@ use solana_program::{
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_rate_other_record_address_seeds},
+    state::{
+        candidate_limit_record::{
+            get_candidate_limit_record_data, get_candidate_limit_record_data_for_tanistry,
+        },
+        enums::ShihonAccountType,
+        mix_content_record::get_mix_content_record_data_for_seeds,
+        rate_other_record::{assert_is_valid_rating, RateOtherRecord},
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_MIX_AND_RATE},
+        triple_multisig::get_triple_multisig_data_for_candidates,
+    },
+};
+
 /// Processes Rate instruction
-pub fn process_rate_other(program_id: &Pubkey, accounts: &[AccountInfo],rating: u64) -> ProgramResult {
-    unimplemented!();
+pub fn process_rate_other(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    rating: u64,
+) -> ProgramResult {
     // this func has three step
-    // we need a multisig that combines two keys each for calling the one candidate or first kicker
+    // The rating needs two of the three keys of the rater, the buddy and the first kicker (TripleMultisig)
     // of the three, only two are trials that raters can be consciously aware of
     // Step 1: Issue a MixContentRecord for mixing init content and buddy candidate's content
     // Step 2: after receive content's link from buddy candidate, put amount of rating point on buddy candidate
@@ -28,19 +48,96 @@ pub fn process_rate_other(program_id: &Pubkey, accounts: &[AccountInfo],rating:
     let account_info_iter = &mut accounts.iter();
 
     let rater_candidate_info = next_account_info(account_info_iter)?; // 0
-    let rating_input_info = next_account_info(account_info_iter)?; // 1
+    let rater_token_owner_info = next_account_info(account_info_iter)?; // 1
     let buddy_candidate_info = next_account_info(account_info_iter)?; // 2
-    let kicker_token_info = next_account_info(account_info_iter)?; // 3
+    let mix_content_record_info = next_account_info(account_info_iter)?; // 3
+    let rate_other_record_info = next_account_info(account_info_iter)?; // 4
+    let triple_multisig_info = next_account_info(account_info_iter)?; // 5
+    let payer_info = next_account_info(account_info_iter)?; // 6
+    let system_info = next_account_info(account_info_iter)?; // 7
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 8
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
 
-    let coordinator_input_info = next_account_info(account_info_iter)?; // 4
+    let clock_info = next_account_info(account_info_iter)?; // 9
+    let clock = Clock::from_account_info(clock_info)?;
 
-    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 5
-    let mix_content_record_info = next_account_info(account_info_iter)?; // 6
+    let shihon_config_info = next_account_info(account_info_iter)?; // 10
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_MIX_AND_RATE)?;
 
-    let rating_info = next_account_info(account_info_iter)?; // 7
-    let system_info = next_account_info(account_info_iter)?; // 8
+    let triple_multisig_signer_infos = account_info_iter.as_slice(); // 11..
 
-    let rent_sysvar_info = next_account_info(account_info_iter)?; // 8
-    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+    assert_is_valid_rating(rating)?;
+
+    let rater_candidate_data = get_candidate_limit_record_data(program_id, rater_candidate_info)?;
+    rater_candidate_data.assert_token_owner_or_delegate_is_signer(rater_token_owner_info)?;
+
+    let mut buddy_candidate_data = get_candidate_limit_record_data_for_tanistry(
+        program_id,
+        buddy_candidate_info,
+        &rater_candidate_data.belonging_tanistry,
+    )?;
+
+    // The rating is only possible on the content the rater mixed with the buddy
+    get_mix_content_record_data_for_seeds(
+        program_id,
+        mix_content_record_info,
+        rater_candidate_info.key,
+        buddy_candidate_info.key,
+    )?;
+
+    get_triple_multisig_data_for_candidates(
+        program_id,
+        triple_multisig_info,
+        rater_candidate_info.key,
+        buddy_candidate_info.key,
+    )?
+    .assert_signed_by_threshold(
+        triple_multisig_signer_infos,
+        &[
+            rater_candidate_data.candidate_delegate,
+            buddy_candidate_data.candidate_delegate,
+            None,
+        ],
+    )?;
+
+    let rate_other_record_address_seeds =
+        get_rate_other_record_address_seeds(rater_candidate_info.key, buddy_candidate_info.key);
+
+    // The CCVoteRecord and the OutsideBuyerRecord aren't issued on the first rating
+    let rate_other_record_data = RateOtherRecord {
+        account_type: ShihonAccountType::RateOtherRecordV2,
+        bump: find_bump_seed(program_id, &rate_other_record_address_seeds),
+        mix_content_record: *mix_content_record_info.key,
+        cc_vote_record: Pubkey::default(),
+        outside_buyer_record: Pubkey::default(),
+        rate_amount: rating,
+        rating_time: Some(clock.unix_timestamp),
+    };
+
+    create_and_serialize_account_signed::<RateOtherRecord>(
+        payer_info,
+        rate_other_record_info,
+        &rate_other_record_data,
+        &rate_other_record_address_seeds,
+        program_id,
+        system_info,
+        rent,
+    )?;
+
+    buddy_candidate_data.received_rating = buddy_candidate_data
+        .received_rating
+        .checked_add(rating)
+        .ok_or(ShihonError::RatingOverflow)?;
+    buddy_candidate_data.serialize(&mut *buddy_candidate_info.data.borrow_mut())?;
+
+    ShihonEvent::Rated {
+        rater: *rater_candidate_info.key,
+        buddy: *buddy_candidate_info.key,
+        mix_content_record: *mix_content_record_info.key,
+        rating,
+    }
+    .emit();
 
+    Ok(())
 }
@@ -0,0 +1,107 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::get_tanistry_address_seeds,
+    state::tanistry::get_tanistry_data,
+    tools::spl_token::{
+        assert_spl_token_owner_is_signer, burn_spl_tokens, get_spl_token_amount,
+        get_spl_token_mint, transfer_spl_tokens_signed,
+    },
+};
+
+/// Processes RedeemCoordinatorRefundReceipt instruction
+pub fn process_redeem_coordinator_refund_receipt(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    // The receipt is burnt and its holder receives the coordinator's share of the settled vault
+    // Note: The receipt can be transferred, so the holder doesn't need to be the coordinator
+
+    let account_info_iter = &mut accounts.iter();
+
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 1
+    let refund_receipt_mint_info = next_account_info(account_info_iter)?; // 2
+    let refund_receipt_token_info = next_account_info(account_info_iter)?; // 3
+    let holder_info = next_account_info(account_info_iter)?; // 4
+    let holder_token_info = next_account_info(account_info_iter)?; // 5
+    let spl_token_info = next_account_info(account_info_iter)?; // 6
+    let tanistry_token_mint_info = next_account_info(account_info_iter)?; // 7
+    let tanistry_token_program_info = next_account_info(account_info_iter)?; // 8
+
+    let mut tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+    tanistry_data.assert_can_claim_refund()?;
+    tanistry_data.assert_is_valid_tanistry_token_holding(
+        program_id,
+        tanistry_info.key,
+        tanistry_token_holding_info.key,
+    )?;
+    tanistry_data.assert_is_valid_tanistry_token_mint(tanistry_token_mint_info.key)?;
+    tanistry_data.assert_is_valid_coordinator_refund_receipt_mint(refund_receipt_mint_info.key)?;
+
+    if get_spl_token_mint(refund_receipt_token_info)? != *refund_receipt_mint_info.key {
+        return Err(ShihonError::InvalidCoordinatorRefundReceiptMint.into());
+    }
+
+    assert_spl_token_owner_is_signer(refund_receipt_token_info, holder_info)?;
+
+    if tanistry_data.is_coordinator_refunded {
+        return Err(ShihonError::RefundAlreadyClaimed.into());
+    }
+
+    burn_spl_tokens(
+        refund_receipt_token_info,
+        refund_receipt_mint_info,
+        holder_info,
+        1,
+        spl_token_info,
+    )?;
+
+    let refund_amount = tanistry_data.coordinator_refund_amount;
+
+    tanistry_data.is_coordinator_refunded = true;
+    tanistry_data.add_refunded_amount(refund_amount)?;
+
+    if refund_amount > 0 {
+        transfer_spl_tokens_signed(
+            tanistry_token_holding_info,
+            tanistry_token_mint_info,
+            holder_token_info,
+            tanistry_info,
+            &get_tanistry_address_seeds(&tanistry_data.kicker_coin_owner_record),
+            program_id,
+            refund_amount,
+            tanistry_token_program_info,
+        )?;
+    }
+
+    // Conservation of funds: whatever hasn't been refunded yet must still be in the vault
+    // The surplus somebody sent to the vault on top of the settled amount is ignored
+    let outstanding_amount = tanistry_data
+        .settled_amount
+        .checked_sub(tanistry_data.refunded_amount)
+        .unwrap();
+
+    if get_spl_token_amount(tanistry_token_holding_info)? < outstanding_amount {
+        return Err(ShihonError::TanistryVaultBalanceMismatch.into());
+    }
+
+    tanistry_data.serialize(&mut *tanistry_info.data.borrow_mut())?;
+
+    ShihonEvent::Refunded {
+        tanistry: *tanistry_info.key,
+        claimant: *holder_info.key,
+        amount: refund_amount,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Program state processor
+
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data,
+        cc_vote_record::get_cc_vote_record_data_for_seeds, ring::get_ring_data,
+    },
+};
+
+/// Processes RelinquishCCVote instruction
+pub fn process_relinquish_cc_vote(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // The voter can withdraw the vote until the counting time of the target ring
+    // The record is kept as relinquished so the same vote can't be counted twice
+    // The candidate token owner or its delegate can relinquish the vote
+
+    let account_info_iter = &mut accounts.iter();
+
+    let target_ring_info = next_account_info(account_info_iter)?; // 0
+    let cc_vote_record_info = next_account_info(account_info_iter)?; // 1
+    let voter_info = next_account_info(account_info_iter)?; // 2
+
+    let clock_info = next_account_info(account_info_iter)?; // 3
+    let clock = Clock::from_account_info(clock_info)?;
+
+    let candidate_limit_record_info = next_account_info(account_info_iter)?; // 4
+
+    let mut target_ring_data = get_ring_data(program_id, target_ring_info)?;
+
+    let mut cc_vote_record_data = get_cc_vote_record_data_for_seeds(
+        program_id,
+        cc_vote_record_info,
+        candidate_limit_record_info.key,
+    )?;
+
+    if cc_vote_record_data.target_ring != *target_ring_info.key {
+        return Err(ShihonError::InvalidTargetRingForCCVoteRecord.into());
+    }
+
+    get_candidate_limit_record_data(program_id, candidate_limit_record_info)?
+        .assert_token_owner_or_delegate_is_signer(voter_info)?;
+
+    cc_vote_record_data.assert_can_vote(&target_ring_data, clock.slot)?;
+
+    target_ring_data.decrease_cc_vote_weight(cc_vote_record_data.voter_weight)?;
+    target_ring_data.serialize(&mut *target_ring_info.data.borrow_mut())?;
+
+    cc_vote_record_data.is_relinquished = true;
+    cc_vote_record_data.serialize(&mut *cc_vote_record_info.data.borrow_mut())?;
+
+    ShihonEvent::CCVoteRelinquished {
+        target_ring: *target_ring_info.key,
+        cc_vote_record: *cc_vote_record_info.key,
+        voter: cc_vote_record_data.voter,
+        voter_weight: cc_vote_record_data.voter_weight,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+use spl_governance_tools::account::dispose_account;
+
+use crate::{
+    events::ShihonEvent,
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data,
+        rate_option::get_rate_option_data_for_seeds,
+    },
+};
+
+/// Processes RemoveRateOption instruction
+pub fn process_remove_rate_option(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Only a RateOption which hasn't been signed off can be removed
+    // The issue number isn't given back so the issue numbers of the Roydamna stay unique
+
+    let account_info_iter = &mut accounts.iter();
+
+    let rate_option_info = next_account_info(account_info_iter)?; // 0
+    let issuer_roydamna_info = next_account_info(account_info_iter)?; // 1
+    let issuer_token_owner_info = next_account_info(account_info_iter)?; // 2
+    let mix_content_record_info = next_account_info(account_info_iter)?; // 3
+    let beneficiary_info = next_account_info(account_info_iter)?; // 4
+
+    let issuer_roydamna_data = get_candidate_limit_record_data(program_id, issuer_roydamna_info)?;
+    issuer_roydamna_data.assert_token_owner_or_delegate_is_signer(issuer_token_owner_info)?;
+
+    let rate_option_data = get_rate_option_data_for_seeds(
+        program_id,
+        rate_option_info,
+        mix_content_record_info.key,
+        issuer_roydamna_info.key,
+    )?;
+    rate_option_data.assert_can_remove()?;
+
+    dispose_account(rate_option_info, beneficiary_info);
+
+    ShihonEvent::RateOptionRemoved {
+        rate_option: *rate_option_info.key,
+        issuer_roydamna: *issuer_roydamna_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    state::{bc_token::get_bc_token_data_for_authority, enums::ShihonAccountType},
+};
+
+/// Processes SetBcTokenAuthority instruction
+pub fn process_set_bc_token_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_authority: Option<Pubkey>,
+    require_acceptance: bool,
+    renounce_authority: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let bc_token_info = next_account_info(account_info_iter)?; // 0
+    let bc_token_authority_info = next_account_info(account_info_iter)?; // 1
+
+    let mut bc_token_data =
+        get_bc_token_data_for_authority(program_id, bc_token_info, bc_token_authority_info.key)?;
+
+    if !bc_token_authority_info.is_signer {
+        return Err(ShihonError::BcTokenAuthorityMustSign.into());
+    }
+
+    bc_token_data.assert_can_set_authority()?;
+
+    // Without an authority nobody can ever move the bcToken again, so it must be a deliberate renounce
+    // Note: None with the acceptance required only withdraws the pending proposal
+    let is_renounce = new_authority.is_none() && !require_acceptance;
+
+    if renounce_authority != is_renounce {
+        return Err(ShihonError::InvalidBcTokenAuthorityRenounce.into());
+    }
+
+    if require_acceptance {
+        // V1 and V2 bcTokens have no room for the pending authority and have to be migrated first
+        if bc_token_data.account_type != ShihonAccountType::BcTokenV3 {
+            return Err(ShihonError::BcTokenMustBeMigrated.into());
+        }
+
+        bc_token_data.pending_authority = new_authority;
+        bc_token_data.serialize(&mut *bc_token_info.data.borrow_mut())?;
+
+        ShihonEvent::BcTokenAuthorityProposed {
+            bc_token: *bc_token_info.key,
+            pending_authority: new_authority,
+        }
+        .emit();
+
+        return Ok(());
+    }
+
+    bc_token_data.set_authority(new_authority);
+    bc_token_data.serialize(&mut *bc_token_info.data.borrow_mut())?;
+
+    ShihonEvent::BcTokenAuthoritySet {
+        bc_token: *bc_token_info.key,
+        authority: new_authority,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    state::{bc_token::get_bc_token_data_for_authority, enums::ShihonAccountType},
+};
+
+/// Processes SetBcTokenDelegate instruction
+pub fn process_set_bc_token_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_delegate: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let bc_token_info = next_account_info(account_info_iter)?; // 0
+    let bc_token_authority_info = next_account_info(account_info_iter)?; // 1
+
+    let mut bc_token_data =
+        get_bc_token_data_for_authority(program_id, bc_token_info, bc_token_authority_info.key)?;
+
+    if !bc_token_authority_info.is_signer {
+        return Err(ShihonError::BcTokenAuthorityMustSign.into());
+    }
+
+    // V1 and V2 bcTokens have no room for the delegate and have to be migrated first
+    if bc_token_data.account_type != ShihonAccountType::BcTokenV3 {
+        return Err(ShihonError::BcTokenMustBeMigrated.into());
+    }
+
+    bc_token_data.delegate = new_delegate;
+    bc_token_data.serialize(&mut *bc_token_info.data.borrow_mut())?;
+
+    ShihonEvent::BcTokenDelegateSet {
+        bc_token: *bc_token_info.key,
+        delegate: new_delegate,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{events::ShihonEvent, state::candidate_limit_record::get_candidate_limit_record_data};
+
+/// Processes SetCandidateDelegate instruction
+pub fn process_set_candidate_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_candidate_delegate: Option<Pubkey>,
+) -> ProgramResult {
+    // Only the owner can change the delegate so a leaked hot key can't hand itself over to another key
+
+    let account_info_iter = &mut accounts.iter();
+
+    let candidate_limit_record_info = next_account_info(account_info_iter)?; // 0
+    let candidate_token_owner_info = next_account_info(account_info_iter)?; // 1
+
+    let mut candidate_limit_record_data =
+        get_candidate_limit_record_data(program_id, candidate_limit_record_info)?;
+    candidate_limit_record_data.assert_token_owner_is_signer(candidate_token_owner_info)?;
+
+    candidate_limit_record_data.candidate_delegate = new_candidate_delegate;
+    candidate_limit_record_data.serialize(&mut *candidate_limit_record_info.data.borrow_mut())?;
+
+    ShihonEvent::CandidateDelegateSet {
+        candidate_limit_record: *candidate_limit_record_info.key,
+        candidate_delegate: new_candidate_delegate,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Program state processor
+
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{events::ShihonEvent, state::shihon_config::get_shihon_config_data};
+
+/// Processes SetPauseFlags instruction
+pub fn process_set_pause_flags(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pause_flags: u32,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 0
+    let pause_authority_info = next_account_info(account_info_iter)?; // 1
+
+    let mut shihon_config_data = get_shihon_config_data(program_id, shihon_config_info)?;
+    shihon_config_data.assert_can_set_pause_flags(pause_authority_info, pause_flags)?;
+
+    shihon_config_data.pause_flags = pause_flags;
+    shihon_config_data.serialize(&mut *shihon_config_info.data.borrow_mut())?;
+
+    ShihonEvent::ShihonPauseFlagsChanged {
+        shihon_config: *shihon_config_info.key,
+        pause_authority: *pause_authority_info.key,
+        pause_flags,
+    }
+    .emit();
+
+    Ok(())
+}
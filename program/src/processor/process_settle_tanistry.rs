@@ -0,0 +1,138 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        enums::TanistryState,
+        kicker_coin_owner_record::get_kicker_coin_owner_record_data,
+        tanistry::{calculate_refund_allocation, get_tanistry_data},
+    },
+    tools::spl_token::get_spl_token_amount,
+};
+
+/// Processes SettleTanistry instruction
+pub fn process_settle_tanistry(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Anybody can crank the settlement once the Crown has been chosen
+    // The vault is split by calculate_refund_allocation and each participant claims own share with ClaimRefund
+    // Note: The vault must hold exactly what was deposited into it, otherwise the settlement is rejected
+
+    let account_info_iter = &mut accounts.iter();
+
+    let tanistry_info = next_account_info(account_info_iter)?; // 0
+    let kicker_coin_owner_record_info = next_account_info(account_info_iter)?; // 1
+    let tanistry_token_holding_info = next_account_info(account_info_iter)?; // 2
+
+    let mut tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
+    tanistry_data.assert_can_settle()?;
+    tanistry_data.assert_is_valid_tanistry_token_holding(
+        program_id,
+        tanistry_info.key,
+        tanistry_token_holding_info.key,
+    )?;
+
+    if tanistry_data.kicker_coin_owner_record != *kicker_coin_owner_record_info.key {
+        return Err(ShihonError::InvalidKickerCoinOwnerRecordForTanistry.into());
+    }
+
+    let kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+
+    // 3..n CandidateLimitRecord accounts in the order of candidate_limit_record_list
+    let candidate_limit_record_infos = account_info_iter.as_slice();
+
+    if candidate_limit_record_infos.len() != tanistry_data.candidate_limit_record_list.len() {
+        return Err(ShihonError::InvalidCandidateLimitRecordsForSettlement.into());
+    }
+
+    let mut candidate_limit_records = vec![];
+
+    for (candidate_limit_record_info, candidate_limit_record) in candidate_limit_record_infos
+        .iter()
+        .zip(tanistry_data.candidate_limit_record_list.iter())
+    {
+        if candidate_limit_record_info.key != candidate_limit_record {
+            return Err(ShihonError::InvalidCandidateLimitRecordsForSettlement.into());
+        }
+
+        candidate_limit_records.push(get_candidate_limit_record_data_for_tanistry(
+            program_id,
+            candidate_limit_record_info,
+            tanistry_info.key,
+        )?);
+    }
+
+    let crown_index = tanistry_data
+        .candidate_limit_record_list
+        .iter()
+        .position(|candidate_limit_record| Some(*candidate_limit_record) == tanistry_data.crown)
+        .ok_or(ShihonError::InvalidCrownForTanistry)?;
+
+    let candidates: Vec<(u64, u64)> = candidate_limit_records
+        .iter()
+        .map(|record| {
+            (
+                record.candidate_token_deposit_amount,
+                record.received_rating,
+            )
+        })
+        .collect();
+
+    // Conservation of funds: the vault must hold the KickerCoin, the self-rating deposits and the outside buyers' funds
+    let mut expected_vault_amount = kicker_coin_owner_record_data
+        .amount_of_kicker_coin
+        .checked_add(tanistry_data.outside_buyer_deposit_amount)
+        .ok_or(ShihonError::RefundAmountOverflow)?;
+
+    for (deposit, _) in candidates.iter() {
+        expected_vault_amount = expected_vault_amount
+            .checked_add(*deposit)
+            .ok_or(ShihonError::RefundAmountOverflow)?;
+    }
+
+    // Anybody can send tokens to the vault, the surplus isn't allocated and stays in the vault
+    if get_spl_token_amount(tanistry_token_holding_info)? < expected_vault_amount {
+        return Err(ShihonError::TanistryVaultBalanceMismatch.into());
+    }
+
+    let allocation = calculate_refund_allocation(
+        kicker_coin_owner_record_data.amount_of_kicker_coin,
+        tanistry_data.outside_buyer_deposit_amount,
+        &candidates,
+        crown_index,
+    )?;
+
+    for ((candidate_limit_record_info, mut candidate_limit_record_data), refund_amount) in
+        candidate_limit_record_infos
+            .iter()
+            .zip(candidate_limit_records)
+            .zip(allocation.candidate_amounts)
+    {
+        candidate_limit_record_data.refund_amount = refund_amount;
+        candidate_limit_record_data
+            .serialize(&mut *candidate_limit_record_info.data.borrow_mut())?;
+    }
+
+    tanistry_data.kicker_refund_amount = allocation.kicker_amount;
+    tanistry_data.coordinator_refund_amount = allocation.coordinator_amount;
+    tanistry_data.settled_amount = expected_vault_amount;
+    tanistry_data.refunded_amount = 0;
+    tanistry_data.state = TanistryState::EnableToRefund;
+
+    tanistry_data.serialize(&mut *tanistry_info.data.borrow_mut())?;
+
+    ShihonEvent::TanistrySettled {
+        tanistry: *tanistry_info.key,
+        settled_amount: tanistry_data.settled_amount,
+    }
+    .emit();
+
+    Ok(())
+}
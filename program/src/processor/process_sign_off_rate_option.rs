@@ -0,0 +1,53 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    events::ShihonEvent,
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data,
+        rate_option::get_rate_option_data_for_seeds, rate_other_record::get_rate_other_record_data,
+    },
+};
+
+/// Processes SignOffRateOption instruction
+pub fn process_sign_off_rate_option(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    // The Roydamna signs off the RateOption once the rating it governs has been cast
+
+    let account_info_iter = &mut accounts.iter();
+
+    let rate_option_info = next_account_info(account_info_iter)?; // 0
+    let issuer_roydamna_info = next_account_info(account_info_iter)?; // 1
+    let issuer_token_owner_info = next_account_info(account_info_iter)?; // 2
+    let rate_other_record_info = next_account_info(account_info_iter)?; // 3
+
+    let issuer_roydamna_data = get_candidate_limit_record_data(program_id, issuer_roydamna_info)?;
+    issuer_roydamna_data.assert_token_owner_or_delegate_is_signer(issuer_token_owner_info)?;
+
+    let mut rate_option_data = get_rate_option_data_for_seeds(
+        program_id,
+        rate_option_info,
+        &get_rate_other_record_data(program_id, rate_other_record_info)?.mix_content_record,
+        issuer_roydamna_info.key,
+    )?;
+    rate_option_data.assert_can_sign_off(rate_other_record_info)?;
+
+    rate_option_data.signed_off = true;
+    rate_option_data.serialize(&mut *rate_option_info.data.borrow_mut())?;
+
+    ShihonEvent::RateOptionSignedOff {
+        rate_option: *rate_option_info.key,
+        issuer_roydamna: *issuer_roydamna_info.key,
+        rate_other_record: *rate_other_record_info.key,
+    }
+    .emit();
+
+    Ok(())
+}
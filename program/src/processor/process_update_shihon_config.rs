@@ -0,0 +1,46 @@
+//! Program state processor
+
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    events::ShihonEvent,
+    state::shihon_config::{get_shihon_config_data, ShihonConfigArgs},
+};
+
+/// Processes UpdateShihonConfig instruction
+pub fn process_update_shihon_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    config_args: ShihonConfigArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 0
+    let admin_authority_info = next_account_info(account_info_iter)?; // 1
+
+    let mut shihon_config_data = get_shihon_config_data(program_id, shihon_config_info)?;
+    shihon_config_data.assert_admin_authority_is_signer(admin_authority_info)?;
+
+    config_args.assert_is_valid()?;
+    shihon_config_data.config = config_args;
+
+    // The new admin doesn't need to sign, the same way the upgrade authority appoints the first admin
+    if let Some(new_admin_authority_info) = account_info_iter.next() {
+        shihon_config_data.admin_authority = *new_admin_authority_info.key;
+    }
+
+    shihon_config_data.serialize(&mut *shihon_config_info.data.borrow_mut())?;
+
+    ShihonEvent::ShihonConfigUpdated {
+        shihon_config: *shihon_config_info.key,
+        admin_authority: shihon_config_data.admin_authority,
+    }
+    .emit();
+
+    Ok(())
+}
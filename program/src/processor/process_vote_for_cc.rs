@@ -1,7 +1,9 @@
 //! Program state processor
 
+use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
@@ -9,10 +11,141 @@ use solana_program::{
 };
 use spl_governance_tools::account::create_and_serialize_account_signed;
 
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{find_bump_seed, get_cc_vote_record_address_seeds},
+    state::{
+        candidate_limit_record::get_candidate_limit_record_data_for_tanistry,
+        cc_vote_record::{get_cc_vote_record_data_for_seeds, CCVoteRecord},
+        enums::{ShihonAccountType, VoteSource},
+        ring::get_ring_data,
+        shihon_config::{assert_shihon_is_not_paused, PAUSE_CC_VOTE},
+        tanistry::get_tanistry_data,
+    },
+};
+
 /// Processes Vote instruction
-pub fn process_vote_for_cc(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-    unimplemented!();
+pub fn process_vote_for_cc(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    target_ring: Pubkey,
+    vote: VoteSource,
+) -> ProgramResult {
     // we can choose fixed and fluid voting
     // and VoteSource has some parameters: pull and push
     // pull need less amount than your ring, push need more amount than your ring
+    // Rings are compared by the cumulative deposit and RFT volume Crowning adds up
+    // The first vote for the target ring opens its counting window which every vote for the ring shares
+    // The votes stay open until the counting time of the ring, see RelinquishCCVote and ChangeCCVote
+    // A CandidateLimitRecord backs a single vote so its deposit can't be counted for several rings
+
+    let account_info_iter = &mut accounts.iter();
+
+    let target_ring_info = next_account_info(account_info_iter)?; // 0
+    let cc_vote_record_info = next_account_info(account_info_iter)?; // 1
+    let candidate_limit_record_info = next_account_info(account_info_iter)?; // 2
+    let voter_info = next_account_info(account_info_iter)?; // 3
+    let payer_info = next_account_info(account_info_iter)?; // 4
+    let system_info = next_account_info(account_info_iter)?; // 5
+
+    let rent_sysvar_info = next_account_info(account_info_iter)?; // 6
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+
+    let clock_info = next_account_info(account_info_iter)?; // 7
+    let clock = Clock::from_account_info(clock_info)?;
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 8
+    assert_shihon_is_not_paused(program_id, shihon_config_info, PAUSE_CC_VOTE)?;
+
+    let voter_tanistry_info = next_account_info(account_info_iter)?; // 9
+    let voter_ring_info = next_account_info(account_info_iter)?; // 10
+
+    if *target_ring_info.key != target_ring {
+        return Err(ShihonError::InvalidTargetRingForCCVoteRecord.into());
+    }
+
+    let mut target_ring_data = get_ring_data(program_id, target_ring_info)?;
+
+    let candidate_limit_record_data = get_candidate_limit_record_data_for_tanistry(
+        program_id,
+        candidate_limit_record_info,
+        voter_tanistry_info.key,
+    )?;
+    candidate_limit_record_data.assert_token_owner_or_delegate_is_signer(voter_info)?;
+    candidate_limit_record_data.assert_can_cc_vote()?;
+
+    // The target ring is pulled or pushed relative to the ring the voter's Tanistry belongs to
+    let voter_tanistry_data = get_tanistry_data(program_id, voter_tanistry_info)?;
+    voter_tanistry_data.assert_can_cc_vote()?;
+    voter_tanistry_data.assert_is_valid_ring(voter_ring_info.key)?;
+
+    let voter_ring_data = get_ring_data(program_id, voter_ring_info)?;
+    voter_ring_data.assert_can_cc_vote(&target_ring_data, &vote)?;
+
+    let voter_weight = candidate_limit_record_data.candidate_token_deposit_amount;
+
+    let counting_time = target_ring_data.open_cc_vote_counting_window(clock.slot)?;
+    target_ring_data.increase_cc_vote_weight(voter_weight)?;
+    target_ring_data.serialize(&mut *target_ring_info.data.borrow_mut())?;
+
+    let cc_vote_record_address_seeds =
+        get_cc_vote_record_address_seeds(candidate_limit_record_info.key);
+
+    let cc_vote_record_data = if cc_vote_record_info.data_is_empty() {
+        let cc_vote_record_data = CCVoteRecord {
+            account_type: ShihonAccountType::CCVoteRecordV2,
+            bump: find_bump_seed(program_id, &cc_vote_record_address_seeds),
+            target_ring,
+            // The delegate can cast the vote but the vote always belongs to the owner
+            voter: candidate_limit_record_data.candidate_token_owner,
+            is_relinquished: false,
+            voter_weight,
+            vote,
+        };
+
+        create_and_serialize_account_signed::<CCVoteRecord>(
+            payer_info,
+            cc_vote_record_info,
+            &cc_vote_record_data,
+            &cc_vote_record_address_seeds,
+            program_id,
+            system_info,
+            rent,
+        )?;
+
+        cc_vote_record_data
+    } else {
+        // The voter relinquished the previous vote and votes again with the same deposit
+        // The counting window of the target ring is shared so voting again can't extend it
+        let mut cc_vote_record_data = get_cc_vote_record_data_for_seeds(
+            program_id,
+            cc_vote_record_info,
+            candidate_limit_record_info.key,
+        )?;
+
+        if !cc_vote_record_data.is_relinquished {
+            return Err(ShihonError::CCVoteAlreadyExists.into());
+        }
+
+        cc_vote_record_data.target_ring = target_ring;
+        cc_vote_record_data.voter = candidate_limit_record_data.candidate_token_owner;
+        cc_vote_record_data.is_relinquished = false;
+        cc_vote_record_data.voter_weight = voter_weight;
+        cc_vote_record_data.vote = vote;
+        cc_vote_record_data.serialize(&mut *cc_vote_record_info.data.borrow_mut())?;
+
+        cc_vote_record_data
+    };
+
+    ShihonEvent::CCVoted {
+        target_ring,
+        cc_vote_record: *cc_vote_record_info.key,
+        voter: cc_vote_record_data.voter,
+        voter_weight: cc_vote_record_data.voter_weight,
+        counting_time,
+    }
+    .emit();
+
+    Ok(())
 }
@@ -0,0 +1,58 @@
+//! Program state processor
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ShihonError,
+    events::ShihonEvent,
+    pda::{get_shihon_config_address_seeds, get_treasury_address},
+    state::shihon_config::get_shihon_config_data,
+    tools::spl_token::transfer_spl_tokens_signed,
+};
+
+/// Processes WithdrawTreasury instruction
+pub fn process_withdraw_treasury(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let shihon_config_info = next_account_info(account_info_iter)?; // 0
+    let admin_authority_info = next_account_info(account_info_iter)?; // 1
+    let treasury_info = next_account_info(account_info_iter)?; // 2
+    let treasury_mint_info = next_account_info(account_info_iter)?; // 3
+    let destination_info = next_account_info(account_info_iter)?; // 4
+    let spl_token_info = next_account_info(account_info_iter)?; // 5
+
+    let shihon_config_data = get_shihon_config_data(program_id, shihon_config_info)?;
+    shihon_config_data.assert_admin_authority_is_signer(admin_authority_info)?;
+
+    if *treasury_info.key != get_treasury_address(program_id, treasury_mint_info.key) {
+        return Err(ShihonError::InvalidTreasuryAddress.into());
+    }
+
+    transfer_spl_tokens_signed(
+        treasury_info,
+        treasury_mint_info,
+        destination_info,
+        shihon_config_info,
+        &get_shihon_config_address_seeds(),
+        program_id,
+        amount,
+        spl_token_info,
+    )?;
+
+    ShihonEvent::TreasuryWithdrawn {
+        treasury: *treasury_info.key,
+        destination: *destination_info.key,
+        amount,
+    }
+    .emit();
+
+    Ok(())
+}
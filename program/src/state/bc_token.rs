@@ -2,21 +2,32 @@ use solana_program::clock::UnixTimestamp;
 
 use crate::state::bc_token_metadata::BcTokenMetadata;
 use crate::state::enums::BcTokenState;
-use crate::{error::ShihonError, state::enums::ShihonAccountType, PROGRAM_AUTHORITY_SEED};
+use crate::{
+    error::ShihonError,
+    pda::{find_bump_seed, get_bc_token_address_seeds, get_bc_token_holding_address, is_valid_pda},
+    state::{
+        enums::ShihonAccountType,
+        legacy::{BcTokenV1, BcTokenV2},
+    },
+};
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
-    pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{assert_is_valid_account, get_account_data, AccountMaxSize};
 
-/// bcToken Account PDA seeds" ['shihon', name]
+/// bcToken Account PDA seeds: ['bc-token', name]
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct BcToken {
     /// account type
     pub account_type: ShihonAccountType,
 
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
     /// bcToken owner pubkey
     pub bc_token_owner_pubkey: Pubkey,
 
@@ -32,6 +43,13 @@ pub struct BcToken {
     /// bcToken authority
     pub authority: Option<Pubkey>,
 
+    /// The delegate the authority allowed to mix, rate and bump self-rate with the bcToken
+    /// The delegate can never withdraw or discard the bcToken
+    pub delegate: Option<Pubkey>,
+
+    /// The authority proposed by the current authority which has yet to accept it
+    pub pending_authority: Option<Pubkey>,
+
     /// bcToken name
     pub name: String,
 
@@ -47,17 +65,43 @@ pub struct BcToken {
 
 impl IsInitialized for BcToken {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::BcToken
+        self.account_type == ShihonAccountType::BcTokenV3
     }
 }
 
 impl AccountMaxSize for BcToken {
     fn get_max_size(&self) -> Option<usize> {
-        Some(self.name.len() + 136)
+        Some(self.name.len() + self.config.version.len() + 404)
     }
 }
 
 impl BcToken {
+    /// Serializes the account into the layout of its version
+    /// V1 and V2 accounts are written back in their own layout until they are migrated
+    pub fn serialize<W: Write>(self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::BcTokenV1 {
+            let bc_token_data_v1: BcTokenV1 = self.into();
+            BorshSerialize::serialize(&bc_token_data_v1, writer)?;
+        } else if self.account_type == ShihonAccountType::BcTokenV2 {
+            let bc_token_data_v2: BcTokenV2 = self.into();
+            BorshSerialize::serialize(&bc_token_data_v2, writer)?;
+        } else {
+            BorshSerialize::serialize(&self, writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the PDA bump of the bcToken
+    /// V1 accounts don't store the bump and it's derived from the seeds
+    pub fn get_bump(&self, program_id: &Pubkey) -> u8 {
+        if self.account_type == ShihonAccountType::BcTokenV1 {
+            find_bump_seed(program_id, &get_bc_token_address_seeds(&self.name))
+        } else {
+            self.bump
+        }
+    }
+
     /// Asserts the given mint is mint of creating the bcToken
     pub fn assert_is_valid_bc_token_mint(
         &self,
@@ -89,26 +133,93 @@ impl BcToken {
         Ok(())
     }
 
-    /// Asserts the given bcToken can be deposited into the Tanistry
-    pub fn asset_can_deposits_on_bc_token(
+    /// Asserts the bcToken authority or its delegate signed the transaction
+    pub fn assert_authority_or_delegate_is_signer(
         &self,
-        bc_token_mint: &Pubkey,
+        bc_token_authority_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if self.authority.is_none() {
+            return Err(ShihonError::BcTokenHasNoAuthority.into());
+        }
+
+        if bc_token_authority_info.is_signer
+            && (self.authority == Some(*bc_token_authority_info.key)
+                || self.delegate == Some(*bc_token_authority_info.key))
+        {
+            return Ok(());
+        }
+
+        Err(ShihonError::BcTokenAuthorityOrDelegateMustSign.into())
+    }
+
+    /// Checks whether the bcToken is taking part in a Tanistry which hasn't been settled or cancelled yet
+    pub fn is_in_active_tanistry(&self) -> bool {
+        self.bc_token_state == BcTokenState::EnableToCandidate
+            || self.bc_token_state == BcTokenState::TanistryHasSetIn
+            || self.bc_token_state == BcTokenState::EnableToVoteToCC
+    }
+
+    /// Asserts the authority of the bcToken can be changed
+    pub fn assert_can_set_authority(&self) -> Result<(), ProgramError> {
+        if self.is_in_active_tanistry() {
+            return Err(ShihonError::BcTokenInActiveTanistry.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the pending authority of the bcToken signed the transaction
+    pub fn assert_pending_authority_is_signer(
+        &self,
+        pending_authority_info: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        ///TODO: create get_bc_token_account_on_tanistry func on tanistry.rs
-        // If the deposit is for the bcToken and the token same as on Tanistry using twice then panic
-        if self.get_bc_token_account_on_tanistry() && self.bc_token_mint == *bc_token_mint {
-            return Err(ShihonError::BcTokenDepositsNotAllowed.into());
+        if self.pending_authority != Some(*pending_authority_info.key) {
+            return Err(ShihonError::InvalidPendingAuthorityForBcToken.into());
+        }
+
+        if !pending_authority_info.is_signer {
+            return Err(ShihonError::BcTokenPendingAuthorityMustSign.into());
+        }
+
+        Ok(())
+    }
+
+    /// Sets the new authority, None freezes the bcToken for good
+    /// The delegate and the pending authority were chosen by the previous authority and are dropped
+    pub fn set_authority(&mut self, new_authority: Option<Pubkey>) {
+        self.authority = new_authority;
+        self.delegate = None;
+        self.pending_authority = None;
+    }
+
+    /// Asserts the bcToken isn't taking part in a Tanistry and can be discarded
+    pub fn assert_can_discard(&self) -> Result<(), ProgramError> {
+        if self.is_in_active_tanistry() {
+            return Err(ShihonError::BcTokenCannotBeDiscarded.into());
         }
 
         Ok(())
     }
 
-    pub fn assert_can_be_kicker() {
-        unimplemented!();
+    /// Checks the bcToken can kick the KickerCoin to a coordinator
+    pub fn assert_can_be_kicker(&self) -> Result<(), ProgramError> {
+        self.assert_can_enter_tanistry()
     }
 
-    pub fn assert_can_candidate() {
-        unimplemented!();
+    /// Checks the bcToken can stand as a candidate in a Tanistry
+    pub fn assert_can_candidate(&self) -> Result<(), ProgramError> {
+        self.assert_can_enter_tanistry()
+    }
+
+    /// Only a bcToken holding its content and not taking part in any Tanistry can enter a new one
+    fn assert_can_enter_tanistry(&self) -> Result<(), ProgramError> {
+        if self.bc_token_state != BcTokenState::HoldingOnPrivate
+            && self.bc_token_state != BcTokenState::PublicOnTheGround
+        {
+            return Err(ShihonError::InvalidBcTokenState.into());
+        }
+
+        Ok(())
     }
 
     // which state of bcToken now?
@@ -128,10 +239,23 @@ pub fn assert_is_valid_bc_token(
 }
 
 /// Deserializes account and checks owner program
+/// V1 and V2 accounts are translated into the current BcToken
 pub fn get_bc_token_data(
     program_id: &Pubkey,
     bc_token_info: &AccountInfo,
 ) -> Result<BcToken, ProgramError> {
+    let account_type: ShihonAccountType = try_from_slice_unchecked(&bc_token_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::BcTokenV1 {
+        let bc_token_data_v1 = get_account_data::<BcTokenV1>(program_id, bc_token_info)?;
+        return Ok(bc_token_data_v1.into());
+    }
+
+    if account_type == ShihonAccountType::BcTokenV2 {
+        let bc_token_data_v2 = get_account_data::<BcTokenV2>(program_id, bc_token_info)?;
+        return Ok(bc_token_data_v2.into());
+    }
+
     get_account_data::<BcToken>(program_id, bc_token_info)
 }
 
@@ -141,10 +265,10 @@ pub fn get_bc_token_data_for_authority(
     bc_token_info: &AccountInfo,
     bc_token_authority: &Pubkey,
 ) -> Result<BcToken, ProgramError> {
-    let bc_token_data = get_account_data::<BcToken>(program_id, bc_token_info)?;
+    let bc_token_data = get_bc_token_data(program_id, bc_token_info)?;
 
     if bc_token_data.authority.is_none() {
-        return Err(ShihonError::RealmHasNoAuthority.into());
+        return Err(ShihonError::BcTokenHasNoAuthority.into());
     }
 
     if bc_token_data.authority.unwrap() != *bc_token_authority {
@@ -167,37 +291,121 @@ pub fn get_bc_token_data_for_bc_token_mint(
     Ok(bc_token_data)
 }
 
-/// Returns bcToken PDA seeds
-pub fn get_bc_token_address_seeds(name: &str) -> [&[u8]; 2] {
-    [PROGRAM_AUTHORITY_SEED, name.as_bytes()]
-}
+/// Deserializes bcToken account and validates its PDA
+pub fn get_bc_token_data_for_seeds(
+    program_id: &Pubkey,
+    bc_token_info: &AccountInfo,
+    name: &str,
+) -> Result<BcToken, ProgramError> {
+    let bc_token_data = get_bc_token_data(program_id, bc_token_info)?;
 
-/// Returns bcToken PDA address
-pub fn get_bc_token_address(program_id: &Pubkey, name: &str) -> Pubkey {
-    Pubkey::find_program_address(&get_bc_token_address_seeds(name), program_id).0
-}
+    if !is_valid_pda(
+        program_id,
+        bc_token_info.key,
+        &get_bc_token_address_seeds(name),
+        bc_token_data.get_bump(program_id),
+    ) {
+        return Err(ShihonError::InvalidBcTokenAddress.into());
+    }
 
-/// Returns bcToken Holding PDA seeds
-pub fn get_bc_token_holding_address_seeds<'a>(
-    bc_token: &'a Pubkey,
-    bc_token_mint: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    [
-        PROGRAM_AUTHORITY_SEED,
-        bc_token.as_ref(),
-        bc_token_mint.as_ref(),
-    ]
+    Ok(bc_token_data)
 }
 
-/// Returns bcToken Holding PDA address
-pub fn get_bc_token_holding_address(
-    program_id: &Pubkey,
-    bc_token: &Pubkey,
-    bc_token_mint: &Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_bc_token_holding_address_seeds(bc_token, bc_token_mint),
-        program_id,
-    )
-    .0
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::bc_token_metadata::{ContentLineage, ContentType};
+
+    fn create_test_bc_token() -> BcToken {
+        BcToken {
+            account_type: ShihonAccountType::BcTokenV3,
+            bump: 255,
+            bc_token_owner_pubkey: Pubkey::new_unique(),
+            amount_of_coin: 10,
+            issue_at: 1_650_000_000,
+            reserved: [0; 8],
+            authority: Some(Pubkey::new_unique()),
+            delegate: Some(Pubkey::new_unique()),
+            pending_authority: Some(Pubkey::new_unique()),
+            name: "Coke and Mentos".to_string(),
+            bc_token_mint: Pubkey::new_unique(),
+            bc_token_state: BcTokenState::HoldingOnPrivate,
+            config: BcTokenMetadata {
+                account_type: ShihonAccountType::BcTokenMetadataV3,
+                bump: 255,
+                content_type: ContentType::Ordinary,
+                updated_at: 100,
+                version: "1".to_string(),
+                reserved: [0; 64],
+                is_writable: false,
+                is_signer: false,
+                content_length: 1_000,
+                content_duration: 60,
+                lineage: Some(ContentLineage {
+                    mix_content_record: Pubkey::new_unique(),
+                    parent_bc_token_a: Pubkey::new_unique(),
+                    parent_bc_token_b: Pubkey::new_unique(),
+                    time_shift_a: 10,
+                    time_shift_b: 20,
+                }),
+            },
+        }
+    }
+
+    #[test]
+    fn test_max_size_with_lineage() {
+        // Arrange
+        let bc_token = create_test_bc_token();
+
+        // Act
+        let size = bc_token.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(bc_token.get_max_size(), Some(size));
+        assert_eq!(
+            bc_token.config.get_max_size(),
+            Some(bc_token.config.try_to_vec().unwrap().len())
+        );
+    }
+
+    #[test]
+    fn test_assert_can_set_authority_in_active_tanistry_error() {
+        // Arrange
+        let mut bc_token = create_test_bc_token();
+        bc_token.bc_token_state = BcTokenState::TanistryHasSetIn;
+
+        // Act
+        let err = bc_token.assert_can_set_authority().err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::BcTokenInActiveTanistry.into());
+    }
+
+    #[test]
+    fn test_assert_can_set_authority_after_refund() {
+        // Arrange
+        let mut bc_token = create_test_bc_token();
+        bc_token.bc_token_state = BcTokenState::EnableToRefund;
+
+        // Act
+        let result = bc_token.assert_can_set_authority();
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_set_authority_drops_delegate_and_pending_authority() {
+        // Arrange
+        let mut bc_token = create_test_bc_token();
+        let new_authority = Pubkey::new_unique();
+
+        // Act
+        bc_token.set_authority(Some(new_authority));
+
+        // Assert
+        assert_eq!(bc_token.authority, Some(new_authority));
+        assert_eq!(bc_token.delegate, None);
+        assert_eq!(bc_token.pending_authority, None);
+    }
 }
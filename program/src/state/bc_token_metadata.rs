@@ -1,13 +1,21 @@
 //! bcToken Metadata Account
 
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, clock::Slot, program_error::ProgramError,
-    program_pack::IsInitialized, pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, clock::Slot,
+    program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{get_account_data, AccountMaxSize};
 
-use crate::state::enums::ShihonAccountType;
+use crate::{
+    error::ShihonError,
+    pda::{find_bump_seed, get_bc_token_metadata_address_seeds, is_valid_pda},
+    state::{
+        enums::ShihonAccountType,
+        legacy::{BcTokenMetadataV1, BcTokenMetadataV2},
+    },
+};
 
 /// bcToken metadata account
 #[repr(C)]
@@ -16,6 +24,9 @@ pub struct BcTokenMetadata {
     /// bcToken account type
     pub account_type: ShihonAccountType,
 
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
     /// what content type
     pub content_type: ContentType,
 
@@ -33,6 +44,37 @@ pub struct BcTokenMetadata {
 
     /// True if an bcToken requires a Transaction signature matching `pubkey`.
     pub is_signer: bool,
+
+    /// The recorded length of the content in bytes
+    pub content_length: u64,
+
+    /// The recorded duration of the content in seconds, 0 for a content without timeline
+    pub content_duration: u64,
+
+    /// The mix the content was promoted from
+    /// None for the original contents published by their creators
+    pub lineage: Option<ContentLineage>,
+}
+
+/// The provenance of a mixed content promoted into a bcToken
+/// Provenance queries walk back parent_bc_token_a and parent_bc_token_b until the originals without lineage
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ContentLineage {
+    /// The MixContentRecord the content was promoted from
+    pub mix_content_record: Pubkey,
+
+    /// The init content's bcToken (first kicker's content)
+    pub parent_bc_token_a: Pubkey,
+
+    /// The buddy content's bcToken
+    pub parent_bc_token_b: Pubkey,
+
+    /// The time shift applied to the init content
+    pub time_shift_a: u64,
+
+    /// The time shift applied to the buddy content
+    pub time_shift_b: u64,
 }
 
 /// The content type of bcToken
@@ -49,25 +91,126 @@ pub enum ContentType {
     Other,
 }
 
+impl ContentType {
+    /// Checks whether the content can be mixed with the content of the other type
+    /// Ordinary and Partially contents mix with each other while Other contents only mix with Other contents
+    pub fn is_compatible_with(&self, other: &ContentType) -> bool {
+        matches!(
+            (self, other),
+            (
+                ContentType::Ordinary | ContentType::Partially,
+                ContentType::Ordinary | ContentType::Partially
+            ) | (ContentType::Other, ContentType::Other)
+        )
+    }
+}
+
+/// The part of a Partially content taken into the mix
+/// The range is measured in bytes from the start of the content
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ContentRange {
+    /// The start of the range (inclusive)
+    pub start: u64,
+
+    /// The end of the range (exclusive)
+    pub end: u64,
+}
+
 impl AccountMaxSize for BcTokenMetadata {
     fn get_max_size(&self) -> Option<usize> {
-        Some(88)
+        Some(self.version.len() + 210)
     }
 }
 
 impl IsInitialized for BcTokenMetadata {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::BcTokenMetadata
+        self.account_type == ShihonAccountType::BcTokenMetadataV3
     }
 }
 
 impl BcTokenMetadata {
+    /// Serializes the account into the layout of its version
+    /// V1 and V2 accounts are written back in their own layout until they are migrated
+    pub fn serialize<W: Write>(self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::BcTokenMetadataV1 {
+            let bc_token_metadata_data_v1: BcTokenMetadataV1 = self.into();
+            BorshSerialize::serialize(&bc_token_metadata_data_v1, writer)?;
+        } else if self.account_type == ShihonAccountType::BcTokenMetadataV2 {
+            let bc_token_metadata_data_v2: BcTokenMetadataV2 = self.into();
+            BorshSerialize::serialize(&bc_token_metadata_data_v2, writer)?;
+        } else {
+            BorshSerialize::serialize(&self, writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the PDA bump of the bcToken Metadata
+    /// V1 accounts don't store the bump and it's derived from the seeds
+    pub fn get_bump(&self, program_id: &Pubkey, bc_token: &Pubkey) -> u8 {
+        if self.account_type == ShihonAccountType::BcTokenMetadataV1 {
+            find_bump_seed(program_id, &get_bc_token_metadata_address_seeds(bc_token))
+        } else {
+            self.bump
+        }
+    }
+
+    /// Returns the parent bcTokens of the content, none for an original content
+    pub fn get_parent_bc_tokens(&self) -> Vec<Pubkey> {
+        self.lineage
+            .as_ref()
+            .map(|lineage| vec![lineage.parent_bc_token_a, lineage.parent_bc_token_b])
+            .unwrap_or_default()
+    }
+
     pub fn assert_can_poking_content() {
         unimplemented!();
     }
 
-    pub fn assert_is_valid_content_type_matched() {
-        unimplemented!();
+    /// Asserts the content can be mixed with the buddy content
+    pub fn assert_is_valid_content_type_matched(
+        &self,
+        buddy_content: &BcTokenMetadata,
+    ) -> Result<(), ProgramError> {
+        if !self
+            .content_type
+            .is_compatible_with(&buddy_content.content_type)
+        {
+            return Err(ShihonError::IncompatibleContentTypesForMixing.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the mix range is given for a Partially content only and lies within the recorded length
+    pub fn assert_is_valid_mix_range(
+        &self,
+        range: &Option<ContentRange>,
+    ) -> Result<(), ProgramError> {
+        match (&self.content_type, range) {
+            (ContentType::Partially, None) => {
+                Err(ShihonError::MixRangeRequiredForPartialContent.into())
+            }
+            (ContentType::Partially, Some(range)) => {
+                if range.start >= range.end || range.end > self.content_length {
+                    return Err(ShihonError::InvalidMixRange.into());
+                }
+
+                Ok(())
+            }
+            (_, Some(_)) => Err(ShihonError::MixRangeOnlyForPartialContent.into()),
+            (_, None) => Ok(()),
+        }
+    }
+
+    /// Asserts the time shift doesn't exceed the recorded duration of the content
+    pub fn assert_is_valid_time_shift(&self, time_shift: u64) -> Result<(), ProgramError> {
+        if time_shift > self.content_duration {
+            return Err(ShihonError::TimeShiftExceedsContentDuration.into());
+        }
+
+        Ok(())
     }
 
     pub fn assert_is_valid_received_link_from_oracle() {
@@ -95,20 +238,201 @@ impl BcTokenMetadata {
     }
 }
 
-/// Returns bcToken Metadata PDA address
-pub fn get_bc_token_metadata_address(program_id: &Pubkey) -> Pubkey {
-    Pubkey::find_program_address(&get_bc_token_metadata_seeds(), program_id).0
-}
+/// Asserts the init content and the buddy content can be mixed with the given time shifts and ranges
+pub fn assert_can_mix_contents(
+    init_content: &BcTokenMetadata,
+    buddy_content: &BcTokenMetadata,
+    time_shift_a: u64,
+    time_shift_b: u64,
+    range_a: &Option<ContentRange>,
+    range_b: &Option<ContentRange>,
+) -> Result<(), ProgramError> {
+    init_content.assert_is_valid_content_type_matched(buddy_content)?;
 
-/// Returns bcToken Metadata PDA seeds
-pub fn get_bc_token_metadata_seeds<'a>() -> [&'a [u8]; 1] {
-    [b"metadata"]
+    init_content.assert_is_valid_mix_range(range_a)?;
+    buddy_content.assert_is_valid_mix_range(range_b)?;
+
+    init_content.assert_is_valid_time_shift(time_shift_a)?;
+    buddy_content.assert_is_valid_time_shift(time_shift_b)?;
+
+    Ok(())
 }
 
 /// Deserializes account and checks owner bcToken
+/// V1 and V2 accounts are translated into the current BcTokenMetadata
 pub fn get_bc_token_metadata_data(
     program_id: &Pubkey,
     bc_token_metadata_info: &AccountInfo,
 ) -> Result<BcTokenMetadata, ProgramError> {
+    let account_type: ShihonAccountType =
+        try_from_slice_unchecked(&bc_token_metadata_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::BcTokenMetadataV1 {
+        let bc_token_metadata_data_v1 =
+            get_account_data::<BcTokenMetadataV1>(program_id, bc_token_metadata_info)?;
+        return Ok(bc_token_metadata_data_v1.into());
+    }
+
+    if account_type == ShihonAccountType::BcTokenMetadataV2 {
+        let bc_token_metadata_data_v2 =
+            get_account_data::<BcTokenMetadataV2>(program_id, bc_token_metadata_info)?;
+        return Ok(bc_token_metadata_data_v2.into());
+    }
+
     get_account_data::<BcTokenMetadata>(program_id, bc_token_metadata_info)
 }
+
+/// Deserializes bcToken Metadata account and validates its PDA
+pub fn get_bc_token_metadata_data_for_seeds(
+    program_id: &Pubkey,
+    bc_token_metadata_info: &AccountInfo,
+    bc_token: &Pubkey,
+) -> Result<BcTokenMetadata, ProgramError> {
+    let bc_token_metadata_data = get_bc_token_metadata_data(program_id, bc_token_metadata_info)?;
+
+    if !is_valid_pda(
+        program_id,
+        bc_token_metadata_info.key,
+        &get_bc_token_metadata_address_seeds(bc_token),
+        bc_token_metadata_data.get_bump(program_id, bc_token),
+    ) {
+        return Err(ShihonError::InvalidBcTokenMetadataAddress.into());
+    }
+
+    Ok(bc_token_metadata_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_bc_token_metadata(content_type: ContentType) -> BcTokenMetadata {
+        BcTokenMetadata {
+            account_type: ShihonAccountType::BcTokenMetadataV3,
+            bump: 255,
+            content_type,
+            updated_at: 100,
+            version: "1".to_string(),
+            reserved: [0; 64],
+            is_writable: false,
+            is_signer: false,
+            content_length: 1_000,
+            content_duration: 60,
+            lineage: None,
+        }
+    }
+
+    #[test]
+    fn test_assert_can_mix_contents() {
+        // Arrange
+        let init_content = create_test_bc_token_metadata(ContentType::Ordinary);
+        let buddy_content = create_test_bc_token_metadata(ContentType::Partially);
+        let range_b = Some(ContentRange {
+            start: 0,
+            end: 1_000,
+        });
+
+        // Act
+        let result = assert_can_mix_contents(&init_content, &buddy_content, 0, 60, &None, &range_b);
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_can_mix_contents_with_incompatible_types_error() {
+        // Arrange
+        let init_content = create_test_bc_token_metadata(ContentType::Ordinary);
+        let buddy_content = create_test_bc_token_metadata(ContentType::Other);
+
+        // Act
+        let err = assert_can_mix_contents(&init_content, &buddy_content, 0, 0, &None, &None)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::IncompatibleContentTypesForMixing.into());
+    }
+
+    #[test]
+    fn test_assert_can_mix_contents_without_partial_range_error() {
+        // Arrange
+        let init_content = create_test_bc_token_metadata(ContentType::Partially);
+        let buddy_content = create_test_bc_token_metadata(ContentType::Ordinary);
+
+        // Act
+        let err = assert_can_mix_contents(&init_content, &buddy_content, 0, 0, &None, &None)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::MixRangeRequiredForPartialContent.into());
+    }
+
+    #[test]
+    fn test_assert_can_mix_contents_with_range_for_ordinary_content_error() {
+        // Arrange
+        let init_content = create_test_bc_token_metadata(ContentType::Ordinary);
+        let buddy_content = create_test_bc_token_metadata(ContentType::Ordinary);
+        let range_b = Some(ContentRange { start: 0, end: 10 });
+
+        // Act
+        let err = assert_can_mix_contents(&init_content, &buddy_content, 0, 0, &None, &range_b)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::MixRangeOnlyForPartialContent.into());
+    }
+
+    #[test]
+    fn test_assert_can_mix_contents_with_range_beyond_content_length_error() {
+        // Arrange
+        let init_content = create_test_bc_token_metadata(ContentType::Partially);
+        let buddy_content = create_test_bc_token_metadata(ContentType::Partially);
+        let range_a = Some(ContentRange { start: 0, end: 10 });
+        let range_b = Some(ContentRange {
+            start: 500,
+            end: 1_001,
+        });
+
+        // Act
+        let err = assert_can_mix_contents(&init_content, &buddy_content, 0, 0, &range_a, &range_b)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidMixRange.into());
+    }
+
+    #[test]
+    fn test_assert_can_mix_contents_with_empty_range_error() {
+        // Arrange
+        let init_content = create_test_bc_token_metadata(ContentType::Partially);
+        let buddy_content = create_test_bc_token_metadata(ContentType::Ordinary);
+        let range_a = Some(ContentRange { start: 10, end: 10 });
+
+        // Act
+        let err = assert_can_mix_contents(&init_content, &buddy_content, 0, 0, &range_a, &None)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidMixRange.into());
+    }
+
+    #[test]
+    fn test_assert_can_mix_contents_with_time_shift_beyond_duration_error() {
+        // Arrange
+        let init_content = create_test_bc_token_metadata(ContentType::Ordinary);
+        let buddy_content = create_test_bc_token_metadata(ContentType::Ordinary);
+
+        // Act
+        let err = assert_can_mix_contents(&init_content, &buddy_content, 61, 0, &None, &None)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::TimeShiftExceedsContentDuration.into());
+    }
+}
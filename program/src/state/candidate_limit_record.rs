@@ -1,25 +1,30 @@
 //! Candidate Limit Record Account
 
-use std::slice::Iter;
-
-use crate::{error::ShihonError, state::enums::ShihonAccountType, PROGRAM_AUTHORITY_SEED};
+use crate::{
+    error::ShihonError,
+    pda::{find_bump_seed, get_candidate_limit_record_address_seeds, is_valid_pda},
+    state::enums::ShihonAccountType,
+};
 
+use crate::state::legacy::CandidateLimitRecordV1;
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::{next_account_info, AccountInfo},
-    program_error::ProgramError,
-    program_pack::IsInitialized,
-    pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{get_account_data, AccountMaxSize};
 
-/// Account PDA seeds: ['shihon', bcToken, token_mint, token_owner ]
+/// Account PDA seeds: ['candidate-limit-record', tanistry, candidate_token_mint, candidate_token_owner]
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct CandidateLimitRecord {
     /// Governance account type
     pub account_type: ShihonAccountType,
 
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
     /// The Tanistry the CandidateLimitRecord belongs to
     pub belonging_tanistry: Pubkey,
 
@@ -34,25 +39,59 @@ pub struct CandidateLimitRecord {
     /// This is who can authorize a withdrawal of the tokens
     pub candidate_token_owner: Pubkey,
 
+    /// The delegate the owner allowed to mix, rate and bump self-rate on its behalf
+    /// The delegate can never withdraw the deposit or claim the refund
+    pub candidate_delegate: Option<Pubkey>,
+
     /// The amount of candidate tokens deposited into the Tanistry
     /// How much pay did candidate as self-rating
     pub candidate_token_deposit_amount: u64,
+
+    /// The total rating the candidate's content received from the other candidates
+    pub received_rating: u64,
+
+    /// The amount the candidate can claim from the Tanistry vault once it's settled
+    pub refund_amount: u64,
+
+    /// Indicates whether the candidate claimed the refund
+    pub is_refunded: bool,
+
+    /// The protocol fee taken from the candidate's deposit into the treasury
+    pub candidate_fee_amount: u64,
+
+    /// The number of RateOptions the candidate issued as a Roydamna
+    pub rate_options_count: u8,
+
+    /// The bcToken the candidate joined the Tanistry with
+    pub bc_token: Pubkey,
 }
 
 impl AccountMaxSize for CandidateLimitRecord {
     fn get_max_size(&self) -> Option<usize> {
-        Some(154)
+        Some(219)
     }
 }
 
 impl IsInitialized for CandidateLimitRecord {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::CandidateLimitRecord
+        self.account_type == ShihonAccountType::CandidateLimitRecordV2
     }
 }
 
 impl CandidateLimitRecord {
-    /// Checks whether the provided bcToken Authority signed transaction
+    /// Serializes the account into the current layout
+    /// V1 accounts have no room for the current fields and have to be migrated before they are updated
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::CandidateLimitRecordV1 {
+            return Err(ShihonError::CandidateLimitRecordMustBeMigrated.into());
+        }
+
+        BorshSerialize::serialize(self, writer)?;
+
+        Ok(())
+    }
+
+    /// Checks whether the candidate token owner or its delegate signed transaction
     pub fn assert_token_owner_or_delegate_is_signer(
         &self,
         candidate_token_authority_info: &AccountInfo,
@@ -60,45 +99,29 @@ impl CandidateLimitRecord {
         if candidate_token_authority_info.is_signer {
             if &self.candidate_token_owner == candidate_token_authority_info.key {
                 return Ok(());
-            };
+            }
+
+            if self.candidate_delegate == Some(*candidate_token_authority_info.key) {
+                return Ok(());
+            }
         }
 
-        Err(ShihonError::CandidateTokenOwnerMustSign.into())
+        Err(ShihonError::CandidateTokenOwnerOrDelegateMustSign.into())
     }
 
-    /// TODO: we need to write the Limit Bar's code here.(mean that can candidate?)
-    /// Asserts TokenOwner has enough tokens to be allowed to candidate
-    pub fn assert_can_create_candidate_limit_record(
+    /// Checks whether the candidate token owner signed transaction
+    /// The delegate can't sign on behalf of the owner
+    pub fn assert_token_owner_is_signer(
         &self,
-        realm_data: &Realm,
-        config: &GovernanceConfig,
-        voter_weight: u64,
+        candidate_token_owner_info: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        let min_weight_to_create_proposal =
-            if self.governing_token_mint == realm_data.community_mint {
-                config.min_community_tokens_to_create_proposal
-            } else if Some(self.governing_token_mint) == realm_data.config.council_mint {
-                config.min_council_tokens_to_create_proposal
-            } else {
-                return Err(ShihonError::InvalidGoverningTokenMint.into());
-            };
-
-        if voter_weight < min_weight_to_create_proposal {
-            return Err(ShihonError::NotEnoughTokensToCreateProposal.into());
+        if candidate_token_owner_info.is_signer
+            && &self.candidate_token_owner == candidate_token_owner_info.key
+        {
+            return Ok(());
         }
 
-        // The number of outstanding proposals is currently restricted to 10
-        // If there is a need to change it in the future then it should be added to realm or governance config
-        if self.outstanding_proposal_count >= 10 {
-            return Err(ShihonError::TooManyOutstandingProposals.into());
-        }
-
-        Ok(())
-    }
-
-    /// this function use within above function
-    fn get_candidate_limit_bar() -> bool {
-        unimplemented!();
+        Err(ShihonError::CandidateTokenOwnerMustSign.into())
     }
 
     /// can create mpc hash on tanistry
@@ -106,68 +129,33 @@ impl CandidateLimitRecord {
         unimplemented!();
     }
 
-    //TODO: need to fix here.
-    /// Asserts TokenOwner has enough tokens to be allowed to create governance
-    pub fn assert_can_create_governance(
-        &self,
-        realm_data: &Realm,
-        voter_weight: u64,
-    ) -> Result<(), ProgramError> {
-        let min_weight_to_create_governance =
-            if self.governing_token_mint == realm_data.community_mint {
-                realm_data.config.min_community_tokens_to_create_governance
-            } else if Some(self.governing_token_mint) == realm_data.config.council_mint {
-                // For council tokens it's enough to be in possession of any number of tokens
-                1
-            } else {
-                return Err(ShihonError::InvalidGoverningTokenMint.into());
-            };
-
-        if voter_weight < min_weight_to_create_governance {
-            return Err(ShihonError::NotEnoughTokensToCreateGovernance.into());
+    /// Checks the refund hasn't been claimed yet
+    pub fn assert_can_claim_refund(&self) -> Result<(), ProgramError> {
+        if self.is_refunded {
+            return Err(ShihonError::RefundAlreadyClaimed.into());
         }
 
         Ok(())
     }
 
-    /// Increases number_of_candidate_count
-    pub fn increase_number_of_candidate_count(&self) {
-        if self.number_of_candidate_count != 0 {
-            self.number_of_candidate_count = self.number_of_candidate_count.checked_sub(1).unwrap();
+    /// Checks the deposit backing the CC vote hasn't been refunded
+    pub fn assert_can_cc_vote(&self) -> Result<(), ProgramError> {
+        if self.is_refunded {
+            return Err(ShihonError::CCVoteFromRefundedCandidate.into());
         }
+
+        Ok(())
     }
-}
 
-/// Returns CandidateLimitRecord PDA address
-pub fn get_candidate_limit_record_address(
-    program_id: &Pubkey,
-    tanistry: &Pubkey,
-    candidate_token_mint: &Pubkey,
-    candidate_token_owner: &Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_candidate_limit_record_address_seeds(
-            tanistry,
-            candidate_token_mint,
-            candidate_token_owner,
-        ),
-        program_id,
-    )
-    .0
-}
+    /// Counts the RateOption issued by the candidate and returns its issue number
+    pub fn issue_rate_option(&mut self) -> Result<u8, ProgramError> {
+        self.rate_options_count = self
+            .rate_options_count
+            .checked_add(1)
+            .ok_or(ShihonError::TooManyRateOptionsIssued)?;
 
-/// Returns CandidateLimitRecord PDA seeds
-pub fn get_candidate_limit_record_address_seeds<'a>(
-    tanistry: &'a Pubkey,
-    candidate_token_mint: &'a Pubkey,
-    candidate_token_owner: &'a Pubkey,
-) -> [&'a [u8]; 4] {
-    [
-        PROGRAM_AUTHORITY_SEED,
-        tanistry.as_ref(),
-        candidate_token_mint.as_ref(),
-        candidate_token_owner.as_ref(),
-    ]
+        Ok(self.rate_options_count)
+    }
 }
 
 /// Deserializes CandidateLimitRecord account and checks owner program
@@ -175,23 +163,53 @@ pub fn get_candidate_limit_record_data(
     program_id: &Pubkey,
     candidate_limit_record_info: &AccountInfo,
 ) -> Result<CandidateLimitRecord, ProgramError> {
+    let account_type: ShihonAccountType =
+        try_from_slice_unchecked(&candidate_limit_record_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::CandidateLimitRecordV1 {
+        let candidate_limit_record_data_v1 =
+            get_account_data::<CandidateLimitRecordV1>(program_id, candidate_limit_record_info)?;
+        return Ok(candidate_limit_record_data_v1.into());
+    }
+
     get_account_data::<CandidateLimitRecord>(program_id, candidate_limit_record_info)
 }
 
-/// Deserializes CandidateLimitRecord account and checks its PDA against the provided seeds
+/// Deserializes CandidateLimitRecord account and validates its PDA
 pub fn get_candidate_limit_record_data_for_seeds(
     program_id: &Pubkey,
     candidate_limit_record_info: &AccountInfo,
-    candidate_limit_record_seeds: &[&[u8]],
+    tanistry: &Pubkey,
+    candidate_token_mint: &Pubkey,
+    candidate_token_owner: &Pubkey,
 ) -> Result<CandidateLimitRecord, ProgramError> {
-    let (candidate_limit_record_address, _) =
-        Pubkey::find_program_address(candidate_limit_record_seeds, program_id);
+    let candidate_limit_record_data =
+        get_candidate_limit_record_data(program_id, candidate_limit_record_info)?;
 
-    if candidate_limit_record_address != *candidate_limit_record_info.key {
-        return Err(ShihonError::InvalidTokenOwnerRecordAccountAddress.into());
+    let candidate_limit_record_address_seeds = get_candidate_limit_record_address_seeds(
+        tanistry,
+        candidate_token_mint,
+        candidate_token_owner,
+    );
+
+    // V1 accounts don't store the bump and it's derived from the seeds
+    let bump =
+        if candidate_limit_record_data.account_type == ShihonAccountType::CandidateLimitRecordV1 {
+            find_bump_seed(program_id, &candidate_limit_record_address_seeds)
+        } else {
+            candidate_limit_record_data.bump
+        };
+
+    if !is_valid_pda(
+        program_id,
+        candidate_limit_record_info.key,
+        &candidate_limit_record_address_seeds,
+        bump,
+    ) {
+        return Err(ShihonError::InvalidCandidateLimitRecordAddress.into());
     }
 
-    get_candidate_limit_record_data(program_id, candidate_limit_record_info)
+    Ok(candidate_limit_record_data)
 }
 
 /// Deserializes CandidateLimitRecord account and asserts it belongs to the given Tanistry
@@ -204,7 +222,7 @@ pub fn get_candidate_limit_record_data_for_tanistry(
         get_candidate_limit_record_data(program_id, candidate_limit_record_info)?;
 
     if candidate_limit_record_data.belonging_tanistry != *tanistry {
-        return Err(ShihonError::InvalidRealmForTokenOwnerRecord.into());
+        return Err(ShihonError::InvalidTanistryForCandidateLimitRecord.into());
     }
 
     Ok(candidate_limit_record_data)
@@ -224,7 +242,7 @@ pub fn get_candidate_limit_record_data_for_tanistry_and_candidate_mint(
     )?;
 
     if candidate_limit_record_data.candidate_token_mint != *candidate_token_mint {
-        return Err(ShihonError::InvalidGoverningMintForTokenOwnerRecord.into());
+        return Err(ShihonError::InvalidCandidateMintForCandidateLimitRecord.into());
     }
 
     Ok(candidate_limit_record_data)
@@ -237,7 +255,7 @@ pub fn get_candidate_limit_record_data_for_rater(
     rater: &Pubkey,
 ) -> Result<CandidateLimitRecord, ProgramError> {
     if candidate_limit_record_info.key != rater {
-        return Err(ShihonError::InvalidProposalOwnerAccount.into());
+        return Err(ShihonError::InvalidRaterForCandidateLimitRecord.into());
     }
 
     get_candidate_limit_record_data(program_id, candidate_limit_record_info)
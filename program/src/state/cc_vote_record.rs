@@ -1,25 +1,27 @@
-//! Proposal Vote Record Account
+//! CC Vote Record Account
 
 use borsh::maybestd::io::Write;
-
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
+use crate::state::legacy::CCVoteRecordV1;
 use solana_program::{
-    account_info::AccountInfo,
-    borsh::try_from_slice_unchecked,
-    clock::{Slot, UnixTimestamp},
-    instruction::{AccountMeta, Instruction},
-    program_error::ProgramError,
-    program_pack::IsInitialized,
-    pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, clock::Slot,
+    program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{get_account_data, AccountMaxSize};
 
 use crate::error::ShihonError;
 
-use crate::PROGRAM_AUTHORITY_SEED;
+use crate::pda::{find_bump_seed, get_cc_vote_record_address_seeds, is_valid_pda};
+
+use crate::state::{
+    enums::{ShihonAccountType, VoteSource},
+    ring::Ring,
+};
 
-use crate::state::enums::{ShihonAccountType, VoteSource};
+/// Number of slots the CC votes of a ring stay open from the first vote before the system counts them
+/// Until then the voters can relinquish or change their votes
+pub const CC_VOTE_COUNTING_PERIOD: Slot = 432_000;
 
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct CCVoteChoice {
@@ -40,18 +42,22 @@ pub enum Vote {
     Push,
 }
 
-/// CC Vote Record
+/// CC Vote Record PDA seeds: ['cc-vote-record', candidate_limit_record]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct CCVoteRecord {
     /// account type
     pub account_type: ShihonAccountType,
 
-    /// The user who casted this vote
-    /// This is the Governing Token Owner who deposited governing tokens into the Realm
-    pub governing_token_owner: Pubkey,
+    /// Bump seed of the account PDA
+    pub bump: u8,
 
-    /// when system counted the whole voting
-    pub counting_time: Slot,
+    /// The ring the vote was casted for
+    /// ChangeCCVote redirects the same record to another ring
+    pub target_ring: Pubkey,
+
+    /// The user who casted this vote
+    /// This is the owner of the CandidateLimitRecord the voter_weight is taken from
+    pub voter: Pubkey,
 
     /// Indicates whether the vote was relinquished by voter
     pub is_relinquished: bool,
@@ -67,20 +73,36 @@ impl AccountMaxSize for CCVoteRecord {}
 
 impl IsInitialized for CCVoteRecord {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::CCVoteRecord
+        self.account_type == ShihonAccountType::CCVoteRecordV2
     }
 }
 
 impl CCVoteRecord {
-    /// Checks can vating for other ring
-    pub fn assert_can_vote(&self) -> Result<(), ProgramError> {
-        if self.is_relinquished {
-            return Err(ShihonError::VoteAlreadyRelinquished.into());
+    /// Serializes the account into the current layout
+    /// V1 accounts have no room for the current fields and have to be migrated before they are updated
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::CCVoteRecordV1 {
+            return Err(ShihonError::CCVoteRecordMustBeMigrated.into());
         }
 
+        BorshSerialize::serialize(self, writer)?;
+
         Ok(())
     }
 
+    /// Checks the vote is still active and the counting time of the target ring hasn't come yet
+    pub fn assert_can_vote(
+        &self,
+        target_ring: &Ring,
+        current_slot: Slot,
+    ) -> Result<(), ProgramError> {
+        if self.is_relinquished {
+            return Err(ShihonError::CCVoteAlreadyRelinquished.into());
+        }
+
+        target_ring.assert_cc_vote_is_open(current_slot)
+    }
+
     pub fn assert_can_reach_to_cc() {
         unimplemented!();
     }
@@ -89,111 +111,65 @@ impl CCVoteRecord {
         unimplemented!();
         // see the limit bar
     }
-
-    ///TODO: What function should be created to count here?
-    /// Serializes account into the target buffer
-    pub fn serialize<W: Write>(self, writer: &mut W) -> Result<(), ProgramError> {
-        if self.account_type == ShihonAccountType::CCVoteRecord {
-            BorshSerialize::serialize(&self, writer)?
-        } else if self.account_type == ShihonAccountType::VoteRecordV1 {
-            // V1 account can't be resized and we have to translate it back to the original format
-            let vote_weight = match &self.vote {
-                Vote::Pull(_options) => VoteWeightV1::Yes(self.voter_weight),
-                Vote::Push => VoteWeightV1::No(self.voter_weight),
-            };
-
-            let vote_record_data_v1 = VoteRecordV1 {
-                account_type: self.account_type,
-                proposal: self.proposal,
-                governing_token_owner: self.governing_token_owner,
-                is_relinquished: self.is_relinquished,
-                vote_weight,
-            };
-
-            BorshSerialize::serialize(&vote_record_data_v1, writer)?;
-        }
-
-        Ok(())
-    }
 }
 
 /// Deserializes CCVoteRecord account data
 pub fn get_cc_vote_record_data(
     program_id: &Pubkey,
-    vote_record_info: &AccountInfo,
+    cc_vote_record_info: &AccountInfo,
 ) -> Result<CCVoteRecord, ProgramError> {
     let account_type: ShihonAccountType =
-        try_from_slice_unchecked(&vote_record_info.data.borrow())?;
-
-    // If the account is V1 version then translate to V2
-    if account_type == ShihonAccountType::CCVoteRecord {
-        let vote_record_data_v1 = get_account_data::<CCVoteRecord>(program_id, vote_record_info)?;
-
-        let (vote, voter_weight) = match vote_record_data_v1.vote_weight {
-            VoteWeightV1::Yes(weight) => (
-                Vote::Pull(vec![CCVoteChoice {
-                    rank: 0,
-                    weight_percentage: 100,
-                }]),
-                weight,
-            ),
-            VoteWeightV1::No(weight) => (Vote::Push, weight),
-        };
-
-        return Ok(CCVoteRecord {
-            account_type,
-            proposal: vote_record_data_v1.proposal,
-            governing_token_owner: vote_record_data_v1.governing_token_owner,
-            is_relinquished: vote_record_data_v1.is_relinquished,
-            voter_weight,
-            vote,
-        });
+        try_from_slice_unchecked(&cc_vote_record_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::CCVoteRecordV1 {
+        let cc_vote_record_data_v1 =
+            get_account_data::<CCVoteRecordV1>(program_id, cc_vote_record_info)?;
+        return Ok(cc_vote_record_data_v1.into());
     }
 
-    get_account_data::<CCVoteRecord>(program_id, vote_record_info)
+    get_account_data::<CCVoteRecord>(program_id, cc_vote_record_info)
 }
 
-/// Deserializes CCVoteRecord data for each rings
-pub fn get_cc_vote_record_data_for_challenger_ring_and_targeted_ring(
+/// Deserializes CCVoteRecord data and asserts it was casted for the given target ring
+pub fn get_cc_vote_record_data_for_target_ring(
     program_id: &Pubkey,
-    vote_record_info: &AccountInfo,
-    proposal: &Pubkey,
-    governing_token_owner: &Pubkey,
+    cc_vote_record_info: &AccountInfo,
+    target_ring: &Pubkey,
 ) -> Result<CCVoteRecord, ProgramError> {
-    let vote_record_data = get_vote_record_data(program_id, vote_record_info)?;
+    let cc_vote_record_data = get_cc_vote_record_data(program_id, cc_vote_record_info)?;
 
-    if vote_record_data.proposal != *proposal {
-        return Err(ShihonError::InvalidProposalForVoterRecord.into());
+    if cc_vote_record_data.target_ring != *target_ring {
+        return Err(ShihonError::InvalidTargetRingForCCVoteRecord.into());
     }
 
-    if vote_record_data.governing_token_owner != *governing_token_owner {
-        return Err(ShihonError::InvalidGoverningTokenOwnerForVoteRecord.into());
-    }
-
-    Ok(vote_record_data)
-}
-
-/// Returns CCVoteRecord PDA seeds
-pub fn get_cc_vote_record_address_seeds<'a>(
-    target_ring: &'a Pubkey,
-    token_owner_record: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    [
-        PROGRAM_AUTHORITY_SEED,
-        target_ring.as_ref(),
-        token_owner_record.as_ref(),
-    ]
+    Ok(cc_vote_record_data)
 }
 
-/// Returns CCVoteRecord PDA address
-pub fn get_cc_vote_record_address<'a>(
+/// Deserializes CCVoteRecord account and validates its PDA
+pub fn get_cc_vote_record_data_for_seeds(
     program_id: &Pubkey,
-    target_ring: &'a Pubkey,
-    token_owner_record: &'a Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_cc_vote_record_address_seeds(target_ring, token_owner_record),
+    cc_vote_record_info: &AccountInfo,
+    candidate_limit_record: &Pubkey,
+) -> Result<CCVoteRecord, ProgramError> {
+    let cc_vote_record_data = get_cc_vote_record_data(program_id, cc_vote_record_info)?;
+
+    let cc_vote_record_address_seeds = get_cc_vote_record_address_seeds(candidate_limit_record);
+
+    // V1 accounts don't store the bump and it's derived from the seeds
+    let bump = if cc_vote_record_data.account_type == ShihonAccountType::CCVoteRecordV1 {
+        find_bump_seed(program_id, &cc_vote_record_address_seeds)
+    } else {
+        cc_vote_record_data.bump
+    };
+
+    if !is_valid_pda(
         program_id,
-    )
-    .0
+        cc_vote_record_info.key,
+        &cc_vote_record_address_seeds,
+        bump,
+    ) {
+        return Err(ShihonError::InvalidCCVoteRecordAddress.into());
+    }
+
+    Ok(cc_vote_record_data)
 }
@@ -4,53 +4,113 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
 /// Defines all Shihon accounts types
 #[repr(C)]
-#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum ShihonAccountType {
     /// Default uninitialized account state
+    #[default]
     Uninitialized,
 
-    /// bcToken
-    BcToken,
+    /// bcToken V1 layout without the PDA bump
+    BcTokenV1,
 
-    /// bcToken's inside metadata
-    BcTokenMetadata,
+    /// bcToken's inside metadata V1 layout without the PDA bump
+    BcTokenMetadataV1,
 
-    /// KickerCoin Owner Record for kicker and coordinator before forming a Tanistry
-    KickerCoinOwnerRecord,
+    /// KickerCoin Owner Record V1 layout without the PDA bump
+    KickerCoinOwnerRecordV1,
 
-    /// Tanistry
-    Tanistry,
+    /// Tanistry V1 layout without the PDA bump
+    TanistryV1,
 
-    /// for candidate limit
-    CandidateLimitRecord,
+    /// CandidateLimitRecord V1 layout without the PDA bump
+    CandidateLimitRecordV1,
 
-    /// Mix Content Record
-    MixContentRecord,
+    /// Mix Content Record V1 layout without the PDA bump
+    MixContentRecordV1,
 
-    /// Rate Option for holding some accounts and data
-    RateOption,
+    /// Rate Option V1 layout without the PDA bump
+    RateOptionV1,
 
-    /// for rating other's content
-    RateOtherRecord,
+    /// RateOtherRecord V1 layout without the PDA bump
+    RateOtherRecordV1,
 
-    /// Outside buyer account
-    OutsideBuyerRecord,
+    /// Outside buyer account V1 layout without the PDA bump
+    OutsideBuyerRecordV1,
 
-    /// for cc voting
-    CCVoteRecord,
-}
+    /// CCVoteRecord V1 layout without the PDA bump
+    CCVoteRecordV1,
+
+    /// bcToken V2
+    /// V2 stores the PDA bump of the account
+    BcTokenV2,
+
+    /// bcToken's inside metadata V2
+    /// V2 stores the PDA bump of the account
+    BcTokenMetadataV2,
+
+    /// Outside buyer account V2
+    /// V2 stores the PDA bump of the account
+    OutsideBuyerRecordV2,
+
+    /// Program-wide config of the Shihon deployment
+    ShihonConfig,
+
+    /// Ring grouping a chain of Tanistries
+    Ring,
+
+    /// Per-round one-time address of the kicker or the coordinator
+    Pinhole,
+
+    /// 2-of-3 approval of the rater, the buddy and the first kicker
+    TripleMultisig,
+
+    /// bcToken V3
+    /// V3 stores the delegate, the pending authority and the V3 metadata
+    BcTokenV3,
+
+    /// bcToken's inside metadata V3
+    /// V3 stores the content length and duration and the lineage of mixed content
+    BcTokenMetadataV3,
+
+    /// Outside buyer account V3
+    /// V3 stores the protocol fee taken from the deposit
+    OutsideBuyerRecordV3,
+
+    /// KickerCoin Owner Record V2
+    /// V2 stores the PDA bump, the protocol fee, the pinholes and the kicked bcToken
+    KickerCoinOwnerRecordV2,
+
+    /// Tanistry V2
+    /// V2 stores the PDA bump, the Ring, the game state and the refunds
+    TanistryV2,
+
+    /// CandidateLimitRecord V2
+    /// V2 stores the PDA bump, the delegate, the rating, the refund, the fee and the bcToken
+    CandidateLimitRecordV2,
+
+    /// Mix Content Record V2
+    /// V2 stores the PDA bump, the mixed bcTokens, the time shifts, the ranges and the promoted bcToken
+    MixContentRecordV2,
+
+    /// Rate Option V2
+    /// V2 stores the PDA bump and the sign-off of the Roydamna
+    RateOptionV2,
 
-impl Default for ShihonAccountType {
-    fn default() -> Self {
-        ShihonAccountType::Uninitialized
-    }
+    /// RateOtherRecord V2
+    /// V2 stores the PDA bump
+    RateOtherRecordV2,
+
+    /// CCVoteRecord V2
+    /// V2 stores the PDA bump and the target ring, the counting time moved to the target Ring
+    CCVoteRecordV2,
 }
 
 /// what state a bcToken is
 #[repr(C)]
-#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum BcTokenState {
     /// bcToken before content itself reaches to Oracle
+    #[default]
     DraftBlankCheck,
 
     /// some error happened when put own content on Oracle
@@ -78,13 +138,29 @@ pub enum BcTokenState {
     AlreadyRefunded,
 }
 
-impl Default for BcTokenState {
-    fn default() -> Self {
-        BcTokenState::DraftBlankCheck
-    }
+/// what state a Tanistry is
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum TanistryState {
+    /// Candidates are joining and the game is running
+    #[default]
+    Active,
+
+    /// Crown has been chosen and the vault is waiting for settlement
+    Crowned,
+
+    /// Vault has been settled and every participant can claim own refund
+    EnableToRefund,
+
+    /// Vault has been drained by refunds
+    AlreadyRefunded,
+
+    /// Enrollment ended without enough candidates and every deposit can be claimed back
+    Cancelled,
 }
 
 /// The source of vote for CC
+/// Transit and Snapshot are only kept for the existing records, a vote is casted with Pull or Push
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum VoteSource {
@@ -93,4 +169,21 @@ pub enum VoteSource {
 
     ///Pull type: Which push or pull
     Snapshot,
+
+    /// Pull the target ring, it must be smaller than the voter's ring
+    Pull,
+
+    /// Push the target ring, it must be bigger than the voter's ring
+    Push,
+}
+
+/// The party of the KickerCoinOwnerRecord a Pinhole was dropped for
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum PinholeSide {
+    /// The KickerCoin holder
+    Kicker,
+
+    /// The latest coordinator
+    Coordinator,
 }
@@ -1,23 +1,31 @@
 //! KickerCoin Owner Record
 
+use crate::state::legacy::KickerCoinOwnerRecordV1;
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
-    pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{get_account_data, AccountMaxSize};
 
-use crate::{error::ShihonError, PROGRAM_AUTHORITY_SEED};
+use crate::{
+    error::ShihonError,
+    pda::{find_bump_seed, get_kicker_coin_owner_record_address_seeds, is_valid_pda},
+};
 
-use crate::state::enums::ShihonAccountType;
+use crate::state::enums::{PinholeSide, ShihonAccountType};
 
-/// KickerCoin Owner Record PDA seeds: ['shihon', kicker_coin_holder, coordinator]
+/// KickerCoin Owner Record PDA seeds: ['kicker-coin-owner-record', kicker_coin_holder, coordinator]
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct KickerCoinOwnerRecord {
     /// account type
     pub account_type: ShihonAccountType,
 
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
     /// KickerCoin holder(first kicker or all Crown)
     pub kicker_coin_holder: Pubkey,
 
@@ -36,17 +44,46 @@ pub struct KickerCoinOwnerRecord {
     /// It means that this record will always be created regardless of whether it is approved by coordinator.
     /// Indicates whether the coordinator approve the KickerCoin
     pub is_kick_off: bool,
+
+    /// The protocol fee taken from the kicked KickerCoin into the treasury
+    pub kicker_coin_fee_amount: u64,
+
+    /// The pinhole the KickerCoin holder dropped for the round
+    pub kicker_pinhole: Option<Pubkey>,
+
+    /// The pinhole the latest coordinator dropped for the round
+    pub coordinator_pinhole: Option<Pubkey>,
+
+    /// The bcToken the KickerCoin holder kicked the KickerCoin with
+    pub kicker_bc_token: Pubkey,
 }
 
-impl AccountMaxSize for KickerCoinOwnerRecord {}
+impl AccountMaxSize for KickerCoinOwnerRecord {
+    fn get_max_size(&self) -> Option<usize> {
+        // The pinholes are dropped after the record is created so the space for both is reserved upfront
+        Some(self.building_hash_key.len() + 186)
+    }
+}
 
 impl IsInitialized for KickerCoinOwnerRecord {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::KickerCoinOwnerRecord
+        self.account_type == ShihonAccountType::KickerCoinOwnerRecordV2
     }
 }
 
 impl KickerCoinOwnerRecord {
+    /// Serializes the account into the current layout
+    /// V1 accounts have no room for the current fields and have to be migrated before they are updated
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::KickerCoinOwnerRecordV1 {
+            return Err(ShihonError::KickerCoinOwnerRecordMustBeMigrated.into());
+        }
+
+        BorshSerialize::serialize(self, writer)?;
+
+        Ok(())
+    }
+
     pub fn assert_is_valid_first_kicker() {
         unimplemented!();
     }
@@ -63,14 +100,22 @@ impl KickerCoinOwnerRecord {
         unimplemented!();
     }
 
-    /// Checks KickerCoin hasn't kicked off yet and is transaction by KickerCoin holder
-    pub fn assert_can_kick_off(&self, kicker_coin_info: &AccountInfo) -> Result<(), ProgramError> {
+    /// Checks KickerCoin hasn't kicked off yet and the latest coordinator signed the transaction
+    pub fn assert_can_kick_off(&self, coordinator_info: &AccountInfo) -> Result<(), ProgramError> {
         if self.is_kick_off {
             return Err(ShihonError::KickerCoinAlreadyKickedOff.into());
         }
 
-        if !kicker_coin_info == self.kicker_coin_holder {
-            return Err(ShihonError::KickerCoinHolderMustSign.into());
+        self.assert_coordinator_is_signer(coordinator_info)
+    }
+
+    /// Checks the latest coordinator of the KickerCoin signed the transaction
+    pub fn assert_coordinator_is_signer(
+        &self,
+        coordinator_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if !coordinator_info.is_signer || coordinator_info.key != &self.latest_coordinator {
+            return Err(ShihonError::CoordinatorMustSign.into());
         }
 
         Ok(())
@@ -85,58 +130,82 @@ impl KickerCoinOwnerRecord {
         Ok(())
     }
 
-    /// Who cannot be selected as a coordinator?
+    // Who cannot be selected as a coordinator?
     //  1. Does not have a bcToken.
     //  2. The same person as the kicker (i.e., self-made).
     //  3. The rules for blooded (read the whitepaper)
 
     /// Checks Coordinator is suitable for receive the KickerCoin
     pub fn assert_can_kick_to_suitable_coordinator(&self) -> Result<(), ProgramError> {
-        unimplemented!();
+        if self.latest_coordinator == self.kicker_coin_holder {
+            return Err(ShihonError::InvalidCoordinatorForTanistry.into());
+        }
+
+        Ok(())
     }
 
-    /// Create the building hash for kickerCoinOwnerRecord
-    fn make_building_hash() {
-        unimplemented!();
+    /// Returns the main wallet of the given party
+    pub fn get_party(&self, side: &PinholeSide) -> Pubkey {
+        match side {
+            PinholeSide::Kicker => self.kicker_coin_holder,
+            PinholeSide::Coordinator => self.latest_coordinator,
+        }
     }
 
-    /// for pinhole address
-    fn drop_address() {
-        unimplemented!();
+    /// Drops the pinhole of the given party for the round
+    /// Every party can drop only one pinhole per round
+    pub fn drop_address(
+        &mut self,
+        side: &PinholeSide,
+        pinhole: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let party_pinhole = match side {
+            PinholeSide::Kicker => &mut self.kicker_pinhole,
+            PinholeSide::Coordinator => &mut self.coordinator_pinhole,
+        };
+
+        if party_pinhole.is_some() {
+            return Err(ShihonError::PinholeAlreadyDropped.into());
+        }
+
+        *party_pinhole = Some(*pinhole);
+
+        Ok(())
     }
-    /// for pinhole address
-    fn pull_address() {
-        unimplemented!();
+
+    /// Returns the pinhole the given party dropped for the round
+    pub fn pull_address(&self, side: &PinholeSide) -> Result<Pubkey, ProgramError> {
+        match side {
+            PinholeSide::Kicker => self.kicker_pinhole,
+            PinholeSide::Coordinator => self.coordinator_pinhole,
+        }
+        .ok_or_else(|| ShihonError::PinholeNotDropped.into())
     }
-    /// getting opposite address(wallet)
-    fn get_opposite_address(me: Pubkey) -> Pubkey {
-        unimplemented!();
+
+    /// Returns the side of the given pinhole
+    fn get_pinhole_side(&self, pinhole: &Pubkey) -> Result<PinholeSide, ProgramError> {
+        if self.kicker_pinhole == Some(*pinhole) {
+            Ok(PinholeSide::Kicker)
+        } else if self.coordinator_pinhole == Some(*pinhole) {
+            Ok(PinholeSide::Coordinator)
+        } else {
+            Err(ShihonError::InvalidPinholeForKickerCoinOwnerRecord.into())
+        }
     }
-}
 
-/// Returns KickerCoinOwnerRecord PDA seeds
-pub fn get_kicker_coin_owner_record_address_seeds<'a>(
-    kicker_coin_holder: &'a Pubkey,
-    coordinator: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    [
-        PROGRAM_AUTHORITY_SEED,
-        kicker_coin_holder.as_ref(),
-        coordinator.as_ref(),
-    ]
-}
+    /// Returns the pinhole of the opposite party the owner of the given pinhole interacts with
+    pub fn get_opposite_address(&self, me: &Pubkey) -> Result<Pubkey, ProgramError> {
+        match self.get_pinhole_side(me)? {
+            PinholeSide::Kicker => self.pull_address(&PinholeSide::Coordinator),
+            PinholeSide::Coordinator => self.pull_address(&PinholeSide::Kicker),
+        }
+    }
 
-/// Returns KickerCoinOwnerRecord PDA address
-pub fn get_kicker_coin_owner_record_address<'a>(
-    program_id: &Pubkey,
-    kicker_coin_holder: &'a Pubkey,
-    coordinator: &'a Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_kicker_coin_owner_record_address_seeds(kicker_coin_holder, coordinator),
-        program_id,
-    )
-    .0
+    /// Resolves the main wallet behind the given pinhole
+    /// Note: Only payouts resolve the wallet, every other interaction stays on the pinholes
+    pub fn resolve_pinhole_owner(&self, pinhole: &Pubkey) -> Result<Pubkey, ProgramError> {
+        Ok(self.get_party(&self.get_pinhole_side(pinhole)?))
+    }
 }
 
 /// Deserializes KickerCoinOwnerRecord account and checks owner program
@@ -144,24 +213,175 @@ pub fn get_kicker_coin_owner_record_data(
     program_id: &Pubkey,
     kicker_coin_owner_record_info: &AccountInfo,
 ) -> Result<KickerCoinOwnerRecord, ProgramError> {
+    let account_type: ShihonAccountType =
+        try_from_slice_unchecked(&kicker_coin_owner_record_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::KickerCoinOwnerRecordV1 {
+        let kicker_coin_owner_record_data_v1 =
+            get_account_data::<KickerCoinOwnerRecordV1>(program_id, kicker_coin_owner_record_info)?;
+        return Ok(kicker_coin_owner_record_data_v1.into());
+    }
+
     get_account_data::<KickerCoinOwnerRecord>(program_id, kicker_coin_owner_record_info)
 }
 
-/// Deserializes KickerCoinOwnerRecord  and validates its PDA
+/// Deserializes KickerCoinOwnerRecord account and validates its PDA
 pub fn get_kicker_coin_owner_record_data_for_seeds(
     program_id: &Pubkey,
     kicker_coin_owner_record_info: &AccountInfo,
     kicker_coin_holder: &Pubkey,
     coordinator: &Pubkey,
 ) -> Result<KickerCoinOwnerRecord, ProgramError> {
-    let (kicker_coin_owner_record_address, _) = Pubkey::find_program_address(
-        &get_kicker_coin_owner_record_address_seeds(kicker_coin_holder, coordinator),
+    let kicker_coin_owner_record_data =
+        get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)?;
+
+    let kicker_coin_owner_record_address_seeds =
+        get_kicker_coin_owner_record_address_seeds(kicker_coin_holder, coordinator);
+
+    // V1 accounts don't store the bump and it's derived from the seeds
+    let bump = if kicker_coin_owner_record_data.account_type
+        == ShihonAccountType::KickerCoinOwnerRecordV1
+    {
+        find_bump_seed(program_id, &kicker_coin_owner_record_address_seeds)
+    } else {
+        kicker_coin_owner_record_data.bump
+    };
+
+    if !is_valid_pda(
         program_id,
-    );
+        kicker_coin_owner_record_info.key,
+        &kicker_coin_owner_record_address_seeds,
+        bump,
+    ) {
+        return Err(ShihonError::InvalidKickerCoinOwnerRecordAddress.into());
+    }
 
-    if kicker_coin_owner_record_address != *kicker_coin_owner_record_info.key {
-        return Err(ShihonError::InvalidSignatoryAddress.into());
+    Ok(kicker_coin_owner_record_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    fn create_test_kicker_coin_owner_record() -> KickerCoinOwnerRecord {
+        KickerCoinOwnerRecord {
+            account_type: ShihonAccountType::KickerCoinOwnerRecordV2,
+            bump: 255,
+            kicker_coin_holder: Pubkey::new_unique(),
+            latest_coordinator: Pubkey::new_unique(),
+            amount_of_kicker_coin: 100,
+            round: 1,
+            building_hash_key: vec![1, 2, 3],
+            is_kick_off: false,
+            kicker_coin_fee_amount: 0,
+            kicker_pinhole: None,
+            coordinator_pinhole: None,
+            kicker_bc_token: Pubkey::new_unique(),
+        }
     }
 
-    get_kicker_coin_owner_record_data(program_id, kicker_coin_owner_record_info)
+    #[test]
+    fn test_max_size_with_pinholes() {
+        // Arrange
+        let kicker_coin_owner_record = KickerCoinOwnerRecord {
+            kicker_pinhole: Some(Pubkey::new_unique()),
+            coordinator_pinhole: Some(Pubkey::new_unique()),
+            ..create_test_kicker_coin_owner_record()
+        };
+
+        // Act
+        let size = kicker_coin_owner_record.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(kicker_coin_owner_record.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_get_opposite_address() {
+        // Arrange
+        let mut kicker_coin_owner_record = create_test_kicker_coin_owner_record();
+        let kicker_pinhole = Pubkey::new_unique();
+        let coordinator_pinhole = Pubkey::new_unique();
+
+        kicker_coin_owner_record
+            .drop_address(&PinholeSide::Kicker, &kicker_pinhole)
+            .unwrap();
+        kicker_coin_owner_record
+            .drop_address(&PinholeSide::Coordinator, &coordinator_pinhole)
+            .unwrap();
+
+        // Act
+        let kicker_opposite = kicker_coin_owner_record.get_opposite_address(&kicker_pinhole);
+        let coordinator_opposite =
+            kicker_coin_owner_record.get_opposite_address(&coordinator_pinhole);
+
+        // Assert
+        assert_eq!(kicker_opposite, Ok(coordinator_pinhole));
+        assert_eq!(coordinator_opposite, Ok(kicker_pinhole));
+    }
+
+    #[test]
+    fn test_get_opposite_address_before_opposite_party_dropped_error() {
+        // Arrange
+        let mut kicker_coin_owner_record = create_test_kicker_coin_owner_record();
+        let kicker_pinhole = Pubkey::new_unique();
+
+        kicker_coin_owner_record
+            .drop_address(&PinholeSide::Kicker, &kicker_pinhole)
+            .unwrap();
+
+        // Act
+        let err = kicker_coin_owner_record
+            .get_opposite_address(&kicker_pinhole)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::PinholeNotDropped.into());
+    }
+
+    #[test]
+    fn test_drop_address_twice_for_same_party_error() {
+        // Arrange
+        let mut kicker_coin_owner_record = create_test_kicker_coin_owner_record();
+
+        kicker_coin_owner_record
+            .drop_address(&PinholeSide::Coordinator, &Pubkey::new_unique())
+            .unwrap();
+
+        // Act
+        let err = kicker_coin_owner_record
+            .drop_address(&PinholeSide::Coordinator, &Pubkey::new_unique())
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::PinholeAlreadyDropped.into());
+    }
+
+    #[test]
+    fn test_resolve_pinhole_owner() {
+        // Arrange
+        let mut kicker_coin_owner_record = create_test_kicker_coin_owner_record();
+        let coordinator_pinhole = Pubkey::new_unique();
+
+        kicker_coin_owner_record
+            .drop_address(&PinholeSide::Coordinator, &coordinator_pinhole)
+            .unwrap();
+
+        // Act
+        let owner = kicker_coin_owner_record.resolve_pinhole_owner(&coordinator_pinhole);
+        let err = kicker_coin_owner_record
+            .resolve_pinhole_owner(&Pubkey::new_unique())
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(owner, Ok(kicker_coin_owner_record.latest_coordinator));
+        assert_eq!(
+            err,
+            ShihonError::InvalidPinholeForKickerCoinOwnerRecord.into()
+        );
+    }
 }
@@ -0,0 +1,1260 @@
+//! Legacy Accounts
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    clock::{Slot, UnixTimestamp},
+    program_pack::IsInitialized,
+    pubkey::Pubkey,
+};
+
+use crate::state::{
+    bc_token::BcToken,
+    bc_token_metadata::{BcTokenMetadata, ContentType},
+    candidate_limit_record::CandidateLimitRecord,
+    cc_vote_record::CCVoteRecord,
+    enums::{BcTokenState, ShihonAccountType, TanistryState, VoteSource},
+    kicker_coin_owner_record::KickerCoinOwnerRecord,
+    mix_content_record::{MixContentRecord, OptionMixResult},
+    outside_buyer_record::OutsideBuyerRecord,
+    rate_option::RateOption,
+    rate_other_record::RateOtherRecord,
+    tanistry::Tanistry,
+};
+
+/// bcToken Account
+/// Note: This account is deprecated and BcTokenV3 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BcTokenV1 {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// bcToken owner pubkey
+    pub bc_token_owner_pubkey: Pubkey,
+
+    /// amount of coin (self rating)
+    pub amount_of_coin: u64,
+
+    /// bcToken issue time
+    pub issue_at: UnixTimestamp,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 8],
+
+    /// bcToken authority
+    pub authority: Option<Pubkey>,
+
+    /// bcToken name
+    pub name: String,
+
+    /// bcToken Mint
+    pub bc_token_mint: Pubkey,
+
+    /// state bcToken
+    pub bc_token_state: BcTokenState,
+
+    /// Metadata of bcToken
+    pub config: BcTokenMetadataV1,
+}
+
+impl IsInitialized for BcTokenV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::BcTokenV1
+    }
+}
+
+impl From<BcTokenV1> for BcToken {
+    /// Translates V1 layout into the current BcToken
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    fn from(bc_token_v1: BcTokenV1) -> Self {
+        BcToken {
+            account_type: bc_token_v1.account_type,
+            bump: 0,
+            bc_token_owner_pubkey: bc_token_v1.bc_token_owner_pubkey,
+            amount_of_coin: bc_token_v1.amount_of_coin,
+            issue_at: bc_token_v1.issue_at,
+            reserved: bc_token_v1.reserved,
+            authority: bc_token_v1.authority,
+            delegate: None,
+            pending_authority: None,
+            name: bc_token_v1.name,
+            bc_token_mint: bc_token_v1.bc_token_mint,
+            bc_token_state: bc_token_v1.bc_token_state,
+            config: bc_token_v1.config.into(),
+        }
+    }
+}
+
+impl From<BcToken> for BcTokenV1 {
+    fn from(bc_token: BcToken) -> Self {
+        BcTokenV1 {
+            account_type: ShihonAccountType::BcTokenV1,
+            bc_token_owner_pubkey: bc_token.bc_token_owner_pubkey,
+            amount_of_coin: bc_token.amount_of_coin,
+            issue_at: bc_token.issue_at,
+            reserved: bc_token.reserved,
+            authority: bc_token.authority,
+            name: bc_token.name,
+            bc_token_mint: bc_token.bc_token_mint,
+            bc_token_state: bc_token.bc_token_state,
+            config: bc_token.config.into(),
+        }
+    }
+}
+
+/// bcToken Account
+/// Note: This account is deprecated and BcTokenV3 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BcTokenV2 {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
+    /// bcToken owner pubkey
+    pub bc_token_owner_pubkey: Pubkey,
+
+    /// amount of coin (self rating)
+    pub amount_of_coin: u64,
+
+    /// bcToken issue time
+    pub issue_at: UnixTimestamp,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 8],
+
+    /// bcToken authority
+    pub authority: Option<Pubkey>,
+
+    /// bcToken name
+    pub name: String,
+
+    /// bcToken Mint
+    pub bc_token_mint: Pubkey,
+
+    /// state bcToken
+    pub bc_token_state: BcTokenState,
+
+    /// Metadata of bcToken
+    pub config: BcTokenMetadataV2,
+}
+
+impl IsInitialized for BcTokenV2 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::BcTokenV2
+    }
+}
+
+impl From<BcTokenV2> for BcToken {
+    /// Translates V2 layout into the current BcToken
+    /// V2 accounts have neither a delegate nor a pending authority
+    fn from(bc_token_v2: BcTokenV2) -> Self {
+        BcToken {
+            account_type: bc_token_v2.account_type,
+            bump: bc_token_v2.bump,
+            bc_token_owner_pubkey: bc_token_v2.bc_token_owner_pubkey,
+            amount_of_coin: bc_token_v2.amount_of_coin,
+            issue_at: bc_token_v2.issue_at,
+            reserved: bc_token_v2.reserved,
+            authority: bc_token_v2.authority,
+            delegate: None,
+            pending_authority: None,
+            name: bc_token_v2.name,
+            bc_token_mint: bc_token_v2.bc_token_mint,
+            bc_token_state: bc_token_v2.bc_token_state,
+            config: bc_token_v2.config.into(),
+        }
+    }
+}
+
+impl From<BcToken> for BcTokenV2 {
+    fn from(bc_token: BcToken) -> Self {
+        BcTokenV2 {
+            account_type: ShihonAccountType::BcTokenV2,
+            bump: bc_token.bump,
+            bc_token_owner_pubkey: bc_token.bc_token_owner_pubkey,
+            amount_of_coin: bc_token.amount_of_coin,
+            issue_at: bc_token.issue_at,
+            reserved: bc_token.reserved,
+            authority: bc_token.authority,
+            name: bc_token.name,
+            bc_token_mint: bc_token.bc_token_mint,
+            bc_token_state: bc_token.bc_token_state,
+            config: bc_token.config.into(),
+        }
+    }
+}
+
+/// bcToken metadata account
+/// Note: This account is deprecated and BcTokenMetadataV3 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BcTokenMetadataV1 {
+    /// bcToken account type
+    pub account_type: ShihonAccountType,
+
+    /// what content type
+    pub content_type: ContentType,
+
+    /// The slot when the metadata was captured
+    pub updated_at: Slot,
+
+    /// The version of the bcToken
+    pub version: String,
+
+    /// Reserved
+    pub reserved: [u8; 64],
+
+    /// True if the `pubkey` can be loaded as a read-write account.
+    pub is_writable: bool,
+
+    /// True if an bcToken requires a Transaction signature matching `pubkey`.
+    pub is_signer: bool,
+}
+
+impl IsInitialized for BcTokenMetadataV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::BcTokenMetadataV1
+    }
+}
+
+impl From<BcTokenMetadataV1> for BcTokenMetadata {
+    /// Translates V1 layout into the current BcTokenMetadata
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    fn from(bc_token_metadata_v1: BcTokenMetadataV1) -> Self {
+        BcTokenMetadata {
+            account_type: bc_token_metadata_v1.account_type,
+            bump: 0,
+            content_type: bc_token_metadata_v1.content_type,
+            updated_at: bc_token_metadata_v1.updated_at,
+            version: bc_token_metadata_v1.version,
+            reserved: bc_token_metadata_v1.reserved,
+            is_writable: bc_token_metadata_v1.is_writable,
+            is_signer: bc_token_metadata_v1.is_signer,
+            content_length: 0,
+            content_duration: 0,
+            lineage: None,
+        }
+    }
+}
+
+impl From<BcTokenMetadata> for BcTokenMetadataV1 {
+    fn from(bc_token_metadata: BcTokenMetadata) -> Self {
+        BcTokenMetadataV1 {
+            account_type: ShihonAccountType::BcTokenMetadataV1,
+            content_type: bc_token_metadata.content_type,
+            updated_at: bc_token_metadata.updated_at,
+            version: bc_token_metadata.version,
+            reserved: bc_token_metadata.reserved,
+            is_writable: bc_token_metadata.is_writable,
+            is_signer: bc_token_metadata.is_signer,
+        }
+    }
+}
+
+/// bcToken metadata account
+/// Note: This account is deprecated and BcTokenMetadataV3 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct BcTokenMetadataV2 {
+    /// bcToken account type
+    pub account_type: ShihonAccountType,
+
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
+    /// what content type
+    pub content_type: ContentType,
+
+    /// The slot when the metadata was captured
+    pub updated_at: Slot,
+
+    /// The version of the bcToken
+    pub version: String,
+
+    /// Reserved
+    pub reserved: [u8; 64],
+
+    /// True if the `pubkey` can be loaded as a read-write account.
+    pub is_writable: bool,
+
+    /// True if an bcToken requires a Transaction signature matching `pubkey`.
+    pub is_signer: bool,
+}
+
+impl IsInitialized for BcTokenMetadataV2 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::BcTokenMetadataV2
+    }
+}
+
+impl From<BcTokenMetadataV2> for BcTokenMetadata {
+    /// Translates V2 layout into the current BcTokenMetadata
+    /// V2 accounts carry no content length, duration or lineage
+    fn from(bc_token_metadata_v2: BcTokenMetadataV2) -> Self {
+        BcTokenMetadata {
+            account_type: bc_token_metadata_v2.account_type,
+            bump: bc_token_metadata_v2.bump,
+            content_type: bc_token_metadata_v2.content_type,
+            updated_at: bc_token_metadata_v2.updated_at,
+            version: bc_token_metadata_v2.version,
+            reserved: bc_token_metadata_v2.reserved,
+            is_writable: bc_token_metadata_v2.is_writable,
+            is_signer: bc_token_metadata_v2.is_signer,
+            content_length: 0,
+            content_duration: 0,
+            lineage: None,
+        }
+    }
+}
+
+impl From<BcTokenMetadata> for BcTokenMetadataV2 {
+    fn from(bc_token_metadata: BcTokenMetadata) -> Self {
+        BcTokenMetadataV2 {
+            account_type: ShihonAccountType::BcTokenMetadataV2,
+            bump: bc_token_metadata.bump,
+            content_type: bc_token_metadata.content_type,
+            updated_at: bc_token_metadata.updated_at,
+            version: bc_token_metadata.version,
+            reserved: bc_token_metadata.reserved,
+            is_writable: bc_token_metadata.is_writable,
+            is_signer: bc_token_metadata.is_signer,
+        }
+    }
+}
+
+/// Outside buyer account
+/// Note: This account is deprecated and OutsideBuyerRecordV3 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct OutsideBuyerRecordV1 {
+    /// OutsideBuyerRecord account type
+    pub account_type: ShihonAccountType,
+
+    /// The Tanistry the OutsideBuyerRecord belongs to
+    pub tanistry: Pubkey,
+
+    /// Buyer Token Mint the OutsideBuyerRecord holds deposit for
+    pub outside_buyer_token_mint: Pubkey,
+
+    /// This is who can authorize a withdrawal of the tokens
+    pub outside_buyer_token_owner: Pubkey,
+
+    /// This amount is the voter weight used when voting on rater
+    pub outside_buyer_token_deposit_amount: u64,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 7],
+}
+
+impl IsInitialized for OutsideBuyerRecordV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::OutsideBuyerRecordV1
+    }
+}
+
+impl From<OutsideBuyerRecordV1> for OutsideBuyerRecord {
+    /// Translates V1 layout into the current OutsideBuyerRecord
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    fn from(outside_buyer_record_v1: OutsideBuyerRecordV1) -> Self {
+        OutsideBuyerRecord {
+            account_type: outside_buyer_record_v1.account_type,
+            bump: 0,
+            tanistry: outside_buyer_record_v1.tanistry,
+            outside_buyer_token_mint: outside_buyer_record_v1.outside_buyer_token_mint,
+            outside_buyer_token_owner: outside_buyer_record_v1.outside_buyer_token_owner,
+            outside_buyer_token_deposit_amount: outside_buyer_record_v1
+                .outside_buyer_token_deposit_amount,
+            outside_buyer_fee_amount: 0,
+            is_refunded: false,
+            reserved: [0; 6],
+        }
+    }
+}
+
+impl From<OutsideBuyerRecord> for OutsideBuyerRecordV1 {
+    fn from(outside_buyer_record: OutsideBuyerRecord) -> Self {
+        OutsideBuyerRecordV1 {
+            account_type: ShihonAccountType::OutsideBuyerRecordV1,
+            tanistry: outside_buyer_record.tanistry,
+            outside_buyer_token_mint: outside_buyer_record.outside_buyer_token_mint,
+            outside_buyer_token_owner: outside_buyer_record.outside_buyer_token_owner,
+            outside_buyer_token_deposit_amount: outside_buyer_record
+                .outside_buyer_token_deposit_amount,
+            reserved: [0; 7],
+        }
+    }
+}
+
+/// Outside buyer account
+/// Note: This account is deprecated and OutsideBuyerRecordV3 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct OutsideBuyerRecordV2 {
+    /// OutsideBuyerRecord account type
+    pub account_type: ShihonAccountType,
+
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
+    /// The Tanistry the OutsideBuyerRecord belongs to
+    pub tanistry: Pubkey,
+
+    /// Buyer Token Mint the OutsideBuyerRecord holds deposit for
+    pub outside_buyer_token_mint: Pubkey,
+
+    /// This is who can authorize a withdrawal of the tokens
+    pub outside_buyer_token_owner: Pubkey,
+
+    /// This amount is the voter weight used when voting on rater
+    pub outside_buyer_token_deposit_amount: u64,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 7],
+}
+
+impl IsInitialized for OutsideBuyerRecordV2 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::OutsideBuyerRecordV2
+    }
+}
+
+impl From<OutsideBuyerRecordV2> for OutsideBuyerRecord {
+    /// Translates V2 layout into the current OutsideBuyerRecord
+    /// No fee was taken from the deposits of V2 accounts
+    fn from(outside_buyer_record_v2: OutsideBuyerRecordV2) -> Self {
+        OutsideBuyerRecord {
+            account_type: outside_buyer_record_v2.account_type,
+            bump: outside_buyer_record_v2.bump,
+            tanistry: outside_buyer_record_v2.tanistry,
+            outside_buyer_token_mint: outside_buyer_record_v2.outside_buyer_token_mint,
+            outside_buyer_token_owner: outside_buyer_record_v2.outside_buyer_token_owner,
+            outside_buyer_token_deposit_amount: outside_buyer_record_v2
+                .outside_buyer_token_deposit_amount,
+            outside_buyer_fee_amount: 0,
+            is_refunded: false,
+            reserved: [0; 6],
+        }
+    }
+}
+
+impl From<OutsideBuyerRecord> for OutsideBuyerRecordV2 {
+    fn from(outside_buyer_record: OutsideBuyerRecord) -> Self {
+        OutsideBuyerRecordV2 {
+            account_type: ShihonAccountType::OutsideBuyerRecordV2,
+            bump: outside_buyer_record.bump,
+            tanistry: outside_buyer_record.tanistry,
+            outside_buyer_token_mint: outside_buyer_record.outside_buyer_token_mint,
+            outside_buyer_token_owner: outside_buyer_record.outside_buyer_token_owner,
+            outside_buyer_token_deposit_amount: outside_buyer_record
+                .outside_buyer_token_deposit_amount,
+            reserved: [0; 7],
+        }
+    }
+}
+
+/// KickerCoin Owner Record account
+/// Note: This account is deprecated and KickerCoinOwnerRecordV2 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct KickerCoinOwnerRecordV1 {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// KickerCoin holder(first kicker or all Crown)
+    pub kicker_coin_holder: Pubkey,
+
+    /// The account of the latest Coordinator, which means the person who receive KickerCoin before the Tanistry
+    pub latest_coordinator: Pubkey,
+
+    /// amount of KickerCoin in the round
+    pub amount_of_kicker_coin: u64,
+
+    /// number of round
+    pub round: u8,
+
+    /// building hash key
+    pub building_hash_key: Vec<u8>,
+
+    /// Indicates whether the coordinator approve the KickerCoin
+    pub is_kick_off: bool,
+}
+
+impl IsInitialized for KickerCoinOwnerRecordV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::KickerCoinOwnerRecordV1
+    }
+}
+
+impl From<KickerCoinOwnerRecordV1> for KickerCoinOwnerRecord {
+    /// Translates V1 layout into the current KickerCoinOwnerRecord
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    /// No fee was taken from V1 KickerCoins and the kicked bcToken wasn't recorded
+    fn from(kicker_coin_owner_record_v1: KickerCoinOwnerRecordV1) -> Self {
+        KickerCoinOwnerRecord {
+            account_type: kicker_coin_owner_record_v1.account_type,
+            bump: 0,
+            kicker_coin_holder: kicker_coin_owner_record_v1.kicker_coin_holder,
+            latest_coordinator: kicker_coin_owner_record_v1.latest_coordinator,
+            amount_of_kicker_coin: kicker_coin_owner_record_v1.amount_of_kicker_coin,
+            round: kicker_coin_owner_record_v1.round,
+            building_hash_key: kicker_coin_owner_record_v1.building_hash_key,
+            is_kick_off: kicker_coin_owner_record_v1.is_kick_off,
+            kicker_coin_fee_amount: 0,
+            kicker_pinhole: None,
+            coordinator_pinhole: None,
+            kicker_bc_token: Pubkey::default(),
+        }
+    }
+}
+
+/// Tanistry account
+/// Note: This account is deprecated and TanistryV2 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct TanistryV1 {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// next tanistry id
+    pub next_tanistry_id: Option<Pubkey>,
+
+    /// previous tanistry id
+    pub previous_tanistry_id: Option<Pubkey>,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 8],
+
+    /// kicker coin owner record
+    pub kicker_coin_owner_record: Pubkey,
+
+    /// CandidateLimitRecord List
+    pub candidate_limit_record_list: Vec<Pubkey>,
+}
+
+impl IsInitialized for TanistryV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::TanistryV1
+    }
+}
+
+impl From<TanistryV1> for Tanistry {
+    /// Translates V1 layout into the current Tanistry
+    /// The bump and the Ring are unknown for V1 accounts and are resolved when the account is migrated
+    /// V1 accounts carry no game state so they start as an Active Tanistry without any refund
+    fn from(tanistry_v1: TanistryV1) -> Self {
+        Tanistry {
+            account_type: tanistry_v1.account_type,
+            bump: 0,
+            next_tanistry_id: tanistry_v1.next_tanistry_id,
+            previous_tanistry_id: tanistry_v1.previous_tanistry_id,
+            ring: Pubkey::default(),
+            reserved: tanistry_v1.reserved,
+            kicker_coin_owner_record: tanistry_v1.kicker_coin_owner_record,
+            state: TanistryState::Active,
+            tanistry_token_mint: Pubkey::default(),
+            crown: None,
+            outside_buyer_deposit_amount: 0,
+            settled_amount: 0,
+            refunded_amount: 0,
+            kicker_refund_amount: 0,
+            coordinator_refund_amount: 0,
+            is_kicker_refunded: false,
+            is_coordinator_refunded: false,
+            coordinator_refund_receipt_mint: None,
+            enrolling_at: 0,
+            candidate_limit_record_list: tanistry_v1.candidate_limit_record_list,
+        }
+    }
+}
+
+/// CandidateLimitRecord account
+/// Note: This account is deprecated and CandidateLimitRecordV2 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CandidateLimitRecordV1 {
+    /// Governance account type
+    pub account_type: ShihonAccountType,
+
+    /// The Tanistry the CandidateLimitRecord belongs to
+    pub belonging_tanistry: Pubkey,
+
+    /// number of candidate count
+    pub number_of_candidate_count: u32,
+
+    /// Candidate Token Mint the CandidateLimitRecord holds deposit for
+    pub candidate_token_mint: Pubkey,
+
+    /// The owner (either single or multisig) of the deposited candidate SPL Tokens
+    pub candidate_token_owner: Pubkey,
+
+    /// The amount of candidate tokens deposited into the Tanistry
+    pub candidate_token_deposit_amount: u64,
+}
+
+impl IsInitialized for CandidateLimitRecordV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::CandidateLimitRecordV1
+    }
+}
+
+impl From<CandidateLimitRecordV1> for CandidateLimitRecord {
+    /// Translates V1 layout into the current CandidateLimitRecord
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    /// V1 candidates have neither a delegate nor any rating, refund or fee
+    fn from(candidate_limit_record_v1: CandidateLimitRecordV1) -> Self {
+        CandidateLimitRecord {
+            account_type: candidate_limit_record_v1.account_type,
+            bump: 0,
+            belonging_tanistry: candidate_limit_record_v1.belonging_tanistry,
+            number_of_candidate_count: candidate_limit_record_v1.number_of_candidate_count,
+            candidate_token_mint: candidate_limit_record_v1.candidate_token_mint,
+            candidate_token_owner: candidate_limit_record_v1.candidate_token_owner,
+            candidate_delegate: None,
+            candidate_token_deposit_amount: candidate_limit_record_v1
+                .candidate_token_deposit_amount,
+            received_rating: 0,
+            refund_amount: 0,
+            is_refunded: false,
+            candidate_fee_amount: 0,
+            rate_options_count: 0,
+            bc_token: Pubkey::default(),
+        }
+    }
+}
+
+/// Mix Content Record account
+/// Note: This account is deprecated and MixContentRecordV2 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct MixContentRecordV1 {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// Encrypted link
+    pub encrypted_link: String,
+
+    /// Rater candidate (It's you!)
+    pub rater_candidate: Pubkey,
+
+    /// Buddy candidate
+    pub buddy_candidate: Pubkey,
+
+    /// Mix content result state before rating action
+    pub option_mix_result: OptionMixResult,
+
+    /// Finger print of Mixed Content
+    pub finger_print_of_mixed_content: String,
+}
+
+impl IsInitialized for MixContentRecordV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::MixContentRecordV1
+    }
+}
+
+impl From<MixContentRecordV1> for MixContentRecord {
+    /// Translates V1 layout into the current MixContentRecord
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    /// V1 records don't know the mixed bcTokens and mixed the whole contents without any time shift
+    fn from(mix_content_record_v1: MixContentRecordV1) -> Self {
+        MixContentRecord {
+            account_type: mix_content_record_v1.account_type,
+            bump: 0,
+            encrypted_link: mix_content_record_v1.encrypted_link,
+            rater_candidate: mix_content_record_v1.rater_candidate,
+            buddy_candidate: mix_content_record_v1.buddy_candidate,
+            option_mix_result: mix_content_record_v1.option_mix_result,
+            finger_print_of_mixed_content: mix_content_record_v1.finger_print_of_mixed_content,
+            init_bc_token: Pubkey::default(),
+            buddy_bc_token: Pubkey::default(),
+            time_shift_a: 0,
+            time_shift_b: 0,
+            range_a: None,
+            range_b: None,
+            promoted_bc_token: None,
+        }
+    }
+}
+
+/// Rate Option account
+/// Note: This account is deprecated and RateOptionV2 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct RateOptionV1 {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// Which Roydamna created this RateOption
+    pub issuer_roydamna: Pubkey,
+
+    /// MixContentRecord
+    pub mix_content_record: Pubkey,
+
+    /// RateOtherRecord
+    pub rate_other_record: Pubkey,
+
+    /// number of issue
+    pub number_of_issue: u8,
+
+    /// Key Holder
+    pub buddy_candidate: Pubkey,
+
+    /// The init content of the MixContentRecord
+    pub init_content: Pubkey,
+}
+
+impl IsInitialized for RateOptionV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::RateOptionV1
+    }
+}
+
+impl From<RateOptionV1> for RateOption {
+    /// Translates V1 layout into the current RateOption
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    fn from(rate_option_v1: RateOptionV1) -> Self {
+        RateOption {
+            account_type: rate_option_v1.account_type,
+            bump: 0,
+            issuer_roydamna: rate_option_v1.issuer_roydamna,
+            mix_content_record: rate_option_v1.mix_content_record,
+            rate_other_record: rate_option_v1.rate_other_record,
+            number_of_issue: rate_option_v1.number_of_issue,
+            buddy_candidate: rate_option_v1.buddy_candidate,
+            init_content: rate_option_v1.init_content,
+            signed_off: false,
+        }
+    }
+}
+
+/// RateOtherRecord account
+/// Note: This account is deprecated and RateOtherRecordV2 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct RateOtherRecordV1 {
+    /// Rate account type
+    pub account_type: ShihonAccountType,
+
+    /// Mix content record
+    pub mix_content_record: Pubkey,
+
+    /// CC vote record
+    pub cc_vote_record: Pubkey,
+
+    /// Outside Buyer Record
+    pub outside_buyer_record: Pubkey,
+
+    /// Rate count
+    pub rate_amount: u64,
+
+    /// Rating time
+    pub rating_time: Option<UnixTimestamp>,
+}
+
+impl IsInitialized for RateOtherRecordV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::RateOtherRecordV1
+    }
+}
+
+impl From<RateOtherRecordV1> for RateOtherRecord {
+    /// Translates V1 layout into the current RateOtherRecord
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    fn from(rate_other_record_v1: RateOtherRecordV1) -> Self {
+        RateOtherRecord {
+            account_type: rate_other_record_v1.account_type,
+            bump: 0,
+            mix_content_record: rate_other_record_v1.mix_content_record,
+            cc_vote_record: rate_other_record_v1.cc_vote_record,
+            outside_buyer_record: rate_other_record_v1.outside_buyer_record,
+            rate_amount: rate_other_record_v1.rate_amount,
+            rating_time: rate_other_record_v1.rating_time,
+        }
+    }
+}
+
+/// CC Vote Record account
+/// Note: This account is deprecated and CCVoteRecordV2 should be used instead
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct CCVoteRecordV1 {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// The user who casted this vote
+    pub governing_token_owner: Pubkey,
+
+    /// when system counted the whole voting
+    pub counting_time: Slot,
+
+    /// Indicates whether the vote was relinquished by voter
+    pub is_relinquished: bool,
+
+    /// The weight of the user casting the vote
+    pub voter_weight: u64,
+
+    /// Voter's vote
+    pub vote: VoteSource,
+}
+
+impl IsInitialized for CCVoteRecordV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::CCVoteRecordV1
+    }
+}
+
+impl From<CCVoteRecordV1> for CCVoteRecord {
+    /// Translates V1 layout into the current CCVoteRecord
+    /// The bump is unknown for V1 accounts and is resolved when the account is migrated
+    /// V1 records don't know the ring they were casted for and their own counting time is dropped
+    /// because the counting window is shared on the target Ring
+    fn from(cc_vote_record_v1: CCVoteRecordV1) -> Self {
+        CCVoteRecord {
+            account_type: cc_vote_record_v1.account_type,
+            bump: 0,
+            target_ring: Pubkey::default(),
+            voter: cc_vote_record_v1.governing_token_owner,
+            is_relinquished: cc_vote_record_v1.is_relinquished,
+            voter_weight: cc_vote_record_v1.voter_weight,
+            vote: cc_vote_record_v1.vote,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use borsh::BorshSerialize;
+    use solana_program::account_info::AccountInfo;
+
+    use super::*;
+    use crate::{
+        error::ShihonError,
+        pda::get_candidate_limit_record_address,
+        state::{
+            bc_token::get_bc_token_data,
+            candidate_limit_record::get_candidate_limit_record_data_for_seeds,
+            cc_vote_record::get_cc_vote_record_data,
+            outside_buyer_record::get_outside_buyer_token_owner_record_data,
+            tanistry::get_tanistry_data,
+        },
+    };
+
+    fn create_test_bc_token_v1() -> BcTokenV1 {
+        BcTokenV1 {
+            account_type: ShihonAccountType::BcTokenV1,
+            bc_token_owner_pubkey: Pubkey::new_unique(),
+            amount_of_coin: 10,
+            issue_at: 1_650_000_000,
+            reserved: [0; 8],
+            authority: Some(Pubkey::new_unique()),
+            name: "bcToken".to_string(),
+            bc_token_mint: Pubkey::new_unique(),
+            bc_token_state: BcTokenState::DraftBlankCheck,
+            config: BcTokenMetadataV1 {
+                account_type: ShihonAccountType::BcTokenMetadataV1,
+                content_type: ContentType::Ordinary,
+                updated_at: 100,
+                version: "1".to_string(),
+                reserved: [0; 64],
+                is_writable: true,
+                is_signer: false,
+            },
+        }
+    }
+
+    fn create_test_outside_buyer_record_v1() -> OutsideBuyerRecordV1 {
+        OutsideBuyerRecordV1 {
+            account_type: ShihonAccountType::OutsideBuyerRecordV1,
+            tanistry: Pubkey::new_unique(),
+            outside_buyer_token_mint: Pubkey::new_unique(),
+            outside_buyer_token_owner: Pubkey::new_unique(),
+            outside_buyer_token_deposit_amount: 50,
+            reserved: [0; 7],
+        }
+    }
+
+    #[test]
+    fn test_get_bc_token_data_translates_v1_account() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let bc_token_v1 = create_test_bc_token_v1();
+        let mut data = bc_token_v1.try_to_vec().unwrap();
+
+        let bc_token_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let bc_token_data = get_bc_token_data(&program_id, &bc_token_info).unwrap();
+
+        // Assert
+        assert_eq!(bc_token_data.account_type, ShihonAccountType::BcTokenV1);
+        assert_eq!(bc_token_data.name, bc_token_v1.name);
+        assert_eq!(bc_token_data.authority, bc_token_v1.authority);
+        assert_eq!(
+            bc_token_data.config.account_type,
+            ShihonAccountType::BcTokenMetadataV1
+        );
+    }
+
+    #[test]
+    fn test_serialize_bc_token_keeps_v1_layout() {
+        // Arrange
+        let bc_token_v1 = create_test_bc_token_v1();
+        let bc_token_data: BcToken = bc_token_v1.clone().into();
+
+        // Act
+        let mut data = vec![];
+        bc_token_data.serialize(&mut data).unwrap();
+
+        // Assert
+        assert_eq!(data, bc_token_v1.try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_bc_token_v2_layout_stores_bumps() {
+        // Arrange
+        let bc_token_v1 = create_test_bc_token_v1();
+        let v1_size = bc_token_v1.try_to_vec().unwrap().len();
+
+        let mut bc_token_data: BcToken = bc_token_v1.into();
+        bc_token_data.account_type = ShihonAccountType::BcTokenV2;
+        bc_token_data.config.account_type = ShihonAccountType::BcTokenMetadataV2;
+
+        // Act
+        let mut data = vec![];
+        bc_token_data.serialize(&mut data).unwrap();
+
+        // Assert
+        // bcToken and its embedded metadata each gain the bump byte
+        assert_eq!(data.len(), v1_size + 2);
+    }
+
+    #[test]
+    fn test_bc_token_v3_layout_stores_delegates_and_content_fields() {
+        // Arrange
+        let bc_token_v1 = create_test_bc_token_v1();
+        let v1_size = bc_token_v1.try_to_vec().unwrap().len();
+
+        let mut bc_token_data: BcToken = bc_token_v1.into();
+        bc_token_data.account_type = ShihonAccountType::BcTokenV3;
+        bc_token_data.config.account_type = ShihonAccountType::BcTokenMetadataV3;
+
+        // Act
+        let mut data = vec![];
+        bc_token_data.serialize(&mut data).unwrap();
+
+        // Assert
+        // On top of the V2 bumps the bcToken gains the delegate and pending authority tags
+        // and the metadata gains the content length, the content duration and the lineage tag
+        assert_eq!(data.len(), v1_size + 2 + 2 + 17);
+    }
+
+    #[test]
+    fn test_get_bc_token_data_translates_v2_account() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let mut bc_token_data: BcToken = create_test_bc_token_v1().into();
+        bc_token_data.account_type = ShihonAccountType::BcTokenV2;
+        bc_token_data.bump = 254;
+        bc_token_data.config.account_type = ShihonAccountType::BcTokenMetadataV2;
+        bc_token_data.config.bump = 253;
+
+        let bc_token_v2: BcTokenV2 = bc_token_data.into();
+        let mut data = bc_token_v2.try_to_vec().unwrap();
+
+        let bc_token_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let bc_token_data = get_bc_token_data(&program_id, &bc_token_info).unwrap();
+
+        // Assert
+        assert_eq!(bc_token_data.account_type, ShihonAccountType::BcTokenV2);
+        assert_eq!(bc_token_data.bump, 254);
+        assert_eq!(bc_token_data.delegate, None);
+        assert_eq!(bc_token_data.pending_authority, None);
+        assert_eq!(bc_token_data.config.bump, 253);
+        assert_eq!(bc_token_data.config.lineage, None);
+
+        let mut data = vec![];
+        bc_token_data.serialize(&mut data).unwrap();
+        assert_eq!(data, bc_token_v2.try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_get_outside_buyer_record_data_translates_v1_account() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let outside_buyer_record_v1 = create_test_outside_buyer_record_v1();
+        let mut data = outside_buyer_record_v1.try_to_vec().unwrap();
+
+        let outside_buyer_record_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let outside_buyer_record_data =
+            get_outside_buyer_token_owner_record_data(&program_id, &outside_buyer_record_info)
+                .unwrap();
+
+        // Assert
+        let outside_buyer_record_data_v1: OutsideBuyerRecordV1 = outside_buyer_record_data.into();
+        assert_eq!(outside_buyer_record_data_v1, outside_buyer_record_v1);
+    }
+
+    #[test]
+    fn test_get_outside_buyer_record_data_translates_v2_account() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let outside_buyer_record_v1 = create_test_outside_buyer_record_v1();
+        let outside_buyer_record_v2 = OutsideBuyerRecordV2 {
+            account_type: ShihonAccountType::OutsideBuyerRecordV2,
+            bump: 254,
+            tanistry: outside_buyer_record_v1.tanistry,
+            outside_buyer_token_mint: outside_buyer_record_v1.outside_buyer_token_mint,
+            outside_buyer_token_owner: outside_buyer_record_v1.outside_buyer_token_owner,
+            outside_buyer_token_deposit_amount: 50,
+            reserved: [0; 7],
+        };
+        let mut data = outside_buyer_record_v2.try_to_vec().unwrap();
+
+        let outside_buyer_record_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let outside_buyer_record_data =
+            get_outside_buyer_token_owner_record_data(&program_id, &outside_buyer_record_info)
+                .unwrap();
+
+        // Assert
+        assert_eq!(
+            outside_buyer_record_data.account_type,
+            ShihonAccountType::OutsideBuyerRecordV2
+        );
+        assert_eq!(outside_buyer_record_data.bump, 254);
+        assert_eq!(outside_buyer_record_data.outside_buyer_fee_amount, 0);
+
+        let outside_buyer_record_data_v2: OutsideBuyerRecordV2 = outside_buyer_record_data.into();
+        assert_eq!(outside_buyer_record_data_v2, outside_buyer_record_v2);
+    }
+
+    #[test]
+    fn test_get_tanistry_data_translates_v1_account() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let tanistry_v1 = TanistryV1 {
+            account_type: ShihonAccountType::TanistryV1,
+            next_tanistry_id: None,
+            previous_tanistry_id: Some(Pubkey::new_unique()),
+            reserved: [0; 8],
+            kicker_coin_owner_record: Pubkey::new_unique(),
+            candidate_limit_record_list: vec![Pubkey::new_unique()],
+        };
+        let mut data = tanistry_v1.try_to_vec().unwrap();
+
+        let tanistry_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let tanistry_data = get_tanistry_data(&program_id, &tanistry_info).unwrap();
+
+        // Assert
+        assert_eq!(tanistry_data.account_type, ShihonAccountType::TanistryV1);
+        assert_eq!(tanistry_data.bump, 0);
+        assert_eq!(
+            tanistry_data.previous_tanistry_id,
+            tanistry_v1.previous_tanistry_id
+        );
+        assert_eq!(
+            tanistry_data.kicker_coin_owner_record,
+            tanistry_v1.kicker_coin_owner_record
+        );
+        assert_eq!(
+            tanistry_data.candidate_limit_record_list,
+            tanistry_v1.candidate_limit_record_list
+        );
+        assert_eq!(tanistry_data.state, TanistryState::Active);
+        assert_eq!(tanistry_data.ring, Pubkey::default());
+    }
+
+    #[test]
+    fn test_serialize_tanistry_v1_must_be_migrated_error() {
+        // Arrange
+        let tanistry_data: Tanistry = TanistryV1 {
+            account_type: ShihonAccountType::TanistryV1,
+            next_tanistry_id: None,
+            previous_tanistry_id: None,
+            reserved: [0; 8],
+            kicker_coin_owner_record: Pubkey::new_unique(),
+            candidate_limit_record_list: vec![],
+        }
+        .into();
+
+        // Act
+        let err = tanistry_data.serialize(&mut vec![]).err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::TanistryMustBeMigrated.into());
+    }
+
+    #[test]
+    fn test_get_candidate_limit_record_data_for_seeds_derives_v1_bump() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let candidate_limit_record_v1 = CandidateLimitRecordV1 {
+            account_type: ShihonAccountType::CandidateLimitRecordV1,
+            belonging_tanistry: Pubkey::new_unique(),
+            number_of_candidate_count: 1,
+            candidate_token_mint: Pubkey::new_unique(),
+            candidate_token_owner: Pubkey::new_unique(),
+            candidate_token_deposit_amount: 100,
+        };
+        let key = get_candidate_limit_record_address(
+            &program_id,
+            &candidate_limit_record_v1.belonging_tanistry,
+            &candidate_limit_record_v1.candidate_token_mint,
+            &candidate_limit_record_v1.candidate_token_owner,
+        );
+        let mut data = candidate_limit_record_v1.try_to_vec().unwrap();
+
+        let candidate_limit_record_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let candidate_limit_record_data = get_candidate_limit_record_data_for_seeds(
+            &program_id,
+            &candidate_limit_record_info,
+            &candidate_limit_record_v1.belonging_tanistry,
+            &candidate_limit_record_v1.candidate_token_mint,
+            &candidate_limit_record_v1.candidate_token_owner,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(
+            candidate_limit_record_data.account_type,
+            ShihonAccountType::CandidateLimitRecordV1
+        );
+        assert_eq!(
+            candidate_limit_record_data.candidate_token_deposit_amount,
+            100
+        );
+        assert_eq!(candidate_limit_record_data.candidate_delegate, None);
+        assert!(!candidate_limit_record_data.is_refunded);
+        // The V1 layout doesn't store the bump and the PDA is validated with the derived one
+        assert_eq!(candidate_limit_record_data.bump, 0);
+    }
+
+    #[test]
+    fn test_get_cc_vote_record_data_translates_v1_account() {
+        // Arrange
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let cc_vote_record_v1 = CCVoteRecordV1 {
+            account_type: ShihonAccountType::CCVoteRecordV1,
+            governing_token_owner: Pubkey::new_unique(),
+            counting_time: 100,
+            is_relinquished: false,
+            voter_weight: 10,
+            vote: VoteSource::Pull,
+        };
+        let mut data = cc_vote_record_v1.try_to_vec().unwrap();
+
+        let cc_vote_record_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Act
+        let cc_vote_record_data =
+            get_cc_vote_record_data(&program_id, &cc_vote_record_info).unwrap();
+
+        // Assert
+        assert_eq!(
+            cc_vote_record_data.account_type,
+            ShihonAccountType::CCVoteRecordV1
+        );
+        assert_eq!(
+            cc_vote_record_data.voter,
+            cc_vote_record_v1.governing_token_owner
+        );
+        assert_eq!(cc_vote_record_data.voter_weight, 10);
+        assert_eq!(cc_vote_record_data.vote, VoteSource::Pull);
+        assert_eq!(cc_vote_record_data.target_ring, Pubkey::default());
+    }
+}
@@ -1,26 +1,30 @@
 //! MixContentRecord Account
 
-use borsh::maybestd::io::Write;
-use std::cmp::Ordering;
-
-use solana_program::borsh::try_from_slice_unchecked;
-use solana_program::clock::{Slot, UnixTimestamp};
-
+use crate::state::legacy::MixContentRecordV1;
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
-    pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{get_account_data, AccountMaxSize};
 
-use crate::{error::ShihonError, state::enums::ShihonAccountType, PROGRAM_AUTHORITY_SEED};
+use crate::{
+    error::ShihonError,
+    pda::{find_bump_seed, get_mix_content_record_address_seeds, is_valid_pda},
+    state::{bc_token_metadata::ContentRange, enums::ShihonAccountType},
+};
 
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
+/// Account PDA seeds: ['mix-content-record', rater_candidate, buddy_candidate]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct MixContentRecord {
-    ///
+    /// account type
     pub account_type: ShihonAccountType,
 
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
     /// Encrypted link
     pub encrypted_link: String,
 
@@ -35,6 +39,27 @@ pub struct MixContentRecord {
 
     /// Finger print of Mixed Content
     pub finger_print_of_mixed_content: String,
+
+    /// The init content's bcToken (first kicker's content)
+    pub init_bc_token: Pubkey,
+
+    /// The buddy content's bcToken
+    pub buddy_bc_token: Pubkey,
+
+    /// The time shift applied to the init content
+    pub time_shift_a: u64,
+
+    /// The time shift applied to the buddy content
+    pub time_shift_b: u64,
+
+    /// The part of the init content taken into the mix, only for Partially content
+    pub range_a: Option<ContentRange>,
+
+    /// The part of the buddy content taken into the mix, only for Partially content
+    pub range_b: Option<ContentRange>,
+
+    /// The bcToken the mixed content was promoted into
+    pub promoted_bc_token: Option<Pubkey>,
 }
 
 /// Mix content result state before rating action
@@ -54,11 +79,37 @@ impl AccountMaxSize for MixContentRecord {}
 
 impl IsInitialized for MixContentRecord {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::MixContentRecord
+        self.account_type == ShihonAccountType::MixContentRecordV2
     }
 }
 
 impl MixContentRecord {
+    /// Serializes the account into the current layout
+    /// V1 accounts have no room for the current fields and have to be migrated before they are updated
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::MixContentRecordV1 {
+            return Err(ShihonError::MixContentRecordMustBeMigrated.into());
+        }
+
+        BorshSerialize::serialize(self, writer)?;
+
+        Ok(())
+    }
+
+    /// Asserts the mixed content can be promoted into a new bcToken
+    /// Only a Succeeded mix can be promoted and only once
+    pub fn assert_can_promote(&self) -> Result<(), ProgramError> {
+        if self.option_mix_result != OptionMixResult::Succeeded {
+            return Err(ShihonError::MixContentNotSucceeded.into());
+        }
+
+        if self.promoted_bc_token.is_some() {
+            return Err(ShihonError::MixContentAlreadyPromoted.into());
+        }
+
+        Ok(())
+    }
+
     pub fn assert_finished_shaffle_on_candidate_list() {
         unimplemented!();
     }
@@ -70,87 +121,118 @@ impl MixContentRecord {
     pub fn assert_can_mix_the_content() {
         unimplemented!();
     }
-
-    /// mixing the content for first step
-    fn multi_sig_1() {
-        unimplemented!();
-    }
 }
 
-/// Deserializes Proposal and validates it belongs to the given content
-pub fn get_content_metadata_for_mixing(
+/// Deserializes MixContentRecord account and checks owner program
+pub fn get_mix_content_record_data(
     program_id: &Pubkey,
-    proposal_info: &AccountInfo,
-    governance: &Pubkey,
-    governing_token_mint: &Pubkey,
-) -> Result<Rate, ProgramError> {
-    let proposal_data = get_proposal_data_for_governance(program_id, proposal_info, governance)?;
-
-    if proposal_data.governing_token_mint != *governing_token_mint {
-        return Err(ShihonError::InvalidGoverningMintForProposal.into());
+    mix_content_record_info: &AccountInfo,
+) -> Result<MixContentRecord, ProgramError> {
+    let account_type: ShihonAccountType =
+        try_from_slice_unchecked(&mix_content_record_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::MixContentRecordV1 {
+        let mix_content_record_data_v1 =
+            get_account_data::<MixContentRecordV1>(program_id, mix_content_record_info)?;
+        return Ok(mix_content_record_data_v1.into());
     }
 
-    Ok(proposal_data)
+    get_account_data::<MixContentRecord>(program_id, mix_content_record_info)
 }
 
-/// Deserializes rate option and validates it belongs to the given two content
-pub fn get_content_metadata_for_rating(
+/// Deserializes MixContentRecord account and validates its PDA
+pub fn get_mix_content_record_data_for_seeds(
     program_id: &Pubkey,
-    proposal_info: &AccountInfo,
-    governance: &Pubkey,
-) -> Result<Rate, ProgramError> {
-    let proposal_data = get_proposal_data(program_id, proposal_info)?;
-
-    if proposal_data.governance != *governance {
-        return Err(ShihonError::InvalidGovernanceForProposal.into());
+    mix_content_record_info: &AccountInfo,
+    rater_candidate: &Pubkey,
+    buddy_candidate: &Pubkey,
+) -> Result<MixContentRecord, ProgramError> {
+    let mix_content_record_data = get_mix_content_record_data(program_id, mix_content_record_info)?;
+
+    let mix_content_record_address_seeds =
+        get_mix_content_record_address_seeds(rater_candidate, buddy_candidate);
+
+    // V1 accounts don't store the bump and it's derived from the seeds
+    let bump = if mix_content_record_data.account_type == ShihonAccountType::MixContentRecordV1 {
+        find_bump_seed(program_id, &mix_content_record_address_seeds)
+    } else {
+        mix_content_record_data.bump
+    };
+
+    if !is_valid_pda(
+        program_id,
+        mix_content_record_info.key,
+        &mix_content_record_address_seeds,
+        bump,
+    ) {
+        return Err(ShihonError::InvalidMixContentRecordAddress.into());
     }
 
-    Ok(proposal_data)
+    Ok(mix_content_record_data)
 }
 
-/// Returns Rate Option PDA seeds
-pub fn get_rate_option_address_seeds<'a>(
-    governance: &'a Pubkey,
-    governing_token_mint: &'a Pubkey,
-    proposal_index_le_bytes: &'a [u8],
-) -> [&'a [u8]; 4] {
-    [
-        PROGRAM_AUTHORITY_SEED,
-        governance.as_ref(),
-        governing_token_mint.as_ref(),
-        proposal_index_le_bytes,
-    ]
-}
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_mix_content_record() -> MixContentRecord {
+        MixContentRecord {
+            account_type: ShihonAccountType::MixContentRecordV2,
+            bump: 255,
+            encrypted_link: "link".to_string(),
+            rater_candidate: Pubkey::new_unique(),
+            buddy_candidate: Pubkey::new_unique(),
+            option_mix_result: OptionMixResult::Succeeded,
+            finger_print_of_mixed_content: "finger print".to_string(),
+            init_bc_token: Pubkey::new_unique(),
+            buddy_bc_token: Pubkey::new_unique(),
+            time_shift_a: 10,
+            time_shift_b: 20,
+            range_a: None,
+            range_b: None,
+            promoted_bc_token: None,
+        }
+    }
 
-/// Returns rate option PDA address
-pub fn get_rate_option_address<'a>(
-    program_id: &Pubkey,
-    governance: &'a Pubkey,
-    governing_token_mint: &'a Pubkey,
-    proposal_index_le_bytes: &'a [u8],
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_proposal_address_seeds(governance, governing_token_mint, proposal_index_le_bytes),
-        program_id,
-    )
-    .0
-}
+    #[test]
+    fn test_assert_can_promote() {
+        // Arrange
+        let mix_content_record = create_test_mix_content_record();
 
-/// Assert can create rate options for holding some key
-pub fn assert_valid_rate_options(options: &[String], vote_type: &R) -> Result<(), ProgramError> {
-    if options.is_empty() {
-        return Err(ShihonError::InvalidProposalOptions.into());
-    }
+        // Act
+        let result = mix_content_record.assert_can_promote();
 
-    if let RateType::MultiChoice(n) = *vote_type {
-        if options.len() == 1 || n as usize != options.len() {
-            return Err(ShihonError::InvalidProposalOptions.into());
-        }
+        // Assert
+        assert_eq!(result, Ok(()));
     }
 
-    if options.iter().any(|o| o.is_empty()) {
-        return Err(ShihonError::InvalidProposalOptions.into());
+    #[test]
+    fn test_assert_can_promote_with_defeated_mix_error() {
+        // Arrange
+        let mix_content_record = MixContentRecord {
+            option_mix_result: OptionMixResult::Defeated,
+            ..create_test_mix_content_record()
+        };
+
+        // Act
+        let err = mix_content_record.assert_can_promote().err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::MixContentNotSucceeded.into());
     }
 
-    Ok(())
+    #[test]
+    fn test_assert_can_promote_with_promoted_mix_error() {
+        // Arrange
+        let mix_content_record = MixContentRecord {
+            promoted_bc_token: Some(Pubkey::new_unique()),
+            ..create_test_mix_content_record()
+        };
+
+        // Act
+        let err = mix_content_record.assert_can_promote().err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::MixContentAlreadyPromoted.into());
+    }
 }
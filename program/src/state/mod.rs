@@ -6,8 +6,13 @@ pub mod candidate_limit_record;
 pub mod cc_vote_record;
 pub mod enums;
 pub mod kicker_coin_owner_record;
+pub mod legacy;
 pub mod mix_content_record;
 pub mod outside_buyer_record;
+pub mod pinhole;
 pub mod rate_option;
 pub mod rate_other_record;
+pub mod ring;
+pub mod shihon_config;
 pub mod tanistry;
+pub mod triple_multisig;
@@ -1,29 +1,32 @@
 //! Token Owner Record Account
 
-use std::slice::Iter;
-
 use crate::{
     error::ShihonError,
-    state::{bc_token::BcToken, enums::ShihonAccountType},
-    PROGRAM_AUTHORITY_SEED,
+    pda::{find_bump_seed, get_outside_buyer_record_address_seeds, is_valid_pda},
+    state::{
+        enums::{ShihonAccountType, TanistryState},
+        legacy::{OutsideBuyerRecordV1, OutsideBuyerRecordV2},
+    },
 };
 
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::{next_account_info, AccountInfo},
-    program_error::ProgramError,
-    program_pack::IsInitialized,
-    pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{get_account_data, AccountMaxSize};
 
-/// Account PDA seeds: ['shihon', bcToken, token_mint, token_owner ]
+/// Account PDA seeds: ['outside-buyer-record', tanistry, outside_buyer_token_mint, outside_buyer_token_owner]
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct OutsideBuyerRecord {
     /// OutsideBuyerRecord account type
     pub account_type: ShihonAccountType,
 
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
     /// The Tanistry the OutsideBuyerRecord belongs to
     pub tanistry: Pubkey,
 
@@ -36,8 +39,14 @@ pub struct OutsideBuyerRecord {
     /// This amount is the voter weight used when voting on rater
     pub outside_buyer_token_deposit_amount: u64,
 
+    /// The protocol fee taken from the outside buyer's deposit into the treasury
+    pub outside_buyer_fee_amount: u64,
+
+    /// Indicates whether the deposit was refunded from the cancelled Tanistry
+    pub is_refunded: bool,
+
     /// Reserved space for future versions
-    pub reserved: [u8; 7],
+    pub reserved: [u8; 6],
 }
 
 impl AccountMaxSize for OutsideBuyerRecord {
@@ -48,177 +57,139 @@ impl AccountMaxSize for OutsideBuyerRecord {
 
 impl IsInitialized for OutsideBuyerRecord {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::OutsideBuyerRecord
+        self.account_type == ShihonAccountType::OutsideBuyerRecordV3
     }
 }
 ///TODO: not yet fix this associated functions
 /// I need pull the data from RateOption for making these functions
 impl OutsideBuyerRecord {
-    /// Checks whether create OutsideBuyerRecord
-    pub fn assert_can_create_outside_buyer_record() {
-        unimplemented!();
-    }
-
-    /// Checks whether the provided exceeded rating token Authority signed transaction
-    pub fn assert_outside_buyer_token_owner(
-        &self,
-        outside_buyer_authority_info: &AccountInfo,
-    ) -> Result<(), ProgramError> {
-        if outside_buyer_authority_info.is_signer {
-            if &self.outside_buyer_token_owner == outside_buyer_authority_info.key {
-                return Ok(());
-            }
+    /// Serializes the account into the layout of its version
+    /// V1 and V2 accounts are written back in their own layout until they are migrated
+    pub fn serialize<W: Write>(self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::OutsideBuyerRecordV1 {
+            let outside_buyer_record_data_v1: OutsideBuyerRecordV1 = self.into();
+            BorshSerialize::serialize(&outside_buyer_record_data_v1, writer)?;
+        } else if self.account_type == ShihonAccountType::OutsideBuyerRecordV2 {
+            let outside_buyer_record_data_v2: OutsideBuyerRecordV2 = self.into();
+            BorshSerialize::serialize(&outside_buyer_record_data_v2, writer)?;
+        } else {
+            BorshSerialize::serialize(&self, writer)?;
         }
-        Err(ShihonError::GoverningTokenOwnerOrDelegateMustSign.into())
+
+        Ok(())
     }
 
-    /// Asserts TokenOwner has enough tokens to be allowed to create proposal and doesn't have any outstanding proposals
-    pub fn assert_can_buy_exceeded_rating_token(
-        &self,
-        bc_token_data: &BcToken,
-        config: &GovernanceConfig,
-        voter_weight: u64,
-    ) -> Result<(), ProgramError> {
-        let min_weight_to_create_proposal = if self.outside_buyer_token_mint
-            == bc_token_data.community_mint
-        {
-            config.min_community_tokens_to_create_proposal
-        } else if Some(self.outside_buyer_token_mint) == bc_token_data.config.outside_buyer_mint {
-            config.min_council_tokens_to_create_proposal
+    /// Returns the PDA bump of the OutsideBuyerRecord
+    /// V1 accounts don't store the bump and it's derived from the seeds
+    pub fn get_bump(&self, program_id: &Pubkey) -> u8 {
+        if self.account_type == ShihonAccountType::OutsideBuyerRecordV1 {
+            find_bump_seed(
+                program_id,
+                &get_outside_buyer_record_address_seeds(
+                    &self.tanistry,
+                    &self.outside_buyer_token_mint,
+                    &self.outside_buyer_token_owner,
+                ),
+            )
         } else {
-            return Err(ShihonError::InvalidGoverningTokenMint.into());
-        };
-
-        if voter_weight < min_weight_to_create_proposal {
-            return Err(ShihonError::NotEnoughTokensToCreateProposal.into());
+            self.bump
         }
-
-        // The number of outstanding proposals is currently restricted to 10
-        // If there is a need to change it in the future then it should be added to realm or governance config
-        if self.outstanding_proposal_count >= 10 {
-            return Err(ShihonError::TooManyOutstandingProposals.into());
-        }
-
-        Ok(())
     }
 
-    /// Asserts TokenOwner has enough tokens to be allowed to create governance
-    pub fn assert_can_sell_own_exceeded_rating_token(
+    /// Checks the deposit can be refunded from the Tanistry in the given state
+    /// The deposits of a settled Tanistry are shared by the candidates, only a cancelled Tanistry refunds them
+    pub fn assert_can_claim_refund(
         &self,
-        bc_token_data: &BcToken,
-        voter_weight: u64,
+        tanistry_state: &TanistryState,
     ) -> Result<(), ProgramError> {
-        let min_weight_to_create_governance =
-            if self.outside_buyer_token_mint == bc_token_data.community_mint {
-                bc_token_data
-                    .config
-                    .min_community_tokens_to_create_governance
-            } else if Some(self.outside_buyer_token_mint) == bc_token_data.config.council_mint {
-                // For council tokens it's enough to be in possession of any number of tokens
-                1
-            } else {
-                return Err(ShihonError::InvalidGoverningTokenMint.into());
-            };
-
-        if voter_weight < min_weight_to_create_governance {
-            return Err(ShihonError::NotEnoughTokensToCreateGovernance.into());
+        if *tanistry_state != TanistryState::Cancelled {
+            return Err(ShihonError::OutsideBuyerRefundOnlyFromCancelledTanistry.into());
         }
 
-        Ok(())
-    }
-
-    /// Determine the multiplier for the purchased tokens and set the refundable period.
-    /// Asserts TokenOwner can withdraw tokens from bought exceeded token
-    pub fn assert_can_refund_for_exceeded_rating_tokens(&self) -> Result<(), ProgramError> {
-        if self.unrelinquished_votes_count > 0 {
-            return Err(ShihonError::AllVotesMustBeRelinquishedToWithdrawGoverningTokens.into());
+        // V1 and V2 records have no room for the refund flag and have to be migrated first
+        if self.account_type != ShihonAccountType::OutsideBuyerRecordV3 {
+            return Err(ShihonError::OutsideBuyerRecordMustBeMigrated.into());
         }
 
-        if self.outstanding_proposal_count > 0 {
-            return Err(ShihonError::AllProposalsMustBeFinalisedToWithdrawGoverningTokens.into());
+        if self.is_refunded {
+            return Err(ShihonError::RefundAlreadyClaimed.into());
         }
 
         Ok(())
     }
 
-    fn get_enable_sellout_value() -> u64 {
-        unimplemented!();
-    }
-
-    fn get_already_sold_out_value() -> u64 {
+    /// Checks whether create OutsideBuyerRecord
+    pub fn assert_can_create_outside_buyer_record() {
         unimplemented!();
     }
-}
 
-/// Returns OutsideBuyerRecord PDA address
-pub fn get_outside_buyer_token_owner_record_address(
-    program_id: &Pubkey,
-    rater_pubkey: &Pubkey,
-    outside_buyer_token_mint: &Pubkey,
-    outside_buyer_token_owner: &Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_outside_buyer_token_owner_record_address_seeds(
-            rater_pubkey,
-            outside_buyer_token_mint,
-            outside_buyer_token_owner,
-        ),
-        program_id,
-    )
-    .0
-}
+    /// Checks whether the provided exceeded rating token Authority signed transaction
+    pub fn assert_outside_buyer_token_owner(
+        &self,
+        outside_buyer_authority_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if outside_buyer_authority_info.is_signer
+            && &self.outside_buyer_token_owner == outside_buyer_authority_info.key
+        {
+            return Ok(());
+        }
 
-/// Returns OutsideBuyerRecord PDA seeds
-pub fn get_outside_buyer_token_owner_record_address_seeds<'a>(
-    rater_pubkey: &'a Pubkey,
-    outside_buyer_token_mint: &'a Pubkey,
-    outside_buyer_token_owner: &'a Pubkey,
-) -> [&'a [u8]; 4] {
-    [
-        PROGRAM_AUTHORITY_SEED,
-        rater_pubkey.as_ref(),
-        outside_buyer_token_mint.as_ref(),
-        outside_buyer_token_owner.as_ref(),
-    ]
+        Err(ShihonError::OutsideBuyerTokenOwnerMustSign.into())
+    }
 }
 
 /// Deserializes OutsideBuyerRecord account and checks owner program
+/// V1 and V2 accounts are translated into the current OutsideBuyerRecord
 pub fn get_outside_buyer_token_owner_record_data(
     program_id: &Pubkey,
     outside_buyer_token_owner_record_info: &AccountInfo,
 ) -> Result<OutsideBuyerRecord, ProgramError> {
-    get_account_data::<OutsideBuyerRecord>(program_id, outside_buyer_token_owner_record_info)
-}
+    let account_type: ShihonAccountType =
+        try_from_slice_unchecked(&outside_buyer_token_owner_record_info.data.borrow())?;
 
-/// Deserializes OutsideBuyerRecord account and checks its PDA against the provided seeds
-pub fn get_outside_buyer_token_owner_record_data_for_seeds(
-    program_id: &Pubkey,
-    outside_buyer_token_owner_record_info: &AccountInfo,
-    outside_buyer_token_owner_record_seeds: &[&[u8]],
-) -> Result<OutsideBuyerRecord, ProgramError> {
-    let (outside_buyer_token_owner_record_address, _) =
-        Pubkey::find_program_address(outside_buyer_token_owner_record_seeds, program_id);
+    if account_type == ShihonAccountType::OutsideBuyerRecordV1 {
+        let outside_buyer_record_data_v1 = get_account_data::<OutsideBuyerRecordV1>(
+            program_id,
+            outside_buyer_token_owner_record_info,
+        )?;
+        return Ok(outside_buyer_record_data_v1.into());
+    }
 
-    if outside_buyer_token_owner_record_address != *outside_buyer_token_owner_record_info.key {
-        return Err(ShihonError::InvalidOutsideBuyerRecordAccountAddress.into());
+    if account_type == ShihonAccountType::OutsideBuyerRecordV2 {
+        let outside_buyer_record_data_v2 = get_account_data::<OutsideBuyerRecordV2>(
+            program_id,
+            outside_buyer_token_owner_record_info,
+        )?;
+        return Ok(outside_buyer_record_data_v2.into());
     }
 
-    get_outside_buyer_token_owner_record_data(program_id, outside_buyer_token_owner_record_info)
+    get_account_data::<OutsideBuyerRecord>(program_id, outside_buyer_token_owner_record_info)
 }
 
-/// Deserializes OutsideBuyerRecord account and asserts it belongs to
-pub fn get_outside_buyer_token_owner_record_data_for_bc_token(
+/// Deserializes OutsideBuyerRecord account and validates its PDA
+pub fn get_outside_buyer_token_owner_record_data_for_seeds(
     program_id: &Pubkey,
     outside_buyer_token_owner_record_info: &AccountInfo,
-    rater_pubkey: &Pubkey,
+    tanistry: &Pubkey,
+    outside_buyer_token_mint: &Pubkey,
+    outside_buyer_token_owner: &Pubkey,
 ) -> Result<OutsideBuyerRecord, ProgramError> {
     let outside_buyer_token_owner_record_data = get_outside_buyer_token_owner_record_data(
         program_id,
         outside_buyer_token_owner_record_info,
     )?;
 
-    if outside_buyer_token_owner_record_data.rater_pubkey != *rater_pubkey {
-        return Err(ShihonError::InvalidRealmForOutsideBuyerRecord.into());
+    if !is_valid_pda(
+        program_id,
+        outside_buyer_token_owner_record_info.key,
+        &get_outside_buyer_record_address_seeds(
+            tanistry,
+            outside_buyer_token_mint,
+            outside_buyer_token_owner,
+        ),
+        outside_buyer_token_owner_record_data.get_bump(program_id),
+    ) {
+        return Err(ShihonError::InvalidOutsideBuyerRecordAddress.into());
     }
 
     Ok(outside_buyer_token_owner_record_data)
@@ -239,7 +210,7 @@ pub fn get_outside_buyer_token_owner_record_data_for_rater_and_outside_buyer_min
         )?;
 
     if outside_buyer_token_owner_record_data.outside_buyer_token_mint != *outside_buyer_token_mint {
-        return Err(ShihonError::InvalidGoverningMintForOutsideBuyerRecord.into());
+        return Err(ShihonError::InvalidMintForOutsideBuyerRecord.into());
     }
 
     Ok(outside_buyer_token_owner_record_data)
@@ -252,7 +223,7 @@ pub fn get_outside_buyer_token_owner_record_data_for_content_owner(
     content_owner: &Pubkey,
 ) -> Result<OutsideBuyerRecord, ProgramError> {
     if outside_buyer_token_owner_record_info.key != content_owner {
-        return Err(ShihonError::InvalidProposalOwnerAccount.into());
+        return Err(ShihonError::InvalidContentOwnerForOutsideBuyerRecord.into());
     }
 
     get_outside_buyer_token_owner_record_data(program_id, outside_buyer_token_owner_record_info)
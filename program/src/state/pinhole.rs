@@ -0,0 +1,126 @@
+//! Pinhole Account
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+    pubkey::Pubkey,
+};
+use spl_governance_tools::account::{get_account_data, AccountMaxSize};
+
+use crate::{
+    error::ShihonError,
+    pda::{get_pinhole_address_seeds, is_valid_pda},
+    state::enums::{PinholeSide, ShihonAccountType},
+};
+
+/// Pinhole Account PDA seeds: ['pinhole', kicker_coin_owner_record, pinhole_authority]
+/// A one-time address the kicker or the coordinator drops for the round of the KickerCoinOwnerRecord
+/// The parties address each other's pinhole instead of the main wallet and
+/// only the program resolves the main wallet behind a pinhole when it pays out
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct Pinhole {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
+    /// The KickerCoinOwnerRecord the pinhole was dropped for
+    pub kicker_coin_owner_record: Pubkey,
+
+    /// The round of the KickerCoinOwnerRecord the pinhole was dropped in
+    pub round: u8,
+
+    /// The party the pinhole stands for
+    pub side: PinholeSide,
+
+    /// The one-time key which signs on behalf of the party
+    pub pinhole_authority: Pubkey,
+}
+
+impl AccountMaxSize for Pinhole {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(68)
+    }
+}
+
+impl IsInitialized for Pinhole {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::Pinhole
+    }
+}
+
+impl Pinhole {
+    /// Asserts the given account is the signing authority of the pinhole
+    pub fn assert_pinhole_authority_is_signer(
+        &self,
+        pinhole_authority_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if self.pinhole_authority != *pinhole_authority_info.key {
+            return Err(ShihonError::InvalidPinholeAddress.into());
+        }
+
+        if !pinhole_authority_info.is_signer {
+            return Err(ShihonError::PinholeAuthorityMustSign.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Deserializes Pinhole account and checks owner program
+pub fn get_pinhole_data(
+    program_id: &Pubkey,
+    pinhole_info: &AccountInfo,
+) -> Result<Pinhole, ProgramError> {
+    get_account_data::<Pinhole>(program_id, pinhole_info)
+}
+
+/// Deserializes Pinhole account, validates its PDA and checks it was dropped for the given KickerCoinOwnerRecord
+pub fn get_pinhole_data_for_kicker_coin_owner_record(
+    program_id: &Pubkey,
+    pinhole_info: &AccountInfo,
+    kicker_coin_owner_record: &Pubkey,
+) -> Result<Pinhole, ProgramError> {
+    let pinhole_data = get_pinhole_data(program_id, pinhole_info)?;
+
+    if pinhole_data.kicker_coin_owner_record != *kicker_coin_owner_record {
+        return Err(ShihonError::InvalidPinholeForKickerCoinOwnerRecord.into());
+    }
+
+    if !is_valid_pda(
+        program_id,
+        pinhole_info.key,
+        &get_pinhole_address_seeds(kicker_coin_owner_record, &pinhole_data.pinhole_authority),
+        pinhole_data.bump,
+    ) {
+        return Err(ShihonError::InvalidPinholeAddress.into());
+    }
+
+    Ok(pinhole_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_max_size() {
+        // Arrange
+        let pinhole = Pinhole {
+            account_type: ShihonAccountType::Pinhole,
+            bump: 255,
+            kicker_coin_owner_record: Pubkey::new_unique(),
+            round: 1,
+            side: PinholeSide::Coordinator,
+            pinhole_authority: Pubkey::new_unique(),
+        };
+
+        // Act
+        let size = pinhole.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(pinhole.get_max_size(), Some(size));
+    }
+}
@@ -1,134 +1,211 @@
 //! Rate Option
 
+use crate::state::legacy::RateOptionV1;
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
-    pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{get_account_data, AccountMaxSize};
 
-use crate::{error::ShihonError, PROGRAM_AUTHORITY_SEED};
-
-use crate::state::enums::ShihonAccountType;
+use crate::{
+    error::ShihonError,
+    pda::{find_bump_seed, get_rate_option_address_seeds, is_valid_pda},
+    state::{enums::ShihonAccountType, mix_content_record::MixContentRecord},
+};
 
-/// Account PDA seeds: []
+/// Account PDA seeds: ['rate-option', mix_content_record, issuer_roydamna]
+/// The option a Roydamna issues on a MixContentRecord it takes part in
+/// It governs the MixContentRecord and the RateOtherRecord of the rater and buddy pair
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct RateOption {
     /// account type
     pub account_type: ShihonAccountType,
 
-    /// Which Roydamna created this RateOption
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
+    /// The CandidateLimitRecord of the Roydamna which created this RateOption
     pub issuer_roydamna: Pubkey,
 
     /// MixContentRecord
     pub mix_content_record: Pubkey,
 
-    /// RateOtherRecord
+    /// RateOtherRecord of the rater and buddy pair of the MixContentRecord
     pub rate_other_record: Pubkey,
 
-    /// number of issue
+    /// The issue number of the RateOption among the RateOptions the Roydamna issued
     pub number_of_issue: u8,
 
     /// Key Holder
     pub buddy_candidate: Pubkey,
 
+    /// The init content's bcToken of the MixContentRecord
     pub init_content: Pubkey,
+
+    /// Indicates whether the Roydamna signed off the RateOption
+    pub signed_off: bool,
 }
 
-impl AccountMaxSize for RateOption {}
+impl AccountMaxSize for RateOption {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(164)
+    }
+}
 
 impl IsInitialized for RateOption {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::RateOption
+        self.account_type == ShihonAccountType::RateOptionV2
     }
 }
 
 impl RateOption {
-    /// Checks signatory hasn't signed off yet and is transaction signer
-    pub fn assert_can_sign_off(&self, signatory_info: &AccountInfo) -> Result<(), ProgramError> {
-        if self.signed_off {
-            return Err(ShihonError::SignatoryAlreadySignedOff.into());
+    /// Serializes the account into the current layout
+    /// V1 accounts have no room for the current fields and have to be migrated before they are updated
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::RateOptionV1 {
+            return Err(ShihonError::RateOptionMustBeMigrated.into());
         }
 
-        if !signatory_info.is_signer {
-            return Err(ShihonError::SignatoryMustSign.into());
-        }
+        BorshSerialize::serialize(self, writer)?;
 
         Ok(())
     }
 
-    /// Checks signatory can be removed from Proposal
-    pub fn assert_can_remove_signatory(&self) -> Result<(), ProgramError> {
+    /// Checks the RateOption hasn't been signed off yet
+    pub fn assert_not_signed_off(&self) -> Result<(), ProgramError> {
         if self.signed_off {
-            return Err(ShihonError::SignatoryAlreadySignedOff.into());
+            return Err(ShihonError::RateOptionAlreadySignedOff.into());
         }
 
         Ok(())
     }
-}
 
-/// Returns Rate Option PDA seeds
-pub fn get_signatory_record_address_seeds<'a>(
-    proposal: &'a Pubkey,
-    signatory: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    [
-        PROGRAM_AUTHORITY_SEED,
-        proposal.as_ref(),
-        signatory.as_ref(),
-    ]
+    /// Checks the RateOption can be signed off with the given RateOtherRecord
+    pub fn assert_can_sign_off(
+        &self,
+        rate_other_record_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        self.assert_not_signed_off()?;
+
+        if self.rate_other_record != *rate_other_record_info.key {
+            return Err(ShihonError::InvalidRateOtherRecordForRateOption.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks the RateOption can be removed
+    pub fn assert_can_remove(&self) -> Result<(), ProgramError> {
+        self.assert_not_signed_off()
+    }
 }
 
-/// Returns Rate Option PDA address
-pub fn get_signatory_record_address<'a>(
-    program_id: &Pubkey,
-    proposal: &'a Pubkey,
-    signatory: &'a Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_signatory_record_address_seeds(proposal, signatory),
-        program_id,
-    )
-    .0
+/// Asserts the given Roydamna takes part in the MixContentRecord either as the rater or the buddy
+pub fn assert_is_valid_issuer_roydamna(
+    mix_content_record_data: &MixContentRecord,
+    issuer_roydamna: &Pubkey,
+) -> Result<(), ProgramError> {
+    if mix_content_record_data.rater_candidate != *issuer_roydamna
+        && mix_content_record_data.buddy_candidate != *issuer_roydamna
+    {
+        return Err(ShihonError::InvalidRateOptionIssuer.into());
+    }
+
+    Ok(())
 }
 
-/// Deserializes Rate Option account and checks owner program
-pub fn get_signatory_record_data(
+/// Deserializes RateOption account and checks owner program
+pub fn get_rate_option_data(
     program_id: &Pubkey,
-    signatory_record_info: &AccountInfo,
+    rate_option_info: &AccountInfo,
 ) -> Result<RateOption, ProgramError> {
-    get_account_data::<RateOption>(program_id, signatory_record_info)
+    let account_type: ShihonAccountType =
+        try_from_slice_unchecked(&rate_option_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::RateOptionV1 {
+        let rate_option_data_v1 = get_account_data::<RateOptionV1>(program_id, rate_option_info)?;
+        return Ok(rate_option_data_v1.into());
+    }
+
+    get_account_data::<RateOption>(program_id, rate_option_info)
 }
 
-/// Deserializes Rate Option  and validates its PDA
-pub fn get_signatory_record_data_for_seeds(
+/// Deserializes RateOption account and validates its PDA
+pub fn get_rate_option_data_for_seeds(
     program_id: &Pubkey,
-    signatory_record_info: &AccountInfo,
-    proposal: &Pubkey,
-    signatory: &Pubkey,
+    rate_option_info: &AccountInfo,
+    mix_content_record: &Pubkey,
+    issuer_roydamna: &Pubkey,
 ) -> Result<RateOption, ProgramError> {
-    let (signatory_record_address, _) = Pubkey::find_program_address(
-        &get_signatory_record_address_seeds(proposal, signatory),
-        program_id,
-    );
+    let rate_option_data = get_rate_option_data(program_id, rate_option_info)?;
 
-    if signatory_record_address != *signatory_record_info.key {
-        return Err(ShihonError::InvalidSignatoryAddress.into());
+    let rate_option_address_seeds =
+        get_rate_option_address_seeds(mix_content_record, issuer_roydamna);
+
+    // V1 accounts don't store the bump and it's derived from the seeds
+    let bump = if rate_option_data.account_type == ShihonAccountType::RateOptionV1 {
+        find_bump_seed(program_id, &rate_option_address_seeds)
+    } else {
+        rate_option_data.bump
+    };
+
+    if !is_valid_pda(
+        program_id,
+        rate_option_info.key,
+        &rate_option_address_seeds,
+        bump,
+    ) {
+        return Err(ShihonError::InvalidRateOptionAddress.into());
     }
 
-    get_signatory_record_data(program_id, signatory_record_info)
+    Ok(rate_option_data)
 }
 
-// going to move to tanistry.rs
-fn calculate_diversity_index_on_tanistry() -> bool {
-    unimplemented!();
-}
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_rate_option() -> RateOption {
+        RateOption {
+            account_type: ShihonAccountType::RateOptionV2,
+            bump: 255,
+            issuer_roydamna: Pubkey::new_unique(),
+            mix_content_record: Pubkey::new_unique(),
+            rate_other_record: Pubkey::new_unique(),
+            number_of_issue: 1,
+            buddy_candidate: Pubkey::new_unique(),
+            init_content: Pubkey::new_unique(),
+            signed_off: false,
+        }
+    }
 
-fn get_higher_diversity_index_candidate_list_sort() -> Vec<Pubkey> {
-    unimplemented!();
-}
+    #[test]
+    fn test_max_size() {
+        // Arrange
+        let rate_option = create_test_rate_option();
 
-fn get_upright_token_index() -> u64 {
-    unimplemented!();
+        // Act
+        let size = rate_option.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(rate_option.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_assert_can_remove_with_signed_off_rate_option_error() {
+        // Arrange
+        let rate_option = RateOption {
+            signed_off: true,
+            ..create_test_rate_option()
+        };
+
+        // Act
+        let err = rate_option.assert_can_remove().err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::RateOptionAlreadySignedOff.into());
+    }
 }
@@ -1,27 +1,32 @@
 //! Signatory Record
 
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
+use crate::state::legacy::RateOtherRecordV1;
 use solana_program::{
-    account_info::AccountInfo,
-    clock::{Slot, UnixTimestamp},
-    program_error::ProgramError,
-    program_pack::IsInitialized,
-    pubkey::Pubkey,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, clock::UnixTimestamp,
+    program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey,
 };
 use spl_governance_tools::account::{get_account_data, AccountMaxSize};
 
-use crate::{error::ShihonError, PROGRAM_AUTHORITY_SEED};
+use crate::{
+    error::ShihonError,
+    pda::{find_bump_seed, get_rate_other_record_address_seeds, is_valid_pda},
+};
 
 use crate::state::enums::ShihonAccountType;
 
-/// Account PDA seeds: ['shihon', rate, signatory]
+/// Account PDA seeds: ['rate-other-record', rater, rated_content]
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct RateOtherRecord {
     /// Rate account type
     pub account_type: ShihonAccountType,
 
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
     /// Mix content record
     pub mix_content_record: Pubkey,
 
@@ -42,28 +47,40 @@ impl AccountMaxSize for RateOtherRecord {}
 
 impl IsInitialized for RateOtherRecord {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::RateOtherRecord
+        self.account_type == ShihonAccountType::RateOtherRecordV2
     }
 }
 
 impl RateOtherRecord {
-    /// Checks signatory hasn't signed off yet and is transaction signer
-    pub fn assert_can_rate(&self, rating_info: &AccountInfo) -> Result<(), ProgramError> {
-        if self.is_initialized {
-            return Err(ShihonError::SignatoryAlreadySignedOff.into());
+    /// Serializes the account into the current layout
+    /// V1 accounts have no room for the current fields and have to be migrated before they are updated
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::RateOtherRecordV1 {
+            return Err(ShihonError::RateOtherRecordMustBeMigrated.into());
+        }
+
+        BorshSerialize::serialize(self, writer)?;
+
+        Ok(())
+    }
+
+    /// Checks the rating hasn't been casted yet and the rater is transaction signer
+    pub fn assert_can_rate(&self, rater_info: &AccountInfo) -> Result<(), ProgramError> {
+        if self.rating_time.is_some() {
+            return Err(ShihonError::RatingAlreadyCasted.into());
         }
 
-        if !rating_info.rater_pubkey {
-            return Err(ShihonError::SignatoryMustSign.into());
+        if !rater_info.is_signer {
+            return Err(ShihonError::RaterMustSign.into());
         }
 
         Ok(())
     }
 
-    /// Checks signatory can be removed from Proposal
+    /// Checks the rating can be removed from the RateOtherRecord
     pub fn assert_can_remove_rating(&self) -> Result<(), ProgramError> {
-        if self.is_initialized {
-            return Err(ShihonError::SignatoryAlreadySignedOff.into());
+        if self.rating_time.is_some() {
+            return Err(ShihonError::RatingAlreadyCasted.into());
         }
 
         Ok(())
@@ -73,38 +90,18 @@ impl RateOtherRecord {
         unimplemented!();
     }
 
-    pub fn assert_is_valid_rating_over_zero_amount() {
-        unimplemented!();
-    }
-
     pub fn assert_can_mint_rft_as_rating_token() {
         unimplemented!();
     }
 }
 
-/// Returns RateOtherRecord PDA seeds
-pub fn get_rate_other_record_address_seeds<'a>(
-    rater_pubkey: &'a Pubkey,
-    rated_content: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    [
-        PROGRAM_AUTHORITY_SEED,
-        rater_pubkey.as_ref(),
-        rated_content.as_ref(),
-    ]
-}
+/// Asserts the rating puts at least one point on the buddy content
+pub fn assert_is_valid_rating(rating: u64) -> Result<(), ProgramError> {
+    if rating == 0 {
+        return Err(ShihonError::InvalidRatingAmount.into());
+    }
 
-/// Returns RateOtherRecord PDA address
-pub fn get_rate_other_record_address<'a>(
-    program_id: &Pubkey,
-    rater_pubkey: &'a Pubkey,
-    rated_content: &'a Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_rate_other_record_address_seeds(rater_pubkey, rated_content),
-        program_id,
-    )
-    .0
+    Ok(())
 }
 
 /// Deserializes RateOtherRecord account and checks owner program
@@ -112,24 +109,67 @@ pub fn get_rate_other_record_data(
     program_id: &Pubkey,
     rate_other_record_info: &AccountInfo,
 ) -> Result<RateOtherRecord, ProgramError> {
+    let account_type: ShihonAccountType =
+        try_from_slice_unchecked(&rate_other_record_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::RateOtherRecordV1 {
+        let rate_other_record_data_v1 =
+            get_account_data::<RateOtherRecordV1>(program_id, rate_other_record_info)?;
+        return Ok(rate_other_record_data_v1.into());
+    }
+
     get_account_data::<RateOtherRecord>(program_id, rate_other_record_info)
 }
 
-/// Deserializes RateOtherRecord  and validates its PDA
-pub fn get_rete_other_record_data_for_seeds(
+/// Deserializes RateOtherRecord account and validates its PDA
+pub fn get_rate_other_record_data_for_seeds(
     program_id: &Pubkey,
     rate_other_record_info: &AccountInfo,
-    rater_pubkey: &Pubkey,
+    rater: &Pubkey,
     rated_content: &Pubkey,
 ) -> Result<RateOtherRecord, ProgramError> {
-    let (rate_other_record_address, _) = Pubkey::find_program_address(
-        &get_rate_other_record_address_seeds(rated_content, rater_pubkey),
+    let rate_other_record_data = get_rate_other_record_data(program_id, rate_other_record_info)?;
+
+    let rate_other_record_address_seeds = get_rate_other_record_address_seeds(rater, rated_content);
+
+    // V1 accounts don't store the bump and it's derived from the seeds
+    let bump = if rate_other_record_data.account_type == ShihonAccountType::RateOtherRecordV1 {
+        find_bump_seed(program_id, &rate_other_record_address_seeds)
+    } else {
+        rate_other_record_data.bump
+    };
+
+    if !is_valid_pda(
         program_id,
-    );
+        rate_other_record_info.key,
+        &rate_other_record_address_seeds,
+        bump,
+    ) {
+        return Err(ShihonError::InvalidRateOtherRecordAddress.into());
+    }
+
+    Ok(rate_other_record_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    if rate_other_record_address != *rate_other_record_info.key {
-        return Err(ShihonError::InvalidSignatoryAddress.into());
+    #[test]
+    fn test_assert_is_valid_rating() {
+        // Act
+        let result = assert_is_valid_rating(1);
+
+        // Assert
+        assert_eq!(result, Ok(()));
     }
 
-    get_rate_other_record_data(program_id, rate_other_record_info)
+    #[test]
+    fn test_assert_is_valid_rating_with_zero_rating_error() {
+        // Act
+        let err = assert_is_valid_rating(0).err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidRatingAmount.into());
+    }
 }
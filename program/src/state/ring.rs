@@ -0,0 +1,388 @@
+//! Ring Account
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, clock::Slot, program_error::ProgramError,
+    program_pack::IsInitialized, pubkey::Pubkey,
+};
+use spl_governance_tools::account::{get_account_data, AccountMaxSize};
+
+use crate::{
+    error::ShihonError,
+    pda::{get_ring_address_seeds, is_valid_pda},
+    state::{
+        cc_vote_record::CC_VOTE_COUNTING_PERIOD,
+        enums::{ShihonAccountType, VoteSource},
+    },
+};
+
+/// Ring Account PDA seeds: ['ring', first_tanistry]
+/// A Ring groups the chain of Tanistries started from the first Tanistry
+/// Every Crown kicks the KickerCoin on and the next Tanistry of the chain links back with previous_tanistry_id
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct Ring {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
+    /// The Tanistry the chain starts from
+    pub first_tanistry: Pubkey,
+
+    /// The latest crowned Tanistry of the chain
+    pub last_crowned_tanistry: Option<Pubkey>,
+
+    /// The number of crowned Tanistries of the chain
+    pub crowned_tanistry_count: u32,
+
+    /// The total amount the candidates and the outside buyers deposited into the crowned Tanistries
+    pub cumulative_deposit_amount: u64,
+
+    /// The total RFT the candidates rated each other with in the crowned Tanistries
+    pub cumulative_rft_volume: u64,
+
+    /// Total weight of the CC votes casted for the ring
+    pub cc_vote_weight: u64,
+
+    /// When the system counts the CC votes casted for the ring, opened by the first vote
+    /// Every vote for the ring can be relinquished or changed until then
+    pub cc_vote_counting_time: Option<Slot>,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for Ring {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(168)
+    }
+}
+
+impl IsInitialized for Ring {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::Ring
+    }
+}
+
+impl Ring {
+    /// Asserts the given Tanistry is the next Tanistry of the chain to be crowned
+    /// The first Tanistry is crowned first and every next one must link back to the last crowned Tanistry
+    pub fn assert_is_next_tanistry(
+        &self,
+        tanistry: &Pubkey,
+        previous_tanistry: Option<Pubkey>,
+    ) -> Result<(), ProgramError> {
+        let is_next_tanistry = match self.last_crowned_tanistry {
+            None => self.first_tanistry == *tanistry,
+            Some(last_crowned_tanistry) => previous_tanistry == Some(last_crowned_tanistry),
+        };
+
+        if !is_next_tanistry {
+            return Err(ShihonError::InvalidTanistryForRing.into());
+        }
+
+        Ok(())
+    }
+
+    /// Adds the deposits and the RFT volume of the crowned Tanistry to the ring
+    pub fn add_crowned_tanistry(
+        &mut self,
+        tanistry: &Pubkey,
+        deposit_amount: u64,
+        rft_volume: u64,
+    ) -> Result<(), ProgramError> {
+        self.cumulative_deposit_amount = self
+            .cumulative_deposit_amount
+            .checked_add(deposit_amount)
+            .ok_or(ShihonError::RingVolumeOverflow)?;
+
+        self.cumulative_rft_volume = self
+            .cumulative_rft_volume
+            .checked_add(rft_volume)
+            .ok_or(ShihonError::RingVolumeOverflow)?;
+
+        self.crowned_tanistry_count = self.crowned_tanistry_count.checked_add(1).unwrap();
+        self.last_crowned_tanistry = Some(*tanistry);
+
+        Ok(())
+    }
+
+    /// Checks whether the ring is bigger than the given ring
+    /// Rings are compared by the cumulative deposit first and by the RFT volume on a tie
+    pub fn is_bigger_than(&self, ring: &Ring) -> bool {
+        (self.cumulative_deposit_amount, self.cumulative_rft_volume)
+            > (ring.cumulative_deposit_amount, ring.cumulative_rft_volume)
+    }
+
+    /// Asserts the target ring can be pulled, only a ring smaller than your ring can be pulled
+    pub fn assert_can_pull(&self, target_ring: &Ring) -> Result<(), ProgramError> {
+        if !self.is_bigger_than(target_ring) {
+            return Err(ShihonError::CannotPullBiggerRing.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the target ring can be pushed, only a ring bigger than your ring can be pushed
+    pub fn assert_can_push(&self, target_ring: &Ring) -> Result<(), ProgramError> {
+        if !target_ring.is_bigger_than(self) {
+            return Err(ShihonError::CannotPushSmallerRing.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts a voter of the ring can cast the given vote for the target ring
+    pub fn assert_can_cc_vote(
+        &self,
+        target_ring: &Ring,
+        vote: &VoteSource,
+    ) -> Result<(), ProgramError> {
+        match vote {
+            VoteSource::Pull => self.assert_can_pull(target_ring),
+            VoteSource::Push => self.assert_can_push(target_ring),
+            VoteSource::Transit | VoteSource::Snapshot => {
+                Err(ShihonError::InvalidCCVoteSource.into())
+            }
+        }
+    }
+
+    /// Adds the given voter weight to the CC vote tally of the ring
+    pub fn increase_cc_vote_weight(&mut self, voter_weight: u64) -> Result<(), ProgramError> {
+        self.cc_vote_weight = self
+            .cc_vote_weight
+            .checked_add(voter_weight)
+            .ok_or(ShihonError::CCVoteWeightOverflow)?;
+
+        Ok(())
+    }
+
+    /// Asserts the CC votes of the ring haven't been counted yet
+    pub fn assert_cc_vote_is_open(&self, current_slot: Slot) -> Result<(), ProgramError> {
+        if let Some(cc_vote_counting_time) = self.cc_vote_counting_time {
+            if current_slot >= cc_vote_counting_time {
+                return Err(ShihonError::CCVoteCountingTimeReached.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Opens the counting window shared by every CC vote of the ring on the first vote
+    /// and returns the counting time of the ring
+    pub fn open_cc_vote_counting_window(
+        &mut self,
+        current_slot: Slot,
+    ) -> Result<Slot, ProgramError> {
+        self.assert_cc_vote_is_open(current_slot)?;
+
+        let cc_vote_counting_time = *self
+            .cc_vote_counting_time
+            .get_or_insert(current_slot.checked_add(CC_VOTE_COUNTING_PERIOD).unwrap());
+
+        Ok(cc_vote_counting_time)
+    }
+
+    /// Removes the given voter weight from the CC vote tally of the ring
+    pub fn decrease_cc_vote_weight(&mut self, voter_weight: u64) -> Result<(), ProgramError> {
+        self.cc_vote_weight = self
+            .cc_vote_weight
+            .checked_sub(voter_weight)
+            .ok_or(ShihonError::CCVoteWeightOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// Deserializes Ring account and checks owner program
+pub fn get_ring_data(program_id: &Pubkey, ring_info: &AccountInfo) -> Result<Ring, ProgramError> {
+    get_account_data::<Ring>(program_id, ring_info)
+}
+
+/// Deserializes Ring account and validates its PDA
+pub fn get_ring_data_for_seeds(
+    program_id: &Pubkey,
+    ring_info: &AccountInfo,
+) -> Result<Ring, ProgramError> {
+    let ring_data = get_ring_data(program_id, ring_info)?;
+
+    if !is_valid_pda(
+        program_id,
+        ring_info.key,
+        &get_ring_address_seeds(&ring_data.first_tanistry),
+        ring_data.bump,
+    ) {
+        return Err(ShihonError::InvalidRingAddress.into());
+    }
+
+    Ok(ring_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_ring() -> Ring {
+        Ring {
+            account_type: ShihonAccountType::Ring,
+            bump: 255,
+            first_tanistry: Pubkey::new_unique(),
+            last_crowned_tanistry: None,
+            crowned_tanistry_count: 0,
+            cumulative_deposit_amount: 0,
+            cumulative_rft_volume: 0,
+            cc_vote_weight: 0,
+            cc_vote_counting_time: None,
+            reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn test_max_size() {
+        // Arrange
+        let ring = Ring {
+            last_crowned_tanistry: Some(Pubkey::new_unique()),
+            cc_vote_counting_time: Some(CC_VOTE_COUNTING_PERIOD),
+            ..create_test_ring()
+        };
+
+        // Act
+        let size = ring.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(ring.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_add_crowned_tanistry() {
+        // Arrange
+        let mut ring = create_test_ring();
+        let tanistry = ring.first_tanistry;
+
+        // Act
+        ring.add_crowned_tanistry(&tanistry, 100, 30).unwrap();
+        ring.add_crowned_tanistry(&Pubkey::new_unique(), 50, 20)
+            .unwrap();
+
+        // Assert
+        assert_eq!(ring.crowned_tanistry_count, 2);
+        assert_eq!(ring.cumulative_deposit_amount, 150);
+        assert_eq!(ring.cumulative_rft_volume, 50);
+    }
+
+    #[test]
+    fn test_assert_is_next_tanistry() {
+        // Arrange
+        let mut ring = create_test_ring();
+        let first_tanistry = ring.first_tanistry;
+
+        // Act
+        let first_result = ring.assert_is_next_tanistry(&first_tanistry, None);
+        ring.add_crowned_tanistry(&first_tanistry, 100, 30).unwrap();
+        let next_result = ring.assert_is_next_tanistry(&Pubkey::new_unique(), Some(first_tanistry));
+
+        // Assert
+        assert_eq!(first_result, Ok(()));
+        assert_eq!(next_result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_is_next_tanistry_with_unlinked_tanistry_error() {
+        // Arrange
+        let mut ring = create_test_ring();
+        let first_tanistry = ring.first_tanistry;
+        ring.add_crowned_tanistry(&first_tanistry, 100, 30).unwrap();
+
+        // Act
+        let err = ring
+            .assert_is_next_tanistry(&Pubkey::new_unique(), Some(Pubkey::new_unique()))
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidTanistryForRing.into());
+    }
+
+    #[test]
+    fn test_assert_can_pull_and_push() {
+        // Arrange
+        let ring = Ring {
+            cumulative_deposit_amount: 100,
+            cumulative_rft_volume: 10,
+            ..create_test_ring()
+        };
+        let smaller_ring = Ring {
+            cumulative_deposit_amount: 100,
+            cumulative_rft_volume: 5,
+            ..create_test_ring()
+        };
+
+        // Act
+        let pull_result = ring.assert_can_pull(&smaller_ring);
+        let push_err = ring.assert_can_push(&smaller_ring).err().unwrap();
+
+        // Assert
+        assert_eq!(pull_result, Ok(()));
+        assert_eq!(push_err, ShihonError::CannotPushSmallerRing.into());
+    }
+
+    #[test]
+    fn test_assert_can_cc_vote() {
+        // Arrange
+        let ring = Ring {
+            cumulative_deposit_amount: 100,
+            ..create_test_ring()
+        };
+        let bigger_ring = Ring {
+            cumulative_deposit_amount: 200,
+            ..create_test_ring()
+        };
+
+        // Act
+        let push_result = ring.assert_can_cc_vote(&bigger_ring, &VoteSource::Push);
+        let pull_err = ring
+            .assert_can_cc_vote(&bigger_ring, &VoteSource::Pull)
+            .err()
+            .unwrap();
+        let transit_err = ring
+            .assert_can_cc_vote(&bigger_ring, &VoteSource::Transit)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(push_result, Ok(()));
+        assert_eq!(pull_err, ShihonError::CannotPullBiggerRing.into());
+        assert_eq!(transit_err, ShihonError::InvalidCCVoteSource.into());
+    }
+
+    #[test]
+    fn test_open_cc_vote_counting_window() {
+        // Arrange
+        let mut ring = create_test_ring();
+
+        // Act
+        let first_counting_time = ring.open_cc_vote_counting_window(10).unwrap();
+        let second_counting_time = ring.open_cc_vote_counting_window(20).unwrap();
+
+        // Assert
+        assert_eq!(first_counting_time, 10 + CC_VOTE_COUNTING_PERIOD);
+        assert_eq!(second_counting_time, first_counting_time);
+    }
+
+    #[test]
+    fn test_open_cc_vote_counting_window_after_counting_time_error() {
+        // Arrange
+        let mut ring = Ring {
+            cc_vote_counting_time: Some(100),
+            ..create_test_ring()
+        };
+
+        // Act
+        let err = ring.open_cc_vote_counting_window(100).err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::CCVoteCountingTimeReached.into());
+    }
+}
@@ -0,0 +1,475 @@
+//! Shihon Config Account
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+    pubkey::Pubkey,
+};
+use spl_governance_tools::account::{get_account_data, AccountMaxSize};
+
+use crate::{
+    error::ShihonError,
+    pda::{
+        get_shihon_config_address, get_shihon_config_address_seeds, get_treasury_address,
+        is_valid_pda,
+    },
+    state::{enums::ShihonAccountType, tanistry::MAX_BPS},
+    tools::spl_token::{get_spl_token_received_amount, transfer_spl_tokens},
+};
+
+/// Minimum number of candidates a Tanistry needs to be played
+pub const MIN_CANDIDATES: u32 = 2;
+
+/// Pauses drafting and creating bcTokens
+pub const PAUSE_BC_TOKEN: u32 = 1;
+
+/// Pauses kicking and approving KickerCoin
+pub const PAUSE_KICKER_COIN: u32 = 1 << 1;
+
+/// Pauses new candidates and candidate self-rate deposits
+pub const PAUSE_CANDIDATE: u32 = 1 << 2;
+
+/// Pauses mixing and rating contents
+pub const PAUSE_MIX_AND_RATE: u32 = 1 << 3;
+
+/// Pauses outside buyers
+pub const PAUSE_OUTSIDE_BUYER: u32 = 1 << 4;
+
+/// Pauses crowning
+pub const PAUSE_CROWNING: u32 = 1 << 5;
+
+/// Pauses CC voting
+pub const PAUSE_CC_VOTE: u32 = 1 << 6;
+
+/// Pauses every pausable instruction
+/// Settling, refunds and withdrawals can't be paused so deposited funds can always be withdrawn
+pub const PAUSE_ALL: u32 = PAUSE_BC_TOKEN
+    | PAUSE_KICKER_COIN
+    | PAUSE_CANDIDATE
+    | PAUSE_MIX_AND_RATE
+    | PAUSE_OUTSIDE_BUYER
+    | PAUSE_CROWNING
+    | PAUSE_CC_VOTE;
+
+/// Tunable parameters of the Shihon deployment
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ShihonConfigArgs {
+    /// Mint of the KickerCoin kicked to the coordinators
+    pub kicker_coin_mint: Pubkey,
+
+    /// Mint of the RFT used for rating
+    pub rft_mint: Pubkey,
+
+    /// Authority of the Oracle the contents are posted to
+    pub oracle_authority: Pubkey,
+
+    /// Maximum number of candidates in a Tanistry
+    pub max_candidates: u32,
+
+    /// Minimum number of candidates a Tanistry needs by the end of the enrollment
+    /// A Tanistry with fewer candidates can be cancelled by anybody
+    pub min_candidates: u32,
+
+    /// Time limit in seconds for candidates to enroll into a Tanistry
+    pub enrollment_time: u32,
+
+    /// Fee taken from the KickerCoin kicked to the coordinator in basis points
+    pub kicker_coin_fee_bps: u16,
+
+    /// Fee taken from the candidate self-rate deposit in basis points
+    pub candidate_fee_bps: u16,
+
+    /// Fee taken from the outside buyer deposit in basis points
+    pub outside_buyer_fee_bps: u16,
+
+    /// Guardian which can pause instructions but can't unpause them
+    pub guardian: Option<Pubkey>,
+}
+
+impl ShihonConfigArgs {
+    /// Asserts the config parameters are within their valid ranges
+    pub fn assert_is_valid(&self) -> Result<(), ProgramError> {
+        if self.max_candidates < MIN_CANDIDATES {
+            return Err(ShihonError::InvalidMaxCandidates.into());
+        }
+
+        if self.min_candidates < MIN_CANDIDATES || self.min_candidates > self.max_candidates {
+            return Err(ShihonError::InvalidMinCandidates.into());
+        }
+
+        if [
+            self.kicker_coin_fee_bps,
+            self.candidate_fee_bps,
+            self.outside_buyer_fee_bps,
+        ]
+        .iter()
+        .any(|fee_bps| *fee_bps as u64 > MAX_BPS)
+        {
+            return Err(ShihonError::InvalidFeeBps.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Program-wide config of the Shihon deployment
+/// Account PDA seeds: ['shihon-config']
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ShihonConfig {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
+    /// Admin authority which can update the config
+    /// The config is created by the program upgrade authority which appoints the admin
+    pub admin_authority: Pubkey,
+
+    /// Config parameters
+    pub config: ShihonConfigArgs,
+
+    /// Bitfield of the paused instructions, see PAUSE_* flags
+    pub pause_flags: u32,
+
+    /// Reserved space for future versions
+    pub reserved: [u8; 64],
+}
+
+impl AccountMaxSize for ShihonConfig {}
+
+impl IsInitialized for ShihonConfig {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::ShihonConfig
+    }
+}
+
+impl ShihonConfig {
+    /// Asserts the given account is the admin authority and signed the transaction
+    pub fn assert_admin_authority_is_signer(
+        &self,
+        admin_authority_info: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        if self.admin_authority != *admin_authority_info.key {
+            return Err(ShihonError::InvalidAdminAuthority.into());
+        }
+
+        if !admin_authority_info.is_signer {
+            return Err(ShihonError::AdminAuthorityMustSign.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the given account is the admin authority or the guardian and signed the transaction
+    /// The guardian can only pause instructions, unpausing requires the admin authority
+    pub fn assert_can_set_pause_flags(
+        &self,
+        pause_authority_info: &AccountInfo,
+        pause_flags: u32,
+    ) -> Result<(), ProgramError> {
+        if !pause_authority_info.is_signer {
+            return Err(ShihonError::PauseAuthorityMustSign.into());
+        }
+
+        if self.admin_authority == *pause_authority_info.key {
+            return Ok(());
+        }
+
+        if self.config.guardian != Some(*pause_authority_info.key) {
+            return Err(ShihonError::InvalidPauseAuthority.into());
+        }
+
+        if self.pause_flags & !pause_flags != 0 {
+            return Err(ShihonError::GuardianCannotUnpause.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether any of the given pause flags is set
+    pub fn is_paused(&self, pause_flag: u32) -> bool {
+        self.pause_flags & pause_flag != 0
+    }
+
+    /// Asserts none of the given pause flags is set
+    pub fn assert_is_not_paused(&self, pause_flag: u32) -> Result<(), ProgramError> {
+        if self.is_paused(pause_flag) {
+            return Err(ShihonError::ShihonPaused.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Deserializes ShihonConfig account and validates its PDA
+pub fn get_shihon_config_data(
+    program_id: &Pubkey,
+    shihon_config_info: &AccountInfo,
+) -> Result<ShihonConfig, ProgramError> {
+    let shihon_config_data = get_account_data::<ShihonConfig>(program_id, shihon_config_info)?;
+
+    if !is_valid_pda(
+        program_id,
+        shihon_config_info.key,
+        &get_shihon_config_address_seeds(),
+        shihon_config_data.bump,
+    ) {
+        return Err(ShihonError::InvalidShihonConfigAddress.into());
+    }
+
+    Ok(shihon_config_data)
+}
+
+/// Deserializes ShihonConfig account if it was created and validates its PDA
+/// Returns None for a deployment without ShihonConfig account
+pub fn get_shihon_config_data_if_exists(
+    program_id: &Pubkey,
+    shihon_config_info: &AccountInfo,
+) -> Result<Option<ShihonConfig>, ProgramError> {
+    if shihon_config_info.data_is_empty() {
+        if *shihon_config_info.key != get_shihon_config_address(program_id) {
+            return Err(ShihonError::InvalidShihonConfigAddress.into());
+        }
+
+        return Ok(None);
+    }
+
+    get_shihon_config_data(program_id, shihon_config_info).map(Some)
+}
+
+/// Asserts the instructions guarded by the given pause flag are not paused
+/// A deployment without ShihonConfig account can't be paused
+pub fn assert_shihon_is_not_paused(
+    program_id: &Pubkey,
+    shihon_config_info: &AccountInfo,
+    pause_flag: u32,
+) -> Result<(), ProgramError> {
+    match get_shihon_config_data_if_exists(program_id, shihon_config_info)? {
+        Some(shihon_config_data) => shihon_config_data.assert_is_not_paused(pause_flag),
+        None => Ok(()),
+    }
+}
+
+/// Returns the protocol fee taken from the given amount
+pub fn calculate_fee_amount(amount: u64, fee_bps: u16) -> u64 {
+    (amount as u128 * fee_bps as u128 / MAX_BPS as u128) as u64
+}
+
+/// Transfers the protocol fee from the source token account into the treasury of the mint
+/// Returns the fee amount the treasury received after the transfer fee of the mint
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_fee_to_treasury<'a>(
+    program_id: &Pubkey,
+    source_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    treasury_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    fee_amount: u64,
+    spl_token_info: &AccountInfo<'a>,
+) -> Result<u64, ProgramError> {
+    if fee_amount == 0 {
+        return Ok(0);
+    }
+
+    if *treasury_info.key != get_treasury_address(program_id, mint_info.key) {
+        return Err(ShihonError::InvalidTreasuryAddress.into());
+    }
+
+    transfer_spl_tokens(
+        source_info,
+        mint_info,
+        treasury_info,
+        authority_info,
+        fee_amount,
+        spl_token_info,
+    )?;
+
+    get_spl_token_received_amount(mint_info, fee_amount)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_config_args() -> ShihonConfigArgs {
+        ShihonConfigArgs {
+            kicker_coin_mint: Pubkey::new_unique(),
+            rft_mint: Pubkey::new_unique(),
+            oracle_authority: Pubkey::new_unique(),
+            max_candidates: 8,
+            min_candidates: 3,
+            enrollment_time: 86_400,
+            kicker_coin_fee_bps: 100,
+            candidate_fee_bps: 50,
+            outside_buyer_fee_bps: 0,
+            guardian: Some(Pubkey::new_unique()),
+        }
+    }
+
+    fn create_test_config(pause_flags: u32) -> ShihonConfig {
+        ShihonConfig {
+            account_type: ShihonAccountType::ShihonConfig,
+            bump: 255,
+            admin_authority: Pubkey::new_unique(),
+            config: create_test_config_args(),
+            pause_flags,
+            reserved: [0; 64],
+        }
+    }
+
+    #[test]
+    fn test_assert_is_valid_config_args() {
+        // Arrange
+        let config_args = create_test_config_args();
+
+        // Act
+        let result = config_args.assert_is_valid();
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_is_valid_with_single_candidate_error() {
+        // Arrange
+        let config_args = ShihonConfigArgs {
+            max_candidates: 1,
+            ..create_test_config_args()
+        };
+
+        // Act
+        let err = config_args.assert_is_valid().err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidMaxCandidates.into());
+    }
+
+    #[test]
+    fn test_assert_is_valid_with_min_candidates_above_max_candidates_error() {
+        // Arrange
+        let config_args = ShihonConfigArgs {
+            min_candidates: 9,
+            ..create_test_config_args()
+        };
+
+        // Act
+        let err = config_args.assert_is_valid().err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidMinCandidates.into());
+    }
+
+    #[test]
+    fn test_assert_is_valid_with_fee_above_max_bps_error() {
+        // Arrange
+        let config_args = ShihonConfigArgs {
+            candidate_fee_bps: MAX_BPS as u16 + 1,
+            ..create_test_config_args()
+        };
+
+        // Act
+        let err = config_args.assert_is_valid().err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidFeeBps.into());
+    }
+
+    #[test]
+    fn test_calculate_fee_amount() {
+        // Arrange
+        let amount = 1_999;
+
+        // Act
+        let fee_amount = calculate_fee_amount(amount, 250);
+
+        // Assert
+        assert_eq!(fee_amount, 49);
+        assert_eq!(calculate_fee_amount(u64::MAX, MAX_BPS as u16), u64::MAX);
+        assert_eq!(calculate_fee_amount(amount, 0), 0);
+    }
+
+    #[test]
+    fn test_assert_is_not_paused_with_other_flag_paused() {
+        // Arrange
+        let shihon_config = create_test_config(PAUSE_CANDIDATE);
+
+        // Act
+        let result = shihon_config.assert_is_not_paused(PAUSE_OUTSIDE_BUYER);
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_is_not_paused_with_paused_error() {
+        // Arrange
+        let shihon_config = create_test_config(PAUSE_ALL);
+
+        // Act
+        let err = shihon_config
+            .assert_is_not_paused(PAUSE_CANDIDATE)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::ShihonPaused.into());
+    }
+
+    #[test]
+    fn test_assert_can_set_pause_flags_with_guardian_pausing() {
+        // Arrange
+        let shihon_config = create_test_config(PAUSE_CANDIDATE);
+        let guardian = shihon_config.config.guardian.unwrap();
+
+        let mut lamports = 0;
+        let mut data = vec![];
+        let guardian_info = AccountInfo::new(
+            &guardian,
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &guardian,
+            false,
+            0,
+        );
+
+        // Act
+        let result = shihon_config.assert_can_set_pause_flags(&guardian_info, PAUSE_ALL);
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_can_set_pause_flags_with_guardian_unpausing_error() {
+        // Arrange
+        let shihon_config = create_test_config(PAUSE_ALL);
+        let guardian = shihon_config.config.guardian.unwrap();
+
+        let mut lamports = 0;
+        let mut data = vec![];
+        let guardian_info = AccountInfo::new(
+            &guardian,
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &guardian,
+            false,
+            0,
+        );
+
+        // Act
+        let err = shihon_config
+            .assert_can_set_pause_flags(&guardian_info, PAUSE_CANDIDATE)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::GuardianCannotUnpause.into());
+    }
+}
@@ -1,44 +1,102 @@
 //! Tanistry Account
 
-use crate::{error::ShihonError, state::enums::ShihonAccountType};
+use crate::state::legacy::TanistryV1;
+use crate::{
+    error::ShihonError,
+    pda::{
+        find_bump_seed, get_tanistry_address_seeds, get_tanistry_token_holding_address,
+        is_valid_pda,
+    },
+    state::{
+        enums::{ShihonAccountType, TanistryState},
+        shihon_config::ShihonConfigArgs,
+    },
+};
+use borsh::maybestd::io::Write;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
-    pubkey::Pubkey,
-};
-use spl_governance_tools::{
-    account::{assert_is_valid_account2, get_account_data, AccountMaxSize},
-    error::GovernanceToolsError,
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, clock::UnixTimestamp,
+    program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey,
 };
+use spl_governance_tools::account::{assert_is_valid_account, get_account_data, AccountMaxSize};
 
-/// Tanistry Account
+/// Tanistry Account PDA seeds: ['tanistry', kicker_coin_owner_record]
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct Tanistry {
     /// account type
     pub account_type: ShihonAccountType,
 
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
     /// next tanistry id
     pub next_tanistry_id: Option<Pubkey>,
 
     /// previous tanistry id
     pub previous_tanistry_id: Option<Pubkey>,
 
+    /// The Ring of the Tanistry chain, PDA seeds: ['ring', first_tanistry]
+    pub ring: Pubkey,
+
     /// Reserved space for future versions
     pub reserved: [u8; 8],
 
     /// kicker coin owner record
     pub kicker_coin_owner_record: Pubkey,
 
+    /// Tanistry state
+    pub state: TanistryState,
+
+    /// The mint of the tokens held by the Tanistry vault
+    pub tanistry_token_mint: Pubkey,
+
+    /// The CandidateLimitRecord of the Crown chosen by Crowning
+    pub crown: Option<Pubkey>,
+
+    /// The amount the outside buyers deposited into the vault buying exceeded rate tokens
+    pub outside_buyer_deposit_amount: u64,
+
+    /// The recorded deposits held by the vault when the Tanistry was settled, any surplus sent to the vault is excluded
+    pub settled_amount: u64,
+
+    /// The amount already refunded from the vault
+    pub refunded_amount: u64,
+
+    /// The amount of KickerCoin refunded to the kicker
+    pub kicker_refund_amount: u64,
+
+    /// The amount of KickerCoin paid to the coordinator
+    pub coordinator_refund_amount: u64,
+
+    /// Indicates whether the kicker claimed the refund
+    pub is_kicker_refunded: bool,
+
+    /// Indicates whether the coordinator claimed the refund
+    pub is_coordinator_refunded: bool,
+
+    /// The mint of the refund receipt NFT issued when the coordinator dropped the game
+    /// The coordinator's refund can only be redeemed with the receipt once it's issued
+    pub coordinator_refund_receipt_mint: Option<Pubkey>,
+
+    /// When the Tanistry opened for candidates
+    /// The enrollment ends enrollment_time of the ShihonConfig later
+    pub enrolling_at: UnixTimestamp,
+
     /// CandidateLimitRecord List
     pub candidate_limit_record_list: Vec<Pubkey>,
 }
 
-impl AccountMaxSize for Tanistry {}
+impl AccountMaxSize for Tanistry {
+    fn get_max_size(&self) -> Option<usize> {
+        // The links, the crown and the refund receipt mint are set after the Tanistry is created
+        Some(self.candidate_limit_record_list.len() * 32 + 293)
+    }
+}
 
 impl IsInitialized for Tanistry {
     fn is_initialized(&self) -> bool {
-        self.account_type == ShihonAccountType::Tanistry
+        self.account_type == ShihonAccountType::TanistryV2
     }
 }
 
@@ -48,53 +106,249 @@ impl IsInitialized for Tanistry {
 // 3. for circuit the MPC key to distributing to all candidates
 
 impl Tanistry {
-    /// Returns Tanistry PDA seeds (All in one)
-    pub fn get_tanistry_address_seeds(&self) -> Result<[&[u8]; 3], ProgramError> {
-        let seeds = match self.account_type {
-            ShihonAccountType::AccountGovernance => {
-                get_account_governance_address_seeds(&self.realm, &self.governed_account)
+    /// Serializes the account into the current layout
+    /// V1 accounts have no room for the current fields and have to be migrated before they are updated
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), ProgramError> {
+        if self.account_type == ShihonAccountType::TanistryV1 {
+            return Err(ShihonError::TanistryMustBeMigrated.into());
+        }
+
+        BorshSerialize::serialize(self, writer)?;
+
+        Ok(())
+    }
+
+    /// Checks the enrollment is open and the Tanistry can take one more candidate
+    pub fn assert_can_candidate(
+        &self,
+        config: Option<&ShihonConfigArgs>,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.state != TanistryState::Active || self.crown.is_some() {
+            return Err(ShihonError::InvalidTanistryStateCannotCandidate.into());
+        }
+
+        if let Some(config) = config {
+            if self.has_enrollment_ended(config.enrollment_time, current_unix_timestamp) {
+                return Err(ShihonError::TanistryEnrollmentEnded.into());
             }
-            ShihonAccountType::ProgramGovernance => {
-                get_program_governance_address_seeds(&self.realm, &self.governed_account)
+
+            if self.candidate_limit_record_list.len() >= config.max_candidates as usize {
+                return Err(ShihonError::TooManyCandidates.into());
             }
-            ShihonAccountType::MintGovernance => {
-                get_mint_governance_address_seeds(&self.realm, &self.governed_account)
+        }
+
+        Ok(())
+    }
+
+    /// Checks the enrollment has ended with enough candidates and outside buyers can buy into the running game
+    pub fn assert_can_buy(
+        &self,
+        config: Option<&ShihonConfigArgs>,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.state != TanistryState::Active || self.crown.is_some() {
+            return Err(ShihonError::InvalidTanistryStateCannotBuy.into());
+        }
+
+        if let Some(config) = config {
+            if !self.has_enrollment_ended(config.enrollment_time, current_unix_timestamp) {
+                return Err(ShihonError::TanistryEnrollmentNotEnded.into());
             }
-            ShihonAccountType::TokenGovernance => {
-                get_token_governance_address_seeds(&self.realm, &self.governed_account)
+
+            // An underfilled Tanistry is going to be cancelled and has no game to buy into
+            if self.candidate_limit_record_list.len() < config.min_candidates as usize {
+                return Err(ShihonError::TanistryHasNotEnoughCandidates.into());
             }
-            _ => return Err(GovernanceToolsError::InvalidAccountType.into()),
-        };
+        }
 
-        Ok(seeds)
+        Ok(())
     }
 
-    /// make the mpc hash key for identity of Roydamna inside the Tanistry
-    fn make_mpc_key(first_candidate: Pubkey, second_candidate: Pubkey) -> Pubkey {
-        unimplemented!();
-        // all hash for identity like this MPC Key is on each CLR(CandidateLimitRecord), not bcToken itself
-        //
+    /// Checks the game is running and the given CandidateLimitRecord can be crowned
+    pub fn assert_can_crown(&self, crown: &Pubkey) -> Result<(), ProgramError> {
+        if self.state != TanistryState::Active || self.crown.is_some() {
+            return Err(ShihonError::InvalidTanistryStateCannotCrown.into());
+        }
+
+        // The coordinator who dropped the game with CoordinatorWithdraw has no say in it anymore
+        if self.coordinator_refund_receipt_mint.is_some() {
+            return Err(ShihonError::CoordinatorAlreadyWithdrawn.into());
+        }
+
+        if !self.candidate_limit_record_list.contains(crown) {
+            return Err(ShihonError::InvalidCrownForTanistry.into());
+        }
+
+        Ok(())
     }
 
-    /// make (pair of candidate + first kicker) triple person
-    fn make_triple(candidate_list: Vec<Pubkey>) {
-        unimplemented!();
-        // The coordinator is not directly involved in the relationship of this triangle
-        // his work is only to give his input and proof of his existence to the first kicker(aka init content holder).
+    /// Checks the Tanistry is crowned and no next Tanistry has been linked to it yet
+    /// Only the Crown's KickerCoin continues the chain
+    pub fn assert_can_link_next_tanistry(&self) -> Result<(), ProgramError> {
+        if self.crown.is_none() || self.next_tanistry_id.is_some() {
+            return Err(ShihonError::InvalidPreviousTanistryCannotLink.into());
+        }
+
+        Ok(())
     }
 
-    /// shuffle the candidate
-    fn shuffle_candidate() {
-        unimplemented!();
+    /// Asserts the given Ring is the Ring of the Tanistry chain
+    pub fn assert_is_valid_ring(&self, ring: &Pubkey) -> Result<(), ProgramError> {
+        if self.ring != *ring {
+            return Err(ShihonError::InvalidRingForTanistry.into());
+        }
+
+        Ok(())
     }
 
-    /// getting the triple person for rating
-    fn get_triple(candidate: Pubkey) {
-        unimplemented!();
+    /// Checks the candidates of the Tanistry can vote for CC
+    /// The deposits of a cancelled Tanistry are claimed back so they can't back any vote
+    pub fn assert_can_cc_vote(&self) -> Result<(), ProgramError> {
+        if self.state == TanistryState::Cancelled {
+            return Err(ShihonError::CCVoteFromCancelledTanistry.into());
+        }
+
+        Ok(())
     }
 
-    fn get_my_buddy_candidate(me: Pubkey) -> Pubkey {
-        unimplemented!();
+    /// Checks the Tanistry is crowned and the vault can be settled
+    pub fn assert_can_settle(&self) -> Result<(), ProgramError> {
+        if self.state != TanistryState::Crowned || self.crown.is_none() {
+            return Err(ShihonError::InvalidTanistryStateCannotSettle.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks the game is running and the candidates can bump their self-rate
+    pub fn assert_can_bump_self_rate(&self) -> Result<(), ProgramError> {
+        if self.state != TanistryState::Active {
+            return Err(ShihonError::InvalidTanistryStateCannotBumpSelfRate.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks the Tanistry is settled or cancelled and refunds can be claimed
+    pub fn assert_can_claim_refund(&self) -> Result<(), ProgramError> {
+        if self.state != TanistryState::EnableToRefund && self.state != TanistryState::Cancelled {
+            return Err(ShihonError::InvalidTanistryStateCannotRefund.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the enrollment time has ended at the given time
+    pub fn has_enrollment_ended(
+        &self,
+        enrollment_time: u32,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> bool {
+        self.enrolling_at
+            .checked_add(enrollment_time as i64)
+            .unwrap()
+            < current_unix_timestamp
+    }
+
+    /// Checks the enrollment has ended without the minimum number of candidates
+    /// or without the coordinator to crown the Crown and the Tanistry can be cancelled
+    pub fn assert_can_cancel(
+        &self,
+        config: &ShihonConfigArgs,
+        current_unix_timestamp: UnixTimestamp,
+    ) -> Result<(), ProgramError> {
+        if self.state != TanistryState::Active || self.crown.is_some() {
+            return Err(ShihonError::InvalidTanistryStateCannotCancel.into());
+        }
+
+        if !self.has_enrollment_ended(config.enrollment_time, current_unix_timestamp) {
+            return Err(ShihonError::TanistryEnrollmentNotEnded.into());
+        }
+
+        // Nobody can crown the Tanistry once the coordinator withdrew so it's cancelled like an underfilled one
+        if self.candidate_limit_record_list.len() >= config.min_candidates as usize
+            && self.coordinator_refund_receipt_mint.is_none()
+        {
+            return Err(ShihonError::TanistryHasEnoughCandidates.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks the Tanistry is still live and the coordinator hasn't dropped the game yet
+    pub fn assert_can_coordinator_withdraw(&self) -> Result<(), ProgramError> {
+        if self.state != TanistryState::Active && self.state != TanistryState::Crowned {
+            return Err(ShihonError::InvalidTanistryStateCannotWithdrawCoordinator.into());
+        }
+
+        if self.coordinator_refund_receipt_mint.is_some() {
+            return Err(ShihonError::CoordinatorAlreadyWithdrawn.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the given mint is the refund receipt mint issued to the coordinator
+    pub fn assert_is_valid_coordinator_refund_receipt_mint(
+        &self,
+        refund_receipt_mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.coordinator_refund_receipt_mint != Some(*refund_receipt_mint) {
+            return Err(ShihonError::InvalidCoordinatorRefundReceiptMint.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the given account is the Tanistry vault
+    pub fn assert_is_valid_tanistry_token_holding(
+        &self,
+        program_id: &Pubkey,
+        tanistry: &Pubkey,
+        tanistry_token_holding: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let tanistry_token_holding_address =
+            get_tanistry_token_holding_address(program_id, tanistry, &self.tanistry_token_mint);
+
+        if tanistry_token_holding_address != *tanistry_token_holding {
+            return Err(ShihonError::InvalidTanistryTokenHoldingAccount.into());
+        }
+
+        Ok(())
+    }
+
+    /// Asserts the given mint is the Tanistry token mint
+    pub fn assert_is_valid_tanistry_token_mint(
+        &self,
+        tanistry_token_mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.tanistry_token_mint != *tanistry_token_mint {
+            return Err(ShihonError::InvalidTanistryTokenMint.into());
+        }
+
+        Ok(())
+    }
+
+    /// Records the refund paid out of the vault and flags the Tanistry refunded once the vault is drained
+    pub fn add_refunded_amount(&mut self, amount: u64) -> Result<(), ProgramError> {
+        self.refunded_amount = self
+            .refunded_amount
+            .checked_add(amount)
+            .ok_or(ShihonError::RefundAmountOverflow)?;
+
+        if self.refunded_amount > self.settled_amount {
+            return Err(ShihonError::RefundExceedsSettledAmount.into());
+        }
+
+        // A cancelled Tanistry stays Cancelled once drained
+        if self.refunded_amount == self.settled_amount
+            && self.state == TanistryState::EnableToRefund
+        {
+            self.state = TanistryState::AlreadyRefunded;
+        }
+
+        Ok(())
     }
 }
 
@@ -103,180 +357,426 @@ pub fn get_tanistry_data(
     program_id: &Pubkey,
     tanistry_info: &AccountInfo,
 ) -> Result<Tanistry, ProgramError> {
+    let account_type: ShihonAccountType = try_from_slice_unchecked(&tanistry_info.data.borrow())?;
+
+    if account_type == ShihonAccountType::TanistryV1 {
+        let tanistry_data_v1 = get_account_data::<TanistryV1>(program_id, tanistry_info)?;
+        return Ok(tanistry_data_v1.into());
+    }
+
     get_account_data::<Tanistry>(program_id, tanistry_info)
 }
 
-/// Deserializes Tanistry account, checks owner program and asserts governance belongs to the given ream
-pub fn get_tanistry_data_for_bc_token(
+/// Deserializes Tanistry account and validates its PDA
+pub fn get_tanistry_data_for_seeds(
     program_id: &Pubkey,
     tanistry_info: &AccountInfo,
-    bc_token: &Pubkey,
+    kicker_coin_owner_record: &Pubkey,
 ) -> Result<Tanistry, ProgramError> {
     let tanistry_data = get_tanistry_data(program_id, tanistry_info)?;
 
-    if tanistry_data.bc_token != *bc_token {
-        return Err(ShihonError::InvalidRealmForGovernance.into());
+    let tanistry_address_seeds = get_tanistry_address_seeds(kicker_coin_owner_record);
+
+    // V1 accounts don't store the bump and it's derived from the seeds
+    let bump = if tanistry_data.account_type == ShihonAccountType::TanistryV1 {
+        find_bump_seed(program_id, &tanistry_address_seeds)
+    } else {
+        tanistry_data.bump
+    };
+
+    if !is_valid_pda(program_id, tanistry_info.key, &tanistry_address_seeds, bump) {
+        return Err(ShihonError::InvalidTanistryAddress.into());
     }
 
     Ok(tanistry_data)
 }
 
-/// Checks the given account is a bcToken account and belongs to the given tanistry
-pub fn assert_tanistry_for_bc_token(
+/// Checks whether tanistry account exists, is initialized and owned by the Tanistry program
+pub fn assert_is_valid_tanistry(
     program_id: &Pubkey,
     tanistry_info: &AccountInfo,
-    bc_token: &Pubkey,
 ) -> Result<(), ProgramError> {
-    get_tanistry_data_for_bc_token(program_id, tanistry_info, bc_token)?;
-    Ok(())
+    assert_is_valid_account(tanistry_info, ShihonAccountType::TanistryV2, program_id)
 }
 
-/// Returns Tanistry PDA seeds
-pub fn get_program_tanistry_address_seeds<'a>(
-    bc_token: &'a Pubkey,
-    tanistry_program: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    // 'program-governance' prefix ensures uniqueness of the PDA
-    // Note: Only the current program upgrade authority can create an account with this PDA using CreateProgramGovernance instruction
-    [
-        b"program-tanistry",
-        bc_token.as_ref(),
-        tanistry_program.as_ref(),
-    ]
-}
+/// The share of the KickerCoin paid to the coordinator, the kicker gets the rest back
+pub const COORDINATOR_KICKER_COIN_SHARE_BPS: u64 = 5_000;
 
-/// Returns ProgramGovernance PDA address
-pub fn get_program_tanistry_address<'a>(
-    program_id: &Pubkey,
-    bc_token: &'a Pubkey,
-    tanistry_program: &'a Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_program_tanistry_address_seeds(bc_token, tanistry_program),
-        program_id,
-    )
-    .0
-}
+/// The share of the candidate pool paid to the Crown as a bonus
+pub const CROWN_POOL_SHARE_BPS: u64 = 1_000;
 
-/// Returns Mint Tanistry PDA seeds
-pub fn get_mint_tanistry_address_seeds<'a>(
-    bc_token: &'a Pubkey,
-    tanistry_mint: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    // 'mint-governance' prefix ensures uniqueness of the PDA
-    // Note: Only the current mint authority can create an account with this PDA using CreateMintGovernance instruction
-    [b"mint-tanistry", bc_token.as_ref(), tanistry_mint.as_ref()]
-}
+/// 100% in basis points
+pub const MAX_BPS: u64 = 10_000;
 
-/// Returns Mint Tanistry PDA address
-pub fn get_mint_tanistry_address<'a>(
-    program_id: &Pubkey,
-    bc_token: &'a Pubkey,
-    tanistry_mint: &'a Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_mint_tanistry_address_seeds(bc_token, tanistry_mint),
-        program_id,
-    )
-    .0
-}
+/// Refund allocation of a settled Tanistry vault
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefundAllocation {
+    /// The KickerCoin refunded to the kicker
+    pub kicker_amount: u64,
 
-/// Returns Token Tanistry PDA seeds
-pub fn get_token_tanistry_address_seeds<'a>(
-    bc_token: &'a Pubkey,
-    tanistry_token: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    // 'token-tanistry' prefix ensures uniqueness of the PDA
-    // Note: Only the current token account owner can create an account with this PDA using CreateTokenTanistry instruction
-    [
-        b"token-tanistry",
-        bc_token.as_ref(),
-        tanistry_token.as_ref(),
-    ]
-}
+    /// The KickerCoin paid to the coordinator
+    pub coordinator_amount: u64,
 
-/// Returns Token Tanistry PDA address
-pub fn get_token_tanistry_address<'a>(
-    program_id: &Pubkey,
-    realm: &'a Pubkey,
-    governed_token: &'a Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_token_tanistry_address_seeds(realm, governed_token),
-        program_id,
-    )
-    .0
+    /// The refunds of the candidates in the order of candidate_limit_record_list
+    /// The Crown's bonus and the rounding remainder are included in the Crown's refund
+    pub candidate_amounts: Vec<u64>,
 }
 
-/// Returns Account Tanistry PDA seeds
-pub fn get_account_tanistry_address_seeds<'a>(
-    realm: &'a Pubkey,
-    governed_account: &'a Pubkey,
-) -> [&'a [u8]; 3] {
-    [
-        b"account-tanistry",
-        realm.as_ref(),
-        governed_account.as_ref(),
-    ]
-}
+/// Splits the Tanistry vault between the kicker, the coordinator, the Crown and the candidates
+/// candidates are (self-rating deposit, received rating) pairs
+///
+/// The candidate pool is the candidates' deposits plus the outside buyers' funds
+/// The Crown receives CROWN_POOL_SHARE_BPS of the pool and the rest is split pro rata to the final ratings
+/// If nobody was rated the pool is split pro rata to the deposits
+/// The amounts always add up to the recorded deposits so they are refunded to exactly zero
+pub fn calculate_refund_allocation(
+    kicker_coin_amount: u64,
+    outside_buyer_deposit_amount: u64,
+    candidates: &[(u64, u64)],
+    crown_index: usize,
+) -> Result<RefundAllocation, ProgramError> {
+    if crown_index >= candidates.len() {
+        return Err(ShihonError::InvalidCrownForTanistry.into());
+    }
 
-/// Returns AccountTanistry PDA address
-pub fn get_account_tanistry_address<'a>(
-    program_id: &Pubkey,
-    realm: &'a Pubkey,
-    governed_account: &'a Pubkey,
-) -> Pubkey {
-    Pubkey::find_program_address(
-        &get_account_tanistry_address_seeds(realm, governed_account),
-        program_id,
-    )
-    .0
+    let coordinator_amount = (kicker_coin_amount as u128)
+        .checked_mul(COORDINATOR_KICKER_COIN_SHARE_BPS as u128)
+        .unwrap()
+        .checked_div(MAX_BPS as u128)
+        .unwrap() as u64;
+    let kicker_amount = kicker_coin_amount.checked_sub(coordinator_amount).unwrap();
+
+    let pool = candidates
+        .iter()
+        .try_fold(
+            outside_buyer_deposit_amount as u128,
+            |pool, (deposit, _)| pool.checked_add(*deposit as u128),
+        )
+        .ok_or(ShihonError::RefundAmountOverflow)?;
+
+    let crown_bonus = pool
+        .checked_mul(CROWN_POOL_SHARE_BPS as u128)
+        .unwrap()
+        .checked_div(MAX_BPS as u128)
+        .unwrap();
+    let distributable = pool.checked_sub(crown_bonus).unwrap();
+
+    let total_rating: u128 = candidates.iter().map(|(_, rating)| *rating as u128).sum();
+    let weights: Vec<u128> = if total_rating > 0 {
+        candidates
+            .iter()
+            .map(|(_, rating)| *rating as u128)
+            .collect()
+    } else {
+        candidates
+            .iter()
+            .map(|(deposit, _)| *deposit as u128)
+            .collect()
+    };
+    let total_weight: u128 = weights.iter().sum();
+
+    let mut candidate_amounts: Vec<u128> = weights
+        .iter()
+        .map(|weight| {
+            if total_weight == 0 {
+                0
+            } else {
+                distributable
+                    .checked_mul(*weight)
+                    .unwrap()
+                    .checked_div(total_weight)
+                    .unwrap()
+            }
+        })
+        .collect();
+
+    let distributed: u128 = candidate_amounts.iter().sum();
+    let remainder = distributable.checked_sub(distributed).unwrap();
+
+    candidate_amounts[crown_index] = candidate_amounts[crown_index]
+        .checked_add(crown_bonus)
+        .unwrap()
+        .checked_add(remainder)
+        .unwrap();
+
+    let candidate_amounts = candidate_amounts
+        .into_iter()
+        .map(|amount| u64::try_from(amount).map_err(|_| ShihonError::RefundAmountOverflow))
+        .collect::<Result<Vec<u64>, ShihonError>>()?;
+
+    Ok(RefundAllocation {
+        kicker_amount,
+        coordinator_amount,
+        candidate_amounts,
+    })
 }
 
-/// Checks whether tanistry account exists, is initialized and owned by the Tanistry program
-pub fn assert_is_valid_tanistry(
-    program_id: &Pubkey,
-    tanistry_info: &AccountInfo,
-) -> Result<(), ProgramError> {
-    assert_is_valid_account2(
-        tanistry_info,
-        &[
-            ShihonAccountType::AccountGovernance,
-            ShihonAccountType::ProgramGovernance,
-            ShihonAccountType::TokenGovernance,
-            ShihonAccountType::MintGovernance,
-        ],
-        program_id,
-    )
-}
+#[cfg(test)]
+mod test {
+    use super::*;
+    use borsh::BorshSerialize;
+    use proptest::prelude::*;
+
+    fn total_allocated(allocation: &RefundAllocation) -> u128 {
+        allocation.kicker_amount as u128
+            + allocation.coordinator_amount as u128
+            + allocation
+                .candidate_amounts
+                .iter()
+                .map(|amount| *amount as u128)
+                .sum::<u128>()
+    }
 
-/// Validates args supplied to create tanistry account
-pub fn assert_valid_create_tanistry_args(
-    program_id: &Pubkey,
-    tanistry_config: &TanistryConfig,
-    bc_token_info: &AccountInfo,
-) -> Result<(), ProgramError> {
-    assert_is_valid_bc_token(program_id, bc_token_info)?;
+    fn create_test_tanistry(candidates: usize) -> Tanistry {
+        Tanistry {
+            account_type: ShihonAccountType::TanistryV2,
+            bump: 255,
+            next_tanistry_id: None,
+            previous_tanistry_id: None,
+            ring: Pubkey::new_unique(),
+            reserved: [0; 8],
+            kicker_coin_owner_record: Pubkey::new_unique(),
+            state: TanistryState::Active,
+            tanistry_token_mint: Pubkey::new_unique(),
+            crown: None,
+            outside_buyer_deposit_amount: 0,
+            settled_amount: 0,
+            refunded_amount: 0,
+            kicker_refund_amount: 0,
+            coordinator_refund_amount: 0,
+            is_kicker_refunded: false,
+            is_coordinator_refunded: false,
+            coordinator_refund_receipt_mint: None,
+            enrolling_at: 1_000,
+            candidate_limit_record_list: (0..candidates).map(|_| Pubkey::new_unique()).collect(),
+        }
+    }
 
-    assert_is_valid_tanistry_config(tanistry_config)?;
+    fn create_test_config_args() -> ShihonConfigArgs {
+        ShihonConfigArgs {
+            kicker_coin_mint: Pubkey::new_unique(),
+            rft_mint: Pubkey::new_unique(),
+            oracle_authority: Pubkey::new_unique(),
+            max_candidates: 8,
+            min_candidates: 3,
+            enrollment_time: 100,
+            kicker_coin_fee_bps: 0,
+            candidate_fee_bps: 0,
+            outside_buyer_fee_bps: 0,
+            guardian: None,
+        }
+    }
 
-    Ok(())
-}
+    #[test]
+    fn test_max_size_with_crown_and_links() {
+        // Arrange
+        let tanistry = Tanistry {
+            next_tanistry_id: Some(Pubkey::new_unique()),
+            previous_tanistry_id: Some(Pubkey::new_unique()),
+            crown: Some(Pubkey::new_unique()),
+            coordinator_refund_receipt_mint: Some(Pubkey::new_unique()),
+            ..create_test_tanistry(3)
+        };
 
-/// these funcs moved from modules/value.rs
-/// sum of members of one tanistry
-fn get_tanistry_value() -> u64 {
-    unimplemented!();
-}
-/// sum of 1,3,5 of tanistry value
-fn get_building_value() -> u64 {
-    unimplemented!();
-}
-/// sum of both wallet (α + β)
-fn get_ring_value() -> u64 {
-    unimplemented!();
-}
+        // Act
+        let size = tanistry.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(tanistry.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_assert_can_cancel() {
+        // Arrange
+        let tanistry = create_test_tanistry(2);
+
+        // Act
+        let result = tanistry.assert_can_cancel(&create_test_config_args(), 1_101);
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_can_cancel_before_enrollment_ended_error() {
+        // Arrange
+        let tanistry = create_test_tanistry(2);
+
+        // Act
+        let err = tanistry
+            .assert_can_cancel(&create_test_config_args(), 1_100)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::TanistryEnrollmentNotEnded.into());
+    }
+
+    #[test]
+    fn test_assert_can_cancel_with_enough_candidates_error() {
+        // Arrange
+        let tanistry = create_test_tanistry(3);
+
+        // Act
+        let err = tanistry
+            .assert_can_cancel(&create_test_config_args(), 1_101)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::TanistryHasEnoughCandidates.into());
+    }
+
+    #[test]
+    fn test_assert_can_cancel_crowned_tanistry_error() {
+        // Arrange
+        let mut tanistry = create_test_tanistry(2);
+        tanistry.state = TanistryState::Crowned;
 
-fn get_cc_value() -> u64 {
-    unimplemented!();
+        // Act
+        let err = tanistry
+            .assert_can_cancel(&create_test_config_args(), 1_101)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidTanistryStateCannotCancel.into());
+    }
+
+    #[test]
+    fn test_assert_can_cancel_after_coordinator_withdraw() {
+        // Arrange
+        let mut tanistry = create_test_tanistry(3);
+        tanistry.coordinator_refund_receipt_mint = Some(Pubkey::new_unique());
+
+        // Act
+        let result = tanistry.assert_can_cancel(&create_test_config_args(), 1_101);
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_can_crown_after_coordinator_withdraw_error() {
+        // Arrange
+        let mut tanistry = create_test_tanistry(3);
+        tanistry.coordinator_refund_receipt_mint = Some(Pubkey::new_unique());
+        let crown = tanistry.candidate_limit_record_list[0];
+
+        // Act
+        let err = tanistry.assert_can_crown(&crown).err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::CoordinatorAlreadyWithdrawn.into());
+    }
+
+    #[test]
+    fn test_assert_can_buy() {
+        // Arrange
+        let tanistry = create_test_tanistry(3);
+
+        // Act
+        let result = tanistry.assert_can_buy(Some(&create_test_config_args()), 1_101);
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_can_buy_without_enough_candidates_error() {
+        // Arrange
+        let tanistry = create_test_tanistry(2);
+
+        // Act
+        let err = tanistry
+            .assert_can_buy(Some(&create_test_config_args()), 1_101)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::TanistryHasNotEnoughCandidates.into());
+    }
+
+    #[test]
+    fn test_add_refunded_amount_keeps_cancelled_state() {
+        // Arrange
+        let mut tanistry = create_test_tanistry(2);
+        tanistry.state = TanistryState::Cancelled;
+        tanistry.settled_amount = 100;
+
+        // Act
+        tanistry.add_refunded_amount(100).unwrap();
+
+        // Assert
+        assert_eq!(tanistry.state, TanistryState::Cancelled);
+    }
+
+    #[test]
+    fn test_refund_allocation_by_rating() {
+        // Arrange
+        let candidates = [(100, 1), (100, 3), (100, 0)];
+
+        // Act
+        let allocation = calculate_refund_allocation(10, 0, &candidates, 1).unwrap();
+
+        // Assert
+        assert_eq!(allocation.coordinator_amount, 5);
+        assert_eq!(allocation.kicker_amount, 5);
+        // pool 300, Crown bonus 30, 270 split 1:3:0
+        assert_eq!(allocation.candidate_amounts, vec![67, 233, 0]);
+        assert_eq!(total_allocated(&allocation), 310);
+    }
+
+    #[test]
+    fn test_refund_allocation_without_ratings_refunds_deposits() {
+        // Arrange
+        let candidates = [(100, 0), (300, 0)];
+
+        // Act
+        let allocation = calculate_refund_allocation(0, 0, &candidates, 0).unwrap();
+
+        // Assert
+        assert_eq!(allocation.candidate_amounts, vec![130, 270]);
+    }
+
+    #[test]
+    fn test_refund_allocation_with_invalid_crown_error() {
+        // Arrange
+        let candidates = [(100, 1)];
+
+        // Act
+        let err = calculate_refund_allocation(0, 0, &candidates, 1)
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::InvalidCrownForTanistry.into());
+    }
+
+    proptest! {
+        #[test]
+        fn test_refund_allocation_drains_vault(
+            kicker_coin_amount in 0..u64::MAX / 4,
+            outside_buyer_deposit_amount in 0..u64::MAX / 4,
+            candidates in prop::collection::vec((0..u64::MAX / 256, any::<u64>()), 1..64),
+            crown_seed in any::<usize>(),
+        ) {
+            let crown_index = crown_seed % candidates.len();
+
+            let allocation = calculate_refund_allocation(
+                kicker_coin_amount,
+                outside_buyer_deposit_amount,
+                &candidates,
+                crown_index,
+            )
+            .unwrap();
+
+            let vault = kicker_coin_amount as u128
+                + outside_buyer_deposit_amount as u128
+                + candidates.iter().map(|(deposit, _)| *deposit as u128).sum::<u128>();
+
+            prop_assert_eq!(allocation.candidate_amounts.len(), candidates.len());
+            prop_assert_eq!(total_allocated(&allocation), vault);
+        }
+    }
 }
@@ -0,0 +1,379 @@
+//! Triple Multisig Account
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, program_pack::IsInitialized,
+    pubkey::Pubkey,
+};
+use spl_governance_tools::account::{get_account_data, AccountMaxSize};
+
+use crate::{
+    error::ShihonError,
+    pda::{get_triple_multisig_address_seeds, is_valid_pda},
+    state::enums::ShihonAccountType,
+};
+
+/// The number of the TripleMultisig signers: the rater, the buddy and the first kicker
+pub const TRIPLE_MULTISIG_SIGNERS: usize = 3;
+
+/// The number of the TripleMultisig signers required to approve
+pub const TRIPLE_MULTISIG_THRESHOLD: u8 = 2;
+
+/// TripleMultisig Account PDA seeds: ['triple-multisig', rater_candidate, buddy_candidate]
+/// Mixing and rating need two of the three keys so no single party can mix or rate on its own
+/// and the rater can only be aware of one of the two other approvals
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct TripleMultisig {
+    /// account type
+    pub account_type: ShihonAccountType,
+
+    /// Bump seed of the account PDA
+    pub bump: u8,
+
+    /// The rater's CandidateLimitRecord
+    pub rater_candidate: Pubkey,
+
+    /// The buddy's CandidateLimitRecord
+    pub buddy_candidate: Pubkey,
+
+    /// Number of signers required
+    pub m: u8,
+
+    /// Number of valid signers
+    pub n: u8,
+
+    /// Signer public keys: the rater's and the buddy's candidate token owners and the first kicker
+    pub signers: [Pubkey; TRIPLE_MULTISIG_SIGNERS],
+}
+
+impl AccountMaxSize for TripleMultisig {
+    fn get_max_size(&self) -> Option<usize> {
+        Some(164)
+    }
+}
+
+impl IsInitialized for TripleMultisig {
+    fn is_initialized(&self) -> bool {
+        self.account_type == ShihonAccountType::TripleMultisig
+    }
+}
+
+impl TripleMultisig {
+    /// Asserts the signer keys are different so no party holds two of the three positions
+    pub fn assert_signers_are_distinct(&self) -> Result<(), ProgramError> {
+        let signers = &self.signers[0..self.n as usize];
+
+        for (position, key) in signers.iter().enumerate() {
+            if signers[position + 1..].contains(key) {
+                return Err(ShihonError::DuplicateTripleMultisigSigners.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Asserts at least m of the n multisig signers signed the transaction
+    /// A position can be signed by its signer or by the delegate of that position
+    /// Follows the M-of-N semantics of the SPL Token Multisig, every signer is counted only once
+    /// even if its key holds several positions
+    pub fn assert_signed_by_threshold(
+        &self,
+        signer_infos: &[AccountInfo],
+        delegates: &[Option<Pubkey>; TRIPLE_MULTISIG_SIGNERS],
+    ) -> Result<(), ProgramError> {
+        let mut num_signers = 0;
+        let mut matched = [false; TRIPLE_MULTISIG_SIGNERS];
+
+        for signer_info in signer_infos.iter() {
+            for (position, key) in self.signers[0..self.n as usize].iter().enumerate() {
+                let is_position_key =
+                    key == signer_info.key || delegates[position] == Some(*signer_info.key);
+
+                if is_position_key && !matched[position] {
+                    if !signer_info.is_signer {
+                        return Err(ShihonError::TripleMultisigSignerMustSign.into());
+                    }
+                    matched[position] = true;
+                    num_signers += 1;
+                    break;
+                }
+            }
+        }
+
+        if num_signers < self.m {
+            return Err(ShihonError::NotEnoughTripleMultisigSigners.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Deserializes TripleMultisig account and checks owner program
+pub fn get_triple_multisig_data(
+    program_id: &Pubkey,
+    triple_multisig_info: &AccountInfo,
+) -> Result<TripleMultisig, ProgramError> {
+    get_account_data::<TripleMultisig>(program_id, triple_multisig_info)
+}
+
+/// Deserializes TripleMultisig account and validates its PDA for the given rater and buddy
+pub fn get_triple_multisig_data_for_candidates(
+    program_id: &Pubkey,
+    triple_multisig_info: &AccountInfo,
+    rater_candidate: &Pubkey,
+    buddy_candidate: &Pubkey,
+) -> Result<TripleMultisig, ProgramError> {
+    let triple_multisig_data = get_triple_multisig_data(program_id, triple_multisig_info)?;
+
+    if !is_valid_pda(
+        program_id,
+        triple_multisig_info.key,
+        &get_triple_multisig_address_seeds(rater_candidate, buddy_candidate),
+        triple_multisig_data.bump,
+    ) {
+        return Err(ShihonError::InvalidTripleMultisigAddress.into());
+    }
+
+    Ok(triple_multisig_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program::clock::Epoch;
+
+    fn create_test_triple_multisig() -> TripleMultisig {
+        TripleMultisig {
+            account_type: ShihonAccountType::TripleMultisig,
+            bump: 255,
+            rater_candidate: Pubkey::new_unique(),
+            buddy_candidate: Pubkey::new_unique(),
+            m: TRIPLE_MULTISIG_THRESHOLD,
+            n: TRIPLE_MULTISIG_SIGNERS as u8,
+            signers: [
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_max_size() {
+        // Arrange
+        let triple_multisig = create_test_triple_multisig();
+
+        // Act
+        let size = triple_multisig.try_to_vec().unwrap().len();
+
+        // Assert
+        assert_eq!(triple_multisig.get_max_size(), Some(size));
+    }
+
+    #[test]
+    fn test_assert_signed_by_threshold() {
+        // Arrange
+        let triple_multisig = create_test_triple_multisig();
+        let (mut rater_lamports, mut first_kicker_lamports) = (0, 0);
+        let (mut rater_data, mut first_kicker_data) = (vec![], vec![]);
+        let owner = Pubkey::new_unique();
+
+        let signer_infos = [
+            AccountInfo::new(
+                &triple_multisig.signers[0],
+                true,
+                false,
+                &mut rater_lamports,
+                &mut rater_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+            AccountInfo::new(
+                &triple_multisig.signers[2],
+                true,
+                false,
+                &mut first_kicker_lamports,
+                &mut first_kicker_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+        ];
+
+        // Act
+        let result = triple_multisig
+            .assert_signed_by_threshold(&signer_infos, &[None; TRIPLE_MULTISIG_SIGNERS]);
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_signed_by_threshold_with_delegates() {
+        // Arrange
+        let triple_multisig = create_test_triple_multisig();
+        let (rater_delegate, buddy_delegate) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut rater_lamports, mut buddy_lamports) = (0, 0);
+        let (mut rater_data, mut buddy_data) = (vec![], vec![]);
+        let owner = Pubkey::new_unique();
+
+        let signer_infos = [
+            AccountInfo::new(
+                &rater_delegate,
+                true,
+                false,
+                &mut rater_lamports,
+                &mut rater_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+            AccountInfo::new(
+                &buddy_delegate,
+                true,
+                false,
+                &mut buddy_lamports,
+                &mut buddy_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+        ];
+
+        // Act
+        let result = triple_multisig.assert_signed_by_threshold(
+            &signer_infos,
+            &[Some(rater_delegate), Some(buddy_delegate), None],
+        );
+
+        // Assert
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_assert_signed_by_threshold_with_same_signer_twice_error() {
+        // Arrange
+        let triple_multisig = create_test_triple_multisig();
+        let (mut first_lamports, mut second_lamports) = (0, 0);
+        let (mut first_data, mut second_data) = (vec![], vec![]);
+        let owner = Pubkey::new_unique();
+
+        let signer_infos = [
+            AccountInfo::new(
+                &triple_multisig.signers[0],
+                true,
+                false,
+                &mut first_lamports,
+                &mut first_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+            AccountInfo::new(
+                &triple_multisig.signers[0],
+                true,
+                false,
+                &mut second_lamports,
+                &mut second_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+        ];
+
+        // Act
+        let err = triple_multisig
+            .assert_signed_by_threshold(&signer_infos, &[None; TRIPLE_MULTISIG_SIGNERS])
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::NotEnoughTripleMultisigSigners.into());
+    }
+
+    #[test]
+    fn test_assert_signers_are_distinct_with_duplicate_signer_error() {
+        // Arrange
+        let mut triple_multisig = create_test_triple_multisig();
+        triple_multisig.signers[2] = triple_multisig.signers[0];
+
+        // Act
+        let err = triple_multisig.assert_signers_are_distinct().err().unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::DuplicateTripleMultisigSigners.into());
+    }
+
+    #[test]
+    fn test_assert_signed_by_threshold_with_signer_holding_two_positions_error() {
+        // Arrange
+        let mut triple_multisig = create_test_triple_multisig();
+        triple_multisig.signers[2] = triple_multisig.signers[0];
+        let mut lamports = 0;
+        let mut data = vec![];
+        let owner = Pubkey::new_unique();
+
+        let signer_infos = [AccountInfo::new(
+            &triple_multisig.signers[0],
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            Epoch::default(),
+        )];
+
+        // Act
+        let err = triple_multisig
+            .assert_signed_by_threshold(&signer_infos, &[None; TRIPLE_MULTISIG_SIGNERS])
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::NotEnoughTripleMultisigSigners.into());
+    }
+
+    #[test]
+    fn test_assert_signed_by_threshold_with_unsigned_signer_error() {
+        // Arrange
+        let triple_multisig = create_test_triple_multisig();
+        let (mut rater_lamports, mut buddy_lamports) = (0, 0);
+        let (mut rater_data, mut buddy_data) = (vec![], vec![]);
+        let owner = Pubkey::new_unique();
+
+        let signer_infos = [
+            AccountInfo::new(
+                &triple_multisig.signers[0],
+                true,
+                false,
+                &mut rater_lamports,
+                &mut rater_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+            AccountInfo::new(
+                &triple_multisig.signers[1],
+                false,
+                false,
+                &mut buddy_lamports,
+                &mut buddy_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+        ];
+
+        // Act
+        let err = triple_multisig
+            .assert_signed_by_threshold(&signer_infos, &[None; TRIPLE_MULTISIG_SIGNERS])
+            .err()
+            .unwrap();
+
+        // Assert
+        assert_eq!(err, ShihonError::TripleMultisigSignerMustSign.into());
+    }
+}
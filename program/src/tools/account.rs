@@ -0,0 +1,49 @@
+//! Account utility functions
+
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, rent::Rent,
+    system_instruction,
+};
+use spl_governance_tools::account::AccountMaxSize;
+
+/// Reallocates the account to the max size of the given data and serializes the data into it
+/// The payer tops up the account lamports to keep it rent exempt at the new size
+pub fn realloc_and_serialize_account_data<'a, T: BorshSerialize + AccountMaxSize>(
+    account_data: &T,
+    account_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
+    rent: &Rent,
+) -> ProgramResult {
+    let serialized_data = account_data.try_to_vec()?;
+    // Accounts with optional fields keep the space reserved for them the same way as when they're created
+    let new_size = account_data
+        .get_max_size()
+        .unwrap_or(serialized_data.len())
+        .max(serialized_data.len());
+
+    let rent_exempt_lamports = rent.minimum_balance(new_size);
+    let top_up_lamports = rent_exempt_lamports.saturating_sub(account_info.lamports());
+
+    if top_up_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer_info.key, account_info.key, top_up_lamports),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_info.clone(),
+            ],
+        )?;
+    }
+
+    if new_size != account_info.data_len() {
+        account_info.realloc(new_size, false)?;
+    }
+
+    let mut data = account_info.data.borrow_mut();
+    data[..serialized_data.len()].copy_from_slice(&serialized_data);
+    data[serialized_data.len()..].fill(0);
+
+    Ok(())
+}
@@ -5,3 +5,5 @@ pub mod spl_token;
 pub mod bpf_loader_upgradeable;
 
 pub mod pack;
+
+pub mod account;
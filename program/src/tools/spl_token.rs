@@ -3,7 +3,9 @@
 use arrayref::array_ref;
 use solana_program::{
     account_info::AccountInfo,
+    clock::Clock,
     entrypoint::ProgramResult,
+    instruction::Instruction,
     msg,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
@@ -12,14 +14,63 @@ use solana_program::{
     pubkey::Pubkey,
     rent::Rent,
     system_instruction,
+    sysvar::Sysvar,
 };
 use spl_token::{
     instruction::{set_authority, AuthorityType},
-    state::{Account, Mint},
+    state::{Account, AccountState, Mint},
+};
+use spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, AccountType, ExtensionType, StateWithExtensions,
 };
 
 use crate::{error::ShihonError, tools::pack::unpack_coption_pubkey};
 
+/// Returns true if the given program is either SPL Token or Token-2022 program
+pub fn is_spl_token_program_id(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::id() || *program_id == spl_token_2022::id()
+}
+
+/// Asserts the given spl_token_info is either SPL Token or Token-2022 program
+pub fn assert_is_spl_token_program(spl_token_info: &AccountInfo) -> Result<(), ProgramError> {
+    if !is_spl_token_program_id(spl_token_info.key) {
+        return Err(ShihonError::InvalidSplTokenProgram.into());
+    }
+
+    Ok(())
+}
+
+/// Addresses the given spl-token instruction to the token program of spl_token_info
+/// Token-2022 keeps the spl-token layout for every instruction it inherited so the spl-token builders can be reused
+fn with_spl_token_program(
+    mut instruction: Instruction,
+    spl_token_info: &AccountInfo,
+) -> Result<Instruction, ProgramError> {
+    assert_is_spl_token_program(spl_token_info)?;
+    instruction.program_id = *spl_token_info.key;
+
+    Ok(instruction)
+}
+
+/// Returns the size of a token account for the given mint
+/// Token-2022 accounts must have space for the account extensions required by the mint extensions
+pub fn get_spl_token_account_len(token_mint_info: &AccountInfo) -> Result<usize, ProgramError> {
+    assert_is_valid_spl_token_mint(token_mint_info)?;
+
+    if *token_mint_info.owner != spl_token_2022::id() {
+        return Ok(Account::LEN);
+    }
+
+    let data = token_mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let account_extension_types =
+        ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+
+    Ok(ExtensionType::get_account_len::<
+        spl_token_2022::state::Account,
+    >(&account_extension_types))
+}
+
 /// Creates and initializes SPL token account with PDA using the provided PDA seeds
 #[allow(clippy::too_many_arguments)]
 pub fn create_spl_token_account_signed<'a>(
@@ -34,12 +85,20 @@ pub fn create_spl_token_account_signed<'a>(
     rent_sysvar_info: &AccountInfo<'a>,
     rent: &Rent,
 ) -> Result<(), ProgramError> {
+    assert_is_spl_token_program(spl_token_info)?;
+
+    if token_mint_info.owner != spl_token_info.key {
+        return Err(ShihonError::SplTokenMintWithInvalidOwner.into());
+    }
+
+    let token_account_len = get_spl_token_account_len(token_mint_info)?;
+
     let create_account_instruction = system_instruction::create_account(
         payer_info.key,
         token_account_info.key,
-        1.max(rent.minimum_balance(spl_token::state::Account::get_packed_len())),
-        spl_token::state::Account::get_packed_len() as u64,
-        &spl_token::id(),
+        1.max(rent.minimum_balance(token_account_len)),
+        token_account_len as u64,
+        spl_token_info.key,
     );
 
     let (account_address, bump_seed) =
@@ -68,11 +127,14 @@ pub fn create_spl_token_account_signed<'a>(
         &[&signers_seeds[..]],
     )?;
 
-    let initialize_account_instruction = spl_token::instruction::initialize_account(
-        &spl_token::id(),
-        token_account_info.key,
-        token_mint_info.key,
-        token_account_owner_info.key,
+    let initialize_account_instruction = with_spl_token_program(
+        spl_token::instruction::initialize_account(
+            &spl_token::id(),
+            token_account_info.key,
+            token_mint_info.key,
+            token_account_owner_info.key,
+        )?,
+        spl_token_info,
     )?;
 
     invoke(
@@ -87,18 +149,246 @@ pub fn create_spl_token_account_signed<'a>(
         ],
     )?;
 
+    // Token-2022 mints with DefaultAccountState extension can initialize the account as frozen
+    // and no tokens could ever be moved in or out of it
+    let data = token_account_info.try_borrow_data()?;
+    let state = array_ref![data, 108, 1];
+
+    if state == &[AccountState::Frozen as u8] {
+        return Err(ShihonError::SplTokenAccountFrozen.into());
+    }
+
     Ok(())
 }
 
-/// Transfers SPL Tokens
-pub fn transfer_spl_tokens<'a>(
-    source_info: &AccountInfo<'a>,
+/// Creates and initializes SPL token mint with PDA using the provided PDA seeds
+#[allow(clippy::too_many_arguments)]
+pub fn create_spl_token_mint_signed<'a>(
+    payer_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    mint_address_seeds: &[&[u8]],
+    mint_authority: &Pubkey,
+    decimals: u8,
+    program_id: &Pubkey,
+    system_info: &AccountInfo<'a>,
+    spl_token_info: &AccountInfo<'a>,
+    rent_sysvar_info: &AccountInfo<'a>,
+    rent: &Rent,
+) -> Result<(), ProgramError> {
+    assert_is_spl_token_program(spl_token_info)?;
+
+    let create_account_instruction = system_instruction::create_account(
+        payer_info.key,
+        mint_info.key,
+        1.max(rent.minimum_balance(Mint::LEN)),
+        Mint::LEN as u64,
+        spl_token_info.key,
+    );
+
+    let (mint_address, bump_seed) = Pubkey::find_program_address(mint_address_seeds, program_id);
+
+    if mint_address != *mint_info.key {
+        msg!(
+            "Create SPL Token Mint with PDA: {:?} was requested while PDA: {:?} was expected",
+            mint_info.key,
+            mint_address
+        );
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut signers_seeds = mint_address_seeds.to_vec();
+    let bump = &[bump_seed];
+    signers_seeds.push(bump);
+
+    invoke_signed(
+        &create_account_instruction,
+        &[payer_info.clone(), mint_info.clone(), system_info.clone()],
+        &[&signers_seeds[..]],
+    )?;
+
+    let initialize_mint_instruction = with_spl_token_program(
+        spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            mint_info.key,
+            mint_authority,
+            None,
+            decimals,
+        )?,
+        spl_token_info,
+    )?;
+
+    invoke(
+        &initialize_mint_instruction,
+        &[
+            mint_info.clone(),
+            spl_token_info.clone(),
+            rent_sysvar_info.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Mints SPL Tokens using the mint authority PDA with the provided seeds
+pub fn mint_spl_tokens_to_signed<'a>(
+    mint_info: &AccountInfo<'a>,
     destination_info: &AccountInfo<'a>,
+    mint_authority_info: &AccountInfo<'a>,
+    mint_authority_seeds: &[&[u8]],
+    program_id: &Pubkey,
+    amount: u64,
+    spl_token_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (mint_authority_address, bump_seed) =
+        Pubkey::find_program_address(mint_authority_seeds, program_id);
+
+    if mint_authority_address != *mint_authority_info.key {
+        msg!(
+            "Mint SPL Token with Authority PDA: {:?} was requested while PDA: {:?} was expected",
+            mint_authority_info.key,
+            mint_authority_address
+        );
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mint_to_instruction = with_spl_token_program(
+        spl_token::instruction::mint_to(
+            &spl_token::id(),
+            mint_info.key,
+            destination_info.key,
+            mint_authority_info.key,
+            &[],
+            amount,
+        )?,
+        spl_token_info,
+    )?;
+
+    let mut signers_seeds = mint_authority_seeds.to_vec();
+    let bump = &[bump_seed];
+    signers_seeds.push(bump);
+
+    invoke_signed(
+        &mint_to_instruction,
+        &[
+            mint_info.clone(),
+            destination_info.clone(),
+            mint_authority_info.clone(),
+            spl_token_info.clone(),
+        ],
+        &[&signers_seeds[..]],
+    )?;
+
+    Ok(())
+}
+
+/// Removes the mint authority using the current mint authority PDA with the provided seeds
+/// Once removed no more tokens can ever be minted
+pub fn remove_spl_token_mint_authority_signed<'a>(
+    mint_info: &AccountInfo<'a>,
+    mint_authority_info: &AccountInfo<'a>,
+    mint_authority_seeds: &[&[u8]],
+    program_id: &Pubkey,
+    spl_token_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (mint_authority_address, bump_seed) =
+        Pubkey::find_program_address(mint_authority_seeds, program_id);
+
+    if mint_authority_address != *mint_authority_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let set_authority_ix = with_spl_token_program(
+        set_authority(
+            &spl_token::id(),
+            mint_info.key,
+            None,
+            AuthorityType::MintTokens,
+            mint_authority_info.key,
+            &[],
+        )?,
+        spl_token_info,
+    )?;
+
+    let mut signers_seeds = mint_authority_seeds.to_vec();
+    let bump = &[bump_seed];
+    signers_seeds.push(bump);
+
+    invoke_signed(
+        &set_authority_ix,
+        &[
+            mint_info.clone(),
+            mint_authority_info.clone(),
+            spl_token_info.clone(),
+        ],
+        &[&signers_seeds[..]],
+    )?;
+
+    Ok(())
+}
+
+/// Burns SPL Tokens
+pub fn burn_spl_tokens<'a>(
+    token_account_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
     authority_info: &AccountInfo<'a>,
     amount: u64,
     spl_token_info: &AccountInfo<'a>,
 ) -> ProgramResult {
-    let transfer_instruction = spl_token::instruction::transfer(
+    let burn_instruction = with_spl_token_program(
+        spl_token::instruction::burn(
+            &spl_token::id(),
+            token_account_info.key,
+            mint_info.key,
+            authority_info.key,
+            &[],
+            amount,
+        )?,
+        spl_token_info,
+    )?;
+
+    invoke(
+        &burn_instruction,
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            authority_info.clone(),
+            spl_token_info.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Creates transfer instruction for the token program of the given mint
+/// Token-2022 mints are transferred with transfer_checked which is required by the mint extensions
+fn create_transfer_instruction(
+    source_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    destination_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    amount: u64,
+    spl_token_info: &AccountInfo,
+) -> Result<Instruction, ProgramError> {
+    assert_is_spl_token_program(spl_token_info)?;
+
+    if mint_info.owner != spl_token_info.key {
+        return Err(ShihonError::SplTokenMintWithInvalidOwner.into());
+    }
+
+    if *spl_token_info.key == spl_token_2022::id() {
+        return spl_token_2022::instruction::transfer_checked(
+            &spl_token_2022::id(),
+            source_info.key,
+            mint_info.key,
+            destination_info.key,
+            authority_info.key,
+            &[],
+            amount,
+            get_spl_token_mint_decimals(mint_info)?,
+        );
+    }
+
+    spl_token::instruction::transfer(
         &spl_token::id(),
         source_info.key,
         destination_info.key,
@@ -106,7 +396,26 @@ pub fn transfer_spl_tokens<'a>(
         &[],
         amount,
     )
-    .unwrap();
+}
+
+/// Transfers SPL Tokens
+/// Returns the amount the destination actually received after the transfer fee of the mint
+pub fn transfer_spl_tokens<'a>(
+    source_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    destination_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    amount: u64,
+    spl_token_info: &AccountInfo<'a>,
+) -> Result<u64, ProgramError> {
+    let transfer_instruction = create_transfer_instruction(
+        source_info,
+        mint_info,
+        destination_info,
+        authority_info,
+        amount,
+        spl_token_info,
+    )?;
 
     invoke(
         &transfer_instruction,
@@ -114,23 +423,27 @@ pub fn transfer_spl_tokens<'a>(
             spl_token_info.clone(),
             authority_info.clone(),
             source_info.clone(),
+            mint_info.clone(),
             destination_info.clone(),
         ],
     )?;
 
-    Ok(())
+    get_spl_token_received_amount(mint_info, amount)
 }
 
 /// Transfers SPL Tokens from a token account owned by the provided PDA authority with seeds
+/// Returns the amount the destination actually received after the transfer fee of the mint
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_spl_tokens_signed<'a>(
     source_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
     destination_info: &AccountInfo<'a>,
     authority_info: &AccountInfo<'a>,
     authority_seeds: &[&[u8]],
     program_id: &Pubkey,
     amount: u64,
     spl_token_info: &AccountInfo<'a>,
-) -> ProgramResult {
+) -> Result<u64, ProgramError> {
     let (authority_address, bump_seed) = Pubkey::find_program_address(authority_seeds, program_id);
 
     if authority_address != *authority_info.key {
@@ -142,15 +455,14 @@ pub fn transfer_spl_tokens_signed<'a>(
         return Err(ProgramError::InvalidSeeds);
     }
 
-    let transfer_instruction = spl_token::instruction::transfer(
-        &spl_token::id(),
-        source_info.key,
-        destination_info.key,
-        authority_info.key,
-        &[],
+    let transfer_instruction = create_transfer_instruction(
+        source_info,
+        mint_info,
+        destination_info,
+        authority_info,
         amount,
-    )
-    .unwrap();
+        spl_token_info,
+    )?;
 
     let mut signers_seeds = authority_seeds.to_vec();
     let bump = &[bump_seed];
@@ -162,30 +474,92 @@ pub fn transfer_spl_tokens_signed<'a>(
             spl_token_info.clone(),
             authority_info.clone(),
             source_info.clone(),
+            mint_info.clone(),
             destination_info.clone(),
         ],
         &[&signers_seeds[..]],
     )?;
 
-    Ok(())
+    get_spl_token_received_amount(mint_info, amount)
+}
+
+/// Returns the transfer fee Token-2022 withholds from the given transfer amount of the mint in the current epoch
+/// Mints without TransferFeeConfig extension have no transfer fee
+pub fn get_spl_token_transfer_fee(
+    mint_info: &AccountInfo,
+    amount: u64,
+) -> Result<u64, ProgramError> {
+    if *mint_info.owner != spl_token_2022::id() {
+        return Ok(0);
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+        let epoch = Clock::get()?.epoch;
+        transfer_fee_config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or_else(|| ShihonError::SplTokenTransferFeeOverflow.into())
+    } else {
+        Ok(0)
+    }
+}
+
+/// Returns the amount the destination receives from the given transfer amount of the mint
+/// Escrow balances must be recorded with the received amount and not with the transferred one
+pub fn get_spl_token_received_amount(
+    mint_info: &AccountInfo,
+    amount: u64,
+) -> Result<u64, ProgramError> {
+    let fee = get_spl_token_transfer_fee(mint_info, amount)?;
+
+    amount
+        .checked_sub(fee)
+        .ok_or_else(|| ShihonError::SplTokenTransferFeeOverflow.into())
+}
+
+/// Returns true if the data length is valid for the base state of the token program account
+/// Token-2022 appends the account type and the extensions after the size of spl-token Account
+fn is_valid_spl_token_data_len(
+    owner: &Pubkey,
+    data_len: usize,
+    base_len: usize,
+    account_type: AccountType,
+    data: &[u8],
+) -> bool {
+    if data_len == base_len {
+        return true;
+    }
+
+    *owner == spl_token_2022::id()
+        && data_len > Account::LEN
+        && data[Account::LEN] == account_type as u8
 }
 
-/// Asserts the given account_info represents a valid SPL Token account which is initialized and belongs to spl_token program
+/// Asserts the given account_info represents a valid SPL Token account which is initialized and belongs to spl_token or spl_token_2022 program
 pub fn assert_is_valid_spl_token_account(account_info: &AccountInfo) -> Result<(), ProgramError> {
     if account_info.data_is_empty() {
         return Err(ShihonError::SplTokenAccountDoesNotExist.into());
     }
 
-    if account_info.owner != &spl_token::id() {
+    if !is_spl_token_program_id(account_info.owner) {
         return Err(ShihonError::SplTokenAccountWithInvalidOwner.into());
     }
 
-    if account_info.data_len() != Account::LEN {
+    // TokeAccount layout:   mint(32), owner(32), amount(8), delegate(36), state(1), ...
+    let data = account_info.try_borrow_data()?;
+
+    if !is_valid_spl_token_data_len(
+        account_info.owner,
+        account_info.data_len(),
+        Account::LEN,
+        AccountType::Account,
+        &data,
+    ) {
         return Err(ShihonError::SplTokenInvalidTokenAccountData.into());
     }
 
-    // TokeAccount layout:   mint(32), owner(32), amount(8), delegate(36), state(1), ...
-    let data = account_info.try_borrow_data()?;
     let state = array_ref![data, 108, 1];
 
     if state == &[0] {
@@ -195,22 +569,29 @@ pub fn assert_is_valid_spl_token_account(account_info: &AccountInfo) -> Result<(
     Ok(())
 }
 
-/// Asserts the given mint_info represents a valid SPL Token Mint account  which is initialized and belongs to spl_token program
+/// Asserts the given mint_info represents a valid SPL Token Mint account  which is initialized and belongs to spl_token or spl_token_2022 program
 pub fn assert_is_valid_spl_token_mint(mint_info: &AccountInfo) -> Result<(), ProgramError> {
     if mint_info.data_is_empty() {
         return Err(ShihonError::SplTokenMintDoesNotExist.into());
     }
 
-    if mint_info.owner != &spl_token::id() {
+    if !is_spl_token_program_id(mint_info.owner) {
         return Err(ShihonError::SplTokenMintWithInvalidOwner.into());
     }
 
-    if mint_info.data_len() != Mint::LEN {
+    // In token program [36, 8, 1, is_initialized(1), 36] is the layout
+    let data = mint_info.try_borrow_data().unwrap();
+
+    if !is_valid_spl_token_data_len(
+        mint_info.owner,
+        mint_info.data_len(),
+        Mint::LEN,
+        AccountType::Mint,
+        &data,
+    ) {
         return Err(ShihonError::SplTokenInvalidMintAccountData.into());
     }
 
-    // In token program [36, 8, 1, is_initialized(1), 36] is the layout
-    let data = mint_info.try_borrow_data().unwrap();
     let is_initialized = array_ref![data, 45, 1];
 
     if is_initialized == &[0] {
@@ -242,6 +623,17 @@ pub fn get_spl_token_owner(token_account_info: &AccountInfo) -> Result<Pubkey, P
     Ok(Pubkey::new_from_array(*owner_data))
 }
 
+/// Computationally cheap method to get amount from a token account
+/// It reads amount without deserializing full account data
+pub fn get_spl_token_amount(token_account_info: &AccountInfo) -> Result<u64, ProgramError> {
+    assert_is_valid_spl_token_account(token_account_info)?;
+
+    // TokeAccount layout:   mint(32), owner(32), amount(8), ...
+    let data = token_account_info.try_borrow_data()?;
+    let amount_data = array_ref![data, 64, 8];
+    Ok(u64::from_le_bytes(*amount_data))
+}
+
 /// Computationally cheap method to just get supply from a mint without unpacking the whole object
 pub fn get_spl_token_mint_supply(mint_info: &AccountInfo) -> Result<u64, ProgramError> {
     assert_is_valid_spl_token_mint(mint_info)?;
@@ -253,6 +645,16 @@ pub fn get_spl_token_mint_supply(mint_info: &AccountInfo) -> Result<u64, Program
     Ok(u64::from_le_bytes(*bytes))
 }
 
+/// Computationally cheap method to just get decimals from a mint without unpacking the whole object
+pub fn get_spl_token_mint_decimals(mint_info: &AccountInfo) -> Result<u8, ProgramError> {
+    assert_is_valid_spl_token_mint(mint_info)?;
+    // In token program, 36, 8, 1, 1 is the layout, where the first 1 is decimals u8.
+    // so we start at 44.
+    let data = mint_info.try_borrow_data().unwrap();
+
+    Ok(data[44])
+}
+
 /// Computationally cheap method to just get authority from a mint without unpacking the whole object
 pub fn get_spl_token_mint_authority(
     mint_info: &AccountInfo,
@@ -313,13 +715,16 @@ pub fn set_spl_token_account_authority<'a>(
     authority_type: AuthorityType,
     spl_token_info: &AccountInfo<'a>,
 ) -> Result<(), ProgramError> {
-    let set_authority_ix = set_authority(
-        &spl_token::id(),
-        account_info.key,
-        Some(new_account_authority),
-        authority_type,
-        account_authority.key,
-        &[],
+    let set_authority_ix = with_spl_token_program(
+        set_authority(
+            &spl_token::id(),
+            account_info.key,
+            Some(new_account_authority),
+            authority_type,
+            account_authority.key,
+            &[],
+        )?,
+        spl_token_info,
     )?;
 
     invoke(
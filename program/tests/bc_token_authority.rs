@@ -0,0 +1,228 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::ShihonProgramTest,
+    shihon::{error::ShihonError, state::enums::BcTokenState},
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_set_bc_token_authority() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let new_authority = Keypair::new();
+
+    // Act
+    shihon_test
+        .set_bc_token_authority(
+            &bc_token_cookie,
+            &bc_token_cookie.authority,
+            Some(new_authority.pubkey()),
+            false,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let bc_token = shihon_test
+        .get_bc_token_account(&bc_token_cookie.address)
+        .await;
+
+    assert_eq!(bc_token.authority, Some(new_authority.pubkey()));
+    assert_eq!(bc_token.pending_authority, None);
+}
+
+#[tokio::test]
+async fn test_freeze_bc_token() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    shihon_test
+        .renounce_bc_token_authority(&bc_token_cookie, &bc_token_cookie.authority)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .set_bc_token_authority(
+            &bc_token_cookie,
+            &bc_token_cookie.authority,
+            Some(Keypair::new().pubkey()),
+            false,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::BcTokenHasNoAuthority);
+
+    let bc_token = shihon_test
+        .get_bc_token_account(&bc_token_cookie.address)
+        .await;
+
+    assert_eq!(bc_token.authority, None);
+}
+
+#[tokio::test]
+async fn test_set_bc_token_authority_to_none_without_renounce_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    // Act
+    let err = shihon_test
+        .set_bc_token_authority(&bc_token_cookie, &bc_token_cookie.authority, None, false)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidBcTokenAuthorityRenounce);
+
+    let bc_token = shihon_test
+        .get_bc_token_account(&bc_token_cookie.address)
+        .await;
+
+    assert_eq!(bc_token.authority, Some(bc_token_cookie.authority.pubkey()));
+}
+
+#[tokio::test]
+async fn test_propose_and_accept_bc_token_authority() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let new_authority = Keypair::new();
+
+    shihon_test
+        .set_bc_token_authority(
+            &bc_token_cookie,
+            &bc_token_cookie.authority,
+            Some(new_authority.pubkey()),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let bc_token = shihon_test
+        .get_bc_token_account(&bc_token_cookie.address)
+        .await;
+
+    assert_eq!(bc_token.authority, Some(bc_token_cookie.authority.pubkey()));
+    assert_eq!(bc_token.pending_authority, Some(new_authority.pubkey()));
+
+    // Act
+    shihon_test
+        .accept_bc_token_authority(&bc_token_cookie, &new_authority)
+        .await
+        .unwrap();
+
+    // Assert
+    let bc_token = shihon_test
+        .get_bc_token_account(&bc_token_cookie.address)
+        .await;
+
+    assert_eq!(bc_token.authority, Some(new_authority.pubkey()));
+    assert_eq!(bc_token.pending_authority, None);
+}
+
+#[tokio::test]
+async fn test_accept_bc_token_authority_with_invalid_pending_authority_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    shihon_test
+        .set_bc_token_authority(
+            &bc_token_cookie,
+            &bc_token_cookie.authority,
+            Some(Keypair::new().pubkey()),
+            true,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .accept_bc_token_authority(&bc_token_cookie, &Keypair::new())
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidPendingAuthorityForBcToken);
+}
+
+#[tokio::test]
+async fn test_set_bc_token_authority_in_active_tanistry_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    shihon_test
+        .set_bc_token_state(&bc_token_cookie, BcTokenState::EnableToCandidate)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .set_bc_token_authority(
+            &bc_token_cookie,
+            &bc_token_cookie.authority,
+            Some(Keypair::new().pubkey()),
+            false,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::BcTokenInActiveTanistry);
+}
+
+#[tokio::test]
+async fn test_accept_bc_token_authority_in_active_tanistry_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let new_authority = Keypair::new();
+
+    shihon_test
+        .set_bc_token_authority(
+            &bc_token_cookie,
+            &bc_token_cookie.authority,
+            Some(new_authority.pubkey()),
+            true,
+        )
+        .await
+        .unwrap();
+
+    shihon_test
+        .set_bc_token_state(&bc_token_cookie, BcTokenState::TanistryHasSetIn)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .accept_bc_token_authority(&bc_token_cookie, &new_authority)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::BcTokenInActiveTanistry);
+}
@@ -0,0 +1,558 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{ShihonProgramTest, ENROLLMENT_TIME, MIN_CANDIDATES},
+    shihon::{
+        error::ShihonError,
+        state::{
+            enums::{BcTokenState, TanistryState},
+            outside_buyer_record::OutsideBuyerRecord,
+        },
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, transaction::TransactionError, transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+    let kicker_coin_amount = 100;
+    let deposit_amount = 50;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, deposit_amount)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    // Act
+    shihon_test
+        .cancel_tanistry(
+            &tanistry_cookie,
+            &candidate_cookies,
+            &[bc_token_cookie.address, candidate_cookies[0].bc_token],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::Cancelled);
+    assert_eq!(tanistry.settled_amount, kicker_coin_amount + deposit_amount);
+    assert_eq!(tanistry.kicker_refund_amount, kicker_coin_amount);
+    assert_eq!(tanistry.coordinator_refund_amount, 0);
+
+    let bc_token = shihon_test
+        .get_bc_token_account(&bc_token_cookie.address)
+        .await;
+
+    assert_eq!(bc_token.bc_token_state, BcTokenState::PublicOnTheGround);
+
+    let candidate_bc_token = shihon_test
+        .get_bc_token_account(&candidate_cookies[0].bc_token)
+        .await;
+
+    assert_eq!(
+        candidate_bc_token.bc_token_state,
+        BcTokenState::PublicOnTheGround
+    );
+
+    // Every deposit is claimed back in full
+    let candidate_cookie = &candidate_cookies[0];
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &candidate_cookie.address,
+            &candidate_cookie.owner,
+            &candidate_cookie.token_account,
+        )
+        .await
+        .unwrap();
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.kicker,
+            &kicker_coin_cookie.kicker_token_account,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&candidate_cookie.token_account)
+            .await,
+        deposit_amount
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&kicker_coin_cookie.kicker_token_account)
+            .await,
+        kicker_coin_amount
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        0
+    );
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::Cancelled);
+    assert_eq!(tanistry.refunded_amount, tanistry.settled_amount);
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry_before_enrollment_ended_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .cancel_tanistry(&tanistry_cookie, &candidate_cookies, &[])
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::TanistryEnrollmentNotEnded);
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry_with_enough_candidates_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    // Act
+    let err = shihon_test
+        .cancel_tanistry(&tanistry_cookie, &candidate_cookies, &[])
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::TanistryHasEnoughCandidates);
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry_with_foreign_bc_token_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    // The bcToken of somebody who never joined the Tanistry
+    let foreign_bc_token_cookie = shihon_test.with_bc_token("foreign bcToken").await;
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    // Act
+    let err = shihon_test
+        .cancel_tanistry(
+            &tanistry_cookie,
+            &candidate_cookies,
+            &[bc_token_cookie.address, foreign_bc_token_cookie.address],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidBcTokenForTanistry);
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry_with_missing_bc_token_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    // Act
+    let err = shihon_test
+        .cancel_tanistry(
+            &tanistry_cookie,
+            &candidate_cookies,
+            &[bc_token_cookie.address],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidBcTokenForTanistry);
+}
+
+#[tokio::test]
+async fn test_buy_without_enough_candidates_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    // Act
+    let err = shihon_test
+        .with_outside_buyer(&tanistry_cookie, 40)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::TanistryHasNotEnoughCandidates);
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry_and_claim_outside_buyer_refund() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+    let kicker_coin_amount = 100;
+    let deposit_amount = 50;
+    let outside_buyer_amount = 40;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, MIN_CANDIDATES as usize, deposit_amount)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    let outside_buyer_cookie = shihon_test
+        .with_outside_buyer(&tanistry_cookie, outside_buyer_amount)
+        .await
+        .unwrap();
+
+    // The admin raises the minimum after the buy and the running Tanistry becomes cancellable
+    shihon_test
+        .update_min_candidates(&shihon_config_cookie, MIN_CANDIDATES + 1)
+        .await
+        .unwrap();
+
+    let mut bc_tokens = vec![bc_token_cookie.address];
+    bc_tokens.extend(
+        candidate_cookies
+            .iter()
+            .map(|candidate_cookie| candidate_cookie.bc_token),
+    );
+
+    shihon_test
+        .cancel_tanistry(&tanistry_cookie, &candidate_cookies, &bc_tokens)
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &outside_buyer_cookie.address,
+            &outside_buyer_cookie.buyer,
+            &outside_buyer_cookie.token_account,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(
+        tanistry.settled_amount,
+        kicker_coin_amount + outside_buyer_amount + deposit_amount * MIN_CANDIDATES as u64
+    );
+    assert_eq!(tanistry.refunded_amount, outside_buyer_amount);
+
+    let outside_buyer_record: OutsideBuyerRecord = shihon_test
+        .get_borsh_account(&outside_buyer_cookie.address)
+        .await;
+
+    assert!(outside_buyer_record.is_refunded);
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&outside_buyer_cookie.token_account)
+            .await,
+        outside_buyer_amount
+    );
+
+    // The candidates and the kicker get their deposits back too and the vault is drained
+    for candidate_cookie in &candidate_cookies {
+        shihon_test
+            .claim_refund(
+                &tanistry_cookie,
+                &candidate_cookie.address,
+                &candidate_cookie.owner,
+                &candidate_cookie.token_account,
+            )
+            .await
+            .unwrap();
+    }
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.kicker,
+            &kicker_coin_cookie.kicker_token_account,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_claim_outside_buyer_refund_from_running_tanistry_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    shihon_test
+        .with_candidates(&tanistry_cookie, MIN_CANDIDATES as usize, 50)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    let outside_buyer_cookie = shihon_test
+        .with_outside_buyer(&tanistry_cookie, 40)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &outside_buyer_cookie.address,
+            &outside_buyer_cookie.buyer,
+            &outside_buyer_cookie.token_account,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(
+        err,
+        ShihonError::OutsideBuyerRefundOnlyFromCancelledTanistry,
+    );
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry_with_dust_sent_to_vault() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+    let kicker_coin_amount = 100;
+    let deposit_amount = 50;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, deposit_amount)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    // Anybody can send tokens to the vault
+    shihon_test
+        .mint_tokens(&tanistry_cookie.token_holding, 1)
+        .await;
+
+    // Act
+    shihon_test
+        .cancel_tanistry(
+            &tanistry_cookie,
+            &candidate_cookies,
+            &[bc_token_cookie.address, candidate_cookies[0].bc_token],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::Cancelled);
+    assert_eq!(tanistry.settled_amount, kicker_coin_amount + deposit_amount);
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry_with_vault_balance_mismatch_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+    let kicker_coin_amount = 100;
+    let deposit_amount = 50;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, deposit_amount)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    // The vault holds less than the KickerCoin and the deposits recorded by the Tanistry
+    shihon_test
+        .set_token_account_balance(&tanistry_cookie.token_holding, kicker_coin_amount)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .cancel_tanistry(
+            &tanistry_cookie,
+            &candidate_cookies,
+            &[bc_token_cookie.address, candidate_cookies[0].bc_token],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::TanistryVaultBalanceMismatch);
+}
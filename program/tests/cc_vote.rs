@@ -0,0 +1,816 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{CandidateCookie, ShihonProgramTest, TanistryCookie, ENROLLMENT_TIME},
+    shihon::{
+        error::ShihonError,
+        state::{cc_vote_record::CC_VOTE_COUNTING_PERIOD, enums::VoteSource},
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+/// Forms a crowned Tanistry whose Ring holds the deposits of its candidates
+async fn with_crowned_tanistry(
+    shihon_test: &ShihonProgramTest,
+    name: &str,
+    deposit_amount: u64,
+) -> (TanistryCookie, Vec<CandidateCookie>) {
+    let bc_token_cookie = shihon_test.with_bc_token(name).await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, deposit_amount)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    (tanistry_cookie, candidate_cookies)
+}
+
+/// Forms two crowned Tanistries with their Rings and enrolls a candidate into a new Tanistry
+/// The voter's Ring is empty so the voter can push both Rings
+async fn with_rings_and_voter(
+    shihon_test: &ShihonProgramTest,
+) -> (TanistryCookie, TanistryCookie, CandidateCookie) {
+    let (tanistry_cookie, _) = with_crowned_tanistry(shihon_test, "bcToken", 50).await;
+    let (other_tanistry_cookie, _) = with_crowned_tanistry(shihon_test, "other bcToken", 60).await;
+
+    let voter_bc_token_cookie = shihon_test.with_bc_token("voter bcToken").await;
+    let voter_kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&voter_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let voter_tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&voter_kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    let candidate_cookie = shihon_test
+        .with_candidate(&voter_tanistry_cookie, 50)
+        .await
+        .unwrap();
+
+    (tanistry_cookie, other_tanistry_cookie, candidate_cookie)
+}
+
+#[tokio::test]
+async fn test_vote_for_cc() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    // Act
+    let cc_vote_record = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let cc_vote_record_account = shihon_test
+        .get_cc_vote_record_account(&cc_vote_record)
+        .await;
+
+    assert_eq!(cc_vote_record_account.target_ring, tanistry_cookie.ring);
+    assert_eq!(
+        cc_vote_record_account.voter,
+        candidate_cookie.owner.pubkey()
+    );
+    assert_eq!(cc_vote_record_account.voter_weight, 50);
+    assert!(!cc_vote_record_account.is_relinquished);
+
+    let ring_account = shihon_test.get_ring_account(&tanistry_cookie.ring).await;
+    assert_eq!(ring_account.cc_vote_weight, 50);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_with_candidate_delegate() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    let candidate_delegate = Keypair::new();
+    shihon_test
+        .set_candidate_delegate(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            Some(candidate_delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let cc_vote_record = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_delegate,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let cc_vote_record_account = shihon_test
+        .get_cc_vote_record_account(&cc_vote_record)
+        .await;
+
+    // The vote belongs to the owner even though the delegate casted it
+    assert_eq!(
+        cc_vote_record_account.voter,
+        candidate_cookie.owner.pubkey()
+    );
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_for_second_ring_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, other_tanistry_cookie, candidate_cookie) =
+        with_rings_and_voter(&shihon_test).await;
+
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &other_tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CCVoteAlreadyExists);
+
+    let other_ring_account = shihon_test
+        .get_ring_account(&other_tanistry_cookie.ring)
+        .await;
+    assert_eq!(other_ring_account.cc_vote_weight, 0);
+}
+
+#[tokio::test]
+async fn test_relinquish_cc_vote() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    let cc_vote_record = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .relinquish_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let cc_vote_record_account = shihon_test
+        .get_cc_vote_record_account(&cc_vote_record)
+        .await;
+    assert!(cc_vote_record_account.is_relinquished);
+
+    let ring_account = shihon_test.get_ring_account(&tanistry_cookie.ring).await;
+    assert_eq!(ring_account.cc_vote_weight, 0);
+}
+
+#[tokio::test]
+async fn test_relinquish_cc_vote_twice_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+    shihon_test
+        .relinquish_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .relinquish_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CCVoteAlreadyRelinquished);
+}
+
+#[tokio::test]
+async fn test_relinquish_cc_vote_with_other_voter_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .relinquish_cc_vote(&candidate_cookie, &Keypair::new(), &tanistry_cookie.ring)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CandidateTokenOwnerOrDelegateMustSign);
+}
+
+#[tokio::test]
+async fn test_relinquish_cc_vote_with_candidate_delegate() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    let cc_vote_record = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    let candidate_delegate = Keypair::new();
+    shihon_test
+        .set_candidate_delegate(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            Some(candidate_delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .relinquish_cc_vote(
+            &candidate_cookie,
+            &candidate_delegate,
+            &tanistry_cookie.ring,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let cc_vote_record_account = shihon_test
+        .get_cc_vote_record_account(&cc_vote_record)
+        .await;
+    assert!(cc_vote_record_account.is_relinquished);
+
+    let ring_account = shihon_test.get_ring_account(&tanistry_cookie.ring).await;
+    assert_eq!(ring_account.cc_vote_weight, 0);
+}
+
+#[tokio::test]
+async fn test_relinquish_cc_vote_after_counting_time_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    shihon_test.advance_slots(CC_VOTE_COUNTING_PERIOD).await;
+
+    // Act
+    let err = shihon_test
+        .relinquish_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CCVoteCountingTimeReached);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_again_after_relinquish() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, other_tanistry_cookie, candidate_cookie) =
+        with_rings_and_voter(&shihon_test).await;
+
+    let cc_vote_record = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+    let counting_time = shihon_test
+        .get_ring_account(&tanistry_cookie.ring)
+        .await
+        .cc_vote_counting_time;
+
+    shihon_test
+        .relinquish_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &other_tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let cc_vote_record_account = shihon_test
+        .get_cc_vote_record_account(&cc_vote_record)
+        .await;
+
+    assert_eq!(
+        cc_vote_record_account.target_ring,
+        other_tanistry_cookie.ring
+    );
+    assert!(!cc_vote_record_account.is_relinquished);
+
+    let ring_account = shihon_test.get_ring_account(&tanistry_cookie.ring).await;
+    assert_eq!(ring_account.cc_vote_weight, 0);
+    assert_eq!(ring_account.cc_vote_counting_time, counting_time);
+
+    let other_ring_account = shihon_test
+        .get_ring_account(&other_tanistry_cookie.ring)
+        .await;
+    assert_eq!(other_ring_account.cc_vote_weight, 50);
+}
+
+#[tokio::test]
+async fn test_change_cc_vote() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, other_tanistry_cookie, candidate_cookie) =
+        with_rings_and_voter(&shihon_test).await;
+
+    let cc_vote_record = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .change_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            &other_tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let cc_vote_record_account = shihon_test
+        .get_cc_vote_record_account(&cc_vote_record)
+        .await;
+
+    assert_eq!(
+        cc_vote_record_account.target_ring,
+        other_tanistry_cookie.ring
+    );
+    assert!(!cc_vote_record_account.is_relinquished);
+
+    let ring_account = shihon_test.get_ring_account(&tanistry_cookie.ring).await;
+    assert_eq!(ring_account.cc_vote_weight, 0);
+
+    let other_ring_account = shihon_test
+        .get_ring_account(&other_tanistry_cookie.ring)
+        .await;
+    assert_eq!(other_ring_account.cc_vote_weight, 50);
+}
+
+#[tokio::test]
+async fn test_change_cc_vote_to_same_ring_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .change_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CCVoteTargetRingNotChanged);
+}
+
+#[tokio::test]
+async fn test_change_relinquished_cc_vote_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, other_tanistry_cookie, candidate_cookie) =
+        with_rings_and_voter(&shihon_test).await;
+
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+    shihon_test
+        .relinquish_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .change_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            &other_tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CCVoteAlreadyRelinquished);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_pull_smaller_ring() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (smaller_tanistry_cookie, _) = with_crowned_tanistry(&shihon_test, "bcToken", 50).await;
+    let (_, candidate_cookies) = with_crowned_tanistry(&shihon_test, "other bcToken", 60).await;
+
+    // Act
+    let cc_vote_record = shihon_test
+        .vote_for_cc(
+            &candidate_cookies[1],
+            &candidate_cookies[1].owner,
+            &smaller_tanistry_cookie.ring,
+            VoteSource::Pull,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let cc_vote_record_account = shihon_test
+        .get_cc_vote_record_account(&cc_vote_record)
+        .await;
+    assert_eq!(cc_vote_record_account.vote, VoteSource::Pull);
+
+    let ring_account = shihon_test
+        .get_ring_account(&smaller_tanistry_cookie.ring)
+        .await;
+    assert_eq!(ring_account.cc_vote_weight, 60);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_pull_bigger_ring_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    // Act
+    let err = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Pull,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CannotPullBiggerRing);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_push_smaller_ring_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (smaller_tanistry_cookie, _) = with_crowned_tanistry(&shihon_test, "bcToken", 50).await;
+    let (_, candidate_cookies) = with_crowned_tanistry(&shihon_test, "other bcToken", 60).await;
+
+    // Act
+    let err = shihon_test
+        .vote_for_cc(
+            &candidate_cookies[1],
+            &candidate_cookies[1].owner,
+            &smaller_tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CannotPushSmallerRing);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_with_transit_vote_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, _, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+
+    // Act
+    let err = shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Transit,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidCCVoteSource);
+}
+
+#[tokio::test]
+async fn test_change_cc_vote_pull_bigger_ring_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (tanistry_cookie, other_tanistry_cookie, candidate_cookie) =
+        with_rings_and_voter(&shihon_test).await;
+
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .change_cc_vote(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &tanistry_cookie.ring,
+            &other_tanistry_cookie.ring,
+            VoteSource::Pull,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CannotPullBiggerRing);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_after_ring_counting_time_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (_, other_tanistry_cookie, candidate_cookie) = with_rings_and_voter(&shihon_test).await;
+    let (_, late_candidate_cookies) = with_crowned_tanistry(&shihon_test, "late bcToken", 50).await;
+
+    // The first vote opens the counting window of the ring for every voter
+    shihon_test
+        .vote_for_cc(
+            &candidate_cookie,
+            &candidate_cookie.owner,
+            &other_tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .unwrap();
+
+    shihon_test.advance_slots(CC_VOTE_COUNTING_PERIOD).await;
+
+    // Act
+    let err = shihon_test
+        .vote_for_cc(
+            &late_candidate_cookies[1],
+            &late_candidate_cookies[1].owner,
+            &other_tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CCVoteCountingTimeReached);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_from_refunded_candidate_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let (_, other_tanistry_cookie, _) = with_rings_and_voter(&shihon_test).await;
+    let (refunded_tanistry_cookie, refunded_candidate_cookies) =
+        with_crowned_tanistry(&shihon_test, "refunded bcToken", 50).await;
+
+    shihon_test
+        .settle_tanistry(&refunded_tanistry_cookie, &refunded_candidate_cookies)
+        .await
+        .unwrap();
+
+    let refunded_candidate_cookie = &refunded_candidate_cookies[1];
+    shihon_test
+        .claim_refund(
+            &refunded_tanistry_cookie,
+            &refunded_candidate_cookie.address,
+            &refunded_candidate_cookie.owner,
+            &refunded_candidate_cookie.token_account,
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .vote_for_cc(
+            refunded_candidate_cookie,
+            &refunded_candidate_cookie.owner,
+            &other_tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CCVoteFromRefundedCandidate);
+}
+
+#[tokio::test]
+async fn test_vote_for_cc_from_cancelled_tanistry_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+    let (tanistry_cookie, _) = with_crowned_tanistry(&shihon_test, "bcToken", 50).await;
+
+    let voter_bc_token_cookie = shihon_test.with_bc_token("voter bcToken").await;
+    let voter_kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&voter_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let voter_tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&voter_kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&voter_tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    shihon_test
+        .cancel_tanistry(
+            &voter_tanistry_cookie,
+            &candidate_cookies,
+            &[voter_bc_token_cookie.address, candidate_cookies[0].bc_token],
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .vote_for_cc(
+            &candidate_cookies[0],
+            &candidate_cookies[0].owner,
+            &tanistry_cookie.ring,
+            VoteSource::Push,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CCVoteFromCancelledTanistry);
+}
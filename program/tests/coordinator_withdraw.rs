@@ -0,0 +1,559 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{ShihonProgramTest, ENROLLMENT_TIME},
+    shihon::{
+        error::ShihonError,
+        pda::get_coordinator_refund_receipt_mint_address,
+        state::{
+            enums::TanistryState,
+            tanistry::{COORDINATOR_KICKER_COIN_SHARE_BPS, MAX_BPS},
+        },
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_coordinator_withdraw_and_redeem_refund_receipt() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let kicker_coin_amount = 100;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    let refund_receipt_token_account = shihon_test
+        .coordinator_withdraw(&kicker_coin_cookie, &tanistry_cookie)
+        .await
+        .unwrap();
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(
+        tanistry.coordinator_refund_receipt_mint,
+        Some(get_coordinator_refund_receipt_mint_address(
+            &shihon_test.program_id,
+            &tanistry_cookie.address
+        ))
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&refund_receipt_token_account)
+            .await,
+        1
+    );
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .redeem_coordinator_refund_receipt(
+            &tanistry_cookie,
+            &refund_receipt_token_account,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let coordinator_refund_amount =
+        kicker_coin_amount * COORDINATOR_KICKER_COIN_SHARE_BPS / MAX_BPS;
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert!(tanistry.is_coordinator_refunded);
+    assert_eq!(
+        tanistry.coordinator_refund_amount,
+        coordinator_refund_amount
+    );
+    assert_eq!(tanistry.refunded_amount, coordinator_refund_amount);
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&kicker_coin_cookie.coordinator_token_account)
+            .await,
+        coordinator_refund_amount
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&refund_receipt_token_account)
+            .await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_coordinator_withdraw_twice_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    shihon_test
+        .coordinator_withdraw(&kicker_coin_cookie, &tanistry_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    // Use the coordinator as the payer to send a different transaction
+    let err = shihon_test
+        .coordinator_withdraw_with_payer(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &kicker_coin_cookie.coordinator,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CoordinatorAlreadyWithdrawn);
+}
+
+#[tokio::test]
+async fn test_redeem_coordinator_refund_receipt_with_other_holder_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    let refund_receipt_token_account = shihon_test
+        .coordinator_withdraw(&kicker_coin_cookie, &tanistry_cookie)
+        .await
+        .unwrap();
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    let other_holder = Keypair::new();
+    let other_holder_token_account = shihon_test
+        .create_token_account_with_balance(&other_holder.pubkey(), 0)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .redeem_coordinator_refund_receipt(
+            &tanistry_cookie,
+            &refund_receipt_token_account,
+            &other_holder,
+            &other_holder_token_account,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidTokenOwner);
+}
+
+#[tokio::test]
+async fn test_redeem_coordinator_refund_receipt_before_settlement_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    let refund_receipt_token_account = shihon_test
+        .coordinator_withdraw(&kicker_coin_cookie, &tanistry_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .redeem_coordinator_refund_receipt(
+            &tanistry_cookie,
+            &refund_receipt_token_account,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidTanistryStateCannotRefund);
+}
+
+#[tokio::test]
+async fn test_coordinator_withdraw_with_other_coordinator_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    let other_coordinator = Keypair::new();
+    let payer = shihon_test.get_payer().await;
+
+    // Act
+    let err = shihon_test
+        .coordinator_withdraw_using_instruction(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &payer,
+            |i| i.accounts[2].pubkey = other_coordinator.pubkey(), // coordinator
+            Some(&[&other_coordinator]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidCoordinatorForTanistry);
+}
+
+#[tokio::test]
+async fn test_coordinator_withdraw_without_coordinator_signature_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    let payer = shihon_test.get_payer().await;
+
+    // Act
+    let err = shihon_test
+        .coordinator_withdraw_using_instruction(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &payer,
+            |i| i.accounts[2].is_signer = false, // coordinator
+            Some(&[]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CoordinatorMustSign);
+}
+
+#[tokio::test]
+async fn test_redeem_coordinator_refund_receipt_without_holder_signature_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    let refund_receipt_token_account = shihon_test
+        .coordinator_withdraw(&kicker_coin_cookie, &tanistry_cookie)
+        .await
+        .unwrap();
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .redeem_coordinator_refund_receipt_using_instruction(
+            &tanistry_cookie,
+            &refund_receipt_token_account,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+            |i| i.accounts[4].is_signer = false, // holder
+            Some(&[]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::TokenOwnerMustSign);
+}
+
+#[tokio::test]
+async fn test_redeem_coordinator_refund_receipt_twice_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    let refund_receipt_token_account = shihon_test
+        .coordinator_withdraw(&kicker_coin_cookie, &tanistry_cookie)
+        .await
+        .unwrap();
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    shihon_test
+        .redeem_coordinator_refund_receipt(
+            &tanistry_cookie,
+            &refund_receipt_token_account,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+        )
+        .await
+        .unwrap();
+
+    // Use another token account to send a different transaction
+    let coordinator_token_account = shihon_test
+        .create_token_account_with_balance(&kicker_coin_cookie.coordinator.pubkey(), 0)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .redeem_coordinator_refund_receipt(
+            &tanistry_cookie,
+            &refund_receipt_token_account,
+            &kicker_coin_cookie.coordinator,
+            &coordinator_token_account,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::RefundAlreadyClaimed);
+}
+
+#[tokio::test]
+async fn test_crowning_after_coordinator_withdraw_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .coordinator_withdraw(&kicker_coin_cookie, &tanistry_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CoordinatorAlreadyWithdrawn);
+}
+
+#[tokio::test]
+async fn test_cancel_tanistry_after_coordinator_withdraw() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+    let kicker_coin_amount = 100;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    let refund_receipt_token_account = shihon_test
+        .coordinator_withdraw(&kicker_coin_cookie, &tanistry_cookie)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    let mut bc_tokens = vec![bc_token_cookie.address];
+    bc_tokens.extend(
+        candidate_cookies
+            .iter()
+            .map(|candidate_cookie| candidate_cookie.bc_token),
+    );
+
+    // Act
+    shihon_test
+        .cancel_tanistry(&tanistry_cookie, &candidate_cookies, &bc_tokens)
+        .await
+        .unwrap();
+
+    // Assert
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::Cancelled);
+    assert_eq!(tanistry.kicker_refund_amount, kicker_coin_amount);
+    assert_eq!(tanistry.coordinator_refund_amount, 0);
+
+    // The receipt of the dropped game redeems nothing
+    shihon_test
+        .redeem_coordinator_refund_receipt(
+            &tanistry_cookie,
+            &refund_receipt_token_account,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&kicker_coin_cookie.coordinator_token_account)
+            .await,
+        0
+    );
+}
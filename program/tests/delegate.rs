@@ -0,0 +1,230 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{ShihonProgramTest, ENROLLMENT_TIME},
+    shihon::error::ShihonError,
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_set_candidate_delegate() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    let candidate_cookie = &candidate_cookies[0];
+    let delegate = Keypair::new();
+
+    // Act
+    shihon_test
+        .set_candidate_delegate(
+            candidate_cookie,
+            &candidate_cookie.owner,
+            Some(delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let candidate_limit_record = shihon_test
+        .get_candidate_limit_record_account(&candidate_cookie.address)
+        .await;
+
+    assert_eq!(
+        candidate_limit_record.candidate_delegate,
+        Some(delegate.pubkey())
+    );
+}
+
+#[tokio::test]
+async fn test_set_candidate_delegate_by_delegate_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    let candidate_cookie = &candidate_cookies[0];
+    let delegate = Keypair::new();
+
+    shihon_test
+        .set_candidate_delegate(
+            candidate_cookie,
+            &candidate_cookie.owner,
+            Some(delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .set_candidate_delegate(candidate_cookie, &delegate, Some(Keypair::new().pubkey()))
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CandidateTokenOwnerMustSign);
+}
+
+#[tokio::test]
+async fn test_rate_content_by_candidate_delegate() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&init_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    let mix_content_record = shihon_test
+        .mix_content(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie.address,
+            &candidate_cookies[1].bc_token,
+        )
+        .await
+        .unwrap();
+
+    let delegate = Keypair::new();
+
+    shihon_test
+        .set_candidate_delegate(
+            &candidate_cookies[0],
+            &candidate_cookies[0].owner,
+            Some(delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .rate_content_with_authority(
+            &candidate_cookies[0],
+            &delegate,
+            &candidate_cookies[1],
+            &mix_content_record,
+            30,
+            &[&candidate_cookies[1].owner, &kicker_coin_cookie.kicker],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let buddy_candidate_limit_record = shihon_test
+        .get_candidate_limit_record_account(&candidate_cookies[1].address)
+        .await;
+
+    assert_eq!(buddy_candidate_limit_record.received_rating, 30);
+}
+
+#[tokio::test]
+async fn test_claim_refund_by_candidate_delegate_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    let candidate_cookie = &candidate_cookies[0];
+    let delegate = Keypair::new();
+
+    shihon_test
+        .set_candidate_delegate(
+            candidate_cookie,
+            &candidate_cookie.owner,
+            Some(delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    shihon_test
+        .cancel_tanistry(
+            &tanistry_cookie,
+            &candidate_cookies,
+            &[bc_token_cookie.address],
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &candidate_cookie.address,
+            &delegate,
+            &candidate_cookie.token_account,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidRefundClaimant);
+}
@@ -0,0 +1,392 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::ShihonProgramTest,
+    shihon::{
+        error::ShihonError,
+        pda::{
+            find_bump_seed, get_bc_token_address, get_bc_token_address_seeds,
+            get_candidate_limit_record_address, get_cc_vote_record_address,
+            get_cc_vote_record_address_seeds, get_outside_buyer_record_address,
+            get_outside_buyer_record_address_seeds, get_ring_address, get_tanistry_address,
+            get_tanistry_address_seeds,
+        },
+        state::{
+            bc_token::BcToken,
+            bc_token_metadata::ContentType,
+            enums::{BcTokenState, ShihonAccountType, VoteSource},
+            legacy::{
+                BcTokenMetadataV2, BcTokenV2, CCVoteRecordV1, CandidateLimitRecordV1,
+                OutsideBuyerRecordV2, RateOtherRecordV1, TanistryV1,
+            },
+            outside_buyer_record::OutsideBuyerRecord,
+        },
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError,
+        transport::TransportError,
+    },
+    spl_governance_tools::account::AccountMaxSize,
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+const BC_TOKEN_NAME: &str = "Coke and Mentos";
+
+fn create_test_bc_token_v2(bump: u8) -> BcTokenV2 {
+    BcTokenV2 {
+        account_type: ShihonAccountType::BcTokenV2,
+        bump,
+        bc_token_owner_pubkey: Pubkey::new_unique(),
+        amount_of_coin: 10,
+        issue_at: 0,
+        reserved: [0; 8],
+        authority: Some(Pubkey::new_unique()),
+        name: BC_TOKEN_NAME.to_string(),
+        bc_token_mint: Pubkey::new_unique(),
+        bc_token_state: BcTokenState::HoldingOnPrivate,
+        config: BcTokenMetadataV2 {
+            account_type: ShihonAccountType::BcTokenMetadataV2,
+            bump: 254,
+            content_type: ContentType::Ordinary,
+            updated_at: 0,
+            version: "1".to_string(),
+            reserved: [0; 64],
+            is_writable: true,
+            is_signer: false,
+        },
+    }
+}
+
+fn create_test_tanistry_v1(previous_tanistry_id: Option<Pubkey>) -> TanistryV1 {
+    TanistryV1 {
+        account_type: ShihonAccountType::TanistryV1,
+        next_tanistry_id: None,
+        previous_tanistry_id,
+        reserved: [0; 8],
+        kicker_coin_owner_record: Pubkey::new_unique(),
+        candidate_limit_record_list: vec![],
+    }
+}
+
+#[tokio::test]
+async fn test_migrate_bc_token_v2() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_address = get_bc_token_address(&shihon_test.program_id, BC_TOKEN_NAME);
+    let bump = find_bump_seed(
+        &shihon_test.program_id,
+        &get_bc_token_address_seeds(BC_TOKEN_NAME),
+    );
+
+    let bc_token_v2 = create_test_bc_token_v2(bump);
+    shihon_test
+        .with_legacy_account(&bc_token_address, &bc_token_v2)
+        .await;
+
+    // Act
+    shihon_test
+        .migrate_account(&bc_token_address, None)
+        .await
+        .unwrap();
+
+    // Assert
+    let bc_token = shihon_test.get_bc_token_account(&bc_token_address).await;
+
+    assert_eq!(bc_token.account_type, ShihonAccountType::BcTokenV3);
+    assert_eq!(bc_token.bump, bump);
+    assert_eq!(bc_token.authority, bc_token_v2.authority);
+    assert_eq!(bc_token.delegate, None);
+    assert_eq!(bc_token.pending_authority, None);
+    assert_eq!(
+        bc_token.config.account_type,
+        ShihonAccountType::BcTokenMetadataV3
+    );
+    assert_eq!(bc_token.config.bump, bc_token_v2.config.bump);
+    assert_eq!(bc_token.config.lineage, None);
+
+    // The account is reallocated to the V3 layout
+    let bc_token_account = shihon_test.get_account(&bc_token_address).await.unwrap();
+
+    assert_eq!(
+        bc_token_account.data.len(),
+        BcToken::from(bc_token_v2).get_max_size().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_bc_token_v2_with_invalid_address_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bump = find_bump_seed(
+        &shihon_test.program_id,
+        &get_bc_token_address_seeds(BC_TOKEN_NAME),
+    );
+
+    let bc_token_address = Pubkey::new_unique();
+    shihon_test
+        .with_legacy_account(&bc_token_address, &create_test_bc_token_v2(bump))
+        .await;
+
+    // Act
+    let err = shihon_test
+        .migrate_account(&bc_token_address, None)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidBcTokenAddress);
+}
+
+#[tokio::test]
+async fn test_migrate_outside_buyer_record_v2() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let tanistry = Pubkey::new_unique();
+    let outside_buyer_token_mint = Pubkey::new_unique();
+    let outside_buyer_token_owner = Pubkey::new_unique();
+
+    let outside_buyer_record_address = get_outside_buyer_record_address(
+        &shihon_test.program_id,
+        &tanistry,
+        &outside_buyer_token_mint,
+        &outside_buyer_token_owner,
+    );
+
+    let outside_buyer_record_v2 = OutsideBuyerRecordV2 {
+        account_type: ShihonAccountType::OutsideBuyerRecordV2,
+        bump: find_bump_seed(
+            &shihon_test.program_id,
+            &get_outside_buyer_record_address_seeds(
+                &tanistry,
+                &outside_buyer_token_mint,
+                &outside_buyer_token_owner,
+            ),
+        ),
+        tanistry,
+        outside_buyer_token_mint,
+        outside_buyer_token_owner,
+        outside_buyer_token_deposit_amount: 50,
+        reserved: [0; 7],
+    };
+    shihon_test
+        .with_legacy_account(&outside_buyer_record_address, &outside_buyer_record_v2)
+        .await;
+
+    // Act
+    shihon_test
+        .migrate_account(&outside_buyer_record_address, None)
+        .await
+        .unwrap();
+
+    // Assert
+    let outside_buyer_record = shihon_test
+        .get_borsh_account::<OutsideBuyerRecord>(&outside_buyer_record_address)
+        .await;
+
+    assert_eq!(
+        outside_buyer_record.account_type,
+        ShihonAccountType::OutsideBuyerRecordV3
+    );
+    assert_eq!(outside_buyer_record.bump, outside_buyer_record_v2.bump);
+    assert_eq!(outside_buyer_record.outside_buyer_token_deposit_amount, 50);
+    assert_eq!(outside_buyer_record.outside_buyer_fee_amount, 0);
+}
+
+#[tokio::test]
+async fn test_migrate_v3_account_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    // Act
+    let err = shihon_test
+        .migrate_account(&bc_token_cookie.address, None)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn test_migrate_first_tanistry_v1() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let tanistry_v1 = create_test_tanistry_v1(None);
+    let tanistry_address = get_tanistry_address(
+        &shihon_test.program_id,
+        &tanistry_v1.kicker_coin_owner_record,
+    );
+    shihon_test
+        .with_legacy_account(&tanistry_address, &tanistry_v1)
+        .await;
+
+    // Act
+    shihon_test
+        .migrate_account(&tanistry_address, None)
+        .await
+        .unwrap();
+
+    // Assert
+    let tanistry = shihon_test.get_tanistry_account(&tanistry_address).await;
+
+    assert_eq!(tanistry.account_type, ShihonAccountType::TanistryV2);
+    assert_eq!(
+        tanistry.bump,
+        find_bump_seed(
+            &shihon_test.program_id,
+            &get_tanistry_address_seeds(&tanistry_v1.kicker_coin_owner_record)
+        )
+    );
+    assert_eq!(
+        tanistry.ring,
+        get_ring_address(&shihon_test.program_id, &tanistry_address)
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_tanistry_v1_with_unmigrated_previous_tanistry_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let previous_tanistry_v1 = create_test_tanistry_v1(None);
+    let previous_tanistry_address = get_tanistry_address(
+        &shihon_test.program_id,
+        &previous_tanistry_v1.kicker_coin_owner_record,
+    );
+    shihon_test
+        .with_legacy_account(&previous_tanistry_address, &previous_tanistry_v1)
+        .await;
+
+    let tanistry_v1 = create_test_tanistry_v1(Some(previous_tanistry_address));
+    let tanistry_address = get_tanistry_address(
+        &shihon_test.program_id,
+        &tanistry_v1.kicker_coin_owner_record,
+    );
+    shihon_test
+        .with_legacy_account(&tanistry_address, &tanistry_v1)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .migrate_account(&tanistry_address, Some(&previous_tanistry_address))
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::TanistryMustBeMigrated);
+}
+
+#[tokio::test]
+async fn test_migrate_cc_vote_record_v1() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let candidate_limit_record_v1 = CandidateLimitRecordV1 {
+        account_type: ShihonAccountType::CandidateLimitRecordV1,
+        belonging_tanistry: Pubkey::new_unique(),
+        number_of_candidate_count: 1,
+        candidate_token_mint: Pubkey::new_unique(),
+        candidate_token_owner: Pubkey::new_unique(),
+        candidate_token_deposit_amount: 100,
+    };
+    let candidate_limit_record_address = get_candidate_limit_record_address(
+        &shihon_test.program_id,
+        &candidate_limit_record_v1.belonging_tanistry,
+        &candidate_limit_record_v1.candidate_token_mint,
+        &candidate_limit_record_v1.candidate_token_owner,
+    );
+    shihon_test
+        .with_legacy_account(&candidate_limit_record_address, &candidate_limit_record_v1)
+        .await;
+
+    let cc_vote_record_v1 = CCVoteRecordV1 {
+        account_type: ShihonAccountType::CCVoteRecordV1,
+        governing_token_owner: candidate_limit_record_v1.candidate_token_owner,
+        counting_time: 100,
+        is_relinquished: false,
+        voter_weight: 10,
+        vote: VoteSource::Push,
+    };
+    let cc_vote_record_address =
+        get_cc_vote_record_address(&shihon_test.program_id, &candidate_limit_record_address);
+    shihon_test
+        .with_legacy_account(&cc_vote_record_address, &cc_vote_record_v1)
+        .await;
+
+    // Act
+    shihon_test
+        .migrate_account(
+            &cc_vote_record_address,
+            Some(&candidate_limit_record_address),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let cc_vote_record = shihon_test
+        .get_cc_vote_record_account(&cc_vote_record_address)
+        .await;
+
+    assert_eq!(
+        cc_vote_record.account_type,
+        ShihonAccountType::CCVoteRecordV2
+    );
+    assert_eq!(
+        cc_vote_record.bump,
+        find_bump_seed(
+            &shihon_test.program_id,
+            &get_cc_vote_record_address_seeds(&candidate_limit_record_address)
+        )
+    );
+    assert_eq!(
+        cc_vote_record.voter,
+        candidate_limit_record_v1.candidate_token_owner
+    );
+    assert_eq!(cc_vote_record.vote, VoteSource::Push);
+}
+
+#[tokio::test]
+async fn test_migrate_rate_other_record_v1_with_invalid_mix_content_record_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let rate_other_record_v1 = RateOtherRecordV1 {
+        account_type: ShihonAccountType::RateOtherRecordV1,
+        mix_content_record: Pubkey::new_unique(),
+        cc_vote_record: Pubkey::new_unique(),
+        outside_buyer_record: Pubkey::new_unique(),
+        rate_amount: 1,
+        rating_time: None,
+    };
+    let rate_other_record_address = Pubkey::new_unique();
+    shihon_test
+        .with_legacy_account(&rate_other_record_address, &rate_other_record_v1)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .migrate_account(&rate_other_record_address, Some(&Pubkey::new_unique()))
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidLinkedAccountForMigration);
+}
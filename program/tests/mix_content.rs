@@ -0,0 +1,210 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{BcTokenCookie, CandidateCookie, KickerCoinCookie, ShihonProgramTest},
+    shihon::{
+        error::ShihonError,
+        state::bc_token_metadata::{ContentRange, ContentType},
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, transaction::TransactionError, transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+/// Sets up a Tanistry on the init content with the rater and the buddy of the given content type
+async fn setup_mix(
+    shihon_test: &ShihonProgramTest,
+    init_bc_token_cookie: &BcTokenCookie,
+    buddy_content_type: ContentType,
+) -> (KickerCoinCookie, Vec<CandidateCookie>) {
+    shihon_test.with_shihon_config().await;
+
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(init_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let rater_cookie = shihon_test
+        .with_candidate(&tanistry_cookie, 50)
+        .await
+        .unwrap();
+    let buddy_cookie = shihon_test
+        .with_candidate_content(&tanistry_cookie, 50, buddy_content_type, 1_000, 60)
+        .await
+        .unwrap();
+
+    (kicker_coin_cookie, vec![rater_cookie, buddy_cookie])
+}
+
+#[tokio::test]
+async fn test_mix_partially_content_with_range() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (kicker_coin_cookie, candidate_cookies) =
+        setup_mix(&shihon_test, &init_bc_token_cookie, ContentType::Partially).await;
+
+    let range_b = ContentRange {
+        start: 100,
+        end: 200,
+    };
+
+    // Act
+    let mix_content_record = shihon_test
+        .mix_content_with_args(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie.address,
+            &candidate_cookies[1].bc_token,
+            10,
+            20,
+            None,
+            Some(range_b.clone()),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let mix_content_record_data = shihon_test
+        .get_mix_content_record_account(&mix_content_record)
+        .await;
+
+    assert_eq!(
+        mix_content_record_data.init_bc_token,
+        init_bc_token_cookie.address
+    );
+    assert_eq!(
+        mix_content_record_data.buddy_bc_token,
+        candidate_cookies[1].bc_token
+    );
+    assert_eq!(mix_content_record_data.time_shift_a, 10);
+    assert_eq!(mix_content_record_data.time_shift_b, 20);
+    assert_eq!(mix_content_record_data.range_a, None);
+    assert_eq!(mix_content_record_data.range_b, Some(range_b));
+}
+
+#[tokio::test]
+async fn test_mix_incompatible_content_types_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (kicker_coin_cookie, candidate_cookies) =
+        setup_mix(&shihon_test, &init_bc_token_cookie, ContentType::Other).await;
+
+    // Act
+    let err = shihon_test
+        .mix_content(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie.address,
+            &candidate_cookies[1].bc_token,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::IncompatibleContentTypesForMixing);
+}
+
+#[tokio::test]
+async fn test_mix_with_time_shift_beyond_duration_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (kicker_coin_cookie, candidate_cookies) =
+        setup_mix(&shihon_test, &init_bc_token_cookie, ContentType::Ordinary).await;
+
+    // Act
+    let err = shihon_test
+        .mix_content_with_args(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie.address,
+            &candidate_cookies[1].bc_token,
+            0,
+            61,
+            None,
+            None,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::TimeShiftExceedsContentDuration);
+}
+
+#[tokio::test]
+async fn test_mix_with_foreign_init_bc_token_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (kicker_coin_cookie, candidate_cookies) =
+        setup_mix(&shihon_test, &init_bc_token_cookie, ContentType::Ordinary).await;
+
+    // A compatible bcToken which isn't the kicker's bcToken of the Tanistry
+    let foreign_bc_token_cookie = shihon_test.with_bc_token("Pepsi").await;
+
+    // Act
+    let err = shihon_test
+        .mix_content(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &foreign_bc_token_cookie.address,
+            &candidate_cookies[1].bc_token,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidInitBcTokenForMixContent);
+}
+
+#[tokio::test]
+async fn test_mix_with_other_candidate_bc_token_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (kicker_coin_cookie, candidate_cookies) =
+        setup_mix(&shihon_test, &init_bc_token_cookie, ContentType::Ordinary).await;
+
+    // Act
+    // The rater's own bcToken is passed as the buddy content
+    let err = shihon_test
+        .mix_content(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie.address,
+            &candidate_cookies[0].bc_token,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidBuddyBcTokenForMixContent);
+}
@@ -0,0 +1,215 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{ShihonProgramTest, ENROLLMENT_TIME},
+    shihon::{
+        error::ShihonError,
+        state::enums::{PinholeSide, ShihonAccountType},
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, signature::Signer, transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_drop_pinholes() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+
+    // Act
+    let kicker_pinhole_cookie = shihon_test
+        .with_pinhole(&kicker_coin_cookie, PinholeSide::Kicker)
+        .await
+        .unwrap();
+    let coordinator_pinhole_cookie = shihon_test
+        .with_pinhole(&kicker_coin_cookie, PinholeSide::Coordinator)
+        .await
+        .unwrap();
+
+    // Assert
+    let kicker_pinhole = shihon_test
+        .get_pinhole_account(&kicker_pinhole_cookie.address)
+        .await;
+
+    assert_eq!(kicker_pinhole.account_type, ShihonAccountType::Pinhole);
+    assert_eq!(kicker_pinhole.side, PinholeSide::Kicker);
+    assert_eq!(
+        kicker_pinhole.kicker_coin_owner_record,
+        kicker_coin_cookie.address
+    );
+    assert_eq!(
+        kicker_pinhole.pinhole_authority,
+        kicker_pinhole_cookie.authority.pubkey()
+    );
+
+    let kicker_coin_owner_record = shihon_test
+        .get_kicker_coin_owner_record_account(&kicker_coin_cookie.address)
+        .await;
+
+    assert_eq!(
+        kicker_coin_owner_record.get_opposite_address(&kicker_pinhole_cookie.address),
+        Ok(coordinator_pinhole_cookie.address)
+    );
+    assert_eq!(
+        kicker_coin_owner_record.get_opposite_address(&coordinator_pinhole_cookie.address),
+        Ok(kicker_pinhole_cookie.address)
+    );
+}
+
+#[tokio::test]
+async fn test_drop_pinhole_twice_for_same_party_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+
+    shihon_test
+        .with_pinhole(&kicker_coin_cookie, PinholeSide::Coordinator)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_pinhole(&kicker_coin_cookie, PinholeSide::Coordinator)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::PinholeAlreadyDropped);
+}
+
+#[tokio::test]
+async fn test_claim_refund_through_pinhole() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+    let kicker_coin_amount = 100;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let kicker_pinhole_cookie = shihon_test
+        .with_pinhole(&kicker_coin_cookie, PinholeSide::Kicker)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    shihon_test
+        .cancel_tanistry(
+            &tanistry_cookie,
+            &candidate_cookies,
+            &[bc_token_cookie.address],
+        )
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .claim_refund_with_pinhole(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_pinhole_cookie.authority,
+            &kicker_coin_cookie.kicker_token_account,
+            Some(&kicker_pinhole_cookie.address),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&kicker_coin_cookie.kicker_token_account)
+            .await,
+        kicker_coin_amount
+    );
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert!(tanistry.is_kicker_refunded);
+}
+
+#[tokio::test]
+async fn test_claim_refund_through_pinhole_to_other_wallet_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let kicker_pinhole_cookie = shihon_test
+        .with_pinhole(&kicker_coin_cookie, PinholeSide::Kicker)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 1, 50)
+        .await
+        .unwrap();
+
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    shihon_test
+        .cancel_tanistry(
+            &tanistry_cookie,
+            &candidate_cookies,
+            &[bc_token_cookie.address],
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .claim_refund_with_pinhole(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_pinhole_cookie.authority,
+            &candidate_cookies[0].token_account,
+            Some(&kicker_pinhole_cookie.address),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidRefundClaimant);
+}
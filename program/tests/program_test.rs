@@ -0,0 +1,1834 @@
+#![allow(dead_code)]
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    shihon::{
+        decode::{decode_account, ShihonAccount},
+        instruction::{
+            accept_bc_token_authority, approve_kicker_coin, buy_exceeded_rate_token,
+            cancel_tanistry, candidate, change_cc_vote, claim_refund, coordinator_withdraw,
+            create_bc_token, create_rate_option, create_ring, create_treasury,
+            create_triple_multisig, crowning, drop_pinhole, kicking_to_coordinator,
+            migrate_account, mix_content, promote_mixed_content, rate_content,
+            redeem_coordinator_refund_receipt, relinquish_cc_vote, remove_rate_option,
+            set_bc_token_authority, set_candidate_delegate, set_pause_flags, settle_tanistry,
+            sign_off_rate_option, update_shihon_config, vote_for_cc, withdraw_treasury,
+        },
+        pda::{
+            find_bump_seed, get_bc_token_address, get_candidate_limit_record_address,
+            get_cc_vote_record_address, get_coordinator_refund_receipt_token_address,
+            get_kicker_coin_owner_record_address, get_mix_content_record_address,
+            get_mix_content_record_address_seeds, get_outside_buyer_record_address,
+            get_pinhole_address, get_rate_option_address, get_rate_other_record_address,
+            get_ring_address, get_shihon_config_address, get_shihon_config_address_seeds,
+            get_tanistry_address, get_tanistry_token_holding_address, get_treasury_address,
+            get_triple_multisig_address,
+        },
+        processor::process_instruction,
+        state::{
+            bc_token::BcToken,
+            bc_token_metadata::{BcTokenMetadata, ContentRange, ContentType},
+            candidate_limit_record::CandidateLimitRecord,
+            cc_vote_record::CCVoteRecord,
+            enums::{BcTokenState, PinholeSide, ShihonAccountType, VoteSource},
+            kicker_coin_owner_record::KickerCoinOwnerRecord,
+            mix_content_record::{MixContentRecord, OptionMixResult},
+            pinhole::Pinhole,
+            rate_option::RateOption,
+            ring::Ring,
+            shihon_config::{ShihonConfig, ShihonConfigArgs},
+            tanistry::Tanistry,
+            triple_multisig::TripleMultisig,
+        },
+    },
+    solana_program::{
+        borsh::try_from_slice_unchecked, clock::Clock, program_pack::Pack, pubkey::Pubkey,
+    },
+    solana_program_test::{processor, tokio::sync::Mutex, ProgramTest, ProgramTestContext},
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        instruction::Instruction,
+        signature::{Keypair, Signer},
+        system_instruction,
+        transaction::Transaction,
+        transport::TransportError,
+    },
+    std::sync::Arc,
+};
+
+/// Minimum number of candidates of the injected ShihonConfig
+pub const MIN_CANDIDATES: u32 = 2;
+
+/// Enrollment time in seconds of the injected ShihonConfig
+pub const ENROLLMENT_TIME: u32 = 3_600;
+
+/// bcToken created by the test
+pub struct BcTokenCookie {
+    pub address: Pubkey,
+    pub name: String,
+    pub authority: Keypair,
+    pub mint: Pubkey,
+}
+
+/// KickerCoin kicked to the coordinator
+pub struct KickerCoinCookie {
+    pub address: Pubkey,
+    pub kicker: Keypair,
+    pub kicker_token_account: Pubkey,
+    pub coordinator: Keypair,
+    pub coordinator_token_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Pinhole dropped by the kicker or the coordinator
+pub struct PinholeCookie {
+    pub address: Pubkey,
+    pub authority: Keypair,
+}
+
+/// Tanistry formed by the coordinator's approval
+pub struct TanistryCookie {
+    pub address: Pubkey,
+    pub kicker_coin_owner_record: Pubkey,
+    pub token_holding: Pubkey,
+    pub ring: Pubkey,
+}
+
+/// Candidate enrolled into the Tanistry
+pub struct CandidateCookie {
+    pub address: Pubkey,
+    pub owner: Keypair,
+    pub bc_token: Pubkey,
+    pub token_account: Pubkey,
+    pub deposit_amount: u64,
+}
+
+/// Outside buyer bought into the running Tanistry
+pub struct OutsideBuyerCookie {
+    pub address: Pubkey,
+    pub buyer: Keypair,
+    pub token_account: Pubkey,
+    pub amount: u64,
+}
+
+/// ShihonConfig injected by the test
+pub struct ShihonConfigCookie {
+    pub address: Pubkey,
+    pub admin_authority: Keypair,
+    pub guardian: Keypair,
+}
+
+/// Test harness running the Shihon program on BanksClient
+/// All the Tanistry funds are in the single token mint created by the harness
+pub struct ShihonProgramTest {
+    pub context: Arc<Mutex<ProgramTestContext>>,
+    pub program_id: Pubkey,
+    pub token_mint: Pubkey,
+    pub mint_authority: Keypair,
+}
+
+impl ShihonProgramTest {
+    pub async fn new() -> Self {
+        let program_id = Pubkey::new_unique();
+
+        let program_test = ProgramTest::new("shihon", program_id, processor!(process_instruction));
+        let context = program_test.start_with_context().await;
+        let context = Arc::new(Mutex::new(context));
+
+        let mut shihon_test = Self {
+            context,
+            program_id,
+            token_mint: Pubkey::default(),
+            mint_authority: Keypair::new(),
+        };
+
+        shihon_test.token_mint = shihon_test.create_mint().await;
+
+        shihon_test
+    }
+
+    pub async fn process_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransportError> {
+        let mut context = self.context.lock().await;
+
+        let payer = keypair_clone(&context.payer);
+        let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            recent_blockhash,
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn get_payer(&self) -> Keypair {
+        keypair_clone(&self.context.lock().await.payer)
+    }
+
+    pub async fn create_mint(&self) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.get_payer().await;
+
+        let rent = self
+            .context
+            .lock()
+            .await
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap();
+
+        self.process_transaction(
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::id(),
+                    &mint.pubkey(),
+                    &self.mint_authority.pubkey(),
+                    None,
+                    0,
+                )
+                .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+
+        mint.pubkey()
+    }
+
+    /// Creates token account of the harness mint for the owner and mints the amount into it
+    pub async fn create_token_account_with_balance(&self, owner: &Pubkey, amount: u64) -> Pubkey {
+        let token_account = Keypair::new();
+        let payer = self.get_payer().await;
+
+        let rent = self
+            .context
+            .lock()
+            .await
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap();
+
+        let mut instructions = vec![
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &token_account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &token_account.pubkey(),
+                &self.token_mint,
+                owner,
+            )
+            .unwrap(),
+        ];
+
+        let mut signers = vec![&token_account];
+
+        if amount > 0 {
+            signers.push(&self.mint_authority);
+            instructions.push(
+                spl_token::instruction::mint_to(
+                    &spl_token::id(),
+                    &self.token_mint,
+                    &token_account.pubkey(),
+                    &self.mint_authority.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            );
+        }
+
+        self.process_transaction(&instructions, &signers)
+            .await
+            .unwrap();
+
+        token_account.pubkey()
+    }
+
+    pub async fn get_token_account_balance(&self, address: &Pubkey) -> u64 {
+        let account = self
+            .context
+            .lock()
+            .await
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap();
+
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    pub async fn get_account(&self, address: &Pubkey) -> Option<Account> {
+        self.context
+            .lock()
+            .await
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_borsh_account<T: BorshDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self
+            .context
+            .lock()
+            .await
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap();
+
+        try_from_slice_unchecked::<T>(&account.data).unwrap()
+    }
+
+    pub async fn get_decoded_account(&self, address: &Pubkey) -> ShihonAccount {
+        let account = self
+            .context
+            .lock()
+            .await
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap();
+
+        decode_account(&account.data).unwrap()
+    }
+
+    pub async fn get_bc_token_account(&self, address: &Pubkey) -> BcToken {
+        self.get_borsh_account::<BcToken>(address).await
+    }
+
+    pub async fn get_kicker_coin_owner_record_account(
+        &self,
+        address: &Pubkey,
+    ) -> KickerCoinOwnerRecord {
+        self.get_borsh_account::<KickerCoinOwnerRecord>(address)
+            .await
+    }
+
+    pub async fn get_tanistry_account(&self, address: &Pubkey) -> Tanistry {
+        self.get_borsh_account::<Tanistry>(address).await
+    }
+
+    pub async fn get_cc_vote_record_account(&self, address: &Pubkey) -> CCVoteRecord {
+        self.get_borsh_account::<CCVoteRecord>(address).await
+    }
+
+    pub async fn get_ring_account(&self, address: &Pubkey) -> Ring {
+        self.get_borsh_account::<Ring>(address).await
+    }
+
+    pub async fn get_pinhole_account(&self, address: &Pubkey) -> Pinhole {
+        self.get_borsh_account::<Pinhole>(address).await
+    }
+
+    pub async fn get_rate_option_account(&self, address: &Pubkey) -> RateOption {
+        self.get_borsh_account::<RateOption>(address).await
+    }
+
+    pub async fn get_triple_multisig_account(&self, address: &Pubkey) -> TripleMultisig {
+        self.get_borsh_account::<TripleMultisig>(address).await
+    }
+
+    pub async fn get_candidate_limit_record_account(
+        &self,
+        address: &Pubkey,
+    ) -> CandidateLimitRecord {
+        self.get_borsh_account::<CandidateLimitRecord>(address)
+            .await
+    }
+
+    pub async fn get_mix_content_record_account(&self, address: &Pubkey) -> MixContentRecord {
+        self.get_borsh_account::<MixContentRecord>(address).await
+    }
+
+    pub async fn get_shihon_config_account(&self) -> ShihonConfig {
+        self.get_borsh_account::<ShihonConfig>(&get_shihon_config_address(&self.program_id))
+            .await
+    }
+
+    /// Injects the ShihonConfig account
+    /// ProgramTest doesn't deploy the program with the upgradeable loader
+    /// so the config can't be created by the program upgrade authority
+    pub async fn with_shihon_config(&self) -> ShihonConfigCookie {
+        self.with_shihon_config_fees(0, 0, 0).await
+    }
+
+    /// Injects the ShihonConfig account with the given protocol fees
+    pub async fn with_shihon_config_fees(
+        &self,
+        kicker_coin_fee_bps: u16,
+        candidate_fee_bps: u16,
+        outside_buyer_fee_bps: u16,
+    ) -> ShihonConfigCookie {
+        let admin_authority = Keypair::new();
+        let guardian = Keypair::new();
+
+        let shihon_config = ShihonConfig {
+            account_type: ShihonAccountType::ShihonConfig,
+            bump: find_bump_seed(&self.program_id, &get_shihon_config_address_seeds()),
+            admin_authority: admin_authority.pubkey(),
+            config: ShihonConfigArgs {
+                kicker_coin_mint: self.token_mint,
+                rft_mint: self.token_mint,
+                oracle_authority: Pubkey::new_unique(),
+                max_candidates: 8,
+                min_candidates: MIN_CANDIDATES,
+                enrollment_time: ENROLLMENT_TIME,
+                kicker_coin_fee_bps,
+                candidate_fee_bps,
+                outside_buyer_fee_bps,
+                guardian: Some(guardian.pubkey()),
+            },
+            pause_flags: 0,
+            reserved: [0; 64],
+        };
+
+        let address = get_shihon_config_address(&self.program_id);
+        let data = shihon_config.try_to_vec().unwrap();
+
+        let mut context = self.context.lock().await;
+        let rent = context.banks_client.get_rent().await.unwrap();
+
+        let mut account = AccountSharedData::new(
+            rent.minimum_balance(data.len()),
+            data.len(),
+            &self.program_id,
+        );
+        account.set_data(data);
+        context.set_account(&address, &account);
+
+        ShihonConfigCookie {
+            address,
+            admin_authority,
+            guardian,
+        }
+    }
+
+    /// Updates the minimum number of candidates signed by the admin authority
+    pub async fn update_min_candidates(
+        &self,
+        shihon_config_cookie: &ShihonConfigCookie,
+        min_candidates: u32,
+    ) -> Result<(), TransportError> {
+        let mut config_args = self.get_shihon_config_account().await.config;
+        config_args.min_candidates = min_candidates;
+
+        let update_shihon_config_ix = update_shihon_config(
+            &self.program_id,
+            &shihon_config_cookie.admin_authority.pubkey(),
+            None,
+            config_args,
+        );
+
+        self.process_transaction(
+            &[update_shihon_config_ix],
+            &[&shihon_config_cookie.admin_authority],
+        )
+        .await
+    }
+
+    /// Sets the pause flags signed by the given admin authority or guardian
+    pub async fn set_pause_flags(
+        &self,
+        pause_authority: &Keypair,
+        pause_flags: u32,
+    ) -> Result<(), TransportError> {
+        let set_pause_flags_ix =
+            set_pause_flags(&self.program_id, &pause_authority.pubkey(), pause_flags);
+
+        self.process_transaction(&[set_pause_flags_ix], &[pause_authority])
+            .await
+    }
+
+    /// Creates the treasury of the harness mint
+    pub async fn with_treasury(&self) -> Pubkey {
+        let payer = self.get_payer().await;
+
+        let create_treasury_ix = create_treasury(
+            &self.program_id,
+            &self.token_mint,
+            &payer.pubkey(),
+            &spl_token::id(),
+        );
+
+        self.process_transaction(&[create_treasury_ix], &[])
+            .await
+            .unwrap();
+
+        get_treasury_address(&self.program_id, &self.token_mint)
+    }
+
+    /// Mints the amount of the harness mint into the token account
+    pub async fn mint_tokens(&self, token_account: &Pubkey, amount: u64) {
+        let mint_to_ix = spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &self.token_mint,
+            token_account,
+            &self.mint_authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap();
+
+        self.process_transaction(&[mint_to_ix], &[&self.mint_authority])
+            .await
+            .unwrap();
+    }
+
+    pub async fn withdraw_treasury(
+        &self,
+        admin_authority: &Keypair,
+        destination: &Pubkey,
+        amount: u64,
+    ) -> Result<(), TransportError> {
+        self.withdraw_treasury_using_instruction(admin_authority, destination, amount, |_| {}, None)
+            .await
+    }
+
+    /// Withdraws from the treasury with the instruction and the signers overridden by the test
+    pub async fn withdraw_treasury_using_instruction<F: Fn(&mut Instruction)>(
+        &self,
+        admin_authority: &Keypair,
+        destination: &Pubkey,
+        amount: u64,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>,
+    ) -> Result<(), TransportError> {
+        let mut withdraw_treasury_ix = withdraw_treasury(
+            &self.program_id,
+            &admin_authority.pubkey(),
+            &self.token_mint,
+            destination,
+            &spl_token::id(),
+            amount,
+        );
+
+        instruction_override(&mut withdraw_treasury_ix);
+
+        self.process_transaction(
+            &[withdraw_treasury_ix],
+            signers_override.unwrap_or(&[admin_authority]),
+        )
+        .await
+    }
+
+    pub async fn with_bc_token(&self, name: &str) -> BcTokenCookie {
+        self.with_bc_token_content(name, ContentType::Ordinary, 1_000, 60)
+            .await
+    }
+
+    /// Creates the bcToken of the given content type, length and duration
+    pub async fn with_bc_token_content(
+        &self,
+        name: &str,
+        content_type: ContentType,
+        content_length: u64,
+        content_duration: u64,
+    ) -> BcTokenCookie {
+        self.with_bc_token_for_authority(
+            name,
+            Keypair::new(),
+            content_type,
+            content_length,
+            content_duration,
+        )
+        .await
+    }
+
+    /// Creates the bcToken of the given content type, length and duration owned by the authority
+    pub async fn with_bc_token_for_authority(
+        &self,
+        name: &str,
+        authority: Keypair,
+        content_type: ContentType,
+        content_length: u64,
+        content_duration: u64,
+    ) -> BcTokenCookie {
+        let payer = self.get_payer().await;
+
+        let config = BcTokenMetadata {
+            account_type: ShihonAccountType::BcTokenMetadataV3,
+            bump: 0,
+            content_type,
+            updated_at: 0,
+            version: "1".to_string(),
+            reserved: [0; 64],
+            is_writable: true,
+            is_signer: false,
+            content_length,
+            content_duration,
+            lineage: None,
+        };
+
+        let create_bc_token_ix = create_bc_token(
+            &self.program_id,
+            &authority.pubkey(),
+            &self.token_mint,
+            &payer.pubkey(),
+            name.to_string(),
+            0,
+            config,
+        );
+
+        self.process_transaction(&[create_bc_token_ix], &[&authority])
+            .await
+            .unwrap();
+
+        BcTokenCookie {
+            address: get_bc_token_address(&self.program_id, name),
+            name: name.to_string(),
+            authority,
+            mint: self.token_mint,
+        }
+    }
+
+    /// Kicks the KickerCoin of the given amount from a new kicker to a new coordinator
+    pub async fn with_kicked_coin(
+        &self,
+        bc_token_cookie: &BcTokenCookie,
+        amount: u64,
+    ) -> Result<KickerCoinCookie, TransportError> {
+        self.with_kicked_coin_using_instruction(bc_token_cookie, amount, |_| {}, None)
+            .await
+    }
+
+    /// Kicks the KickerCoin with the instruction and the signers overridden by the test
+    pub async fn with_kicked_coin_using_instruction<F: Fn(&mut Instruction)>(
+        &self,
+        bc_token_cookie: &BcTokenCookie,
+        amount: u64,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>,
+    ) -> Result<KickerCoinCookie, TransportError> {
+        let kicker = keypair_clone(&bc_token_cookie.authority);
+        let coordinator = Keypair::new();
+        let payer = self.get_payer().await;
+
+        let kicker_token_account = self
+            .create_token_account_with_balance(&kicker.pubkey(), amount)
+            .await;
+        let coordinator_token_account = self
+            .create_token_account_with_balance(&coordinator.pubkey(), 0)
+            .await;
+
+        let kicker_coin_owner_record_address = get_kicker_coin_owner_record_address(
+            &self.program_id,
+            &kicker.pubkey(),
+            &coordinator.pubkey(),
+        );
+
+        let mut kick_ix = kicking_to_coordinator(
+            &self.program_id,
+            &bc_token_cookie.address,
+            &kicker.pubkey(),
+            &kicker_token_account,
+            &self.token_mint,
+            &payer.pubkey(),
+            &coordinator.pubkey(),
+            amount,
+        );
+
+        instruction_override(&mut kick_ix);
+
+        self.process_transaction(&[kick_ix], signers_override.unwrap_or(&[&kicker]))
+            .await?;
+
+        Ok(KickerCoinCookie {
+            address: kicker_coin_owner_record_address,
+            kicker,
+            kicker_token_account,
+            coordinator,
+            coordinator_token_account,
+            amount,
+        })
+    }
+
+    /// Kicks the KickerCoin of the given amount from the candidate with its bcToken to a new coordinator
+    pub async fn with_candidate_kicked_coin(
+        &self,
+        candidate_cookie: &CandidateCookie,
+        amount: u64,
+    ) -> Result<KickerCoinCookie, TransportError> {
+        let bc_token_cookie = BcTokenCookie {
+            address: candidate_cookie.bc_token,
+            name: format!(
+                "candidate-{}",
+                &candidate_cookie.owner.pubkey().to_string()[..8]
+            ),
+            authority: keypair_clone(&candidate_cookie.owner),
+            mint: self.token_mint,
+        };
+
+        self.with_kicked_coin(&bc_token_cookie, amount).await
+    }
+
+    /// The coordinator approves the KickerCoin and the Tanistry is formed
+    pub async fn with_approved_kicker_coin(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+    ) -> Result<TanistryCookie, TransportError> {
+        self.with_approved_kicker_coin_using_instruction(kicker_coin_cookie, |_| {}, None)
+            .await
+    }
+
+    /// Approves the KickerCoin with the approval instruction and the signers overridden by the test
+    pub async fn with_approved_kicker_coin_using_instruction<F: Fn(&mut Instruction)>(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>,
+    ) -> Result<TanistryCookie, TransportError> {
+        let payer = self.get_payer().await;
+
+        let tanistry_address = get_tanistry_address(&self.program_id, &kicker_coin_cookie.address);
+        let tanistry_token_holding_address = get_tanistry_token_holding_address(
+            &self.program_id,
+            &tanistry_address,
+            &self.token_mint,
+        );
+
+        let mut approve_ix = approve_kicker_coin(
+            &self.program_id,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.coordinator.pubkey(),
+            &self.token_mint,
+            &payer.pubkey(),
+            None,
+            "coordinator input".to_string(),
+        );
+
+        instruction_override(&mut approve_ix);
+
+        // The first Tanistry of the chain starts a new Ring
+        let create_ring_ix = create_ring(&self.program_id, &tanistry_address, &payer.pubkey());
+
+        self.process_transaction(
+            &[approve_ix, create_ring_ix],
+            signers_override.unwrap_or(&[&kicker_coin_cookie.coordinator]),
+        )
+        .await?;
+
+        Ok(TanistryCookie {
+            address: tanistry_address,
+            kicker_coin_owner_record: kicker_coin_cookie.address,
+            token_holding: tanistry_token_holding_address,
+            ring: get_ring_address(&self.program_id, &tanistry_address),
+        })
+    }
+
+    /// The coordinator approves the KickerCoin the Crown of the previous Tanistry kicked on
+    /// The Tanistry is linked to the previous one and joins its Ring
+    pub async fn with_next_approved_kicker_coin(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        previous_tanistry_cookie: &TanistryCookie,
+        crown_cookie: &CandidateCookie,
+    ) -> Result<TanistryCookie, TransportError> {
+        let payer = self.get_payer().await;
+
+        let tanistry_address = get_tanistry_address(&self.program_id, &kicker_coin_cookie.address);
+        let tanistry_token_holding_address = get_tanistry_token_holding_address(
+            &self.program_id,
+            &tanistry_address,
+            &self.token_mint,
+        );
+
+        let approve_ix = approve_kicker_coin(
+            &self.program_id,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.coordinator.pubkey(),
+            &self.token_mint,
+            &payer.pubkey(),
+            Some((&previous_tanistry_cookie.address, &crown_cookie.address)),
+            "coordinator input".to_string(),
+        );
+
+        self.process_transaction(&[approve_ix], &[&kicker_coin_cookie.coordinator])
+            .await?;
+
+        Ok(TanistryCookie {
+            address: tanistry_address,
+            kicker_coin_owner_record: kicker_coin_cookie.address,
+            token_holding: tanistry_token_holding_address,
+            ring: previous_tanistry_cookie.ring,
+        })
+    }
+
+    /// Enrolls a new candidate depositing the given amount into the Tanistry
+    pub async fn with_candidate(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        deposit_amount: u64,
+    ) -> Result<CandidateCookie, TransportError> {
+        self.with_candidate_content(
+            tanistry_cookie,
+            deposit_amount,
+            ContentType::Ordinary,
+            1_000,
+            60,
+        )
+        .await
+    }
+
+    /// Enrolls a new candidate with the bcToken of the given content type, length and duration
+    pub async fn with_candidate_content(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        deposit_amount: u64,
+        content_type: ContentType,
+        content_length: u64,
+        content_duration: u64,
+    ) -> Result<CandidateCookie, TransportError> {
+        self.with_candidate_for_owner(
+            tanistry_cookie,
+            Keypair::new(),
+            deposit_amount,
+            content_type,
+            content_length,
+            content_duration,
+        )
+        .await
+    }
+
+    /// Enrolls the owner as a candidate with the bcToken of the given content type, length and duration
+    pub async fn with_candidate_for_owner(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        owner: Keypair,
+        deposit_amount: u64,
+        content_type: ContentType,
+        content_length: u64,
+        content_duration: u64,
+    ) -> Result<CandidateCookie, TransportError> {
+        // Every candidate joins the Tanistry with its own bcToken
+        let bc_token_name = format!("candidate-{}", &owner.pubkey().to_string()[..8]);
+        let bc_token_cookie = self
+            .with_bc_token_for_authority(
+                &bc_token_name,
+                owner,
+                content_type,
+                content_length,
+                content_duration,
+            )
+            .await;
+
+        self.with_candidate_using_instruction(
+            tanistry_cookie,
+            bc_token_cookie,
+            deposit_amount,
+            |_| {},
+            None,
+        )
+        .await
+    }
+
+    /// Enrolls the bcToken authority as a candidate with the instruction and the signers overridden by the test
+    pub async fn with_candidate_using_instruction<F: Fn(&mut Instruction)>(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        bc_token_cookie: BcTokenCookie,
+        deposit_amount: u64,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>,
+    ) -> Result<CandidateCookie, TransportError> {
+        let payer = self.get_payer().await;
+        let owner = bc_token_cookie.authority;
+
+        let token_account = self
+            .create_token_account_with_balance(&owner.pubkey(), deposit_amount)
+            .await;
+
+        let mut candidate_ix = candidate(
+            &self.program_id,
+            &tanistry_cookie.address,
+            &owner.pubkey(),
+            &token_account,
+            &bc_token_cookie.address,
+            &self.token_mint,
+            &payer.pubkey(),
+            deposit_amount,
+        );
+
+        instruction_override(&mut candidate_ix);
+
+        self.process_transaction(&[candidate_ix], signers_override.unwrap_or(&[&owner]))
+            .await?;
+
+        Ok(CandidateCookie {
+            address: get_candidate_limit_record_address(
+                &self.program_id,
+                &tanistry_cookie.address,
+                &self.token_mint,
+                &owner.pubkey(),
+            ),
+            owner,
+            bc_token: bc_token_cookie.address,
+            token_account,
+            deposit_amount,
+        })
+    }
+
+    /// A new outside buyer buys into the Tanistry with the given amount
+    pub async fn with_outside_buyer(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        amount: u64,
+    ) -> Result<OutsideBuyerCookie, TransportError> {
+        let buyer = Keypair::new();
+        let payer = self.get_payer().await;
+
+        let token_account = self
+            .create_token_account_with_balance(&buyer.pubkey(), amount)
+            .await;
+
+        let buy_ix = buy_exceeded_rate_token(
+            &self.program_id,
+            &tanistry_cookie.address,
+            &buyer.pubkey(),
+            &token_account,
+            &self.token_mint,
+            &payer.pubkey(),
+            amount,
+        );
+
+        self.process_transaction(&[buy_ix], &[&buyer]).await?;
+
+        Ok(OutsideBuyerCookie {
+            address: get_outside_buyer_record_address(
+                &self.program_id,
+                &tanistry_cookie.address,
+                &self.token_mint,
+                &buyer.pubkey(),
+            ),
+            buyer,
+            token_account,
+            amount,
+        })
+    }
+
+    /// Enrolls the given number of candidates with the same deposit
+    pub async fn with_candidates(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        count: usize,
+        deposit_amount: u64,
+    ) -> Result<Vec<CandidateCookie>, TransportError> {
+        let mut candidate_cookies = vec![];
+
+        for _ in 0..count {
+            candidate_cookies.push(self.with_candidate(tanistry_cookie, deposit_amount).await?);
+        }
+
+        Ok(candidate_cookies)
+    }
+
+    /// Mixes the init content with the buddy candidate's content for the rater
+    pub async fn mix_content(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        rater_cookie: &CandidateCookie,
+        buddy_cookie: &CandidateCookie,
+        init_bc_token: &Pubkey,
+        buddy_bc_token: &Pubkey,
+    ) -> Result<Pubkey, TransportError> {
+        self.mix_content_with_args(
+            kicker_coin_cookie,
+            rater_cookie,
+            buddy_cookie,
+            init_bc_token,
+            buddy_bc_token,
+            0,
+            0,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Mixes the contents with the given time shifts and ranges
+    #[allow(clippy::too_many_arguments)]
+    pub async fn mix_content_with_args(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        rater_cookie: &CandidateCookie,
+        buddy_cookie: &CandidateCookie,
+        init_bc_token: &Pubkey,
+        buddy_bc_token: &Pubkey,
+        time_shift_a: u64,
+        time_shift_b: u64,
+        range_a: Option<ContentRange>,
+        range_b: Option<ContentRange>,
+    ) -> Result<Pubkey, TransportError> {
+        let payer = self.get_payer().await;
+
+        self.with_triple_multisig(kicker_coin_cookie, rater_cookie, buddy_cookie)
+            .await?;
+
+        let mix_content_ix = mix_content(
+            &self.program_id,
+            &rater_cookie.address,
+            &rater_cookie.owner.pubkey(),
+            &buddy_cookie.address,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.coordinator.pubkey(),
+            init_bc_token,
+            buddy_bc_token,
+            &payer.pubkey(),
+            &[
+                rater_cookie.owner.pubkey(),
+                kicker_coin_cookie.kicker.pubkey(),
+            ],
+            time_shift_a,
+            time_shift_b,
+            range_a,
+            range_b,
+        );
+
+        self.process_transaction(
+            &[mix_content_ix],
+            &[&rater_cookie.owner, &kicker_coin_cookie.kicker],
+        )
+        .await?;
+
+        Ok(get_mix_content_record_address(
+            &self.program_id,
+            &rater_cookie.address,
+            &buddy_cookie.address,
+        ))
+    }
+
+    /// Creates the TripleMultisig of the rater, the buddy and the first kicker
+    pub async fn with_triple_multisig(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        rater_cookie: &CandidateCookie,
+        buddy_cookie: &CandidateCookie,
+    ) -> Result<Pubkey, TransportError> {
+        let payer = self.get_payer().await;
+
+        let create_triple_multisig_ix = create_triple_multisig(
+            &self.program_id,
+            &rater_cookie.address,
+            &buddy_cookie.address,
+            &get_tanistry_address(&self.program_id, &kicker_coin_cookie.address),
+            &kicker_coin_cookie.address,
+            &payer.pubkey(),
+        );
+
+        self.process_transaction(&[create_triple_multisig_ix], &[])
+            .await?;
+
+        Ok(get_triple_multisig_address(
+            &self.program_id,
+            &rater_cookie.address,
+            &buddy_cookie.address,
+        ))
+    }
+
+    /// Injects the MixContentRecord of the rater resolved with the given mix result
+    pub async fn with_mix_content_record(
+        &self,
+        rater_cookie: &CandidateCookie,
+        buddy_cookie: &CandidateCookie,
+        init_bc_token_cookie: &BcTokenCookie,
+        buddy_bc_token_cookie: &BcTokenCookie,
+        option_mix_result: OptionMixResult,
+    ) -> Pubkey {
+        let mix_content_record = MixContentRecord {
+            account_type: ShihonAccountType::MixContentRecordV2,
+            bump: find_bump_seed(
+                &self.program_id,
+                &get_mix_content_record_address_seeds(&rater_cookie.address, &buddy_cookie.address),
+            ),
+            encrypted_link: "encrypted link".to_string(),
+            rater_candidate: rater_cookie.address,
+            buddy_candidate: buddy_cookie.address,
+            option_mix_result,
+            finger_print_of_mixed_content: "finger print".to_string(),
+            init_bc_token: init_bc_token_cookie.address,
+            buddy_bc_token: buddy_bc_token_cookie.address,
+            time_shift_a: 10,
+            time_shift_b: 20,
+            range_a: None,
+            range_b: None,
+            promoted_bc_token: None,
+        };
+
+        let address = get_mix_content_record_address(
+            &self.program_id,
+            &rater_cookie.address,
+            &buddy_cookie.address,
+        );
+        let data = mix_content_record.try_to_vec().unwrap();
+
+        let mut context = self.context.lock().await;
+        let rent = context.banks_client.get_rent().await.unwrap();
+
+        let mut account = AccountSharedData::new(
+            rent.minimum_balance(data.len()),
+            data.len(),
+            &self.program_id,
+        );
+        account.set_data(data);
+        context.set_account(&address, &account);
+
+        address
+    }
+
+    /// The rater promotes the Succeeded mixed content into a new bcToken
+    pub async fn promote_mixed_content(
+        &self,
+        mix_content_record: &Pubkey,
+        rater_cookie: &CandidateCookie,
+        init_bc_token_cookie: &BcTokenCookie,
+        buddy_bc_token_cookie: &BcTokenCookie,
+        name: &str,
+    ) -> Result<Pubkey, TransportError> {
+        let payer = self.get_payer().await;
+
+        let promote_ix = promote_mixed_content(
+            &self.program_id,
+            mix_content_record,
+            &rater_cookie.address,
+            &rater_cookie.owner.pubkey(),
+            &init_bc_token_cookie.address,
+            &buddy_bc_token_cookie.address,
+            &self.token_mint,
+            &payer.pubkey(),
+            name.to_string(),
+            "1".to_string(),
+        );
+
+        self.process_transaction(&[promote_ix], &[&rater_cookie.owner])
+            .await?;
+
+        Ok(get_bc_token_address(&self.program_id, name))
+    }
+
+    /// The rater puts the rating on the buddy candidate's mixed content approved by the first kicker
+    pub async fn rate_content(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        rater_cookie: &CandidateCookie,
+        buddy_cookie: &CandidateCookie,
+        mix_content_record: &Pubkey,
+        rating: u64,
+    ) -> Result<(), TransportError> {
+        self.rate_content_with_signers(
+            rater_cookie,
+            buddy_cookie,
+            mix_content_record,
+            rating,
+            &[&rater_cookie.owner, &kicker_coin_cookie.kicker],
+        )
+        .await
+    }
+
+    /// Rates with the given TripleMultisig signers
+    pub async fn rate_content_with_signers(
+        &self,
+        rater_cookie: &CandidateCookie,
+        buddy_cookie: &CandidateCookie,
+        mix_content_record: &Pubkey,
+        rating: u64,
+        triple_multisig_signers: &[&Keypair],
+    ) -> Result<(), TransportError> {
+        self.rate_content_with_authority(
+            rater_cookie,
+            &rater_cookie.owner,
+            buddy_cookie,
+            mix_content_record,
+            rating,
+            triple_multisig_signers,
+        )
+        .await
+    }
+
+    /// Rates signing as the rater with the given candidate token owner or delegate
+    pub async fn rate_content_with_authority(
+        &self,
+        rater_cookie: &CandidateCookie,
+        rater_token_authority: &Keypair,
+        buddy_cookie: &CandidateCookie,
+        mix_content_record: &Pubkey,
+        rating: u64,
+        triple_multisig_signers: &[&Keypair],
+    ) -> Result<(), TransportError> {
+        let payer = self.get_payer().await;
+
+        let signers: Vec<Pubkey> = triple_multisig_signers
+            .iter()
+            .map(|signer| signer.pubkey())
+            .collect();
+
+        let rate_content_ix = rate_content(
+            &self.program_id,
+            &rater_cookie.address,
+            &rater_token_authority.pubkey(),
+            &buddy_cookie.address,
+            mix_content_record,
+            &payer.pubkey(),
+            &signers,
+            rating,
+        );
+
+        let mut transaction_signers = vec![rater_token_authority];
+        transaction_signers.extend(triple_multisig_signers.iter());
+
+        self.process_transaction(&[rate_content_ix], &transaction_signers)
+            .await
+    }
+
+    /// The coordinator elects the Crown from the Tanistry candidates
+    pub async fn crown(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        tanistry_cookie: &TanistryCookie,
+        candidate_cookies: &[CandidateCookie],
+        crown_cookie: &CandidateCookie,
+    ) -> Result<(), TransportError> {
+        self.crown_with_coordinator(
+            kicker_coin_cookie,
+            &kicker_coin_cookie.coordinator,
+            tanistry_cookie,
+            candidate_cookies,
+            &crown_cookie.address,
+        )
+        .await
+    }
+
+    /// Elects the given Crown signed by the given coordinator
+    pub async fn crown_with_coordinator(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        coordinator: &Keypair,
+        tanistry_cookie: &TanistryCookie,
+        candidate_cookies: &[CandidateCookie],
+        crown: &Pubkey,
+    ) -> Result<(), TransportError> {
+        self.crown_using_instruction(
+            kicker_coin_cookie,
+            coordinator,
+            tanistry_cookie,
+            candidate_cookies,
+            crown,
+            |_| {},
+            None,
+        )
+        .await
+    }
+
+    /// Elects the given Crown with the instruction and the signers overridden by the test
+    #[allow(clippy::too_many_arguments)]
+    pub async fn crown_using_instruction<F: Fn(&mut Instruction)>(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        coordinator: &Keypair,
+        tanistry_cookie: &TanistryCookie,
+        candidate_cookies: &[CandidateCookie],
+        crown: &Pubkey,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>,
+    ) -> Result<(), TransportError> {
+        let candidate_limit_records: Vec<Pubkey> = candidate_cookies
+            .iter()
+            .map(|candidate_cookie| candidate_cookie.address)
+            .collect();
+
+        let mut crowning_ix = crowning(
+            &self.program_id,
+            &coordinator.pubkey(),
+            &kicker_coin_cookie.address,
+            &tanistry_cookie.ring,
+            &candidate_limit_records,
+            crown,
+        );
+
+        instruction_override(&mut crowning_ix);
+
+        self.process_transaction(&[crowning_ix], signers_override.unwrap_or(&[coordinator]))
+            .await
+    }
+
+    pub async fn settle_tanistry(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        candidate_cookies: &[CandidateCookie],
+    ) -> Result<(), TransportError> {
+        let candidate_limit_records: Vec<Pubkey> = candidate_cookies
+            .iter()
+            .map(|candidate_cookie| candidate_cookie.address)
+            .collect();
+
+        let settle_ix = settle_tanistry(
+            &self.program_id,
+            &tanistry_cookie.address,
+            &tanistry_cookie.kicker_coin_owner_record,
+            &self.token_mint,
+            &candidate_limit_records,
+        );
+
+        self.process_transaction(&[settle_ix], &[]).await
+    }
+
+    /// Returns the Tanistry the candidate belongs to and the Ring of its chain
+    pub async fn get_candidate_tanistry_and_ring(
+        &self,
+        candidate_cookie: &CandidateCookie,
+    ) -> (Pubkey, Pubkey) {
+        let tanistry = self
+            .get_candidate_limit_record_account(&candidate_cookie.address)
+            .await
+            .belonging_tanistry;
+        let ring = self.get_tanistry_account(&tanistry).await.ring;
+
+        (tanistry, ring)
+    }
+
+    /// Casts the CC vote of the candidate signed by the given voter
+    /// Returns the CCVoteRecord address
+    pub async fn vote_for_cc(
+        &self,
+        candidate_cookie: &CandidateCookie,
+        voter: &Keypair,
+        target_ring: &Pubkey,
+        vote: VoteSource,
+    ) -> Result<Pubkey, TransportError> {
+        let (tanistry, ring) = self.get_candidate_tanistry_and_ring(candidate_cookie).await;
+
+        let vote_for_cc_ix = vote_for_cc(
+            &self.program_id,
+            &candidate_cookie.address,
+            &tanistry,
+            &ring,
+            &voter.pubkey(),
+            &self.get_payer().await.pubkey(),
+            target_ring,
+            vote,
+        );
+
+        self.process_transaction(&[vote_for_cc_ix], &[voter])
+            .await?;
+
+        Ok(get_cc_vote_record_address(
+            &self.program_id,
+            &candidate_cookie.address,
+        ))
+    }
+
+    pub async fn relinquish_cc_vote(
+        &self,
+        candidate_cookie: &CandidateCookie,
+        voter: &Keypair,
+        target_ring: &Pubkey,
+    ) -> Result<(), TransportError> {
+        let relinquish_cc_vote_ix = relinquish_cc_vote(
+            &self.program_id,
+            target_ring,
+            &candidate_cookie.address,
+            &voter.pubkey(),
+        );
+
+        self.process_transaction(&[relinquish_cc_vote_ix], &[voter])
+            .await
+    }
+
+    pub async fn change_cc_vote(
+        &self,
+        candidate_cookie: &CandidateCookie,
+        voter: &Keypair,
+        target_ring: &Pubkey,
+        new_target_ring: &Pubkey,
+        vote: VoteSource,
+    ) -> Result<(), TransportError> {
+        let (tanistry, ring) = self.get_candidate_tanistry_and_ring(candidate_cookie).await;
+
+        let change_cc_vote_ix = change_cc_vote(
+            &self.program_id,
+            target_ring,
+            &candidate_cookie.address,
+            &tanistry,
+            &ring,
+            &voter.pubkey(),
+            new_target_ring,
+            vote,
+        );
+
+        self.process_transaction(&[change_cc_vote_ix], &[voter])
+            .await
+    }
+
+    /// Moves the bank forward by the given number of slots
+    pub async fn advance_slots(&self, slots: u64) {
+        let mut context = self.context.lock().await;
+
+        let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        context.warp_to_slot(clock.slot + slots).unwrap();
+    }
+
+    /// Moves the Clock sysvar forward by the given number of seconds
+    pub async fn advance_clock(&self, seconds: u32) {
+        let mut context = self.context.lock().await;
+
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds as i64;
+
+        context.set_sysvar(&clock);
+    }
+
+    pub async fn cancel_tanistry(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        candidate_cookies: &[CandidateCookie],
+        bc_tokens: &[Pubkey],
+    ) -> Result<(), TransportError> {
+        let candidate_limit_records: Vec<Pubkey> = candidate_cookies
+            .iter()
+            .map(|candidate_cookie| candidate_cookie.address)
+            .collect();
+
+        let cancel_ix = cancel_tanistry(
+            &self.program_id,
+            &tanistry_cookie.address,
+            &tanistry_cookie.kicker_coin_owner_record,
+            &self.token_mint,
+            &candidate_limit_records,
+            bc_tokens,
+        );
+
+        self.process_transaction(&[cancel_ix], &[]).await
+    }
+
+    pub async fn claim_refund(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        refund_record: &Pubkey,
+        claimant: &Keypair,
+        claimant_token_account: &Pubkey,
+    ) -> Result<(), TransportError> {
+        self.claim_refund_with_pinhole(
+            tanistry_cookie,
+            refund_record,
+            claimant,
+            claimant_token_account,
+            None,
+        )
+        .await
+    }
+
+    /// Claims the refund signed by the pinhole authority when the pinhole is given
+    pub async fn claim_refund_with_pinhole(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        refund_record: &Pubkey,
+        claimant: &Keypair,
+        claimant_token_account: &Pubkey,
+        pinhole: Option<&Pubkey>,
+    ) -> Result<(), TransportError> {
+        let claim_refund_ix = claim_refund(
+            &self.program_id,
+            &tanistry_cookie.address,
+            &self.token_mint,
+            refund_record,
+            &claimant.pubkey(),
+            claimant_token_account,
+            &spl_token::id(),
+            pinhole,
+        );
+
+        self.process_transaction(&[claim_refund_ix], &[claimant])
+            .await
+    }
+
+    /// The coordinator withdraws from the Tanistry and receives the refund receipt
+    /// Returns the refund receipt token account of the coordinator
+    pub async fn coordinator_withdraw(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        tanistry_cookie: &TanistryCookie,
+    ) -> Result<Pubkey, TransportError> {
+        let payer = self.get_payer().await;
+
+        self.coordinator_withdraw_with_payer(kicker_coin_cookie, tanistry_cookie, &payer)
+            .await
+    }
+
+    /// Withdraws the coordinator paying the receipt accounts from the given payer
+    pub async fn coordinator_withdraw_with_payer(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        tanistry_cookie: &TanistryCookie,
+        payer: &Keypair,
+    ) -> Result<Pubkey, TransportError> {
+        self.coordinator_withdraw_using_instruction(
+            kicker_coin_cookie,
+            tanistry_cookie,
+            payer,
+            |_| {},
+            None,
+        )
+        .await
+    }
+
+    /// Withdraws the coordinator with the instruction and the signers overridden by the test
+    pub async fn coordinator_withdraw_using_instruction<F: Fn(&mut Instruction)>(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        tanistry_cookie: &TanistryCookie,
+        payer: &Keypair,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>,
+    ) -> Result<Pubkey, TransportError> {
+        let mut coordinator_withdraw_ix = coordinator_withdraw(
+            &self.program_id,
+            &tanistry_cookie.address,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.coordinator.pubkey(),
+            &payer.pubkey(),
+        );
+
+        instruction_override(&mut coordinator_withdraw_ix);
+
+        self.process_transaction(
+            &[coordinator_withdraw_ix],
+            signers_override.unwrap_or(&[&kicker_coin_cookie.coordinator, payer]),
+        )
+        .await?;
+
+        Ok(get_coordinator_refund_receipt_token_address(
+            &self.program_id,
+            &tanistry_cookie.address,
+            &kicker_coin_cookie.coordinator.pubkey(),
+        ))
+    }
+
+    pub async fn redeem_coordinator_refund_receipt(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        refund_receipt_token_account: &Pubkey,
+        holder: &Keypair,
+        holder_token_account: &Pubkey,
+    ) -> Result<(), TransportError> {
+        self.redeem_coordinator_refund_receipt_using_instruction(
+            tanistry_cookie,
+            refund_receipt_token_account,
+            holder,
+            holder_token_account,
+            |_| {},
+            None,
+        )
+        .await
+    }
+
+    /// Redeems the coordinator refund receipt with the instruction and the signers overridden by the test
+    pub async fn redeem_coordinator_refund_receipt_using_instruction<F: Fn(&mut Instruction)>(
+        &self,
+        tanistry_cookie: &TanistryCookie,
+        refund_receipt_token_account: &Pubkey,
+        holder: &Keypair,
+        holder_token_account: &Pubkey,
+        instruction_override: F,
+        signers_override: Option<&[&Keypair]>,
+    ) -> Result<(), TransportError> {
+        let mut redeem_ix = redeem_coordinator_refund_receipt(
+            &self.program_id,
+            &tanistry_cookie.address,
+            &self.token_mint,
+            refund_receipt_token_account,
+            &holder.pubkey(),
+            holder_token_account,
+            &spl_token::id(),
+        );
+
+        instruction_override(&mut redeem_ix);
+
+        self.process_transaction(&[redeem_ix], signers_override.unwrap_or(&[holder]))
+            .await
+    }
+
+    /// Drops a pinhole with a new one-time authority for the given party of the KickerCoin
+    pub async fn with_pinhole(
+        &self,
+        kicker_coin_cookie: &KickerCoinCookie,
+        side: PinholeSide,
+    ) -> Result<PinholeCookie, TransportError> {
+        let payer = self.get_payer().await;
+        let authority = Keypair::new();
+
+        let party = match side {
+            PinholeSide::Kicker => &kicker_coin_cookie.kicker,
+            PinholeSide::Coordinator => &kicker_coin_cookie.coordinator,
+        };
+
+        let drop_pinhole_ix = drop_pinhole(
+            &self.program_id,
+            &kicker_coin_cookie.address,
+            &party.pubkey(),
+            &authority.pubkey(),
+            &payer.pubkey(),
+            side,
+        );
+
+        self.process_transaction(&[drop_pinhole_ix], &[party, &authority])
+            .await?;
+
+        Ok(PinholeCookie {
+            address: get_pinhole_address(
+                &self.program_id,
+                &kicker_coin_cookie.address,
+                &authority.pubkey(),
+            ),
+            authority,
+        })
+    }
+
+    /// The Roydamna issues the RateOption on the MixContentRecord
+    pub async fn with_rate_option(
+        &self,
+        issuer_cookie: &CandidateCookie,
+        mix_content_record: &Pubkey,
+    ) -> Result<Pubkey, TransportError> {
+        let payer = self.get_payer().await;
+
+        let create_rate_option_ix = create_rate_option(
+            &self.program_id,
+            &issuer_cookie.address,
+            &issuer_cookie.owner.pubkey(),
+            mix_content_record,
+            &payer.pubkey(),
+        );
+
+        self.process_transaction(&[create_rate_option_ix], &[&issuer_cookie.owner])
+            .await?;
+
+        Ok(get_rate_option_address(
+            &self.program_id,
+            mix_content_record,
+            &issuer_cookie.address,
+        ))
+    }
+
+    /// The Roydamna signs off the RateOption with the RateOtherRecord of the MixContentRecord
+    pub async fn sign_off_rate_option(
+        &self,
+        issuer_cookie: &CandidateCookie,
+        mix_content_record: &Pubkey,
+    ) -> Result<(), TransportError> {
+        let mix_content_record_data = self
+            .get_mix_content_record_account(mix_content_record)
+            .await;
+
+        let sign_off_rate_option_ix = sign_off_rate_option(
+            &self.program_id,
+            &issuer_cookie.address,
+            &issuer_cookie.owner.pubkey(),
+            mix_content_record,
+            &get_rate_other_record_address(
+                &self.program_id,
+                &mix_content_record_data.rater_candidate,
+                &mix_content_record_data.buddy_candidate,
+            ),
+        );
+
+        self.process_transaction(&[sign_off_rate_option_ix], &[&issuer_cookie.owner])
+            .await
+    }
+
+    /// The Roydamna removes the RateOption, the payer receives the lamports
+    pub async fn remove_rate_option(
+        &self,
+        issuer_cookie: &CandidateCookie,
+        mix_content_record: &Pubkey,
+    ) -> Result<(), TransportError> {
+        let payer = self.get_payer().await;
+
+        let remove_rate_option_ix = remove_rate_option(
+            &self.program_id,
+            &issuer_cookie.address,
+            &issuer_cookie.owner.pubkey(),
+            mix_content_record,
+            &payer.pubkey(),
+        );
+
+        self.process_transaction(&[remove_rate_option_ix], &[&issuer_cookie.owner])
+            .await
+    }
+
+    /// Sets the delegate of the candidate signing with the given key
+    pub async fn set_candidate_delegate(
+        &self,
+        candidate_cookie: &CandidateCookie,
+        candidate_token_owner: &Keypair,
+        new_candidate_delegate: Option<Pubkey>,
+    ) -> Result<(), TransportError> {
+        let set_candidate_delegate_ix = set_candidate_delegate(
+            &self.program_id,
+            &candidate_cookie.address,
+            &candidate_token_owner.pubkey(),
+            new_candidate_delegate,
+        );
+
+        self.process_transaction(&[set_candidate_delegate_ix], &[candidate_token_owner])
+            .await
+    }
+
+    /// Injects the account in the legacy layout the previous program version created it with
+    pub async fn with_legacy_account<T: BorshSerialize>(&self, address: &Pubkey, legacy_data: &T) {
+        let data = legacy_data.try_to_vec().unwrap();
+
+        let mut context = self.context.lock().await;
+        let rent = context.banks_client.get_rent().await.unwrap();
+
+        let mut account = AccountSharedData::new(
+            rent.minimum_balance(data.len()),
+            data.len(),
+            &self.program_id,
+        );
+        account.set_data(data);
+        context.set_account(address, &account);
+    }
+
+    /// Migrates the legacy account to the current layout
+    pub async fn migrate_account(
+        &self,
+        address: &Pubkey,
+        linked_account: Option<&Pubkey>,
+    ) -> Result<(), TransportError> {
+        let payer = self.get_payer().await;
+
+        let migrate_account_ix =
+            migrate_account(&self.program_id, address, &payer.pubkey(), linked_account);
+
+        self.process_transaction(&[migrate_account_ix], &[]).await
+    }
+
+    /// Overwrites the state of the bcToken, the way the Tanistry lifecycle moves it
+    pub async fn set_bc_token_state(&self, bc_token_cookie: &BcTokenCookie, state: BcTokenState) {
+        let account = self.get_account(&bc_token_cookie.address).await.unwrap();
+
+        let mut bc_token = try_from_slice_unchecked::<BcToken>(&account.data).unwrap();
+        bc_token.bc_token_state = state;
+
+        let mut data = account.data.clone();
+        let bc_token_data = bc_token.try_to_vec().unwrap();
+        data[..bc_token_data.len()].copy_from_slice(&bc_token_data);
+
+        let mut account_data =
+            AccountSharedData::new(account.lamports, data.len(), &self.program_id);
+        account_data.set_data(data);
+
+        self.context
+            .lock()
+            .await
+            .set_account(&bc_token_cookie.address, &account_data);
+    }
+
+    /// Overwrites the balance of the token account, no instruction moves the vault funds out of the lifecycle
+    pub async fn set_token_account_balance(&self, address: &Pubkey, amount: u64) {
+        let account = self.get_account(address).await.unwrap();
+
+        let mut token_account = spl_token::state::Account::unpack(&account.data).unwrap();
+        token_account.amount = amount;
+
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(token_account, &mut data).unwrap();
+
+        let mut account_data =
+            AccountSharedData::new(account.lamports, data.len(), &spl_token::id());
+        account_data.set_data(data);
+
+        self.context
+            .lock()
+            .await
+            .set_account(address, &account_data);
+    }
+
+    /// Sets or proposes the new authority of the bcToken signed by the given authority
+    pub async fn set_bc_token_authority(
+        &self,
+        bc_token_cookie: &BcTokenCookie,
+        bc_token_authority: &Keypair,
+        new_authority: Option<Pubkey>,
+        require_acceptance: bool,
+    ) -> Result<(), TransportError> {
+        let set_bc_token_authority_ix = set_bc_token_authority(
+            &self.program_id,
+            &bc_token_cookie.address,
+            &bc_token_authority.pubkey(),
+            new_authority,
+            require_acceptance,
+            false,
+        );
+
+        self.process_transaction(&[set_bc_token_authority_ix], &[bc_token_authority])
+            .await
+    }
+
+    /// Renounces the authority of the bcToken signed by the given authority
+    pub async fn renounce_bc_token_authority(
+        &self,
+        bc_token_cookie: &BcTokenCookie,
+        bc_token_authority: &Keypair,
+    ) -> Result<(), TransportError> {
+        let renounce_bc_token_authority_ix = set_bc_token_authority(
+            &self.program_id,
+            &bc_token_cookie.address,
+            &bc_token_authority.pubkey(),
+            None,
+            false,
+            true,
+        );
+
+        self.process_transaction(&[renounce_bc_token_authority_ix], &[bc_token_authority])
+            .await
+    }
+
+    /// Accepts the authority of the bcToken signed by the given pending authority
+    pub async fn accept_bc_token_authority(
+        &self,
+        bc_token_cookie: &BcTokenCookie,
+        pending_authority: &Keypair,
+    ) -> Result<(), TransportError> {
+        let accept_bc_token_authority_ix = accept_bc_token_authority(
+            &self.program_id,
+            &bc_token_cookie.address,
+            &pending_authority.pubkey(),
+        );
+
+        self.process_transaction(&[accept_bc_token_authority_ix], &[pending_authority])
+            .await
+    }
+}
+
+pub fn keypair_clone(kp: &Keypair) -> Keypair {
+    Keypair::from_bytes(&kp.to_bytes()).expect("failed to copy keypair")
+}
@@ -0,0 +1,199 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::ShihonProgramTest,
+    shihon::{
+        error::ShihonError,
+        state::{enums::BcTokenState, mix_content_record::OptionMixResult},
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, signature::Signer, transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_promote_mixed_content() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let buddy_bc_token_cookie = shihon_test.with_bc_token("Mentos").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&init_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    let mix_content_record = shihon_test
+        .with_mix_content_record(
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie,
+            &buddy_bc_token_cookie,
+            OptionMixResult::Succeeded,
+        )
+        .await;
+
+    // Act
+    let bc_token_address = shihon_test
+        .promote_mixed_content(
+            &mix_content_record,
+            &candidate_cookies[0],
+            &init_bc_token_cookie,
+            &buddy_bc_token_cookie,
+            "Coke and Mentos",
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let bc_token = shihon_test.get_bc_token_account(&bc_token_address).await;
+
+    assert_eq!(
+        bc_token.bc_token_owner_pubkey,
+        candidate_cookies[0].owner.pubkey()
+    );
+    assert_eq!(bc_token.bc_token_state, BcTokenState::HoldingOnPrivate);
+
+    let lineage = bc_token.config.lineage.unwrap();
+
+    assert_eq!(lineage.mix_content_record, mix_content_record);
+    assert_eq!(lineage.parent_bc_token_a, init_bc_token_cookie.address);
+    assert_eq!(lineage.parent_bc_token_b, buddy_bc_token_cookie.address);
+    assert_eq!(lineage.time_shift_a, 10);
+    assert_eq!(lineage.time_shift_b, 20);
+
+    let mix_content_record_data = shihon_test
+        .get_mix_content_record_account(&mix_content_record)
+        .await;
+
+    assert_eq!(
+        mix_content_record_data.promoted_bc_token,
+        Some(bc_token_address)
+    );
+}
+
+#[tokio::test]
+async fn test_promote_mixed_content_twice_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let buddy_bc_token_cookie = shihon_test.with_bc_token("Mentos").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&init_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    let mix_content_record = shihon_test
+        .with_mix_content_record(
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie,
+            &buddy_bc_token_cookie,
+            OptionMixResult::Succeeded,
+        )
+        .await;
+
+    shihon_test
+        .promote_mixed_content(
+            &mix_content_record,
+            &candidate_cookies[0],
+            &init_bc_token_cookie,
+            &buddy_bc_token_cookie,
+            "Coke and Mentos",
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .promote_mixed_content(
+            &mix_content_record,
+            &candidate_cookies[0],
+            &init_bc_token_cookie,
+            &buddy_bc_token_cookie,
+            "Coke and Mentos 2",
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::MixContentAlreadyPromoted);
+}
+
+#[tokio::test]
+async fn test_promote_defeated_mixed_content_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let buddy_bc_token_cookie = shihon_test.with_bc_token("Mentos").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&init_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    let mix_content_record = shihon_test
+        .with_mix_content_record(
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie,
+            &buddy_bc_token_cookie,
+            OptionMixResult::Defeated,
+        )
+        .await;
+
+    // Act
+    let err = shihon_test
+        .promote_mixed_content(
+            &mix_content_record,
+            &candidate_cookies[0],
+            &init_bc_token_cookie,
+            &buddy_bc_token_cookie,
+            "Coke and Mentos",
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::MixContentNotSucceeded);
+}
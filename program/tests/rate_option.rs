@@ -0,0 +1,205 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{BcTokenCookie, CandidateCookie, ShihonProgramTest},
+    shihon::{error::ShihonError, pda::get_rate_other_record_address},
+    solana_program::pubkey::Pubkey,
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, transaction::TransactionError, transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+/// Sets up a Tanistry with three candidates, the first mixes its content with the second
+async fn setup_rate_option(
+    shihon_test: &ShihonProgramTest,
+    init_bc_token_cookie: &BcTokenCookie,
+) -> (Vec<CandidateCookie>, Pubkey) {
+    shihon_test.with_shihon_config().await;
+
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(init_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 3, 50)
+        .await
+        .unwrap();
+
+    let mix_content_record = shihon_test
+        .mix_content(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie.address,
+            &candidate_cookies[1].bc_token,
+        )
+        .await
+        .unwrap();
+
+    shihon_test
+        .rate_content(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &mix_content_record,
+            30,
+        )
+        .await
+        .unwrap();
+
+    (candidate_cookies, mix_content_record)
+}
+
+#[tokio::test]
+async fn test_create_rate_option() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (candidate_cookies, mix_content_record) =
+        setup_rate_option(&shihon_test, &init_bc_token_cookie).await;
+
+    // Act
+    let rate_option = shihon_test
+        .with_rate_option(&candidate_cookies[1], &mix_content_record)
+        .await
+        .unwrap();
+
+    // Assert
+    let rate_option_data = shihon_test.get_rate_option_account(&rate_option).await;
+
+    assert_eq!(
+        rate_option_data.issuer_roydamna,
+        candidate_cookies[1].address
+    );
+    assert_eq!(rate_option_data.mix_content_record, mix_content_record);
+    assert_eq!(
+        rate_option_data.rate_other_record,
+        get_rate_other_record_address(
+            &shihon_test.program_id,
+            &candidate_cookies[0].address,
+            &candidate_cookies[1].address,
+        )
+    );
+    assert_eq!(rate_option_data.number_of_issue, 1);
+    assert_eq!(rate_option_data.init_content, init_bc_token_cookie.address);
+    assert!(!rate_option_data.signed_off);
+
+    let issuer_candidate_limit_record = shihon_test
+        .get_candidate_limit_record_account(&candidate_cookies[1].address)
+        .await;
+
+    assert_eq!(issuer_candidate_limit_record.rate_options_count, 1);
+}
+
+#[tokio::test]
+async fn test_create_rate_option_by_outsider_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (candidate_cookies, mix_content_record) =
+        setup_rate_option(&shihon_test, &init_bc_token_cookie).await;
+
+    // Act
+    let err = shihon_test
+        .with_rate_option(&candidate_cookies[2], &mix_content_record)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidRateOptionIssuer);
+}
+
+#[tokio::test]
+async fn test_sign_off_rate_option() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (candidate_cookies, mix_content_record) =
+        setup_rate_option(&shihon_test, &init_bc_token_cookie).await;
+
+    let rate_option = shihon_test
+        .with_rate_option(&candidate_cookies[0], &mix_content_record)
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .sign_off_rate_option(&candidate_cookies[0], &mix_content_record)
+        .await
+        .unwrap();
+
+    // Assert
+    let rate_option_data = shihon_test.get_rate_option_account(&rate_option).await;
+
+    assert!(rate_option_data.signed_off);
+}
+
+#[tokio::test]
+async fn test_remove_rate_option() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (candidate_cookies, mix_content_record) =
+        setup_rate_option(&shihon_test, &init_bc_token_cookie).await;
+
+    let rate_option = shihon_test
+        .with_rate_option(&candidate_cookies[0], &mix_content_record)
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .remove_rate_option(&candidate_cookies[0], &mix_content_record)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(shihon_test.get_account(&rate_option).await, None);
+}
+
+#[tokio::test]
+async fn test_remove_signed_off_rate_option_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (candidate_cookies, mix_content_record) =
+        setup_rate_option(&shihon_test, &init_bc_token_cookie).await;
+
+    shihon_test
+        .with_rate_option(&candidate_cookies[0], &mix_content_record)
+        .await
+        .unwrap();
+    shihon_test
+        .sign_off_rate_option(&candidate_cookies[0], &mix_content_record)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .remove_rate_option(&candidate_cookies[0], &mix_content_record)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::RateOptionAlreadySignedOff);
+}
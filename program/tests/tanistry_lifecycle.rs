@@ -0,0 +1,1530 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::ShihonProgramTest,
+    shihon::{
+        decode::ShihonAccount,
+        error::ShihonError,
+        state::{
+            enums::TanistryState,
+            outside_buyer_record::OutsideBuyerRecord,
+            shihon_config::{PAUSE_ALL, PAUSE_CANDIDATE, PAUSE_CROWNING, PAUSE_KICKER_COIN},
+            tanistry::{calculate_refund_allocation, COORDINATOR_KICKER_COIN_SHARE_BPS, MAX_BPS},
+        },
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_tanistry_lifecycle() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let kicker_coin_amount = 100;
+    let deposit_amount = 50;
+
+    // Kick
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+
+    let kicker_coin_owner_record = shihon_test
+        .get_kicker_coin_owner_record_account(&kicker_coin_cookie.address)
+        .await;
+
+    assert_eq!(
+        kicker_coin_owner_record.kicker_coin_holder,
+        kicker_coin_cookie.kicker.pubkey()
+    );
+    assert_eq!(
+        kicker_coin_owner_record.latest_coordinator,
+        kicker_coin_cookie.coordinator.pubkey()
+    );
+    assert_eq!(
+        kicker_coin_owner_record.amount_of_kicker_coin,
+        kicker_coin_amount
+    );
+    assert!(!kicker_coin_owner_record.is_kick_off);
+
+    // Approve
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::Active);
+    assert_eq!(
+        tanistry.kicker_coin_owner_record,
+        kicker_coin_cookie.address
+    );
+    assert_eq!(tanistry.tanistry_token_mint, shihon_test.token_mint);
+    assert!(tanistry.candidate_limit_record_list.is_empty());
+    assert_eq!(
+        shihon_test
+            .get_decoded_account(&tanistry_cookie.address)
+            .await,
+        ShihonAccount::Tanistry(tanistry.clone())
+    );
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&kicker_coin_cookie.kicker_token_account)
+            .await,
+        0
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        kicker_coin_amount
+    );
+
+    // Candidate
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 3, deposit_amount)
+        .await
+        .unwrap();
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(
+        tanistry.candidate_limit_record_list,
+        candidate_cookies
+            .iter()
+            .map(|candidate_cookie| candidate_cookie.address)
+            .collect::<Vec<_>>()
+    );
+
+    for (index, candidate_cookie) in candidate_cookies.iter().enumerate() {
+        let candidate_limit_record = shihon_test
+            .get_candidate_limit_record_account(&candidate_cookie.address)
+            .await;
+
+        assert_eq!(
+            candidate_limit_record.belonging_tanistry,
+            tanistry_cookie.address
+        );
+        assert_eq!(
+            candidate_limit_record.candidate_token_owner,
+            candidate_cookie.owner.pubkey()
+        );
+        assert_eq!(
+            candidate_limit_record.candidate_token_deposit_amount,
+            deposit_amount
+        );
+        assert_eq!(
+            candidate_limit_record.number_of_candidate_count,
+            index as u32 + 1
+        );
+        assert_eq!(
+            shihon_test
+                .get_token_account_balance(&candidate_cookie.token_account)
+                .await,
+            0
+        );
+    }
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        kicker_coin_amount + 3 * deposit_amount
+    );
+
+    // Mix and Rate
+    let ratings = [30, 10, 20];
+
+    for (index, rater_cookie) in candidate_cookies.iter().enumerate() {
+        let buddy_index = (index + 1) % candidate_cookies.len();
+        let buddy_cookie = &candidate_cookies[buddy_index];
+
+        let mix_content_record = shihon_test
+            .mix_content(
+                &kicker_coin_cookie,
+                rater_cookie,
+                buddy_cookie,
+                &bc_token_cookie.address,
+                &buddy_cookie.bc_token,
+            )
+            .await
+            .unwrap();
+
+        let mix_content_record_data = shihon_test
+            .get_mix_content_record_account(&mix_content_record)
+            .await;
+
+        assert_eq!(
+            mix_content_record_data.rater_candidate,
+            rater_cookie.address
+        );
+
+        shihon_test
+            .rate_content(
+                &kicker_coin_cookie,
+                rater_cookie,
+                buddy_cookie,
+                &mix_content_record,
+                ratings[(index + 1) % ratings.len()],
+            )
+            .await
+            .unwrap();
+    }
+
+    for (candidate_cookie, rating) in candidate_cookies.iter().zip(ratings) {
+        let candidate_limit_record = shihon_test
+            .get_candidate_limit_record_account(&candidate_cookie.address)
+            .await;
+
+        assert_eq!(candidate_limit_record.received_rating, rating);
+    }
+
+    // Crown
+    let crown_index = 0;
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[crown_index],
+        )
+        .await
+        .unwrap();
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::Crowned);
+    assert_eq!(tanistry.crown, Some(candidate_cookies[crown_index].address));
+
+    let ring = shihon_test.get_ring_account(&tanistry_cookie.ring).await;
+
+    assert_eq!(ring.first_tanistry, tanistry_cookie.address);
+    assert_eq!(ring.last_crowned_tanistry, Some(tanistry_cookie.address));
+    assert_eq!(ring.crowned_tanistry_count, 1);
+    assert_eq!(ring.cumulative_deposit_amount, 3 * deposit_amount);
+    assert_eq!(ring.cumulative_rft_volume, ratings.iter().sum::<u64>());
+
+    // Settle
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    let refund_allocation = calculate_refund_allocation(
+        kicker_coin_amount,
+        0,
+        &ratings
+            .iter()
+            .map(|rating| (deposit_amount, *rating))
+            .collect::<Vec<_>>(),
+        crown_index,
+    )
+    .unwrap();
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::EnableToRefund);
+    assert_eq!(
+        tanistry.settled_amount,
+        kicker_coin_amount + 3 * deposit_amount
+    );
+    assert_eq!(
+        tanistry.kicker_refund_amount,
+        refund_allocation.kicker_amount
+    );
+    assert_eq!(
+        tanistry.coordinator_refund_amount,
+        kicker_coin_amount * COORDINATOR_KICKER_COIN_SHARE_BPS / MAX_BPS
+    );
+
+    for (candidate_cookie, refund_amount) in candidate_cookies
+        .iter()
+        .zip(refund_allocation.candidate_amounts.iter())
+    {
+        let candidate_limit_record = shihon_test
+            .get_candidate_limit_record_account(&candidate_cookie.address)
+            .await;
+
+        assert_eq!(candidate_limit_record.refund_amount, *refund_amount);
+        assert!(!candidate_limit_record.is_refunded);
+    }
+
+    // Act
+
+    // Claim refunds
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.kicker,
+            &kicker_coin_cookie.kicker_token_account,
+        )
+        .await
+        .unwrap();
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+        )
+        .await
+        .unwrap();
+
+    for candidate_cookie in candidate_cookies.iter() {
+        shihon_test
+            .claim_refund(
+                &tanistry_cookie,
+                &candidate_cookie.address,
+                &candidate_cookie.owner,
+                &candidate_cookie.token_account,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Assert
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&kicker_coin_cookie.kicker_token_account)
+            .await,
+        refund_allocation.kicker_amount
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&kicker_coin_cookie.coordinator_token_account)
+            .await,
+        refund_allocation.coordinator_amount
+    );
+
+    for (candidate_cookie, refund_amount) in candidate_cookies
+        .iter()
+        .zip(refund_allocation.candidate_amounts.iter())
+    {
+        assert_eq!(
+            shihon_test
+                .get_token_account_balance(&candidate_cookie.token_account)
+                .await,
+            *refund_amount
+        );
+
+        let candidate_limit_record = shihon_test
+            .get_candidate_limit_record_account(&candidate_cookie.address)
+            .await;
+
+        assert!(candidate_limit_record.is_refunded);
+    }
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        0
+    );
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::AlreadyRefunded);
+    assert_eq!(tanistry.refunded_amount, tanistry.settled_amount);
+}
+
+#[tokio::test]
+async fn test_settle_and_claim_with_outside_buyer() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let kicker_coin_amount = 100;
+    let deposit_amount = 50;
+    let outside_buyer_amount = 40;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, deposit_amount)
+        .await
+        .unwrap();
+
+    let outside_buyer_cookie = shihon_test
+        .with_outside_buyer(&tanistry_cookie, outside_buyer_amount)
+        .await
+        .unwrap();
+
+    let outside_buyer_record: OutsideBuyerRecord = shihon_test
+        .get_borsh_account(&outside_buyer_cookie.address)
+        .await;
+
+    assert_eq!(
+        outside_buyer_record.outside_buyer_token_owner,
+        outside_buyer_cookie.buyer.pubkey()
+    );
+    assert_eq!(
+        outside_buyer_record.outside_buyer_token_deposit_amount,
+        outside_buyer_amount
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&outside_buyer_cookie.token_account)
+            .await,
+        0
+    );
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.outside_buyer_deposit_amount, outside_buyer_amount);
+
+    let crown_index = 1;
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[crown_index],
+        )
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.kicker,
+            &kicker_coin_cookie.kicker_token_account,
+        )
+        .await
+        .unwrap();
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+        )
+        .await
+        .unwrap();
+
+    for candidate_cookie in candidate_cookies.iter() {
+        shihon_test
+            .claim_refund(
+                &tanistry_cookie,
+                &candidate_cookie.address,
+                &candidate_cookie.owner,
+                &candidate_cookie.token_account,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Assert
+    let refund_allocation = calculate_refund_allocation(
+        kicker_coin_amount,
+        outside_buyer_amount,
+        &[(deposit_amount, 0), (deposit_amount, 0)],
+        crown_index,
+    )
+    .unwrap();
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(
+        tanistry.settled_amount,
+        kicker_coin_amount + 2 * deposit_amount + outside_buyer_amount
+    );
+    assert_eq!(tanistry.state, TanistryState::AlreadyRefunded);
+
+    for (candidate_cookie, refund_amount) in candidate_cookies
+        .iter()
+        .zip(refund_allocation.candidate_amounts.iter())
+    {
+        assert_eq!(
+            shihon_test
+                .get_token_account_balance(&candidate_cookie.token_account)
+                .await,
+            *refund_amount
+        );
+    }
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_settle_and_claim_with_dust_sent_to_vault() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let kicker_coin_amount = 100;
+    let deposit_amount = 50;
+    let dust_amount = 1;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, kicker_coin_amount)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, deposit_amount)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    // Anybody can send tokens to the vault
+    shihon_test
+        .mint_tokens(&tanistry_cookie.token_holding, dust_amount)
+        .await;
+
+    // Act
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.kicker,
+            &kicker_coin_cookie.kicker_token_account,
+        )
+        .await
+        .unwrap();
+
+    // More dust in between the claims doesn't block the remaining claims either
+    shihon_test
+        .mint_tokens(&tanistry_cookie.token_holding, dust_amount)
+        .await;
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+        )
+        .await
+        .unwrap();
+
+    for candidate_cookie in candidate_cookies.iter() {
+        shihon_test
+            .claim_refund(
+                &tanistry_cookie,
+                &candidate_cookie.address,
+                &candidate_cookie.owner,
+                &candidate_cookie.token_account,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Assert
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    // The dust isn't allocated to anybody and stays in the vault
+    assert_eq!(
+        tanistry.settled_amount,
+        kicker_coin_amount + deposit_amount * 2
+    );
+    assert_eq!(tanistry.refunded_amount, tanistry.settled_amount);
+    assert_eq!(tanistry.state, TanistryState::AlreadyRefunded);
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        dust_amount * 2
+    );
+}
+
+#[tokio::test]
+async fn test_buy_exceeded_rate_token_after_crowning_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_outside_buyer(&tanistry_cookie, 40)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidTanistryStateCannotBuy);
+}
+
+#[tokio::test]
+async fn test_crowning_with_other_coordinator_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    let other_coordinator = Keypair::new();
+
+    // Act
+    let err = shihon_test
+        .crown_with_coordinator(
+            &kicker_coin_cookie,
+            &other_coordinator,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0].address,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CoordinatorMustSign);
+}
+
+#[tokio::test]
+async fn test_crowning_with_crown_outside_tanistry_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .crown_with_coordinator(
+            &kicker_coin_cookie,
+            &kicker_coin_cookie.coordinator,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &Pubkey::new_unique(),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidCrownForTanistry);
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::Active);
+    assert_eq!(tanistry.crown, None);
+}
+
+#[tokio::test]
+async fn test_crowning_without_coordinator_signature_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .crown_using_instruction(
+            &kicker_coin_cookie,
+            &kicker_coin_cookie.coordinator,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0].address,
+            |i| i.accounts[0].is_signer = false, // coordinator
+            Some(&[]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CoordinatorMustSign);
+}
+
+#[tokio::test]
+async fn test_crowning_with_missing_candidate_limit_record_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .crown_with_coordinator(
+            &kicker_coin_cookie,
+            &kicker_coin_cookie.coordinator,
+            &tanistry_cookie,
+            &candidate_cookies[..1],
+            &candidate_cookies[0].address,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidCandidateLimitRecordsForSettlement);
+}
+
+#[tokio::test]
+async fn test_kick_to_coordinator_without_kicker_signature_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    // Act
+    let err = shihon_test
+        .with_kicked_coin_using_instruction(
+            &bc_token_cookie,
+            100,
+            |i| i.accounts[2].is_signer = false, // kicker
+            Some(&[]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::KickerCoinHolderMustSign);
+}
+
+#[tokio::test]
+async fn test_kick_to_coordinator_with_other_kicker_coin_mint_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let other_mint = shihon_test.create_mint().await;
+
+    // Act
+    let err = shihon_test
+        .with_kicked_coin_using_instruction(
+            &bc_token_cookie,
+            100,
+            |i| i.accounts[6].pubkey = other_mint, // kicker_coin_mint
+            None,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidKickerCoinMint);
+}
+
+#[tokio::test]
+async fn test_approve_kicker_coin_with_other_coordinator_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+
+    let other_coordinator = Keypair::new();
+
+    // Act
+    let err = shihon_test
+        .with_approved_kicker_coin_using_instruction(
+            &kicker_coin_cookie,
+            |i| i.accounts[2].pubkey = other_coordinator.pubkey(), // coordinator
+            Some(&[&other_coordinator]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CoordinatorMustSign);
+}
+
+#[tokio::test]
+async fn test_approve_kicker_coin_without_coordinator_signature_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_approved_kicker_coin_using_instruction(
+            &kicker_coin_cookie,
+            |i| i.accounts[2].is_signer = false, // coordinator
+            Some(&[]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CoordinatorMustSign);
+}
+
+#[tokio::test]
+async fn test_candidate_without_candidate_token_owner_signature_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    let candidate_bc_token_cookie = shihon_test.with_bc_token("candidate").await;
+
+    // Act
+    let err = shihon_test
+        .with_candidate_using_instruction(
+            &tanistry_cookie,
+            candidate_bc_token_cookie,
+            50,
+            |i| i.accounts[2].is_signer = false, // candidate_token_owner
+            Some(&[]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::CandidateTokenOwnerMustSign);
+}
+
+#[tokio::test]
+async fn test_settle_tanistry_before_crowning_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidTanistryStateCannotSettle);
+}
+
+#[tokio::test]
+async fn test_claim_refund_twice_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.kicker,
+            &kicker_coin_cookie.kicker_token_account,
+        )
+        .await
+        .unwrap();
+
+    // Use another token account to send a different transaction
+    let kicker_token_account = shihon_test
+        .create_token_account_with_balance(&kicker_coin_cookie.kicker.pubkey(), 0)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.kicker,
+            &kicker_token_account,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::RefundAlreadyClaimed);
+}
+
+#[tokio::test]
+async fn test_claim_refund_with_other_candidate_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &candidate_cookies[0].address,
+            &candidate_cookies[1].owner,
+            &candidate_cookies[1].token_account,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidRefundClaimant);
+}
+
+#[tokio::test]
+async fn test_candidate_while_paused_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    shihon_test
+        .set_pause_flags(&shihon_config_cookie.guardian, PAUSE_CANDIDATE)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_candidate(&tanistry_cookie, 50)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::ShihonPaused);
+}
+
+#[tokio::test]
+async fn test_kick_to_coordinator_while_paused_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    shihon_test
+        .set_pause_flags(&shihon_config_cookie.guardian, PAUSE_KICKER_COIN)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::ShihonPaused);
+}
+
+#[tokio::test]
+async fn test_approve_kicker_coin_while_paused_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+
+    shihon_test
+        .set_pause_flags(&shihon_config_cookie.guardian, PAUSE_KICKER_COIN)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::ShihonPaused);
+}
+
+#[tokio::test]
+async fn test_approve_kicker_coin_with_other_instructions_paused() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    shihon_test
+        .set_pause_flags(
+            &shihon_config_cookie.guardian,
+            PAUSE_ALL & !PAUSE_KICKER_COIN,
+        )
+        .await
+        .unwrap();
+
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+
+    // Act
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    // Assert
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::Active);
+}
+
+#[tokio::test]
+async fn test_crown_while_paused_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .set_pause_flags(&shihon_config_cookie.guardian, PAUSE_CROWNING)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::ShihonPaused);
+}
+
+#[tokio::test]
+async fn test_claim_refund_while_paused() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    shihon_test
+        .set_pause_flags(&shihon_config_cookie.guardian, PAUSE_ALL)
+        .await
+        .unwrap();
+
+    // Act
+    shihon_test
+        .settle_tanistry(&tanistry_cookie, &candidate_cookies)
+        .await
+        .unwrap();
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.kicker,
+            &kicker_coin_cookie.kicker_token_account,
+        )
+        .await
+        .unwrap();
+
+    shihon_test
+        .claim_refund(
+            &tanistry_cookie,
+            &kicker_coin_cookie.address,
+            &kicker_coin_cookie.coordinator,
+            &kicker_coin_cookie.coordinator_token_account,
+        )
+        .await
+        .unwrap();
+
+    for candidate_cookie in candidate_cookies.iter() {
+        shihon_test
+            .claim_refund(
+                &tanistry_cookie,
+                &candidate_cookie.address,
+                &candidate_cookie.owner,
+                &candidate_cookie.token_account,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Assert
+    assert_eq!(
+        shihon_test.get_shihon_config_account().await.pause_flags,
+        PAUSE_ALL
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        0
+    );
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.state, TanistryState::AlreadyRefunded);
+}
+
+#[tokio::test]
+async fn test_set_pause_flags_with_guardian_unpausing_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    shihon_test
+        .set_pause_flags(&shihon_config_cookie.guardian, PAUSE_ALL)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .set_pause_flags(&shihon_config_cookie.guardian, 0)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::GuardianCannotUnpause);
+
+    shihon_test
+        .set_pause_flags(&shihon_config_cookie.admin_authority, 0)
+        .await
+        .unwrap();
+
+    assert_eq!(shihon_test.get_shihon_config_account().await.pause_flags, 0);
+}
+
+#[tokio::test]
+async fn test_crown_kicks_on_and_next_tanistry_joins_ring() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    let next_kicker_coin_cookie = shihon_test
+        .with_candidate_kicked_coin(&candidate_cookies[0], 100)
+        .await
+        .unwrap();
+
+    // Act
+    let next_tanistry_cookie = shihon_test
+        .with_next_approved_kicker_coin(
+            &next_kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    let next_candidate_cookies = shihon_test
+        .with_candidates(&next_tanistry_cookie, 2, 30)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &next_kicker_coin_cookie,
+            &next_tanistry_cookie,
+            &next_candidate_cookies,
+            &next_candidate_cookies[1],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+    assert_eq!(
+        tanistry.next_tanistry_id,
+        Some(next_tanistry_cookie.address)
+    );
+
+    let next_tanistry = shihon_test
+        .get_tanistry_account(&next_tanistry_cookie.address)
+        .await;
+    assert_eq!(
+        next_tanistry.previous_tanistry_id,
+        Some(tanistry_cookie.address)
+    );
+    assert_eq!(next_tanistry.ring, tanistry_cookie.ring);
+
+    let ring = shihon_test.get_ring_account(&tanistry_cookie.ring).await;
+
+    assert_eq!(ring.first_tanistry, tanistry_cookie.address);
+    assert_eq!(
+        ring.last_crowned_tanistry,
+        Some(next_tanistry_cookie.address)
+    );
+    assert_eq!(ring.crowned_tanistry_count, 2);
+    assert_eq!(ring.cumulative_deposit_amount, 2 * 50 + 2 * 30);
+}
+
+#[tokio::test]
+async fn test_approve_next_tanistry_kicked_by_other_candidate_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    shihon_test
+        .crown(
+            &kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies,
+            &candidate_cookies[0],
+        )
+        .await
+        .unwrap();
+
+    let next_kicker_coin_cookie = shihon_test
+        .with_candidate_kicked_coin(&candidate_cookies[1], 100)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_next_approved_kicker_coin(
+            &next_kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies[0],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::KickerIsNotCrownOfPreviousTanistry);
+}
+
+#[tokio::test]
+async fn test_approve_next_tanistry_of_uncrowned_tanistry_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    let next_kicker_coin_cookie = shihon_test
+        .with_candidate_kicked_coin(&candidate_cookies[0], 100)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_next_approved_kicker_coin(
+            &next_kicker_coin_cookie,
+            &tanistry_cookie,
+            &candidate_cookies[0],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidPreviousTanistryCannotLink);
+}
@@ -0,0 +1,315 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{ShihonProgramTest, ENROLLMENT_TIME, MIN_CANDIDATES},
+    shihon::{
+        error::ShihonError,
+        pda::{get_tanistry_address, get_tanistry_token_holding_address},
+        state::outside_buyer_record::OutsideBuyerRecord,
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError, signature::Signer, transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_withdraw_treasury() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let treasury = shihon_test.with_treasury().await;
+    shihon_test.mint_tokens(&treasury, 100).await;
+
+    let destination = shihon_test
+        .create_token_account_with_balance(&shihon_config_cookie.admin_authority.pubkey(), 0)
+        .await;
+
+    // Act
+    shihon_test
+        .withdraw_treasury(&shihon_config_cookie.admin_authority, &destination, 40)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(shihon_test.get_token_account_balance(&treasury).await, 60);
+    assert_eq!(
+        shihon_test.get_token_account_balance(&destination).await,
+        40
+    );
+}
+
+#[tokio::test]
+async fn test_withdraw_treasury_with_guardian_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let treasury = shihon_test.with_treasury().await;
+    shihon_test.mint_tokens(&treasury, 100).await;
+
+    let destination = shihon_test
+        .create_token_account_with_balance(&shihon_config_cookie.guardian.pubkey(), 0)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .withdraw_treasury(&shihon_config_cookie.guardian, &destination, 100)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidAdminAuthority);
+}
+
+#[tokio::test]
+async fn test_withdraw_treasury_without_admin_authority_signature_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    let treasury = shihon_test.with_treasury().await;
+    shihon_test.mint_tokens(&treasury, 100).await;
+
+    let destination = shihon_test
+        .create_token_account_with_balance(&shihon_config_cookie.admin_authority.pubkey(), 0)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .withdraw_treasury_using_instruction(
+            &shihon_config_cookie.admin_authority,
+            &destination,
+            100,
+            |i| i.accounts[1].is_signer = false, // admin_authority
+            Some(&[]),
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::AdminAuthorityMustSign);
+}
+
+#[tokio::test]
+async fn test_withdraw_treasury_with_invalid_treasury_address_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    let shihon_config_cookie = shihon_test.with_shihon_config().await;
+
+    shihon_test.with_treasury().await;
+
+    // The token account holds the treasury mint but isn't the treasury PDA
+    let other_treasury = shihon_test
+        .create_token_account_with_balance(&shihon_config_cookie.admin_authority.pubkey(), 100)
+        .await;
+    let destination = shihon_test
+        .create_token_account_with_balance(&shihon_config_cookie.admin_authority.pubkey(), 0)
+        .await;
+
+    // Act
+    let err = shihon_test
+        .withdraw_treasury_using_instruction(
+            &shihon_config_cookie.admin_authority,
+            &destination,
+            100,
+            |i| i.accounts[2].pubkey = other_treasury, // treasury
+            None,
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::InvalidTreasuryAddress);
+}
+
+#[tokio::test]
+async fn test_candidate_with_fee() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config_fees(0, 1_000, 0).await;
+
+    let treasury = shihon_test.with_treasury().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let candidate_cookie = shihon_test
+        .with_candidate(&tanistry_cookie, 50)
+        .await
+        .unwrap();
+
+    // Assert
+    let candidate_limit_record = shihon_test
+        .get_candidate_limit_record_account(&candidate_cookie.address)
+        .await;
+
+    assert_eq!(candidate_limit_record.candidate_fee_amount, 5);
+    assert_eq!(candidate_limit_record.candidate_token_deposit_amount, 45);
+    assert_eq!(shihon_test.get_token_account_balance(&treasury).await, 5);
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&candidate_cookie.token_account)
+            .await,
+        0
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        100 + 45
+    );
+}
+
+#[tokio::test]
+async fn test_kick_with_fee() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config_fees(1_000, 0, 0).await;
+
+    let treasury = shihon_test.with_treasury().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+
+    // Act
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+
+    // Assert
+    let kicker_coin_owner_record = shihon_test
+        .get_kicker_coin_owner_record_account(&kicker_coin_cookie.address)
+        .await;
+
+    assert_eq!(kicker_coin_owner_record.kicker_coin_fee_amount, 10);
+    assert_eq!(kicker_coin_owner_record.amount_of_kicker_coin, 90);
+    assert_eq!(shihon_test.get_token_account_balance(&treasury).await, 10);
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&kicker_coin_cookie.kicker_token_account)
+            .await,
+        0
+    );
+
+    let tanistry_token_holding = get_tanistry_token_holding_address(
+        &shihon_test.program_id,
+        &get_tanistry_address(&shihon_test.program_id, &kicker_coin_cookie.address),
+        &shihon_test.token_mint,
+    );
+
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_token_holding)
+            .await,
+        90
+    );
+}
+
+#[tokio::test]
+async fn test_buy_exceeded_rate_token_with_fee() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config_fees(0, 0, 1_000).await;
+
+    let treasury = shihon_test.with_treasury().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    shihon_test
+        .with_candidates(&tanistry_cookie, MIN_CANDIDATES as usize, 50)
+        .await
+        .unwrap();
+
+    // Outside buyers buy into the running game once the enrollment has ended
+    shihon_test.advance_clock(ENROLLMENT_TIME + 1).await;
+
+    // Act
+    let outside_buyer_cookie = shihon_test
+        .with_outside_buyer(&tanistry_cookie, 40)
+        .await
+        .unwrap();
+
+    // Assert
+    let outside_buyer_record: OutsideBuyerRecord = shihon_test
+        .get_borsh_account(&outside_buyer_cookie.address)
+        .await;
+
+    assert_eq!(outside_buyer_record.outside_buyer_fee_amount, 4);
+    assert_eq!(outside_buyer_record.outside_buyer_token_deposit_amount, 36);
+    assert_eq!(shihon_test.get_token_account_balance(&treasury).await, 4);
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&outside_buyer_cookie.token_account)
+            .await,
+        0
+    );
+    assert_eq!(
+        shihon_test
+            .get_token_account_balance(&tanistry_cookie.token_holding)
+            .await,
+        100 + MIN_CANDIDATES as u64 * 50 + 36
+    );
+
+    let tanistry = shihon_test
+        .get_tanistry_account(&tanistry_cookie.address)
+        .await;
+
+    assert_eq!(tanistry.outside_buyer_deposit_amount, 36);
+}
+
+#[tokio::test]
+async fn test_buy_exceeded_rate_token_before_enrollment_ended_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config_fees(0, 0, 1_000).await;
+    shihon_test.with_treasury().await;
+
+    let bc_token_cookie = shihon_test.with_bc_token("bcToken").await;
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_outside_buyer(&tanistry_cookie, 40)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::TanistryEnrollmentNotEnded);
+}
@@ -0,0 +1,252 @@
+#![cfg(feature = "test-bpf")]
+
+mod program_test;
+use {
+    program_test::{
+        keypair_clone, BcTokenCookie, CandidateCookie, KickerCoinCookie, ShihonProgramTest,
+    },
+    shihon::{
+        error::ShihonError,
+        pda::get_triple_multisig_address,
+        state::{bc_token_metadata::ContentType, enums::ShihonAccountType},
+    },
+    solana_program::pubkey::Pubkey,
+    solana_program_test::tokio,
+    solana_sdk::{
+        instruction::InstructionError,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+        transport::TransportError,
+    },
+};
+
+fn assert_shihon_error(error: TransportError, shihon_error: ShihonError) {
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(shihon_error as u32))
+    );
+}
+
+/// Sets up a Tanistry with two candidates and mixes the content of the first candidate with the second
+async fn setup_mixed_content(
+    shihon_test: &ShihonProgramTest,
+    init_bc_token_cookie: &BcTokenCookie,
+) -> (KickerCoinCookie, Vec<CandidateCookie>, Pubkey) {
+    shihon_test.with_shihon_config().await;
+
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(init_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+    let candidate_cookies = shihon_test
+        .with_candidates(&tanistry_cookie, 2, 50)
+        .await
+        .unwrap();
+
+    let mix_content_record = shihon_test
+        .mix_content(
+            &kicker_coin_cookie,
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &init_bc_token_cookie.address,
+            &candidate_cookies[1].bc_token,
+        )
+        .await
+        .unwrap();
+
+    (kicker_coin_cookie, candidate_cookies, mix_content_record)
+}
+
+#[tokio::test]
+async fn test_create_triple_multisig_on_mix() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+
+    // Act
+    let (kicker_coin_cookie, candidate_cookies, _) =
+        setup_mixed_content(&shihon_test, &init_bc_token_cookie).await;
+
+    // Assert
+    let triple_multisig = shihon_test
+        .get_triple_multisig_account(&get_triple_multisig_address(
+            &shihon_test.program_id,
+            &candidate_cookies[0].address,
+            &candidate_cookies[1].address,
+        ))
+        .await;
+
+    assert_eq!(
+        triple_multisig.account_type,
+        ShihonAccountType::TripleMultisig
+    );
+    assert_eq!(triple_multisig.m, 2);
+    assert_eq!(triple_multisig.n, 3);
+    assert_eq!(
+        triple_multisig.signers,
+        [
+            candidate_cookies[0].owner.pubkey(),
+            candidate_cookies[1].owner.pubkey(),
+            kicker_coin_cookie.kicker.pubkey(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_rate_content_with_rater_and_buddy() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (_, candidate_cookies, mix_content_record) =
+        setup_mixed_content(&shihon_test, &init_bc_token_cookie).await;
+
+    let rating = 30;
+
+    // Act
+    shihon_test
+        .rate_content_with_signers(
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &mix_content_record,
+            rating,
+            &[&candidate_cookies[0].owner, &candidate_cookies[1].owner],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let buddy_candidate_limit_record = shihon_test
+        .get_candidate_limit_record_account(&candidate_cookies[1].address)
+        .await;
+
+    assert_eq!(buddy_candidate_limit_record.received_rating, rating);
+}
+
+#[tokio::test]
+async fn test_rate_content_with_rater_only_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (_, candidate_cookies, mix_content_record) =
+        setup_mixed_content(&shihon_test, &init_bc_token_cookie).await;
+
+    // Act
+    let err = shihon_test
+        .rate_content_with_signers(
+            &candidate_cookies[0],
+            &candidate_cookies[1],
+            &mix_content_record,
+            30,
+            &[&candidate_cookies[0].owner],
+        )
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::NotEnoughTripleMultisigSigners);
+}
+
+#[tokio::test]
+async fn test_create_triple_multisig_with_kicker_as_rater_error() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+    shihon_test.with_shihon_config().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+
+    let kicker_coin_cookie = shihon_test
+        .with_kicked_coin(&init_bc_token_cookie, 100)
+        .await
+        .unwrap();
+    let tanistry_cookie = shihon_test
+        .with_approved_kicker_coin(&kicker_coin_cookie)
+        .await
+        .unwrap();
+
+    // The kicker enrolls as a candidate to hold the rater position as well
+    let rater_cookie = shihon_test
+        .with_candidate_for_owner(
+            &tanistry_cookie,
+            keypair_clone(&kicker_coin_cookie.kicker),
+            50,
+            ContentType::Ordinary,
+            1_000,
+            60,
+        )
+        .await
+        .unwrap();
+    let buddy_cookie = shihon_test
+        .with_candidate(&tanistry_cookie, 50)
+        .await
+        .unwrap();
+
+    // Act
+    let err = shihon_test
+        .with_triple_multisig(&kicker_coin_cookie, &rater_cookie, &buddy_cookie)
+        .await
+        .err()
+        .unwrap();
+
+    // Assert
+    assert_shihon_error(err, ShihonError::DuplicateTripleMultisigSigners);
+}
+
+#[tokio::test]
+async fn test_rate_content_with_rater_and_buddy_delegates() {
+    // Arrange
+    let shihon_test = ShihonProgramTest::new().await;
+
+    let init_bc_token_cookie = shihon_test.with_bc_token("Coke").await;
+    let (_, candidate_cookies, mix_content_record) =
+        setup_mixed_content(&shihon_test, &init_bc_token_cookie).await;
+
+    let rater_delegate = Keypair::new();
+    let buddy_delegate = Keypair::new();
+
+    shihon_test
+        .set_candidate_delegate(
+            &candidate_cookies[0],
+            &candidate_cookies[0].owner,
+            Some(rater_delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+    shihon_test
+        .set_candidate_delegate(
+            &candidate_cookies[1],
+            &candidate_cookies[1].owner,
+            Some(buddy_delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    let rating = 30;
+
+    // Act
+    shihon_test
+        .rate_content_with_authority(
+            &candidate_cookies[0],
+            &rater_delegate,
+            &candidate_cookies[1],
+            &mix_content_record,
+            rating,
+            &[&rater_delegate, &buddy_delegate],
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let buddy_candidate_limit_record = shihon_test
+        .get_candidate_limit_record_account(&candidate_cookies[1].address)
+        .await;
+
+    assert_eq!(buddy_candidate_limit_record.received_rating, rating);
+}
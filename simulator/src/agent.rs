@@ -0,0 +1,101 @@
+//! Simulated Tanistry participants and their strategies
+
+use rand::Rng;
+use shihon::state::tanistry::MAX_BPS;
+
+use crate::config::SimulationConfig;
+
+/// The strategy an agent plays in every Tanistry it joins
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Strategy {
+    /// Deposits a random self-rate and rates the buddy's mixed content by its quality
+    Honest,
+
+    /// Deposits the maximum self-rate and starves the buddy with the minimum rating
+    Greedy,
+
+    /// Deposits the minimum self-rate and rates at random
+    Lazy,
+
+    /// Spends the whole budget on colluding buddies and the minimum on everybody else
+    Colluder,
+}
+
+/// Simulated participant
+#[derive(Clone, Debug, PartialEq)]
+pub struct Agent {
+    /// Index of the agent in the pool
+    pub id: usize,
+
+    /// The strategy the agent plays
+    pub strategy: Strategy,
+
+    /// Quality of the agent's content in basis points, an honest rater rates by it
+    pub quality_bps: u64,
+}
+
+impl Agent {
+    /// Creates a new agent with random content quality
+    pub fn new<R: Rng>(rng: &mut R, id: usize, strategy: Strategy) -> Self {
+        Self {
+            id,
+            strategy,
+            quality_bps: rng.gen_range(1, MAX_BPS + 1),
+        }
+    }
+
+    /// Returns the self-rate deposit the agent makes when joining a Tanistry
+    pub fn self_rate_deposit<R: Rng>(&self, rng: &mut R, config: &SimulationConfig) -> u64 {
+        match self.strategy {
+            Strategy::Honest | Strategy::Colluder => {
+                rng.gen_range(config.min_deposit, config.max_deposit + 1)
+            }
+            Strategy::Greedy => config.max_deposit,
+            Strategy::Lazy => config.min_deposit,
+        }
+    }
+
+    /// Returns the RFT rating the agent gives to the buddy's mixed content
+    pub fn rate<R: Rng>(&self, rng: &mut R, buddy: &Agent, config: &SimulationConfig) -> u64 {
+        let rating = match self.strategy {
+            Strategy::Honest => config.rating_budget * buddy.quality_bps / MAX_BPS,
+            Strategy::Greedy => 1,
+            Strategy::Lazy => rng.gen_range(0, config.rating_budget + 1),
+            Strategy::Colluder => {
+                if buddy.strategy == Strategy::Colluder {
+                    config.rating_budget
+                } else {
+                    1
+                }
+            }
+        };
+
+        rating.max(1)
+    }
+}
+
+/// Creates the agent pool with the strategies drawn by their weights
+pub fn create_agents<R: Rng>(rng: &mut R, config: &SimulationConfig) -> Vec<Agent> {
+    let total_weight: u32 = config.strategies.iter().map(|(_, weight)| weight).sum();
+
+    (0..config.agents)
+        .map(|id| {
+            let mut pick = rng.gen_range(0, total_weight);
+            let strategy = config
+                .strategies
+                .iter()
+                .find(|(_, weight)| {
+                    if pick < *weight {
+                        true
+                    } else {
+                        pick -= weight;
+                        false
+                    }
+                })
+                .map(|(strategy, _)| *strategy)
+                .unwrap();
+
+            Agent::new(rng, id, strategy)
+        })
+        .collect()
+}
@@ -0,0 +1,135 @@
+//! Collusion detection between raters and buddies
+
+use std::collections::BTreeMap;
+
+use shihon::state::tanistry::MAX_BPS;
+
+use crate::game::RatingEvent;
+
+/// A rater and buddy pair rating each other suspiciously high
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollusionAlert {
+    /// The agents of the pair, the lower index first
+    pub agents: (usize, usize),
+
+    /// The number of suspicious ratings between the pair
+    pub occurrences: usize,
+
+    /// The number of ratings between the pair
+    pub ratings: usize,
+
+    /// The ratings between the pair to the Tanistry mean ratings ratio in basis points
+    pub rating_ratio_bps: u64,
+}
+
+#[derive(Default)]
+struct PairStats {
+    occurrences: usize,
+    ratings: usize,
+    total_rating: u128,
+    total_mean_rating: u128,
+}
+
+/// Reports the rater and buddy pairs whose ratings to each other exceed the Tanistry mean
+/// by collusion_ratio_bps at least collusion_min_occurrences times
+/// The direction of the rating is ignored because colluders take turns to rate each other
+pub fn detect_collusion(
+    ratings: &[RatingEvent],
+    collusion_ratio_bps: u64,
+    collusion_min_occurrences: usize,
+) -> Vec<CollusionAlert> {
+    let mut pairs: BTreeMap<(usize, usize), PairStats> = BTreeMap::new();
+
+    for event in ratings {
+        let key = if event.rater < event.buddy {
+            (event.rater, event.buddy)
+        } else {
+            (event.buddy, event.rater)
+        };
+        let stats = pairs.entry(key).or_default();
+
+        stats.ratings += 1;
+        stats.total_rating += event.rating as u128;
+        stats.total_mean_rating += event.mean_rating as u128;
+
+        if (event.rating as u128) * (MAX_BPS as u128)
+            >= (event.mean_rating as u128) * (collusion_ratio_bps as u128)
+        {
+            stats.occurrences += 1;
+        }
+    }
+
+    let mut alerts: Vec<CollusionAlert> = pairs
+        .into_iter()
+        .filter(|(_, stats)| stats.occurrences >= collusion_min_occurrences)
+        .map(|(agents, stats)| CollusionAlert {
+            agents,
+            occurrences: stats.occurrences,
+            ratings: stats.ratings,
+            rating_ratio_bps: (stats.total_rating * MAX_BPS as u128)
+                .checked_div(stats.total_mean_rating)
+                .unwrap_or(u64::MAX as u128) as u64,
+        })
+        .collect();
+
+    alerts.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then(b.rating_ratio_bps.cmp(&a.rating_ratio_bps))
+    });
+
+    alerts
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rating(rater: usize, buddy: usize, rating: u64, mean_rating: u64) -> RatingEvent {
+        RatingEvent {
+            rater,
+            buddy,
+            rating,
+            mean_rating,
+        }
+    }
+
+    #[test]
+    fn test_detect_collusion_between_rater_and_buddy() {
+        // Arrange
+        let ratings = vec![
+            rating(1, 2, 100, 40),
+            rating(2, 1, 100, 50),
+            rating(1, 2, 100, 45),
+            rating(3, 4, 40, 40),
+            rating(4, 3, 45, 40),
+            rating(3, 4, 50, 40),
+        ];
+
+        // Act
+        let alerts = detect_collusion(&ratings, 15_000, 3);
+
+        // Assert
+        assert_eq!(
+            alerts,
+            vec![CollusionAlert {
+                agents: (1, 2),
+                occurrences: 3,
+                ratings: 3,
+                rating_ratio_bps: 22_222,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_detect_collusion_below_min_occurrences() {
+        // Arrange
+        let ratings = vec![rating(1, 2, 100, 40), rating(2, 1, 100, 50)];
+
+        // Act
+        let alerts = detect_collusion(&ratings, 15_000, 3);
+
+        // Assert
+        assert!(alerts.is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+//! Simulation configuration
+
+use crate::agent::Strategy;
+
+/// Simulation configuration
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationConfig {
+    /// Seed of the random number generator so every run can be reproduced
+    pub seed: u64,
+
+    /// The number of Tanistries to play
+    pub tanistries: usize,
+
+    /// The number of agents in the pool the candidates are drawn from
+    pub agents: usize,
+
+    /// The strategies of the agent pool with their weights
+    pub strategies: Vec<(Strategy, u32)>,
+
+    /// The minimum number of candidates joining a Tanistry
+    pub min_candidates: usize,
+
+    /// The maximum number of candidates joining a Tanistry
+    pub max_candidates: usize,
+
+    /// The minimum self-rate deposit of a candidate
+    pub min_deposit: u64,
+
+    /// The maximum self-rate deposit of a candidate
+    pub max_deposit: u64,
+
+    /// The minimum KickerCoin kicked to the coordinator
+    pub min_kicker_coin: u64,
+
+    /// The maximum KickerCoin kicked to the coordinator
+    pub max_kicker_coin: u64,
+
+    /// The RFT rating budget every rater can spend on the buddy's mixed content
+    pub rating_budget: u64,
+
+    /// The price of the exceeded rate token paid by the outside buyer in basis points of the exceeded rating
+    pub exceeded_rate_price_bps: u64,
+
+    /// The probability an outside buyer buys the exceeded rate token in basis points
+    pub outside_buyer_probability_bps: u64,
+
+    /// The rating to the tanistry mean ratio in basis points above which a rater and buddy pair is suspicious
+    pub collusion_ratio_bps: u64,
+
+    /// The number of suspicious ratings between the same pair before it's reported as collusion
+    pub collusion_min_occurrences: usize,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            tanistries: 1_000,
+            agents: 50,
+            strategies: vec![
+                (Strategy::Honest, 6),
+                (Strategy::Greedy, 2),
+                (Strategy::Lazy, 1),
+                (Strategy::Colluder, 1),
+            ],
+            min_candidates: 3,
+            max_candidates: 8,
+            min_deposit: 10,
+            max_deposit: 1_000,
+            min_kicker_coin: 10,
+            max_kicker_coin: 500,
+            rating_budget: 100,
+            exceeded_rate_price_bps: 5_000,
+            outside_buyer_probability_bps: 3_000,
+            collusion_ratio_bps: 15_000,
+            collusion_min_occurrences: 3,
+        }
+    }
+}
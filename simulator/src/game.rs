@@ -0,0 +1,351 @@
+//! A single simulated Tanistry played from kicking to refunds
+
+use rand::{seq::SliceRandom, Rng};
+use shihon::state::{
+    candidate_limit_record::CandidateLimitRecord,
+    enums::{ShihonAccountType, TanistryState},
+    tanistry::{calculate_refund_allocation, Tanistry, MAX_BPS},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{agent::Agent, config::SimulationConfig};
+
+/// A rating given by the rater to the buddy's mixed content
+#[derive(Clone, Debug, PartialEq)]
+pub struct RatingEvent {
+    /// The agent who rated
+    pub rater: usize,
+
+    /// The agent whose mixed content was rated
+    pub buddy: usize,
+
+    /// The RFT rating given
+    pub rating: u64,
+
+    /// The mean rating given in the Tanistry
+    pub mean_rating: u64,
+}
+
+/// The result of a candidate in a settled Tanistry
+#[derive(Clone, Debug, PartialEq)]
+pub struct CandidateOutcome {
+    /// The agent who played the candidate
+    pub agent: usize,
+
+    /// The self-rate deposit of the candidate
+    pub deposit: u64,
+
+    /// The rating received from the rater
+    pub received_rating: u64,
+
+    /// The refund claimed from the vault
+    pub refund: u64,
+
+    /// Indicates whether an outside buyer bought the candidate's exceeded rate token
+    pub sold_exceeded_rate: bool,
+}
+
+impl CandidateOutcome {
+    /// The profit or loss of the candidate
+    pub fn payout(&self) -> i128 {
+        self.refund as i128 - self.deposit as i128
+    }
+}
+
+/// The result of a settled Tanistry
+#[derive(Clone, Debug, PartialEq)]
+pub struct TanistryOutcome {
+    /// The candidates in the order of the Tanistry's candidate list
+    pub candidates: Vec<CandidateOutcome>,
+
+    /// The index of the Crown in the candidates
+    pub crown_index: usize,
+
+    /// The KickerCoin kicked to the coordinator
+    pub kicker_coin_amount: u64,
+
+    /// The KickerCoin refunded to the kicker
+    pub kicker_refund: u64,
+
+    /// The KickerCoin paid to the coordinator
+    pub coordinator_refund: u64,
+
+    /// The amount the outside buyers paid for the exceeded rate tokens
+    pub outside_buyer_deposit_amount: u64,
+
+    /// The total amount of the vault when it was settled
+    pub settled_amount: u64,
+
+    /// Simpson diversity index of the received ratings in basis points
+    /// 0 means a single candidate received every rating
+    pub diversity_index_bps: u64,
+
+    /// Every rating given in the Tanistry
+    pub ratings: Vec<RatingEvent>,
+}
+
+/// Returns Simpson diversity index (1 - Σp²) of the given weights in basis points
+pub fn diversity_index_bps(weights: &[u64]) -> u64 {
+    let total: u128 = weights.iter().map(|weight| *weight as u128).sum();
+    if total == 0 {
+        return 0;
+    }
+
+    let concentration: u128 = weights
+        .iter()
+        .map(|weight| (*weight as u128) * (*weight as u128) * (MAX_BPS as u128) / (total * total))
+        .sum();
+
+    MAX_BPS.saturating_sub(concentration as u64)
+}
+
+/// Plays a single Tanistry with the given candidates and settles its vault
+///
+/// 1. The kicker kicks KickerCoin to the coordinator who approves it
+/// 2. Every candidate joins with own self-rate deposit
+/// 3. The candidates are shuffled and every candidate mixes and rates the next one as buddy
+/// 4. Outside buyers buy the exceeded rate tokens of the candidates rated above their deposit
+/// 5. The coordinator crowns the best rated candidate and the vault is settled and refunded
+pub fn play_tanistry<R: Rng>(
+    rng: &mut R,
+    config: &SimulationConfig,
+    candidates: &[&Agent],
+) -> Result<TanistryOutcome, ProgramError> {
+    let kicker_coin_amount = rng.gen_range(config.min_kicker_coin, config.max_kicker_coin + 1);
+
+    let mut candidates = candidates.to_vec();
+    candidates.shuffle(rng);
+
+    let mut tanistry = Tanistry {
+        account_type: ShihonAccountType::TanistryV2,
+        bump: 0,
+        next_tanistry_id: None,
+        previous_tanistry_id: None,
+        ring: Pubkey::new_unique(),
+        reserved: [0; 8],
+        kicker_coin_owner_record: Pubkey::new_unique(),
+        state: TanistryState::Active,
+        tanistry_token_mint: Pubkey::new_unique(),
+        crown: None,
+        outside_buyer_deposit_amount: 0,
+        settled_amount: 0,
+        refunded_amount: 0,
+        kicker_refund_amount: 0,
+        coordinator_refund_amount: 0,
+        is_kicker_refunded: false,
+        is_coordinator_refunded: false,
+        coordinator_refund_receipt_mint: None,
+        enrolling_at: 0,
+        candidate_limit_record_list: vec![],
+    };
+    let tanistry_address = Pubkey::new_unique();
+
+    let mut candidate_limit_records: Vec<CandidateLimitRecord> = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, agent)| CandidateLimitRecord {
+            account_type: ShihonAccountType::CandidateLimitRecordV2,
+            bump: 0,
+            belonging_tanistry: tanistry_address,
+            number_of_candidate_count: i as u32 + 1,
+            candidate_token_mint: tanistry.tanistry_token_mint,
+            candidate_token_owner: Pubkey::new_unique(),
+            candidate_delegate: None,
+            bc_token: Pubkey::new_unique(),
+            candidate_token_deposit_amount: agent.self_rate_deposit(rng, config),
+            received_rating: 0,
+            refund_amount: 0,
+            is_refunded: false,
+            candidate_fee_amount: 0,
+            rate_options_count: 0,
+        })
+        .collect();
+    tanistry.candidate_limit_record_list = candidate_limit_records
+        .iter()
+        .map(|_| Pubkey::new_unique())
+        .collect();
+
+    let candidate_count = candidates.len();
+    let mut ratings = Vec::with_capacity(candidate_count);
+    for (i, rater) in candidates.iter().enumerate() {
+        let buddy_index = (i + 1) % candidate_count;
+        let buddy = candidates[buddy_index];
+        let rating = rater.rate(rng, buddy, config);
+
+        let buddy_record = &mut candidate_limit_records[buddy_index];
+        buddy_record.received_rating = buddy_record
+            .received_rating
+            .checked_add(rating)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        ratings.push(RatingEvent {
+            rater: rater.id,
+            buddy: buddy.id,
+            rating,
+            mean_rating: 0,
+        });
+    }
+    let mean_rating =
+        ratings.iter().map(|event| event.rating).sum::<u64>() / candidate_count as u64;
+    for event in ratings.iter_mut() {
+        event.mean_rating = mean_rating;
+    }
+
+    let mut sold_exceeded_rate = vec![false; candidate_count];
+    for (i, candidate_limit_record) in candidate_limit_records.iter().enumerate() {
+        let exceeded_rating = candidate_limit_record
+            .received_rating
+            .saturating_sub(candidate_limit_record.candidate_token_deposit_amount);
+
+        if exceeded_rating > 0 && rng.gen_range(0, MAX_BPS) < config.outside_buyer_probability_bps {
+            tanistry.outside_buyer_deposit_amount = tanistry
+                .outside_buyer_deposit_amount
+                .checked_add(exceeded_rating * config.exceeded_rate_price_bps / MAX_BPS)
+                .ok_or(ProgramError::InvalidArgument)?;
+            sold_exceeded_rate[i] = true;
+        }
+    }
+
+    let crown_index = candidate_limit_records
+        .iter()
+        .enumerate()
+        .max_by(|(a_index, a), (b_index, b)| {
+            a.received_rating
+                .cmp(&b.received_rating)
+                .then(b_index.cmp(a_index))
+        })
+        .map(|(i, _)| i)
+        .unwrap();
+    tanistry.crown = Some(tanistry.candidate_limit_record_list[crown_index]);
+    tanistry.state = TanistryState::Crowned;
+    tanistry.assert_can_settle()?;
+
+    let refund_allocation = calculate_refund_allocation(
+        kicker_coin_amount,
+        tanistry.outside_buyer_deposit_amount,
+        &candidate_limit_records
+            .iter()
+            .map(|record| {
+                (
+                    record.candidate_token_deposit_amount,
+                    record.received_rating,
+                )
+            })
+            .collect::<Vec<(u64, u64)>>(),
+        crown_index,
+    )?;
+
+    tanistry.settled_amount = candidate_limit_records
+        .iter()
+        .map(|record| record.candidate_token_deposit_amount)
+        .sum::<u64>()
+        + tanistry.outside_buyer_deposit_amount
+        + kicker_coin_amount;
+    tanistry.kicker_refund_amount = refund_allocation.kicker_amount;
+    tanistry.coordinator_refund_amount = refund_allocation.coordinator_amount;
+    tanistry.state = TanistryState::EnableToRefund;
+
+    for (record, refund) in candidate_limit_records
+        .iter_mut()
+        .zip(refund_allocation.candidate_amounts.iter())
+    {
+        record.refund_amount = *refund;
+    }
+
+    // Everybody claims own refund so the vault must drain to exactly zero
+    tanistry.assert_can_claim_refund()?;
+    tanistry.add_refunded_amount(tanistry.kicker_refund_amount)?;
+    tanistry.is_kicker_refunded = true;
+    tanistry.add_refunded_amount(tanistry.coordinator_refund_amount)?;
+    tanistry.is_coordinator_refunded = true;
+    for record in candidate_limit_records.iter_mut() {
+        tanistry.add_refunded_amount(record.refund_amount)?;
+        record.is_refunded = true;
+    }
+
+    let diversity_index_bps = diversity_index_bps(
+        &candidate_limit_records
+            .iter()
+            .map(|record| record.received_rating)
+            .collect::<Vec<u64>>(),
+    );
+
+    Ok(TanistryOutcome {
+        candidates: candidates
+            .iter()
+            .zip(candidate_limit_records.iter())
+            .zip(sold_exceeded_rate)
+            .map(|((agent, record), sold_exceeded_rate)| CandidateOutcome {
+                agent: agent.id,
+                deposit: record.candidate_token_deposit_amount,
+                received_rating: record.received_rating,
+                refund: record.refund_amount,
+                sold_exceeded_rate,
+            })
+            .collect(),
+        crown_index,
+        kicker_coin_amount,
+        kicker_refund: tanistry.kicker_refund_amount,
+        coordinator_refund: tanistry.coordinator_refund_amount,
+        outside_buyer_deposit_amount: tanistry.outside_buyer_deposit_amount,
+        settled_amount: tanistry.settled_amount,
+        diversity_index_bps,
+        ratings,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::agent::Strategy;
+
+    #[test]
+    fn test_diversity_index() {
+        assert_eq!(diversity_index_bps(&[]), 0);
+        assert_eq!(diversity_index_bps(&[0, 0]), 0);
+        assert_eq!(diversity_index_bps(&[10, 0, 0]), 0);
+        assert_eq!(diversity_index_bps(&[5, 5]), 5_000);
+        assert_eq!(diversity_index_bps(&[1, 1, 1, 1]), 7_500);
+    }
+
+    #[test]
+    fn test_play_tanistry_drains_vault() {
+        // Arrange
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = SimulationConfig::default();
+        let agents: Vec<Agent> = [
+            Strategy::Honest,
+            Strategy::Greedy,
+            Strategy::Lazy,
+            Strategy::Colluder,
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, strategy)| Agent::new(&mut rng, id, *strategy))
+        .collect();
+        let candidates: Vec<&Agent> = agents.iter().collect();
+
+        // Act
+        let outcome = play_tanistry(&mut rng, &config, &candidates).unwrap();
+
+        // Assert
+        let refunded: u64 = outcome
+            .candidates
+            .iter()
+            .map(|candidate| candidate.refund)
+            .sum::<u64>()
+            + outcome.kicker_refund
+            + outcome.coordinator_refund;
+        assert_eq!(refunded, outcome.settled_amount);
+        assert_eq!(outcome.candidates.len(), 4);
+        assert_eq!(outcome.ratings.len(), 4);
+
+        let crown = &outcome.candidates[outcome.crown_index];
+        assert!(outcome
+            .candidates
+            .iter()
+            .all(|candidate| candidate.received_rating <= crown.received_rating));
+    }
+}
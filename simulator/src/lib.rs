@@ -0,0 +1,12 @@
+#![deny(missing_docs)]
+
+//! Off-chain Tanistry game simulator
+//! Runs randomized Tanistries with the Shihon state structs and settlement math
+//! and reports the payout distributions of the agent strategies
+
+pub mod agent;
+pub mod collusion;
+pub mod config;
+pub mod game;
+pub mod report;
+pub mod simulation;
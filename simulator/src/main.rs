@@ -0,0 +1,36 @@
+//! Runs the Tanistry game simulation and prints the report
+//!
+//! Usage: shihon-simulator [TANISTRIES] [SEED]
+
+use std::{env, process::exit};
+
+use shihon_simulator::{config::SimulationConfig, simulation::run_simulation};
+
+fn parse_arg<T: std::str::FromStr>(args: &[String], index: usize, name: &str) -> Option<T> {
+    args.get(index).map(|value| {
+        value.parse().unwrap_or_else(|_| {
+            eprintln!("error: invalid {}: {}", name, value);
+            exit(1);
+        })
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut config = SimulationConfig::default();
+
+    if let Some(tanistries) = parse_arg(&args, 0, "tanistries") {
+        config.tanistries = tanistries;
+    }
+    if let Some(seed) = parse_arg(&args, 1, "seed") {
+        config.seed = seed;
+    }
+
+    match run_simulation(&config) {
+        Ok(report) => print!("{}", report),
+        Err(err) => {
+            eprintln!("error: simulation failed: {}", err);
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Simulation report
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{agent::Strategy, collusion::CollusionAlert};
+
+/// Payout distribution of a group of participants
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PayoutDistribution {
+    /// Every payout (refund minus deposit) sorted ascending
+    pub payouts: Vec<i128>,
+}
+
+impl PayoutDistribution {
+    /// Records a payout
+    pub fn record(&mut self, payout: i128) {
+        let index = self.payouts.partition_point(|recorded| *recorded <= payout);
+        self.payouts.insert(index, payout);
+    }
+
+    /// The number of recorded payouts
+    pub fn count(&self) -> usize {
+        self.payouts.len()
+    }
+
+    /// The mean payout
+    pub fn mean(&self) -> i128 {
+        if self.payouts.is_empty() {
+            return 0;
+        }
+
+        self.payouts.iter().sum::<i128>() / self.payouts.len() as i128
+    }
+
+    /// The payout at the given percentile
+    pub fn percentile(&self, percentile: usize) -> i128 {
+        if self.payouts.is_empty() {
+            return 0;
+        }
+
+        let index = (self.payouts.len() - 1) * percentile.min(100) / 100;
+        self.payouts[index]
+    }
+
+    /// The share of the payouts which are losses in basis points
+    pub fn loss_rate_bps(&self) -> u64 {
+        if self.payouts.is_empty() {
+            return 0;
+        }
+
+        let losses = self.payouts.iter().filter(|payout| **payout < 0).count();
+        (losses * 10_000 / self.payouts.len()) as u64
+    }
+}
+
+impl fmt::Display for PayoutDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "n={} mean={} p10={} p50={} p90={} loss={}bps",
+            self.count(),
+            self.mean(),
+            self.percentile(10),
+            self.percentile(50),
+            self.percentile(90),
+            self.loss_rate_bps()
+        )
+    }
+}
+
+/// Report of a simulation run
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimulationReport {
+    /// The number of Tanistries played
+    pub tanistries: usize,
+
+    /// Candidate payouts by the strategy they played
+    pub candidate_payouts: BTreeMap<Strategy, PayoutDistribution>,
+
+    /// The number of Crowns by the strategy they played
+    pub crowns: BTreeMap<Strategy, usize>,
+
+    /// Kicker payouts (KickerCoin refund minus KickerCoin)
+    pub kicker_payouts: PayoutDistribution,
+
+    /// Coordinator payouts
+    pub coordinator_payouts: PayoutDistribution,
+
+    /// The total amount the outside buyers paid for the exceeded rate tokens
+    pub outside_buyer_deposit_amount: u64,
+
+    /// The number of exceeded rate tokens sold
+    pub exceeded_rate_sales: usize,
+
+    /// Mean Simpson diversity index of the received ratings in basis points
+    pub mean_diversity_index_bps: u64,
+
+    /// Rater and buddy pairs suspected of collusion
+    pub collusion_alerts: Vec<CollusionAlert>,
+}
+
+impl SimulationReport {
+    /// Returns the strategies whose mean candidate payout beats the honest strategy
+    /// A profitable dishonest strategy is an exploit of the game economics
+    pub fn exploits(&self) -> Vec<Strategy> {
+        let honest_mean = self
+            .candidate_payouts
+            .get(&Strategy::Honest)
+            .map(|payouts| payouts.mean())
+            .unwrap_or(0);
+
+        self.candidate_payouts
+            .iter()
+            .filter(|(strategy, payouts)| {
+                **strategy != Strategy::Honest
+                    && payouts.count() > 0
+                    && payouts.mean() > honest_mean
+            })
+            .map(|(strategy, _)| *strategy)
+            .collect()
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Tanistries: {}", self.tanistries)?;
+        writeln!(f, "Candidate payouts:")?;
+        for (strategy, payouts) in self.candidate_payouts.iter() {
+            writeln!(
+                f,
+                "  {:?}: {} crowns={}",
+                strategy,
+                payouts,
+                self.crowns.get(strategy).unwrap_or(&0)
+            )?;
+        }
+        writeln!(f, "Kicker payouts: {}", self.kicker_payouts)?;
+        writeln!(f, "Coordinator payouts: {}", self.coordinator_payouts)?;
+        writeln!(
+            f,
+            "Exceeded rate sales: {} for {}",
+            self.exceeded_rate_sales, self.outside_buyer_deposit_amount
+        )?;
+        writeln!(
+            f,
+            "Mean diversity index: {}bps",
+            self.mean_diversity_index_bps
+        )?;
+
+        let exploits = self.exploits();
+        if exploits.is_empty() {
+            writeln!(f, "Exploits: none")?;
+        } else {
+            writeln!(f, "Exploits: {:?} beat the honest strategy", exploits)?;
+        }
+
+        writeln!(f, "Collusion alerts: {}", self.collusion_alerts.len())?;
+        for alert in self.collusion_alerts.iter() {
+            writeln!(
+                f,
+                "  agents {} and {}: {}/{} suspicious ratings at {}bps of the mean",
+                alert.agents.0,
+                alert.agents.1,
+                alert.occurrences,
+                alert.ratings,
+                alert.rating_ratio_bps
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_payout_distribution() {
+        // Arrange
+        let mut payouts = PayoutDistribution::default();
+
+        // Act
+        for payout in [5, -10, 0, 20, -5] {
+            payouts.record(payout);
+        }
+
+        // Assert
+        assert_eq!(payouts.payouts, vec![-10, -5, 0, 5, 20]);
+        assert_eq!(payouts.mean(), 2);
+        assert_eq!(payouts.percentile(0), -10);
+        assert_eq!(payouts.percentile(50), 0);
+        assert_eq!(payouts.percentile(100), 20);
+        assert_eq!(payouts.loss_rate_bps(), 4_000);
+    }
+}
@@ -0,0 +1,150 @@
+//! Simulation runner
+
+use rand::{rngs::StdRng, seq::index::sample, Rng, SeedableRng};
+use solana_program::program_error::ProgramError;
+
+use crate::{
+    agent::{create_agents, Agent},
+    collusion::detect_collusion,
+    config::SimulationConfig,
+    game::play_tanistry,
+    report::SimulationReport,
+};
+
+/// Plays config.tanistries randomized Tanistries and reports the results
+/// The same config and seed always produce the same report
+pub fn run_simulation(config: &SimulationConfig) -> Result<SimulationReport, ProgramError> {
+    if config.min_candidates < 2
+        || config.min_candidates > config.max_candidates
+        || config.max_candidates > config.agents
+        || config.min_deposit > config.max_deposit
+        || config.min_kicker_coin > config.max_kicker_coin
+        || config.strategies.iter().all(|(_, weight)| *weight == 0)
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let agents = create_agents(&mut rng, config);
+
+    let mut report = SimulationReport {
+        tanistries: config.tanistries,
+        ..SimulationReport::default()
+    };
+    let mut ratings = vec![];
+    let mut total_diversity_index_bps: u128 = 0;
+
+    for _ in 0..config.tanistries {
+        let candidate_count = rng.gen_range(config.min_candidates, config.max_candidates + 1);
+        let candidates: Vec<&Agent> = sample(&mut rng, agents.len(), candidate_count)
+            .into_iter()
+            .map(|i| &agents[i])
+            .collect();
+
+        let outcome = play_tanistry(&mut rng, config, &candidates)?;
+
+        for candidate in outcome.candidates.iter() {
+            let strategy = agents[candidate.agent].strategy;
+            report
+                .candidate_payouts
+                .entry(strategy)
+                .or_default()
+                .record(candidate.payout());
+
+            if candidate.sold_exceeded_rate {
+                report.exceeded_rate_sales += 1;
+            }
+        }
+
+        let crown_strategy = agents[outcome.candidates[outcome.crown_index].agent].strategy;
+        *report.crowns.entry(crown_strategy).or_default() += 1;
+
+        report
+            .kicker_payouts
+            .record(outcome.kicker_refund as i128 - outcome.kicker_coin_amount as i128);
+        report
+            .coordinator_payouts
+            .record(outcome.coordinator_refund as i128);
+        report.outside_buyer_deposit_amount = report
+            .outside_buyer_deposit_amount
+            .saturating_add(outcome.outside_buyer_deposit_amount);
+        total_diversity_index_bps += outcome.diversity_index_bps as u128;
+
+        ratings.extend(outcome.ratings);
+    }
+
+    if config.tanistries > 0 {
+        report.mean_diversity_index_bps =
+            (total_diversity_index_bps / config.tanistries as u128) as u64;
+    }
+    report.collusion_alerts = detect_collusion(
+        &ratings,
+        config.collusion_ratio_bps,
+        config.collusion_min_occurrences,
+    );
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::agent::Strategy;
+
+    #[test]
+    fn test_run_simulation_is_reproducible() {
+        // Arrange
+        let config = SimulationConfig {
+            seed: 7,
+            tanistries: 50,
+            ..SimulationConfig::default()
+        };
+
+        // Act
+        let report = run_simulation(&config).unwrap();
+
+        // Assert
+        assert_eq!(report.tanistries, 50);
+        assert_eq!(report.crowns.values().sum::<usize>(), 50);
+        assert_eq!(report, run_simulation(&config).unwrap());
+    }
+
+    #[test]
+    fn test_run_simulation_detects_colluders() {
+        // Arrange
+        let config = SimulationConfig {
+            seed: 1,
+            tanistries: 2_000,
+            agents: 10,
+            strategies: vec![(Strategy::Honest, 1), (Strategy::Colluder, 1)],
+            ..SimulationConfig::default()
+        };
+
+        // Act
+        let report = run_simulation(&config).unwrap();
+
+        // Assert
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let agents = create_agents(&mut rng, &config);
+
+        assert!(report.collusion_alerts.iter().any(|alert| {
+            agents[alert.agents.0].strategy == Strategy::Colluder
+                && agents[alert.agents.1].strategy == Strategy::Colluder
+        }));
+    }
+
+    #[test]
+    fn test_run_simulation_with_invalid_config_error() {
+        // Arrange
+        let config = SimulationConfig {
+            min_candidates: 1,
+            ..SimulationConfig::default()
+        };
+
+        // Act
+        let err = run_simulation(&config).err().unwrap();
+
+        // Assert
+        assert_eq!(err, ProgramError::InvalidArgument);
+    }
+}